@@ -0,0 +1,48 @@
+//! Cache load benchmark
+//!
+//! Compares loading a synthetic 10k-symbol cache saved as JSON against the
+//! same cache saved in the binary (MessagePack) format.
+//!
+//! Run with `cargo bench --bench cache_load`.
+
+use acp::cache::{Cache, SymbolEntry};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const SYMBOLS: usize = 10_000;
+
+fn symbol(i: usize) -> (String, SymbolEntry) {
+    let name = format!("symbol_{}", i);
+    let file = format!("src/module_{}.ts", i / 20);
+    let entry = SymbolEntry {
+        lines: [1, 40],
+        signature: Some(format!("function {}(input: string): Result", name)),
+        summary: Some(format!("Handles step {} of the request pipeline", i)),
+        calls: (0..8)
+            .map(|c| format!("symbol_{}", (i + c) % SYMBOLS))
+            .collect(),
+        ..SymbolEntry::new(name.clone(), file)
+    };
+    (name, entry)
+}
+
+fn cache_load(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    let mut cache = Cache::new("bench", ".");
+    cache.symbols = (0..SYMBOLS).map(symbol).collect();
+    cache.update_stats();
+
+    let json_path = dir.path().join(".acp.cache.json");
+    let bin_path = dir.path().join(".acp.cache.bin");
+    cache.write_json(&json_path).unwrap();
+    cache.write_binary(&bin_path).unwrap();
+
+    let mut group = c.benchmark_group("cache_load_10k_symbols");
+    group.bench_function("json", |b| b.iter(|| Cache::from_json(&json_path).unwrap()));
+    group.bench_function("binary", |b| {
+        b.iter(|| Cache::from_binary(&bin_path).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, cache_load);
+criterion_main!(benches);
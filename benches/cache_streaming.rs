@@ -0,0 +1,65 @@
+//! Streaming cache writer benchmark
+//!
+//! Compares peak RSS of streaming a synthetic 20k-symbol cache through
+//! `CacheWriter` against building the full `Cache` and calling `write_json`.
+//! Peak RSS is process-wide, so this runs as its own binary rather than
+//! under criterion, and only on Linux (it reads `/proc/self/status`).
+//!
+//! Run with `cargo bench --bench cache_streaming`.
+
+use acp::cache::{Cache, CacheWriter, FileEntry, SymbolEntry};
+
+const SYMBOLS: usize = 20_000;
+
+fn symbol(i: usize) -> (String, SymbolEntry) {
+    let name = format!("symbol_{}", i);
+    let file = format!("src/module_{}.ts", i / 20);
+    let entry = SymbolEntry {
+        lines: [1, 40],
+        signature: Some(format!("function {}(input: string): Result", name)),
+        // ~1KB per symbol so the in-memory path is clearly measurable
+        summary: Some("x".repeat(1024)),
+        calls: (0..8).map(|c| format!("callee_{}", c)).collect(),
+        ..SymbolEntry::new(name.clone(), file)
+    };
+    (name, entry)
+}
+
+/// Peak resident set size of this process in kB
+fn peak_rss_kb() -> Option<usize> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|l| l.strip_prefix("VmHWM:"))
+        .and_then(|v| v.trim().trim_end_matches("kB").trim().parse().ok())
+}
+
+fn main() {
+    let Some(baseline) = peak_rss_kb() else {
+        println!("cache_streaming: peak RSS is only available on Linux, skipping");
+        return;
+    };
+    let dir = tempfile::tempdir().unwrap();
+    let header = Cache::new("bench", ".");
+
+    // Streaming path first: the high-water mark only ever grows
+    CacheWriter::create(dir.path().join("streamed.json"))
+        .unwrap()
+        .write(
+            &header,
+            std::iter::empty::<(String, FileEntry)>(),
+            (0..SYMBOLS).map(symbol),
+        )
+        .unwrap();
+    let streamed_peak = peak_rss_kb().unwrap();
+
+    // Materialise every symbol, then serialize
+    let mut cache = Cache::new("bench", ".");
+    cache.symbols = (0..SYMBOLS).map(symbol).collect();
+    cache.write_json(dir.path().join("in_memory.json")).unwrap();
+    let in_memory_peak = peak_rss_kb().unwrap();
+
+    println!("cache_streaming_20k_symbols: peak RSS growth");
+    println!("  streamed   {:>8} kB", streamed_peak - baseline);
+    println!("  in-memory  {:>8} kB", in_memory_peak - baseline);
+}
@@ -0,0 +1,83 @@
+//! Git metadata benchmark
+//!
+//! Builds a synthetic repository with a deep history (many commits, each
+//! touching a few files) and compares walking history and blame file by file
+//! against the shared-walk [`BlameCache`] the indexer uses.
+//!
+//! Run with `cargo bench --bench git_metadata`.
+
+use std::path::{Path, PathBuf};
+
+use acp::git::{BlameCache, BlameInfo, FileHistory, GitRepository};
+use criterion::{criterion_group, criterion_main, Criterion};
+use git2::{Repository, Signature};
+
+const FILES: usize = 20;
+const COMMITS: usize = 500;
+const FILES_PER_COMMIT: usize = 3;
+
+fn build_repo(root: &Path) -> Vec<PathBuf> {
+    let repo = Repository::init(root).unwrap();
+    let files: Vec<PathBuf> = (0..FILES)
+        .map(|i| root.join(format!("src/module_{}.ts", i)))
+        .collect();
+    std::fs::create_dir_all(root.join("src")).unwrap();
+
+    let mut contents: Vec<Vec<String>> = vec![Vec::new(); FILES];
+    let mut parent = None;
+    for n in 0..COMMITS {
+        let mut index = repo.index().unwrap();
+        for k in 0..FILES_PER_COMMIT {
+            let i = (n * 7 + k * 3) % FILES;
+            contents[i].push(format!("export const step{} = {};", n, n));
+            std::fs::write(&files[i], contents[i].join("\n")).unwrap();
+            let relative = files[i].strip_prefix(root).unwrap();
+            index.add_path(relative).unwrap();
+        }
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let who = format!("dev{}", n % 5);
+        let sig = Signature::now(&who, &format!("{}@example.com", who)).unwrap();
+        let parents: Vec<_> = parent.iter().collect();
+        let oid = repo
+            .commit(
+                Some("HEAD"),
+                &sig,
+                &sig,
+                &format!("step {}", n),
+                &tree,
+                &parents,
+            )
+            .unwrap();
+        parent = Some(repo.find_commit(oid).unwrap());
+    }
+    files
+}
+
+fn git_metadata(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    let files = build_repo(dir.path());
+    let repo = GitRepository::open(dir.path()).unwrap();
+
+    let mut group = c.benchmark_group("git_metadata_500_commits");
+    group.sample_size(10);
+    group.bench_function("per_file", |b| {
+        b.iter(|| {
+            for file in &files {
+                FileHistory::for_file(&repo, file, 0).unwrap();
+                BlameInfo::for_file(&repo, file).unwrap();
+            }
+        })
+    });
+    group.bench_function("blame_cache", |b| {
+        b.iter(|| {
+            let mut cache = BlameCache::new(&repo, 0);
+            cache.prefetch_histories(&files).unwrap();
+            cache.prefetch_blames(&files).unwrap();
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, git_metadata);
+criterion_main!(benches);
@@ -123,18 +123,27 @@ impl Analyzer {
     /// and identifies gaps where annotations are missing.
     pub fn analyze_file(&self, file_path: &Path) -> Result<AnalysisResult> {
         let content = std::fs::read_to_string(file_path)?;
+        self.analyze_source(file_path, &content)
+    }
+
+    /// @acp:summary "Analyzes in-memory source as if it were the given file"
+    ///
+    /// `file_path` is only used for language detection and path heuristics;
+    /// nothing is read from disk.
+    pub fn analyze_source(&self, file_path: &Path, content: &str) -> Result<AnalysisResult> {
         let path_str = file_path.to_string_lossy().to_string();
 
         // Detect language from extension
         let language = self.detect_language(file_path);
 
         let mut result = AnalysisResult::new(&path_str, &language);
+        result.content_hash = Some(super::source_digest(content));
 
         // Extract existing annotations from comments
-        result.existing_annotations = self.extract_existing_annotations(&content, &path_str);
+        result.existing_annotations = self.extract_existing_annotations(content, &path_str);
 
         // Parse AST and extract symbols
-        if let Ok(symbols) = self.ast_parser.parse_file(file_path, &content) {
+        if let Ok(symbols) = self.ast_parser.parse_file(file_path, content) {
             // Associate annotations with their correct symbol targets
             self.associate_annotations_with_symbols(&mut result.existing_annotations, &symbols);
 
@@ -178,7 +187,7 @@ impl Analyzer {
                         if let Some(doc) = &symbol.doc_comment {
                             // Try to find actual doc comment boundaries in source
                             if let Some((start, end)) =
-                                self.find_doc_comment_range(&content, symbol.start_line)
+                                self.find_doc_comment_range(content, symbol.start_line)
                             {
                                 gap = gap.with_doc_comment_range(doc, start, end);
                             } else {
@@ -242,6 +251,7 @@ impl Analyzer {
                 "rs" => "rust",
                 "go" => "go",
                 "java" => "java",
+                "rb" | "rake" => "ruby",
                 _ => "unknown",
             })
             .unwrap_or("unknown")
@@ -440,13 +450,12 @@ impl Analyzer {
     }
 
     /// @acp:summary "Calculates total coverage across multiple analysis results"
-    pub fn calculate_total_coverage(results: &[AnalysisResult]) -> f32 {
-        if results.is_empty() {
-            return 100.0;
-        }
-
-        let total_annotated: usize = results.iter().map(|r| r.existing_annotations.len()).sum();
-        let total_gaps: usize = results.iter().map(|r| r.gaps.len()).sum();
+    pub fn calculate_total_coverage<'a>(
+        results: impl IntoIterator<Item = &'a AnalysisResult>,
+    ) -> f32 {
+        let (total_annotated, total_gaps) = results.into_iter().fold((0, 0), |(a, g), r| {
+            (a + r.existing_annotations.len(), g + r.gaps.len())
+        });
         let total = total_annotated + total_gaps;
 
         if total == 0 {
@@ -19,8 +19,9 @@
 //! - Attributes, Methods
 //!
 //! ## Sphinx/reST Style
-//! - :param:, :type:, :returns:, :rtype:
+//! - :param:, :type:, :returns:, :rtype: (`:type:`/`:rtype:` may come in any order)
 //! - :raises:, :deprecated:, :version:, :since:
+//! - Field bodies continue on lines indented past the field marker
 //! - :seealso:, :note:, :warning:, :example:, :todo:
 //! - :var:, :ivar:, :cvar:, :meta:
 //!
@@ -41,10 +42,13 @@ static GOOGLE_SECTION: LazyLock<Regex> = LazyLock::new(|| {
     ).expect("Invalid Google section regex")
 });
 
-/// @acp:summary "Matches Sphinx-style tags"
+/// @acp:summary "Matches Sphinx-style field markers (`:param int x: desc`)"
+///
+/// The closing colon must be followed by whitespace or end of line, so
+/// inline roles such as ``:class:`Foo` `` are not mistaken for fields.
 static SPHINX_TAG: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(
-        r"^:(param|type|returns|rtype|raises|raise|var|ivar|cvar|deprecated|version|since|seealso|see|note|warning|example|todo|meta|keyword|kwarg|kwparam)(\s+\w+)?:\s*(.*)$"
+        r"^:(param|parameter|arg|argument|type|returns|return|rtype|raises|raise|except|exception|var|ivar|cvar|deprecated|version|since|seealso|see|note|warning|example|todo|meta|keyword|key|kwarg|kwparam)(?:\s+([^:]+?))?:(?:\s+(.*))?$"
     ).expect("Invalid Sphinx tag regex")
 });
 
@@ -132,15 +136,16 @@ impl DocstringParser {
     pub fn detect_style(raw: &str) -> DocstringStyle {
         let lines: Vec<&str> = raw.lines().collect();
 
+        // A leading-colon field anywhere is unambiguous, even if a
+        // Google-looking "Note:" line comes first
+        if lines.iter().any(|line| SPHINX_TAG.is_match(line.trim())) {
+            return DocstringStyle::Sphinx;
+        }
+
         // Check each line for style indicators
         for (i, line) in lines.iter().enumerate() {
             let trimmed = line.trim();
 
-            // Check for Sphinx tags (line by line)
-            if SPHINX_TAG.is_match(trimmed) {
-                return DocstringStyle::Sphinx;
-            }
-
             // Check for Google style sections (line by line)
             if GOOGLE_SECTION.is_match(trimmed) {
                 return DocstringStyle::Google;
@@ -471,53 +476,48 @@ impl DocstringParser {
     fn parse_sphinx_style(&self, raw: &str) -> ParsedDocumentation {
         let mut doc = ParsedDocumentation::new();
         let mut summary_lines = Vec::new();
-        let mut found_tag = false;
 
-        // For multiline tag content
-        let mut current_tag: Option<(String, Option<String>)> = None;
-        let mut current_content = String::new();
+        // (tag, argument, body lines) for each field, in order
+        let mut fields: Vec<(String, Option<String>, Vec<String>)> = Vec::new();
+        // Indent of the current field's marker while its body may continue
+        let mut field_indent: Option<usize> = None;
 
         for line in raw.lines() {
             let trimmed = line.trim();
+            let indent = line.len() - line.trim_start().len();
 
             if let Some(caps) = SPHINX_TAG.captures(trimmed) {
-                // Save previous multiline tag if any
-                if let Some((tag, name)) = current_tag.take() {
-                    self.save_sphinx_tag(&mut doc, &tag, name.as_deref(), &current_content);
-                    current_content.clear();
+                let tag = caps[1].to_string();
+                let arg = caps.get(2).map(|m| m.as_str().trim().to_string());
+                let body = caps
+                    .get(3)
+                    .map(|m| m.as_str().trim().to_string())
+                    .filter(|b| !b.is_empty());
+                fields.push((tag, arg, body.into_iter().collect()));
+                field_indent = Some(indent);
+            } else if fields.is_empty() {
+                if !trimmed.is_empty() {
+                    summary_lines.push(trimmed.to_string());
                 }
-
-                found_tag = true;
-                let tag = caps.get(1).map(|m| m.as_str()).unwrap_or("");
-                let name = caps.get(2).map(|m| m.as_str().trim().to_string());
-                let content = caps.get(3).map(|m| m.as_str().trim().to_string());
-
-                // Check if this tag might have multiline content
-                if let Some(c) = &content {
-                    if c.is_empty() {
-                        // Start collecting multiline content
-                        current_tag = Some((tag.to_string(), name));
-                    } else {
-                        // Single line tag
-                        self.save_sphinx_tag(&mut doc, tag, name.as_deref(), c);
-                    }
-                } else {
-                    // Tag with no content
-                    self.save_sphinx_tag(&mut doc, tag, name.as_deref(), "");
+            } else if trimmed.is_empty() {
+                // Blank lines separate paragraphs within a body
+            } else if field_indent.is_some_and(|marker| indent > marker) {
+                if let Some((_, _, body)) = fields.last_mut() {
+                    body.push(trimmed.to_string());
                 }
-            } else if current_tag.is_some() && (line.starts_with("    ") || line.starts_with("\t"))
-            {
-                // Continuation of multiline content
-                current_content.push_str(trimmed);
-                current_content.push('\n');
-            } else if !found_tag && !trimmed.is_empty() {
-                summary_lines.push(trimmed.to_string());
+            } else {
+                // Unindented text ends the field list
+                field_indent = None;
             }
         }
 
-        // Save final multiline tag
-        if let Some((tag, name)) = current_tag.take() {
-            self.save_sphinx_tag(&mut doc, &tag, name.as_deref(), &current_content);
+        // Types attach to their param/returns regardless of field order
+        let (types, others): (Vec<_>, Vec<_>) = fields
+            .into_iter()
+            .partition(|(tag, _, _)| matches!(tag.as_str(), "type" | "rtype"));
+        for (tag, arg, body) in others.iter().chain(&types) {
+            let separator = if tag == "example" { "\n" } else { " " };
+            self.save_sphinx_tag(&mut doc, tag, arg.as_deref(), &body.join(separator));
         }
 
         if !summary_lines.is_empty() {
@@ -539,11 +539,16 @@ impl DocstringParser {
         let content = content.trim().to_string();
 
         match tag {
-            "param" => {
+            "param" | "parameter" | "arg" | "argument" => {
                 if let Some(n) = name {
+                    // `:param int x:` carries the type inline
+                    let (param_type, n) = match n.rsplit_once(char::is_whitespace) {
+                        Some((t, n)) => (Some(t.trim().to_string()), n),
+                        None => (None, n),
+                    };
                     doc.params.push((
                         n.to_string(),
-                        None,
+                        param_type,
                         if content.is_empty() {
                             None
                         } else {
@@ -552,7 +557,7 @@ impl DocstringParser {
                     ));
                 }
             }
-            "keyword" | "kwarg" | "kwparam" => {
+            "keyword" | "key" | "kwarg" | "kwparam" => {
                 if let Some(n) = name {
                     doc.custom_tags
                         .push(("kwarg".to_string(), format!("{}: {}", n, content)));
@@ -560,7 +565,7 @@ impl DocstringParser {
             }
             "type" => {
                 // Update type for matching param
-                if let Some(n) = name {
+                if let (Some(n), false) = (name, content.is_empty()) {
                     for param in &mut doc.params {
                         if param.0 == n {
                             param.1 = Some(content.clone());
@@ -569,7 +574,7 @@ impl DocstringParser {
                     }
                 }
             }
-            "returns" => {
+            "returns" | "return" => {
                 doc.returns = Some((
                     None,
                     if content.is_empty() {
@@ -586,16 +591,20 @@ impl DocstringParser {
                     doc.returns = Some((Some(content), None));
                 }
             }
-            "raises" | "raise" => {
-                if let Some(exc) = name {
-                    doc.throws.push((
-                        exc.to_string(),
-                        if content.is_empty() {
-                            None
-                        } else {
-                            Some(content)
-                        },
-                    ));
+            "raises" | "raise" | "except" | "exception" => {
+                // `:raises ValueError, TypeError:` lists several exceptions
+                for exc in name.into_iter().flat_map(|n| n.split(',')) {
+                    let exc = exc.trim();
+                    if !exc.is_empty() {
+                        doc.throws.push((
+                            exc.to_string(),
+                            if content.is_empty() {
+                                None
+                            } else {
+                                Some(content.clone())
+                            },
+                        ));
+                    }
                 }
             }
             "deprecated" => {
@@ -1784,6 +1793,52 @@ Todo:
         );
     }
 
+    #[test]
+    fn test_sphinx_field_order_and_inline_types() {
+        let parser = DocstringParser::new();
+        let doc = parser.parse(
+            r#"
+Update the counter.
+
+Note:
+    Detected as Sphinx despite the Google-style header above.
+
+:type count: int
+:param count: How many.
+:param str label: Display label,
+    shown in the UI.
+:rtype: bool
+:return: Whether anything changed.
+:raises ValueError, KeyError: On bad input.
+"#,
+        );
+
+        assert_eq!(
+            doc.params,
+            vec![
+                (
+                    "count".to_string(),
+                    Some("int".to_string()),
+                    Some("How many.".to_string())
+                ),
+                (
+                    "label".to_string(),
+                    Some("str".to_string()),
+                    Some("Display label, shown in the UI.".to_string())
+                ),
+            ]
+        );
+        assert_eq!(
+            doc.returns,
+            Some((
+                Some("bool".to_string()),
+                Some("Whether anything changed.".to_string())
+            ))
+        );
+        assert_eq!(doc.throws.len(), 2);
+        assert_eq!(doc.throws[1].0, "KeyError");
+    }
+
     #[test]
     fn test_multiline_sphinx_content() {
         let parser = DocstringParser::new();
@@ -13,7 +13,10 @@
 //! - `/* */` block comments
 //!
 //! ## Conventions
-//! - First sentence should start with the element name
+//! - First sentence should start with the element name (stripped from the
+//!   summary when the name is known)
+//! - Methods are named with their receiver: `(*Client).Do` or `Client.Do`
+//! - `Example*`, `Test*`, `Benchmark*` and `Fuzz*` functions belong to `go test`
 //! - "Deprecated:" prefix marks deprecated items
 //! - "BUG(who):" for known issues
 //! - Code examples are indented by a tab
@@ -74,6 +77,67 @@ pub struct GoDocExtensions {
 
     /// Code examples found (indented blocks)
     pub code_examples: Vec<String>,
+
+    /// Method receiver type (e.g. `*Client`)
+    pub receiver: Option<String>,
+
+    /// Set when the element is a `go test` function
+    pub test_func: Option<GoTestFunc>,
+}
+
+/// @acp:summary "Special function recognized by go test"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GoTestFunc {
+    /// Runnable example; holds the documented symbol (`Client.Do`), or None
+    /// for a package example
+    Example(Option<String>),
+    /// TestXxx
+    Test,
+    /// BenchmarkXxx
+    Benchmark,
+    /// FuzzXxx
+    Fuzz,
+}
+
+impl GoTestFunc {
+    /// @acp:summary "Classifies a function name by go test naming rules"
+    ///
+    /// The prefix must be followed by the end of the name, `_`, or a
+    /// non-lowercase letter, so `Testify` and `Examples` are ordinary functions.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let after = |prefix: &str| {
+            name.strip_prefix(prefix)
+                .filter(|rest| !rest.starts_with(|c: char| c.is_lowercase()))
+        };
+
+        if let Some(rest) = after("Example") {
+            // ExampleT_M_suffix documents T.M; a lowercase part is a suffix
+            let parts: Vec<&str> = rest
+                .split('_')
+                .filter(|p| !p.is_empty() && !p.starts_with(|c: char| c.is_lowercase()))
+                .collect();
+            let documented = (!parts.is_empty()).then(|| parts.join("."));
+            Some(Self::Example(documented))
+        } else if after("Test").is_some() {
+            Some(Self::Test)
+        } else if after("Benchmark").is_some() {
+            Some(Self::Benchmark)
+        } else if after("Fuzz").is_some() {
+            Some(Self::Fuzz)
+        } else {
+            None
+        }
+    }
+
+    /// @acp:summary "Name used in custom tags"
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Example(_) => "example",
+            Self::Test => "test",
+            Self::Benchmark => "benchmark",
+            Self::Fuzz => "fuzz",
+        }
+    }
 }
 
 /// @acp:summary "Parses Go doc comments"
@@ -124,9 +188,25 @@ impl GodocParser {
         }
     }
 
+    /// @acp:summary "Splits the element name into receiver and bare name"
+    ///
+    /// Accepts `Do`, `Client.Do`, `(*Client).Do` and qualified targets such
+    /// as `client.go:Client.Do`.
+    fn split_element_name(&self) -> Option<(Option<String>, &str)> {
+        let name = self.element_name.as_deref()?;
+        let name = name.rsplit(':').next().unwrap_or(name);
+        Some(match name.rsplit_once('.') {
+            Some((receiver, bare)) => {
+                let receiver = receiver.trim_start_matches('(').trim_end_matches(')');
+                (Some(receiver.to_string()), bare)
+            }
+            None => (None, name),
+        })
+    }
+
     /// @acp:summary "Checks if first sentence starts with element name (Go convention)"
     fn check_convention(&self, first_line: &str) -> bool {
-        if let Some(name) = &self.element_name {
+        if let Some((_, name)) = self.split_element_name() {
             first_line.starts_with(name)
         } else {
             // Can't check without element name
@@ -134,6 +214,21 @@ impl GodocParser {
         }
     }
 
+    /// @acp:summary "Removes the leading element name from a summary"
+    ///
+    /// "Parse reads the file." becomes "Reads the file."
+    fn strip_element_name(&self, summary: &str) -> Option<String> {
+        let (_, name) = self.split_element_name()?;
+        let rest = summary.strip_prefix(name)?;
+        if !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let rest = rest.trim_start();
+        let mut chars = rest.chars();
+        let first = chars.next()?;
+        Some(first.to_uppercase().chain(chars).collect())
+    }
+
     /// @acp:summary "Extracts the first sentence as summary"
     fn extract_summary(text: &str) -> String {
         // Find first sentence ending with period, question mark, or exclamation
@@ -311,6 +406,9 @@ impl DocStandardParser for GodocParser {
 
             // Check convention (starts with element name)
             extensions.follows_convention = self.check_convention(&summary);
+            if let Some(stripped) = self.strip_element_name(&summary) {
+                doc.summary = Some(stripped);
+            }
         }
 
         // Set description if there's more than summary
@@ -331,7 +429,28 @@ impl DocStandardParser for GodocParser {
             doc.examples.push(example.clone());
         }
 
+        // Receiver and go test functions come from the element name
+        if let Some((receiver, name)) = self.split_element_name() {
+            extensions.receiver = receiver;
+            extensions.test_func = GoTestFunc::from_name(name);
+        }
+
         // Store extensions in custom tags
+        if let Some(receiver) = &extensions.receiver {
+            doc.custom_tags
+                .push(("receiver".to_string(), receiver.clone()));
+        }
+        if let Some(test_func) = &extensions.test_func {
+            // Test functions are driven by go test, not part of the API
+            doc.custom_tags
+                .push(("visibility".to_string(), "internal".to_string()));
+            doc.custom_tags
+                .push(("go_test".to_string(), test_func.kind().to_string()));
+            if let GoTestFunc::Example(Some(documented)) = test_func {
+                doc.custom_tags
+                    .push(("example_for".to_string(), documented.clone()));
+            }
+        }
         if extensions.is_package_doc {
             doc.custom_tags
                 .push(("package_doc".to_string(), "true".to_string()));
@@ -436,6 +555,43 @@ impl DocStandardParser for GodocParser {
             ));
         }
 
+        // Go-specific: go test functions
+        match parsed
+            .custom_tags
+            .iter()
+            .find(|(k, _)| k == "go_test")
+            .map(|(_, v)| v.as_str())
+        {
+            Some("example") => {
+                if let Some((_, documented)) =
+                    parsed.custom_tags.iter().find(|(k, _)| k == "example_for")
+                {
+                    suggestions.push(Suggestion::new(
+                        target,
+                        line,
+                        AnnotationType::Ref,
+                        documented,
+                        SuggestionSource::Converted,
+                    ));
+                }
+                suggestions.push(Suggestion::ai_hint(
+                    target,
+                    line,
+                    "runnable example; go test checks its // Output: comment",
+                    SuggestionSource::Converted,
+                ));
+            }
+            Some(kind) => {
+                suggestions.push(Suggestion::ai_hint(
+                    target,
+                    line,
+                    format!("go test {} function; not part of the package API", kind),
+                    SuggestionSource::Converted,
+                ));
+            }
+            None => {}
+        }
+
         // Go-specific: package documentation
         if parsed
             .custom_tags
@@ -655,6 +811,85 @@ mod tests {
             .any(|(k, v)| k == "unconventional_doc" && v == "true"));
     }
 
+    #[test]
+    fn test_pointer_receiver_method() {
+        let parser = GodocParser::new().with_element_name("(*Client).Do");
+        let doc = parser.parse(
+            r#"
+// Do sends an HTTP request and returns the response.
+//
+// Example:
+//
+//	resp, err := client.Do(req)
+"#,
+        );
+
+        assert_eq!(
+            doc.summary,
+            Some("Sends an HTTP request and returns the response.".to_string())
+        );
+        assert!(doc
+            .custom_tags
+            .contains(&("receiver".to_string(), "*Client".to_string())));
+        assert!(!doc
+            .custom_tags
+            .iter()
+            .any(|(k, _)| k == "unconventional_doc"));
+        assert_eq!(
+            doc.examples,
+            vec!["resp, err := client.Do(req)".to_string()]
+        );
+        assert_eq!(doc.get_visibility(), None);
+    }
+
+    #[test]
+    fn test_example_function() {
+        let parser = GodocParser::new().with_element_name("ExampleFoo");
+        let doc = parser.parse(
+            r#"
+// ExampleFoo shows basic usage.
+//
+//	fmt.Println(Foo(2))
+//	// Output: 4
+"#,
+        );
+
+        assert_eq!(doc.summary, Some("Shows basic usage.".to_string()));
+        assert_eq!(doc.get_visibility(), Some("internal"));
+        assert!(doc
+            .custom_tags
+            .contains(&("example_for".to_string(), "Foo".to_string())));
+        assert_eq!(
+            doc.examples,
+            vec!["fmt.Println(Foo(2))\n// Output: 4".to_string()]
+        );
+
+        let suggestions = parser.to_suggestions(&doc, "ExampleFoo", 1);
+        assert!(suggestions
+            .iter()
+            .any(|s| s.annotation_type == AnnotationType::Ref && s.value == "Foo"));
+    }
+
+    #[test]
+    fn test_go_test_func_names() {
+        assert_eq!(
+            GoTestFunc::from_name("ExampleClient_Do_retry"),
+            Some(GoTestFunc::Example(Some("Client.Do".to_string())))
+        );
+        assert_eq!(
+            GoTestFunc::from_name("Example"),
+            Some(GoTestFunc::Example(None))
+        );
+        assert_eq!(GoTestFunc::from_name("TestParse"), Some(GoTestFunc::Test));
+        assert_eq!(GoTestFunc::from_name("Test_parse"), Some(GoTestFunc::Test));
+        assert_eq!(
+            GoTestFunc::from_name("BenchmarkParse"),
+            Some(GoTestFunc::Benchmark)
+        );
+        assert_eq!(GoTestFunc::from_name("Testify"), None);
+        assert_eq!(GoTestFunc::from_name("Examples"), None);
+    }
+
     #[test]
     fn test_block_comment() {
         let parser = GodocParser::new();
@@ -0,0 +1,272 @@
+//! @acp:module "Markdown Doc Parser"
+//! @acp:summary "Extracts a summary paragraph from Markdown documentation files"
+//! @acp:domain cli
+//! @acp:layer service
+//! @acp:stability experimental
+//!
+//! # Markdown Doc Parser
+//!
+//! Unlike the other converters this reads whole documents (`README.md`,
+//! `parser.md`) rather than doc comments. Only the summary and description
+//! are filled in:
+//!
+//! - YAML (`---`) and TOML (`+++`) front matter is skipped
+//! - Headings, code fences, HTML blocks, tables and badge/image lines never
+//!   count as a paragraph
+//! - When a section name is known, the first paragraph under a heading with
+//!   that name wins; otherwise the first paragraph of the document
+//! - Inline markup (links, emphasis, code spans) is reduced to plain text
+
+use super::{DocStandardParser, ParsedDocumentation};
+
+/// @acp:summary "Parses Markdown documents into a summary paragraph"
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownParser {
+    /// Heading to prefer, e.g. the source file the doc describes
+    section: Option<String>,
+}
+
+/// A paragraph and the heading it appears under
+struct Paragraph {
+    heading: Option<String>,
+    text: String,
+}
+
+impl MarkdownParser {
+    /// @acp:summary "Creates a new Markdown parser"
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// @acp:summary "Prefers the paragraph under a heading naming this section"
+    ///
+    /// Headings match case-insensitively, with or without code spans and the
+    /// file extension (`parser.rs` matches `## Parser` and ``## `parser.rs` ``).
+    pub fn with_section(mut self, name: impl Into<String>) -> Self {
+        self.section = Some(name.into());
+        self
+    }
+
+    /// @acp:summary "Strips YAML or TOML front matter"
+    fn strip_front_matter(content: &str) -> &str {
+        let content = content.trim_start_matches('\u{feff}');
+        for fence in ["---", "+++"] {
+            let Some(rest) = content.strip_prefix(fence) else {
+                continue;
+            };
+            let Some(rest) = rest
+                .strip_prefix("\r\n")
+                .or_else(|| rest.strip_prefix('\n'))
+            else {
+                continue;
+            };
+            let mut offset = 0;
+            for line in rest.split_inclusive('\n') {
+                offset += line.len();
+                if line.trim_end() == fence {
+                    return &rest[offset..];
+                }
+            }
+        }
+        content
+    }
+
+    /// @acp:summary "Splits a document into prose paragraphs"
+    fn paragraphs(body: &str) -> Vec<Paragraph> {
+        let mut paragraphs = Vec::new();
+        let mut heading: Option<String> = None;
+        let mut current: Vec<&str> = Vec::new();
+        let mut fence: Option<&str> = None;
+        let mut in_comment = false;
+
+        let flush = |current: &mut Vec<&str>, heading: &Option<String>, out: &mut Vec<_>| {
+            if !current.is_empty() {
+                out.push(Paragraph {
+                    heading: heading.clone(),
+                    text: Self::clean_inline(&current.join(" ")),
+                });
+                current.clear();
+            }
+        };
+
+        for line in body.lines() {
+            let trimmed = line.trim();
+
+            if let Some(marker) = fence {
+                if trimmed.starts_with(marker) {
+                    fence = None;
+                }
+                continue;
+            }
+            if in_comment {
+                in_comment = !trimmed.contains("-->");
+                continue;
+            }
+
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                flush(&mut current, &heading, &mut paragraphs);
+                fence = Some(&trimmed[..3]);
+            } else if trimmed.starts_with("<!--") {
+                flush(&mut current, &heading, &mut paragraphs);
+                in_comment = !trimmed.contains("-->");
+            } else if let Some(text) = trimmed.strip_prefix('#') {
+                flush(&mut current, &heading, &mut paragraphs);
+                heading = Some(text.trim_start_matches('#').trim().to_string());
+            } else if Self::is_setext_underline(trimmed) && current.len() == 1 {
+                // "Title\n=====" is a heading, not a paragraph
+                heading = Some(current[0].trim().to_string());
+                current.clear();
+            } else if trimmed.is_empty() || Self::is_non_prose(trimmed) {
+                flush(&mut current, &heading, &mut paragraphs);
+            } else {
+                current.push(trimmed.trim_start_matches('>').trim());
+            }
+        }
+        flush(&mut current, &heading, &mut paragraphs);
+
+        paragraphs.retain(|p| !p.text.is_empty());
+        paragraphs
+    }
+
+    fn is_setext_underline(line: &str) -> bool {
+        !line.is_empty() && (line.chars().all(|c| c == '=') || line.chars().all(|c| c == '-'))
+    }
+
+    /// Lines that are markup rather than prose: tables, HTML, badges, rules
+    fn is_non_prose(line: &str) -> bool {
+        line.starts_with('|')
+            || line.starts_with('<')
+            || line.starts_with("![")
+            || line.starts_with("[![")
+            || line.chars().all(|c| matches!(c, '-' | '*' | '_' | ' '))
+    }
+
+    /// @acp:summary "Reduces inline Markdown to plain text"
+    fn clean_inline(text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+
+        while let Some(start) = rest.find('[') {
+            let is_image = rest[..start].ends_with('!');
+            out.push_str(&rest[..start - usize::from(is_image)]);
+            let after = &rest[start + 1..];
+            let link = after
+                .find("](")
+                .and_then(|mid| Some((mid, after[mid + 2..].find(')')? + mid + 2)));
+            match link {
+                Some((mid, end)) => {
+                    if !is_image {
+                        out.push_str(&after[..mid]);
+                    }
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    out.push('[');
+                    rest = after;
+                }
+            }
+        }
+        out.push_str(rest);
+
+        out.replace("**", "")
+            .replace("__", "")
+            .replace('`', "")
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn heading_matches(heading: &str, section: &str) -> bool {
+        let normalize = |s: &str| s.replace('`', "").trim().to_lowercase();
+        let heading = normalize(heading);
+        let section = normalize(section);
+        let stem = section
+            .rsplit_once('.')
+            .map_or(section.as_str(), |(s, _)| s);
+        heading == section || heading == stem
+    }
+}
+
+impl DocStandardParser for MarkdownParser {
+    fn parse(&self, raw_comment: &str) -> ParsedDocumentation {
+        let mut doc = ParsedDocumentation::new();
+        let paragraphs = Self::paragraphs(Self::strip_front_matter(raw_comment));
+
+        let in_section = self.section.as_deref().and_then(|section| {
+            paragraphs.iter().find(|p| {
+                p.heading
+                    .as_deref()
+                    .is_some_and(|h| Self::heading_matches(h, section))
+            })
+        });
+
+        if let Some(paragraph) = in_section.or(paragraphs.first()) {
+            doc.summary = Some(paragraph.text.clone());
+            doc.description = Some(paragraph.text.clone());
+        }
+        doc
+    }
+
+    fn standard_name(&self) -> &'static str {
+        "markdown"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_paragraph_after_front_matter() {
+        let doc = r#"---
+title: Parser
+summary: not this
+---
+# Parser
+
+[![CI](https://ci.example/badge.svg)](https://ci.example)
+
+<!-- generated
+  do not edit -->
+Turns **source files** into a
+[`ParseResult`](src/parse.rs) for the indexer.
+
+Second paragraph.
+"#;
+        let parsed = MarkdownParser::new().parse(doc);
+        assert_eq!(
+            parsed.summary.as_deref(),
+            Some("Turns source files into a ParseResult for the indexer.")
+        );
+    }
+
+    #[test]
+    fn test_prefers_matching_section() {
+        let doc = r#"Utilities
+=========
+
+Shared helpers for the CLI.
+
+```rust
+// not prose
+```
+
+## `paths.rs`
+
+Path normalization helpers.
+
+## Other
+"#;
+        let parser = MarkdownParser::new();
+        assert_eq!(
+            parser.parse(doc).summary.as_deref(),
+            Some("Shared helpers for the CLI.")
+        );
+        let parser = MarkdownParser::new().with_section("paths.rs");
+        assert_eq!(
+            parser.parse(doc).summary.as_deref(),
+            Some("Path normalization helpers.")
+        );
+        assert!(MarkdownParser::new().parse("# Title only\n").is_empty());
+    }
+}
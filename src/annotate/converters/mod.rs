@@ -12,6 +12,7 @@
 //! - Rust doc comments
 //! - Go doc comments
 //! - Javadoc (Java)
+//! - Markdown documents paired with source files
 //!
 //! Each converter parses the raw documentation format into a structured
 //! [`ParsedDocumentation`] and then converts it to ACP [`Suggestion`]s.
@@ -20,12 +21,14 @@ pub mod docstring;
 pub mod godoc;
 pub mod javadoc;
 pub mod jsdoc;
+pub mod markdown;
 pub mod rustdoc;
 
 pub use docstring::DocstringParser;
-pub use godoc::{GoDocExtensions, GodocParser};
+pub use godoc::{GoDocExtensions, GoTestFunc, GodocParser};
 pub use javadoc::{JavadocExtensions, JavadocParser};
 pub use jsdoc::{JsDocParser, TsDocExtensions, TsDocParser};
+pub use markdown::MarkdownParser;
 pub use rustdoc::{RustDocExtensions, RustdocParser};
 
 use crate::annotate::{AnnotationType, Suggestion, SuggestionSource};
@@ -215,15 +215,41 @@ impl NamingHeuristics {
 
         let summary = match kind {
             Some(SymbolKind::Function) | Some(SymbolKind::Method) => {
-                // "getUserById" → "Gets user by ID"
-                let verb = &words[0];
-                let rest: Vec<String> = words[1..].iter().map(|w| w.to_lowercase()).collect();
-
-                let verb_third_person = to_third_person(verb);
-                if rest.is_empty() {
-                    capitalize(&verb_third_person)
-                } else {
-                    format!("{} {}", capitalize(&verb_third_person), rest.join(" "))
+                // Ruby suffixes: "empty?" is a predicate, "name=" a writer and
+                // "save!" a variant of "save"
+                let (stem, suffix) = match name.char_indices().last() {
+                    Some((i, c @ ('?' | '!' | '='))) => (&name[..i], Some(c)),
+                    _ => (name, None),
+                };
+                let words = split_identifier(stem);
+                if words.is_empty() {
+                    return None;
+                }
+                let lower: Vec<String> = words.iter().map(|w| w.to_lowercase()).collect();
+
+                match suffix {
+                    Some('=') => format!("Sets {}", lower.join(" ")),
+                    Some('?')
+                        if !matches!(
+                            lower[0].as_str(),
+                            "is" | "has" | "can" | "should" | "will" | "does"
+                        ) =>
+                    {
+                        format!("Checks if {}", lower.join(" "))
+                    }
+                    _ => {
+                        // "getUserById" → "Gets user by ID"
+                        let verb_third_person = to_third_person(&words[0]);
+                        if lower.len() == 1 {
+                            capitalize(&verb_third_person)
+                        } else {
+                            format!(
+                                "{} {}",
+                                capitalize(&verb_third_person),
+                                lower[1..].join(" ")
+                            )
+                        }
+                    }
                 }
             }
             Some(SymbolKind::Class) | Some(SymbolKind::Struct) => {
@@ -235,6 +261,15 @@ impl NamingHeuristics {
                     .join(" ")
                     .pipe(|s| capitalize(&s))
             }
+            Some(SymbolKind::Module) => {
+                // "Billing" → "Billing module"
+                let name = words
+                    .iter()
+                    .map(|w| w.to_lowercase())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                format!("{} module", capitalize(&name))
+            }
             Some(SymbolKind::Interface) | Some(SymbolKind::Trait) => {
                 // "Serializable" → "Serializable interface/trait"
                 let type_name = match kind {
@@ -760,4 +795,31 @@ mod tests {
         let summary = heuristics.generate_summary("UserService", Some(SymbolKind::Class));
         assert_eq!(summary, Some("User service".to_string()));
     }
+
+    #[test]
+    fn test_generate_summary_ruby_suffixes() {
+        let heuristics = NamingHeuristics::new();
+        let method = Some(SymbolKind::Method);
+
+        assert_eq!(
+            heuristics.generate_summary("empty?", method),
+            Some("Checks if empty".to_string())
+        );
+        assert_eq!(
+            heuristics.generate_summary("is_valid?", method),
+            Some("Checks if valid".to_string())
+        );
+        assert_eq!(
+            heuristics.generate_summary("save!", method),
+            Some("Saves".to_string())
+        );
+        assert_eq!(
+            heuristics.generate_summary("email=", method),
+            Some("Sets email".to_string())
+        );
+        assert_eq!(
+            heuristics.generate_summary("Billing", Some(SymbolKind::Module)),
+            Some("Billing module".to_string())
+        );
+    }
 }
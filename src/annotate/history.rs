@@ -0,0 +1,273 @@
+//! @acp:module "Generation History"
+//! @acp:summary "Append-only audit log of annotation generation runs (RFC-0003)"
+//! @acp:domain cli
+//! @acp:layer service
+//!
+//! Every applied `acp annotate` run appends one JSON line to
+//! `.acp/generations.jsonl`, so teams can trace when generated annotations
+//! entered the codebase. Each record is written with a single `write_all`
+//! on a file opened in append mode. A line cut short by an interrupted write
+//! is skipped when reading, and the next append starts on a fresh line.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::GenerationInfo;
+use crate::config::Config;
+use crate::error::Result;
+
+/// Default file name, placed next to the cache
+pub const GENERATIONS_FILE: &str = "generations.jsonl";
+
+/// @acp:summary "One generation run as recorded in the audit log"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerationRecord {
+    /// Batch id, timestamp and counts
+    #[serde(flatten)]
+    pub info: GenerationInfo,
+    /// Files that received annotations
+    pub files: Vec<String>,
+    /// Annotations per suggestion source
+    #[serde(default)]
+    pub by_source: BTreeMap<String, u64>,
+    /// Spread of suggestion confidence
+    pub confidence: ConfidenceDistribution,
+    /// Lines inserted per file, used by `acp annotate --undo`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub insertions: BTreeMap<String, FileInsertions>,
+}
+
+/// @acp:summary "Lines one generation inserted into a file"
+///
+/// The hashes let undo verify the file is exactly as the run left it
+/// before deleting anything, and that deleting the lines restores it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileInsertions {
+    /// SHA-256 of the file before the run
+    pub before: String,
+    /// SHA-256 of the file as the run wrote it
+    pub after: String,
+    /// 1-indexed line numbers of inserted lines in the written file
+    pub lines: Vec<usize>,
+}
+
+/// @acp:summary "Confidence summary for a generation run"
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfidenceDistribution {
+    pub min: f64,
+    pub max: f64,
+    pub average: f64,
+    /// Below 0.5
+    pub low: u64,
+    /// 0.5 up to 0.8
+    pub medium: u64,
+    /// 0.8 and above
+    pub high: u64,
+}
+
+impl ConfidenceDistribution {
+    /// Summarize a set of confidence scores
+    pub fn from_scores<I: IntoIterator<Item = f64>>(scores: I) -> Self {
+        let mut dist = Self {
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            ..Default::default()
+        };
+        let mut total = 0.0;
+        let mut count = 0u64;
+        for score in scores {
+            dist.min = dist.min.min(score);
+            dist.max = dist.max.max(score);
+            total += score;
+            count += 1;
+            match score {
+                s if s < 0.5 => dist.low += 1,
+                s if s < 0.8 => dist.medium += 1,
+                _ => dist.high += 1,
+            }
+        }
+        if count == 0 {
+            return Self::default();
+        }
+        dist.average = total / count as f64;
+        dist
+    }
+}
+
+/// @acp:summary "Reader and appender for the generation audit log"
+#[derive(Debug, Clone)]
+pub struct GenerationLog {
+    path: PathBuf,
+}
+
+impl GenerationLog {
+    /// Log at an explicit path
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Log stored next to a cache file (e.g. `.acp/generations.jsonl`)
+    pub fn beside_cache<P: AsRef<Path>>(cache: P) -> Self {
+        Self::new(cache.as_ref().with_file_name(GENERATIONS_FILE))
+    }
+
+    /// Log next to the configured cache
+    ///
+    /// `annotate` writes here and `review history` reads here, so a
+    /// one-off `--cache` override cannot send them to different files.
+    pub fn for_config(config: &Config) -> Self {
+        Self::beside_cache(config.cache_path())
+    }
+
+    /// Path of the log file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append one record as a JSON line
+    pub fn append(&self, record: &GenerationRecord) -> Result<()> {
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .read(true)
+            .open(&self.path)?;
+
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+        if !ends_with_newline(&mut file)? {
+            line.insert(0, '\n');
+        }
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    /// Read every complete record in append order
+    ///
+    /// Returns the records and the number of lines skipped as unreadable.
+    /// A missing log reads as empty.
+    pub fn read(&self) -> Result<(Vec<GenerationRecord>, usize)> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((Vec::new(), 0)),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut records = Vec::new();
+        let mut skipped = 0;
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line) {
+                Ok(record) => records.push(record),
+                Err(_) => skipped += 1,
+            }
+        }
+        Ok((records, skipped))
+    }
+}
+
+/// True for an empty file or one whose last byte is `\n`
+fn ends_with_newline(file: &mut File) -> Result<bool> {
+    if file.metadata()?.len() == 0 {
+        return Ok(true);
+    }
+    file.seek(SeekFrom::End(-1))?;
+    let mut last = [0u8; 1];
+    file.read_exact(&mut last)?;
+    Ok(last[0] == b'\n')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, scores: &[f64]) -> GenerationRecord {
+        GenerationRecord {
+            info: GenerationInfo {
+                id: id.to_string(),
+                timestamp: "2026-01-01T00:00:00Z".to_string(),
+                annotations_generated: scores.len() as u64,
+                files_affected: 1,
+            },
+            files: vec!["src/app.ts".to_string()],
+            by_source: BTreeMap::from([("Heuristic".to_string(), scores.len() as u64)]),
+            confidence: ConfidenceDistribution::from_scores(scores.iter().copied()),
+            insertions: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_confidence_distribution() {
+        let dist = ConfidenceDistribution::from_scores([0.4, 0.6, 0.9, 1.0]);
+        assert_eq!((dist.low, dist.medium, dist.high), (1, 1, 2));
+        assert_eq!((dist.min, dist.max), (0.4, 1.0));
+        assert!((dist.average - 0.725).abs() < 1e-9);
+
+        assert_eq!(
+            ConfidenceDistribution::from_scores([]),
+            ConfidenceDistribution::default()
+        );
+    }
+
+    #[test]
+    fn test_append_and_read_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = GenerationLog::beside_cache(dir.path().join(".acp/acp.cache.json"));
+        assert_eq!(log.read().unwrap(), (Vec::new(), 0));
+
+        log.append(&record("gen-1", &[0.5])).unwrap();
+        log.append(&record("gen-2", &[0.9, 0.7])).unwrap();
+
+        let (records, skipped) = log.read().unwrap();
+        assert_eq!(skipped, 0);
+        let ids: Vec<_> = records.iter().map(|r| r.info.id.as_str()).collect();
+        assert_eq!(ids, vec!["gen-1", "gen-2"]);
+        assert_eq!(records[1], record("gen-2", &[0.9, 0.7]));
+
+        let raw = fs::read_to_string(log.path()).unwrap();
+        assert_eq!(raw.lines().count(), 2);
+        assert!(raw.contains("\"annotationsGenerated\":1"));
+    }
+
+    #[test]
+    fn test_log_follows_configured_cache() {
+        let mut config = Config::default();
+        config.output.get_or_insert_with(Default::default).cache =
+            PathBuf::from("build/acp/cache.json");
+        assert_eq!(
+            GenerationLog::for_config(&config).path(),
+            Path::new("build/acp/generations.jsonl")
+        );
+    }
+
+    #[test]
+    fn test_recovers_from_partial_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = GenerationLog::new(dir.path().join("generations.jsonl"));
+        log.append(&record("gen-1", &[0.5])).unwrap();
+
+        // Simulate a write cut off mid-record
+        let mut file = OpenOptions::new().append(true).open(log.path()).unwrap();
+        file.write_all(b"{\"id\":\"gen-broken\",\"times").unwrap();
+        drop(file);
+
+        log.append(&record("gen-2", &[0.8])).unwrap();
+
+        let (records, skipped) = log.read().unwrap();
+        let ids: Vec<_> = records.iter().map(|r| r.info.id.as_str()).collect();
+        assert_eq!(ids, vec!["gen-1", "gen-2"]);
+        assert_eq!(skipped, 1);
+    }
+}
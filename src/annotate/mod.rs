@@ -20,17 +20,22 @@
 //! - [`writer`] - File modification with diff support
 //! - [`heuristics`] - Pattern-based inference rules
 //! - [`converters`] - Per-standard documentation conversion
+//! - [`history`] - Append-only audit log of generation runs
 
 pub mod analyzer;
 pub mod converters;
 pub mod heuristics;
+pub mod history;
 pub mod suggester;
+pub mod undo;
 pub mod writer;
 
 pub use analyzer::Analyzer;
 pub use converters::{DocStandardParser, ParsedDocumentation};
+pub use history::{ConfidenceDistribution, FileInsertions, GenerationLog, GenerationRecord};
 pub use suggester::Suggester;
-pub use writer::{CommentStyle, Writer};
+pub use undo::{find_undoable, undo_generation, UndoReport};
+pub use writer::{comment_prefix, source_digest, ApplyOutcome, CommentStyle, Writer};
 
 use serde::{Deserialize, Serialize};
 
@@ -393,6 +398,11 @@ pub struct AnalysisResult {
 
     /// Annotation coverage percentage (0.0 - 100.0)
     pub coverage: f32,
+
+    /// SHA-256 of the content that was analyzed, used by `--apply` to
+    /// detect files edited since analysis
+    #[serde(skip)]
+    pub content_hash: Option<String>,
 }
 
 impl AnalysisResult {
@@ -404,6 +414,7 @@ impl AnalysisResult {
             existing_annotations: Vec::new(),
             gaps: Vec::new(),
             coverage: 0.0,
+            content_hash: None,
         }
     }
 
@@ -617,7 +628,7 @@ impl ConversionSource {
             "javascript" | "jsx" | "js" => Self::Jsdoc,
             "python" | "py" => Self::Docstring,
             "rust" | "rs" => Self::Rustdoc,
-            "go" => Self::Godoc,
+            "go" | "golang" => Self::Godoc,
             "java" => Self::Javadoc,
             _ => Self::Auto,
         }
@@ -78,7 +78,7 @@ impl Suggester {
             // Try to convert existing doc comments
             if let Some(doc_comment) = &gap.doc_comment {
                 let source = self.get_conversion_source(&analysis.language);
-                if let Some(parser) = self.get_parser(source) {
+                if let Some(parser) = self.get_parser(source, &gap.target) {
                     let parsed = parser.parse(doc_comment);
                     let converted = parser.to_suggestions(&parsed, &gap.target, gap.line);
                     gap_suggestions.extend(converted);
@@ -130,7 +130,7 @@ impl Suggester {
             // Try to convert existing doc comments
             if let Some(doc_comment) = &gap.doc_comment {
                 let source = self.get_conversion_source(&analysis.language);
-                if let Some(parser) = self.get_parser(source) {
+                if let Some(parser) = self.get_parser(source, &gap.target) {
                     let parsed = parser.parse(doc_comment);
                     let converted = parser.to_suggestions(&parsed, &gap.target, gap.line);
                     gap_suggestions.extend(converted);
@@ -173,7 +173,13 @@ impl Suggester {
     }
 
     /// @acp:summary "Gets the appropriate doc parser for a conversion source"
-    fn get_parser(&self, source: ConversionSource) -> Option<Box<dyn DocStandardParser>> {
+    ///
+    /// Godoc needs the documented name to apply Go's naming conventions.
+    fn get_parser(
+        &self,
+        source: ConversionSource,
+        target: &str,
+    ) -> Option<Box<dyn DocStandardParser>> {
         use super::converters::{
             DocstringParser, GodocParser, JavadocParser, JsDocParser, RustdocParser,
         };
@@ -182,7 +188,7 @@ impl Suggester {
             ConversionSource::Jsdoc | ConversionSource::Tsdoc => Some(Box::new(JsDocParser::new())),
             ConversionSource::Docstring => Some(Box::new(DocstringParser::new())),
             ConversionSource::Rustdoc => Some(Box::new(RustdocParser::new())),
-            ConversionSource::Godoc => Some(Box::new(GodocParser::new())),
+            ConversionSource::Godoc => Some(Box::new(GodocParser::new().with_element_name(target))),
             ConversionSource::Javadoc => Some(Box::new(JavadocParser::new())),
             ConversionSource::Auto => None,
         }
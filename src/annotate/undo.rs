@@ -0,0 +1,186 @@
+//! @acp:module "Generation Undo"
+//! @acp:summary "Removes the annotations a generation run inserted (RFC-0003)"
+//! @acp:domain cli
+//! @acp:layer service
+//!
+//! Each applied run records the lines it inserted per file together with the
+//! file's hash before and after. Undo only touches a file whose content still
+//! hashes to the recorded "after" value, and only writes the result if
+//! removing the lines reproduces the "before" hash. Files edited since the
+//! run are reported as drifted and left alone.
+
+use std::path::{Path, PathBuf};
+
+use super::history::{FileInsertions, GenerationRecord};
+use super::writer::{sibling_path, source_digest, write_atomic};
+use crate::error::Result;
+
+/// @acp:summary "Per-file result of undoing a generation"
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UndoReport {
+    /// Files whose inserted lines were removed
+    pub reverted: Vec<String>,
+    /// Files already back to their pre-run content
+    pub unchanged: Vec<String>,
+    /// Files edited since the run, with the backup to restore from if one exists
+    pub drifted: Vec<(String, Option<PathBuf>)>,
+}
+
+impl UndoReport {
+    /// @acp:summary "True when every file was reverted or already clean"
+    pub fn is_complete(&self) -> bool {
+        self.drifted.is_empty()
+    }
+}
+
+/// @acp:summary "Pick the generation to undo"
+///
+/// With an id, the matching record; otherwise the most recent record with
+/// insertions that has not been fully undone yet. Paths resolve against `root`.
+pub fn find_undoable<'a>(
+    records: &'a [GenerationRecord],
+    id: Option<&str>,
+    root: &Path,
+) -> Option<&'a GenerationRecord> {
+    match id {
+        Some(id) => records.iter().rev().find(|r| r.info.id == id),
+        None => records.iter().rev().find(|r| {
+            r.insertions.iter().any(|(file, inserted)| {
+                std::fs::read_to_string(root.join(file))
+                    .is_ok_and(|content| source_digest(&content) != inserted.before)
+            })
+        }),
+    }
+}
+
+/// @acp:summary "Remove a generation's inserted lines from its files"
+pub fn undo_generation(record: &GenerationRecord, root: &Path) -> Result<UndoReport> {
+    let mut report = UndoReport::default();
+
+    for (file, inserted) in &record.insertions {
+        let path = root.join(file);
+        let content = std::fs::read_to_string(&path)?;
+        let hash = source_digest(&content);
+
+        if hash == inserted.before {
+            report.unchanged.push(file.clone());
+            continue;
+        }
+        let restored = (hash == inserted.after)
+            .then(|| remove_lines(&content, inserted))
+            .filter(|restored| source_digest(restored) == inserted.before);
+
+        match restored {
+            Some(restored) => {
+                write_atomic(&path, &restored)?;
+                report.reverted.push(file.clone());
+            }
+            None => {
+                let backup = sibling_path(&path, ".acp.bak");
+                let backup = std::fs::read_to_string(&backup)
+                    .is_ok_and(|b| source_digest(&b) == inserted.before)
+                    .then_some(backup);
+                report.drifted.push((file.clone(), backup));
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Content with the recorded 1-indexed lines deleted, keeping line endings
+fn remove_lines(content: &str, inserted: &FileInsertions) -> String {
+    content
+        .split_inclusive('\n')
+        .enumerate()
+        .filter(|(i, _)| !inserted.lines.contains(&(i + 1)))
+        .map(|(_, line)| line)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::annotate::{
+        ApplyOutcome, ConfidenceDistribution, FileChange, Suggestion, SuggestionSource, Writer,
+    };
+    use crate::cache::GenerationInfo;
+    use std::collections::BTreeMap;
+
+    fn apply(root: &Path, file: &str, id: &str) -> GenerationRecord {
+        let path = root.join(file);
+        let mut change = FileChange::new(path.to_string_lossy(), 2).with_symbol("run");
+        change.add_annotation(Suggestion::summary(
+            "run",
+            2,
+            "Runs the app",
+            SuggestionSource::Heuristic,
+        ));
+        let ApplyOutcome::Applied(inserted) = Writer::new()
+            .with_backup(true)
+            .apply_changes(&path, &[change], None)
+            .unwrap()
+        else {
+            panic!("expected changes to be applied");
+        };
+
+        GenerationRecord {
+            info: GenerationInfo {
+                id: id.to_string(),
+                timestamp: "2026-01-01T00:00:00Z".to_string(),
+                annotations_generated: 1,
+                files_affected: 1,
+            },
+            files: vec![file.to_string()],
+            by_source: BTreeMap::new(),
+            confidence: ConfidenceDistribution::default(),
+            insertions: BTreeMap::from([(file.to_string(), inserted)]),
+        }
+    }
+
+    #[test]
+    fn test_undo_restores_original() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = "import x from 'x';\nexport function run() {}\n";
+        std::fs::write(dir.path().join("app.ts"), original).unwrap();
+
+        let record = apply(dir.path(), "app.ts", "gen-1");
+        let records = vec![record];
+        let found = find_undoable(&records, None, dir.path()).unwrap();
+
+        let report = undo_generation(found, dir.path()).unwrap();
+        assert_eq!(report.reverted, vec!["app.ts".to_string()]);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("app.ts")).unwrap(),
+            original
+        );
+
+        // Nothing left to undo
+        assert!(find_undoable(&records, None, dir.path()).is_none());
+        let report = undo_generation(&records[0], dir.path()).unwrap();
+        assert_eq!(report.unchanged, vec!["app.ts".to_string()]);
+    }
+
+    #[test]
+    fn test_undo_refuses_drifted_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.ts");
+        std::fs::write(&path, "import x from 'x';\nexport function run() {}\n").unwrap();
+
+        let record = apply(dir.path(), "app.ts", "gen-1");
+        let mut edited = std::fs::read_to_string(&path).unwrap();
+        edited.insert_str(0, "// edited by hand\n");
+        std::fs::write(&path, &edited).unwrap();
+
+        let report = undo_generation(&record, dir.path()).unwrap();
+        assert!(!report.is_complete());
+        assert_eq!(
+            report.drifted,
+            vec![(
+                "app.ts".to_string(),
+                Some(dir.path().join("app.ts.acp.bak"))
+            )]
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), edited);
+    }
+}
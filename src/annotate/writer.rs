@@ -13,12 +13,14 @@
 //! - Preserving existing documentation
 
 use std::collections::HashSet;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use sha2::{Digest, Sha256};
 use similar::TextDiff;
 
 use crate::error::Result;
 
+use super::history::FileInsertions;
 use super::{AnalysisResult, FileChange, ProvenanceConfig, Suggestion};
 
 /// @acp:summary "Comment style for different languages"
@@ -36,6 +38,8 @@ pub enum CommentStyle {
     GoDoc,
     /// Javadoc: /** ... */
     Javadoc,
+    /// Ruby: #
+    RubyDoc,
 }
 
 impl CommentStyle {
@@ -52,74 +56,71 @@ impl CommentStyle {
                 }
             }
             "go" => Self::GoDoc,
-            "java" => Self::Javadoc,
+            "java" | "kotlin" => Self::Javadoc,
+            "ruby" => Self::RubyDoc,
             _ => Self::JsDoc, // Default to JSDoc style
         }
     }
 
-    /// @acp:summary "Formats annotations into a comment block"
-    pub fn format_annotations(&self, annotations: &[Suggestion], indent: &str) -> String {
-        if annotations.is_empty() {
-            return String::new();
+    /// @acp:summary "Prefix written before each annotation line"
+    ///
+    /// For block styles this is the ` *` continuation inside `/** ... */`.
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Self::JsDoc | Self::Javadoc => " *",
+            Self::PyDocstring | Self::RubyDoc => "#",
+            Self::RustDoc => "///",
+            Self::RustModuleDoc => "//!",
+            Self::GoDoc => "//",
         }
+    }
 
-        match self {
-            Self::JsDoc | Self::Javadoc => {
-                let mut lines = vec![format!("{}/**", indent)];
-                for ann in annotations {
-                    lines.push(format!("{} * {}", indent, ann.to_annotation_string()));
-                }
-                lines.push(format!("{} */", indent));
-                lines.join("\n")
-            }
-            Self::PyDocstring => annotations
-                .iter()
-                .map(|ann| format!("{}# {}", indent, ann.to_annotation_string()))
-                .collect::<Vec<_>>()
-                .join("\n"),
-            Self::RustDoc => annotations
-                .iter()
-                .map(|ann| format!("{}/// {}", indent, ann.to_annotation_string()))
-                .collect::<Vec<_>>()
-                .join("\n"),
-            Self::RustModuleDoc => annotations
-                .iter()
-                .map(|ann| format!("{}//! {}", indent, ann.to_annotation_string()))
-                .collect::<Vec<_>>()
-                .join("\n"),
-            Self::GoDoc => annotations
-                .iter()
-                .map(|ann| format!("{}// {}", indent, ann.to_annotation_string()))
-                .collect::<Vec<_>>()
-                .join("\n"),
+    /// Whether annotations are wrapped in a `/** ... */` block
+    fn is_block(&self) -> bool {
+        matches!(self, Self::JsDoc | Self::Javadoc)
+    }
+
+    /// Prefixes each line, wrapping block styles in `/** ... */`
+    fn block(&self, lines: &[String], indent: &str) -> String {
+        if lines.is_empty() {
+            return String::new();
+        }
+        let mut block = Vec::with_capacity(lines.len() + 2);
+        if self.is_block() {
+            block.push(format!("{}/**", indent));
         }
+        block.extend(self.lines(lines, indent));
+        if self.is_block() {
+            block.push(format!("{} */", indent));
+        }
+        block.join("\n")
+    }
+
+    /// Prefixes each line for insertion into an existing comment
+    fn lines(&self, lines: &[String], indent: &str) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| format!("{}{} {}", indent, self.prefix(), line))
+            .collect()
+    }
+
+    /// @acp:summary "Formats annotations into a comment block"
+    pub fn format_annotations(&self, annotations: &[Suggestion], indent: &str) -> String {
+        let lines: Vec<String> = annotations
+            .iter()
+            .map(|ann| ann.to_annotation_string())
+            .collect();
+        self.block(&lines, indent)
     }
 
     /// @acp:summary "Formats annotations for insertion into existing doc comment"
     /// Places ACP annotations at the beginning of the comment.
     pub fn format_for_insertion(&self, annotations: &[Suggestion], indent: &str) -> Vec<String> {
-        match self {
-            Self::JsDoc | Self::Javadoc => annotations
-                .iter()
-                .map(|ann| format!("{} * {}", indent, ann.to_annotation_string()))
-                .collect(),
-            Self::PyDocstring => annotations
-                .iter()
-                .map(|ann| format!("{}# {}", indent, ann.to_annotation_string()))
-                .collect(),
-            Self::RustDoc => annotations
-                .iter()
-                .map(|ann| format!("{}/// {}", indent, ann.to_annotation_string()))
-                .collect(),
-            Self::RustModuleDoc => annotations
-                .iter()
-                .map(|ann| format!("{}//! {}", indent, ann.to_annotation_string()))
-                .collect(),
-            Self::GoDoc => annotations
-                .iter()
-                .map(|ann| format!("{}// {}", indent, ann.to_annotation_string()))
-                .collect(),
-        }
+        let lines: Vec<String> = annotations
+            .iter()
+            .map(|ann| ann.to_annotation_string())
+            .collect();
+        self.lines(&lines, indent)
     }
 
     /// @acp:summary "Formats annotations with RFC-0003 provenance markers"
@@ -129,46 +130,12 @@ impl CommentStyle {
         indent: &str,
         config: &ProvenanceConfig,
     ) -> String {
-        if annotations.is_empty() {
-            return String::new();
-        }
-
         // Collect all annotation lines (main + provenance markers)
         let all_lines: Vec<String> = annotations
             .iter()
             .flat_map(|ann| ann.to_annotation_strings_with_provenance(config))
             .collect();
-
-        match self {
-            Self::JsDoc | Self::Javadoc => {
-                let mut lines = vec![format!("{}/**", indent)];
-                for line in all_lines {
-                    lines.push(format!("{} * {}", indent, line));
-                }
-                lines.push(format!("{} */", indent));
-                lines.join("\n")
-            }
-            Self::PyDocstring => all_lines
-                .iter()
-                .map(|line| format!("{}# {}", indent, line))
-                .collect::<Vec<_>>()
-                .join("\n"),
-            Self::RustDoc => all_lines
-                .iter()
-                .map(|line| format!("{}/// {}", indent, line))
-                .collect::<Vec<_>>()
-                .join("\n"),
-            Self::RustModuleDoc => all_lines
-                .iter()
-                .map(|line| format!("{}//! {}", indent, line))
-                .collect::<Vec<_>>()
-                .join("\n"),
-            Self::GoDoc => all_lines
-                .iter()
-                .map(|line| format!("{}// {}", indent, line))
-                .collect::<Vec<_>>()
-                .join("\n"),
-        }
+        self.block(&all_lines, indent)
     }
 
     /// @acp:summary "Formats annotations for insertion with RFC-0003 provenance markers"
@@ -183,32 +150,28 @@ impl CommentStyle {
             .iter()
             .flat_map(|ann| ann.to_annotation_strings_with_provenance(config))
             .collect();
-
-        match self {
-            Self::JsDoc | Self::Javadoc => all_lines
-                .iter()
-                .map(|line| format!("{} * {}", indent, line))
-                .collect(),
-            Self::PyDocstring => all_lines
-                .iter()
-                .map(|line| format!("{}# {}", indent, line))
-                .collect(),
-            Self::RustDoc => all_lines
-                .iter()
-                .map(|line| format!("{}/// {}", indent, line))
-                .collect(),
-            Self::RustModuleDoc => all_lines
-                .iter()
-                .map(|line| format!("{}//! {}", indent, line))
-                .collect(),
-            Self::GoDoc => all_lines
-                .iter()
-                .map(|line| format!("{}// {}", indent, line))
-                .collect(),
-        }
+        self.lines(&all_lines, indent)
     }
 }
 
+/// @acp:summary "Line prefix for annotations written into a language's source"
+pub fn comment_prefix(language: &str) -> &'static str {
+    CommentStyle::from_language(language, false).prefix()
+}
+
+/// @acp:summary "Indentation to give a comment inserted above `line` (1-indexed)"
+///
+/// Copies the leading whitespace of the target line verbatim, so tab-indented
+/// files stay tab-indented. A blank target borrows from the next non-blank line.
+fn line_indent(lines: &[String], line: usize) -> &str {
+    lines
+        .iter()
+        .skip(line.saturating_sub(1))
+        .find(|l| !l.trim().is_empty())
+        .map(|l| &l[..l.len() - l.trim_start().len()])
+        .unwrap_or("")
+}
+
 /// @acp:summary "Writes annotations to files and generates diffs"
 /// @acp:lock normal
 pub struct Writer {
@@ -216,6 +179,17 @@ pub struct Writer {
     preserve_existing: bool,
     /// RFC-0003: Provenance configuration (None = no provenance markers)
     provenance_config: Option<ProvenanceConfig>,
+    /// Keep the original content in `<file>.acp.bak` when applying
+    backup: bool,
+}
+
+/// @acp:summary "What happened when applying changes to a file"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApplyOutcome {
+    /// Changes were written; records the inserted lines for undo
+    Applied(FileInsertions),
+    /// The file changed on disk since it was analyzed; nothing was written
+    Modified,
 }
 
 impl Writer {
@@ -224,9 +198,16 @@ impl Writer {
         Self {
             preserve_existing: true,
             provenance_config: None,
+            backup: false,
         }
     }
 
+    /// @acp:summary "Sets whether to write a .acp.bak backup before applying"
+    pub fn with_backup(mut self, backup: bool) -> Self {
+        self.backup = backup;
+        self
+    }
+
     /// @acp:summary "Sets whether to preserve existing documentation"
     pub fn with_preserve_existing(mut self, preserve: bool) -> Self {
         self.preserve_existing = preserve;
@@ -317,6 +298,19 @@ impl Writer {
         Ok(diff)
     }
 
+    /// @acp:summary "Renders in-memory source with changes applied"
+    ///
+    /// Like [`Writer::generate_diff`] but for content that is not on disk;
+    /// `file_path` only selects the comment style.
+    pub fn render(
+        &self,
+        file_path: &Path,
+        content: &str,
+        changes: &[FileChange],
+    ) -> Result<String> {
+        self.apply_to_content(content, changes, &self.detect_language(file_path))
+    }
+
     /// @acp:summary "Applies changes to file content"
     fn apply_to_content(
         &self,
@@ -334,19 +328,15 @@ impl Writer {
             let is_module_level = change.symbol_name.is_none();
             let style = CommentStyle::from_language(language, is_module_level);
 
-            // Detect indentation from the target line
-            let indent = if change.line > 0 && change.line <= lines.len() {
-                let target_line = &lines[change.line - 1];
-                let trimmed = target_line.trim_start();
-                &target_line[..target_line.len() - trimmed.len()]
-            } else {
-                ""
-            };
+            let indent = line_indent(&lines, change.line).to_string();
+            let indent = indent.as_str();
 
-            // For Python/Go style (# or // comments), ALWAYS insert before the symbol
+            // For Python/Ruby/Go style (# or // comments), ALWAYS insert before the symbol
             // regardless of existing docstrings (since docstrings are inside the body, not before)
-            let is_line_comment_style =
-                matches!(style, CommentStyle::PyDocstring | CommentStyle::GoDoc);
+            let is_line_comment_style = matches!(
+                style,
+                CommentStyle::PyDocstring | CommentStyle::RubyDoc | CommentStyle::GoDoc
+            );
 
             if change.existing_doc_start.is_some() && !is_line_comment_style {
                 // Insert into existing doc comment (JSDoc, Javadoc, etc.)
@@ -432,17 +422,54 @@ impl Writer {
             }
         }
 
-        Ok(lines.join("\n"))
+        let mut modified = lines.join("\n");
+        if content.ends_with('\n') {
+            modified.push('\n');
+        }
+        Ok(modified)
     }
 
     /// @acp:summary "Applies changes to a file on disk"
-    pub fn apply_changes(&self, file_path: &Path, changes: &[FileChange]) -> Result<()> {
+    ///
+    /// When `expected_hash` is given and the file no longer matches it, the
+    /// file is left alone so concurrent edits aren't clobbered. The new
+    /// content goes to a temporary file that is renamed over the original,
+    /// so an interrupted run never leaves a half-written source file.
+    pub fn apply_changes(
+        &self,
+        file_path: &Path,
+        changes: &[FileChange],
+        expected_hash: Option<&str>,
+    ) -> Result<ApplyOutcome> {
         let content = std::fs::read_to_string(file_path)?;
+        if expected_hash.is_some_and(|hash| hash != source_digest(&content)) {
+            return Ok(ApplyOutcome::Modified);
+        }
+
         let language = self.detect_language(file_path);
         let modified = self.apply_to_content(&content, changes, &language)?;
 
-        std::fs::write(file_path, modified)?;
-        Ok(())
+        if self.backup {
+            write_atomic(&sibling_path(file_path, ".acp.bak"), &content)?;
+        }
+        write_atomic(file_path, &modified)?;
+
+        let lines = TextDiff::from_lines(&content, &modified)
+            .ops()
+            .iter()
+            .filter_map(|op| match *op {
+                similar::DiffOp::Insert {
+                    new_index, new_len, ..
+                } => Some(new_index + 1..=new_index + new_len),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        Ok(ApplyOutcome::Applied(FileInsertions {
+            before: source_digest(&content),
+            after: source_digest(&modified),
+            lines,
+        }))
     }
 
     /// @acp:summary "Detects language from file extension"
@@ -456,6 +483,8 @@ impl Writer {
                 "rs" => "rust",
                 "go" => "go",
                 "java" => "java",
+                "kt" | "kts" => "kotlin",
+                "rb" | "rake" => "ruby",
                 _ => "unknown",
             })
             .unwrap_or("unknown")
@@ -469,6 +498,37 @@ impl Default for Writer {
     }
 }
 
+/// @acp:summary "SHA-256 of file content, hex encoded"
+pub fn source_digest(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+/// `path` with `suffix` appended to its file name
+pub(super) fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Write via a temporary file in the same directory, then rename into place
+pub(super) fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(path.file_name().unwrap_or_default());
+    temp_name.push(".acp.tmp");
+    let temp = path.with_file_name(temp_name);
+
+    std::fs::write(&temp, content)?;
+    // Keep the original file's permissions (e.g. executable scripts)
+    if let Ok(metadata) = std::fs::metadata(path) {
+        std::fs::set_permissions(&temp, metadata.permissions())?;
+    }
+    if let Err(e) = std::fs::rename(&temp, path) {
+        let _ = std::fs::remove_file(&temp);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
 /// @acp:summary "Generates a unified diff between original and modified content"
 pub fn generate_unified_diff(file_path: &str, original: &str, modified: &str) -> String {
     let diff = TextDiff::from_lines(original, modified);
@@ -503,6 +563,93 @@ mod tests {
             CommentStyle::from_language("rust", true),
             CommentStyle::RustModuleDoc
         );
+        assert_eq!(
+            CommentStyle::from_language("ruby", false),
+            CommentStyle::RubyDoc
+        );
+        assert_eq!(
+            CommentStyle::from_language("kotlin", false),
+            CommentStyle::Javadoc
+        );
+        assert_eq!(
+            CommentStyle::from_language("unknown", false),
+            CommentStyle::JsDoc
+        );
+
+        assert_eq!(comment_prefix("python"), "#");
+        assert_eq!(comment_prefix("rust"), "///");
+        assert_eq!(comment_prefix("go"), "//");
+        assert_eq!(comment_prefix("typescript"), " *");
+    }
+
+    #[test]
+    fn test_render_matches_indentation() {
+        let summary = |target: &str, line| {
+            let mut change = FileChange::new("app.py", line).with_symbol(target);
+            change.add_annotation(Suggestion::summary(
+                target,
+                line,
+                "Does work",
+                SuggestionSource::Heuristic,
+            ));
+            change
+        };
+
+        // Tab-indented methods keep their tabs
+        let source = "class App:\n\tdef run(self):\n\t\tpass\n\n\tdef stop(self):\n\t\tpass\n";
+        let rendered = Writer::new()
+            .render(
+                Path::new("app.py"),
+                source,
+                &[summary("run", 2), summary("stop", 5)],
+            )
+            .unwrap();
+        assert_eq!(
+            rendered,
+            "class App:\n\
+             \t# @acp:summary \"Does work\"\n\
+             \tdef run(self):\n\
+             \t\tpass\n\
+             \n\
+             \t# @acp:summary \"Does work\"\n\
+             \tdef stop(self):\n\
+             \t\tpass\n"
+        );
+
+        // Type stubs are Python too
+        let rendered = Writer::new()
+            .render(
+                Path::new("app.pyi"),
+                "def run() -> None: ...\n",
+                &[summary("run", 1)],
+            )
+            .unwrap();
+        assert_eq!(
+            rendered,
+            "# @acp:summary \"Does work\"\ndef run() -> None: ...\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_keeps_trailing_newline() {
+        let mut change = FileChange::new("app.ts", 1).with_symbol("run");
+        change.add_annotation(Suggestion::summary(
+            "run",
+            1,
+            "Runs",
+            SuggestionSource::Heuristic,
+        ));
+        let writer = Writer::new();
+        let path = Path::new("app.ts");
+
+        // Undo restores by hash, so the final newline must survive
+        let rendered = writer
+            .render(path, "function run() {}\n", std::slice::from_ref(&change))
+            .unwrap();
+        assert!(rendered.ends_with("function run() {}\n"));
+
+        let rendered = writer.render(path, "function run() {}", &[change]).unwrap();
+        assert!(rendered.ends_with("function run() {}"));
     }
 
     #[test]
@@ -605,4 +752,46 @@ mod tests {
 
         assert!(formatted.contains("// @acp:summary \"Test summary\""));
     }
+
+    #[test]
+    fn test_apply_changes_backup_and_hash_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.ts");
+        let original = "export function run() {}\n";
+        std::fs::write(&path, original).unwrap();
+
+        let mut change = FileChange::new(path.to_string_lossy(), 1).with_symbol("run");
+        change.add_annotation(Suggestion::summary(
+            "run",
+            1,
+            "Runs the app",
+            SuggestionSource::Heuristic,
+        ));
+        let changes = vec![change];
+        let writer = Writer::new().with_backup(true);
+
+        // Stale hash: the file is left untouched
+        let outcome = writer
+            .apply_changes(&path, &changes, Some("not-the-hash"))
+            .unwrap();
+        assert_eq!(outcome, ApplyOutcome::Modified);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), original);
+
+        let hash = source_digest(original);
+        let ApplyOutcome::Applied(inserted) =
+            writer.apply_changes(&path, &changes, Some(&hash)).unwrap()
+        else {
+            panic!("expected changes to be applied");
+        };
+        assert_eq!(inserted.before, hash);
+        assert_eq!(inserted.lines, vec![1, 2, 3]);
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("@acp:summary \"Runs the app\""));
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("app.ts.acp.bak")).unwrap(),
+            original
+        );
+        assert!(!dir.path().join(".app.ts.acp.tmp").exists());
+    }
 }
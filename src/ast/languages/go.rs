@@ -4,7 +4,9 @@
 //! @acp:layer parsing
 
 use super::{node_text, LanguageExtractor};
-use crate::ast::{ExtractedSymbol, FunctionCall, Import, Parameter, SymbolKind, Visibility};
+use crate::ast::{
+    ExtractedSymbol, FunctionCall, Import, ImportKind, Parameter, SymbolKind, Visibility,
+};
 use crate::error::Result;
 use tree_sitter::{Language, Node, Tree};
 
@@ -543,6 +545,7 @@ impl GoExtractor {
             is_default: false,
             is_namespace: is_dot_import,
             line: node.start_position().row + 1,
+            kind: ImportKind::Import,
         });
     }
 
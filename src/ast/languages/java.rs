@@ -5,7 +5,8 @@
 
 use super::{node_text, LanguageExtractor};
 use crate::ast::{
-    ExtractedSymbol, FunctionCall, Import, ImportedName, Parameter, SymbolKind, Visibility,
+    ExtractedSymbol, FunctionCall, Import, ImportKind, ImportedName, Parameter, SymbolKind,
+    Visibility,
 };
 use crate::error::Result;
 use tree_sitter::{Language, Node, Tree};
@@ -552,6 +553,7 @@ impl JavaExtractor {
             is_default: false,
             is_namespace: is_wildcard,
             line: node.start_position().row + 1,
+            kind: ImportKind::Import,
         })
     }
 
@@ -609,9 +611,21 @@ impl JavaExtractor {
     }
 
     fn build_method_signature(&self, node: &Node, source: &str, is_constructor: bool) -> String {
-        let modifiers = node
+        // Keyword modifiers only; annotations like @Override are not part of
+        // the signature
+        let modifiers: String = node
             .children(&mut node.walk())
             .find(|c| c.kind() == "modifiers")
+            .map(|n| {
+                n.children(&mut n.walk())
+                    .filter(|m| !m.kind().ends_with("annotation"))
+                    .map(|m| format!("{} ", node_text(&m, source)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let type_params = node
+            .child_by_field_name("type_parameters")
             .map(|n| format!("{} ", node_text(&n, source)))
             .unwrap_or_default();
 
@@ -633,7 +647,16 @@ impl JavaExtractor {
             .map(|n| node_text(&n, source))
             .unwrap_or("()");
 
-        format!("{}{}{}{}", modifiers, return_type, name, params)
+        let throws = node
+            .children(&mut node.walk())
+            .find(|c| c.kind() == "throws")
+            .map(|n| format!(" {}", node_text(&n, source)))
+            .unwrap_or_default();
+
+        format!(
+            "{}{}{}{}{}{}",
+            modifiers, type_params, return_type, name, params, throws
+        )
     }
 
     fn clean_javadoc(comment: &str) -> String {
@@ -3,9 +3,13 @@
 //! @acp:domain cli
 //! @acp:layer parsing
 
-use super::{node_text, LanguageExtractor};
+use super::{
+    binding_signature, declaration_text, node_text, parse_reexport, parse_require_call,
+    LanguageExtractor,
+};
 use crate::ast::{
-    ExtractedSymbol, FunctionCall, Import, ImportedName, Parameter, SymbolKind, Visibility,
+    ExtractedSymbol, FunctionCall, Import, ImportKind, ImportedName, Parameter, SymbolKind,
+    Visibility,
 };
 use crate::error::Result;
 use tree_sitter::{Language, Node, Tree};
@@ -53,7 +57,12 @@ impl LanguageExtractor for JavaScriptExtractor {
     }
 
     fn extract_doc_comment(&self, node: &Node, source: &str) -> Option<String> {
-        if let Some(prev) = node.prev_sibling() {
+        // `export function f` puts the comment before the export statement
+        let anchor = match node.parent() {
+            Some(parent) if parent.kind() == "export_statement" => parent,
+            _ => *node,
+        };
+        if let Some(prev) = anchor.prev_sibling() {
             if prev.kind() == "comment" {
                 let comment = node_text(&prev, source);
                 if comment.starts_with("/**") {
@@ -143,7 +152,7 @@ impl JavaScriptExtractor {
             sym = sym.with_parent(p);
         }
 
-        sym.signature = Some(self.build_function_signature(node, source));
+        sym.signature = Some(declaration_text(node, source));
 
         Some(sym)
     }
@@ -183,6 +192,7 @@ impl JavaScriptExtractor {
                         }
 
                         sym.doc_comment = self.extract_doc_comment(node, source);
+                        sym.signature = Some(binding_signature(node, name, &value, source));
 
                         if let Some(p) = parent {
                             sym = sym.with_parent(p);
@@ -301,6 +311,7 @@ impl JavaScriptExtractor {
         }
 
         sym.doc_comment = self.extract_doc_comment(node, source);
+        sym.signature = Some(declaration_text(node, source));
 
         if let Some(p) = class_name {
             sym = sym.with_parent(p);
@@ -395,10 +406,14 @@ impl JavaScriptExtractor {
     }
 
     fn extract_imports_recursive(&self, node: &Node, source: &str, imports: &mut Vec<Import>) {
-        if node.kind() == "import_statement" {
-            if let Some(import) = self.parse_import(node, source) {
-                imports.push(import);
-            }
+        let import = match node.kind() {
+            "import_statement" => self.parse_import(node, source),
+            "export_statement" => parse_reexport(node, source),
+            "call_expression" => parse_require_call(node, source),
+            _ => None,
+        };
+        if let Some(import) = import {
+            imports.push(import);
         }
 
         let mut cursor = node.walk();
@@ -419,6 +434,7 @@ impl JavaScriptExtractor {
             is_default: false,
             is_namespace: false,
             line: node.start_position().row + 1,
+            kind: ImportKind::Import,
         };
 
         let mut cursor = node.walk();
@@ -538,20 +554,6 @@ impl JavaScriptExtractor {
         })
     }
 
-    fn build_function_signature(&self, node: &Node, source: &str) -> String {
-        let name = node
-            .child_by_field_name("name")
-            .map(|n| node_text(&n, source))
-            .unwrap_or("anonymous");
-
-        let params = node
-            .child_by_field_name("parameters")
-            .map(|n| node_text(&n, source))
-            .unwrap_or("()");
-
-        format!("function {}{}", name, params)
-    }
-
     fn clean_jsdoc(comment: &str) -> String {
         comment
             .trim_start_matches("/**")
@@ -577,6 +579,24 @@ mod tests {
         (tree, source.to_string())
     }
 
+    #[test]
+    fn test_doc_comment_on_exported_function() {
+        let source = r#"
+/** Greets someone */
+export function greet(name) {
+    return name;
+}
+"#;
+        let (tree, src) = parse_js(source);
+        let symbols = JavaScriptExtractor.extract_symbols(&tree, &src).unwrap();
+
+        let greet: Vec<_> = symbols.iter().filter(|s| s.name == "greet").collect();
+        assert!(!greet.is_empty());
+        for symbol in greet {
+            assert_eq!(symbol.doc_comment.as_deref(), Some("Greets someone"));
+        }
+    }
+
     #[test]
     fn test_extract_function() {
         let source = r#"
@@ -593,6 +613,35 @@ function greet(name) {
         assert_eq!(symbols[0].kind, SymbolKind::Function);
     }
 
+    #[test]
+    fn test_extract_require_and_reexports() {
+        let source = r#"
+import fs from 'fs';
+const path = require('path');
+export { parse, format as fmt } from './parser';
+export * from './utils';
+export const local = 1;
+"#;
+        let (tree, src) = parse_js(source);
+        let imports = JavaScriptExtractor.extract_imports(&tree, &src).unwrap();
+        let kinds: Vec<_> = imports
+            .iter()
+            .map(|i| (i.source.as_str(), i.kind))
+            .collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                ("fs", ImportKind::Import),
+                ("path", ImportKind::Require),
+                ("./parser", ImportKind::ReExport),
+                ("./utils", ImportKind::ReExport),
+            ]
+        );
+        assert_eq!(imports[2].names[1].alias.as_deref(), Some("fmt"));
+        assert!(imports[3].is_namespace);
+    }
+
     #[test]
     fn test_extract_class() {
         let source = r#"
@@ -0,0 +1,768 @@
+//! @acp:module "Kotlin Extractor"
+//! @acp:summary "Symbol extraction for Kotlin source files"
+//! @acp:domain cli
+//! @acp:layer parsing
+//!
+//! Kotlin declarations are public unless marked otherwise, so a missing
+//! visibility modifier maps to [`Visibility::Public`]; `private`, `protected`
+//! and `internal` map to their namesakes. `val`/`var` constructor parameters
+//! become fields of their class, and members of a `companion object` are
+//! attached to the enclosing class as static.
+
+use super::{node_text, normalize_signature, LanguageExtractor};
+use crate::ast::{
+    ExtractedSymbol, FunctionCall, Import, ImportKind, ImportedName, Parameter, SymbolKind,
+    Visibility,
+};
+use crate::error::Result;
+use tree_sitter::{Language, Node, Tree};
+
+/// Kotlin language extractor
+pub struct KotlinExtractor;
+
+impl LanguageExtractor for KotlinExtractor {
+    fn language(&self) -> Language {
+        tree_sitter_kotlin_ng::LANGUAGE.into()
+    }
+
+    fn name(&self) -> &'static str {
+        "kotlin"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["kt", "kts"]
+    }
+
+    fn extract_symbols(&self, tree: &Tree, source: &str) -> Result<Vec<ExtractedSymbol>> {
+        let mut symbols = Vec::new();
+        let root = tree.root_node();
+        self.extract_symbols_recursive(&root, source, &mut symbols, None, false);
+        Ok(symbols)
+    }
+
+    fn extract_imports(&self, tree: &Tree, source: &str) -> Result<Vec<Import>> {
+        let mut imports = Vec::new();
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        for child in root.children(&mut cursor) {
+            if child.kind() == "import" {
+                if let Some(import) = self.parse_import(&child, source) {
+                    imports.push(import);
+                }
+            }
+        }
+        Ok(imports)
+    }
+
+    fn extract_calls(
+        &self,
+        tree: &Tree,
+        source: &str,
+        current_function: Option<&str>,
+    ) -> Result<Vec<FunctionCall>> {
+        let mut calls = Vec::new();
+        let root = tree.root_node();
+        self.extract_calls_recursive(&root, source, &mut calls, current_function);
+        Ok(calls)
+    }
+
+    fn extract_doc_comment(&self, node: &Node, source: &str) -> Option<String> {
+        let prev = node.prev_sibling()?;
+        let comment = node_text(&prev, source);
+        if prev.kind() != "block_comment" || !comment.starts_with("/**") {
+            return None;
+        }
+
+        Some(
+            comment
+                .trim_start_matches("/**")
+                .trim_end_matches("*/")
+                .lines()
+                .map(|line| line.trim().trim_start_matches('*').trim())
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+}
+
+impl KotlinExtractor {
+    fn extract_symbols_recursive(
+        &self,
+        node: &Node,
+        source: &str,
+        symbols: &mut Vec<ExtractedSymbol>,
+        parent: Option<&str>,
+        is_static: bool,
+    ) {
+        let sym = match node.kind() {
+            "class_declaration" | "object_declaration" => {
+                self.extract_class(node, source, symbols, parent);
+                return;
+            }
+
+            "companion_object" => {
+                // Companion members belong to the enclosing class
+                if let Some(body) = self.child_of_kind(node, "class_body") {
+                    self.extract_members(&body, source, symbols, parent, true);
+                }
+                return;
+            }
+
+            "function_declaration" => self.extract_function(node, source, parent),
+            "property_declaration" => self.extract_property(node, source, parent),
+            "type_alias" => self.extract_typealias(node, source, parent),
+            _ => {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    self.extract_symbols_recursive(&child, source, symbols, parent, is_static);
+                }
+                return;
+            }
+        };
+
+        if let Some(mut sym) = sym {
+            if is_static {
+                sym = sym.static_fn();
+            }
+            symbols.push(sym);
+        }
+    }
+
+    /// Classes, interfaces, enum classes and objects
+    fn extract_class(
+        &self,
+        node: &Node,
+        source: &str,
+        symbols: &mut Vec<ExtractedSymbol>,
+        parent: Option<&str>,
+    ) {
+        let Some(name_node) = node.child_by_field_name("name") else {
+            return;
+        };
+        let name = node_text(&name_node, source).to_string();
+
+        let kind = if self.child_of_kind(node, "interface").is_some() {
+            SymbolKind::Interface
+        } else if self.has_modifier(node, source, "enum") {
+            SymbolKind::Enum
+        } else {
+            SymbolKind::Class
+        };
+
+        let mut sym = ExtractedSymbol::new(
+            name.clone(),
+            kind,
+            node.start_position().row + 1,
+            node.end_position().row + 1,
+        )
+        .with_columns(node.start_position().column, node.end_position().column);
+
+        sym.visibility = self.extract_visibility(node, source);
+        if matches!(sym.visibility, Visibility::Public) {
+            sym = sym.exported();
+        }
+
+        if let Some(type_params) = self.child_of_kind(node, "type_parameters") {
+            self.extract_generics(&type_params, source, &mut sym);
+        }
+
+        sym.doc_comment = self.extract_doc_comment(node, source);
+        sym.signature = Some(self.signature(node, source));
+
+        if let Some(p) = parent {
+            sym = sym.with_parent(p);
+        }
+
+        symbols.push(sym);
+
+        if let Some(constructor) = self.child_of_kind(node, "primary_constructor") {
+            self.extract_constructor_properties(&constructor, source, symbols, &name);
+        }
+
+        if let Some(body) = self.child_of_kind(node, "enum_class_body") {
+            self.extract_enum_entries(&body, source, symbols, &name);
+            self.extract_members(&body, source, symbols, Some(&name), false);
+        } else if let Some(body) = self.child_of_kind(node, "class_body") {
+            self.extract_members(&body, source, symbols, Some(&name), false);
+        }
+    }
+
+    fn extract_members(
+        &self,
+        body: &Node,
+        source: &str,
+        symbols: &mut Vec<ExtractedSymbol>,
+        parent: Option<&str>,
+        is_static: bool,
+    ) {
+        let mut cursor = body.walk();
+        for child in body.children(&mut cursor) {
+            if child.kind() != "enum_entry" {
+                self.extract_symbols_recursive(&child, source, symbols, parent, is_static);
+            }
+        }
+    }
+
+    fn extract_enum_entries(
+        &self,
+        body: &Node,
+        source: &str,
+        symbols: &mut Vec<ExtractedSymbol>,
+        enum_name: &str,
+    ) {
+        let mut cursor = body.walk();
+        for entry in body.children(&mut cursor) {
+            if entry.kind() != "enum_entry" {
+                continue;
+            }
+            let Some(name_node) = self.child_of_kind(&entry, "identifier") else {
+                continue;
+            };
+
+            let mut sym = ExtractedSymbol::new(
+                node_text(&name_node, source).to_string(),
+                SymbolKind::EnumVariant,
+                entry.start_position().row + 1,
+                entry.end_position().row + 1,
+            )
+            .with_parent(enum_name);
+
+            sym.visibility = Visibility::Public;
+            symbols.push(sym.exported());
+        }
+    }
+
+    /// `class Point(val x: Int)` declares the property `x`
+    fn extract_constructor_properties(
+        &self,
+        constructor: &Node,
+        source: &str,
+        symbols: &mut Vec<ExtractedSymbol>,
+        class_name: &str,
+    ) {
+        let Some(params) = self.child_of_kind(constructor, "class_parameters") else {
+            return;
+        };
+        let mut cursor = params.walk();
+        for param in params.children(&mut cursor) {
+            if param.kind() != "class_parameter"
+                || (self.child_of_kind(&param, "val").is_none()
+                    && self.child_of_kind(&param, "var").is_none())
+            {
+                continue;
+            }
+            let Some(name_node) = self.child_of_kind(&param, "identifier") else {
+                continue;
+            };
+
+            let mut sym = ExtractedSymbol::new(
+                node_text(&name_node, source).to_string(),
+                SymbolKind::Field,
+                param.start_position().row + 1,
+                param.end_position().row + 1,
+            )
+            .with_parent(class_name);
+
+            sym.visibility = self.extract_visibility(&param, source);
+            if matches!(sym.visibility, Visibility::Public) {
+                sym = sym.exported();
+            }
+            sym.type_info = self.type_after_colon(&param, source);
+
+            symbols.push(sym);
+        }
+    }
+
+    fn extract_function(
+        &self,
+        node: &Node,
+        source: &str,
+        parent: Option<&str>,
+    ) -> Option<ExtractedSymbol> {
+        let name_node = node.child_by_field_name("name")?;
+        let name = node_text(&name_node, source).to_string();
+
+        let kind = if parent.is_some() {
+            SymbolKind::Method
+        } else {
+            SymbolKind::Function
+        };
+
+        let mut sym = ExtractedSymbol::new(
+            name,
+            kind,
+            node.start_position().row + 1,
+            node.end_position().row + 1,
+        );
+
+        sym.visibility = self.extract_visibility(node, source);
+        if matches!(sym.visibility, Visibility::Public) {
+            sym = sym.exported();
+        }
+        sym.is_async = self.has_modifier(node, source, "suspend");
+
+        // A type before the name is the receiver of an extension function;
+        // the return type follows the parameter list
+        let mut after_params = false;
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                "type_parameters" => self.extract_generics(&child, source, &mut sym),
+                "function_value_parameters" => {
+                    self.extract_parameters(&child, source, &mut sym);
+                    after_params = true;
+                }
+                "function_body" => break,
+                _ if after_params && child.is_named() && sym.return_type.is_none() => {
+                    sym.return_type = Some(node_text(&child, source).to_string());
+                }
+                _ => {}
+            }
+        }
+
+        sym.doc_comment = self.extract_doc_comment(node, source);
+        sym.signature = Some(self.signature(node, source));
+
+        if let Some(p) = parent {
+            sym = sym.with_parent(p);
+        }
+
+        Some(sym)
+    }
+
+    /// `vararg` sits in a sibling before its parameter, and a default value
+    /// follows it after `=`
+    fn extract_parameters(&self, params: &Node, source: &str, sym: &mut ExtractedSymbol) {
+        let mut is_rest = false;
+        let mut after_equals = false;
+        let mut cursor = params.walk();
+        for child in params.children(&mut cursor) {
+            match child.kind() {
+                "parameter_modifiers" => {
+                    is_rest = node_text(&child, source)
+                        .split_whitespace()
+                        .any(|word| word == "vararg");
+                }
+                "parameter" => {
+                    let name = self
+                        .child_of_kind(&child, "identifier")
+                        .map(|n| node_text(&n, source).to_string())
+                        .unwrap_or_default();
+                    sym.add_parameter(Parameter {
+                        name,
+                        type_info: self.type_after_colon(&child, source),
+                        default_value: None,
+                        is_rest,
+                        is_optional: false,
+                    });
+                    is_rest = false;
+                }
+                "=" => after_equals = true,
+                _ if after_equals && child.is_named() => {
+                    if let Some(param) = sym.parameters.last_mut() {
+                        param.default_value = Some(node_text(&child, source).to_string());
+                        param.is_optional = true;
+                    }
+                    after_equals = false;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn extract_property(
+        &self,
+        node: &Node,
+        source: &str,
+        parent: Option<&str>,
+    ) -> Option<ExtractedSymbol> {
+        let declaration = self.child_of_kind(node, "variable_declaration")?;
+        let name_node = self.child_of_kind(&declaration, "identifier")?;
+        let name = node_text(&name_node, source).to_string();
+
+        let is_val = self.child_of_kind(node, "val").is_some();
+        let kind = match (parent, is_val) {
+            (Some(_), _) => SymbolKind::Field,
+            (None, true) => SymbolKind::Constant,
+            (None, false) => SymbolKind::Variable,
+        };
+
+        let mut sym = ExtractedSymbol::new(
+            name,
+            kind,
+            node.start_position().row + 1,
+            node.end_position().row + 1,
+        );
+
+        sym.visibility = self.extract_visibility(node, source);
+        if matches!(sym.visibility, Visibility::Public) {
+            sym = sym.exported();
+        }
+        sym.type_info = self.type_after_colon(&declaration, source);
+        sym.doc_comment = self.extract_doc_comment(node, source);
+
+        if let Some(p) = parent {
+            sym = sym.with_parent(p);
+        }
+
+        Some(sym)
+    }
+
+    fn extract_typealias(
+        &self,
+        node: &Node,
+        source: &str,
+        parent: Option<&str>,
+    ) -> Option<ExtractedSymbol> {
+        let name_node = node.child_by_field_name("type")?;
+        let name = node_text(&name_node, source).to_string();
+
+        let mut sym = ExtractedSymbol::new(
+            name,
+            SymbolKind::TypeAlias,
+            node.start_position().row + 1,
+            node.end_position().row + 1,
+        );
+
+        sym.visibility = self.extract_visibility(node, source);
+        if matches!(sym.visibility, Visibility::Public) {
+            sym = sym.exported();
+        }
+
+        sym.doc_comment = self.extract_doc_comment(node, source);
+
+        if let Some(p) = parent {
+            sym = sym.with_parent(p);
+        }
+
+        Some(sym)
+    }
+
+    fn child_of_kind<'t>(&self, node: &Node<'t>, kind: &str) -> Option<Node<'t>> {
+        let mut cursor = node.walk();
+        let child = node.children(&mut cursor).find(|c| c.kind() == kind);
+        child
+    }
+
+    /// The first type node after a `:` among `node`'s children
+    fn type_after_colon(&self, node: &Node, source: &str) -> Option<String> {
+        let mut after_colon = false;
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                ":" => after_colon = true,
+                _ if after_colon && child.is_named() => {
+                    return Some(node_text(&child, source).to_string());
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Declaration text up to the body
+    fn signature(&self, node: &Node, source: &str) -> String {
+        let mut cursor = node.walk();
+        let end = node
+            .children(&mut cursor)
+            .find(|c| matches!(c.kind(), "function_body" | "class_body" | "enum_class_body"))
+            .map(|b| b.start_byte())
+            .unwrap_or(node.end_byte());
+        normalize_signature(&source[node.start_byte()..end])
+    }
+
+    fn has_modifier(&self, node: &Node, source: &str, modifier: &str) -> bool {
+        self.child_of_kind(node, "modifiers")
+            .is_some_and(|modifiers| {
+                node_text(&modifiers, source)
+                    .split_whitespace()
+                    .any(|word| word == modifier)
+            })
+    }
+
+    fn extract_visibility(&self, node: &Node, source: &str) -> Visibility {
+        let Some(modifiers) = self.child_of_kind(node, "modifiers") else {
+            return Visibility::Public;
+        };
+
+        let mut cursor = modifiers.walk();
+        for child in modifiers.children(&mut cursor) {
+            if child.kind() != "visibility_modifier" {
+                continue;
+            }
+            match node_text(&child, source) {
+                "private" => return Visibility::Private,
+                "protected" => return Visibility::Protected,
+                "internal" => return Visibility::Internal,
+                _ => return Visibility::Public,
+            }
+        }
+        Visibility::Public
+    }
+
+    fn extract_generics(&self, type_params: &Node, source: &str, sym: &mut ExtractedSymbol) {
+        let mut cursor = type_params.walk();
+        for child in type_params.children(&mut cursor) {
+            if child.kind() == "type_parameter" {
+                if let Some(name) = self.child_of_kind(&child, "identifier") {
+                    sym.add_generic(node_text(&name, source));
+                }
+            }
+        }
+    }
+
+    /// `import a.b.C` imports `C` from `a.b`; `import a.b.*` all of `a.b`
+    fn parse_import(&self, node: &Node, source: &str) -> Option<Import> {
+        let path = self.child_of_kind(node, "qualified_identifier")?;
+        let path = node_text(&path, source);
+        let is_wildcard = self.child_of_kind(node, "*").is_some();
+        let alias = self
+            .child_of_kind(node, "identifier")
+            .map(|a| node_text(&a, source).to_string());
+
+        let (source_path, names) = if is_wildcard {
+            (path.to_string(), Vec::new())
+        } else {
+            let (package, name) = path.rsplit_once('.').unwrap_or(("", path));
+            (
+                package.to_string(),
+                vec![ImportedName {
+                    name: name.to_string(),
+                    alias,
+                }],
+            )
+        };
+
+        Some(Import {
+            source: source_path,
+            names,
+            is_default: false,
+            is_namespace: is_wildcard,
+            line: node.start_position().row + 1,
+            kind: ImportKind::Import,
+        })
+    }
+
+    fn extract_calls_recursive(
+        &self,
+        node: &Node,
+        source: &str,
+        calls: &mut Vec<FunctionCall>,
+        current_function: Option<&str>,
+    ) {
+        if node.kind() == "call_expression" {
+            if let Some(call) = self.parse_call(node, source, current_function) {
+                calls.push(call);
+            }
+        }
+
+        let func_name = if node.kind() == "function_declaration" {
+            node.child_by_field_name("name")
+                .map(|n| node_text(&n, source))
+        } else {
+            None
+        };
+
+        let current = func_name.or(current_function);
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.extract_calls_recursive(&child, source, calls, current);
+        }
+    }
+
+    fn parse_call(
+        &self,
+        node: &Node,
+        source: &str,
+        current_function: Option<&str>,
+    ) -> Option<FunctionCall> {
+        let callee = node.named_child(0)?;
+
+        let (name, receiver) = match callee.kind() {
+            "identifier" => (node_text(&callee, source).to_string(), None),
+            "navigation_expression" => {
+                let count = callee.named_child_count();
+                let name = callee.named_child(count.checked_sub(1)?)?;
+                let target = callee
+                    .named_child(0)
+                    .filter(|_| count > 1)
+                    .map(|t| node_text(&t, source).to_string());
+                (node_text(&name, source).to_string(), target)
+            }
+            _ => return None,
+        };
+
+        Some(FunctionCall {
+            caller: current_function.unwrap_or("<module>").to_string(),
+            callee: name,
+            line: node.start_position().row + 1,
+            is_method: receiver.is_some(),
+            receiver,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_kotlin(source: &str) -> (Tree, String) {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_kotlin_ng::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        (tree, source.to_string())
+    }
+
+    fn find<'a>(symbols: &'a [ExtractedSymbol], name: &str) -> &'a ExtractedSymbol {
+        symbols.iter().find(|s| s.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_extract_class_members() {
+        let source = r#"
+/** Stores users */
+class UserStore<T>(private val name: String, var count: Int = 3) : Store {
+    private val users: List<String> = listOf()
+
+    internal fun reload(id: Int, vararg tags: String = arrayOf()): Boolean {
+        return true
+    }
+
+    protected suspend fun load() = Unit
+
+    companion object {
+        fun shared(): UserStore<Int> = UserStore("x")
+    }
+}
+"#;
+        let (tree, src) = parse_kotlin(source);
+        let symbols = KotlinExtractor.extract_symbols(&tree, &src).unwrap();
+
+        let class = find(&symbols, "UserStore");
+        assert_eq!(class.kind, SymbolKind::Class);
+        assert!(class.exported);
+        assert_eq!(class.generics, vec!["T".to_string()]);
+        assert_eq!(class.doc_comment.as_deref(), Some("Stores users"));
+
+        let name = find(&symbols, "name");
+        assert_eq!(name.kind, SymbolKind::Field);
+        assert_eq!(name.visibility, Visibility::Private);
+        assert_eq!(find(&symbols, "count").type_info.as_deref(), Some("Int"));
+
+        let users = find(&symbols, "users");
+        assert_eq!(users.parent.as_deref(), Some("UserStore"));
+        assert_eq!(users.type_info.as_deref(), Some("List<String>"));
+
+        let reload = find(&symbols, "reload");
+        assert_eq!(reload.kind, SymbolKind::Method);
+        assert_eq!(reload.visibility, Visibility::Internal);
+        assert!(!reload.exported);
+        assert_eq!(reload.return_type.as_deref(), Some("Boolean"));
+        assert_eq!(reload.parameters.len(), 2);
+        assert_eq!(reload.parameters[0].type_info.as_deref(), Some("Int"));
+        assert!(reload.parameters[1].is_rest);
+        assert_eq!(
+            reload.parameters[1].default_value.as_deref(),
+            Some("arrayOf()")
+        );
+        assert_eq!(
+            reload.signature.as_deref(),
+            Some("internal fun reload(id: Int, vararg tags: String = arrayOf()): Boolean")
+        );
+
+        let load = find(&symbols, "load");
+        assert_eq!(load.visibility, Visibility::Protected);
+        assert!(load.is_async);
+
+        let shared = find(&symbols, "shared");
+        assert_eq!(shared.parent.as_deref(), Some("UserStore"));
+        assert!(shared.is_static && shared.exported);
+        assert_eq!(shared.return_type.as_deref(), Some("UserStore<Int>"));
+    }
+
+    #[test]
+    fn test_extract_interface_enum_and_top_level() {
+        let source = r#"
+interface Store {
+    fun load(): List<String>
+}
+
+enum class Status { ACTIVE, INACTIVE }
+
+object Registry {
+    fun get() {}
+}
+
+fun <K> helper(key: K) {}
+private fun String.shout(): String = uppercase()
+const val ANSWER = 42
+var counter = 0
+typealias Names = List<String>
+"#;
+        let (tree, src) = parse_kotlin(source);
+        let symbols = KotlinExtractor.extract_symbols(&tree, &src).unwrap();
+
+        assert_eq!(find(&symbols, "Store").kind, SymbolKind::Interface);
+        assert_eq!(find(&symbols, "load").parent.as_deref(), Some("Store"));
+
+        assert_eq!(find(&symbols, "Status").kind, SymbolKind::Enum);
+        for entry in ["ACTIVE", "INACTIVE"] {
+            let sym = find(&symbols, entry);
+            assert_eq!(sym.kind, SymbolKind::EnumVariant);
+            assert_eq!(sym.parent.as_deref(), Some("Status"));
+        }
+
+        assert_eq!(find(&symbols, "Registry").kind, SymbolKind::Class);
+        assert_eq!(find(&symbols, "get").parent.as_deref(), Some("Registry"));
+
+        let helper = find(&symbols, "helper");
+        assert_eq!(helper.kind, SymbolKind::Function);
+        assert_eq!(helper.generics, vec!["K".to_string()]);
+
+        // The receiver type is not the return type
+        let shout = find(&symbols, "shout");
+        assert_eq!(shout.visibility, Visibility::Private);
+        assert_eq!(shout.return_type.as_deref(), Some("String"));
+
+        assert_eq!(find(&symbols, "ANSWER").kind, SymbolKind::Constant);
+        assert_eq!(find(&symbols, "counter").kind, SymbolKind::Variable);
+        assert_eq!(find(&symbols, "Names").kind, SymbolKind::TypeAlias);
+    }
+
+    #[test]
+    fn test_extract_imports_and_calls() {
+        let source = r#"
+package com.example.app
+
+import kotlin.collections.List
+import com.example.util.*
+import com.example.Foo as Bar
+
+fun reload(id: Int) {
+    fetch(id)
+    this.cache.clear()
+}
+"#;
+        let (tree, src) = parse_kotlin(source);
+        let imports = KotlinExtractor.extract_imports(&tree, &src).unwrap();
+        let sources: Vec<_> = imports.iter().map(|i| i.source.as_str()).collect();
+        assert_eq!(
+            sources,
+            vec!["kotlin.collections", "com.example.util", "com.example"]
+        );
+        assert!(imports[1].is_namespace);
+        assert_eq!(imports[2].names[0].name, "Foo");
+        assert_eq!(imports[2].names[0].alias.as_deref(), Some("Bar"));
+
+        let calls = KotlinExtractor.extract_calls(&tree, &src, None).unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].callee, "fetch");
+        assert_eq!(calls[0].caller, "reload");
+        assert_eq!(calls[1].callee, "clear");
+        assert_eq!(calls[1].receiver.as_deref(), Some("this.cache"));
+    }
+}
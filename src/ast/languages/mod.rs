@@ -6,11 +6,14 @@
 pub mod go;
 pub mod java;
 pub mod javascript;
+pub mod kotlin;
 pub mod python;
+pub mod ruby;
 pub mod rust;
+pub mod swift;
 pub mod typescript;
 
-use super::{ExtractedSymbol, FunctionCall, Import};
+use super::{ExtractedSymbol, FunctionCall, Import, ImportKind, ImportedName};
 use crate::error::Result;
 use tree_sitter::{Language, Node, Tree};
 
@@ -48,6 +51,118 @@ pub fn node_text<'a>(node: &Node, source: &'a str) -> &'a str {
     &source[node.byte_range()]
 }
 
+/// Declaration text of a node up to its `body`, normalized
+///
+/// Used as the symbol signature: everything before the body, including
+/// modifiers, generics and return type.
+pub fn declaration_text(node: &Node, source: &str) -> String {
+    let end = node
+        .child_by_field_name("body")
+        .map(|b| b.start_byte())
+        .unwrap_or(node.end_byte());
+    normalize_signature(&source[node.start_byte()..end])
+}
+
+/// Signature of a function bound to a variable (`const name = (a, b) => …`)
+pub fn binding_signature(declaration: &Node, name: &str, value: &Node, source: &str) -> String {
+    let keyword = node_text(declaration, source)
+        .split_whitespace()
+        .next()
+        .unwrap_or("const");
+    format!("{} {} = {}", keyword, name, declaration_text(value, source))
+}
+
+/// Collapse whitespace in a signature and drop any body-opening token
+///
+/// Line breaks inside parameter lists become single spaces, padding and
+/// trailing commas inside parentheses are removed, and a trailing `{`,
+/// `=>`, `:` or `;` is stripped.
+pub fn normalize_signature(signature: &str) -> String {
+    let collapsed = signature
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace("( ", "(")
+        .replace(" )", ")")
+        .replace(",)", ")");
+
+    let mut signature = collapsed.as_str();
+    while let Some(rest) = ["=>", "{", ":", ";"]
+        .iter()
+        .find_map(|token| signature.strip_suffix(token))
+    {
+        signature = rest.trim_end();
+    }
+    signature.to_string()
+}
+
+/// Parse a CommonJS `require("x")` call (JavaScript/TypeScript)
+///
+/// Returns `None` for calls to anything other than `require` or when the
+/// argument is not a string literal.
+pub fn parse_require_call(node: &Node, source: &str) -> Option<Import> {
+    let function = node.child_by_field_name("function")?;
+    if function.kind() != "identifier" || node_text(&function, source) != "require" {
+        return None;
+    }
+    let arguments = node.child_by_field_name("arguments")?;
+    let mut cursor = arguments.walk();
+    let arg = arguments
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "string")?;
+
+    Some(Import {
+        source: node_text(&arg, source)
+            .trim_matches(|c| c == '"' || c == '\'')
+            .to_string(),
+        names: Vec::new(),
+        is_default: false,
+        is_namespace: false,
+        line: node.start_position().row + 1,
+        kind: ImportKind::Require,
+    })
+}
+
+/// Parse a re-export such as `export { a } from "./b"` or `export * from "./b"`
+///
+/// Plain `export` statements without a `from` clause return `None`.
+pub fn parse_reexport(node: &Node, source: &str) -> Option<Import> {
+    let source_node = node.child_by_field_name("source")?;
+    let mut import = Import {
+        source: node_text(&source_node, source)
+            .trim_matches(|c| c == '"' || c == '\'')
+            .to_string(),
+        names: Vec::new(),
+        is_default: false,
+        is_namespace: false,
+        line: node.start_position().row + 1,
+        kind: ImportKind::ReExport,
+    };
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "*" => import.is_namespace = true,
+            "export_clause" => {
+                let mut inner = child.walk();
+                for spec in child.named_children(&mut inner) {
+                    if let Some(name) = spec.child_by_field_name("name") {
+                        import.names.push(ImportedName {
+                            name: node_text(&name, source).to_string(),
+                            alias: spec
+                                .child_by_field_name("alias")
+                                .map(|a| node_text(&a, source).to_string()),
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(import)
+}
+
 /// Get an extractor for a language name
 pub fn get_extractor(language: &str) -> Option<Box<dyn LanguageExtractor>> {
     match language.to_lowercase().as_str() {
@@ -57,6 +172,9 @@ pub fn get_extractor(language: &str) -> Option<Box<dyn LanguageExtractor>> {
         "python" | "py" => Some(Box::new(python::PythonExtractor)),
         "go" => Some(Box::new(go::GoExtractor)),
         "java" => Some(Box::new(java::JavaExtractor)),
+        "ruby" | "rb" => Some(Box::new(ruby::RubyExtractor)),
+        "swift" => Some(Box::new(swift::SwiftExtractor)),
+        "kotlin" | "kt" => Some(Box::new(kotlin::KotlinExtractor)),
         _ => None,
     }
 }
@@ -70,6 +188,9 @@ pub fn extractor_for_extension(ext: &str) -> Option<Box<dyn LanguageExtractor>>
         "py" | "pyi" => Some(Box::new(python::PythonExtractor)),
         "go" => Some(Box::new(go::GoExtractor)),
         "java" => Some(Box::new(java::JavaExtractor)),
+        "rb" | "rake" => Some(Box::new(ruby::RubyExtractor)),
+        "swift" => Some(Box::new(swift::SwiftExtractor)),
+        "kt" | "kts" => Some(Box::new(kotlin::KotlinExtractor)),
         _ => None,
     }
 }
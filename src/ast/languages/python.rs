@@ -3,9 +3,10 @@
 //! @acp:domain cli
 //! @acp:layer parsing
 
-use super::{node_text, LanguageExtractor};
+use super::{declaration_text, node_text, LanguageExtractor};
 use crate::ast::{
-    ExtractedSymbol, FunctionCall, Import, ImportedName, Parameter, SymbolKind, Visibility,
+    ExtractedSymbol, FunctionCall, Import, ImportKind, ImportedName, Parameter, SymbolKind,
+    Visibility,
 };
 use crate::error::Result;
 use tree_sitter::{Language, Node, Tree};
@@ -199,7 +200,7 @@ impl PythonExtractor {
             sym.kind = SymbolKind::Method;
         }
 
-        sym.signature = Some(self.build_function_signature(node, source));
+        sym.signature = Some(declaration_text(node, source));
 
         // For non-decorated functions, definition_start_line equals start_line
         if sym.definition_start_line.is_none() {
@@ -308,8 +309,10 @@ impl PythonExtractor {
                     }
                 }
                 "typed_parameter" => {
+                    // typed_parameter has no name field; the name is its first child
                     let name = child
-                        .child_by_field_name("name")
+                        .named_child(0)
+                        .filter(|n| n.kind() == "identifier")
                         .map(|n| node_text(&n, source).to_string())
                         .unwrap_or_default();
 
@@ -396,6 +399,7 @@ impl PythonExtractor {
             is_default: false,
             is_namespace: false,
             line: node.start_position().row + 1,
+            kind: ImportKind::Import,
         };
 
         let mut cursor = node.walk();
@@ -437,6 +441,7 @@ impl PythonExtractor {
             is_default: false,
             is_namespace: false,
             line: node.start_position().row + 1,
+            kind: ImportKind::From,
         };
 
         let mut cursor = node.walk();
@@ -534,31 +539,6 @@ impl PythonExtractor {
         })
     }
 
-    fn build_function_signature(&self, node: &Node, source: &str) -> String {
-        let async_kw = if node_text(node, source).starts_with("async") {
-            "async "
-        } else {
-            ""
-        };
-
-        let name = node
-            .child_by_field_name("name")
-            .map(|n| node_text(&n, source))
-            .unwrap_or("unknown");
-
-        let params = node
-            .child_by_field_name("parameters")
-            .map(|n| node_text(&n, source))
-            .unwrap_or("()");
-
-        let return_type = node
-            .child_by_field_name("return_type")
-            .map(|n| format!(" {}", node_text(&n, source)))
-            .unwrap_or_default();
-
-        format!("{}def {}{}{}", async_kw, name, params, return_type)
-    }
-
     fn clean_docstring(text: &str) -> String {
         // Remove quotes
         let text = text
@@ -599,6 +579,9 @@ def greet(name: str) -> str:
         assert_eq!(symbols.len(), 1);
         assert_eq!(symbols[0].name, "greet");
         assert_eq!(symbols[0].kind, SymbolKind::Function);
+        assert_eq!(symbols[0].parameters[0].name, "name");
+        assert_eq!(symbols[0].parameters[0].type_info.as_deref(), Some("str"));
+        assert_eq!(symbols[0].return_type.as_deref(), Some("str"));
     }
 
     #[test]
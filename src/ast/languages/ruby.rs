@@ -0,0 +1,628 @@
+//! @acp:module "Ruby Extractor"
+//! @acp:summary "Symbol extraction for Ruby source files"
+//! @acp:domain cli
+//! @acp:layer parsing
+
+use super::{node_text, LanguageExtractor};
+use crate::ast::{
+    ExtractedSymbol, FunctionCall, Import, ImportKind, Parameter, SymbolKind, Visibility,
+};
+use crate::error::Result;
+use tree_sitter::{Language, Node, Tree};
+
+/// Ruby language extractor
+pub struct RubyExtractor;
+
+impl LanguageExtractor for RubyExtractor {
+    fn language(&self) -> Language {
+        tree_sitter_ruby::LANGUAGE.into()
+    }
+
+    fn name(&self) -> &'static str {
+        "ruby"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["rb", "rake"]
+    }
+
+    fn extract_symbols(&self, tree: &Tree, source: &str) -> Result<Vec<ExtractedSymbol>> {
+        let mut symbols = Vec::new();
+        let root = tree.root_node();
+        self.extract_body(&root, source, &mut symbols, None);
+        Ok(symbols)
+    }
+
+    fn extract_imports(&self, tree: &Tree, source: &str) -> Result<Vec<Import>> {
+        let mut imports = Vec::new();
+        let root = tree.root_node();
+        self.extract_imports_recursive(&root, source, &mut imports);
+        Ok(imports)
+    }
+
+    fn extract_calls(
+        &self,
+        tree: &Tree,
+        source: &str,
+        current_function: Option<&str>,
+    ) -> Result<Vec<FunctionCall>> {
+        let mut calls = Vec::new();
+        let root = tree.root_node();
+        self.extract_calls_recursive(&root, source, &mut calls, current_function);
+        Ok(calls)
+    }
+
+    fn extract_doc_comment(&self, node: &Node, source: &str) -> Option<String> {
+        // Comments are not always siblings of the definition in the tree
+        // (a comment before a nested class belongs to the enclosing module),
+        // so read the `#` lines directly above the definition instead
+        let lines: Vec<&str> = source.lines().collect();
+        let mut row = node.start_position().row;
+        let mut doc_lines = Vec::new();
+
+        while row > 0 {
+            row -= 1;
+            let line = lines.get(row).map(|l| l.trim()).unwrap_or_default();
+            match line.strip_prefix('#') {
+                Some(text) if !text.starts_with('!') => {
+                    doc_lines.push(text.strip_prefix(' ').unwrap_or(text));
+                }
+                _ => break,
+            }
+        }
+
+        if doc_lines.is_empty() {
+            None
+        } else {
+            doc_lines.reverse();
+            Some(doc_lines.join("\n"))
+        }
+    }
+}
+
+impl RubyExtractor {
+    /// Extract definitions from a program or class/module body
+    ///
+    /// A bare `private`/`protected`/`public` applies to the instance methods
+    /// that follow it, so the current section is tracked while walking.
+    fn extract_body(
+        &self,
+        body: &Node,
+        source: &str,
+        symbols: &mut Vec<ExtractedSymbol>,
+        parent: Option<&str>,
+    ) {
+        let first = symbols.len();
+        let mut section = Visibility::Public;
+
+        let mut cursor = body.walk();
+        for child in body.named_children(&mut cursor) {
+            match child.kind() {
+                "class" | "module" => {
+                    self.extract_namespace(&child, source, symbols, parent);
+                }
+                "method" => {
+                    if let Some(sym) = self.extract_method(&child, source, parent, section) {
+                        symbols.push(sym);
+                    }
+                }
+                "singleton_method" => {
+                    // Section keywords don't apply to `def self.x`
+                    if let Some(sym) =
+                        self.extract_method(&child, source, parent, Visibility::Public)
+                    {
+                        symbols.push(sym);
+                    }
+                }
+                "identifier" => {
+                    if let Some(visibility) = Self::visibility_keyword(node_text(&child, source)) {
+                        section = visibility;
+                    }
+                }
+                "call" => {
+                    self.extract_visibility_call(&child, source, symbols, parent, first);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn extract_namespace(
+        &self,
+        node: &Node,
+        source: &str,
+        symbols: &mut Vec<ExtractedSymbol>,
+        parent: Option<&str>,
+    ) {
+        let Some(name_node) = node.child_by_field_name("name") else {
+            return;
+        };
+        let name = node_text(&name_node, source).to_string();
+        let kind = if node.kind() == "module" {
+            SymbolKind::Module
+        } else {
+            SymbolKind::Class
+        };
+
+        let mut sym = ExtractedSymbol::new(
+            name.clone(),
+            kind,
+            node.start_position().row + 1,
+            node.end_position().row + 1,
+        )
+        .with_columns(node.start_position().column, node.end_position().column)
+        .exported();
+
+        if let Some(superclass) = node.child_by_field_name("superclass") {
+            sym.type_info = Some(
+                node_text(&superclass, source)
+                    .trim_start_matches('<')
+                    .trim()
+                    .to_string(),
+            );
+        }
+
+        sym.doc_comment = self.extract_doc_comment(node, source);
+        sym.definition_start_line = Some(node.start_position().row + 1);
+
+        let path = match parent {
+            Some(p) => {
+                sym = sym.with_parent(p);
+                format!("{}::{}", p, name)
+            }
+            None => name,
+        };
+        symbols.push(sym);
+
+        if let Some(body) = node.child_by_field_name("body") {
+            self.extract_body(&body, source, symbols, Some(&path));
+        }
+    }
+
+    fn extract_method(
+        &self,
+        node: &Node,
+        source: &str,
+        parent: Option<&str>,
+        visibility: Visibility,
+    ) -> Option<ExtractedSymbol> {
+        let name_node = node.child_by_field_name("name")?;
+        let name = node_text(&name_node, source).to_string();
+
+        let mut sym = ExtractedSymbol::new(
+            name,
+            SymbolKind::Function,
+            node.start_position().row + 1,
+            node.end_position().row + 1,
+        )
+        .with_columns(node.start_position().column, node.end_position().column);
+
+        Self::set_visibility(&mut sym, visibility);
+
+        if node.kind() == "singleton_method" {
+            sym = sym.static_fn();
+        }
+
+        if let Some(params) = node.child_by_field_name("parameters") {
+            self.extract_parameters(&params, source, &mut sym);
+        }
+
+        sym.doc_comment = self.extract_doc_comment(node, source);
+
+        if let Some(p) = parent {
+            sym = sym.with_parent(p);
+            sym.kind = SymbolKind::Method;
+        }
+
+        sym.signature = Some(self.build_method_signature(node, source));
+        sym.definition_start_line = Some(node.start_position().row + 1);
+
+        Some(sym)
+    }
+
+    /// Handle `private def x ... end` and `private :x, :y`
+    ///
+    /// The symbol form is retroactive, so it updates methods of the same body
+    /// (those pushed since `first`) that were already extracted.
+    fn extract_visibility_call(
+        &self,
+        node: &Node,
+        source: &str,
+        symbols: &mut Vec<ExtractedSymbol>,
+        parent: Option<&str>,
+        first: usize,
+    ) {
+        if node.child_by_field_name("receiver").is_some() {
+            return;
+        }
+        let Some(method) = node.child_by_field_name("method") else {
+            return;
+        };
+        let (visibility, singleton) = match node_text(&method, source) {
+            "private_class_method" => (Visibility::Private, true),
+            "public_class_method" => (Visibility::Public, true),
+            keyword => match Self::visibility_keyword(keyword) {
+                Some(visibility) => (visibility, false),
+                None => return,
+            },
+        };
+        let Some(arguments) = node.child_by_field_name("arguments") else {
+            return;
+        };
+
+        let mut cursor = arguments.walk();
+        for arg in arguments.named_children(&mut cursor) {
+            match arg.kind() {
+                "method" | "singleton_method" => {
+                    if let Some(sym) = self.extract_method(&arg, source, parent, visibility) {
+                        symbols.push(sym);
+                    }
+                }
+                "simple_symbol" => {
+                    let name = node_text(&arg, source).trim_start_matches(':');
+                    for sym in symbols[first..].iter_mut().filter(|s| {
+                        s.name == name
+                            && s.is_static == singleton
+                            && s.parent.as_deref() == parent
+                            && matches!(s.kind, SymbolKind::Method | SymbolKind::Function)
+                    }) {
+                        Self::set_visibility(sym, visibility);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn visibility_keyword(text: &str) -> Option<Visibility> {
+        match text {
+            "public" => Some(Visibility::Public),
+            "private" => Some(Visibility::Private),
+            "protected" => Some(Visibility::Protected),
+            _ => None,
+        }
+    }
+
+    fn set_visibility(sym: &mut ExtractedSymbol, visibility: Visibility) {
+        sym.exported = visibility == Visibility::Public;
+        sym.visibility = visibility;
+    }
+
+    fn extract_parameters(&self, params: &Node, source: &str, sym: &mut ExtractedSymbol) {
+        let field = |node: &Node, name: &str| {
+            node.child_by_field_name(name)
+                .map(|n| node_text(&n, source).to_string())
+        };
+
+        let mut cursor = params.walk();
+        for child in params.named_children(&mut cursor) {
+            let (name, default_value, is_rest, is_optional) = match child.kind() {
+                "identifier" => (
+                    Some(node_text(&child, source).to_string()),
+                    None,
+                    false,
+                    false,
+                ),
+                "optional_parameter" => {
+                    (field(&child, "name"), field(&child, "value"), false, true)
+                }
+                "keyword_parameter" => {
+                    let value = field(&child, "value");
+                    let optional = value.is_some();
+                    (field(&child, "name"), value, false, optional)
+                }
+                "splat_parameter" => (field(&child, "name"), None, true, true),
+                "hash_splat_parameter" | "block_parameter" => {
+                    (field(&child, "name"), None, false, true)
+                }
+                _ => continue,
+            };
+
+            // Anonymous splats (`*`, `**`, `&`) have no name to report
+            if let Some(name) = name {
+                sym.add_parameter(Parameter {
+                    name,
+                    type_info: None,
+                    default_value,
+                    is_rest,
+                    is_optional,
+                });
+            }
+        }
+    }
+
+    fn extract_imports_recursive(&self, node: &Node, source: &str, imports: &mut Vec<Import>) {
+        if node.kind() == "call" {
+            if let Some(import) = self.parse_require(node, source) {
+                imports.push(import);
+            }
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.extract_imports_recursive(&child, source, imports);
+        }
+    }
+
+    /// Parse `require "x"` or `require_relative "x"`
+    fn parse_require(&self, node: &Node, source: &str) -> Option<Import> {
+        if node.child_by_field_name("receiver").is_some() {
+            return None;
+        }
+        let method = node.child_by_field_name("method")?;
+        let relative = match node_text(&method, source) {
+            "require" => false,
+            "require_relative" => true,
+            _ => return None,
+        };
+
+        let arguments = node.child_by_field_name("arguments")?;
+        let mut cursor = arguments.walk();
+        let arg = arguments
+            .named_children(&mut cursor)
+            .find(|c| c.kind() == "string")?;
+        let path = node_text(&arg, source).trim_matches(|c| c == '"' || c == '\'');
+
+        // Mark relative requires the same way as relative JS/Python imports
+        let source = if relative && !path.starts_with('.') {
+            format!("./{}", path)
+        } else {
+            path.to_string()
+        };
+
+        Some(Import {
+            source,
+            names: Vec::new(),
+            is_default: false,
+            is_namespace: false,
+            line: node.start_position().row + 1,
+            kind: ImportKind::Require,
+        })
+    }
+
+    fn extract_calls_recursive(
+        &self,
+        node: &Node,
+        source: &str,
+        calls: &mut Vec<FunctionCall>,
+        current_function: Option<&str>,
+    ) {
+        if node.kind() == "call" {
+            if let Some(call) = self.parse_call(node, source, current_function) {
+                calls.push(call);
+            }
+        }
+
+        let func_name = if matches!(node.kind(), "method" | "singleton_method") {
+            node.child_by_field_name("name")
+                .map(|n| node_text(&n, source))
+        } else {
+            None
+        };
+
+        let current = func_name
+            .map(String::from)
+            .or_else(|| current_function.map(String::from));
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.extract_calls_recursive(&child, source, calls, current.as_deref());
+        }
+    }
+
+    fn parse_call(
+        &self,
+        node: &Node,
+        source: &str,
+        current_function: Option<&str>,
+    ) -> Option<FunctionCall> {
+        let method = node.child_by_field_name("method")?;
+        let callee = node_text(&method, source).to_string();
+        let receiver = node
+            .child_by_field_name("receiver")
+            .map(|n| node_text(&n, source).to_string());
+
+        // Requires and visibility modifiers are declarations, not calls
+        if receiver.is_none()
+            && (matches!(callee.as_str(), "require" | "require_relative")
+                || Self::visibility_keyword(&callee).is_some())
+        {
+            return None;
+        }
+
+        Some(FunctionCall {
+            caller: current_function.unwrap_or("<main>").to_string(),
+            callee,
+            line: node.start_position().row + 1,
+            is_method: receiver.is_some(),
+            receiver,
+        })
+    }
+
+    fn build_method_signature(&self, node: &Node, source: &str) -> String {
+        let name = node
+            .child_by_field_name("name")
+            .map(|n| node_text(&n, source))
+            .unwrap_or("unknown");
+
+        let object = node
+            .child_by_field_name("object")
+            .map(|n| format!("{}.", node_text(&n, source)))
+            .unwrap_or_default();
+
+        // Parentheses are optional around Ruby parameters
+        let params = node
+            .child_by_field_name("parameters")
+            .map(|n| node_text(&n, source))
+            .map(|p| {
+                if p.starts_with('(') {
+                    p.to_string()
+                } else {
+                    format!("({})", p)
+                }
+            })
+            .unwrap_or_default();
+
+        format!("def {}{}{}", object, name, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_rb(source: &str) -> (Tree, String) {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_ruby::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        (tree, source.to_string())
+    }
+
+    fn find<'a>(symbols: &'a [ExtractedSymbol], name: &str) -> &'a ExtractedSymbol {
+        symbols.iter().find(|s| s.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_extract_classes_and_modules() {
+        let source = r#"
+module Billing
+  # Creates invoices for customers
+  class InvoiceService < BaseService
+    def self.build(customer, items = [], *rest, currency:, **opts, &block)
+      new(customer)
+    end
+
+    def paid?
+      true
+    end
+  end
+end
+
+def helper
+end
+"#;
+        let (tree, src) = parse_rb(source);
+        let symbols = RubyExtractor.extract_symbols(&tree, &src).unwrap();
+
+        let module = find(&symbols, "Billing");
+        assert_eq!(module.kind, SymbolKind::Module);
+
+        let class = find(&symbols, "InvoiceService");
+        assert_eq!(class.kind, SymbolKind::Class);
+        assert_eq!(class.parent.as_deref(), Some("Billing"));
+        assert_eq!(class.type_info.as_deref(), Some("BaseService"));
+        assert_eq!(
+            class.doc_comment.as_deref(),
+            Some("Creates invoices for customers")
+        );
+
+        let build = find(&symbols, "build");
+        assert_eq!(build.kind, SymbolKind::Method);
+        assert!(build.is_static);
+        assert_eq!(build.parent.as_deref(), Some("Billing::InvoiceService"));
+        assert_eq!(
+            build.signature.as_deref(),
+            Some("def self.build(customer, items = [], *rest, currency:, **opts, &block)")
+        );
+        let params: Vec<_> = build.parameters.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(
+            params,
+            ["customer", "items", "rest", "currency", "opts", "block"]
+        );
+        assert!(!build.parameters[0].is_optional);
+        assert!(build.parameters[1].is_optional);
+        assert!(build.parameters[2].is_rest);
+        assert!(!build.parameters[3].is_optional);
+
+        assert_eq!(find(&symbols, "paid?").kind, SymbolKind::Method);
+        assert_eq!(find(&symbols, "helper").kind, SymbolKind::Function);
+    }
+
+    #[test]
+    fn test_visibility_sections() {
+        let source = r#"
+class Account
+  def balance
+  end
+
+  def reset!
+  end
+  private :reset!
+
+  protected
+
+  def compare(other)
+  end
+
+  private
+
+  def secret
+  end
+
+  def self.factory
+  end
+
+  public
+
+  def open
+  end
+
+  private def inline_private
+  end
+end
+"#;
+        let (tree, src) = parse_rb(source);
+        let symbols = RubyExtractor.extract_symbols(&tree, &src).unwrap();
+
+        let visibility = |name: &str| {
+            let sym = find(&symbols, name);
+            (sym.visibility, sym.exported)
+        };
+        assert_eq!(visibility("balance"), (Visibility::Public, true));
+        assert_eq!(visibility("reset!"), (Visibility::Private, false));
+        assert_eq!(visibility("compare"), (Visibility::Protected, false));
+        assert_eq!(visibility("secret"), (Visibility::Private, false));
+        assert_eq!(visibility("factory"), (Visibility::Public, true));
+        assert_eq!(visibility("open"), (Visibility::Public, true));
+        assert_eq!(visibility("inline_private"), (Visibility::Private, false));
+    }
+
+    #[test]
+    fn test_extract_requires() {
+        let source = r#"
+require "json"
+require_relative 'support/helper'
+require_relative "../lib/thing"
+"#;
+        let (tree, src) = parse_rb(source);
+        let imports = RubyExtractor.extract_imports(&tree, &src).unwrap();
+
+        let sources: Vec<_> = imports.iter().map(|i| i.source.as_str()).collect();
+        assert_eq!(sources, ["json", "./support/helper", "../lib/thing"]);
+        assert!(imports.iter().all(|i| i.kind == ImportKind::Require));
+    }
+
+    #[test]
+    fn test_extract_calls() {
+        let source = r#"
+class Report
+  def render
+    rows = fetch_rows
+    format_rows(rows)
+    rows.each { |r| puts r }
+  end
+end
+"#;
+        let (tree, src) = parse_rb(source);
+        let calls = RubyExtractor.extract_calls(&tree, &src, None).unwrap();
+
+        assert!(calls
+            .iter()
+            .any(|c| c.callee == "format_rows" && c.caller == "render" && !c.is_method));
+        assert!(calls
+            .iter()
+            .any(|c| c.callee == "each" && c.receiver.as_deref() == Some("rows")));
+    }
+}
@@ -3,9 +3,10 @@
 //! @acp:domain cli
 //! @acp:layer parsing
 
-use super::{node_text, LanguageExtractor};
+use super::{declaration_text, node_text, LanguageExtractor};
 use crate::ast::{
-    ExtractedSymbol, FunctionCall, Import, ImportedName, Parameter, SymbolKind, Visibility,
+    ExtractedSymbol, FunctionCall, Import, ImportKind, ImportedName, Parameter, SymbolKind,
+    Visibility,
 };
 use crate::error::Result;
 use tree_sitter::{Language, Node, Tree};
@@ -233,7 +234,7 @@ impl RustExtractor {
             sym = sym.with_parent(p);
         }
 
-        sym.signature = Some(self.build_function_signature(node, source));
+        sym.signature = Some(declaration_text(node, source));
 
         // Set definition_start_line (before attributes/doc comments)
         sym.definition_start_line = Some(self.find_definition_start_line(node, source));
@@ -452,6 +453,7 @@ impl RustExtractor {
                     }
 
                     sym.doc_comment = self.extract_doc_comment(&child, source);
+                    sym.signature = Some(declaration_text(&child, source));
 
                     if let Some(p) = trait_name {
                         sym = sym.with_parent(p);
@@ -753,6 +755,7 @@ impl RustExtractor {
             is_default: false,
             is_namespace: false,
             line: node.start_position().row + 1,
+            kind: ImportKind::Import,
         };
 
         self.parse_use_path(&argument, source, &mut import, String::new());
@@ -886,45 +889,6 @@ impl RustExtractor {
         })
     }
 
-    fn build_function_signature(&self, node: &Node, source: &str) -> String {
-        let vis = node
-            .children(&mut node.walk())
-            .find(|c| c.kind() == "visibility_modifier")
-            .map(|n| format!("{} ", node_text(&n, source)))
-            .unwrap_or_default();
-
-        let async_kw = if node_text(node, source).contains("async fn") {
-            "async "
-        } else {
-            ""
-        };
-
-        let name = node
-            .child_by_field_name("name")
-            .map(|n| node_text(&n, source))
-            .unwrap_or("unknown");
-
-        let generics = node
-            .child_by_field_name("type_parameters")
-            .map(|n| node_text(&n, source))
-            .unwrap_or("");
-
-        let params = node
-            .child_by_field_name("parameters")
-            .map(|n| node_text(&n, source))
-            .unwrap_or("()");
-
-        let return_type = node
-            .child_by_field_name("return_type")
-            .map(|n| format!(" {}", node_text(&n, source)))
-            .unwrap_or_default();
-
-        format!(
-            "{}{}fn {}{}{}{}",
-            vis, async_kw, name, generics, params, return_type
-        )
-    }
-
     fn clean_block_doc(comment: &str) -> String {
         comment
             .trim_start_matches("/**")
@@ -0,0 +1,771 @@
+//! @acp:module "Swift Extractor"
+//! @acp:summary "Symbol extraction for Swift source files"
+//! @acp:domain cli
+//! @acp:layer parsing
+//!
+//! Swift's access levels map onto [`Visibility`]: `public` and `open` are
+//! exported, `private` and `fileprivate` are private, and `internal` (the
+//! default) stays internal. Members of an `extension` are attached to the
+//! extended type rather than producing a symbol for the extension itself.
+
+use super::{declaration_text, node_text, LanguageExtractor};
+use crate::ast::{
+    ExtractedSymbol, FunctionCall, Import, ImportKind, Parameter, SymbolKind, Visibility,
+};
+use crate::error::Result;
+use tree_sitter::{Language, Node, Tree};
+
+/// Swift language extractor
+pub struct SwiftExtractor;
+
+impl LanguageExtractor for SwiftExtractor {
+    fn language(&self) -> Language {
+        tree_sitter_swift::LANGUAGE.into()
+    }
+
+    fn name(&self) -> &'static str {
+        "swift"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["swift"]
+    }
+
+    fn extract_symbols(&self, tree: &Tree, source: &str) -> Result<Vec<ExtractedSymbol>> {
+        let mut symbols = Vec::new();
+        let root = tree.root_node();
+        self.extract_symbols_recursive(&root, source, &mut symbols, None);
+        Ok(symbols)
+    }
+
+    fn extract_imports(&self, tree: &Tree, source: &str) -> Result<Vec<Import>> {
+        let mut imports = Vec::new();
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        for child in root.children(&mut cursor) {
+            if child.kind() == "import_declaration" {
+                if let Some(import) = self.parse_import(&child, source) {
+                    imports.push(import);
+                }
+            }
+        }
+        Ok(imports)
+    }
+
+    fn extract_calls(
+        &self,
+        tree: &Tree,
+        source: &str,
+        current_function: Option<&str>,
+    ) -> Result<Vec<FunctionCall>> {
+        let mut calls = Vec::new();
+        let root = tree.root_node();
+        self.extract_calls_recursive(&root, source, &mut calls, current_function);
+        Ok(calls)
+    }
+
+    fn extract_doc_comment(&self, node: &Node, source: &str) -> Option<String> {
+        let mut lines = Vec::new();
+        let mut prev = node.prev_sibling();
+
+        while let Some(sibling) = prev {
+            let text = node_text(&sibling, source);
+            match sibling.kind() {
+                "comment" if text.starts_with("///") => {
+                    lines.push(text.trim_start_matches("///").trim().to_string());
+                }
+                "multiline_comment" if text.starts_with("/**") && lines.is_empty() => {
+                    return Some(Self::clean_block_doc(text));
+                }
+                _ => break,
+            }
+            prev = sibling.prev_sibling();
+        }
+
+        if lines.is_empty() {
+            return None;
+        }
+        lines.reverse();
+        Some(lines.join("\n"))
+    }
+}
+
+impl SwiftExtractor {
+    fn extract_symbols_recursive(
+        &self,
+        node: &Node,
+        source: &str,
+        symbols: &mut Vec<ExtractedSymbol>,
+        parent: Option<&str>,
+    ) {
+        match node.kind() {
+            "class_declaration" => {
+                self.extract_type(node, source, symbols, parent);
+                return;
+            }
+
+            "protocol_declaration" => {
+                if let Some(sym) = self.extract_protocol(node, source, parent) {
+                    let protocol_name = sym.name.clone();
+                    let visibility = sym.visibility;
+                    symbols.push(sym);
+
+                    if let Some(body) = node.child_by_field_name("body") {
+                        self.extract_protocol_members(
+                            &body,
+                            source,
+                            symbols,
+                            &protocol_name,
+                            visibility,
+                        );
+                    }
+                }
+                return;
+            }
+
+            "function_declaration" | "init_declaration" => {
+                if let Some(sym) = self.extract_function(node, source, parent) {
+                    symbols.push(sym);
+                }
+                return;
+            }
+
+            "property_declaration" => {
+                if let Some(sym) = self.extract_property(node, source, parent) {
+                    symbols.push(sym);
+                }
+                return;
+            }
+
+            "typealias_declaration" => {
+                if let Some(sym) = self.extract_typealias(node, source, parent) {
+                    symbols.push(sym);
+                }
+                return;
+            }
+
+            _ => {}
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.extract_symbols_recursive(&child, source, symbols, parent);
+        }
+    }
+
+    /// Classes, structs, enums, actors and extensions share `class_declaration`
+    fn extract_type(
+        &self,
+        node: &Node,
+        source: &str,
+        symbols: &mut Vec<ExtractedSymbol>,
+        parent: Option<&str>,
+    ) {
+        let Some(name_node) = node.child_by_field_name("name") else {
+            return;
+        };
+        let name = node_text(&name_node, source).to_string();
+        let body = node.child_by_field_name("body");
+
+        let kind = match node
+            .child_by_field_name("declaration_kind")
+            .map(|k| node_text(&k, source))
+        {
+            Some("struct") => SymbolKind::Struct,
+            Some("enum") => SymbolKind::Enum,
+            Some("extension") => {
+                if let Some(body) = body {
+                    self.extract_type_members(&body, source, symbols, &name);
+                }
+                return;
+            }
+            _ => SymbolKind::Class,
+        };
+
+        let mut sym = ExtractedSymbol::new(
+            name.clone(),
+            kind,
+            node.start_position().row + 1,
+            node.end_position().row + 1,
+        )
+        .with_columns(node.start_position().column, node.end_position().column);
+
+        sym.visibility = self.extract_visibility(node, source);
+        if matches!(sym.visibility, Visibility::Public) {
+            sym = sym.exported();
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.kind() == "type_parameters" {
+                self.extract_generics(&child, source, &mut sym);
+            }
+        }
+
+        sym.doc_comment = self.extract_doc_comment(node, source);
+
+        if let Some(p) = parent {
+            sym = sym.with_parent(p);
+        }
+
+        symbols.push(sym);
+
+        if let Some(body) = body {
+            self.extract_type_members(&body, source, symbols, &name);
+        }
+    }
+
+    fn extract_type_members(
+        &self,
+        body: &Node,
+        source: &str,
+        symbols: &mut Vec<ExtractedSymbol>,
+        type_name: &str,
+    ) {
+        let mut cursor = body.walk();
+        for child in body.children(&mut cursor) {
+            if child.kind() == "enum_entry" {
+                self.extract_enum_cases(&child, source, symbols, type_name);
+            } else {
+                self.extract_symbols_recursive(&child, source, symbols, Some(type_name));
+            }
+        }
+    }
+
+    fn extract_enum_cases(
+        &self,
+        node: &Node,
+        source: &str,
+        symbols: &mut Vec<ExtractedSymbol>,
+        enum_name: &str,
+    ) {
+        let mut cursor = node.walk();
+        for (i, child) in node.children(&mut cursor).enumerate() {
+            if node.field_name_for_child(i as u32) != Some("name") {
+                continue;
+            }
+
+            let mut sym = ExtractedSymbol::new(
+                node_text(&child, source).to_string(),
+                SymbolKind::EnumVariant,
+                child.start_position().row + 1,
+                child.end_position().row + 1,
+            )
+            .with_parent(enum_name);
+
+            sym.visibility = Visibility::Public;
+            symbols.push(sym.exported());
+        }
+    }
+
+    fn extract_protocol(
+        &self,
+        node: &Node,
+        source: &str,
+        parent: Option<&str>,
+    ) -> Option<ExtractedSymbol> {
+        let name_node = node.child_by_field_name("name")?;
+        let name = node_text(&name_node, source).to_string();
+
+        let mut sym = ExtractedSymbol::new(
+            name,
+            SymbolKind::Interface,
+            node.start_position().row + 1,
+            node.end_position().row + 1,
+        );
+
+        sym.visibility = self.extract_visibility(node, source);
+        if matches!(sym.visibility, Visibility::Public) {
+            sym = sym.exported();
+        }
+
+        sym.doc_comment = self.extract_doc_comment(node, source);
+
+        if let Some(p) = parent {
+            sym = sym.with_parent(p);
+        }
+
+        Some(sym)
+    }
+
+    /// Protocol requirements take the protocol's own access level
+    fn extract_protocol_members(
+        &self,
+        body: &Node,
+        source: &str,
+        symbols: &mut Vec<ExtractedSymbol>,
+        protocol_name: &str,
+        visibility: Visibility,
+    ) {
+        let mut cursor = body.walk();
+        for child in body.children(&mut cursor) {
+            let sym = match child.kind() {
+                "protocol_function_declaration" => {
+                    self.extract_function(&child, source, Some(protocol_name))
+                }
+                "protocol_property_declaration" => {
+                    self.extract_property(&child, source, Some(protocol_name))
+                }
+                _ => None,
+            };
+
+            if let Some(mut sym) = sym {
+                sym.visibility = visibility;
+                sym.exported = matches!(visibility, Visibility::Public);
+                symbols.push(sym);
+            }
+        }
+    }
+
+    fn extract_function(
+        &self,
+        node: &Node,
+        source: &str,
+        parent: Option<&str>,
+    ) -> Option<ExtractedSymbol> {
+        let name = if node.kind() == "init_declaration" {
+            "init".to_string()
+        } else {
+            let name_node = node.child_by_field_name("name")?;
+            node_text(&name_node, source).to_string()
+        };
+
+        let kind = if parent.is_some() {
+            SymbolKind::Method
+        } else {
+            SymbolKind::Function
+        };
+
+        let mut sym = ExtractedSymbol::new(
+            name,
+            kind,
+            node.start_position().row + 1,
+            node.end_position().row + 1,
+        );
+
+        sym.visibility = self.extract_visibility(node, source);
+        if matches!(sym.visibility, Visibility::Public) {
+            sym = sym.exported();
+        }
+
+        if self.is_static(node, source) {
+            sym = sym.static_fn();
+        }
+
+        let mut after_arrow = false;
+        let mut cursor = node.walk();
+        for (i, child) in node.children(&mut cursor).enumerate() {
+            match child.kind() {
+                "type_parameters" => self.extract_generics(&child, source, &mut sym),
+                "parameter" => self.add_parameter(&child, source, &mut sym),
+                "async" => sym.is_async = true,
+                "->" => after_arrow = true,
+                _ if node.field_name_for_child(i as u32) == Some("default_value") => {
+                    if let Some(param) = sym.parameters.last_mut() {
+                        param.default_value = Some(node_text(&child, source).to_string());
+                        param.is_optional = true;
+                    }
+                }
+                _ if after_arrow && child.is_named() => {
+                    sym.return_type = Some(node_text(&child, source).to_string());
+                    after_arrow = false;
+                }
+                _ => {}
+            }
+        }
+
+        sym.doc_comment = self.extract_doc_comment(node, source);
+        sym.signature = Some(declaration_text(node, source));
+
+        if let Some(p) = parent {
+            sym = sym.with_parent(p);
+        }
+
+        Some(sym)
+    }
+
+    fn add_parameter(&self, node: &Node, source: &str, sym: &mut ExtractedSymbol) {
+        let name = node
+            .child_by_field_name("name")
+            .map(|n| node_text(&n, source).to_string())
+            .unwrap_or_default();
+
+        // The type follows the `:` and is also tagged `name`
+        let mut after_colon = false;
+        let mut type_info = None;
+        let mut is_rest = false;
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                ":" => after_colon = true,
+                "..." => is_rest = true,
+                _ if after_colon && child.is_named() && type_info.is_none() => {
+                    type_info = Some(node_text(&child, source).to_string());
+                }
+                _ => {}
+            }
+        }
+
+        sym.add_parameter(Parameter {
+            name,
+            type_info,
+            default_value: None,
+            is_rest,
+            is_optional: false,
+        });
+    }
+
+    fn extract_property(
+        &self,
+        node: &Node,
+        source: &str,
+        parent: Option<&str>,
+    ) -> Option<ExtractedSymbol> {
+        let pattern = node.child_by_field_name("name")?;
+        let name_node = pattern.child_by_field_name("bound_identifier")?;
+        let name = node_text(&name_node, source).to_string();
+
+        let mut cursor = node.walk();
+        let is_let = node
+            .children(&mut cursor)
+            .find(|c| c.kind() == "value_binding_pattern")
+            .is_some_and(|binding| node_text(&binding, source) == "let");
+        let kind = match (parent, is_let) {
+            (Some(_), _) => SymbolKind::Field,
+            (None, true) => SymbolKind::Constant,
+            (None, false) => SymbolKind::Variable,
+        };
+
+        let mut sym = ExtractedSymbol::new(
+            name,
+            kind,
+            node.start_position().row + 1,
+            node.end_position().row + 1,
+        );
+
+        sym.visibility = self.extract_visibility(node, source);
+        if matches!(sym.visibility, Visibility::Public) {
+            sym = sym.exported();
+        }
+
+        if self.is_static(node, source) {
+            sym = sym.static_fn();
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.kind() == "type_annotation" {
+                sym.type_info = child
+                    .child_by_field_name("name")
+                    .map(|n| node_text(&n, source).to_string());
+            }
+        }
+
+        sym.doc_comment = self.extract_doc_comment(node, source);
+
+        if let Some(p) = parent {
+            sym = sym.with_parent(p);
+        }
+
+        Some(sym)
+    }
+
+    fn extract_typealias(
+        &self,
+        node: &Node,
+        source: &str,
+        parent: Option<&str>,
+    ) -> Option<ExtractedSymbol> {
+        let name_node = node.child_by_field_name("name")?;
+        let name = node_text(&name_node, source).to_string();
+
+        let mut sym = ExtractedSymbol::new(
+            name,
+            SymbolKind::TypeAlias,
+            node.start_position().row + 1,
+            node.end_position().row + 1,
+        );
+
+        sym.visibility = self.extract_visibility(node, source);
+        if matches!(sym.visibility, Visibility::Public) {
+            sym = sym.exported();
+        }
+
+        sym.doc_comment = self.extract_doc_comment(node, source);
+
+        if let Some(p) = parent {
+            sym = sym.with_parent(p);
+        }
+
+        Some(sym)
+    }
+
+    fn modifiers<'t>(&self, node: &Node<'t>) -> Option<Node<'t>> {
+        let mut cursor = node.walk();
+        let modifiers = node.children(&mut cursor).find(|c| c.kind() == "modifiers");
+        modifiers
+    }
+
+    fn extract_visibility(&self, node: &Node, source: &str) -> Visibility {
+        let Some(modifiers) = self.modifiers(node) else {
+            return Visibility::Internal;
+        };
+
+        let mut cursor = modifiers.walk();
+        for child in modifiers.children(&mut cursor) {
+            if child.kind() != "visibility_modifier" {
+                continue;
+            }
+            // `private(set)` only restricts the setter
+            match node_text(&child, source) {
+                "public" | "open" => return Visibility::Public,
+                "private" | "fileprivate" => return Visibility::Private,
+                "internal" => return Visibility::Internal,
+                _ => {}
+            }
+        }
+        Visibility::Internal
+    }
+
+    fn is_static(&self, node: &Node, source: &str) -> bool {
+        self.modifiers(node).is_some_and(|modifiers| {
+            node_text(&modifiers, source)
+                .split_whitespace()
+                .any(|word| word == "static" || word == "class")
+        })
+    }
+
+    fn extract_generics(&self, type_params: &Node, source: &str, sym: &mut ExtractedSymbol) {
+        let mut cursor = type_params.walk();
+        for child in type_params.children(&mut cursor) {
+            if child.kind() == "type_parameter" {
+                let mut inner_cursor = child.walk();
+                let name = child
+                    .children(&mut inner_cursor)
+                    .find(|c| c.kind() == "type_identifier");
+                if let Some(name) = name {
+                    sym.add_generic(node_text(&name, source));
+                }
+            }
+        }
+    }
+
+    fn parse_import(&self, node: &Node, source: &str) -> Option<Import> {
+        let mut cursor = node.walk();
+        let path = node
+            .children(&mut cursor)
+            .find(|c| c.kind() == "identifier")?;
+
+        Some(Import {
+            source: node_text(&path, source).to_string(),
+            names: Vec::new(),
+            is_default: false,
+            is_namespace: false,
+            line: node.start_position().row + 1,
+            kind: ImportKind::Import,
+        })
+    }
+
+    fn extract_calls_recursive(
+        &self,
+        node: &Node,
+        source: &str,
+        calls: &mut Vec<FunctionCall>,
+        current_function: Option<&str>,
+    ) {
+        if node.kind() == "call_expression" {
+            if let Some(call) = self.parse_call(node, source, current_function) {
+                calls.push(call);
+            }
+        }
+
+        let func_name = match node.kind() {
+            "function_declaration" => node
+                .child_by_field_name("name")
+                .map(|n| node_text(&n, source)),
+            "init_declaration" => Some("init"),
+            _ => None,
+        };
+
+        let current = func_name.or(current_function);
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.extract_calls_recursive(&child, source, calls, current);
+        }
+    }
+
+    fn parse_call(
+        &self,
+        node: &Node,
+        source: &str,
+        current_function: Option<&str>,
+    ) -> Option<FunctionCall> {
+        let callee = node.named_child(0)?;
+
+        let (name, receiver) = match callee.kind() {
+            "simple_identifier" => (node_text(&callee, source).to_string(), None),
+            "navigation_expression" => {
+                let suffix = callee
+                    .child_by_field_name("suffix")?
+                    .child_by_field_name("suffix")?;
+                let target = callee
+                    .child_by_field_name("target")
+                    .map(|t| node_text(&t, source).to_string());
+                (node_text(&suffix, source).to_string(), target)
+            }
+            _ => return None,
+        };
+
+        Some(FunctionCall {
+            caller: current_function.unwrap_or("<module>").to_string(),
+            callee: name,
+            line: node.start_position().row + 1,
+            is_method: receiver.is_some(),
+            receiver,
+        })
+    }
+
+    fn clean_block_doc(comment: &str) -> String {
+        comment
+            .trim_start_matches("/**")
+            .trim_end_matches("*/")
+            .lines()
+            .map(|line| line.trim().trim_start_matches('*').trim())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_swift(source: &str) -> (Tree, String) {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_swift::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        (tree, source.to_string())
+    }
+
+    fn find<'a>(symbols: &'a [ExtractedSymbol], name: &str) -> &'a ExtractedSymbol {
+        symbols.iter().find(|s| s.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_extract_class_members() {
+        let source = r#"
+/// Stores users
+public class UserStore<T>: Store {
+    private var users: [String] = []
+
+    public static func shared() -> UserStore { return UserStore() }
+
+    init(name: String, count: Int = 3) {}
+
+    fileprivate func reload(_ id: Int) async throws -> Bool { return true }
+}
+"#;
+        let (tree, src) = parse_swift(source);
+        let symbols = SwiftExtractor.extract_symbols(&tree, &src).unwrap();
+
+        let class = find(&symbols, "UserStore");
+        assert_eq!(class.kind, SymbolKind::Class);
+        assert!(class.exported);
+        assert_eq!(class.generics, vec!["T".to_string()]);
+        assert_eq!(class.doc_comment.as_deref(), Some("Stores users"));
+
+        let users = find(&symbols, "users");
+        assert_eq!(users.kind, SymbolKind::Field);
+        assert_eq!(users.visibility, Visibility::Private);
+        assert_eq!(users.type_info.as_deref(), Some("[String]"));
+
+        let shared = find(&symbols, "shared");
+        assert_eq!(shared.kind, SymbolKind::Method);
+        assert!(shared.is_static && shared.exported);
+        assert_eq!(shared.return_type.as_deref(), Some("UserStore"));
+
+        let init = find(&symbols, "init");
+        assert_eq!(init.visibility, Visibility::Internal);
+        assert_eq!(init.parent.as_deref(), Some("UserStore"));
+        assert_eq!(init.parameters.len(), 2);
+        assert_eq!(init.parameters[1].default_value.as_deref(), Some("3"));
+
+        let reload = find(&symbols, "reload");
+        assert_eq!(reload.visibility, Visibility::Private);
+        assert!(reload.is_async);
+        assert_eq!(reload.parameters[0].name, "id");
+        assert_eq!(reload.parameters[0].type_info.as_deref(), Some("Int"));
+        assert_eq!(reload.return_type.as_deref(), Some("Bool"));
+    }
+
+    #[test]
+    fn test_extract_protocol_enum_and_extension() {
+        let source = r#"
+public protocol Store {
+    func load() -> [String]
+    var count: Int { get }
+}
+
+enum Status {
+    case active, inactive
+    case pending
+}
+
+extension Status {
+    public func label() -> String { return "" }
+}
+
+struct Point {}
+let answer = 42
+func helper() {}
+"#;
+        let (tree, src) = parse_swift(source);
+        let symbols = SwiftExtractor.extract_symbols(&tree, &src).unwrap();
+
+        assert_eq!(find(&symbols, "Store").kind, SymbolKind::Interface);
+        let load = find(&symbols, "load");
+        assert_eq!(load.parent.as_deref(), Some("Store"));
+        assert!(load.exported);
+        assert_eq!(find(&symbols, "count").kind, SymbolKind::Field);
+
+        assert_eq!(find(&symbols, "Status").kind, SymbolKind::Enum);
+        for case in ["active", "inactive", "pending"] {
+            assert_eq!(find(&symbols, case).kind, SymbolKind::EnumVariant);
+        }
+        // The extension adds members but is not a symbol of its own
+        assert_eq!(symbols.iter().filter(|s| s.name == "Status").count(), 1);
+        assert_eq!(find(&symbols, "label").parent.as_deref(), Some("Status"));
+
+        assert_eq!(find(&symbols, "Point").kind, SymbolKind::Struct);
+        assert_eq!(find(&symbols, "answer").kind, SymbolKind::Constant);
+        assert_eq!(find(&symbols, "helper").kind, SymbolKind::Function);
+    }
+
+    #[test]
+    fn test_extract_imports_and_calls() {
+        let source = r#"
+import Foundation
+import UIKit.UIView
+
+func reload() {
+    fetch(1)
+    self.cache.clear()
+}
+"#;
+        let (tree, src) = parse_swift(source);
+        let imports = SwiftExtractor.extract_imports(&tree, &src).unwrap();
+        let sources: Vec<_> = imports.iter().map(|i| i.source.as_str()).collect();
+        assert_eq!(sources, vec!["Foundation", "UIKit.UIView"]);
+
+        let calls = SwiftExtractor.extract_calls(&tree, &src, None).unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].callee, "fetch");
+        assert_eq!(calls[0].caller, "reload");
+        assert_eq!(calls[1].callee, "clear");
+        assert_eq!(calls[1].receiver.as_deref(), Some("self.cache"));
+    }
+}
@@ -3,9 +3,13 @@
 //! @acp:domain cli
 //! @acp:layer parsing
 
-use super::{node_text, LanguageExtractor};
+use super::{
+    binding_signature, declaration_text, node_text, parse_reexport, parse_require_call,
+    LanguageExtractor,
+};
 use crate::ast::{
-    ExtractedSymbol, FunctionCall, Import, ImportedName, Parameter, SymbolKind, Visibility,
+    ExtractedSymbol, FunctionCall, Import, ImportKind, ImportedName, Parameter, SymbolKind,
+    Visibility,
 };
 use crate::error::Result;
 use tree_sitter::{Language, Node, Tree};
@@ -62,8 +66,13 @@ impl LanguageExtractor for TypeScriptExtractor {
     }
 
     fn extract_doc_comment(&self, node: &Node, source: &str) -> Option<String> {
-        // Look for comment nodes before this node
-        if let Some(prev) = node.prev_sibling() {
+        // Look for comment nodes before this node;
+        // `export function f` puts the comment before the export statement
+        let anchor = match node.parent() {
+            Some(parent) if parent.kind() == "export_statement" => parent,
+            _ => *node,
+        };
+        if let Some(prev) = anchor.prev_sibling() {
             if prev.kind() == "comment" {
                 let comment = node_text(&prev, source);
                 // Check for JSDoc style comments
@@ -197,7 +206,7 @@ impl TypeScriptExtractor {
         }
 
         // Build signature
-        sym.signature = Some(self.build_function_signature(node, source));
+        sym.signature = Some(declaration_text(node, source));
 
         // Set definition_start_line (before decorators/doc comments)
         sym.definition_start_line = Some(self.find_definition_start_line(node, source));
@@ -253,6 +262,7 @@ impl TypeScriptExtractor {
                         }
 
                         sym.doc_comment = self.extract_doc_comment(node, source);
+                        sym.signature = Some(binding_signature(node, name, &value, source));
 
                         if let Some(p) = parent {
                             sym = sym.with_parent(p);
@@ -419,6 +429,7 @@ impl TypeScriptExtractor {
         }
 
         sym.doc_comment = self.extract_doc_comment(node, source);
+        sym.signature = Self::member_signature(node, source);
 
         if let Some(p) = class_name {
             sym = sym.with_parent(p);
@@ -430,6 +441,22 @@ impl TypeScriptExtractor {
         Some(sym)
     }
 
+    /// Methods keep their declaration; fields only when they hold a function
+    fn member_signature(node: &Node, source: &str) -> Option<String> {
+        if node.kind() == "method_definition" {
+            return Some(declaration_text(node, source));
+        }
+        let name = node.child_by_field_name("name")?;
+        let value = node
+            .child_by_field_name("value")
+            .filter(|v| matches!(v.kind(), "arrow_function" | "function_expression"))?;
+        Some(format!(
+            "{} = {}",
+            node_text(&name, source),
+            declaration_text(&value, source)
+        ))
+    }
+
     fn extract_property(
         &self,
         node: &Node,
@@ -682,10 +709,14 @@ impl TypeScriptExtractor {
     }
 
     fn extract_imports_recursive(&self, node: &Node, source: &str, imports: &mut Vec<Import>) {
-        if node.kind() == "import_statement" {
-            if let Some(import) = self.parse_import(node, source) {
-                imports.push(import);
-            }
+        let import = match node.kind() {
+            "import_statement" => self.parse_import(node, source),
+            "export_statement" => parse_reexport(node, source),
+            "call_expression" => parse_require_call(node, source),
+            _ => None,
+        };
+        if let Some(import) = import {
+            imports.push(import);
         }
 
         let mut cursor = node.walk();
@@ -706,6 +737,7 @@ impl TypeScriptExtractor {
             is_default: false,
             is_namespace: false,
             line: node.start_position().row + 1,
+            kind: ImportKind::Import,
         };
 
         // Parse import clause
@@ -834,25 +866,6 @@ impl TypeScriptExtractor {
         })
     }
 
-    fn build_function_signature(&self, node: &Node, source: &str) -> String {
-        let name = node
-            .child_by_field_name("name")
-            .map(|n| node_text(&n, source))
-            .unwrap_or("anonymous");
-
-        let params = node
-            .child_by_field_name("parameters")
-            .map(|n| node_text(&n, source))
-            .unwrap_or("()");
-
-        let return_type = node
-            .child_by_field_name("return_type")
-            .map(|n| node_text(&n, source))
-            .unwrap_or("");
-
-        format!("function {}{}{}", name, params, return_type)
-    }
-
     fn clean_jsdoc(comment: &str) -> String {
         comment
             .trim_start_matches("/**")
@@ -970,6 +983,24 @@ mod tests {
         (tree, source.to_string())
     }
 
+    #[test]
+    fn test_doc_comment_on_exported_function() {
+        let source = r#"
+/** Greets someone */
+export function greet(name: string) {
+    return name;
+}
+"#;
+        let (tree, src) = parse_ts(source);
+        let symbols = TypeScriptExtractor.extract_symbols(&tree, &src).unwrap();
+
+        let greet: Vec<_> = symbols.iter().filter(|s| s.name == "greet").collect();
+        assert!(!greet.is_empty());
+        for symbol in greet {
+            assert_eq!(symbol.doc_comment.as_deref(), Some("Greets someone"));
+        }
+    }
+
     #[test]
     fn test_extract_function() {
         let source = r#"
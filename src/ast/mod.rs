@@ -275,6 +275,24 @@ pub struct Import {
     pub is_namespace: bool,
     /// Line number
     pub line: usize,
+    /// Syntactic form of the import
+    #[serde(default)]
+    pub kind: ImportKind,
+}
+
+/// How an import was written in source
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImportKind {
+    /// Language-native import (`import x from`, `import x`, `use`)
+    #[default]
+    Import,
+    /// CommonJS `require("x")`
+    Require,
+    /// Python `from x import y`
+    From,
+    /// Re-export (`export { x } from "./y"`, `export * from "./y"`)
+    ReExport,
 }
 
 /// A single imported name
@@ -3,7 +3,9 @@
 //! @acp:domain cli
 //! @acp:layer parsing
 
-use super::languages::{extractor_for_extension, get_extractor, LanguageExtractor};
+use super::languages::{
+    extractor_for_extension, get_extractor, normalize_signature, LanguageExtractor,
+};
 use super::{ExtractedSymbol, FunctionCall, Import};
 use crate::error::{AcpError, Result};
 use std::collections::HashMap;
@@ -31,8 +33,7 @@ impl AstParser {
         let extractor = get_extractor(language)
             .ok_or_else(|| AcpError::UnsupportedLanguage(language.to_string()))?;
 
-        let tree = self.parse(source, extractor.as_ref())?;
-        extractor.extract_symbols(&tree, source)
+        self.extract_symbols(source, extractor.as_ref())
     }
 
     /// Parse source code by file extension
@@ -40,8 +41,21 @@ impl AstParser {
         let extractor = extractor_for_extension(ext)
             .ok_or_else(|| AcpError::UnsupportedLanguage(format!(".{}", ext)))?;
 
-        let tree = self.parse(source, extractor.as_ref())?;
-        extractor.extract_symbols(&tree, source)
+        self.extract_symbols(source, extractor.as_ref())
+    }
+
+    /// Extract symbols with every signature normalized to a single line
+    fn extract_symbols(
+        &self,
+        source: &str,
+        extractor: &dyn LanguageExtractor,
+    ) -> Result<Vec<ExtractedSymbol>> {
+        let tree = self.parse(source, extractor)?;
+        let mut symbols = extractor.extract_symbols(&tree, source)?;
+        for signature in symbols.iter_mut().filter_map(|s| s.signature.as_mut()) {
+            *signature = normalize_signature(signature);
+        }
+        Ok(symbols)
     }
 
     /// Parse a file and extract symbols (convenience method for indexer)
@@ -115,13 +129,24 @@ impl AstParser {
 
     /// Get supported languages
     pub fn supported_languages() -> &'static [&'static str] {
-        &["typescript", "javascript", "rust", "python", "go", "java"]
+        &[
+            "typescript",
+            "javascript",
+            "rust",
+            "python",
+            "go",
+            "java",
+            "ruby",
+            "swift",
+            "kotlin",
+        ]
     }
 
     /// Get supported file extensions
     pub fn supported_extensions() -> &'static [&'static str] {
         &[
-            "ts", "tsx", "js", "jsx", "mjs", "cjs", "rs", "py", "pyi", "go", "java",
+            "ts", "tsx", "js", "jsx", "mjs", "cjs", "rs", "py", "pyi", "go", "java", "rb", "rake",
+            "swift", "kt", "kts",
         ]
     }
 
@@ -169,4 +194,49 @@ mod tests {
         assert!(AstParser::is_extension_supported("py"));
         assert!(!AstParser::is_extension_supported("cob"));
     }
+
+    #[test]
+    fn test_signatures_normalized() {
+        let parser = AstParser::new().unwrap();
+        let signature = |source: &str, ext: &str, name: &str| {
+            parser
+                .parse_by_extension(source, ext)
+                .unwrap()
+                .into_iter()
+                .find(|s| s.name == name)
+                .and_then(|s| s.signature)
+        };
+
+        let ts = "export const mul = (a: number,\n    b: number): number => a * b;\nclass A {\n  async find(\n    id: string,\n  ): Promise<string> { return id; }\n}\n";
+        assert_eq!(
+            signature(ts, "ts", "mul").as_deref(),
+            Some("const mul = (a: number, b: number): number")
+        );
+        assert_eq!(
+            signature(ts, "ts", "find").as_deref(),
+            Some("async find(id: string): Promise<string>")
+        );
+
+        let rs = "pub fn add(a: i32,\n           b: i32) -> i32 { a + b }\ntrait Repo { fn save(&self, item: &str); }\n";
+        assert_eq!(
+            signature(rs, "rs", "add").as_deref(),
+            Some("pub fn add(a: i32, b: i32) -> i32")
+        );
+        assert_eq!(
+            signature(rs, "rs", "save").as_deref(),
+            Some("fn save(&self, item: &str)")
+        );
+
+        let py = "def add(a: int,\n        b: int = 2) -> int:\n    return a + b\n";
+        assert_eq!(
+            signature(py, "py", "add").as_deref(),
+            Some("def add(a: int, b: int = 2) -> int")
+        );
+
+        let java = "class S {\n  @Override\n  public <T> List<T> find(String id) throws IOException { return null; }\n}\n";
+        assert_eq!(
+            signature(java, "java", "find").as_deref(),
+            Some("public <T> List<T> find(String id) throws IOException")
+        );
+    }
 }
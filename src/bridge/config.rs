@@ -162,6 +162,35 @@ fn default_rust_sections() -> Vec<String> {
     ]
 }
 
+/// @acp:summary "Markdown file documentation configuration"
+///
+/// Pairs source files with Markdown docs to fill in missing file summaries.
+/// Patterns are resolved against the source file's directory; `{stem}` is
+/// the file name without extension and `{name}` the full file name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkdownConfig {
+    /// Whether Markdown bridging is enabled (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Candidate doc paths, tried in order
+    #[serde(default = "default_markdown_patterns")]
+    pub patterns: Vec<String>,
+}
+
+impl Default for MarkdownConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            patterns: default_markdown_patterns(),
+        }
+    }
+}
+
+fn default_markdown_patterns() -> Vec<String> {
+    vec!["{stem}.md".to_string(), "README.md".to_string()]
+}
+
 /// @acp:summary "Provenance tracking configuration"
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -187,6 +216,15 @@ fn default_true() -> bool {
     true
 }
 
+/// @acp:summary "Precedence for files matching a glob pattern"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrecedenceOverride {
+    /// Glob matched against project-relative paths (e.g. `vendor/**`)
+    pub pattern: String,
+    /// Precedence used for matching files
+    pub precedence: Precedence,
+}
+
 /// @acp:summary "RFC-0006: Documentation bridging configuration"
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -197,6 +235,9 @@ pub struct BridgeConfig {
     /// Precedence mode when both native and ACP exist
     #[serde(default)]
     pub precedence: Precedence,
+    /// Per-path precedence; the most specific matching pattern wins
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<PrecedenceOverride>,
     /// How to handle malformed documentation
     #[serde(default)]
     pub strictness: Strictness,
@@ -209,6 +250,9 @@ pub struct BridgeConfig {
     /// Rust doc comment settings
     #[serde(default)]
     pub rust: RustConfig,
+    /// Paired Markdown doc settings
+    #[serde(default)]
+    pub markdown: MarkdownConfig,
     /// Provenance tracking settings
     #[serde(default)]
     pub provenance: ProvenanceConfig,
@@ -284,6 +328,15 @@ mod tests {
         assert!(!config.is_enabled_for("python"));
     }
 
+    #[test]
+    fn test_markdown_config_from_json() {
+        let config: BridgeConfig =
+            serde_json::from_str(r#"{"enabled": true, "markdown": {"enabled": true}}"#).unwrap();
+        assert!(config.markdown.enabled);
+        assert_eq!(config.markdown.patterns, vec!["{stem}.md", "README.md"]);
+        assert!(!BridgeConfig::enabled().markdown.enabled);
+    }
+
     #[test]
     fn test_precedence_display() {
         assert_eq!(Precedence::AcpFirst.to_string(), "acp-first");
@@ -25,8 +25,8 @@ impl FormatDetector {
             config: config.clone(),
             // NumPy: Section headers with underlines
             numpy_pattern: Regex::new(r"(?m)^\s*(Parameters|Returns|Raises|Yields|Examples?|Notes?|Attributes?)\s*\n\s*-{3,}").unwrap(),
-            // Sphinx: :param:, :returns:, :raises: tags
-            sphinx_pattern: Regex::new(r":(param|returns?|raises?|type|rtype)\s+").unwrap(),
+            // Sphinx: :param x:, :returns:, :raises Exc: fields at the start of a line
+            sphinx_pattern: Regex::new(r"(?m)^\s*:(param|parameter|arg|argument|key|keyword|type|returns?|rtype|raises?|except|exception)(\s+[^:\n]+)?:(\s|$)").unwrap(),
             // Google: Args:, Returns:, Raises: sections
             google_pattern: Regex::new(r"(?m)^\s*(Args|Arguments|Parameters|Returns|Raises|Yields|Examples?|Attributes?):\s*$").unwrap(),
             // JSDoc: @param, @returns, etc.
@@ -65,7 +65,8 @@ impl FormatDetector {
                 }
             }
             "java" | "kotlin" => Some(SourceFormat::Javadoc),
-            "go" => Some(SourceFormat::Godoc),
+            // Go has one doc convention; never fall through to generic
+            "go" | "golang" => Some(SourceFormat::Godoc),
             _ => None,
         }
     }
@@ -142,7 +143,7 @@ impl FormatDetector {
             "python" | "py" => content.contains("\"\"\"") || content.contains("'''"),
             "rust" | "rs" => content.contains("///") || content.contains("//!"),
             "java" | "kotlin" => content.contains("/**"),
-            "go" => {
+            "go" | "golang" => {
                 // Go doc comments are // directly before declaration
                 content.lines().any(|line| {
                     let trimmed = line.trim();
@@ -267,6 +268,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_detect_godoc_for_go() {
+        let detector = FormatDetector::new(&enabled_config());
+
+        // JSDoc-looking tags in a Go comment are still Godoc
+        let godoc = "// Parse reads the file.\n// @param is not a Go tag";
+        assert_eq!(detector.detect(godoc, "go"), Some(SourceFormat::Godoc));
+        assert_eq!(detector.detect(godoc, "golang"), Some(SourceFormat::Godoc));
+    }
+
     #[test]
     fn test_detect_disabled() {
         let config = BridgeConfig::new(); // disabled by default
@@ -0,0 +1,87 @@
+//! @acp:module "Markdown Bridge"
+//! @acp:summary "RFC-0006: Pairs source files with Markdown docs for file summaries"
+//! @acp:domain cli
+//! @acp:layer service
+//!
+//! A source file with no `@acp:summary` can borrow one from a Markdown doc
+//! next to it. `bridge.markdown.patterns` lists the candidates relative to the
+//! source file's directory; the first doc that yields a paragraph wins. A
+//! shared doc such as `README.md` is searched for a heading naming the file
+//! before falling back to its first paragraph.
+
+use std::path::{Path, PathBuf};
+
+use super::config::MarkdownConfig;
+use crate::annotate::converters::{DocStandardParser, MarkdownParser};
+
+/// @acp:summary "Finds a source file's paired Markdown doc and its summary"
+#[derive(Debug, Clone)]
+pub struct MarkdownBridge {
+    patterns: Vec<String>,
+}
+
+impl MarkdownBridge {
+    pub fn new(config: &MarkdownConfig) -> Self {
+        Self {
+            patterns: config.patterns.clone(),
+        }
+    }
+
+    /// @acp:summary "Candidate doc paths for a source file, in pattern order"
+    pub fn candidates(&self, source: &Path) -> Vec<PathBuf> {
+        let dir = source.parent().unwrap_or(Path::new(""));
+        let name = source.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        let stem = source.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+
+        self.patterns
+            .iter()
+            .map(|pattern| dir.join(pattern.replace("{stem}", stem).replace("{name}", name)))
+            .filter(|candidate| candidate != source)
+            .collect()
+    }
+
+    /// @acp:summary "Summary paragraph from the first paired doc that has one"
+    pub fn summary_for(&self, source: &Path) -> Option<String> {
+        let name = source.file_name()?.to_str()?;
+        self.candidates(source).iter().find_map(|doc| {
+            let content = std::fs::read_to_string(doc).ok()?;
+            MarkdownParser::new()
+                .with_section(name)
+                .parse(&content)
+                .summary
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_from_paired_docs() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(src.join("parser.md"), "# Parser\n\nParses things.\n").unwrap();
+        std::fs::write(
+            src.join("README.md"),
+            "# src\n\nSource root.\n\n## lexer.rs\n\nSplits input into tokens.\n",
+        )
+        .unwrap();
+
+        let bridge = MarkdownBridge::new(&MarkdownConfig::default());
+        assert_eq!(
+            bridge.summary_for(&src.join("parser.rs")).as_deref(),
+            Some("Parses things.")
+        );
+        assert_eq!(
+            bridge.summary_for(&src.join("lexer.rs")).as_deref(),
+            Some("Splits input into tokens.")
+        );
+        assert_eq!(
+            bridge.summary_for(&src.join("main.rs")).as_deref(),
+            Some("Source root.")
+        );
+        assert!(bridge.summary_for(&dir.path().join("build.rs")).is_none());
+    }
+}
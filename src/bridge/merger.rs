@@ -6,7 +6,11 @@
 use super::config::{BridgeConfig, Precedence};
 use super::BridgeResult;
 use crate::annotate::converters::ParsedDocumentation;
-use crate::cache::{BridgeSource, ParamEntry, ReturnsEntry, SourceFormat, ThrowsEntry};
+use crate::cache::{
+    normalize_path, BridgeSource, ParamEntry, ReturnsEntry, SourceFormat, ThrowsEntry,
+};
+use crate::globs::MATCH_OPTIONS;
+use glob::Pattern;
 
 /// @acp:summary "Parsed ACP annotations for a symbol"
 #[derive(Debug, Clone, Default)]
@@ -26,22 +30,59 @@ pub struct AcpAnnotations {
 /// @acp:summary "Merges native documentation with ACP annotations"
 pub struct BridgeMerger {
     config: BridgeConfig,
+    /// Compiled `overrides`, most specific first
+    overrides: Vec<(Pattern, Precedence)>,
 }
 
 impl BridgeMerger {
     /// @acp:summary "Create a new merger with configuration"
+    ///
+    /// Override patterns that fail to compile are ignored.
     pub fn new(config: &BridgeConfig) -> Self {
+        let mut overrides: Vec<_> = config
+            .overrides
+            .iter()
+            .filter_map(|o| Some((Pattern::new(&o.pattern).ok()?, o.precedence)))
+            .collect();
+        // Stable, so equally specific patterns keep config order
+        overrides.sort_by_key(|(pattern, _)| std::cmp::Reverse(specificity(pattern.as_str())));
+
         Self {
             config: config.clone(),
+            overrides,
         }
     }
 
+    /// @acp:summary "Effective precedence for a project-relative path"
+    ///
+    /// The most specific matching override wins, where specificity is the
+    /// number of literal (non-wildcard) characters in the pattern. Falls back
+    /// to the global `precedence`.
+    pub fn precedence_for(&self, path: &str) -> Precedence {
+        let path = normalize_path(path);
+        self.overrides
+            .iter()
+            .find(|(pattern, _)| pattern.matches_with(&path, MATCH_OPTIONS))
+            .map_or(self.config.precedence, |(_, precedence)| *precedence)
+    }
+
     /// @acp:summary "Merge native documentation with ACP annotations"
     pub fn merge(
         &self,
         native: Option<&ParsedDocumentation>,
         native_format: SourceFormat,
         acp: &AcpAnnotations,
+    ) -> BridgeResult {
+        self.merge_with(native, native_format, acp, self.config.precedence)
+    }
+
+    /// @acp:summary "Merge using an explicit precedence (see `precedence_for`)"
+    pub fn merge_with(
+        &self,
+        native: Option<&ParsedDocumentation>,
+        native_format: SourceFormat,
+        acp: &AcpAnnotations,
+        precedence: Precedence,
     ) -> BridgeResult {
         // If no native docs, return ACP-only result
         if native.is_none() || !self.config.enabled {
@@ -61,7 +102,7 @@ impl BridgeMerger {
         }
 
         // Merge based on precedence mode
-        match self.config.precedence {
+        match precedence {
             Precedence::AcpFirst => self.merge_acp_first(native, native_format, acp),
             Precedence::NativeFirst => self.merge_native_first(native, native_format, acp),
             Precedence::Merge => self.merge_combined(native, native_format, acp),
@@ -408,9 +449,18 @@ fn type_source_from_format(format: SourceFormat) -> Option<crate::cache::TypeSou
     }
 }
 
+/// Number of literal characters in a glob pattern
+fn specificity(pattern: &str) -> usize {
+    pattern
+        .chars()
+        .filter(|c| !matches!(c, '*' | '?' | '[' | ']'))
+        .count()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bridge::PrecedenceOverride;
 
     fn test_config() -> BridgeConfig {
         BridgeConfig::enabled()
@@ -531,6 +581,54 @@ mod tests {
         assert_eq!(result.params[0].directive, Some("MUST be UUID".to_string()));
     }
 
+    #[test]
+    fn test_precedence_overrides_most_specific() {
+        let mut config = BridgeConfig::enabled();
+        config.overrides = vec![
+            PrecedenceOverride {
+                pattern: "vendor/legacy/**".to_string(),
+                precedence: Precedence::Merge,
+            },
+            PrecedenceOverride {
+                pattern: "vendor/**".to_string(),
+                precedence: Precedence::NativeFirst,
+            },
+            PrecedenceOverride {
+                pattern: "**/*.py".to_string(),
+                precedence: Precedence::AcpFirst,
+            },
+        ];
+        let merger = BridgeMerger::new(&config);
+
+        assert_eq!(merger.precedence_for("src/app.ts"), Precedence::AcpFirst);
+        assert_eq!(
+            merger.precedence_for("vendor/lib/index.js"),
+            Precedence::NativeFirst
+        );
+        assert_eq!(
+            merger.precedence_for("./vendor/legacy/old.js"),
+            Precedence::Merge
+        );
+        // `vendor/**` is more specific than `**/*.py`
+        assert_eq!(
+            merger.precedence_for("vendor/lib/util.py"),
+            Precedence::NativeFirst
+        );
+
+        let mut native = ParsedDocumentation::new();
+        native.summary = Some("Native summary".to_string());
+        let acp = AcpAnnotations {
+            params: vec![("id".to_string(), "MUST be UUID".to_string())],
+            ..Default::default()
+        };
+        // Native-first only layers directives onto documented params
+        let precedence = merger.precedence_for("vendor/lib/index.js");
+        let result = merger.merge_with(Some(&native), SourceFormat::Jsdoc, &acp, precedence);
+        assert!(result.params.is_empty());
+        let result = merger.merge(Some(&native), SourceFormat::Jsdoc, &acp);
+        assert_eq!(result.params.len(), 1);
+    }
+
     #[test]
     fn test_merge_disabled() {
         let config = BridgeConfig::new(); // disabled
@@ -14,12 +14,14 @@
 //! - Rust doc comments
 //! - Go doc comments
 //! - Javadoc
+//! - Markdown docs paired with source files (file summaries only)
 //!
 //! ## Core Components
 //!
 //! - [`BridgeConfig`]: Configuration for bridging behavior
 //! - [`FormatDetector`]: Auto-detects documentation format
 //! - [`BridgeMerger`]: Merges native docs with ACP annotations
+//! - [`MarkdownBridge`]: Finds a source file's Markdown doc and its summary
 //!
 //! ## Usage
 //!
@@ -50,10 +52,15 @@
 
 pub mod config;
 pub mod detector;
+pub mod markdown;
 pub mod merger;
 
-pub use config::{BridgeConfig, JsDocConfig, ProvenanceConfig, PythonConfig, RustConfig};
+pub use config::{
+    BridgeConfig, JsDocConfig, MarkdownConfig, PrecedenceOverride, ProvenanceConfig, PythonConfig,
+    RustConfig,
+};
 pub use detector::FormatDetector;
+pub use markdown::MarkdownBridge;
 pub use merger::BridgeMerger;
 
 use crate::annotate::converters::ParsedDocumentation;
@@ -0,0 +1,614 @@
+//! @acp:module "Cache Diff"
+//! @acp:summary "Semantic comparison of two cache snapshots"
+//! @acp:domain cli
+//! @acp:layer model
+//!
+//! Compares caches by meaning rather than by JSON text: symbols are matched
+//! on qualified name and files on path, so map iteration order and line
+//! shifts never show up as changes. Every list in the result is sorted, so
+//! diffing the same pair of caches always produces identical output.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+
+use super::types::{Cache, FileEntry, SymbolEntry, SymbolType};
+use crate::constraints::LockLevel;
+
+/// @acp:summary "Everything that changed between two caches"
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CacheDiff {
+    pub files: FileDiff,
+    pub symbols: SymbolDiff,
+    pub hacks: HackDiff,
+    /// Files whose effective lock level changed
+    pub locks: Vec<LockChange>,
+    pub coverage: CoverageDelta,
+}
+
+/// @acp:summary "Files added, removed or changed"
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FileDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<FileChange>,
+}
+
+/// @acp:summary "Metadata changes on a file present in both caches"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileChange {
+    pub path: String,
+    pub changes: Vec<FieldChange>,
+}
+
+/// @acp:summary "Symbols added, removed or changed, matched by qualified name"
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SymbolDiff {
+    pub added: Vec<SymbolRef>,
+    pub removed: Vec<SymbolRef>,
+    pub modified: Vec<SymbolChange>,
+}
+
+/// @acp:summary "A symbol as listed in a diff"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SymbolRef {
+    pub qualified_name: String,
+    pub file: String,
+    pub symbol_type: SymbolType,
+    pub line: usize,
+}
+
+/// @acp:summary "Changes on a symbol present in both caches"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SymbolChange {
+    pub qualified_name: String,
+    pub file: String,
+    pub changes: Vec<FieldChange>,
+}
+
+impl SymbolChange {
+    /// True when the symbol's signature differs between the caches
+    pub fn signature_changed(&self) -> bool {
+        self.changes.iter().any(|c| c.field == "signature")
+    }
+}
+
+/// @acp:summary "One field with its old and new value"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// @acp:summary "Hack markers that appeared or went away"
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct HackDiff {
+    pub added: Vec<HackRef>,
+    pub removed: Vec<HackRef>,
+}
+
+/// @acp:summary "A hack marker as listed in a diff"
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct HackRef {
+    pub file: String,
+    pub line: Option<usize>,
+    pub reason: String,
+}
+
+/// @acp:summary "Effective lock level change on one file"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LockChange {
+    pub path: String,
+    /// `None` when the file was unlocked (or absent) in the old cache
+    pub old: Option<LockLevel>,
+    /// `None` when the file is unlocked (or absent) in the new cache
+    pub new: Option<LockLevel>,
+}
+
+impl LockChange {
+    /// True when the file became frozen
+    pub fn newly_frozen(&self) -> bool {
+        self.new == Some(LockLevel::Frozen) && self.old != Some(LockLevel::Frozen)
+    }
+}
+
+/// @acp:summary "Annotation coverage before and after, in percent"
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CoverageDelta {
+    pub old: f64,
+    pub new: f64,
+    pub delta: f64,
+}
+
+impl CacheDiff {
+    /// @acp:summary "Compare two caches"
+    pub fn between(old: &Cache, new: &Cache) -> Self {
+        Self {
+            files: diff_files(old, new),
+            symbols: diff_symbols(old, new),
+            hacks: diff_hacks(old, new),
+            locks: diff_locks(old, new),
+            coverage: CoverageDelta {
+                old: old.stats.annotation_coverage,
+                new: new.stats.annotation_coverage,
+                delta: new.stats.annotation_coverage - old.stats.annotation_coverage,
+            },
+        }
+    }
+
+    /// True when nothing tracked by the diff changed
+    pub fn is_empty(&self) -> bool {
+        self.files.added.is_empty()
+            && self.files.removed.is_empty()
+            && self.files.modified.is_empty()
+            && self.symbols.added.is_empty()
+            && self.symbols.removed.is_empty()
+            && self.symbols.modified.is_empty()
+            && self.hacks.added.is_empty()
+            && self.hacks.removed.is_empty()
+            && self.locks.is_empty()
+            && self.coverage.delta == 0.0
+    }
+}
+
+fn diff_files(old: &Cache, new: &Cache) -> FileDiff {
+    let old_files: BTreeMap<&str, &FileEntry> =
+        old.files.iter().map(|(k, v)| (k.as_str(), v)).collect();
+    let new_files: BTreeMap<&str, &FileEntry> =
+        new.files.iter().map(|(k, v)| (k.as_str(), v)).collect();
+
+    let mut diff = FileDiff::default();
+    for (path, new_file) in &new_files {
+        match old_files.get(path) {
+            None => diff.added.push(path.to_string()),
+            Some(old_file) => {
+                let changes = file_changes(old_file, new_file);
+                if !changes.is_empty() {
+                    diff.modified.push(FileChange {
+                        path: path.to_string(),
+                        changes,
+                    });
+                }
+            }
+        }
+    }
+    diff.removed = old_files
+        .keys()
+        .filter(|path| !new_files.contains_key(*path))
+        .map(|path| path.to_string())
+        .collect();
+    diff
+}
+
+fn file_changes(old: &FileEntry, new: &FileEntry) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    push_change(&mut changes, "lines", Some(old.lines), Some(new.lines));
+    push_change(
+        &mut changes,
+        "module",
+        old.module.as_ref(),
+        new.module.as_ref(),
+    );
+    push_change(
+        &mut changes,
+        "summary",
+        old.summary.as_ref(),
+        new.summary.as_ref(),
+    );
+    push_change(
+        &mut changes,
+        "purpose",
+        old.purpose.as_ref(),
+        new.purpose.as_ref(),
+    );
+    push_change(
+        &mut changes,
+        "owner",
+        old.owner.as_ref(),
+        new.owner.as_ref(),
+    );
+    push_change(
+        &mut changes,
+        "layer",
+        old.layer.as_ref(),
+        new.layer.as_ref(),
+    );
+    push_change(
+        &mut changes,
+        "stability",
+        old.stability.map(|s| format!("{:?}", s).to_lowercase()),
+        new.stability.map(|s| format!("{:?}", s).to_lowercase()),
+    );
+    push_change(
+        &mut changes,
+        "domains",
+        sorted_list(&old.domains),
+        sorted_list(&new.domains),
+    );
+    push_change(
+        &mut changes,
+        "exports",
+        sorted_list(&old.exports),
+        sorted_list(&new.exports),
+    );
+    changes
+}
+
+fn diff_symbols(old: &Cache, new: &Cache) -> SymbolDiff {
+    let old_symbols = by_qualified_name(old);
+    let new_symbols = by_qualified_name(new);
+
+    let mut diff = SymbolDiff::default();
+    for (name, new_symbol) in &new_symbols {
+        match old_symbols.get(name) {
+            None => diff.added.push(symbol_ref(new_symbol)),
+            Some(old_symbol) => {
+                let changes = symbol_changes(old_symbol, new_symbol);
+                if !changes.is_empty() {
+                    diff.modified.push(SymbolChange {
+                        qualified_name: name.to_string(),
+                        file: new_symbol.file.clone(),
+                        changes,
+                    });
+                }
+            }
+        }
+    }
+    diff.removed = old_symbols
+        .iter()
+        .filter(|(name, _)| !new_symbols.contains_key(*name))
+        .map(|(_, symbol)| symbol_ref(symbol))
+        .collect();
+    diff
+}
+
+/// Symbols keyed by qualified name; the cache's own keys depend on
+/// collision order, so they can't be compared across snapshots
+fn by_qualified_name(cache: &Cache) -> BTreeMap<&str, &SymbolEntry> {
+    cache
+        .symbols
+        .values()
+        .map(|s| (s.qualified_name.as_str(), s))
+        .collect()
+}
+
+fn symbol_ref(symbol: &SymbolEntry) -> SymbolRef {
+    SymbolRef {
+        qualified_name: symbol.qualified_name.clone(),
+        file: symbol.file.clone(),
+        symbol_type: symbol.symbol_type,
+        line: symbol.lines[0],
+    }
+}
+
+/// Line ranges are left out on purpose: edits above a symbol move it
+/// without changing it
+fn symbol_changes(old: &SymbolEntry, new: &SymbolEntry) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    push_change(
+        &mut changes,
+        "signature",
+        old.signature.as_ref(),
+        new.signature.as_ref(),
+    );
+    push_change(
+        &mut changes,
+        "type",
+        Some(format!("{:?}", old.symbol_type).to_lowercase()),
+        Some(format!("{:?}", new.symbol_type).to_lowercase()),
+    );
+    push_change(
+        &mut changes,
+        "exported",
+        Some(old.exported),
+        Some(new.exported),
+    );
+    push_change(
+        &mut changes,
+        "visibility",
+        Some(format!("{:?}", old.visibility).to_lowercase()),
+        Some(format!("{:?}", new.visibility).to_lowercase()),
+    );
+    push_change(
+        &mut changes,
+        "async",
+        Some(old.async_fn),
+        Some(new.async_fn),
+    );
+    push_change(
+        &mut changes,
+        "summary",
+        old.summary.as_ref(),
+        new.summary.as_ref(),
+    );
+    push_change(
+        &mut changes,
+        "purpose",
+        old.purpose.as_ref(),
+        new.purpose.as_ref(),
+    );
+    push_change(
+        &mut changes,
+        "constraint",
+        old.constraints.as_ref().map(|c| &c.level),
+        new.constraints.as_ref().map(|c| &c.level),
+    );
+    changes
+}
+
+/// Hacks are matched on file and reason so that a marker moving down a
+/// few lines isn't reported as removed and re-added
+fn diff_hacks(old: &Cache, new: &Cache) -> HackDiff {
+    let old_hacks = hack_refs(old);
+    let new_hacks = hack_refs(new);
+    HackDiff {
+        added: unmatched(&new_hacks, &old_hacks),
+        removed: unmatched(&old_hacks, &new_hacks),
+    }
+}
+
+fn hack_refs(cache: &Cache) -> Vec<HackRef> {
+    let mut hacks: Vec<HackRef> = cache
+        .constraints
+        .iter()
+        .flat_map(|c| &c.hacks)
+        .map(|h| HackRef {
+            file: h.file.clone(),
+            line: h.line,
+            reason: h.reason.clone(),
+        })
+        .collect();
+    hacks.sort();
+    hacks
+}
+
+/// Entries of `from` with no counterpart in `other`, counting duplicates
+fn unmatched(from: &[HackRef], other: &[HackRef]) -> Vec<HackRef> {
+    let mut available: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+    for hack in other {
+        *available.entry((&hack.file, &hack.reason)).or_default() += 1;
+    }
+    from.iter()
+        .filter(
+            |hack| match available.get_mut(&(hack.file.as_str(), hack.reason.as_str())) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            },
+        )
+        .cloned()
+        .collect()
+}
+
+fn diff_locks(old: &Cache, new: &Cache) -> Vec<LockChange> {
+    let paths: BTreeSet<&str> = old
+        .files
+        .keys()
+        .chain(new.files.keys())
+        .map(|k| k.as_str())
+        .collect();
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let old_level = lock_level(old, path);
+            let new_level = lock_level(new, path);
+            (old_level != new_level).then(|| LockChange {
+                path: path.to_string(),
+                old: old_level,
+                new: new_level,
+            })
+        })
+        .collect()
+}
+
+/// Effective lock, with `normal` treated the same as no lock
+fn lock_level(cache: &Cache, path: &str) -> Option<LockLevel> {
+    if !cache.files.contains_key(path) {
+        return None;
+    }
+    cache
+        .constraints
+        .as_ref()
+        .and_then(|c| c.effective_lock(path))
+        .map(|lock| lock.level)
+        .filter(|level| *level != LockLevel::Normal)
+}
+
+fn push_change<T: ToString>(
+    changes: &mut Vec<FieldChange>,
+    field: &str,
+    old: Option<T>,
+    new: Option<T>,
+) {
+    let old = old.map(|v| v.to_string());
+    let new = new.map(|v| v.to_string());
+    if old != new {
+        changes.push(FieldChange {
+            field: field.to_string(),
+            old,
+            new,
+        });
+    }
+}
+
+/// Order-insensitive rendering of a list; empty lists count as unset
+fn sorted_list(items: &[String]) -> Option<String> {
+    if items.is_empty() {
+        return None;
+    }
+    let mut sorted: Vec<&str> = items.iter().map(|s| s.as_str()).collect();
+    sorted.sort_unstable();
+    Some(sorted.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheBuilder;
+    use crate::constraints::{ConstraintIndex, Constraints, HackMarker, HackType};
+    use serde_json::json;
+
+    fn file(path: &str, lines: usize) -> FileEntry {
+        serde_json::from_value(json!({
+            "path": path,
+            "lines": lines,
+            "language": "typescript",
+            "exports": [],
+            "imports": []
+        }))
+        .unwrap()
+    }
+
+    fn symbol(name: &str, file: &str, signature: &str) -> SymbolEntry {
+        SymbolEntry {
+            lines: [1, 5],
+            signature: Some(signature.to_string()),
+            ..SymbolEntry::new(name, file)
+        }
+    }
+
+    fn hack(file: &str, line: usize, reason: &str) -> HackMarker {
+        HackMarker {
+            id: format!("{}:{}", file, line),
+            hack_type: HackType::Workaround,
+            file: file.to_string(),
+            line: Some(line),
+            created_at: chrono::Utc::now(),
+            author: None,
+            reason: reason.to_string(),
+            ticket: None,
+            expires: None,
+            original_code: None,
+            revert_instructions: None,
+        }
+    }
+
+    fn frozen() -> Constraints {
+        serde_json::from_value(json!({ "mutation": { "level": "frozen" } })).unwrap()
+    }
+
+    fn cache(
+        files: Vec<FileEntry>,
+        symbols: Vec<SymbolEntry>,
+        constraints: ConstraintIndex,
+    ) -> Cache {
+        let mut builder = CacheBuilder::new("test", ".");
+        for f in files {
+            builder = builder.add_file(f);
+        }
+        for s in symbols {
+            builder = builder.add_symbol(s);
+        }
+        let mut cache = builder.build();
+        cache.constraints = Some(constraints);
+        cache
+    }
+
+    #[test]
+    fn test_identical_caches_have_no_diff() {
+        let a = cache(
+            vec![file("a.ts", 10)],
+            vec![symbol("run", "a.ts", "run()")],
+            ConstraintIndex::default(),
+        );
+        assert!(CacheDiff::between(&a, &a.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_files_and_symbols() {
+        let old = cache(
+            vec![file("a.ts", 10), file("gone.ts", 3)],
+            vec![
+                symbol("run", "a.ts", "run()"),
+                symbol("old", "gone.ts", "old()"),
+                // Same name in another file must not mask a change in a.ts
+                symbol("run", "b.ts", "run()"),
+            ],
+            ConstraintIndex::default(),
+        );
+        let mut moved = symbol("run", "a.ts", "run(id: string)");
+        moved.lines = [40, 50];
+        let new = cache(
+            vec![file("a.ts", 12), file("new.ts", 1)],
+            vec![
+                symbol("run", "b.ts", "run()"),
+                moved,
+                symbol("fresh", "new.ts", "fresh()"),
+            ],
+            ConstraintIndex::default(),
+        );
+
+        let diff = CacheDiff::between(&old, &new);
+        assert_eq!(diff.files.added, vec!["new.ts"]);
+        assert_eq!(diff.files.removed, vec!["gone.ts"]);
+        assert_eq!(diff.files.modified[0].changes[0].field, "lines");
+
+        assert_eq!(diff.symbols.added[0].qualified_name, "new.ts:fresh");
+        assert_eq!(diff.symbols.removed[0].qualified_name, "gone.ts:old");
+        assert_eq!(diff.symbols.modified.len(), 1);
+        let changed = &diff.symbols.modified[0];
+        assert_eq!(changed.qualified_name, "a.ts:run");
+        assert!(changed.signature_changed());
+        // Moving lines alone is not a change
+        assert_eq!(changed.changes.len(), 1);
+    }
+
+    #[test]
+    fn test_hacks_locks_and_coverage() {
+        let files = || vec![file("a.ts", 10), file("b.ts", 10)];
+        let mut old = cache(
+            files(),
+            vec![],
+            ConstraintIndex {
+                hacks: vec![hack("a.ts", 3, "timeout workaround")],
+                ..Default::default()
+            },
+        );
+        old.stats.annotation_coverage = 40.0;
+
+        let mut new_constraints = ConstraintIndex {
+            hacks: vec![
+                hack("a.ts", 7, "timeout workaround"),
+                hack("b.ts", 2, "retry loop"),
+            ],
+            ..Default::default()
+        };
+        new_constraints.by_file.insert("b.ts".to_string(), frozen());
+        let mut new = cache(files(), vec![], new_constraints);
+        new.stats.annotation_coverage = 55.0;
+
+        let diff = CacheDiff::between(&old, &new);
+        // A hack that only moved lines is not new
+        assert_eq!(diff.hacks.added.len(), 1);
+        assert_eq!(diff.hacks.added[0].reason, "retry loop");
+        assert!(diff.hacks.removed.is_empty());
+
+        assert_eq!(diff.locks.len(), 1);
+        assert!(diff.locks[0].newly_frozen());
+        assert_eq!(diff.locks[0].path, "b.ts");
+
+        assert_eq!(diff.coverage.delta, 15.0);
+    }
+
+    #[test]
+    fn test_diff_is_order_independent() {
+        let symbols = vec![
+            symbol("a", "x.ts", "a()"),
+            symbol("b", "y.ts", "b()"),
+            symbol("c", "z.ts", "c()"),
+        ];
+        let mut reversed = symbols.clone();
+        reversed.reverse();
+        let empty = cache(vec![], vec![], ConstraintIndex::default());
+
+        let forward = CacheDiff::between(&empty, &cache(vec![], symbols, Default::default()));
+        let backward = CacheDiff::between(&empty, &cache(vec![], reversed, Default::default()));
+        assert_eq!(
+            serde_json::to_string(&forward).unwrap(),
+            serde_json::to_string(&backward).unwrap()
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! @acp:module "Stats History"
+//! @acp:summary "Append-only log of cache stats snapshots for trend tracking"
+//! @acp:domain cli
+//! @acp:layer model
+//!
+//! `acp index --history` appends one JSON object per run to
+//! `stats-history.jsonl` next to the cache. The file is never rewritten, so
+//! it can be committed or edited by hand: blank lines, `#` comments and
+//! lines that no longer parse are skipped when reading, and missing fields
+//! fall back to zero.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::types::{Cache, SourceCounts};
+use crate::error::Result;
+
+/// File name of the history log, stored beside the cache file
+pub const HISTORY_FILE: &str = "stats-history.jsonl";
+
+/// @acp:summary "Cache totals at one point in time"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsSnapshot {
+    pub timestamp: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+    #[serde(default)]
+    pub files: usize,
+    #[serde(default)]
+    pub symbols: usize,
+    #[serde(default)]
+    pub lines: usize,
+    /// Annotation coverage percentage
+    #[serde(default)]
+    pub coverage: f64,
+    /// Annotation counts by source origin (RFC-0003)
+    #[serde(default)]
+    pub provenance: SourceCounts,
+    #[serde(default)]
+    pub needs_review: u64,
+}
+
+impl StatsSnapshot {
+    /// @acp:summary "Snapshot of a freshly built cache"
+    pub fn from_cache(cache: &Cache) -> Self {
+        Self {
+            timestamp: cache.generated_at,
+            git_commit: cache.git_commit.clone(),
+            files: cache.stats.files,
+            symbols: cache.stats.symbols,
+            lines: cache.stats.lines,
+            coverage: cache.stats.annotation_coverage,
+            provenance: cache.provenance.summary.by_source.clone(),
+            needs_review: cache.provenance.summary.needs_review,
+        }
+    }
+}
+
+/// @acp:summary "History log path for a cache file"
+pub fn history_path(cache_path: &Path) -> PathBuf {
+    cache_path.with_file_name(HISTORY_FILE)
+}
+
+/// @acp:summary "Append a snapshot as one line of the history log"
+pub fn append_snapshot(path: &Path, snapshot: &StatsSnapshot) -> Result<()> {
+    // A hand-edited file may have lost its final newline
+    let needs_newline = fs::read(path)
+        .map(|bytes| bytes.last().is_some_and(|b| *b != b'\n'))
+        .unwrap_or(false);
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut line = serde_json::to_string(snapshot)?;
+    if needs_newline {
+        line.insert(0, '\n');
+    }
+    line.push('\n');
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// @acp:summary "Snapshots in the history log, oldest first"
+///
+/// A missing file is an empty history.
+pub fn read_history(path: &Path) -> Result<Vec<StatsSnapshot>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut snapshots: Vec<StatsSnapshot> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    snapshots.sort_by_key(|s| s.timestamp);
+    Ok(snapshots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_tolerates_manual_edits() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = history_path(&dir.path().join("acp.cache.json"));
+        assert!(read_history(&path).unwrap().is_empty());
+
+        // Out of order, a comment, a broken line, and no trailing newline
+        fs::write(
+            &path,
+            "# release 1.2\n\
+             {\"timestamp\":\"2026-02-01T00:00:00Z\",\"files\":10,\"coverage\":50.0}\n\
+             not json\n\n\
+             {\"timestamp\":\"2026-01-01T00:00:00Z\",\"coverage\":40.0}",
+        )
+        .unwrap();
+
+        let mut cache = Cache::new("demo", ".");
+        cache.generated_at = "2026-03-01T00:00:00Z".parse().unwrap();
+        cache.stats.annotation_coverage = 62.5;
+        append_snapshot(&path, &StatsSnapshot::from_cache(&cache)).unwrap();
+
+        let history = read_history(&path).unwrap();
+        let coverage: Vec<_> = history.iter().map(|s| s.coverage).collect();
+        assert_eq!(coverage, vec![40.0, 50.0, 62.5]);
+        assert_eq!(history[1].files, 10);
+        assert_eq!(history[0].files, 0);
+    }
+}
@@ -3,6 +3,21 @@
 //! @acp:domain cli
 //! @acp:layer model
 
+mod diff;
+mod history;
+mod refs;
+mod staleness;
 mod types;
+mod versions;
+mod writer;
 
+pub use diff::{
+    CacheDiff, CoverageDelta, FieldChange, FileChange, FileDiff, HackDiff, HackRef, LockChange,
+    SymbolChange, SymbolDiff, SymbolRef,
+};
+pub use history::{append_snapshot, history_path, read_history, StatsSnapshot, HISTORY_FILE};
+pub use refs::{BrokenRef, RefKind, RefReport};
+pub use staleness::{content_hash, stale_source_files, StaleReport};
 pub use types::*;
+pub use versions::{parse_version, VersionProblem, VersionReport};
+pub use writer::CacheWriter;
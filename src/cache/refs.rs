@@ -0,0 +1,239 @@
+//! @acp:module "Reference Check"
+//! @acp:summary "Verifies @acp:see targets and @acp:link URLs recorded in a cache"
+//! @acp:domain cli
+//! @acp:layer model
+//!
+//! `@acp:see` targets are written by hand and drift as code is renamed or
+//! moved. A target resolves when it names a symbol by qualified name
+//! (`src/auth.ts:AuthService.login`), by simple name (`login`, or the last
+//! segment of `AuthService.login`), or names an indexed file.
+
+use super::types::{Cache, SymbolEntry};
+
+/// @acp:summary "Which annotation a broken reference came from"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefKind {
+    /// `@acp:see`
+    See,
+    /// `@acp:link`
+    Link,
+}
+
+impl RefKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::See => "@acp:see",
+            Self::Link => "@acp:link",
+        }
+    }
+}
+
+/// @acp:summary "A reference that does not resolve"
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrokenRef {
+    /// Qualified name of the symbol carrying the annotation
+    pub symbol: String,
+    pub file: String,
+    pub line: usize,
+    pub kind: RefKind,
+    pub target: String,
+    pub reason: String,
+}
+
+/// @acp:summary "Result of checking every reference in a cache"
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RefReport {
+    /// Number of `@acp:see` and `@acp:link` values examined
+    pub checked: usize,
+    /// Broken references, sorted by file and line
+    pub broken: Vec<BrokenRef>,
+}
+
+impl RefReport {
+    /// @acp:summary "Check all see/link references on the cache's symbols"
+    pub fn check(cache: &Cache) -> Self {
+        let mut report = Self::default();
+
+        for symbol in cache.symbols.values() {
+            let Some(docs) = &symbol.documentation else {
+                continue;
+            };
+
+            for target in &docs.see_also {
+                report.checked += 1;
+                if !resolves(cache, target) {
+                    report.push(symbol, RefKind::See, target, "no matching symbol or file");
+                }
+            }
+
+            for url in &docs.links {
+                report.checked += 1;
+                if let Err(reason) = check_url(url) {
+                    report.push(symbol, RefKind::Link, url, reason);
+                }
+            }
+        }
+
+        report.broken.sort_by(|a, b| {
+            (&a.file, a.line, &a.symbol, &a.target).cmp(&(&b.file, b.line, &b.symbol, &b.target))
+        });
+        report
+    }
+
+    /// @acp:summary "True when every reference resolved"
+    pub fn is_clean(&self) -> bool {
+        self.broken.is_empty()
+    }
+
+    fn push(&mut self, symbol: &SymbolEntry, kind: RefKind, target: &str, reason: &str) {
+        self.broken.push(BrokenRef {
+            symbol: symbol.qualified_name.clone(),
+            file: symbol.file.clone(),
+            line: symbol.lines[0],
+            kind,
+            target: target.to_string(),
+            reason: reason.to_string(),
+        });
+    }
+}
+
+/// Whether an `@acp:see` target names a known symbol or file
+fn resolves(cache: &Cache, target: &str) -> bool {
+    let target = target.trim().trim_end_matches("()");
+    if target.is_empty() {
+        return false;
+    }
+
+    // Qualified name (cache keys are simple names unless they collide)
+    if cache.symbols.contains_key(target)
+        || cache.symbols.values().any(|s| s.qualified_name == target)
+    {
+        return true;
+    }
+
+    // Simple name: the last segment of `Type.method`, `mod::item`,
+    // `Class#method` or `path/file.ts:name`
+    let simple = target.rsplit(['.', ':', '#']).next().unwrap_or(target);
+    if !cache.symbols_named(simple).is_empty() {
+        return true;
+    }
+
+    cache.get_file(target).is_some()
+}
+
+/// Validate an `@acp:link` URL, returning why it is malformed
+fn check_url(url: &str) -> Result<(), &'static str> {
+    if url.chars().any(char::is_whitespace) {
+        return Err("URL contains whitespace");
+    }
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return Err("missing scheme (expected http:// or https://)");
+    };
+    if !matches!(scheme.to_ascii_lowercase().as_str(), "http" | "https") {
+        return Err("unsupported scheme (expected http or https)");
+    }
+
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    let (host, port, bracketed) = match host.strip_prefix('[') {
+        // IPv6 literal such as [::1]:8080
+        Some(inner) => match inner.split_once(']') {
+            Some((ip, "")) => (ip, None, true),
+            Some((ip, after)) => match after.strip_prefix(':') {
+                Some(port) => (ip, Some(port), true),
+                None => return Err("invalid host"),
+            },
+            None => return Err("invalid host"),
+        },
+        None => match host.split_once(':') {
+            Some((host, port)) => (host, Some(port), false),
+            None => (host, None, false),
+        },
+    };
+
+    if let Some(port) = port {
+        if port.is_empty() || !port.chars().all(|c| c.is_ascii_digit()) {
+            return Err("invalid port");
+        }
+    }
+    if host.is_empty() {
+        return Err("missing host");
+    }
+    if !bracketed
+        && !host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+    {
+        return Err("invalid host");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheBuilder;
+    use serde_json::json;
+
+    fn symbol(name: &str, file: &str, see: &[&str], links: &[&str]) -> SymbolEntry {
+        let documentation = serde_json::from_value(json!({ "seeAlso": see, "links": links }));
+        SymbolEntry {
+            lines: [3, 9],
+            documentation: Some(documentation.unwrap()),
+            ..SymbolEntry::new(name, file)
+        }
+    }
+
+    #[test]
+    fn test_see_resolves_by_qualified_and_simple_name() {
+        let cache = CacheBuilder::new("test", ".")
+            .add_symbol(symbol("login", "src/auth.ts", &[], &[]))
+            .add_symbol(symbol(
+                "logout",
+                "src/session.ts",
+                &[
+                    "src/auth.ts:login",
+                    "login",
+                    "AuthService.login",
+                    "login()",
+                    "missingThing",
+                ],
+                &[],
+            ))
+            .build();
+
+        let report = RefReport::check(&cache);
+        assert_eq!(report.checked, 5);
+        assert_eq!(report.broken.len(), 1);
+
+        let broken = &report.broken[0];
+        assert_eq!(broken.symbol, "src/session.ts:logout");
+        assert_eq!(broken.kind, RefKind::See);
+        assert_eq!(broken.target, "missingThing");
+        assert_eq!(broken.line, 3);
+    }
+
+    #[test]
+    fn test_link_urls() {
+        for ok in [
+            "https://example.com",
+            "http://docs.example.com:8080/api?x=1#frag",
+            "https://user@example.com/path",
+            "https://[::1]:3000/",
+        ] {
+            assert_eq!(check_url(ok), Ok(()), "{}", ok);
+        }
+        for bad in [
+            "example.com/docs",
+            "ftp://example.com",
+            "https://",
+            "https:///path",
+            "https://exa mple.com",
+            "https://example.com:port/",
+            "https://exa$mple.com",
+        ] {
+            assert!(check_url(bad).is_err(), "{}", bad);
+        }
+    }
+}
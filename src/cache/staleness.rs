@@ -0,0 +1,232 @@
+//! @acp:module "Cache Staleness"
+//! @acp:summary "Content hash and stat-only staleness checks for loaded caches"
+//! @acp:domain cli
+//! @acp:layer model
+//!
+//! `source_files` records every indexed path with its mtime. Hashing that map
+//! gives a cheap integrity check for a cache on disk, and re-statting the
+//! tree against it tells whether the cache is out of date without parsing
+//! any source.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use sha2::{Digest, Sha256};
+
+use super::types::Cache;
+use crate::config::Config;
+use crate::error::Result;
+use crate::index::find_source_files;
+
+/// @acp:summary "Files that changed on disk since the cache was written"
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StaleReport {
+    /// Files matching the include patterns that the cache doesn't know about
+    pub added: Vec<String>,
+    /// Cached files that no longer exist
+    pub removed: Vec<String>,
+    /// Cached files whose mtime differs from the recorded one
+    pub modified: Vec<String>,
+    /// Stored `content_hash` disagrees with `source_files`
+    pub hash_mismatch: bool,
+}
+
+impl StaleReport {
+    /// Number of files the cache is out of date by
+    pub fn out_of_date(&self) -> usize {
+        self.added.len() + self.removed.len() + self.modified.len()
+    }
+
+    /// True when the cache no longer reflects the tree
+    pub fn is_stale(&self) -> bool {
+        self.hash_mismatch || self.out_of_date() > 0
+    }
+}
+
+/// @acp:summary "SHA-256 over sorted source file paths and mtimes"
+///
+/// Sorting makes the hash independent of map iteration order, so an
+/// unchanged tree always hashes the same.
+pub fn content_hash(source_files: &HashMap<String, DateTime<Utc>>) -> String {
+    let sorted: BTreeMap<_, _> = source_files.iter().collect();
+    let mut hasher = Sha256::new();
+    for (path, modified) in sorted {
+        hasher.update(path.as_bytes());
+        hasher.update([0]);
+        hasher.update(modified.to_rfc3339_opts(SecondsFormat::Nanos, true));
+        hasher.update([b'\n']);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+impl Cache {
+    /// @acp:summary "Hash of the current source_files map"
+    pub fn compute_content_hash(&self) -> String {
+        content_hash(&self.source_files)
+    }
+
+    /// @acp:summary "Re-stat the tree and report files changed since indexing"
+    ///
+    /// Stat-only: no file is read or parsed. New files are found with the
+    /// include/exclude patterns from `.acp.config.json` under `root`, falling
+    /// back to the defaults.
+    pub fn is_stale<P: AsRef<Path>>(&self, root: P) -> Result<StaleReport> {
+        Ok(StaleReport {
+            hash_mismatch: self
+                .content_hash
+                .as_ref()
+                .is_some_and(|hash| *hash != self.compute_content_hash()),
+            ..stale_source_files(root, &self.source_files)
+        })
+    }
+
+    /// @acp:summary "Re-stat only the cached files, without walking the tree"
+    ///
+    /// Cheap enough to run before every query: `added` is always empty,
+    /// since finding new files needs the full walk [`Cache::is_stale`] does.
+    pub fn changed_source_files<P: AsRef<Path>>(&self, root: P) -> StaleReport {
+        let mut report = stat_source_files(root.as_ref(), &self.source_files);
+        report.hash_mismatch = self
+            .content_hash
+            .as_ref()
+            .is_some_and(|hash| *hash != self.compute_content_hash());
+        report
+    }
+}
+
+/// @acp:summary "Re-stat the tree against a recorded `source_files` map"
+///
+/// The stat-only part of [`Cache::is_stale`], for holders of just the map;
+/// `hash_mismatch` is always false.
+pub fn stale_source_files<P: AsRef<Path>>(
+    root: P,
+    source_files: &HashMap<String, DateTime<Utc>>,
+) -> StaleReport {
+    let root = root.as_ref();
+    let config = Config::load(root.join(".acp.config.json")).unwrap_or_default();
+    let mut report = stat_source_files(root, source_files);
+
+    let known: HashSet<&str> = source_files.keys().map(String::as_str).collect();
+    for file in find_source_files(
+        root,
+        &config.include,
+        &config.exclude,
+        config.respect_gitignore,
+    ) {
+        let relative = Path::new(&file)
+            .strip_prefix(root)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or(file);
+        if !known.contains(relative.as_str()) {
+            report.added.push(relative);
+        }
+    }
+
+    report.added.sort();
+    report
+}
+
+/// Modified and removed files among `source_files`; `added` stays empty
+fn stat_source_files(root: &Path, source_files: &HashMap<String, DateTime<Utc>>) -> StaleReport {
+    let mut report = StaleReport::default();
+    for (path, recorded) in source_files {
+        match fs::metadata(root.join(path)).and_then(|m| m.modified()) {
+            Ok(modified) => {
+                if DateTime::<Utc>::from(modified) != *recorded {
+                    report.modified.push(path.clone());
+                }
+            }
+            Err(_) => report.removed.push(path.clone()),
+        }
+    }
+
+    report.removed.sort();
+    report.modified.sort();
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    fn indexed(root: &Path, files: &[&str]) -> Cache {
+        let mut cache = Cache::new("demo", &root.to_string_lossy());
+        for file in files {
+            let modified = fs::metadata(root.join(file)).unwrap().modified().unwrap();
+            cache
+                .source_files
+                .insert(file.to_string(), DateTime::<Utc>::from(modified));
+        }
+        cache.content_hash = Some(cache.compute_content_hash());
+        cache
+    }
+
+    #[test]
+    fn test_content_hash_is_order_independent() {
+        let t = Utc::now();
+        let a = HashMap::from([("a.ts".to_string(), t), ("b.ts".to_string(), t)]);
+        let b = HashMap::from([("b.ts".to_string(), t), ("a.ts".to_string(), t)]);
+        assert_eq!(content_hash(&a), content_hash(&b));
+
+        let moved = HashMap::from([("a.ts".to_string(), t), ("c.ts".to_string(), t)]);
+        assert_ne!(content_hash(&a), content_hash(&moved));
+    }
+
+    #[test]
+    fn test_unchanged_tree_is_fresh() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.ts"), "export const a = 1;").unwrap();
+        let cache = indexed(dir.path(), &["a.ts"]);
+
+        let report = cache.is_stale(dir.path()).unwrap();
+        assert!(!report.is_stale(), "{:?}", report);
+    }
+
+    #[test]
+    fn test_reports_added_removed_and_modified() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["keep.ts", "edit.ts", "gone.ts"] {
+            fs::write(dir.path().join(name), "export {};").unwrap();
+        }
+        let cache = indexed(dir.path(), &["keep.ts", "edit.ts", "gone.ts"]);
+
+        fs::remove_file(dir.path().join("gone.ts")).unwrap();
+        fs::write(dir.path().join("new.ts"), "export {};").unwrap();
+        fs::write(dir.path().join("notes.txt"), "not a source file").unwrap();
+        let later = SystemTime::now() + Duration::from_secs(60);
+        fs::File::options()
+            .write(true)
+            .open(dir.path().join("edit.ts"))
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+
+        let report = cache.is_stale(dir.path()).unwrap();
+        assert_eq!(report.added, vec!["new.ts"]);
+        assert_eq!(report.removed, vec!["gone.ts"]);
+        assert_eq!(report.modified, vec!["edit.ts"]);
+        assert!(!report.hash_mismatch);
+        assert_eq!(report.out_of_date(), 3);
+
+        // The quick check skips the walk, so it can't see new files
+        let quick = cache.changed_source_files(dir.path());
+        assert!(quick.added.is_empty());
+        assert_eq!(quick.removed, vec!["gone.ts"]);
+        assert_eq!(quick.modified, vec!["edit.ts"]);
+    }
+
+    #[test]
+    fn test_detects_hash_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.ts"), "export {};").unwrap();
+        let mut cache = indexed(dir.path(), &["a.ts"]);
+        cache.content_hash = Some("0".repeat(64));
+
+        let report = cache.is_stale(dir.path()).unwrap();
+        assert!(report.hash_mismatch);
+        assert!(report.is_stale());
+    }
+}
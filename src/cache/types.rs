@@ -7,17 +7,39 @@
 //! Includes RFC-003 annotation provenance tracking support.
 
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
-use std::io::{BufReader, BufWriter};
+use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::Path;
+use std::sync::OnceLock;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
 
+use super::writer::sorted;
+use super::CacheWriter;
+use crate::config::DomainNormalize;
 use crate::constraints::ConstraintIndex;
-use crate::error::Result;
+use crate::error::{AcpError, Result};
 use crate::git::{GitFileInfo, GitSymbolInfo};
 use crate::parse::SourceOrigin;
 
+/// Serialize a map in key order so cache output is deterministic
+pub(crate) fn serialize_sorted<S, K, V>(
+    map: &HashMap<K, V>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    K: Ord + Serialize,
+    V: Serialize,
+{
+    serializer.collect_map(map.iter().collect::<BTreeMap<_, _>>())
+}
+
 /// @acp:summary "Normalize a file path for cross-platform compatibility"
 ///
 /// Handles:
@@ -70,21 +92,32 @@ pub struct Cache {
     /// Git commit SHA (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub git_commit: Option<String>,
+    /// SHA-256 over sorted source file paths and mtimes, written on save (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
     /// Project metadata (required)
     pub project: ProjectInfo,
     /// Aggregate statistics (required)
     pub stats: Stats,
     /// Map of file paths to modification times for staleness detection (required)
+    #[serde(serialize_with = "serialize_sorted")]
     pub source_files: HashMap<String, DateTime<Utc>>,
     /// Files indexed by path (required)
+    #[serde(serialize_with = "serialize_sorted")]
     pub files: HashMap<String, FileEntry>,
-    /// Symbols indexed by name (required)
+    /// Symbols indexed by name; a name defined in several files keys the
+    /// later definitions by qualified name (required)
+    #[serde(serialize_with = "serialize_sorted")]
     pub symbols: HashMap<String, SymbolEntry>,
     /// Call graph relationships (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub graph: Option<CallGraph>,
     /// Domain groupings (optional)
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted"
+    )]
     pub domains: HashMap<String, DomainEntry>,
     /// AI behavioral constraints (optional)
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -98,8 +131,25 @@ pub struct Cache {
     /// RFC-0015: Auto-detected naming and import conventions (optional)
     #[serde(default, skip_serializing_if = "Conventions::is_empty")]
     pub conventions: Conventions,
+    /// Top-level fields written by a newer version, kept so they survive a
+    /// rewrite (see [`CacheWriter`])
+    #[serde(flatten, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+    /// Folded (lowercased, accent-stripped) simple and qualified names to
+    /// `symbols` keys, built on the first case-insensitive lookup
+    #[serde(skip)]
+    symbols_ci: OnceLock<HashMap<String, Vec<String>>>,
 }
 
+/// Leading bytes of every gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Leading bytes of a binary cache; the MessagePack document follows
+pub const BINARY_CACHE_MAGIC: [u8; 4] = *b"ACPB";
+
+/// File name suffix that selects the binary format when writing
+pub const BINARY_CACHE_EXTENSION: &str = ".bin";
+
 fn default_cache_schema() -> String {
     "https://acp-protocol.dev/schemas/v1/cache.schema.json".to_string()
 }
@@ -112,6 +162,7 @@ impl Cache {
             version: crate::VERSION.to_string(),
             generated_at: Utc::now(),
             git_commit: None,
+            content_hash: None,
             project: ProjectInfo {
                 name: project_name.to_string(),
                 root: root.to_string(),
@@ -127,22 +178,84 @@ impl Cache {
             provenance: ProvenanceStats::default(),
             bridge: BridgeStats::default(),
             conventions: Conventions::default(),
+            extra: serde_json::Map::new(),
+            symbols_ci: OnceLock::new(),
         }
     }
 
     /// @acp:summary "Load cache from JSON file"
+    ///
+    /// Gzip-compressed and binary caches are detected by their magic bytes,
+    /// so either loads regardless of its file extension.
     pub fn from_json<P: AsRef<Path>>(path: P) -> Result<Self> {
         let file = File::open(path)?;
-        let reader = BufReader::new(file);
-        let cache = serde_json::from_reader(reader)?;
+        let mut reader = BufReader::new(file);
+        let head = reader.fill_buf()?;
+        let cache = if head.starts_with(&BINARY_CACHE_MAGIC) {
+            Self::read_binary(reader)?
+        } else if head.starts_with(&GZIP_MAGIC) {
+            serde_json::from_reader(BufReader::new(GzDecoder::new(reader)))?
+        } else {
+            serde_json::from_reader(reader)?
+        };
         Ok(cache)
     }
 
+    /// @acp:summary "Load cache from binary (MessagePack) file"
+    ///
+    /// Fails unless the file starts with [`BINARY_CACHE_MAGIC`].
+    pub fn from_binary<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::read_binary(BufReader::new(File::open(path)?))
+    }
+
+    fn read_binary<R: BufRead>(mut reader: R) -> Result<Self> {
+        let mut magic = [0u8; BINARY_CACHE_MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if magic != BINARY_CACHE_MAGIC {
+            return Err(AcpError::BinaryCache(
+                "missing binary cache header".to_string(),
+            ));
+        }
+        rmp_serde::from_read(reader).map_err(|e| AcpError::BinaryCache(e.to_string()))
+    }
+
+    /// @acp:summary "Write cache to binary (MessagePack) file"
+    ///
+    /// A load-time cache, not an interchange format: fields are written by
+    /// name so optional ones can be skipped, but map order is not fixed and
+    /// `content_hash` is written as stored. JSON stays the canonical form.
+    pub fn write_binary<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        out.write_all(&BINARY_CACHE_MAGIC)?;
+        rmp_serde::encode::write_named(&mut out, self)
+            .map_err(|e| AcpError::BinaryCache(e.to_string()))?;
+        out.flush()?;
+        Ok(())
+    }
+
     /// @acp:summary "Write cache to JSON file"
+    ///
+    /// Streams `files` and `symbols` entry by entry via [`CacheWriter`].
+    /// Paths ending in `.json.gz` are gzip-compressed; the JSON inside stays
+    /// pretty-printed. Paths ending in `.bin` get the binary format instead
+    /// (see [`Cache::write_binary`]). Every JSON map is written in key order,
+    /// so indexing the same tree twice gives the same bytes apart from
+    /// `generated_at`.
     pub fn write_json<P: AsRef<Path>>(&self, path: P) -> Result<()> {
-        let file = File::create(path)?;
-        let writer = BufWriter::new(file);
-        serde_json::to_writer_pretty(writer, self)?;
+        let path = path.as_ref();
+        if path.to_string_lossy().ends_with(BINARY_CACHE_EXTENSION) {
+            self.write_binary(path)?;
+        } else if path.to_string_lossy().ends_with(".json.gz") {
+            let encoder = GzEncoder::new(File::create(path)?, Compression::default());
+            let writer = CacheWriter::new(BufWriter::new(encoder));
+            let buffered = writer.write(self, sorted(&self.files), sorted(&self.symbols))?;
+            buffered
+                .into_inner()
+                .map_err(|e| e.into_error())?
+                .finish()?;
+        } else {
+            CacheWriter::create(path)?.write(self, sorted(&self.files), sorted(&self.symbols))?;
+        }
         Ok(())
     }
 
@@ -151,6 +264,91 @@ impl Cache {
         self.symbols.get(name)
     }
 
+    /// @acp:summary "Symbols whose simple or qualified name matches ignoring case and accents"
+    ///
+    /// Names are compared after [`fold_name`], so `cafe` finds `Café`.
+    /// Results are ordered by file and line. The folded index behind this
+    /// is built on first use, so it does not see symbols added afterwards.
+    pub fn get_symbol_ci(&self, name: &str) -> Vec<&SymbolEntry> {
+        let index = self.symbols_ci.get_or_init(|| {
+            let mut index: HashMap<String, Vec<String>> = HashMap::new();
+            for (key, sym) in &self.symbols {
+                let name = fold_name(&sym.name);
+                let qualified = fold_name(&sym.qualified_name);
+                if qualified != name {
+                    index.entry(qualified).or_default().push(key.clone());
+                }
+                index.entry(name).or_default().push(key.clone());
+            }
+            index
+        });
+        let mut matches: Vec<&SymbolEntry> = index
+            .get(&fold_name(name))
+            .into_iter()
+            .flatten()
+            .filter_map(|key| self.symbols.get(key))
+            .collect();
+        matches.sort_by(|a, b| a.file.cmp(&b.file).then(a.lines[0].cmp(&b.lines[0])));
+        matches
+    }
+
+    /// @acp:summary "Every symbol with this simple name, ordered by file and line"
+    pub fn symbols_named(&self, name: &str) -> Vec<&SymbolEntry> {
+        let mut matches: Vec<&SymbolEntry> =
+            self.symbols.values().filter(|s| s.name == name).collect();
+        matches.sort_by(|a, b| a.file.cmp(&b.file).then(a.lines[0].cmp(&b.lines[0])));
+        matches
+    }
+
+    /// @acp:summary "Look up a symbol by name within a specific file"
+    pub fn get_symbol_in_file(&self, name: &str, file: &str) -> Option<&SymbolEntry> {
+        let file = normalize_path(file);
+        let in_file = |s: &&SymbolEntry| normalize_path(&s.file) == file;
+        self.symbols
+            .get(name)
+            .filter(in_file)
+            .or_else(|| self.symbols.values().find(|s| s.name == name && in_file(s)))
+    }
+
+    /// @acp:summary "Simple names defined more than once, with their counts"
+    ///
+    /// The call graph is keyed by simple name, so callers and callees of
+    /// these symbols cannot be told apart.
+    pub fn ambiguous_symbol_names(&self) -> Vec<(String, usize)> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for sym in self.symbols.values() {
+            *counts.entry(sym.name.as_str()).or_default() += 1;
+        }
+        let mut ambiguous: Vec<(String, usize)> = counts
+            .into_iter()
+            .filter(|(_, n)| *n > 1)
+            .map(|(name, n)| (name.to_string(), n))
+            .collect();
+        ambiguous.sort();
+        ambiguous
+    }
+
+    /// @acp:summary "Groups of domain names that differ only in case or separators"
+    ///
+    /// Each group is sorted; `domains.normalize` would merge its members.
+    pub fn domain_collisions(&self) -> Vec<Vec<String>> {
+        let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for name in self.domains.keys() {
+            groups
+                .entry(DomainNormalize::Kebab.apply(name))
+                .or_default()
+                .push(name.clone());
+        }
+        groups
+            .into_values()
+            .filter(|names| names.len() > 1)
+            .map(|mut names| {
+                names.sort();
+                names
+            })
+            .collect()
+    }
+
     /// @acp:summary "Get a file by path - O(1) lookup with cross-platform path normalization"
     ///
     /// Handles various path formats:
@@ -193,6 +391,24 @@ impl Cache {
         self.graph.as_ref().and_then(|g| g.reverse.get(symbol))
     }
 
+    /// @acp:summary "Get the recorded call sites of a symbol"
+    pub fn get_call_sites(&self, symbol: &str) -> Vec<&CallEdge> {
+        self.graph
+            .iter()
+            .flat_map(|g| &g.edges)
+            .filter(|e| e.to == symbol)
+            .collect()
+    }
+
+    /// @acp:summary "Get the heuristic call edges leaving a symbol"
+    pub fn get_heuristic_callees(&self, symbol: &str) -> Vec<&CallEdge> {
+        self.graph
+            .iter()
+            .flat_map(|g| &g.heuristic_edges)
+            .filter(|e| e.from == symbol)
+            .collect()
+    }
+
     /// @acp:summary "Get callees of a symbol from forward call graph"
     pub fn get_callees(&self, symbol: &str) -> Option<&Vec<String>> {
         self.graph.as_ref().and_then(|g| g.forward.get(symbol))
@@ -224,12 +440,17 @@ impl Cache {
 /// @acp:summary "Builder for incremental cache construction"
 pub struct CacheBuilder {
     cache: Cache,
+    /// Call-site and heuristic edges already added, for O(1) dedupe
+    seen_edges: HashSet<CallEdge>,
+    seen_heuristic_edges: HashSet<CallEdge>,
 }
 
 impl CacheBuilder {
     pub fn new(project_name: &str, root: &str) -> Self {
         Self {
             cache: Cache::new(project_name, root),
+            seen_edges: HashSet::new(),
+            seen_heuristic_edges: HashSet::new(),
         }
     }
 
@@ -239,18 +460,38 @@ impl CacheBuilder {
         self
     }
 
+    /// Add a symbol under its simple name
+    ///
+    /// When another file already holds that name, the symbol is keyed by its
+    /// qualified name instead so neither definition is lost.
     pub fn add_symbol(mut self, symbol: SymbolEntry) -> Self {
-        let name = symbol.name.clone();
-        self.cache.symbols.insert(name, symbol);
+        let key = match self.cache.symbols.get(&symbol.name) {
+            Some(existing) if existing.qualified_name != symbol.qualified_name => {
+                symbol.qualified_name.clone()
+            }
+            _ => symbol.name.clone(),
+        };
+        self.cache.symbols.insert(key, symbol);
         self
     }
 
+    /// @acp:summary "Add calls from one caller, merged with any already recorded"
+    ///
+    /// Replacing would drop edges when a caller is reported more than once
+    /// (per call site, or same-named functions in different files) while the
+    /// reverse graph kept them, leaving the two directions out of step.
     pub fn add_call_edge(mut self, from: &str, to: Vec<String>) -> Self {
         let graph = self.cache.graph.get_or_insert_with(CallGraph::default);
-        graph.forward.insert(from.to_string(), to.clone());
 
-        // Build reverse graph
+        // A caller may be reported once per call site; merge rather than replace
+        let callees = graph.forward.entry(from.to_string()).or_default();
         for callee in to {
+            if callees.contains(&callee) {
+                continue;
+            }
+            callees.push(callee.clone());
+
+            // Build reverse graph
             graph
                 .reverse
                 .entry(callee)
@@ -260,11 +501,42 @@ impl CacheBuilder {
         self
     }
 
+    /// @acp:summary "Add a call edge along with where the call happens"
+    pub fn add_call_site(mut self, edge: CallEdge) -> Self {
+        self = self.add_call_edge(&edge.from, vec![edge.to.clone()]);
+        if self.seen_edges.insert(edge.clone()) {
+            let graph = self.cache.graph.get_or_insert_with(CallGraph::default);
+            graph.edges.push(edge);
+        }
+        self
+    }
+
+    /// @acp:summary "Add a possible call guessed by the heuristic pass"
+    ///
+    /// Dropped when the precise graph already has the same caller and callee.
+    pub fn add_heuristic_edge(mut self, edge: CallEdge) -> Self {
+        let graph = self.cache.graph.get_or_insert_with(CallGraph::default);
+        let known = graph
+            .forward
+            .get(&edge.from)
+            .is_some_and(|callees| callees.contains(&edge.to));
+        if !known && self.seen_heuristic_edges.insert(edge.clone()) {
+            graph.heuristic_edges.push(edge);
+        }
+        self
+    }
+
     pub fn add_source_file(mut self, path: String, modified_at: DateTime<Utc>) -> Self {
         self.cache.source_files.insert(path, modified_at);
         self
     }
 
+    /// Record a file that matched the include patterns but was not parsed
+    pub fn add_skipped_file(mut self, skipped: SkippedFile) -> Self {
+        self.cache.stats.skipped.push(skipped);
+        self
+    }
+
     pub fn add_domain(mut self, domain: DomainEntry) -> Self {
         let name = domain.name.clone();
         self.cache.domains.insert(name, domain);
@@ -283,6 +555,7 @@ impl CacheBuilder {
 
     pub fn build(mut self) -> Cache {
         self.cache.update_stats();
+        self.cache.content_hash = Some(self.cache.compute_content_hash());
         self.cache
     }
 }
@@ -314,6 +587,20 @@ pub struct Stats {
     /// RFC-0015: When the cache was last indexed
     #[serde(skip_serializing_if = "Option::is_none")]
     pub indexed_at: Option<DateTime<Utc>>,
+    /// Matched files left unparsed because they exceed `limits.max_file_size_mb`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<SkippedFile>,
+}
+
+/// @acp:summary "A matched source file that was too large to index"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedFile {
+    /// Path relative to the project root
+    pub path: String,
+    pub size_bytes: u64,
+    /// Line count, streamed rather than read into memory
+    pub lines: usize,
 }
 
 /// @acp:summary "RFC-0015: Language statistics entry"
@@ -337,15 +624,57 @@ pub struct Conventions {
     /// Import/module style conventions
     #[serde(skip_serializing_if = "Option::is_none")]
     pub imports: Option<ImportConventions>,
+    /// Test file naming and placement conventions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tests: Option<TestConventions>,
 }
 
 impl Conventions {
     /// Check if conventions are empty (for serialization skip)
     pub fn is_empty(&self) -> bool {
-        self.file_naming.is_empty() && self.imports.is_none()
+        self.file_naming.is_empty() && self.imports.is_none() && self.tests.is_none()
     }
 }
 
+/// @acp:summary "RFC-0015: Test file naming and placement conventions"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestConventions {
+    /// Total test files detected
+    pub test_files: usize,
+    /// Test naming patterns, most common first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<TestFilePattern>,
+    /// Whether tests sit next to sources or in dedicated folders
+    pub location: TestLocation,
+    /// Dedicated test directories (e.g. `src/__tests__`, `tests`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub test_directories: Vec<String>,
+}
+
+/// @acp:summary "RFC-0015: A test file naming pattern and its share"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestFilePattern {
+    /// Glob-style pattern (e.g., '*.test.ts', 'test_*.py')
+    pub pattern: String,
+    /// Number of test files matching this pattern
+    pub files: usize,
+    /// Percentage of pattern-named test files (0-100)
+    pub percentage: f64,
+}
+
+/// @acp:summary "RFC-0015: Where test files are placed"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TestLocation {
+    /// Next to the code under test
+    Colocated,
+    /// In `__tests__`/`tests`/`test`/`spec` folders
+    Dedicated,
+    Mixed,
+}
+
 /// @acp:summary "RFC-0015: File naming pattern for a directory"
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -377,6 +706,18 @@ pub struct ImportConventions {
     /// Whether index files re-export from subdirectories
     #[serde(default, skip_serializing_if = "is_false")]
     pub index_exports: bool,
+    /// Share of JS/TS imports using the detected module system (0.0-1.0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module_system_confidence: Option<f64>,
+    /// Share of project-internal imports using the detected path style (0.0-1.0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path_style_confidence: Option<f64>,
+    /// Python import statement style
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub python_style: Option<PythonImportStyle>,
+    /// Share of Python imports using the detected style (0.0-1.0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub python_style_confidence: Option<f64>,
 }
 
 /// @acp:summary "RFC-0015: JavaScript/TypeScript module system"
@@ -398,6 +739,17 @@ pub enum PathStyle {
     Mixed,
 }
 
+/// @acp:summary "RFC-0015: Python import statement style"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PythonImportStyle {
+    /// `from x import y`
+    From,
+    /// `import x`
+    Import,
+    Mixed,
+}
+
 /// @acp:summary "File entry with metadata (RFC-001 compliant)"
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileEntry {
@@ -434,6 +786,9 @@ pub struct FileEntry {
     /// Domain classifications (optional)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub domains: Vec<String>,
+    /// Labels from the config's `tags` patterns (e.g. "generated", "vendor")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
     /// Architectural layer (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub layer: Option<String>,
@@ -447,7 +802,11 @@ pub struct FileEntry {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub git: Option<GitFileInfo>,
     /// RFC-0003: Annotation provenance tracking
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted"
+    )]
     pub annotations: HashMap<String, AnnotationProvenance>,
     /// RFC-0006: Bridge metadata for this file
     #[serde(default, skip_serializing_if = "BridgeMetadata::is_empty")]
@@ -473,6 +832,9 @@ pub struct FileEntry {
     /// RFC-0002: Style guide configuration
     #[serde(skip_serializing_if = "Option::is_none")]
     pub style: Option<StyleEntry>,
+    /// Fields this version doesn't know, kept so they survive a rewrite
+    #[serde(flatten, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// @acp:summary "RFC-0002: Documentation reference entry"
@@ -583,7 +945,11 @@ pub struct SymbolEntry {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub git: Option<GitSymbolInfo>,
     /// RFC-0003: Annotation provenance tracking
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted"
+    )]
     pub annotations: HashMap<String, AnnotationProvenance>,
     /// RFC-0009: Behavioral characteristics
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -600,9 +966,55 @@ pub struct SymbolEntry {
     /// RFC-0008: Type annotation information
     #[serde(skip_serializing_if = "Option::is_none")]
     pub type_info: Option<TypeInfo>,
+    /// Exceptions/errors from @acp:throws/@acp:raises and bridged doc comments
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub throws: Vec<ThrowsEntry>,
+    /// Calls itself, directly or through a short cycle (derived from the call graph)
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub recursive: bool,
+    /// Fields this version doesn't know, kept so they survive a rewrite
+    #[serde(flatten, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// @acp:summary "RFC-001: Symbol-level constraint"
+impl SymbolEntry {
+    /// @acp:summary "An exported public function spanning line 1, with nothing else set"
+    ///
+    /// A starting point for struct update syntax, so code that only cares
+    /// about a few fields (tests, benchmarks) need not list all of them:
+    /// `SymbolEntry { lines: [3, 9], ..SymbolEntry::new("login", "src/auth.ts") }`.
+    pub fn new(name: impl Into<String>, file: impl Into<String>) -> Self {
+        let (name, file) = (name.into(), file.into());
+        Self {
+            qualified_name: format!("{}:{}", file, name),
+            name,
+            symbol_type: SymbolType::Function,
+            file,
+            lines: [1, 1],
+            exported: true,
+            signature: None,
+            summary: None,
+            purpose: None,
+            constraints: None,
+            async_fn: false,
+            visibility: Visibility::Public,
+            calls: vec![],
+            called_by: vec![],
+            git: None,
+            annotations: HashMap::new(),
+            behavioral: None,
+            lifecycle: None,
+            documentation: None,
+            performance: None,
+            type_info: None,
+            throws: vec![],
+            recursive: false,
+            extra: serde_json::Map::new(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SymbolConstraint {
     /// Lock level for this symbol
@@ -677,15 +1089,146 @@ pub enum Language {
     Kotlin,
 }
 
+/// @acp:summary "Fold a name for case- and accent-insensitive comparison"
+///
+/// NFKD-decomposes the name, drops combining marks and lowercases the rest,
+/// so `Café`, `CAFÉ` and `cafe` all fold to `cafe`.
+pub fn fold_name(name: &str) -> String {
+    name.nfkd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
 /// @acp:summary "Bidirectional call graph"
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CallGraph {
     /// Forward: caller -> [callees]
-    #[serde(default)]
+    #[serde(default, serialize_with = "serialize_sorted")]
     pub forward: HashMap<String, Vec<String>>,
     /// Reverse: callee -> [callers]
-    #[serde(default)]
+    #[serde(default, serialize_with = "serialize_sorted")]
     pub reverse: HashMap<String, Vec<String>>,
+    /// Call sites, one per call expression (absent in older caches)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub edges: Vec<CallEdge>,
+    /// Possible calls guessed from string literals; kept apart from the
+    /// precise graph (`call_graph.heuristic_edges`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub heuristic_edges: Vec<CallEdge>,
+}
+
+impl CallGraph {
+    /// @acp:summary "Nodes that reach themselves in at most `max_cycle` calls"
+    ///
+    /// A `max_cycle` of 1 finds direct recursion only. The search from each
+    /// node stays inside its strongly connected component, so nodes on no
+    /// cycle cost nothing beyond the component pass.
+    pub fn recursive_nodes(&self, max_cycle: usize) -> HashSet<String> {
+        let mut names: Vec<&str> = self.forward.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        let index: HashMap<&str, usize> = names.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+        let succ: Vec<Vec<usize>> = names
+            .iter()
+            .map(|n| {
+                self.forward[*n]
+                    .iter()
+                    .filter_map(|callee| index.get(callee.as_str()).copied())
+                    .collect()
+            })
+            .collect();
+        let component = strongly_connected(&succ);
+
+        names
+            .iter()
+            .enumerate()
+            .filter(|&(v, _)| {
+                // Breadth-first within the component, up to max_cycle calls deep
+                let mut frontier = vec![v];
+                let mut seen = HashSet::new();
+                for _ in 0..max_cycle {
+                    let mut next = Vec::new();
+                    for u in frontier {
+                        for &w in &succ[u] {
+                            if w == v {
+                                return true;
+                            }
+                            if component[w] == component[v] && seen.insert(w) {
+                                next.push(w);
+                            }
+                        }
+                    }
+                    frontier = next;
+                }
+                false
+            })
+            .map(|(_, name)| name.to_string())
+            .collect()
+    }
+}
+
+/// Component id per node (Kosaraju, iterative so deep graphs can't overflow)
+fn strongly_connected(succ: &[Vec<usize>]) -> Vec<usize> {
+    let n = succ.len();
+    let mut order = Vec::with_capacity(n);
+    let mut seen = vec![false; n];
+    for start in 0..n {
+        if seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let mut stack = vec![(start, 0)];
+        while let Some(top) = stack.last_mut() {
+            let v = top.0;
+            if let Some(&w) = succ[v].get(top.1) {
+                top.1 += 1;
+                if !seen[w] {
+                    seen[w] = true;
+                    stack.push((w, 0));
+                }
+            } else {
+                order.push(v);
+                stack.pop();
+            }
+        }
+    }
+
+    let mut pred = vec![Vec::new(); n];
+    for (v, callees) in succ.iter().enumerate() {
+        for &w in callees {
+            pred[w].push(v);
+        }
+    }
+    let mut component = vec![usize::MAX; n];
+    for &root in order.iter().rev() {
+        if component[root] != usize::MAX {
+            continue;
+        }
+        component[root] = root;
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            for &w in &pred[v] {
+                if component[w] == usize::MAX {
+                    component[w] = root;
+                    stack.push(w);
+                }
+            }
+        }
+    }
+    component
+}
+
+/// @acp:summary "Where one symbol calls another"
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CallEdge {
+    /// Calling symbol
+    pub from: String,
+    /// Called symbol
+    pub to: String,
+    /// File containing the call
+    pub file: String,
+    /// Line of the call (1-indexed)
+    pub line: usize,
 }
 
 /// @acp:summary "Domain grouping (schema-compliant)"
@@ -701,6 +1244,22 @@ pub struct DomainEntry {
     /// Human description (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// How each file joined the domain
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub membership: BTreeMap<String, DomainSource>,
+    /// Spellings merged into this name by `domains.normalize`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+}
+
+/// @acp:summary "Why a file belongs to a domain"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DomainSource {
+    /// Explicit `@acp:domain` annotation (takes precedence)
+    Annotation,
+    /// Path matched a `domains` pattern in the config
+    Pattern,
 }
 
 // ============================================================================
@@ -898,7 +1457,11 @@ pub struct BridgeStats {
     /// Summary statistics
     pub summary: BridgeSummary,
     /// Counts by source format
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted"
+    )]
     pub by_format: HashMap<String, u64>,
 }
 
@@ -1224,7 +1787,11 @@ pub struct ProvenanceSummary {
     /// Count already reviewed
     pub reviewed: u64,
     /// Average confidence by source type
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted"
+    )]
     pub average_confidence: HashMap<String, f64>,
 }
 
@@ -1263,7 +1830,7 @@ pub struct LowConfidenceEntry {
 }
 
 /// Information about a generation run (RFC-0003)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GenerationInfo {
     /// Unique batch identifier
@@ -1284,29 +1851,9 @@ mod tests {
     fn test_cache_roundtrip() {
         let cache = CacheBuilder::new("test", "/test")
             .add_symbol(SymbolEntry {
-                name: "test_fn".to_string(),
-                qualified_name: "test.rs:test_fn".to_string(),
-                symbol_type: SymbolType::Function,
-                file: "test.rs".to_string(),
                 lines: [1, 10],
-                exported: true,
-                signature: None,
                 summary: Some("Test function".to_string()),
-                purpose: None,
-                constraints: None,
-                async_fn: false,
-                visibility: Visibility::Public,
-                calls: vec![],
-                called_by: vec![],
-                git: None,
-                annotations: HashMap::new(), // RFC-0003
-                // RFC-0009: Extended annotation types
-                behavioral: None,
-                lifecycle: None,
-                documentation: None,
-                performance: None,
-                // RFC-0008: Type annotation info
-                type_info: None,
+                ..SymbolEntry::new("test_fn", "test.rs")
             })
             .build();
 
@@ -1317,6 +1864,124 @@ mod tests {
         assert!(parsed.symbols.contains_key("test_fn"));
     }
 
+    #[test]
+    fn test_duplicate_symbol_names_kept() {
+        let symbol = |file: &str, line: usize| SymbolEntry {
+            lines: [line, line + 5],
+            ..SymbolEntry::new("handler", file)
+        };
+        let cache = CacheBuilder::new("test", "/test")
+            .add_symbol(symbol("src/b.ts", 1))
+            .add_symbol(symbol("src/a.ts", 10))
+            // Re-adding the same definition replaces it
+            .add_symbol(symbol("src/a.ts", 10))
+            .build();
+
+        assert_eq!(cache.stats.symbols, 2);
+        assert_eq!(cache.symbols["handler"].file, "src/b.ts");
+        assert_eq!(cache.symbols["src/a.ts:handler"].file, "src/a.ts");
+
+        let files: Vec<_> = cache
+            .symbols_named("handler")
+            .iter()
+            .map(|s| s.file.as_str())
+            .collect();
+        assert_eq!(files, vec!["src/a.ts", "src/b.ts"]);
+        assert_eq!(
+            cache
+                .get_symbol_in_file("handler", "src/a.ts")
+                .unwrap()
+                .lines[0],
+            10
+        );
+        assert_eq!(
+            cache.ambiguous_symbol_names(),
+            vec![("handler".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn test_recursive_nodes_within_cycle_length() {
+        let graph = CallGraph {
+            forward: [
+                ("walk", vec!["walk", "log"]),
+                ("even", vec!["odd"]),
+                ("odd", vec!["even"]),
+                ("a", vec!["b"]),
+                ("b", vec!["c"]),
+                ("c", vec!["d"]),
+                ("d", vec!["a"]),
+                ("main", vec!["walk", "even", "a"]),
+            ]
+            .into_iter()
+            .map(|(from, to)| (from.to_string(), to.into_iter().map(String::from).collect()))
+            .collect(),
+            ..Default::default()
+        };
+
+        let mut direct: Vec<_> = graph.recursive_nodes(1).into_iter().collect();
+        direct.sort();
+        assert_eq!(direct, vec!["walk"]);
+
+        let mut short: Vec<_> = graph.recursive_nodes(3).into_iter().collect();
+        short.sort();
+        assert_eq!(short, vec!["even", "odd", "walk"]);
+
+        assert_eq!(graph.recursive_nodes(4).len(), 7);
+    }
+
+    #[test]
+    fn test_get_symbol_ci_matches_every_case() {
+        let symbol = |name: &str, file: &str| SymbolEntry {
+            lines: [1, 5],
+            ..SymbolEntry::new(name, file)
+        };
+        let cache = CacheBuilder::new("test", "/test")
+            .add_symbol(symbol("parseURL", "src/url.ts"))
+            .add_symbol(symbol("parseUrl", "src/legacy.ts"))
+            .add_symbol(symbol("ParseUrlOptions", "src/url.ts"))
+            .build();
+
+        let files: Vec<_> = cache
+            .get_symbol_ci("PARSEurl")
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(files, vec!["parseUrl", "parseURL"]);
+        assert_eq!(cache.get_symbol_ci("SRC/URL.TS:PARSEURL").len(), 1);
+        assert!(cache.get_symbol_ci("parse").is_empty());
+        assert!(cache.get_symbol("PARSEurl").is_none());
+    }
+
+    #[test]
+    fn test_get_symbol_ci_ignores_accents() {
+        let cache = CacheBuilder::new("test", "/test")
+            .add_symbol(SymbolEntry::new("créerCafé", "src/menu.ts"))
+            .build();
+
+        assert_eq!(cache.get_symbol_ci("creercafe").len(), 1);
+        assert_eq!(cache.get_symbol_ci("CRÉERCAFÉ").len(), 1);
+        // Decomposed input (e + combining acute) folds the same way
+        assert_eq!(cache.get_symbol_ci("cre\u{301}ercafe\u{301}").len(), 1);
+        assert_eq!(fold_name("Ǆemal"), "dzemal");
+    }
+
+    #[test]
+    fn test_call_edges_merge_per_caller() {
+        // Same-named callers in two files report their calls separately
+        let cache = CacheBuilder::new("test", "/test")
+            .add_call_edge("init", vec!["load".into(), "log".into()])
+            .add_call_edge("init", vec!["log".into(), "save".into()])
+            .build();
+        let graph = cache.graph.unwrap();
+
+        assert_eq!(graph.forward["init"], vec!["load", "log", "save"]);
+        // Reverse stays in step with forward: no dropped or doubled callers
+        for callee in ["load", "log", "save"] {
+            assert_eq!(graph.reverse[callee], vec!["init"]);
+        }
+    }
+
     // ========================================================================
     // Path Normalization Tests
     // ========================================================================
@@ -1398,6 +2063,7 @@ mod tests {
                 owner: None,
                 inline: vec![],
                 domains: vec![],
+                tags: vec![],
                 layer: None,
                 stability: None,
                 ai_hints: vec![],
@@ -1413,6 +2079,7 @@ mod tests {
                 // RFC-0002: Documentation references and style
                 refs: vec![],
                 style: None,
+                extra: serde_json::Map::new(),
             },
         );
         cache
@@ -1482,6 +2149,7 @@ mod tests {
                 owner: None,
                 inline: vec![],
                 domains: vec![],
+                tags: vec![],
                 layer: None,
                 stability: None,
                 ai_hints: vec![],
@@ -1497,6 +2165,7 @@ mod tests {
                 // RFC-0002: Documentation references and style
                 refs: vec![],
                 style: None,
+                extra: serde_json::Map::new(),
             },
         );
 
@@ -1505,4 +2174,136 @@ mod tests {
         assert!(cache.get_file("./src/sample.ts").is_some());
         assert!(cache.get_file("src\\sample.ts").is_some());
     }
+
+    #[test]
+    fn test_gzip_roundtrip_and_magic_detection() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = Cache::new("gz", ".");
+        cache.files.insert(
+            "src/a.ts".to_string(),
+            serde_json::from_value(serde_json::json!({
+                "path": "src/a.ts",
+                "lines": 3,
+                "language": "typescript",
+            }))
+            .unwrap(),
+        );
+
+        let gz_path = dir.path().join("cache.json.gz");
+        cache.write_json(&gz_path).unwrap();
+        let bytes = std::fs::read(&gz_path).unwrap();
+        assert!(bytes.starts_with(&GZIP_MAGIC));
+
+        // Pretty-printing is preserved inside the gzip stream
+        let mut inflated = String::new();
+        std::io::Read::read_to_string(&mut GzDecoder::new(&bytes[..]), &mut inflated).unwrap();
+        assert!(inflated.starts_with("{\n  \"$schema\""));
+
+        let loaded = Cache::from_json(&gz_path).unwrap();
+        assert!(loaded.files.contains_key("src/a.ts"));
+
+        // A renamed compressed file still loads
+        let renamed = dir.path().join("cache.json");
+        std::fs::rename(&gz_path, &renamed).unwrap();
+        assert_eq!(Cache::from_json(&renamed).unwrap().project.name, "gz");
+
+        // Plain .json output is unchanged
+        let plain = dir.path().join("plain.json");
+        cache.write_json(&plain).unwrap();
+        assert!(std::fs::read(&plain).unwrap().starts_with(b"{"));
+        assert_eq!(Cache::from_json(&plain).unwrap().files.len(), 1);
+    }
+
+    #[test]
+    fn test_binary_roundtrip_matches_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = Cache::new("bin", ".");
+        cache.files.insert(
+            "src/a.ts".to_string(),
+            serde_json::from_value(serde_json::json!({
+                "path": "src/a.ts",
+                "lines": 3,
+                "language": "typescript",
+                "summary": "Auth helpers",
+                "inline": [{"type": "todo", "value": "Retry", "line": 2, "directive": "Retry twice"}],
+            }))
+            .unwrap(),
+        );
+        cache.symbols.insert(
+            "login".to_string(),
+            SymbolEntry {
+                lines: [1, 3],
+                calls: vec!["refresh".to_string()],
+                ..SymbolEntry::new("login", "src/a.ts")
+            },
+        );
+        cache.update_stats();
+
+        let bin_path = dir.path().join(".acp.cache.bin");
+        cache.write_json(&bin_path).unwrap();
+        assert!(std::fs::read(&bin_path)
+            .unwrap()
+            .starts_with(&BINARY_CACHE_MAGIC));
+
+        let loaded = Cache::from_binary(&bin_path).unwrap();
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(&cache).unwrap()
+        );
+
+        // Detected by magic bytes whatever the extension
+        let renamed = dir.path().join("cache.json");
+        std::fs::rename(&bin_path, &renamed).unwrap();
+        assert_eq!(Cache::from_json(&renamed).unwrap().symbols.len(), 1);
+
+        // JSON is not mistaken for binary
+        let plain = dir.path().join("plain.json");
+        cache.write_json(&plain).unwrap();
+        assert!(Cache::from_binary(&plain).is_err());
+    }
+
+    #[test]
+    fn test_unknown_fields_survive_rewrite() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut json = serde_json::to_value(Cache::new("future", ".")).unwrap();
+        json["ownership"] = serde_json::json!({"team": "payments"});
+        json["files"]["src/a.ts"] = serde_json::json!({
+            "path": "src/a.ts",
+            "lines": 3,
+            "language": "typescript",
+            "owner": "@payments",
+        });
+        json["symbols"]["login"] = serde_json::json!({
+            "name": "login",
+            "qualified_name": "src/a.ts:login",
+            "type": "function",
+            "file": "src/a.ts",
+            "lines": [1, 3],
+            "exported": true,
+            "complexity": {"cyclomatic": 4},
+        });
+        let path = dir.path().join("cache.json");
+        std::fs::write(&path, serde_json::to_string(&json).unwrap()).unwrap();
+
+        let mut cache = Cache::from_json(&path).unwrap();
+        assert_eq!(cache.extra["ownership"]["team"], "payments");
+        cache.update_stats();
+        for name in ["cache.json", "cache.json.gz", ".acp.cache.bin"] {
+            let out = dir.path().join(format!("out-{}", name));
+            cache.write_json(&out).unwrap();
+            let reloaded = serde_json::to_value(Cache::from_json(&out).unwrap()).unwrap();
+            assert_eq!(reloaded["ownership"], json["ownership"], "{}", name);
+            assert_eq!(reloaded["files"]["src/a.ts"]["owner"], "@payments");
+            assert_eq!(
+                reloaded["symbols"]["login"]["complexity"]["cyclomatic"], 4,
+                "{}",
+                name
+            );
+            assert_eq!(reloaded["stats"]["symbols"], 1);
+        }
+
+        // Nothing extra is written for a cache without unknown fields
+        let plain = serde_json::to_string(&Cache::new("plain", ".")).unwrap();
+        assert!(!plain.contains("extra"));
+    }
 }
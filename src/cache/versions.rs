@@ -0,0 +1,232 @@
+//! @acp:module "Version Check"
+//! @acp:summary "Verifies @acp:version and @acp:since values recorded in a cache"
+//! @acp:domain cli
+//! @acp:layer model
+//!
+//! Versions are parsed as semantic versions, with an optional leading `v`.
+//! A symbol's `since` is compared against its file's `@acp:version`, and a
+//! file's own `since` against the same; both are also compared against the
+//! project version when one is configured. A `since` copied from a newer
+//! API surface then shows up as introduced after the code it belongs to.
+
+use semver::Version;
+
+use super::types::Cache;
+
+/// @acp:summary "A version annotation that is malformed or out of order"
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionProblem {
+    pub file: String,
+    /// First line of the symbol; absent for file-level annotations
+    pub line: Option<usize>,
+    /// Qualified name of the symbol carrying the annotation
+    pub symbol: Option<String>,
+    /// `@acp:version` or `@acp:since`
+    pub annotation: &'static str,
+    pub value: String,
+    pub reason: String,
+}
+
+/// @acp:summary "Result of checking every version annotation in a cache"
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VersionReport {
+    /// Number of version values examined
+    pub checked: usize,
+    /// Problems, sorted by file and line
+    pub problems: Vec<VersionProblem>,
+}
+
+impl VersionReport {
+    /// @acp:summary "Check file and symbol version/since values as semver"
+    ///
+    /// `project` is the project's own version (`documentation.projectVersion`),
+    /// which no `since` may exceed.
+    pub fn check(cache: &Cache, project: Option<&Version>) -> Self {
+        let mut report = Self::default();
+
+        for file in cache.files.values() {
+            let version = file
+                .version
+                .as_deref()
+                .and_then(|v| report.parse(&file.path, None, None, "@acp:version", v));
+            let lifecycle_since = file.lifecycle.as_ref().and_then(|l| l.since.as_deref());
+            // `since` is stored on both the file and its lifecycle; check it once
+            for since in file
+                .since
+                .as_deref()
+                .into_iter()
+                .chain(lifecycle_since.filter(|s| Some(*s) != file.since.as_deref()))
+            {
+                report.check_since(&file.path, None, None, since, version.as_ref(), project);
+            }
+        }
+
+        for symbol in cache.symbols.values() {
+            let Some(since) = symbol.lifecycle.as_ref().and_then(|l| l.since.as_deref()) else {
+                continue;
+            };
+            let version = cache
+                .get_file(&symbol.file)
+                .and_then(|f| f.version.as_deref())
+                .and_then(|v| parse_version(v).ok());
+            report.check_since(
+                &symbol.file,
+                Some(symbol.lines[0]),
+                Some(&symbol.qualified_name),
+                since,
+                version.as_ref(),
+                project,
+            );
+        }
+
+        report.problems.sort_by(|a, b| {
+            (&a.file, a.line, &a.symbol, a.annotation).cmp(&(
+                &b.file,
+                b.line,
+                &b.symbol,
+                b.annotation,
+            ))
+        });
+        report
+    }
+
+    /// @acp:summary "True when every version parsed and was in order"
+    pub fn is_clean(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    fn check_since(
+        &mut self,
+        file: &str,
+        line: Option<usize>,
+        symbol: Option<&str>,
+        since: &str,
+        version: Option<&Version>,
+        project: Option<&Version>,
+    ) {
+        let Some(parsed) = self.parse(file, line, symbol, "@acp:since", since) else {
+            return;
+        };
+        let reason = if let Some(version) = version.filter(|v| parsed > **v) {
+            format!("newer than the file's @acp:version {}", version)
+        } else if let Some(project) = project.filter(|v| parsed > **v) {
+            format!("newer than the project version {}", project)
+        } else {
+            return;
+        };
+        self.push(file, line, symbol, "@acp:since", since, reason);
+    }
+
+    /// Count and parse a value, recording it as a problem when malformed
+    fn parse(
+        &mut self,
+        file: &str,
+        line: Option<usize>,
+        symbol: Option<&str>,
+        annotation: &'static str,
+        value: &str,
+    ) -> Option<Version> {
+        self.checked += 1;
+        match parse_version(value) {
+            Ok(version) => Some(version),
+            Err(e) => {
+                self.push(file, line, symbol, annotation, value, e.to_string());
+                None
+            }
+        }
+    }
+
+    fn push(
+        &mut self,
+        file: &str,
+        line: Option<usize>,
+        symbol: Option<&str>,
+        annotation: &'static str,
+        value: &str,
+        reason: String,
+    ) {
+        self.problems.push(VersionProblem {
+            file: file.to_string(),
+            line,
+            symbol: symbol.map(str::to_string),
+            annotation,
+            value: value.to_string(),
+            reason,
+        });
+    }
+}
+
+/// @acp:summary "Parse a semantic version, allowing a leading `v`"
+pub fn parse_version(value: &str) -> Result<Version, semver::Error> {
+    let value = value.trim();
+    Version::parse(value.strip_prefix('v').unwrap_or(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{CacheBuilder, FileEntry, LifecycleAnnotations, SymbolEntry};
+    use serde_json::json;
+
+    fn symbol(name: &str, since: &str) -> SymbolEntry {
+        SymbolEntry {
+            lines: [7, 12],
+            lifecycle: Some(LifecycleAnnotations {
+                since: Some(since.to_string()),
+                ..Default::default()
+            }),
+            ..SymbolEntry::new(name, "src/api.ts")
+        }
+    }
+
+    #[test]
+    fn test_versions_parse_and_since_not_after_file_version() {
+        let file: FileEntry = serde_json::from_value(json!({
+            "path": "src/api.ts",
+            "lines": 40,
+            "language": "typescript",
+            "exports": [],
+            "imports": [],
+            "version": "v2.1.0",
+            "since": "1.0.0"
+        }))
+        .unwrap();
+        let cache = CacheBuilder::new("test", ".")
+            .add_file(file)
+            .add_symbol(symbol("create", "2.0.0"))
+            .add_symbol(symbol("update", "2.3.0"))
+            .add_symbol(symbol("remove", "1.2"))
+            .build();
+
+        let report = VersionReport::check(&cache, None);
+        assert_eq!(report.checked, 5);
+        let problems: Vec<_> = report
+            .problems
+            .iter()
+            .map(|p| (p.symbol.as_deref(), p.value.as_str()))
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                (Some("src/api.ts:remove"), "1.2"),
+                (Some("src/api.ts:update"), "2.3.0"),
+            ]
+        );
+        assert_eq!(
+            report.problems[1].reason,
+            "newer than the file's @acp:version 2.1.0"
+        );
+        assert_eq!(report.problems[0].line, Some(7));
+
+        // A since within the file's version can still outrun the project's
+        let project = parse_version("1.5.0").unwrap();
+        let report = VersionReport::check(&cache, Some(&project));
+        let create = report
+            .problems
+            .iter()
+            .find(|p| p.symbol.as_deref() == Some("src/api.ts:create"))
+            .unwrap();
+        assert_eq!(create.reason, "newer than the project version 1.5.0");
+        assert_eq!(report.problems.len(), 3);
+    }
+}
@@ -0,0 +1,533 @@
+//! @acp:module "Cache Writer"
+//! @acp:summary "Streams cache documents to disk entry by entry"
+//! @acp:domain cli
+//! @acp:layer model
+//!
+//! `files` and `symbols` dominate the size of a cache. [`CacheWriter`] takes
+//! them as iterators and serializes one entry at a time, so a producer can
+//! generate entries lazily and never hold the full maps in memory. Given
+//! entries in key order, output is byte-identical to
+//! `serde_json::to_writer_pretty` on an equivalent [`Cache`] whose
+//! `content_hash` is up to date. Every other field is written through
+//! `Cache`'s own `Serialize` impl, so new fields need no changes here.
+
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use serde::ser::{Impossible, SerializeMap, SerializeStruct, Serializer};
+use serde::Serialize;
+use serde_json::ser::Formatter;
+
+use super::types::{Cache, FileEntry, SymbolEntry};
+use crate::error::Result;
+
+/// @acp:summary "Incremental writer for cache JSON documents"
+pub struct CacheWriter<W: Write> {
+    out: W,
+    /// Top-level fields written so far
+    fields: usize,
+}
+
+impl CacheWriter<BufWriter<File>> {
+    /// Create a writer for a file path
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> CacheWriter<W> {
+    /// Wrap any writer; callers should supply a buffered one
+    pub fn new(out: W) -> Self {
+        Self { out, fields: 0 }
+    }
+
+    /// Write a complete cache document and return the flushed writer
+    ///
+    /// `header` supplies every field except `files` and `symbols`; its own
+    /// maps are ignored in favour of the iterators, and a stored
+    /// `content_hash` is recomputed from its `source_files`. Entries are
+    /// written in iteration order.
+    pub fn write<F, FK, FV, S, SK, SV>(mut self, header: &Cache, files: F, symbols: S) -> Result<W>
+    where
+        F: IntoIterator<Item = (FK, FV)>,
+        FK: AsRef<str>,
+        FV: Borrow<FileEntry>,
+        S: IntoIterator<Item = (SK, SV)>,
+        SK: AsRef<str>,
+        SV: Borrow<SymbolEntry>,
+    {
+        self.out.write_all(b"{")?;
+
+        // Field order and skip rules come from `Cache`'s own Serialize impl
+        let mut fields = HeaderFields {
+            writer: &mut self,
+            content_hash: header.compute_content_hash(),
+            files: Some(|w: &mut Self| w.map::<_, _, _, FileEntry>("files", files)),
+            symbols: Some(|w: &mut Self| w.map::<_, _, _, SymbolEntry>("symbols", symbols)),
+            key: None,
+        };
+        header.serialize(&mut fields)?;
+
+        self.out
+            .write_all(if self.fields > 0 { b"\n}" } else { b"}" })?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    /// Write one top-level `"key": value` pair
+    fn field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<()> {
+        self.key(key)?;
+        write_value(&mut self.out, value, 1)?;
+        Ok(())
+    }
+
+    /// Write a top-level object, serializing one entry at a time
+    fn map<I, K, V, T>(&mut self, key: &str, entries: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Borrow<T>,
+        T: Serialize,
+    {
+        self.key(key)?;
+        self.out.write_all(b"{")?;
+        let mut count = 0;
+        for (k, v) in entries {
+            self.out
+                .write_all(if count == 0 { b"\n" } else { b",\n" })?;
+            indent(&mut self.out, 2)?;
+            serde_json::to_writer(&mut self.out, k.as_ref())?;
+            self.out.write_all(b": ")?;
+            write_value(&mut self.out, v.borrow(), 2)?;
+            count += 1;
+        }
+        if count > 0 {
+            self.out.write_all(b"\n")?;
+            indent(&mut self.out, 1)?;
+        }
+        self.out.write_all(b"}")?;
+        Ok(())
+    }
+
+    fn key(&mut self, key: &str) -> Result<()> {
+        self.out
+            .write_all(if self.fields == 0 { b"\n" } else { b",\n" })?;
+        indent(&mut self.out, 1)?;
+        serde_json::to_writer(&mut self.out, key)?;
+        self.out.write_all(b": ")?;
+        self.fields += 1;
+        Ok(())
+    }
+}
+
+/// Serializer that receives `Cache`'s top-level fields and writes them with
+/// a [`CacheWriter`], streaming `files` and `symbols` from closures in place
+/// of the header's own maps
+struct HeaderFields<'w, W: Write, F, S> {
+    writer: &'w mut CacheWriter<W>,
+    content_hash: String,
+    files: Option<F>,
+    symbols: Option<S>,
+    /// Key of the map entry being serialized
+    key: Option<String>,
+}
+
+impl<W: Write, F, S> HeaderFields<'_, W, F, S>
+where
+    F: FnOnce(&mut CacheWriter<W>) -> Result<()>,
+    S: FnOnce(&mut CacheWriter<W>) -> Result<()>,
+{
+    fn field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<()> {
+        match key {
+            "files" => self.files.take().map_or(Ok(()), |f| f(self.writer)),
+            "symbols" => self.symbols.take().map_or(Ok(()), |s| s(self.writer)),
+            // Always recomputed so the saved hash matches the saved source_files
+            "content_hash" => self.writer.field(key, &self.content_hash),
+            _ => self.writer.field(key, value),
+        }
+    }
+}
+
+type SerResult<T> = std::result::Result<T, serde_json::Error>;
+
+fn not_a_header() -> serde_json::Error {
+    serde::ser::Error::custom("a cache header must serialize as a map or struct")
+}
+
+impl<W: Write, F, S> Serializer for &mut HeaderFields<'_, W, F, S>
+where
+    F: FnOnce(&mut CacheWriter<W>) -> Result<()>,
+    S: FnOnce(&mut CacheWriter<W>) -> Result<()>,
+{
+    type Ok = ();
+    type Error = serde_json::Error;
+    type SerializeSeq = Impossible<(), serde_json::Error>;
+    type SerializeTuple = Impossible<(), serde_json::Error>;
+    type SerializeTupleStruct = Impossible<(), serde_json::Error>;
+    type SerializeTupleVariant = Impossible<(), serde_json::Error>;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<(), serde_json::Error>;
+
+    fn serialize_map(self, _len: Option<usize>) -> SerResult<Self> {
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> SerResult<Self> {
+        Ok(self)
+    }
+
+    fn serialize_bool(self, _: bool) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_i8(self, _: i8) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_i16(self, _: i16) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_i32(self, _: i32) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_i64(self, _: i64) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_u8(self, _: u8) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_u16(self, _: u16) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_u32(self, _: u32) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_u64(self, _: u64) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_f32(self, _: f32) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_f64(self, _: f64) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_char(self, _: char) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_str(self, _: &str) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_bytes(self, _: &[u8]) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_none(self) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_some<T: Serialize + ?Sized>(self, _: &T) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_unit(self) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_unit_struct(self, _: &'static str) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_unit_variant(self, _: &'static str, _: u32, _: &'static str) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: &T,
+    ) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> SerResult<()> {
+        Err(not_a_header())
+    }
+    fn serialize_seq(self, _: Option<usize>) -> SerResult<Self::SerializeSeq> {
+        Err(not_a_header())
+    }
+    fn serialize_tuple(self, _: usize) -> SerResult<Self::SerializeTuple> {
+        Err(not_a_header())
+    }
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> SerResult<Self::SerializeTupleStruct> {
+        Err(not_a_header())
+    }
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> SerResult<Self::SerializeTupleVariant> {
+        Err(not_a_header())
+    }
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> SerResult<Self::SerializeStructVariant> {
+        Err(not_a_header())
+    }
+}
+
+// `#[serde(flatten)]` on `Cache::extra` makes the derive use a map
+impl<W: Write, F, S> SerializeMap for &mut HeaderFields<'_, W, F, S>
+where
+    F: FnOnce(&mut CacheWriter<W>) -> Result<()>,
+    S: FnOnce(&mut CacheWriter<W>) -> Result<()>,
+{
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> SerResult<()> {
+        match serde_json::to_value(key)? {
+            serde_json::Value::String(key) => self.key = Some(key),
+            _ => return Err(serde::ser::Error::custom("cache keys must be strings")),
+        }
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> SerResult<()> {
+        let key = self.key.take().unwrap_or_default();
+        self.field(&key, value).map_err(serde::ser::Error::custom)
+    }
+
+    fn end(self) -> SerResult<()> {
+        Ok(())
+    }
+}
+
+impl<W: Write, F, S> SerializeStruct for &mut HeaderFields<'_, W, F, S>
+where
+    F: FnOnce(&mut CacheWriter<W>) -> Result<()>,
+    S: FnOnce(&mut CacheWriter<W>) -> Result<()>,
+{
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> SerResult<()> {
+        self.field(key, value).map_err(serde::ser::Error::custom)
+    }
+
+    fn end(self) -> SerResult<()> {
+        Ok(())
+    }
+}
+
+/// A map's entries in key order
+pub(super) fn sorted<V>(map: &HashMap<String, V>) -> BTreeMap<&String, &V> {
+    map.iter().collect()
+}
+
+/// Serialize a value pretty-printed as if nested `depth` levels deep
+fn write_value<W: Write, T: Serialize + ?Sized>(
+    out: &mut W,
+    value: &T,
+    depth: usize,
+) -> Result<()> {
+    let mut ser = serde_json::Serializer::with_formatter(out, NestedFormatter::new(depth));
+    value.serialize(&mut ser)?;
+    Ok(())
+}
+
+fn indent<W: Write + ?Sized>(out: &mut W, depth: usize) -> io::Result<()> {
+    for _ in 0..depth {
+        out.write_all(b"  ")?;
+    }
+    Ok(())
+}
+
+/// Pretty formatter that starts at a given indentation depth
+///
+/// Same output as `serde_json::ser::PrettyFormatter`, which always starts at
+/// depth zero.
+struct NestedFormatter {
+    depth: usize,
+    has_value: bool,
+}
+
+impl NestedFormatter {
+    fn new(depth: usize) -> Self {
+        Self {
+            depth,
+            has_value: false,
+        }
+    }
+
+    fn open<W: Write + ?Sized>(&mut self, out: &mut W, bracket: &[u8]) -> io::Result<()> {
+        self.depth += 1;
+        self.has_value = false;
+        out.write_all(bracket)
+    }
+
+    fn close<W: Write + ?Sized>(&mut self, out: &mut W, bracket: &[u8]) -> io::Result<()> {
+        self.depth -= 1;
+        if self.has_value {
+            out.write_all(b"\n")?;
+            indent(out, self.depth)?;
+        }
+        out.write_all(bracket)
+    }
+
+    fn item<W: Write + ?Sized>(&mut self, out: &mut W, first: bool) -> io::Result<()> {
+        out.write_all(if first { b"\n" } else { b",\n" })?;
+        indent(out, self.depth)
+    }
+}
+
+impl Formatter for NestedFormatter {
+    fn begin_array<W: Write + ?Sized>(&mut self, out: &mut W) -> io::Result<()> {
+        self.open(out, b"[")
+    }
+
+    fn end_array<W: Write + ?Sized>(&mut self, out: &mut W) -> io::Result<()> {
+        self.close(out, b"]")
+    }
+
+    fn begin_array_value<W: Write + ?Sized>(&mut self, out: &mut W, first: bool) -> io::Result<()> {
+        self.item(out, first)
+    }
+
+    fn end_array_value<W: Write + ?Sized>(&mut self, _out: &mut W) -> io::Result<()> {
+        self.has_value = true;
+        Ok(())
+    }
+
+    fn begin_object<W: Write + ?Sized>(&mut self, out: &mut W) -> io::Result<()> {
+        self.open(out, b"{")
+    }
+
+    fn end_object<W: Write + ?Sized>(&mut self, out: &mut W) -> io::Result<()> {
+        self.close(out, b"}")
+    }
+
+    fn begin_object_key<W: Write + ?Sized>(&mut self, out: &mut W, first: bool) -> io::Result<()> {
+        self.item(out, first)
+    }
+
+    fn begin_object_value<W: Write + ?Sized>(&mut self, out: &mut W) -> io::Result<()> {
+        out.write_all(b": ")
+    }
+
+    fn end_object_value<W: Write + ?Sized>(&mut self, _out: &mut W) -> io::Result<()> {
+        self.has_value = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::DomainEntry;
+
+    fn file(path: &str) -> FileEntry {
+        serde_json::from_value(serde_json::json!({
+            "path": path,
+            "lines": 42,
+            "language": "typescript",
+            "exports": ["a", "b"],
+        }))
+        .unwrap()
+    }
+
+    fn symbol(name: &str, file: &str) -> SymbolEntry {
+        SymbolEntry {
+            lines: [1, 10],
+            summary: Some("Does a thing".to_string()),
+            calls: vec!["other".to_string()],
+            ..SymbolEntry::new(name, file)
+        }
+    }
+
+    fn stream(cache: &Cache) -> String {
+        let out = CacheWriter::new(Vec::new())
+            .write(cache, sorted(&cache.files), sorted(&cache.symbols))
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_matches_serde_pretty_output() {
+        let mut cache = Cache::new("demo", ".");
+        cache.git_commit = Some("abc123".to_string());
+        cache
+            .source_files
+            .insert("src/file0.ts".to_string(), chrono::Utc::now());
+        cache.content_hash = Some(cache.compute_content_hash());
+        for i in 0..5 {
+            let path = format!("src/file{}.ts", i);
+            cache.files.insert(path.clone(), file(&path));
+            cache
+                .symbols
+                .insert(format!("fn{}", i), symbol(&format!("fn{}", i), &path));
+        }
+        cache.domains.insert(
+            "auth".to_string(),
+            DomainEntry {
+                name: "auth".to_string(),
+                files: vec!["src/file0.ts".to_string()],
+                symbols: vec![],
+                description: None,
+                membership: Default::default(),
+                aliases: vec![],
+            },
+        );
+        // Unknown top-level fields are kept and written like any other
+        cache
+            .extra
+            .insert("x-generator".to_string(), serde_json::json!("demo"));
+        assert_eq!(
+            stream(&cache),
+            serde_json::to_string_pretty(&cache).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_empty_cache_matches_and_round_trips() {
+        let mut cache = Cache::new("empty", ".");
+        cache.content_hash = Some(cache.compute_content_hash());
+        let json = stream(&cache);
+        assert_eq!(json, serde_json::to_string_pretty(&cache).unwrap());
+
+        let parsed: Cache = serde_json::from_str(&json).unwrap();
+        assert!(parsed.files.is_empty());
+        assert_eq!(parsed.project.name, "empty");
+    }
+
+    #[test]
+    fn test_streams_owned_entries_from_iterators() {
+        let header = Cache::new("lazy", ".");
+        let files = (0..3).map(|i| {
+            let path = format!("src/{}.ts", i);
+            (path.clone(), file(&path))
+        });
+        let symbols = (0..3).map(|i| (format!("s{}", i), symbol(&format!("s{}", i), "src/0.ts")));
+
+        let out = CacheWriter::new(Vec::new())
+            .write(&header, files, symbols)
+            .unwrap();
+        let parsed: Cache = serde_json::from_slice(&out).unwrap();
+        assert_eq!(parsed.files.len(), 3);
+        assert_eq!(parsed.symbols["s2"].file, "src/0.ts");
+    }
+}
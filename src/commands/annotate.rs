@@ -6,8 +6,8 @@
 //! Implements `acp annotate` command for annotation analysis and generation.
 //! Supports RFC-0003 annotation provenance tracking.
 
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::Result;
@@ -17,10 +17,14 @@ use rand::Rng;
 use rayon::prelude::*;
 
 use crate::annotate::{
-    Analyzer, AnnotateLevel, ConversionSource, OutputFormat, ProvenanceConfig, Suggester, Writer,
+    find_undoable, undo_generation, AnalysisResult, Analyzer, AnnotateLevel, ApplyOutcome,
+    ConfidenceDistribution, ConversionSource, FileChange, GenerationLog, GenerationRecord,
+    OutputFormat, ProvenanceConfig, Suggester, Suggestion, Writer,
 };
+use crate::cache::GenerationInfo;
 use crate::config::Config;
 use crate::git::GitRepository;
+use crate::globs::MATCH_OPTIONS;
 
 /// Options for the annotate command
 #[derive(Debug, Clone)]
@@ -29,6 +33,10 @@ pub struct AnnotateOptions {
     pub path: PathBuf,
     /// Apply changes directly
     pub apply: bool,
+    /// Keep `<file>.acp.bak` copies of files modified by `apply`
+    pub backup: bool,
+    /// Undo a generation instead of analyzing (`Some(None)` = most recent)
+    pub undo: Option<Option<String>>,
     /// Convert from existing doc format only (no heuristics)
     pub convert: bool,
     /// Source format for conversion
@@ -55,6 +63,10 @@ pub struct AnnotateOptions {
     pub no_provenance: bool,
     /// RFC-0003: Mark all generated annotations as needing review
     pub mark_needs_review: bool,
+    /// Read one file from stdin and write the annotated source to stdout
+    pub stdin: bool,
+    /// Language of the stdin buffer (otherwise inferred from `path`)
+    pub lang: Option<String>,
 }
 
 impl Default for AnnotateOptions {
@@ -62,6 +74,8 @@ impl Default for AnnotateOptions {
         Self {
             path: PathBuf::from("."),
             apply: false,
+            backup: true,
+            undo: None,
             convert: false,
             from: ConversionSource::Auto,
             level: AnnotateLevel::Standard,
@@ -75,6 +89,8 @@ impl Default for AnnotateOptions {
             verbose: false,
             no_provenance: false,
             mark_needs_review: false,
+            stdin: false,
+            lang: None,
         }
     }
 }
@@ -94,15 +110,15 @@ fn generate_generation_id() -> String {
 
 /// Execute the annotate command
 pub fn execute_annotate(options: AnnotateOptions, config: Config) -> Result<()> {
-    // Configure thread pool if workers specified
-    if let Some(num_workers) = options.workers {
-        rayon::ThreadPoolBuilder::new()
-            .num_threads(num_workers)
-            .build_global()
-            .ok(); // Ignore error if already initialized
+    if let Some(id) = &options.undo {
+        return execute_undo(id.as_deref(), &config);
+    }
+    if options.stdin {
+        return execute_stdin(&options, &config);
     }
 
-    println!(
+    // Status goes to stderr so `--format json` output stays parseable
+    eprintln!(
         "{} Analyzing codebase for annotations...",
         style("→").cyan()
     );
@@ -127,8 +143,8 @@ pub fn execute_annotate(options: AnnotateOptions, config: Config) -> Result<()>
     // CLI --mark-needs-review flag overrides config setting
     let mark_needs_review = options.mark_needs_review || config.annotate.defaults.mark_needs_review;
 
-    let provenance_config = if provenance_enabled {
-        let generation_id = generate_generation_id();
+    let generation_id = provenance_enabled.then(generate_generation_id);
+    let provenance_config = if let Some(generation_id) = generation_id.clone() {
         if options.verbose {
             eprintln!("Provenance generation ID: {}", generation_id);
             eprintln!(
@@ -156,7 +172,8 @@ pub fn execute_annotate(options: AnnotateOptions, config: Config) -> Result<()>
         Writer::new().with_provenance(config)
     } else {
         Writer::new()
-    };
+    }
+    .with_backup(options.backup);
 
     // Discover files
     let files = analyzer.discover_files(&options.path, options.filter.as_deref())?;
@@ -210,40 +227,9 @@ pub fn execute_annotate(options: AnnotateOptions, config: Config) -> Result<()>
         }
     }
 
-    // Clone path for parallel access
-    let repo_path = options.path.clone();
-
-    // Process files in parallel
-    let results: Vec<_> = files
-        .par_iter()
-        .filter_map(|file_path| {
-            // Analyze file
-            let analysis = match analyzer.analyze_file(file_path) {
-                Ok(a) => a,
-                Err(_) => return None,
-            };
-
-            // Open git repo per-thread for thread safety
-            let git_repo = GitRepository::open(&repo_path).ok();
-
-            // Generate suggestions (with git-based heuristics if repo is available)
-            let mut suggestions = suggester.suggest_with_git(&analysis, git_repo.as_ref());
-
-            // Filter by scope
-            if options.files_only {
-                suggestions.retain(|s| s.is_file_level());
-            }
-            if options.symbols_only {
-                suggestions.retain(|s| !s.is_file_level());
-            }
-
-            // Filter by minimum confidence (from config)
-            let min_conf = config.annotate.provenance.min_confidence as f32;
-            suggestions.retain(|s| s.confidence >= min_conf);
-
-            Some((file_path.clone(), analysis, suggestions))
-        })
-        .collect();
+    // Process files in parallel; results come back in stable path order
+    let min_confidence = config.annotate.provenance.min_confidence as f32;
+    let results = analyze_files(&files, &analyzer, &suggester, &options, min_confidence)?;
 
     // Aggregate results
     let mut total_suggestions = 0;
@@ -259,17 +245,18 @@ pub fn execute_annotate(options: AnnotateOptions, config: Config) -> Result<()>
             total_suggestions += suggestions.len();
 
             let changes = writer.plan_changes(&file_path, &suggestions, &analysis)?;
-            all_changes.push((file_path, changes));
+            all_changes.push((file_path, changes, analysis.content_hash.clone()));
         }
     }
 
-    // Calculate statistics for output
-    let mut type_counts: HashMap<String, usize> = HashMap::new();
-    let mut source_counts: HashMap<String, usize> = HashMap::new();
+    // Calculate statistics for output (ordered maps keep output reproducible)
+    let mut type_counts: BTreeMap<String, usize> = BTreeMap::new();
+    let mut source_counts: BTreeMap<String, usize> = BTreeMap::new();
     let mut total_confidence: f32 = 0.0;
     let mut suggestion_count: usize = 0;
+    let mut confidences: Vec<f64> = Vec::new();
 
-    for (_, changes) in &all_changes {
+    for (_, changes, _) in &all_changes {
         for change in changes {
             for suggestion in &change.annotations {
                 let type_name = format!("{:?}", suggestion.annotation_type).to_lowercase();
@@ -280,6 +267,8 @@ pub fn execute_annotate(options: AnnotateOptions, config: Config) -> Result<()>
 
                 total_confidence += suggestion.confidence;
                 suggestion_count += 1;
+                // Round away f32 noise (0.7 would otherwise log as 0.699999988)
+                confidences.push((suggestion.confidence as f64 * 100.0).round() / 100.0);
             }
         }
     }
@@ -291,17 +280,32 @@ pub fn execute_annotate(options: AnnotateOptions, config: Config) -> Result<()>
     };
 
     let coverage = Analyzer::calculate_total_coverage(&all_results);
+    let threshold = options.min_coverage.unwrap_or(80.0);
+    let groups = if options.check {
+        coverage_groups(
+            &all_results,
+            &config.root,
+            &config.annotate.coverage,
+            threshold,
+        )?
+    } else {
+        Vec::new()
+    };
 
     // Output results
     match options.format {
         OutputFormat::Diff => {
-            for (file_path, changes) in &all_changes {
+            for (file_path, changes, _) in &all_changes {
                 let diff = writer.generate_diff(file_path, changes)?;
                 if !diff.is_empty() {
                     println!("{}", diff);
                 }
             }
         }
+        OutputFormat::Json if options.check => {
+            let report = coverage_report(&all_results, threshold, &groups);
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
         OutputFormat::Json => {
             let output = serde_json::json!({
                 "summary": {
@@ -315,25 +319,8 @@ pub fn execute_annotate(options: AnnotateOptions, config: Config) -> Result<()>
                     "by_type": type_counts,
                     "by_source": source_counts,
                 },
-                "files": all_changes.iter().map(|(path, changes)| {
-                    let file_suggestions: Vec<_> = changes.iter().flat_map(|c| {
-                        c.annotations.iter().map(|s| {
-                            serde_json::json!({
-                                "target": c.symbol_name.as_deref().unwrap_or("(file)"),
-                                "line": s.line,
-                                "type": format!("{:?}", s.annotation_type).to_lowercase(),
-                                "value": s.value,
-                                "source": format!("{:?}", s.source),
-                                "confidence": (s.confidence * 100.0).round() / 100.0,
-                            })
-                        }).collect::<Vec<_>>()
-                    }).collect();
-
-                    serde_json::json!({
-                        "path": path.display().to_string(),
-                        "suggestion_count": file_suggestions.len(),
-                        "suggestions": file_suggestions,
-                    })
+                "files": all_changes.iter().map(|(path, changes, _)| {
+                    file_json(path, changes)
                 }).collect::<Vec<_>>(),
             });
             println!("{}", serde_json::to_string_pretty(&output)?);
@@ -352,7 +339,7 @@ pub fn execute_annotate(options: AnnotateOptions, config: Config) -> Result<()>
                 println!("\n{}", style("By Annotation Type").bold());
                 println!("------------------");
                 let mut sorted_types: Vec<_> = type_counts.iter().collect();
-                sorted_types.sort_by(|a, b| b.1.cmp(a.1)); // Sort by count descending
+                sorted_types.sort_by(|a, b| b.1.cmp(a.1)); // Sort by count descending (stable)
                 for (type_name, count) in sorted_types {
                     println!("  @acp:{:<14} {}", type_name, count);
                 }
@@ -371,7 +358,7 @@ pub fn execute_annotate(options: AnnotateOptions, config: Config) -> Result<()>
             if options.verbose {
                 println!("\n{}", style("File Details").bold());
                 println!("------------");
-                for (file_path, changes) in &all_changes {
+                for (file_path, changes, _) in &all_changes {
                     println!("\n{}:", file_path.display());
                     for change in changes {
                         let target = change.symbol_name.as_deref().unwrap_or("(file)");
@@ -389,44 +376,733 @@ pub fn execute_annotate(options: AnnotateOptions, config: Config) -> Result<()>
 
     // Apply changes if requested
     if options.apply {
-        for (file_path, changes) in &all_changes {
-            writer.apply_changes(file_path, changes)?;
-            if options.verbose {
-                eprintln!("Updated: {}", file_path.display());
+        let mut skipped = Vec::new();
+        let mut insertions = BTreeMap::new();
+        for (file_path, changes, hash) in &all_changes {
+            match writer.apply_changes(file_path, changes, hash.as_deref())? {
+                ApplyOutcome::Applied(inserted) => {
+                    if !inserted.lines.is_empty() {
+                        insertions.insert(file_path.display().to_string(), inserted);
+                    }
+                    if options.verbose {
+                        eprintln!("Updated: {}", file_path.display());
+                    }
+                }
+                ApplyOutcome::Modified => {
+                    eprintln!(
+                        "{} Skipped {}: modified since analysis",
+                        style("⚠").yellow(),
+                        file_path.display()
+                    );
+                    skipped.push(file_path.clone());
+                }
             }
         }
+        // Skipped files aren't part of this generation
+        let applied_suggestions = total_suggestions
+            - all_changes
+                .iter()
+                .filter(|(path, _, _)| skipped.contains(path))
+                .flat_map(|(_, changes, _)| changes)
+                .map(|change| change.annotations.len())
+                .sum::<usize>();
+        let total_suggestions = applied_suggestions;
+        let files_with_changes = files_with_changes - skipped.len();
         eprintln!(
             "\n{} Applied {} suggestions to {} files",
             style("✓").green(),
             total_suggestions,
             files_with_changes
         );
+
+        // RFC-0003: Record the run in the generation audit log
+        if let Some(id) = generation_id.filter(|_| total_suggestions > 0) {
+            let record = GenerationRecord {
+                info: GenerationInfo {
+                    id,
+                    timestamp: Utc::now().to_rfc3339(),
+                    annotations_generated: total_suggestions as u64,
+                    files_affected: files_with_changes as u64,
+                },
+                files: all_changes
+                    .iter()
+                    .filter(|(path, _, _)| !skipped.contains(path))
+                    .map(|(path, _, _)| path.display().to_string())
+                    .collect(),
+                by_source: source_counts
+                    .iter()
+                    .map(|(source, count)| (source.clone(), *count as u64))
+                    .collect(),
+                confidence: ConfidenceDistribution::from_scores(confidences),
+                insertions,
+            };
+            let log = GenerationLog::for_config(&config);
+            if let Err(e) = log.append(&record) {
+                eprintln!(
+                    "{} Could not record generation in {}: {}",
+                    style("⚠").yellow(),
+                    log.path().display(),
+                    e
+                );
+            }
+        }
     } else if !options.check && total_suggestions > 0 {
         eprintln!("\nRun with {} to write changes", style("--apply").cyan());
     }
 
-    // CI mode: exit with error if coverage below threshold
+    // CI mode: exit with error if any group's coverage is below its threshold
     if options.check {
-        let coverage = Analyzer::calculate_total_coverage(&all_results);
-        let threshold = options.min_coverage.unwrap_or(80.0);
+        let passed = groups.iter().all(CoverageGroup::passed);
+        if options.format == OutputFormat::Json {
+            if !passed {
+                std::process::exit(1);
+            }
+        } else if config.annotate.coverage.is_empty() {
+            if passed {
+                println!(
+                    "\n{} Coverage {:.1}% meets threshold {:.1}%",
+                    style("✓").green(),
+                    coverage,
+                    threshold
+                );
+            } else {
+                eprintln!(
+                    "\n{} Coverage {:.1}% is below threshold {:.1}%",
+                    style("✗").red(),
+                    coverage,
+                    threshold
+                );
+                std::process::exit(1);
+            }
+        } else {
+            println!("\n{}", style("Coverage by group").bold());
+            for group in &groups {
+                let mark = if group.passed() {
+                    style("✓").green()
+                } else {
+                    style("✗").red()
+                };
+                println!(
+                    "  {} {:<24} {:>5.1}% (required {:.1}%, {} files)",
+                    mark,
+                    group.label(),
+                    group.coverage,
+                    group.threshold,
+                    group.files
+                );
+            }
+            if !passed {
+                eprintln!(
+                    "\n{} Coverage is below threshold for {} group(s)",
+                    style("✗").red(),
+                    groups.iter().filter(|g| !g.passed()).count()
+                );
+                std::process::exit(1);
+            }
+        }
+    }
 
-        if coverage < threshold {
-            eprintln!(
-                "\n{} Coverage {:.1}% is below threshold {:.1}%",
+    Ok(())
+}
+
+/// Remove the annotations inserted by a generation (default: the latest)
+///
+/// Files edited since the run are left untouched; exits non-zero if any
+/// were found so the user can restore from `.acp.bak` or fix them by hand.
+fn execute_undo(id: Option<&str>, config: &Config) -> Result<()> {
+    let log = GenerationLog::for_config(config);
+    let (records, _) = log.read()?;
+    let root = config.root.as_path();
+
+    let Some(record) = find_undoable(&records, id, root) else {
+        match id {
+            Some(id) => anyhow::bail!("Generation {} not found in {}", id, log.path().display()),
+            None => {
+                println!("{} No generation to undo", style("•").dim());
+                return Ok(());
+            }
+        }
+    };
+    if record.insertions.is_empty() {
+        anyhow::bail!(
+            "Generation {} has no recorded insertions (applied before undo support)",
+            record.info.id
+        );
+    }
+
+    println!(
+        "{} Undoing generation {} ({})",
+        style("→").cyan(),
+        record.info.id,
+        record.info.timestamp
+    );
+    let report = undo_generation(record, root)?;
+
+    for file in &report.reverted {
+        println!("  {} {}", style("✓").green(), file);
+    }
+    for file in &report.unchanged {
+        println!("  {} {} (already undone)", style("•").dim(), file);
+    }
+    for (file, backup) in &report.drifted {
+        match backup {
+            Some(backup) => println!(
+                "  {} {}: modified since generation; original is in {}",
                 style("✗").red(),
-                coverage,
-                threshold
-            );
-            std::process::exit(1);
-        } else {
+                file,
+                backup.display()
+            ),
+            None => println!("  {} {}: modified since generation", style("✗").red(), file),
+        }
+    }
+
+    if report.is_complete() {
+        println!(
+            "\n{} Removed annotations from {} file(s)",
+            style("✓").green(),
+            report.reverted.len()
+        );
+        Ok(())
+    } else {
+        println!(
+            "\n{} {} file(s) changed since generation were left untouched",
+            style("✗").red(),
+            report.drifted.len()
+        );
+        std::process::exit(1);
+    }
+}
+
+/// Analyze files and generate suggestions on a bounded worker pool
+///
+/// The pool is sized to `options.workers` (default: one thread per CPU) and
+/// is local to this call. Files are processed in sorted path order and the
+/// indexed parallel iterator preserves that order, so output does not depend
+/// on the worker count. Nothing is written here; `--apply` writes happen
+/// sequentially afterwards.
+fn analyze_files(
+    files: &[PathBuf],
+    analyzer: &Analyzer,
+    suggester: &Suggester,
+    options: &AnnotateOptions,
+    min_confidence: f32,
+) -> Result<Vec<(PathBuf, AnalysisResult, Vec<Suggestion>)>> {
+    let mut files = files.to_vec();
+    files.sort();
+    files.dedup();
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(options.workers.unwrap_or(0))
+        .build()?;
+    let repo_path: &Path = &options.path;
+
+    let results = pool.install(|| {
+        files
+            .par_iter()
+            .filter_map(|file_path| {
+                // Analyze file
+                let analysis = analyzer.analyze_file(file_path).ok()?;
+
+                // Open git repo per-thread for thread safety
+                let git_repo = GitRepository::open(repo_path).ok();
+
+                // Generate suggestions (with git-based heuristics if repo is available)
+                let mut suggestions = suggester.suggest_with_git(&analysis, git_repo.as_ref());
+
+                retain_in_scope(&mut suggestions, options, min_confidence);
+
+                Some((file_path.clone(), analysis, suggestions))
+            })
+            .collect()
+    });
+
+    Ok(results)
+}
+
+/// Drops suggestions outside `--files-only`/`--symbols-only` or below the
+/// configured minimum confidence
+fn retain_in_scope(
+    suggestions: &mut Vec<Suggestion>,
+    options: &AnnotateOptions,
+    min_confidence: f32,
+) {
+    if options.files_only {
+        suggestions.retain(|s| s.is_file_level());
+    }
+    if options.symbols_only {
+        suggestions.retain(|s| !s.is_file_level());
+    }
+    suggestions.retain(|s| s.confidence >= min_confidence);
+}
+
+/// JSON object for one file's planned changes
+fn file_json(path: &Path, changes: &[FileChange]) -> serde_json::Value {
+    let suggestions: Vec<_> = changes
+        .iter()
+        .flat_map(|c| {
+            c.annotations.iter().map(|s| {
+                serde_json::json!({
+                    "target": c.symbol_name.as_deref().unwrap_or("(file)"),
+                    "line": s.line,
+                    "type": format!("{:?}", s.annotation_type).to_lowercase(),
+                    "value": s.value,
+                    "source": format!("{:?}", s.source),
+                    "confidence": (s.confidence * 100.0).round() / 100.0,
+                })
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "path": path.display().to_string(),
+        "suggestion_count": suggestions.len(),
+        "suggestions": suggestions,
+    })
+}
+
+/// Files under one `--check` threshold and the coverage they reach
+#[derive(Debug, Clone, PartialEq)]
+struct CoverageGroup {
+    /// Glob from `annotate.coverage`; `None` for files matching no glob
+    pattern: Option<String>,
+    threshold: f32,
+    coverage: f32,
+    files: usize,
+}
+
+impl CoverageGroup {
+    fn passed(&self) -> bool {
+        self.coverage >= self.threshold
+    }
+
+    fn label(&self) -> &str {
+        self.pattern.as_deref().unwrap_or("(other files)")
+    }
+}
+
+/// @acp:summary "Split results into the coverage groups checked by `--check`"
+///
+/// Each `annotate.coverage` glob is checked over every file it matches, so a
+/// file may count towards several groups. Files matching none are checked
+/// against `fallback`; that group is left out when every file matched a glob.
+/// Globs match paths relative to the project root.
+fn coverage_groups(
+    results: &[AnalysisResult],
+    root: &Path,
+    thresholds: &BTreeMap<String, f32>,
+    fallback: f32,
+) -> Result<Vec<CoverageGroup>> {
+    let relative = |path: &str| -> String {
+        let path = Path::new(path);
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .trim_start_matches("./")
+            .replace('\\', "/")
+    };
+    let paths: Vec<String> = results.iter().map(|r| relative(&r.file_path)).collect();
+    let group =
+        |pattern: Option<&String>, threshold: f32, members: Vec<&AnalysisResult>| CoverageGroup {
+            pattern: pattern.cloned(),
+            threshold,
+            coverage: Analyzer::calculate_total_coverage(members.iter().copied()),
+            files: members.len(),
+        };
+
+    let mut matched = vec![false; results.len()];
+    let mut groups = Vec::new();
+    for (pattern, &threshold) in thresholds {
+        let glob = glob::Pattern::new(pattern).map_err(|e| {
+            anyhow::anyhow!("Invalid annotate.coverage pattern '{}': {}", pattern, e)
+        })?;
+        let members: Vec<_> = results
+            .iter()
+            .zip(&paths)
+            .enumerate()
+            .filter(|(_, (_, path))| glob.matches_with(path, MATCH_OPTIONS))
+            .map(|(i, (result, _))| {
+                matched[i] = true;
+                result
+            })
+            .collect();
+        groups.push(group(Some(pattern), threshold, members));
+    }
+
+    let rest: Vec<_> = results
+        .iter()
+        .zip(&matched)
+        .filter(|(_, &m)| !m)
+        .map(|(r, _)| r)
+        .collect();
+    if thresholds.is_empty() || !rest.is_empty() {
+        groups.push(group(None, fallback, rest));
+    }
+    Ok(groups)
+}
+
+/// Coverage report for `--check --format json`
+///
+/// Lists every symbol still missing annotations, with the annotation types it
+/// lacks, so CI dashboards can point reviewers at what pulled coverage down.
+/// File-level gaps count towards the percentages but are not listed.
+/// `passed` requires every group to pass; `threshold_percent` is the
+/// `--min-coverage` fallback.
+fn coverage_report(
+    results: &[AnalysisResult],
+    threshold: f32,
+    groups: &[CoverageGroup],
+) -> serde_json::Value {
+    // One decimal place, without f32 noise
+    let percent = |value: f32| (value as f64 * 10.0).round() / 10.0;
+    let coverage = Analyzer::calculate_total_coverage(results);
+
+    let mut undocumented: Vec<_> = results
+        .iter()
+        .flat_map(|r| {
+            r.gaps
+                .iter()
+                .filter(|gap| gap.symbol_kind.is_some())
+                .map(move |gap| (r.file_path.as_str(), gap))
+        })
+        .collect();
+    undocumented.sort_by_key(|(file, gap)| (*file, gap.line));
+    undocumented.dedup_by(|a, b| a.0 == b.0 && a.1.line == b.1.line && a.1.target == b.1.target);
+
+    serde_json::json!({
+        "coverage_percent": percent(coverage),
+        "threshold_percent": percent(threshold),
+        "passed": groups.iter().all(CoverageGroup::passed),
+        "groups": groups.iter().map(|g| serde_json::json!({
+            "pattern": g.pattern,
+            "files": g.files,
+            "coverage_percent": percent(g.coverage),
+            "threshold_percent": percent(g.threshold),
+            "passed": g.passed(),
+        })).collect::<Vec<_>>(),
+        "files": results.iter().map(|r| serde_json::json!({
+            "path": r.file_path,
+            "coverage_percent": percent(r.coverage),
+            "annotated": r.existing_annotations.len(),
+            "gaps": r.gaps.len(),
+        })).collect::<Vec<_>>(),
+        "undocumented": undocumented.iter().map(|(file, gap)| serde_json::json!({
+            "name": gap.target,
+            "kind": gap.symbol_kind,
+            "file": file,
+            "line": gap.line,
+            "missing": gap.missing,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+/// File extensions for `--lang`, keyed by language name or extension
+const STDIN_LANGUAGES: &[(&str, &str)] = &[
+    ("typescript", "ts"),
+    ("ts", "ts"),
+    ("tsx", "tsx"),
+    ("javascript", "js"),
+    ("js", "js"),
+    ("jsx", "jsx"),
+    ("python", "py"),
+    ("py", "py"),
+    ("rust", "rs"),
+    ("rs", "rs"),
+    ("go", "go"),
+    ("java", "java"),
+    ("ruby", "rb"),
+    ("rb", "rb"),
+    ("swift", "swift"),
+    ("kotlin", "kt"),
+    ("kt", "kt"),
+];
+
+/// Resolves the path the stdin buffer is analyzed as.
+///
+/// `--lang` overrides the extension of `path`; without it `path` must carry
+/// one, since language detection is extension-based.
+fn stdin_path(path: &Path, lang: Option<&str>) -> Result<PathBuf> {
+    let Some(lang) = lang else {
+        if path.extension().is_none() {
+            anyhow::bail!("Cannot infer the language of stdin: pass --lang or a file path");
+        }
+        return Ok(path.to_path_buf());
+    };
+
+    let lang = lang.to_lowercase();
+    let Some((_, ext)) = STDIN_LANGUAGES.iter().find(|(name, _)| *name == lang) else {
+        anyhow::bail!(
+            "Unsupported language '{}' (expected one of: typescript, javascript, python, rust, go, java, ruby, swift, kotlin)",
+            lang
+        );
+    };
+    let path = if path.file_name().is_some() {
+        path.with_extension(ext)
+    } else {
+        PathBuf::from(format!("stdin.{}", ext))
+    };
+    Ok(path)
+}
+
+/// Annotates in-memory source, returning the planned changes and the
+/// rewritten source
+fn annotate_source(
+    path: &Path,
+    content: &str,
+    analyzer: &Analyzer,
+    suggester: &Suggester,
+    writer: &Writer,
+    options: &AnnotateOptions,
+    min_confidence: f32,
+) -> Result<(Vec<FileChange>, String)> {
+    let analysis = analyzer.analyze_source(path, content)?;
+    let mut suggestions = suggester.suggest(&analysis);
+    retain_in_scope(&mut suggestions, options, min_confidence);
+
+    let changes = writer.plan_changes(path, &suggestions, &analysis)?;
+    let mut annotated = writer.render(path, content, &changes)?;
+    if content.ends_with('\n') && !annotated.ends_with('\n') {
+        annotated.push('\n');
+    }
+    Ok((changes, annotated))
+}
+
+/// Annotates a single buffer from stdin for editor integrations.
+///
+/// Nothing is read from or written to the workspace and no generation is
+/// logged, so provenance markers carry no generation ID.
+fn execute_stdin(options: &AnnotateOptions, config: &Config) -> Result<()> {
+    let path = stdin_path(&options.path, options.lang.as_deref())?;
+
+    let mut content = String::new();
+    std::io::Read::read_to_string(&mut std::io::stdin(), &mut content)?;
+
+    let analyzer = Analyzer::new(config)?.with_level(options.level);
+    let suggester = Suggester::new(options.level)
+        .with_conversion_source(options.from)
+        .with_heuristics(!options.convert);
+
+    let provenance = &config.annotate.provenance;
+    let writer = if provenance.enabled && !options.no_provenance {
+        Writer::new().with_provenance(
+            ProvenanceConfig::new()
+                .with_needs_review(
+                    options.mark_needs_review || config.annotate.defaults.mark_needs_review,
+                )
+                .with_review_threshold(provenance.review_threshold as f32)
+                .with_min_confidence(provenance.min_confidence as f32),
+        )
+    } else {
+        Writer::new()
+    };
+
+    let (changes, annotated) = annotate_source(
+        &path,
+        &content,
+        &analyzer,
+        &suggester,
+        &writer,
+        options,
+        provenance.min_confidence as f32,
+    )?;
+
+    match options.format {
+        OutputFormat::Json => {
             println!(
-                "\n{} Coverage {:.1}% meets threshold {:.1}%",
-                style("✓").green(),
-                coverage,
-                threshold
+                "{}",
+                serde_json::to_string_pretty(&file_json(&path, &changes))?
             );
         }
+        OutputFormat::Diff | OutputFormat::Summary => print!("{}", annotated),
     }
-
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::annotate::{AnnotationGap, AnnotationType, ExistingAnnotation};
+
+    fn render_diffs(root: &Path, workers: usize) -> String {
+        let config = Config::default();
+        let analyzer = Analyzer::new(&config).unwrap();
+        let suggester = Suggester::new(AnnotateLevel::Standard);
+        let writer = Writer::new();
+        let options = AnnotateOptions {
+            path: root.to_path_buf(),
+            workers: Some(workers),
+            ..Default::default()
+        };
+
+        let mut files = analyzer.discover_files(root, None).unwrap();
+        files.reverse();
+        let results = analyze_files(&files, &analyzer, &suggester, &options, 0.0).unwrap();
+
+        let mut output = String::new();
+        for (path, analysis, suggestions) in results {
+            let changes = writer.plan_changes(&path, &suggestions, &analysis).unwrap();
+            output.push_str(&path.display().to_string());
+            output.push('\n');
+            output.push_str(&writer.generate_diff(&path, &changes).unwrap());
+        }
+        output
+    }
+
+    #[test]
+    fn test_output_independent_of_worker_count() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        for i in 0..12 {
+            std::fs::write(
+                src.join(format!("module_{:02}.ts", i)),
+                format!(
+                    "/**\n * Handles item {i}.\n */\nexport function handle{i}(x: number): number {{\n    return x + {i};\n}}\n\nexport class Service{i} {{}}\n"
+                ),
+            )
+            .unwrap();
+        }
+
+        let single = render_diffs(dir.path(), 1);
+        let parallel = render_diffs(dir.path(), 4);
+
+        assert!(single.contains("module_00.ts"));
+        assert!(single.contains("@acp:"));
+        assert_eq!(single, parallel);
+    }
+
+    #[test]
+    fn test_coverage_report_lists_undocumented_symbols() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("auth.ts"),
+            "// @acp:module \"Auth\"\n// @acp:summary \"Login helpers\"\n\n\
+             // @acp:summary \"Logs a user in\"\nexport function login() {}\n\n\
+             export function logout() {}\n",
+        )
+        .unwrap();
+
+        let config = Config::default();
+        let analyzer = Analyzer::new(&config).unwrap();
+        let suggester = Suggester::new(AnnotateLevel::Standard);
+        let files = analyzer.discover_files(dir.path(), None).unwrap();
+        let results: Vec<_> = analyze_files(
+            &files,
+            &analyzer,
+            &suggester,
+            &AnnotateOptions::default(),
+            0.0,
+        )
+        .unwrap()
+        .into_iter()
+        .map(|(_, analysis, _)| analysis)
+        .collect();
+
+        let groups = coverage_groups(&results, dir.path(), &BTreeMap::new(), 90.0).unwrap();
+        let report = coverage_report(&results, 90.0, &groups);
+        assert_eq!(report["threshold_percent"], 90.0);
+        assert_eq!(report["passed"], false);
+        assert_eq!(report["groups"][0]["pattern"], serde_json::Value::Null);
+        assert_eq!(report["files"].as_array().unwrap().len(), 1);
+        assert_eq!(report["files"][0]["annotated"], 3);
+
+        let undocumented = report["undocumented"].as_array().unwrap();
+        let names: Vec<_> = undocumented.iter().map(|u| &u["name"]).collect();
+        assert_eq!(names, ["login", "logout"]);
+        let logout = &undocumented[1];
+        assert_eq!(logout["kind"], "function");
+        assert_eq!(logout["line"], 7);
+        assert_eq!(logout["missing"][0], "summary");
+        assert!(logout["file"].as_str().unwrap().ends_with("auth.ts"));
+    }
+
+    #[test]
+    fn test_coverage_groups_use_per_glob_thresholds() {
+        // `annotated` existing annotations and `gaps` gaps
+        let result = |path: &str, annotated: usize, gaps: usize| {
+            let mut result = AnalysisResult::new(path, "typescript");
+            for line in 0..annotated {
+                result.existing_annotations.push(ExistingAnnotation {
+                    target: path.to_string(),
+                    annotation_type: AnnotationType::Summary,
+                    value: String::new(),
+                    line,
+                });
+            }
+            for line in 0..gaps {
+                result.gaps.push(AnnotationGap::new("f", line));
+            }
+            result
+        };
+        let results = vec![
+            result("./src/lib.rs", 9, 1),
+            result("./gen/api.ts", 1, 1),
+            result("./scripts/build.ts", 1, 3),
+        ];
+        let thresholds: BTreeMap<String, f32> =
+            [("**/*.rs".to_string(), 90.0), ("gen/**".to_string(), 60.0)].into();
+
+        let groups = coverage_groups(&results, Path::new("."), &thresholds, 20.0).unwrap();
+        let summary: Vec<_> = groups
+            .iter()
+            .map(|g| (g.label(), g.files, g.coverage.round(), g.passed()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("**/*.rs", 1, 90.0, true),
+                ("gen/**", 1, 50.0, false),
+                ("(other files)", 1, 25.0, true),
+            ]
+        );
+
+        let report = coverage_report(&results, 20.0, &groups);
+        assert_eq!(report["passed"], false);
+        assert_eq!(report["groups"][1]["threshold_percent"], 60.0);
+
+        // Every file matched a glob, so there is no fallback group
+        let all: BTreeMap<String, f32> = [("**".to_string(), 50.0)].into();
+        let groups = coverage_groups(&results, Path::new("."), &all, 99.0).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert!(groups[0].passed());
+    }
+
+    #[test]
+    fn test_annotate_stdin_source() {
+        assert_eq!(
+            stdin_path(Path::new("."), Some("Rust")).unwrap(),
+            PathBuf::from("stdin.rs")
+        );
+        assert_eq!(
+            stdin_path(Path::new("src/lib.txt"), Some("python")).unwrap(),
+            PathBuf::from("src/lib.py")
+        );
+        assert_eq!(
+            stdin_path(Path::new("."), Some("kotlin")).unwrap(),
+            PathBuf::from("stdin.kt")
+        );
+        assert!(stdin_path(Path::new("."), None).is_err());
+        assert!(stdin_path(Path::new("a.rs"), Some("cobol")).is_err());
+
+        let config = Config::default();
+        let analyzer = Analyzer::new(&config).unwrap();
+        let suggester = Suggester::new(AnnotateLevel::Standard);
+        let path = Path::new("src/does_not_exist.rs");
+        let content = "/// Adds numbers\npub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n";
+
+        let (changes, annotated) = annotate_source(
+            path,
+            content,
+            &analyzer,
+            &suggester,
+            &Writer::new(),
+            &AnnotateOptions::default(),
+            0.0,
+        )
+        .unwrap();
+
+        assert!(!changes.is_empty());
+        assert!(annotated.contains("/// @acp:summary \"Adds numbers\""));
+        assert!(annotated.ends_with("    a + b\n}\n"));
+        assert!(!path.exists());
+    }
+}
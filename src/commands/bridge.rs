@@ -114,6 +114,9 @@ fn output_status_text(config: &Config, cache: Option<&Cache>) -> Result<()> {
         }
     );
     println!("  Precedence: {}", config.bridge.precedence);
+    for o in &config.bridge.overrides {
+        println!("    {} → {}", o.pattern, o.precedence);
+    }
     println!("  Strictness: {:?}", config.bridge.strictness);
     println!();
 
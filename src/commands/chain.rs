@@ -3,12 +3,14 @@
 //! @acp:domain cli
 //! @acp:layer handler
 
+use std::collections::HashSet;
 use std::path::PathBuf;
 
 use anyhow::Result;
 use console::style;
 
-use crate::vars::{VarExpander, VarResolver, VarsFile};
+use super::output::TreeRenderer;
+use crate::vars::{InheritanceNode, VarExpander, VarResolver, VarsFile};
 
 /// Options for the chain command
 #[derive(Debug, Clone)]
@@ -19,38 +21,182 @@ pub struct ChainOptions {
     pub vars: PathBuf,
     /// Show as tree
     pub tree: bool,
+    /// Show a compact one-line-per-variable listing
+    pub flat: bool,
+    /// Stop following refs below this depth
+    pub max_depth: Option<usize>,
 }
 
 /// Execute the chain command
 pub fn execute_chain(options: ChainOptions) -> Result<()> {
     let vars_file = VarsFile::from_json(&options.vars)?;
     let resolver = VarResolver::new(vars_file);
+    // Cycles are marked in the output rather than refusing to show anything
+    if let Some(cycle) = resolver.find_cycle() {
+        eprintln!(
+            "{} Circular variable reference: {}",
+            style("⚠").yellow(),
+            cycle.join(" → ")
+        );
+    }
     let expander = VarExpander::new(resolver);
 
     let name = options.name.trim_start_matches('$');
-    let chain = expander.get_inheritance_chain(name);
 
-    if options.tree {
-        println!("{}", style(format!("${}", name)).cyan().bold());
-        print_chain_tree(&chain.chain, 0);
-    } else {
-        println!("Root: {}", style(&chain.root).cyan());
-        println!("Depth: {}", chain.depth);
-        println!("Chain: {}", chain.chain.join(" → "));
+    if options.tree || options.flat {
+        let root = expander.get_inheritance_tree(name, options.max_depth);
+        let lines = if options.flat {
+            flat_lines(&root)
+        } else {
+            tree_lines(&root, &TreeRenderer::default())
+        };
+        for line in lines {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    let mut chain = expander.get_inheritance_chain(name);
+    if let Some(max) = options.max_depth {
+        chain.chain.truncate(max + 1);
+        chain.depth = chain.depth.min(max);
     }
+    println!("Root: {}", style(&chain.root).cyan());
+    println!("Depth: {}", chain.depth);
+    println!("Chain: {}", chain.chain.join(" → "));
 
     Ok(())
 }
 
-/// Print inheritance chain as a tree
-fn print_chain_tree(chain: &[String], depth: usize) {
-    for (i, item) in chain.iter().enumerate() {
-        let prefix = if i == chain.len() - 1 {
-            "└── "
-        } else {
-            "├── "
-        };
-        let indent = "    ".repeat(depth);
-        println!("{}{}{}", indent, prefix, style(item).dim());
+/// Label for a tree node, with a marker for where traversal stopped
+fn node_label(node: &InheritanceNode) -> String {
+    let marker = if node.cycle {
+        format!(" {}", style("(cycle)").yellow())
+    } else if node.repeated {
+        format!(" {}", style("(see above)").dim())
+    } else if node.truncated {
+        format!(" {}", style("(max depth)").dim())
+    } else if node.missing {
+        format!(" {}", style("(undefined)").red())
+    } else {
+        String::new()
+    };
+    format!("${}{}", node.name, marker)
+}
+
+/// Render an inheritance tree, one line per node
+fn tree_lines(root: &InheritanceNode, renderer: &TreeRenderer) -> Vec<String> {
+    fn walk(
+        node: &InheritanceNode,
+        renderer: &TreeRenderer,
+        indent: &str,
+        lines: &mut Vec<String>,
+    ) {
+        for (i, child) in node.children.iter().enumerate() {
+            let is_last = i == node.children.len() - 1;
+            let branch = if is_last {
+                renderer.last_branch()
+            } else {
+                renderer.branch()
+            };
+            lines.push(format!(
+                "{}{} {}",
+                indent,
+                branch,
+                style(node_label(child)).dim()
+            ));
+            let next = if is_last {
+                format!("{}   ", indent)
+            } else {
+                format!("{}{} ", indent, renderer.vertical())
+            };
+            walk(child, renderer, &next, lines);
+        }
+    }
+
+    let mut lines = vec![style(node_label(root)).cyan().bold().to_string()];
+    walk(root, renderer, "", &mut lines);
+    lines
+}
+
+/// Compact listing: each variable once, in the order first reached, with its depth
+fn flat_lines(root: &InheritanceNode) -> Vec<String> {
+    fn walk<'a>(
+        node: &'a InheritanceNode,
+        depth: usize,
+        seen: &mut HashSet<&'a str>,
+        lines: &mut Vec<String>,
+    ) {
+        if !node.cycle && !seen.insert(node.name.as_str()) {
+            return;
+        }
+        lines.push(format!("{:>3}  {}", depth, node_label(node)));
+        for child in &node.children {
+            walk(child, depth + 1, seen, lines);
+        }
+    }
+
+    let mut lines = Vec::new();
+    walk(root, 0, &mut HashSet::new(), &mut lines);
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vars::VarEntry;
+
+    fn cyclic_expander() -> VarExpander {
+        let mut vars_file = VarsFile::new();
+        for (name, refs) in [
+            ("SYM_A", vec!["SYM_B"]),
+            ("SYM_B", vec!["SYM_C", "$SYM_GONE"]),
+            ("SYM_C", vec!["SYM_A"]),
+        ] {
+            vars_file.add_variable(
+                name.to_string(),
+                VarEntry::symbol_with_refs(
+                    "x.rs:x",
+                    None,
+                    refs.into_iter().map(str::to_string).collect(),
+                ),
+            );
+        }
+        VarExpander::new(VarResolver::new(vars_file))
+    }
+
+    #[test]
+    fn test_cyclic_chain_terminates_with_marker() {
+        console::set_colors_enabled(false);
+        let expander = cyclic_expander();
+        let renderer = TreeRenderer { use_unicode: false };
+
+        let root = expander.get_inheritance_tree("SYM_A", None);
+        assert_eq!(
+            tree_lines(&root, &renderer),
+            vec![
+                "$SYM_A",
+                "`- $SYM_B",
+                "   |- $SYM_C",
+                "   |  `- $SYM_A (cycle)",
+                "   `- $SYM_GONE (undefined)",
+            ]
+        );
+        assert_eq!(
+            flat_lines(&root),
+            vec![
+                "  0  $SYM_A",
+                "  1  $SYM_B",
+                "  2  $SYM_C",
+                "  3  $SYM_A (cycle)",
+                "  2  $SYM_GONE (undefined)",
+            ]
+        );
+
+        let shallow = expander.get_inheritance_tree("SYM_A", Some(1));
+        assert_eq!(
+            tree_lines(&shallow, &renderer),
+            vec!["$SYM_A", "`- $SYM_B (max depth)"]
+        );
     }
 }
@@ -1,30 +1,96 @@
 //! @acp:module "Check Command"
-//! @acp:summary "Check guardrails for a file"
+//! @acp:summary "Check guardrails for a file, or many files at once"
 //! @acp:domain cli
 //! @acp:layer handler
+//!
+//! One plain path prints that file's details. Several paths or glob
+//! patterns (`acp check 'src/**/*.rs'`, or the staged files of a pre-commit
+//! hook) are checked against a single load of the cache, with findings
+//! grouped by file and a non-zero exit when any of them is an error.
+//!
+//! `--fix` rewrites the mechanically fixable annotation problems in source
+//! (missing directives, malformed hack dates, orphaned `@acp:lock-reason`);
+//! `errorHandling.auto_correct` applies the same fixes before every check.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use console::style;
+use glob::Pattern;
 
-use crate::cache::Cache;
+use crate::cache::{normalize_path, Cache};
+use crate::config::Config;
+use crate::constraints::{
+    build_findings_sarif_report, build_sarif_report, check_lock_policy, Constraints,
+    GuardrailEnforcer, LockSource, MutationConstraint, SarifFinding, SarifLevel, Severity,
+};
+use crate::git::GitRepository;
+use crate::globs::MATCH_OPTIONS;
+use crate::index::{check_file_params, detect_language, ParamIssue, ParamIssueKind};
+use crate::parse::{apply_fixes, plan_fixes, read_source, FixKind, Parser};
+use crate::AstParser;
+
+/// Output format for the check command
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CheckFormat {
+    /// Human-readable summary
+    #[default]
+    Text,
+    /// SARIF 2.1.0 report for code scanning
+    Sarif,
+}
 
 /// Options for the check command
 #[derive(Debug, Clone)]
 pub struct CheckOptions {
-    /// File to check
-    pub file: PathBuf,
+    /// Files or glob patterns to check ("." for the whole project)
+    pub files: Vec<PathBuf>,
     /// Cache file
     pub cache: PathBuf,
+    /// Output format
+    pub format: CheckFormat,
+    /// Compare @acp:param annotations against real signatures
+    pub types: bool,
+    /// Check git history of frozen/restricted files against approvers
+    pub policy: bool,
+    /// Rewrite mechanically fixable annotation problems in source
+    pub fix: bool,
+    /// With `fix`, show the changes without writing them
+    pub dry_run: bool,
 }
 
 /// Execute the check command
-pub fn execute_check(options: CheckOptions) -> Result<()> {
+pub fn execute_check(options: CheckOptions, config: &Config) -> Result<()> {
+    if options.types {
+        return check_param_types(&options, config);
+    }
+    if options.policy {
+        return check_policy(&options, config);
+    }
+    if options.fix {
+        fix_annotations(&options, config, true)?;
+        return Ok(());
+    }
+    let auto_correct = config
+        .error_handling
+        .as_ref()
+        .is_some_and(|e| e.auto_correct);
+    if auto_correct && options.format == CheckFormat::Text {
+        fix_annotations(&options, config, false)?;
+    }
+
     let cache_data = Cache::from_json(&options.cache)?;
 
+    let file = match options.files.as_slice() {
+        [file] if !is_glob(file) => file,
+        _ => return check_many(&cache_data, &options, &config.root),
+    };
+
     // If path is ".", show all files with constraints
-    let file_str = options.file.to_string_lossy().to_string();
+    let file_str = file.to_string_lossy().to_string();
+    if options.format == CheckFormat::Sarif {
+        return print_sarif(&cache_data, &file_str, &config.root);
+    }
     if file_str == "." {
         return show_all_constraints(&cache_data);
     }
@@ -55,44 +121,506 @@ pub fn execute_check(options: CheckOptions) -> Result<()> {
 
         // Check constraints if available
         if let Some(ref constraints) = cache_data.constraints {
-            let file_constraints = constraints
-                .by_file
-                .get(&file_entry.path)
-                .or_else(|| constraints.by_file.get(&format!("./{}", file_entry.path)))
-                .or_else(|| {
-                    let stripped = file_entry
-                        .path
-                        .strip_prefix("./")
-                        .unwrap_or(&file_entry.path);
-                    constraints.by_file.get(stripped)
-                });
-
-            if let Some(file_constraints) = file_constraints {
-                if let Some(mutation) = &file_constraints.mutation {
-                    println!("  Lock level: {:?}", mutation.level);
-                    if mutation.requires_approval {
-                        println!("  {} Requires approval", style("⚠").yellow());
-                    }
-                    if mutation.requires_tests {
-                        println!("  {} Requires tests", style("⚠").yellow());
-                    }
-                    if mutation.requires_docs {
-                        println!("  {} Requires documentation", style("⚠").yellow());
-                    }
+            if let Some(lock) = constraints.effective_lock(&file_entry.path) {
+                let source = match &lock.source {
+                    LockSource::Explicit => "explicit @acp:lock".to_string(),
+                    LockSource::Inherited { pattern } => format!("inherited from {}", pattern),
+                };
+                println!("  Lock level: {:?} ({})", lock.level, source);
+
+                let mutation = constraints
+                    .get_effective(&file_entry.path, &Constraints::default())
+                    .mutation
+                    .unwrap_or_else(|| MutationConstraint::from_level(lock.level));
+
+                if mutation.requires_approval {
+                    println!("  {} Requires approval", style("⚠").yellow());
                 }
+                if mutation.requires_tests {
+                    println!("  {} Requires tests", style("⚠").yellow());
+                }
+                if mutation.requires_docs {
+                    println!("  {} Requires documentation", style("⚠").yellow());
+                }
+            }
+        }
+
+        // Symbol-level locks still apply when the file itself is editable
+        let wanted = normalize_path(&file_entry.path);
+        let symbol_check = GuardrailEnforcer::check_symbols(
+            cache_data
+                .symbols
+                .values()
+                .filter(|s| normalize_path(&s.file) == wanted),
+        );
+        let mut locks: Vec<_> = symbol_check
+            .violations
+            .iter()
+            .map(|v| (v.lines, &v.symbol, &v.message, Some(v.severity)))
+            .chain(
+                symbol_check
+                    .warnings
+                    .iter()
+                    .map(|w| (w.lines, &w.symbol, &w.message, None)),
+            )
+            .collect();
+        locks.sort_by_key(|(lines, ..)| *lines);
+
+        if !locks.is_empty() {
+            println!("  Symbol locks:");
+            for (lines, symbol, message, severity) in locks {
+                let marker = match severity {
+                    Some(Severity::Error) => style("✗").red(),
+                    _ => style("⚠").yellow(),
+                };
+                let [start, end] = lines.unwrap_or_default();
+                println!(
+                    "    {} {} (lines {}-{}) {}",
+                    marker,
+                    symbol.as_deref().unwrap_or_default(),
+                    start,
+                    end,
+                    message
+                );
             }
         }
     } else {
-        eprintln!(
-            "{} File not in cache: {}",
-            style("✗").red(),
-            options.file.display()
+        eprintln!("{} File not in cache: {}", style("✗").red(), file.display());
+    }
+
+    Ok(())
+}
+
+/// Whether a path argument is a glob pattern rather than a file
+fn is_glob(path: &Path) -> bool {
+    path.to_string_lossy().contains(['*', '?', '['])
+}
+
+/// @acp:summary "Resolve paths and glob patterns to cached file paths"
+///
+/// Returns the matched cache paths, sorted and deduplicated, and the
+/// arguments that matched nothing.
+fn resolve_files(cache: &Cache, args: &[PathBuf]) -> Result<(Vec<String>, Vec<String>)> {
+    let mut files = Vec::new();
+    let mut unmatched = Vec::new();
+    for arg in args {
+        let arg = arg.to_string_lossy();
+        let before = files.len();
+        if arg == "." {
+            files.extend(cache.files.keys().cloned());
+        } else if is_glob(Path::new(arg.as_ref())) {
+            let pattern = Pattern::new(&normalize_path(&arg))
+                .map_err(|e| anyhow::anyhow!("Invalid glob '{}': {}", arg, e))?;
+            files.extend(
+                cache
+                    .files
+                    .keys()
+                    .filter(|k| pattern.matches_with(&normalize_path(k), MATCH_OPTIONS))
+                    .cloned(),
+            );
+        } else {
+            let wanted = normalize_path(&arg);
+            files.extend(
+                cache
+                    .files
+                    .keys()
+                    .find(|k| normalize_path(k) == wanted)
+                    .cloned(),
+            );
+        }
+        if files.len() == before {
+            unmatched.push(arg.to_string());
+        }
+    }
+    files.sort();
+    files.dedup();
+    Ok((files, unmatched))
+}
+
+/// One guardrail finding in a multi-file check
+struct Finding {
+    level: SarifLevel,
+    rule: String,
+    message: String,
+    line: usize,
+}
+
+/// @acp:summary "Check several files or patterns against one cache load"
+///
+/// Exits non-zero when any finding is an error (frozen or AI-readonly files,
+/// frozen symbols). Paths not in the cache are listed but never fail the run,
+/// so a hook can pass every staged file.
+fn check_many(cache_data: &Cache, options: &CheckOptions, root: &Path) -> Result<()> {
+    let (files, unmatched) = resolve_files(cache_data, &options.files)?;
+    let report = build_sarif_report(cache_data, &files, root);
+    if options.format == CheckFormat::Sarif {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    let mut findings: Vec<(&str, Vec<Finding>)> =
+        files.iter().map(|f| (f.as_str(), Vec::new())).collect();
+    for result in report.runs.iter().flat_map(|run| &run.results) {
+        let location = &result.locations[0].physical_location;
+        let uri = &location.artifact_location.uri;
+        if let Some((_, found)) = findings.iter_mut().find(|(f, _)| normalize_path(f) == *uri) {
+            found.push(Finding {
+                level: result.level,
+                rule: result.rule_id.clone(),
+                message: result.message.text.clone(),
+                line: location.region.start_line,
+            });
+        }
+    }
+    for (file, found) in &mut findings {
+        let wanted = normalize_path(file);
+        let symbol_check = GuardrailEnforcer::check_symbols(
+            cache_data
+                .symbols
+                .values()
+                .filter(|s| normalize_path(&s.file) == wanted),
         );
+        let violations = symbol_check
+            .violations
+            .into_iter()
+            .map(|v| (v.severity.into(), v.symbol, v.message, v.lines));
+        let warnings = symbol_check
+            .warnings
+            .into_iter()
+            .map(|w| (SarifLevel::Warning, w.symbol, w.message, w.lines));
+        for (level, symbol, message, lines) in violations.chain(warnings) {
+            let [start, _] = lines.unwrap_or_default();
+            found.push(Finding {
+                level,
+                rule: "acp/lock/symbol".to_string(),
+                message: format!("{}: {}", symbol.unwrap_or_default(), message),
+                line: start,
+            });
+        }
+        found.sort_by_key(|f| f.line);
+    }
+
+    let count = |level: SarifLevel| {
+        findings
+            .iter()
+            .flat_map(|(_, found)| found)
+            .filter(|f| f.level == level)
+            .count()
+    };
+    let (errors, warnings) = (count(SarifLevel::Error), count(SarifLevel::Warning));
+    let flagged = findings
+        .iter()
+        .filter(|(_, found)| !found.is_empty())
+        .count();
+
+    for (file, found) in findings.iter().filter(|(_, found)| !found.is_empty()) {
+        println!("{}", style(file).bold());
+        for finding in found {
+            let marker = match finding.level {
+                SarifLevel::Error => style("✗").red(),
+                SarifLevel::Warning => style("⚠").yellow(),
+                SarifLevel::Note => style("•").dim(),
+            };
+            println!(
+                "  {} {}:{} {}",
+                marker, finding.rule, finding.line, finding.message
+            );
+        }
+        println!();
+    }
+    for arg in &unmatched {
+        println!("{} Not in cache: {}", style("•").dim(), arg);
+    }
+
+    let summary = format!(
+        "{} error(s), {} warning(s) in {} of {} file(s) checked",
+        errors,
+        warnings,
+        flagged,
+        files.len()
+    );
+    if errors == 0 {
+        println!("{} {}", style("✓").green(), summary);
+        Ok(())
+    } else {
+        println!("{} {}", style("✗").red(), summary);
+        std::process::exit(1);
     }
+}
 
+/// Print a SARIF report for one file, or every cached file for "."
+fn print_sarif(cache_data: &Cache, file_str: &str, root: &Path) -> Result<()> {
+    let files: Vec<String> = if file_str == "." {
+        cache_data.files.keys().cloned().collect()
+    } else {
+        let wanted = normalize_path(file_str);
+        let path = cache_data
+            .files
+            .keys()
+            .find(|k| normalize_path(k) == wanted)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("File not in cache: {}", file_str))?;
+        vec![path]
+    };
+
+    let report = build_sarif_report(cache_data, &files, root);
+    println!("{}", serde_json::to_string_pretty(&report)?);
     Ok(())
 }
 
+/// @acp:summary "Source files to read for a check, as (display, full path) pairs"
+///
+/// A single plain path is read from disk and need not be indexed; anything
+/// else is resolved through the cache. The flag is true in the latter case,
+/// where callers skip unreadable files instead of failing.
+fn source_files(options: &CheckOptions, config: &Config) -> Result<(Vec<(String, PathBuf)>, bool)> {
+    match options.files.as_slice() {
+        [file] if !is_glob(file) && file.as_os_str() != "." => Ok((
+            vec![(file.to_string_lossy().to_string(), file.clone())],
+            false,
+        )),
+        args => {
+            let cache_data = Cache::from_json(&options.cache)?;
+            let (paths, _) = resolve_files(&cache_data, args)?;
+            let files = paths
+                .into_iter()
+                .map(|p| {
+                    let full = config.root.join(&p);
+                    (p, full)
+                })
+                .collect();
+            Ok((files, true))
+        }
+    }
+}
+
+/// @acp:summary "Apply mechanical annotation fixes, or preview them"
+///
+/// Prints each change and every problem left for a human. With
+/// `report_clean` unset (the auto_correct pass) nothing is printed when
+/// there is nothing to fix.
+fn fix_annotations(options: &CheckOptions, config: &Config, report_clean: bool) -> Result<()> {
+    let (files, whole_project) = source_files(options, config)?;
+    let parser = Parser::from_config(config);
+    let (mut fixed, mut fixed_files, mut skipped) = (0, 0, 0);
+
+    for (display, full) in &files {
+        let Some(language) = detect_language(display) else {
+            if whole_project {
+                continue;
+            }
+            anyhow::bail!("Unsupported file type: {}", display);
+        };
+        let source = match read_source(full) {
+            Ok(source) => source,
+            Err(e) if whole_project => {
+                eprintln!("{} Skipping {}: {}", style("⚠").yellow(), display, e);
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let plan = plan_fixes(&source, language, &parser);
+        for fix in &plan.fixes {
+            let kind = match fix.kind {
+                FixKind::Directive => "add directive",
+                FixKind::HackDate => "normalize hack date",
+                FixKind::MissingLock => "add missing @acp:lock",
+            };
+            let marker = if options.dry_run {
+                style("→").cyan()
+            } else {
+                style("✓").green()
+            };
+            println!("{} {}:{} {}", marker, display, fix.line, kind);
+            if !fix.before.is_empty() {
+                println!("    {}", style(format!("- {}", fix.before.trim())).red());
+            }
+            println!("    {}", style(format!("+ {}", fix.after.trim())).green());
+        }
+        for (line, reason) in &plan.skipped {
+            println!("{} {}:{} {}", style("⚠").yellow(), display, line, reason);
+        }
+
+        skipped += plan.skipped.len();
+        if plan.fixes.is_empty() {
+            continue;
+        }
+        fixed += plan.fixes.len();
+        fixed_files += 1;
+        if !options.dry_run {
+            std::fs::write(full, apply_fixes(&source, &plan.fixes))?;
+        }
+    }
+
+    if fixed == 0 && skipped == 0 && !report_clean {
+        return Ok(());
+    }
+    let verb = if options.dry_run {
+        "Would fix"
+    } else {
+        "Fixed"
+    };
+    println!(
+        "{} {} {} issue(s) in {} file(s); {} left for review",
+        style("✓").green(),
+        verb,
+        fixed,
+        fixed_files,
+        skipped
+    );
+    if !report_clean {
+        println!();
+    }
+    Ok(())
+}
+
+/// Report @acp:param drift for one file, or every cached file for "."
+///
+/// Exits non-zero when names or arity disagree; type mismatches are advisory.
+/// With `--format sarif` the findings are printed as a report instead.
+fn check_param_types(options: &CheckOptions, config: &Config) -> Result<()> {
+    let (files, whole_project) = source_files(options, config)?;
+
+    let ast_parser = AstParser::new()?;
+    let annotation_parser = Parser::from_config(config);
+    let mut issues: Vec<ParamIssue> = Vec::new();
+    for (display, full) in &files {
+        let source = match std::fs::read_to_string(full) {
+            Ok(source) => source,
+            Err(e) if whole_project => {
+                eprintln!("{} Skipping {}: {}", style("⚠").yellow(), display, e);
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+        match check_file_params(display, &source, &ast_parser, &annotation_parser) {
+            Ok(found) => issues.extend(found),
+            // Languages without AST support have nothing to compare
+            Err(_) if whole_project => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    if options.format == CheckFormat::Sarif {
+        let findings: Vec<_> = issues.iter().map(param_finding).collect();
+        let report = build_findings_sarif_report(&findings);
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    for issue in &issues {
+        let marker = if issue.is_advisory() {
+            style("⚠").yellow()
+        } else {
+            style("✗").red()
+        };
+        println!(
+            "{} {}:{} {}: {}",
+            marker, issue.file, issue.line, issue.symbol, issue.message
+        );
+    }
+
+    let errors = issues.iter().filter(|i| !i.is_advisory()).count();
+    let advisories = issues.len() - errors;
+    if !issues.is_empty() {
+        println!();
+    }
+    if errors == 0 {
+        println!(
+            "{} @acp:param annotations match signatures in {} file(s) ({} type advisory)",
+            style("✓").green(),
+            files.len(),
+            advisories
+        );
+        Ok(())
+    } else {
+        println!(
+            "{} {} parameter mismatch(es), {} type advisory",
+            style("✗").red(),
+            errors,
+            advisories
+        );
+        std::process::exit(1);
+    }
+}
+
+/// A parameter mismatch as a SARIF finding
+fn param_finding(issue: &ParamIssue) -> SarifFinding {
+    let rule_id = match issue.kind {
+        ParamIssueKind::Missing => "acp/param/missing",
+        ParamIssueKind::Extra => "acp/param/extra",
+        ParamIssueKind::Reordered => "acp/param/reordered",
+        ParamIssueKind::Type => "acp/param/type",
+    };
+    SarifFinding {
+        rule_id,
+        file: issue.file.clone(),
+        line: issue.line,
+        message: format!("{}: {}", issue.symbol, issue.message),
+        key: format!("{}:{}", issue.symbol, issue.message),
+    }
+}
+
+/// Report commits to frozen/restricted files made after the lock by
+/// authors outside `constraints.approvers`
+fn check_policy(options: &CheckOptions, config: &Config) -> Result<()> {
+    let cache_data = Cache::from_json(&options.cache)?;
+    let root = config.root.canonicalize()?;
+    let repo = GitRepository::open(&root)?;
+    let approvers = config
+        .constraints
+        .as_ref()
+        .map(|c| c.approvers.as_slice())
+        .unwrap_or_default();
+
+    let (files, unmatched) = resolve_files(&cache_data, &options.files)?;
+    for arg in &unmatched {
+        println!("{} Not in cache: {}", style("•").dim(), arg);
+    }
+
+    let report = check_lock_policy(&repo, &root, &cache_data, &files, approvers)?;
+
+    for (file, reason) in &report.skipped {
+        println!("{} Skipping {}: {}", style("⚠").yellow(), file, reason);
+    }
+    for v in &report.violations {
+        println!(
+            "{} {}:{} ({:?}, locked in {} by {})",
+            style("✗").red(),
+            v.file,
+            v.lock_line,
+            v.level,
+            &v.lock_commit[..7.min(v.lock_commit.len())],
+            v.lock_author
+        );
+        println!(
+            "    {} {} <{}> {}: {}",
+            &v.commit[..7.min(v.commit.len())],
+            v.author,
+            v.author_email,
+            v.timestamp.format("%Y-%m-%d"),
+            v.message
+        );
+    }
+
+    if report.is_clean() {
+        println!(
+            "{} No unapproved changes to {} locked file(s)",
+            style("✓").green(),
+            report.checked
+        );
+        Ok(())
+    } else {
+        println!(
+            "\n{} {} unapproved commit(s) to locked files",
+            style("✗").red(),
+            report.violations.len()
+        );
+        std::process::exit(1);
+    }
+}
+
 /// Show all files with constraints
 fn show_all_constraints(cache_data: &Cache) -> Result<()> {
     let constraints = match &cache_data.constraints {
@@ -103,7 +631,7 @@ fn show_all_constraints(cache_data: &Cache) -> Result<()> {
         }
     };
 
-    if constraints.by_file.is_empty() {
+    if constraints.by_file.is_empty() && constraints.directory_locks.is_empty() {
         println!("{} No file constraints defined", style("•").dim());
         return Ok(());
     }
@@ -121,6 +649,20 @@ fn show_all_constraints(cache_data: &Cache) -> Result<()> {
         }
     }
 
+    // Files without their own lock inherit from directory rules
+    if !constraints.directory_locks.is_empty() {
+        let mut paths: Vec<&String> = cache_data.files.keys().collect();
+        paths.sort();
+        for path in paths {
+            if let Some(lock) = constraints.effective_lock(path) {
+                if matches!(lock.source, LockSource::Inherited { .. }) {
+                    let level = format!("{:?}", lock.level);
+                    by_level.entry(level).or_default().push(path);
+                }
+            }
+        }
+    }
+
     // Sort by severity (frozen first)
     let level_order = [
         "Frozen",
@@ -156,3 +698,34 @@ fn show_all_constraints(cache_data: &Cache) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_files_expands_globs_against_cache() {
+        let mut cache = Cache::new("demo", ".");
+        for path in ["./src/a.rs", "./src/nested/b.rs", "./README.ts"] {
+            let file = serde_json::from_value(serde_json::json!({
+                "path": path,
+                "lines": 1,
+                "language": "rust",
+            }))
+            .unwrap();
+            cache.files.insert(path.to_string(), file);
+        }
+        let args = |paths: &[&str]| paths.iter().map(PathBuf::from).collect::<Vec<_>>();
+
+        let (files, unmatched) = resolve_files(&cache, &args(&["./src/a.rs", "new.rs"])).unwrap();
+        assert_eq!(files, vec!["./src/a.rs"]);
+        assert_eq!(unmatched, vec!["new.rs"]);
+
+        // Globs match as in `include`/`exclude`: `*` crosses directories
+        for glob in ["src/*.rs", "src/**/*.rs"] {
+            let (files, _) = resolve_files(&cache, &args(&[glob])).unwrap();
+            assert_eq!(files, vec!["./src/a.rs", "./src/nested/b.rs"]);
+        }
+        assert!(resolve_files(&cache, &args(&["src/[.rs"])).is_err());
+    }
+}
@@ -314,7 +314,7 @@ fn execute_debug_context(cache: &Cache, target: &str, options: &ContextOptions)
         let symbols: Vec<SymbolInfo> = file
             .exports
             .iter()
-            .filter_map(|name| cache.symbols.get(name))
+            .filter_map(|name| cache.get_symbol_in_file(name, target))
             .map(|s| SymbolInfo {
                 name: s.name.clone(),
                 symbol_type: format!("{:?}", s.symbol_type).to_lowercase(),
@@ -350,7 +350,7 @@ fn execute_debug_context(cache: &Cache, target: &str, options: &ContextOptions)
     // Get hotpaths using Query API
     let q = Query::new(cache);
     let hotpaths: Vec<String> = q
-        .hotpaths()
+        .most_called()
         .filter(|hp| hp.contains(&file_path) || hp.contains(target))
         .take(3)
         .map(String::from)
@@ -4,14 +4,26 @@
 //! @acp:layer handler
 //!
 //! Implements `acp daemon` command for daemon lifecycle management.
+//!
+//! With `--watch` the daemon runs in-process instead of through `acpd`:
+//! the file watcher rebuilds the cache on change and the cache server
+//! pushes each rebuild to `/events` subscribers.
 
 use std::io::{BufRead, BufReader};
 use std::net::TcpListener;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::Arc;
 
 use anyhow::Result;
 use console::style;
+use tokio::sync::watch;
+
+use super::index::report_index_problems;
+use crate::config::Config;
+use crate::index::Indexer;
+use crate::server::{serve, CacheHub, CacheStatus};
+use crate::watch::{ChangeStream, DEFAULT_DEBOUNCE};
 
 /// Daemon subcommands
 #[derive(Debug, Clone)]
@@ -22,6 +34,10 @@ pub enum DaemonSubcommand {
         foreground: bool,
         /// HTTP server port
         port: u16,
+        /// Re-index on file changes and serve `/cache` and `/events`
+        watch: bool,
+        /// Config file, passed on to the background process in watch mode
+        config_path: PathBuf,
     },
     /// Stop the daemon
     Stop,
@@ -37,15 +53,21 @@ pub enum DaemonSubcommand {
 }
 
 /// Execute daemon subcommands
-pub fn execute_daemon(cmd: DaemonSubcommand) -> Result<()> {
+pub async fn execute_daemon(cmd: DaemonSubcommand, config: &Config) -> Result<()> {
     let acp_dir = PathBuf::from(".acp");
     let pid_file = acp_dir.join("daemon.pid");
     let log_file = acp_dir.join("daemon.log");
 
     match cmd {
-        DaemonSubcommand::Start { foreground, port } => {
-            // Check if already running
-            if let Some(pid) = read_pid_file(&pid_file) {
+        DaemonSubcommand::Start {
+            foreground,
+            port,
+            watch,
+            config_path,
+        } => {
+            // Check if already running (a background watch daemon finds its
+            // own PID here once the parent has written the file)
+            if let Some(pid) = read_pid_file(&pid_file).filter(|&p| p != std::process::id()) {
                 if is_process_running(pid) {
                     println!(
                         "{} Daemon already running with PID {}",
@@ -71,8 +93,39 @@ pub fn execute_daemon(cmd: DaemonSubcommand) -> Result<()> {
                 std::fs::create_dir_all(&acp_dir)?;
             }
 
-            // Find the acpd binary
-            let acpd_path = find_acpd_binary()?;
+            if watch && foreground {
+                // Record this process so `daemon status` and `daemon stop`
+                // find it; a background start writes the same pid:port
+                let pid = std::process::id();
+                std::fs::write(&pid_file, format!("{}:{}", pid, port))?;
+                let result = run_watch_daemon(port, config.clone()).await;
+                if read_pid_file(&pid_file) == Some(pid) {
+                    let _ = std::fs::remove_file(&pid_file);
+                }
+                return result;
+            }
+
+            // Watch mode runs this binary in the background; otherwise use acpd
+            let (program, args) = if watch {
+                (
+                    std::env::current_exe()?,
+                    vec![
+                        "--config".to_string(),
+                        config_path.to_string_lossy().to_string(),
+                        "daemon".to_string(),
+                        "start".to_string(),
+                        "--watch".to_string(),
+                        "--foreground".to_string(),
+                        "--port".to_string(),
+                        port.to_string(),
+                    ],
+                )
+            } else {
+                (
+                    find_acpd_binary()?,
+                    vec!["--port".to_string(), port.to_string(), "run".to_string()],
+                )
+            };
 
             if foreground {
                 // Run in foreground - exec the daemon
@@ -80,11 +133,7 @@ pub fn execute_daemon(cmd: DaemonSubcommand) -> Result<()> {
                     "{} Starting daemon in foreground mode...",
                     style("→").cyan()
                 );
-                let status = Command::new(&acpd_path)
-                    .arg("--port")
-                    .arg(port.to_string())
-                    .arg("run")
-                    .status()?;
+                let status = Command::new(&program).args(&args).status()?;
 
                 if !status.success() {
                     eprintln!("{} Daemon exited with error", style("✗").red());
@@ -95,10 +144,8 @@ pub fn execute_daemon(cmd: DaemonSubcommand) -> Result<()> {
                 let log = std::fs::File::create(&log_file)?;
                 let log_err = log.try_clone()?;
 
-                let child = Command::new(&acpd_path)
-                    .arg("--port")
-                    .arg(port.to_string())
-                    .arg("run")
+                let child = Command::new(&program)
+                    .args(&args)
                     .stdout(log)
                     .stderr(log_err)
                     .spawn()?;
@@ -115,6 +162,9 @@ pub fn execute_daemon(cmd: DaemonSubcommand) -> Result<()> {
                 );
                 println!("  Log file: {}", log_file.display());
                 println!("  API: http://127.0.0.1:{}/health", port);
+                if watch {
+                    println!("  Events: http://127.0.0.1:{}/events", port);
+                }
             }
         }
 
@@ -172,6 +222,10 @@ pub fn execute_daemon(cmd: DaemonSubcommand) -> Result<()> {
                     if let Ok(health) = check_daemon_health(port) {
                         println!("  Health: {}", health);
                     }
+                    // Only the watch daemon serves /status
+                    if let Some(status) = fetch_daemon_status(port) {
+                        print_cache_status(&status);
+                    }
                 } else {
                     println!(
                         "{} Daemon not running (stale PID file)",
@@ -226,6 +280,108 @@ pub fn execute_daemon(cmd: DaemonSubcommand) -> Result<()> {
     Ok(())
 }
 
+/// Run the watcher and cache server in this process until SIGINT/SIGTERM
+async fn run_watch_daemon(port: u16, config: Config) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port)).await?;
+    let root = config.root.clone();
+    let cache_path = config.cache_path();
+    let indexer = Indexer::new(config.clone())?;
+
+    // Millisecond start time keeps event ids increasing across restarts
+    let hub = Arc::new(
+        CacheHub::new(chrono::Utc::now().timestamp_millis().max(0) as u64)
+            .with_cache_path(&cache_path),
+    );
+    rebuild(&indexer, &root, &cache_path, &hub, Vec::new()).await?;
+
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let server = tokio::spawn(serve(listener, hub.clone(), shutdown_rx));
+
+    let mut changes = ChangeStream::spawn(config, root.clone(), DEFAULT_DEBOUNCE);
+
+    println!(
+        "{} Watching {} and serving http://127.0.0.1:{} (/health, /cache, /events)",
+        style("✓").green(),
+        root.display(),
+        port
+    );
+
+    let signal = shutdown_signal();
+    tokio::pin!(signal);
+    loop {
+        tokio::select! {
+            _ = &mut signal => break,
+            changed = changes.next_batch() => {
+                let Some(changed) = changed else {
+                    eprintln!("{} File watcher stopped", style("✗").red());
+                    break;
+                };
+                if let Err(e) = rebuild(&indexer, &root, &cache_path, &hub, changed).await {
+                    eprintln!("{} Re-index failed: {}", style("✗").red(), e);
+                }
+            }
+        }
+    }
+
+    println!("{} Shutting down...", style("→").cyan());
+    let _ = shutdown_tx.send(true);
+    let _ = server.await;
+    changes.stop()?;
+    Ok(())
+}
+
+/// Re-index, write the cache file and notify subscribers
+async fn rebuild(
+    indexer: &Indexer,
+    root: &Path,
+    cache_path: &Path,
+    hub: &CacheHub,
+    changed: Vec<String>,
+) -> Result<()> {
+    let report = indexer.index_with_report(root).await?;
+    report_index_problems(&report, false);
+    let cache = report.cache;
+    if let Some(parent) = cache_path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    cache.write_json(cache_path)?;
+    let event = hub.publish(&cache, changed)?;
+    println!(
+        "{} Cache rebuilt (event {}, {} files, {} symbols)",
+        style("→").cyan(),
+        event.id,
+        event.files,
+        event.symbols
+    );
+    Ok(())
+}
+
+/// Resolve on Ctrl-C, or on SIGTERM from `acp daemon stop`
+pub(crate) async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut term) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = term.recv() => {}
+                }
+            }
+            Err(_) => {
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
 /// Read PID file content. Format: "pid" or "pid:port"
 fn read_pid_file(path: &PathBuf) -> Option<u32> {
     std::fs::read_to_string(path).ok().and_then(|s| {
@@ -305,6 +461,43 @@ fn find_acpd_binary() -> Result<PathBuf> {
     ))
 }
 
+/// Fetch `/status`, if the daemon serves it and has finished indexing
+fn fetch_daemon_status(port: u16) -> Option<CacheStatus> {
+    let output = Command::new("curl")
+        .arg("-sf")
+        .arg("-m")
+        .arg("2") // 2 second timeout
+        .arg(format!("http://127.0.0.1:{}/status", port))
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| serde_json::from_slice(&output.stdout).ok())
+        .flatten()
+}
+
+fn print_cache_status(status: &CacheStatus) {
+    if let Some(path) = &status.cache_path {
+        println!("  Cache: {}", path);
+    }
+    println!(
+        "  Indexed: {} ({} files, {} symbols)",
+        status.generated_at.format("%Y-%m-%d %H:%M:%S UTC"),
+        status.files,
+        status.symbols
+    );
+    if status.stale {
+        println!(
+            "  {} Stale: {} file(s) changed since indexing",
+            style("⚠").yellow(),
+            status.out_of_date
+        );
+    } else {
+        println!("  {} Up to date with the working tree", style("✓").green());
+    }
+}
+
 fn check_daemon_health(port: u16) -> std::result::Result<String, Box<dyn std::error::Error>> {
     let output = Command::new("curl")
         .arg("-s")
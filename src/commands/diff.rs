@@ -0,0 +1,195 @@
+//! @acp:module "Diff Command"
+//! @acp:summary "Compare two cache snapshots for PR review"
+//! @acp:domain cli
+//! @acp:layer handler
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use console::style;
+
+use super::output::format_constraint_level;
+use crate::cache::{Cache, CacheDiff, FieldChange};
+
+/// Options for the diff command
+#[derive(Debug, Clone)]
+pub struct DiffOptions {
+    /// Cache before the change
+    pub old: PathBuf,
+    /// Cache after the change
+    pub new: PathBuf,
+    /// Output as JSON
+    pub json: bool,
+}
+
+/// Execute the diff command
+pub fn execute_diff(options: DiffOptions) -> Result<()> {
+    let old = Cache::from_json(&options.old)?;
+    let new = Cache::from_json(&options.new)?;
+    let diff = CacheDiff::between(&old, &new);
+
+    if options.json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+        return Ok(());
+    }
+
+    if diff.is_empty() {
+        println!("{} No changes", style("✓").green());
+        return Ok(());
+    }
+
+    if !diff.files.added.is_empty()
+        || !diff.files.removed.is_empty()
+        || !diff.files.modified.is_empty()
+    {
+        println!("{}", style("Files:").bold());
+        for path in &diff.files.added {
+            println!("  {} {}", style("+").green(), path);
+        }
+        for path in &diff.files.removed {
+            println!("  {} {}", style("-").red(), path);
+        }
+        for file in &diff.files.modified {
+            println!("  {} {}", style("~").yellow(), file.path);
+            print_changes(&file.changes);
+        }
+        println!();
+    }
+
+    if !diff.symbols.added.is_empty()
+        || !diff.symbols.removed.is_empty()
+        || !diff.symbols.modified.is_empty()
+    {
+        println!("{}", style("Symbols:").bold());
+        for symbol in &diff.symbols.added {
+            println!(
+                "  {} {} {}",
+                style("+").green(),
+                symbol.qualified_name,
+                style(format!("({:?})", symbol.symbol_type).to_lowercase()).dim()
+            );
+        }
+        for symbol in &diff.symbols.removed {
+            println!(
+                "  {} {} {}",
+                style("-").red(),
+                symbol.qualified_name,
+                style(format!("({:?})", symbol.symbol_type).to_lowercase()).dim()
+            );
+        }
+        for symbol in &diff.symbols.modified {
+            let marker = if symbol.signature_changed() {
+                style(" [signature]").yellow().to_string()
+            } else {
+                String::new()
+            };
+            println!(
+                "  {} {}{}",
+                style("~").yellow(),
+                symbol.qualified_name,
+                marker
+            );
+            print_changes(&symbol.changes);
+        }
+        println!();
+    }
+
+    if !diff.hacks.added.is_empty() || !diff.hacks.removed.is_empty() {
+        println!("{}", style("Hacks:").bold());
+        for hack in &diff.hacks.added {
+            println!(
+                "  {} {}: {}",
+                style("+").green(),
+                location(&hack.file, hack.line),
+                hack.reason
+            );
+        }
+        for hack in &diff.hacks.removed {
+            println!(
+                "  {} {}: {}",
+                style("-").red(),
+                location(&hack.file, hack.line),
+                hack.reason
+            );
+        }
+        println!();
+    }
+
+    if !diff.locks.is_empty() {
+        println!("{}", style("Locks:").bold());
+        for lock in &diff.locks {
+            let old = lock
+                .old
+                .map(|l| format_constraint_level(&l).to_string())
+                .unwrap_or_else(|| style("none").dim().to_string());
+            let new = lock
+                .new
+                .map(|l| format_constraint_level(&l).to_string())
+                .unwrap_or_else(|| style("none").dim().to_string());
+            let mark = if lock.newly_frozen() {
+                style("⚠").yellow()
+            } else {
+                style("~").yellow()
+            };
+            println!("  {} {}: {} → {}", mark, lock.path, old, new);
+        }
+        println!();
+    }
+
+    let delta = diff.coverage.delta;
+    let delta_text = format!("{:+.1}%", delta);
+    let delta_styled = if delta > 0.0 {
+        style(delta_text).green()
+    } else if delta < 0.0 {
+        style(delta_text).red()
+    } else {
+        style(delta_text).dim()
+    };
+    println!(
+        "{} {:.1}% → {:.1}% ({})",
+        style("Coverage:").bold(),
+        diff.coverage.old,
+        diff.coverage.new,
+        delta_styled
+    );
+
+    println!();
+    println!(
+        "{} files +{} -{} ~{}, symbols +{} -{} ~{} ({} signature changes), hacks +{} -{}, {} lock changes",
+        style("Summary:").bold(),
+        diff.files.added.len(),
+        diff.files.removed.len(),
+        diff.files.modified.len(),
+        diff.symbols.added.len(),
+        diff.symbols.removed.len(),
+        diff.symbols.modified.len(),
+        diff.symbols
+            .modified
+            .iter()
+            .filter(|s| s.signature_changed())
+            .count(),
+        diff.hacks.added.len(),
+        diff.hacks.removed.len(),
+        diff.locks.len()
+    );
+
+    Ok(())
+}
+
+fn print_changes(changes: &[FieldChange]) {
+    for change in changes {
+        println!(
+            "      {}: {} → {}",
+            change.field,
+            style(change.old.as_deref().unwrap_or("none")).dim(),
+            change.new.as_deref().unwrap_or("none")
+        );
+    }
+}
+
+fn location(file: &str, line: Option<usize>) -> String {
+    match line {
+        Some(line) => format!("{}:{}", file, line),
+        None => file.to_string(),
+    }
+}
@@ -5,7 +5,7 @@
 
 use std::path::PathBuf;
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use console::style;
 
 use crate::vars::{ExpansionMode, VarExpander, VarResolver, VarsFile};
@@ -13,25 +13,32 @@ use crate::vars::{ExpansionMode, VarExpander, VarResolver, VarsFile};
 /// Options for the expand command
 #[derive(Debug, Clone)]
 pub struct ExpandOptions {
-    /// Text to expand (reads from stdin if None)
+    /// Text to expand (reads from `file` or stdin if None)
     pub text: Option<String>,
+    /// File to read the text from
+    pub file: Option<PathBuf>,
     /// Expansion mode
     pub mode: String,
     /// Vars file path
     pub vars: PathBuf,
     /// Show inheritance chains
     pub chains: bool,
+    /// Fail when any variable reference cannot be resolved
+    pub strict: bool,
 }
 
 /// Execute the expand command
 pub fn execute_expand(options: ExpandOptions) -> Result<()> {
     let vars_file = VarsFile::from_json(&options.vars)?;
     let resolver = VarResolver::new(vars_file);
+    resolver.validate()?;
     let mut expander = VarExpander::new(resolver);
 
-    let input = match options.text {
-        Some(t) => t,
-        None => {
+    let input = match (options.text, &options.file) {
+        (Some(t), _) => t,
+        (None, Some(file)) => std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?,
+        (None, None) => {
             use std::io::Read;
             let mut buf = String::new();
             std::io::stdin().read_to_string(&mut buf)?;
@@ -39,17 +46,28 @@ pub fn execute_expand(options: ExpandOptions) -> Result<()> {
         }
     };
 
-    let expansion_mode = match options.mode.as_str() {
-        "none" => ExpansionMode::None,
-        "summary" => ExpansionMode::Summary,
-        "inline" => ExpansionMode::Inline,
-        "annotated" => ExpansionMode::Annotated,
-        "block" => ExpansionMode::Block,
-        "interactive" => ExpansionMode::Interactive,
-        _ => ExpansionMode::Annotated,
+    let result = if options.mode == "template" {
+        expander.expand_template(&input)
+    } else {
+        let expansion_mode = match options.mode.as_str() {
+            "none" => ExpansionMode::None,
+            "summary" => ExpansionMode::Summary,
+            "inline" => ExpansionMode::Inline,
+            "annotated" => ExpansionMode::Annotated,
+            "block" => ExpansionMode::Block,
+            "interactive" => ExpansionMode::Interactive,
+            _ => ExpansionMode::Annotated,
+        };
+        expander.expand_text(&input, expansion_mode)
     };
 
-    let result = expander.expand_text(&input, expansion_mode);
+    if options.strict && !result.vars_unresolved.is_empty() {
+        let mut unresolved = result.vars_unresolved.clone();
+        unresolved.sort();
+        unresolved.dedup();
+        bail!("Unresolved variables: {}", unresolved.join(", "));
+    }
+
     println!("{}", result.expanded);
 
     if options.chains && !result.inheritance_chains.is_empty() {
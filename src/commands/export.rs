@@ -0,0 +1,205 @@
+//! @acp:module "Export Command"
+//! @acp:summary "Export the cache in formats other tools consume"
+//! @acp:domain cli
+//! @acp:layer handler
+//!
+//! `acp export ctags` writes an Exuberant-ctags `tags` file from the cached
+//! symbols, so editors can jump to definitions without a separate ctags run.
+//! Each tag is addressed by a search pattern built from its definition line;
+//! when the source can't be read the line number is used instead. Cached
+//! paths are relative to the project root, so sources are read from there
+//! rather than the working directory.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use console::style;
+
+use crate::cache::{Cache, SymbolEntry, SymbolType};
+use crate::config::Config;
+
+/// Subcommands for the export command
+#[derive(Debug, Clone)]
+pub enum ExportSubcommand {
+    /// Write an Exuberant-ctags tags file
+    Ctags { output: PathBuf },
+}
+
+/// Options for the export command
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    /// Cache file path
+    pub cache: PathBuf,
+    /// Subcommand to execute
+    pub subcommand: ExportSubcommand,
+}
+
+/// Execute the export command
+pub fn execute_export(options: ExportOptions, config: &Config) -> Result<()> {
+    let cache = Cache::from_json(&options.cache)?;
+    match options.subcommand {
+        ExportSubcommand::Ctags { output } => {
+            let mut sources = HashMap::new();
+            let tags = render_ctags(&cache, |file| {
+                sources
+                    .entry(file.to_string())
+                    .or_insert_with(|| fs::read_to_string(config.root.join(file)).ok())
+                    .clone()
+            });
+            fs::write(&output, tags)?;
+            println!(
+                "{} Wrote {} tags to {}",
+                style("✓").green(),
+                cache.symbols.len(),
+                output.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// @acp:summary "Render cached symbols as a sorted Exuberant-ctags file"
+///
+/// `read_source` returns a file's contents, or `None` if it can't be read.
+pub fn render_ctags(cache: &Cache, mut read_source: impl FnMut(&str) -> Option<String>) -> String {
+    let mut symbols: Vec<&SymbolEntry> = cache.symbols.values().collect();
+    // The format requires byte-wise sorting by tag name
+    symbols.sort_by(|a, b| (&a.name, &a.file, a.lines[0]).cmp(&(&b.name, &b.file, b.lines[0])));
+
+    let mut out = String::from(
+        "!_TAG_FILE_FORMAT\t2\t/extended format; --format=1 will not append ;\" to lines/\n\
+         !_TAG_FILE_SORTED\t1\t/0=unsorted, 1=sorted, 2=foldcase/\n\
+         !_TAG_PROGRAM_NAME\tacp\t//\n",
+    );
+    out.push_str(&format!(
+        "!_TAG_PROGRAM_VERSION\t{}\t//\n",
+        env!("CARGO_PKG_VERSION")
+    ));
+
+    for symbol in symbols {
+        let line = symbol.lines[0];
+        let definition = read_source(&symbol.file)
+            .and_then(|source| source.lines().nth(line.checked_sub(1)?).map(str::to_string));
+        let address = match definition {
+            Some(text) => format!("/^{}$/", escape_pattern(&text)),
+            None => line.to_string(),
+        };
+        out.push_str(&format!(
+            "{}\t{}\t{};\"\t{}\tline:{}",
+            symbol.name,
+            symbol.file,
+            address,
+            ctags_kind(symbol.symbol_type),
+            line
+        ));
+        // ctags marks symbols only visible in their own file this way
+        if !symbol.exported {
+            out.push_str("\tfile:");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// @acp:summary "ctags kind letter for a symbol type"
+fn ctags_kind(symbol_type: SymbolType) -> char {
+    match symbol_type {
+        SymbolType::Function => 'f',
+        SymbolType::Method => 'm',
+        SymbolType::Class => 'c',
+        SymbolType::Interface | SymbolType::Trait => 'i',
+        SymbolType::Type => 't',
+        SymbolType::Enum => 'g',
+        SymbolType::Struct => 's',
+        SymbolType::Const => 'C',
+    }
+}
+
+/// @acp:summary "Escape a source line for a /^...$/ search pattern"
+///
+/// Backslashes and the `/` delimiter are escaped; a trailing `$` would read as
+/// the end anchor, so it is escaped too.
+fn escape_pattern(line: &str) -> String {
+    let mut escaped = line.replace('\\', "\\\\").replace('/', "\\/");
+    if escaped.ends_with('$') {
+        escaped.insert(escaped.len() - 1, '\\');
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(name: &str, symbol_type: SymbolType, line: usize, exported: bool) -> SymbolEntry {
+        SymbolEntry {
+            symbol_type,
+            lines: [line, line + 2],
+            exported,
+            ..SymbolEntry::new(name, "src/app.ts")
+        }
+    }
+
+    #[test]
+    fn test_render_ctags_sorted_and_escaped() {
+        let mut cache = Cache::new("demo", ".");
+        for sym in [
+            symbol("parse", SymbolType::Function, 2, true),
+            symbol("Config", SymbolType::Class, 1, true),
+            symbol("helper", SymbolType::Function, 3, false),
+            symbol("missing", SymbolType::Const, 40, true),
+        ] {
+            cache.symbols.insert(sym.qualified_name.clone(), sym);
+        }
+        let source = "export class Config {}\n\
+                      export function parse(s) { return s.split(/\\//); }\n\
+                      function helper() { return '$' }$\n";
+
+        let tags = render_ctags(&cache, |_| Some(source.to_string()));
+        let lines: Vec<&str> = tags.lines().filter(|l| !l.starts_with("!_")).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "Config\tsrc/app.ts\t/^export class Config {}$/;\"\tc\tline:1",
+                "helper\tsrc/app.ts\t/^function helper() { return '$' }\\$$/;\"\tf\tline:3\tfile:",
+                "missing\tsrc/app.ts\t40;\"\tC\tline:40",
+                "parse\tsrc/app.ts\t/^export function parse(s) { return s.split(\\/\\\\\\/\\/); }$/;\"\tf\tline:2",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_export_reads_sources_from_project_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/app.ts"), "export function parse(s) {}\n").unwrap();
+
+        let mut cache = Cache::new("demo", root.to_str().unwrap());
+        let sym = symbol("parse", SymbolType::Function, 1, true);
+        cache.symbols.insert(sym.qualified_name.clone(), sym);
+        let cache_path = root.join("acp.cache.json");
+        cache.write_json(&cache_path).unwrap();
+
+        let output = root.join("tags");
+        let config = Config {
+            root: root.to_path_buf(),
+            ..Default::default()
+        };
+        execute_export(
+            ExportOptions {
+                cache: cache_path,
+                subcommand: ExportSubcommand::Ctags {
+                    output: output.clone(),
+                },
+            },
+            &config,
+        )
+        .unwrap();
+
+        let tags = fs::read_to_string(output).unwrap();
+        assert!(tags.contains("\t/^export function parse(s) {}$/;\""));
+    }
+}
@@ -0,0 +1,88 @@
+//! @acp:module "Gate Command"
+//! @acp:summary "Evaluate project quality gates for CI"
+//! @acp:domain cli
+//! @acp:layer handler
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use console::style;
+
+use crate::cache::Cache;
+use crate::config::Config;
+use crate::constraints::evaluate_gates;
+
+/// Options for the gate command
+#[derive(Debug, Clone)]
+pub struct GateOptions {
+    /// Cache file
+    pub cache: PathBuf,
+    /// Output as JSON
+    pub json: bool,
+}
+
+/// Execute the gate command
+///
+/// Exits with status 1 when any configured gate fails.
+pub fn execute_gate(options: GateOptions, config: &Config) -> Result<()> {
+    let gates = config
+        .constraints
+        .as_ref()
+        .and_then(|c| c.gates.clone())
+        .unwrap_or_default();
+
+    let cache_data = Cache::from_json(&options.cache)?;
+    let report = evaluate_gates(&cache_data, &gates, &config.root);
+
+    if options.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else if report.gates.is_empty() {
+        println!(
+            "{} No quality gates configured (set constraints.gates in .acp.config.json)",
+            style("•").dim()
+        );
+    } else {
+        for outcome in &report.gates {
+            let mark = if outcome.passed {
+                style("✓").green()
+            } else {
+                style("✗").red()
+            };
+            println!(
+                "{} {}: {}",
+                mark,
+                style(&outcome.gate).bold(),
+                outcome.message
+            );
+            for offender in outcome.offenders.iter().take(10) {
+                println!("    {}", offender);
+            }
+            if outcome.offenders.len() > 10 {
+                println!("    ... and {} more", outcome.offenders.len() - 10);
+            }
+        }
+
+        let failed = report.gates.iter().filter(|g| !g.passed).count();
+        println!();
+        if failed == 0 {
+            println!(
+                "{} All {} gates passed",
+                style("✓").green(),
+                report.gates.len()
+            );
+        } else {
+            println!(
+                "{} {} of {} gates failed",
+                style("✗").red(),
+                failed,
+                report.gates.len()
+            );
+        }
+    }
+
+    if !report.passed {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
@@ -10,8 +10,12 @@ use std::path::PathBuf;
 use anyhow::Result;
 use console::style;
 
+use crate::cache::{append_snapshot, history_path, Cache, StatsSnapshot};
 use crate::config::Config;
-use crate::index::Indexer;
+use crate::index::{IndexPlan, IndexReport, Indexer, SkipReason};
+
+/// Parse failures listed individually; the rest are only counted
+const MAX_REPORTED_FAILURES: usize = 5;
 
 /// Options for the index command
 #[derive(Debug, Clone)]
@@ -26,6 +30,16 @@ pub struct IndexOptions {
     pub bridge: bool,
     /// Disable documentation bridging (overrides config)
     pub no_bridge: bool,
+    /// Reuse git metadata from the existing cache for files unchanged since it
+    pub git_changed_only: bool,
+    /// Fail without writing the cache if any file fails to parse
+    pub strict: bool,
+    /// Append a stats snapshot to the history log (also `output.history`)
+    pub history: bool,
+    /// Report what would be indexed without parsing or writing a cache
+    pub dry_run: bool,
+    /// Print the dry-run report as JSON
+    pub json: bool,
 }
 
 impl Default for IndexOptions {
@@ -36,14 +50,17 @@ impl Default for IndexOptions {
             vars: false,
             bridge: false,
             no_bridge: false,
+            git_changed_only: false,
+            strict: false,
+            history: false,
+            dry_run: false,
+            json: false,
         }
     }
 }
 
 /// Execute the index command
 pub async fn execute_index(options: IndexOptions, config: Config) -> Result<()> {
-    println!("{} Indexing codebase...", style("→").cyan());
-
     // Use config from target root if it exists, otherwise use defaults
     let mut effective_config = {
         let root_config = options.root.join(".acp.config.json");
@@ -66,6 +83,18 @@ pub async fn execute_index(options: IndexOptions, config: Config) -> Result<()>
         effective_config.bridge.enabled = true;
     }
 
+    if options.dry_run {
+        let plan = Indexer::new(effective_config)?.plan(&options.root);
+        if options.json {
+            println!("{}", serde_json::to_string_pretty(&plan)?);
+        } else {
+            print_plan(&plan);
+        }
+        return Ok(());
+    }
+
+    println!("{} Indexing codebase...", style("→").cyan());
+
     // Show bridging status
     if effective_config.bridge.enabled {
         println!(
@@ -75,8 +104,26 @@ pub async fn execute_index(options: IndexOptions, config: Config) -> Result<()>
         );
     }
 
-    let indexer = Indexer::new(effective_config.clone())?;
-    let cache = indexer.index(&options.root).await?;
+    let mut indexer = Indexer::new(effective_config.clone())?;
+    if options.git_changed_only {
+        match Cache::from_json(&options.output) {
+            Ok(previous) => indexer = indexer.with_previous_cache(previous),
+            Err(_) => eprintln!(
+                "{} No readable cache at {}, computing git metadata for all files",
+                style("⚠").yellow(),
+                options.output.display()
+            ),
+        }
+    }
+    let report = indexer.index_with_report(&options.root).await?;
+    report_index_problems(&report, options.strict);
+    if options.strict && !report.failures.is_empty() {
+        anyhow::bail!(
+            "Indexing failed: {} file(s) could not be parsed (--strict)",
+            report.failures.len()
+        );
+    }
+    let cache = report.cache;
 
     // Warn if no files were found, but still create empty cache
     if cache.stats.files == 0 {
@@ -95,6 +142,56 @@ pub async fn execute_index(options: IndexOptions, config: Config) -> Result<()>
         // Still create the cache file (empty but valid)
     }
 
+    // Oversized files (minified bundles, generated code) are left out
+    if !cache.stats.skipped.is_empty() {
+        eprintln!(
+            "{} Skipped {} file(s) larger than {} MB (limits.max_file_size_mb):",
+            style("⚠").yellow(),
+            cache.stats.skipped.len(),
+            effective_config
+                .limits
+                .clone()
+                .unwrap_or_default()
+                .max_file_size_mb
+        );
+        for skipped in &cache.stats.skipped {
+            eprintln!(
+                "    {} ({:.1} MB, {} lines)",
+                skipped.path,
+                skipped.size_bytes as f64 / (1024.0 * 1024.0),
+                skipped.lines
+            );
+        }
+    }
+
+    // The call graph is keyed by simple name, so duplicates blur callers/callees
+    let ambiguous = cache.ambiguous_symbol_names();
+    if !ambiguous.is_empty() {
+        let examples: Vec<_> = ambiguous.iter().take(5).map(|(n, _)| n.as_str()).collect();
+        eprintln!(
+            "{} {} symbol name(s) are defined in more than one place ({}{})",
+            style("⚠").yellow(),
+            ambiguous.len(),
+            examples.join(", "),
+            if ambiguous.len() > examples.len() {
+                ", ..."
+            } else {
+                ""
+            }
+        );
+        eprintln!("  Call graph edges for these names are shared; see `acp query symbol <name>`");
+    }
+
+    // `API` and `api` are separate domains unless domains.normalize merges them
+    for names in cache.domain_collisions() {
+        eprintln!(
+            "{} Domains {} differ only in case or separators",
+            style("⚠").yellow(),
+            names.join(", ")
+        );
+        eprintln!("  Set domains.normalize to \"lower\" or \"kebab\" to merge them");
+    }
+
     // Create output directory if needed
     if let Some(parent) = options.output.parent() {
         if !parent.as_os_str().is_empty() && !parent.exists() {
@@ -112,8 +209,19 @@ pub async fn execute_index(options: IndexOptions, config: Config) -> Result<()>
     println!("  Symbols: {}", cache.stats.symbols);
     println!("  Lines: {}", cache.stats.lines);
 
+    let history = options.history || effective_config.output.as_ref().is_some_and(|o| o.history);
+    if history {
+        let path = history_path(&options.output);
+        append_snapshot(&path, &StatsSnapshot::from_cache(&cache))?;
+        println!(
+            "{} Stats snapshot appended to {}",
+            style("✓").green(),
+            path.display()
+        );
+    }
+
     if options.vars {
-        let vars_file = indexer.generate_vars(&cache);
+        let mut vars_file = indexer.generate_vars(&cache);
         // Replace acp.cache.json with acp.vars.json
         let output_str = options.output.to_string_lossy();
         let vars_path = if output_str.contains("acp.cache.json") {
@@ -123,13 +231,106 @@ pub async fn execute_index(options: IndexOptions, config: Config) -> Result<()>
         } else {
             options.output.with_extension("vars.json")
         };
+        let kept = vars_file.keep_manual_from(&vars_path)?;
         vars_file.write_json(&vars_path)?;
         println!(
             "{} Vars written to {}",
             style("✓").green(),
             vars_path.display()
         );
+        if kept > 0 {
+            println!("  Kept {} hand-added vars", kept);
+        }
     }
 
     Ok(())
 }
+
+/// @acp:summary "Print the warnings and parse failures from an index run"
+///
+/// Shared by `index`, `watch` and `daemon`. Failures are marked as errors
+/// when `strict` makes them fatal.
+pub(crate) fn report_index_problems(report: &IndexReport, strict: bool) {
+    for warning in &report.warnings {
+        eprintln!(
+            "{} {}: {}",
+            style("⚠").yellow(),
+            warning.file,
+            warning.message
+        );
+    }
+
+    // Files that failed to parse are missing from the cache
+    let failures = &report.failures;
+    if failures.is_empty() {
+        return;
+    }
+    let marker = if strict {
+        style("✗").red()
+    } else {
+        style("⚠").yellow()
+    };
+    eprintln!(
+        "{} {} file(s) failed to parse and were left out of the cache:",
+        marker,
+        failures.len()
+    );
+    for (path, err) in failures.iter().take(MAX_REPORTED_FAILURES) {
+        eprintln!("    {}: {}", path.display(), err);
+    }
+    if failures.len() > MAX_REPORTED_FAILURES {
+        eprintln!(
+            "    ... and {} more",
+            failures.len() - MAX_REPORTED_FAILURES
+        );
+    }
+}
+
+/// Print a dry-run plan as text
+fn print_plan(plan: &IndexPlan) {
+    println!(
+        "{} Dry run: {} files would be indexed (~{} lines, {:.1} MB)",
+        style("→").cyan(),
+        plan.total_files,
+        plan.estimated_lines,
+        plan.total_bytes as f64 / (1024.0 * 1024.0)
+    );
+    for (language, stats) in &plan.languages {
+        println!(
+            "  {:<12} {:>6} files  ~{} lines",
+            language, stats.files, stats.estimated_lines
+        );
+    }
+
+    if plan.exceeds_max_files {
+        println!(
+            "{} More than {} files match (limits.max_files); indexing would fail",
+            style("✗").red(),
+            plan.max_files
+        );
+    }
+    for (reason, label) in [
+        (SkipReason::Oversized, "larger than limits.max_file_size_mb"),
+        (SkipReason::Unsupported, "in unsupported languages"),
+    ] {
+        let skipped: Vec<_> = plan.skipped.iter().filter(|s| s.reason == reason).collect();
+        if skipped.is_empty() {
+            continue;
+        }
+        println!(
+            "{} {} file(s) {} would be skipped:",
+            style("⚠").yellow(),
+            skipped.len(),
+            label
+        );
+        for skip in skipped {
+            println!("    {} ({} bytes)", skip.path, skip.size_bytes);
+        }
+    }
+    if !plan.excluded.is_empty() {
+        println!("{} Excluded by pattern:", style("•").dim());
+        for (pattern, count) in &plan.excluded {
+            println!("    {:>6}  {}", count, pattern);
+        }
+    }
+}
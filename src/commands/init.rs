@@ -6,14 +6,14 @@
 //! Implements `acp init` command for project initialization.
 
 use std::io::IsTerminal;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use console::style;
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, MultiSelect};
 
-use crate::config::Config;
-use crate::scan::scan_project;
+use crate::config::{Config, SyncSettings};
+use crate::scan::{scan_project, ProjectScan};
 use crate::sync::{SyncExecutor, Tool as SyncTool};
 
 /// Options for the init command
@@ -37,6 +37,8 @@ pub struct InitOptions {
     pub yes: bool,
     /// Skip AI tool bootstrap
     pub no_bootstrap: bool,
+    /// Named preset (rust, node, python, go, monorepo) to start from
+    pub profile: Option<String>,
 }
 
 /// Execute the init command
@@ -54,11 +56,20 @@ pub fn execute_init(options: InitOptions) -> Result<()> {
         std::process::exit(1);
     }
 
-    let mut config = Config::default();
+    let mut config = match &options.profile {
+        Some(name) => Config::from_profile(name)?,
+        None => Config::default(),
+    };
+    // Sync adapters are only ever written by hand; keep them across a
+    // forced re-init so bootstrapping below can use them
+    if let Some(sync) = existing_sync_settings(&config_path) {
+        config.sync = sync;
+    }
 
     // Interactive mode if stdin is TTY, no CLI options, and not using --yes
     let interactive = !options.yes
         && std::io::stdin().is_terminal()
+        && options.profile.is_none()
         && options.include.is_empty()
         && options.exclude.is_empty()
         && options.output.is_none()
@@ -66,11 +77,14 @@ pub fn execute_init(options: InitOptions) -> Result<()> {
         && options.vars_path.is_none()
         && options.workers.is_none();
 
+    let scan = scan_project(".");
     if interactive {
-        run_interactive_init(&mut config)?;
+        run_interactive_init(&mut config, &scan)?;
     } else {
         apply_cli_options(&mut config, &options);
     }
+    // Skip the dependency and build directories of the languages present
+    config.add_language_excludes(scan.languages.iter().map(|l| l.language));
 
     // Create .acp directory
     let acp_dir = PathBuf::from(".acp");
@@ -81,11 +95,19 @@ pub fn execute_init(options: InitOptions) -> Result<()> {
 
     // Write config
     config.save(&config_path)?;
-    println!("{} Created {}", style("✓").green(), config_path.display());
+    match &options.profile {
+        Some(name) => println!(
+            "{} Created {} ({} profile)",
+            style("✓").green(),
+            config_path.display(),
+            name
+        ),
+        None => println!("{} Created {}", style("✓").green(), config_path.display()),
+    }
 
     // Bootstrap AI tool files
     if !options.no_bootstrap {
-        bootstrap_ai_tools(interactive)?;
+        bootstrap_ai_tools(&config, interactive, Path::new("."))?;
     }
 
     // Print next steps
@@ -99,13 +121,9 @@ pub fn execute_init(options: InitOptions) -> Result<()> {
     Ok(())
 }
 
-fn run_interactive_init(config: &mut Config) -> Result<()> {
+fn run_interactive_init(config: &mut Config, scan: &ProjectScan) -> Result<()> {
     println!("{} ACP Project Setup\n", style("→").cyan());
 
-    // Scan project to detect languages
-    println!("{} Scanning project...", style("→").dim());
-    let scan = scan_project(".");
-
     if scan.languages.is_empty() {
         println!("{} No supported languages detected\n", style("⚠").yellow());
     } else {
@@ -163,6 +181,9 @@ fn select_languages_manually(config: &mut Config) -> Result<()> {
         ("Python", vec!["**/*.py"]),
         ("Go", vec!["**/*.go"]),
         ("Java", vec!["**/*.java"]),
+        ("Ruby", vec!["**/*.rb"]),
+        ("Swift", vec!["**/*.swift"]),
+        ("Kotlin", vec!["**/*.kt", "**/*.kts"]),
     ];
 
     let items: Vec<&str> = all_languages.iter().map(|(name, _)| *name).collect();
@@ -190,16 +211,60 @@ fn apply_cli_options(config: &mut Config, options: &InitOptions) {
     // cache_path and vars_path can be passed to commands directly
 }
 
-fn bootstrap_ai_tools(interactive: bool) -> Result<()> {
-    let sync = SyncExecutor::new();
-    let project_root = PathBuf::from(".");
-    let detected = sync.detect_tools(&project_root);
+/// Print what bootstrapping did to one tool file
+fn report_bootstrap(action: &crate::sync::BootstrapAction, output_path: &Path) {
+    let action = match action {
+        crate::sync::BootstrapAction::Created => "Created",
+        crate::sync::BootstrapAction::Merged => "Updated",
+        crate::sync::BootstrapAction::Skipped => "Skipped",
+        crate::sync::BootstrapAction::Conflict => {
+            println!(
+                "{} Kept hand edits in {}; new content written to {}",
+                style("⚠").yellow(),
+                output_path.display(),
+                crate::sync::conflict_path(output_path).display()
+            );
+            return;
+        }
+    };
+    println!(
+        "{} {} {}",
+        style("✓").green(),
+        action,
+        output_path.display()
+    );
+}
 
-    if !detected.is_empty() {
+/// Sync settings from the config file being replaced, if it has any
+fn existing_sync_settings(config_path: &Path) -> Option<SyncSettings> {
+    let existing = Config::load(config_path).ok()?;
+    (!existing.sync.is_empty()).then_some(existing.sync)
+}
+
+fn bootstrap_ai_tools(config: &Config, interactive: bool, project_root: &Path) -> Result<()> {
+    let sync = SyncExecutor::from_config(config).unwrap_or_else(|e| {
+        eprintln!(
+            "{} Ignoring custom sync adapters: {}",
+            style("⚠").yellow(),
+            e
+        );
+        SyncExecutor::new()
+    });
+    let detected = sync.detect_tools(project_root);
+    let detected_custom = sync.detect_custom(project_root);
+
+    if !detected.is_empty() || !detected_custom.is_empty() {
         println!("\n{} Detected AI tools:", style("✓").green());
         for tool in &detected {
             println!("    {} ({})", style(tool.name()).cyan(), tool.output_path());
         }
+        for adapter in &detected_custom {
+            println!(
+                "    {} ({}, custom)",
+                style(adapter.name()).cyan(),
+                adapter.output_path()
+            );
+        }
 
         // In interactive mode, confirm; in non-interactive, just do it
         let should_bootstrap = if interactive {
@@ -214,32 +279,33 @@ fn bootstrap_ai_tools(interactive: bool) -> Result<()> {
         if should_bootstrap {
             println!();
             for tool in detected {
-                match sync.bootstrap_tool(tool, &project_root) {
-                    Ok(result) => {
-                        let action = match result.action {
-                            crate::sync::BootstrapAction::Created => "Created",
-                            crate::sync::BootstrapAction::Merged => "Updated",
-                            crate::sync::BootstrapAction::Skipped => "Skipped",
-                        };
-                        println!(
-                            "{} {} {}",
-                            style("✓").green(),
-                            action,
-                            result.output_path.display()
-                        );
-                    }
+                match sync.bootstrap_tool(tool, project_root) {
+                    Ok(result) => report_bootstrap(&result.action, &result.output_path),
                     Err(e) => {
                         eprintln!("{} Failed {}: {}", style("✗").red(), tool.output_path(), e);
                     }
                 }
             }
+            for adapter in detected_custom {
+                match sync.bootstrap_custom(adapter, project_root) {
+                    Ok(result) => report_bootstrap(&result.action, &result.output_path),
+                    Err(e) => {
+                        eprintln!(
+                            "{} Failed {}: {}",
+                            style("✗").red(),
+                            adapter.output_path(),
+                            e
+                        );
+                    }
+                }
+            }
         }
     }
 
     // Always create AGENTS.md as fallback if it doesn't exist
     let agents_md = project_root.join("AGENTS.md");
     if !agents_md.exists() {
-        match sync.bootstrap_tool(SyncTool::Generic, &project_root) {
+        match sync.bootstrap_tool(SyncTool::Generic, project_root) {
             Ok(result) => {
                 println!(
                     "{} Created {} (universal fallback)",
@@ -255,3 +321,38 @@ fn bootstrap_ai_tools(interactive: bool) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CustomAdapterConfig;
+
+    #[test]
+    fn test_reinit_bootstraps_custom_adapter() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        std::fs::create_dir(root.join(".zed")).unwrap();
+
+        // A hand-edited config from an earlier init
+        let config_path = root.join(".acp.config.json");
+        let mut previous = Config::default();
+        previous.sync.custom = vec![CustomAdapterConfig {
+            name: "Zed".to_string(),
+            output_path: ".zed/rules.md".to_string(),
+            detect_globs: vec![".zed".to_string()],
+            section_markers: None,
+            template: "# Rules for {{name}}\n\n{{context}}".to_string(),
+        }];
+        previous.save(&config_path).unwrap();
+
+        let mut config = Config::default();
+        config.sync = existing_sync_settings(&config_path).unwrap();
+        bootstrap_ai_tools(&config, false, root).unwrap();
+
+        let rules = std::fs::read_to_string(root.join(".zed/rules.md")).unwrap();
+        assert!(rules.contains("# Rules for Zed"));
+        assert!(root.join("AGENTS.md").exists());
+
+        assert!(existing_sync_settings(&root.join("missing.json")).is_none());
+    }
+}
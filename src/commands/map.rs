@@ -5,7 +5,7 @@
 //!
 //! Implements `acp map <path>` command for hierarchical codebase navigation.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::Path;
 
 use console::style;
@@ -23,6 +23,8 @@ pub enum MapFormat {
     Tree,
     Flat,
     Json,
+    /// Mermaid `graph TD` diagram for embedding in Markdown
+    Mermaid,
 }
 
 /// Options for the map command
@@ -205,19 +207,21 @@ impl<'a> MapBuilder<'a> {
             .exports
             .iter()
             .filter_map(|sym_name| {
-                self.cache.symbols.get(sym_name).map(|sym| {
-                    let is_frozen = sym
-                        .constraints
-                        .as_ref()
-                        .map(|c| c.level == "frozen")
-                        .unwrap_or(false);
-                    SymbolNode {
-                        name: sym.name.clone(),
-                        symbol_type: format!("{:?}", sym.symbol_type).to_lowercase(),
-                        line: sym.lines[0],
-                        is_frozen,
-                    }
-                })
+                self.cache
+                    .get_symbol_in_file(sym_name, &file.path)
+                    .map(|sym| {
+                        let is_frozen = sym
+                            .constraints
+                            .as_ref()
+                            .map(|c| c.level == "frozen")
+                            .unwrap_or(false);
+                        SymbolNode {
+                            name: sym.name.clone(),
+                            symbol_type: format!("{:?}", sym.symbol_type).to_lowercase(),
+                            line: sym.lines[0],
+                            is_frozen,
+                        }
+                    })
             })
             .collect();
 
@@ -285,6 +289,7 @@ pub fn render_map(node: &DirectoryNode, options: &MapOptions, all_issues: &[Inli
         MapFormat::Tree => render_tree(node, options, all_issues),
         MapFormat::Flat => render_flat(node),
         MapFormat::Json => render_json(node, all_issues),
+        MapFormat::Mermaid => print!("{}", render_mermaid(node)),
     }
 }
 
@@ -394,6 +399,113 @@ fn render_json(node: &DirectoryNode, issues: &[InlineIssue]) {
     println!("{}", serde_json::to_string_pretty(&output).unwrap());
 }
 
+/// Render the map tree as a Mermaid `graph TD` diagram
+///
+/// Directories and files become nodes; file labels carry the constraint
+/// level and, when `--inline` populated them, per-type inline issue counts.
+pub fn render_mermaid(node: &DirectoryNode) -> String {
+    let mut out = String::from("graph TD\n");
+    let mut ids = MermaidIds::default();
+
+    let root_label = if node.path.is_empty() || node.path == "." {
+        "./".to_string()
+    } else {
+        format!("{}/", node.path)
+    };
+    let root_id = ids.assign("d", &node.path);
+    out.push_str(&format!(
+        "    {}[\"{}\"]\n",
+        root_id,
+        mermaid_label(&root_label)
+    ));
+    render_mermaid_dir(node, &root_id, &mut ids, &mut out);
+    out
+}
+
+fn render_mermaid_dir(node: &DirectoryNode, node_id: &str, ids: &mut MermaidIds, out: &mut String) {
+    for file in &node.files {
+        let file_id = ids.assign("f", &file.path);
+        let mut label = file.name.clone();
+        if let Some(ref level) = file.constraint_level {
+            label.push_str(&format!(" ({})", level));
+        }
+        let counts = issue_counts(&file.inline_issues);
+        if !counts.is_empty() {
+            label.push_str("<br/>");
+            label.push_str(&counts);
+        }
+        out.push_str(&format!(
+            "    {} --> {}[\"{}\"]\n",
+            node_id,
+            file_id,
+            mermaid_label(&label)
+        ));
+    }
+
+    for subdir in &node.subdirs {
+        let dir_id = ids.assign("d", &subdir.path);
+        out.push_str(&format!(
+            "    {} --> {}[\"{}/\"]\n",
+            node_id,
+            dir_id,
+            mermaid_label(&subdir.name)
+        ));
+        render_mermaid_dir(subdir, &dir_id, ids, out);
+    }
+}
+
+/// Summarize inline issues as "2 hacks, 1 todo"
+fn issue_counts(issues: &[InlineIssue]) -> String {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for issue in issues {
+        *counts.entry(issue.issue_type.as_str()).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .map(|(kind, n)| {
+            if n == 1 {
+                format!("{} {}", n, kind)
+            } else {
+                format!("{} {}s", n, kind)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Escape characters that would terminate a quoted Mermaid label
+fn mermaid_label(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
+
+/// Allocates unique Mermaid node IDs from file paths
+///
+/// Mermaid IDs may only contain word characters, so `/`, `.`, `-` and friends
+/// are folded to `_`. Paths that collide after folding get a numeric suffix.
+#[derive(Default)]
+struct MermaidIds {
+    used: HashSet<String>,
+}
+
+impl MermaidIds {
+    fn assign(&mut self, prefix: &str, path: &str) -> String {
+        let sanitized: String = path
+            .trim_start_matches("./")
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let base = format!("{}_{}", prefix, sanitized);
+
+        let mut id = base.clone();
+        let mut n = 2;
+        while !self.used.insert(id.clone()) {
+            id = format!("{}_{}", base, n);
+            n += 1;
+        }
+        id
+    }
+}
+
 /// Execute the map command
 pub fn execute_map(cache: &Cache, path: &Path, options: MapOptions) -> Result<()> {
     let builder = MapBuilder::new(cache, options.clone());
@@ -419,4 +531,58 @@ mod tests {
         assert!(!opts.show_inline);
         assert_eq!(opts.format, MapFormat::Tree);
     }
+
+    fn file_node(path: &str, issues: &[&str]) -> FileNode {
+        FileNode {
+            name: Path::new(path)
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string(),
+            path: path.to_string(),
+            constraint_level: None,
+            purpose: None,
+            symbols: vec![],
+            inline_issues: issues
+                .iter()
+                .enumerate()
+                .map(|(i, t)| InlineIssue {
+                    file: path.to_string(),
+                    line: i + 1,
+                    issue_type: t.to_string(),
+                    message: String::new(),
+                    expires: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_render_mermaid() {
+        let tree = DirectoryNode {
+            name: "src".to_string(),
+            path: "src".to_string(),
+            files: vec![file_node("src/main.rs", &["hack", "hack", "todo"])],
+            subdirs: vec![DirectoryNode {
+                name: "auth".to_string(),
+                path: "src/auth".to_string(),
+                files: vec![file_node("src/auth/session.rs", &[])],
+                subdirs: vec![],
+            }],
+        };
+
+        let out = render_mermaid(&tree);
+        assert!(out.starts_with("graph TD\n"));
+        assert!(out.contains("d_src[\"src/\"]"));
+        assert!(out.contains("d_src --> f_src_main_rs[\"main.rs<br/>2 hacks, 1 todo\"]"));
+        assert!(out.contains("d_src --> d_src_auth[\"auth/\"]"));
+        assert!(out.contains("d_src_auth --> f_src_auth_session_rs[\"session.rs\"]"));
+    }
+
+    #[test]
+    fn test_mermaid_ids_unique() {
+        let mut ids = MermaidIds::default();
+        assert_eq!(ids.assign("f", "a-b.rs"), "f_a_b_rs");
+        assert_eq!(ids.assign("f", "a_b.rs"), "f_a_b_rs_2");
+    }
 }
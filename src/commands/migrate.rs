@@ -1,11 +1,16 @@
 //! @acp:module "Migrate Command"
-//! @acp:summary "Add directive suffixes to existing annotations (RFC-001)"
+//! @acp:summary "Upgrade existing annotations and adopt ACP from native docs (RFC-001)"
 //! @acp:domain cli
 //! @acp:layer service
 //!
-//! Implements `acp migrate --add-directives` command for upgrading annotations.
-
-use std::collections::HashMap;
+//! Implements `acp migrate --add-directives`, which adds directive suffixes
+//! to existing annotations, and `acp migrate --from <format>`, which converts
+//! native doc comments (JSDoc, docstrings, rustdoc, ...) into `@acp:`
+//! annotations marked `@acp:source converted`. Conversion leaves the native
+//! text in place and skips any symbol or file that already carries an
+//! `@acp:` annotation, so re-running it never converts twice.
+
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -13,7 +18,12 @@ use console::style;
 use dialoguer::Confirm;
 use regex::Regex;
 
+use crate::annotate::{
+    Analyzer, AnnotateLevel, ConversionSource, ProvenanceConfig, Suggester, Suggestion,
+    SuggestionSource, Writer,
+};
 use crate::cache::Cache;
+use crate::config::Config;
 use crate::error::Result;
 
 /// Options for the migrate command
@@ -23,6 +33,8 @@ pub struct MigrateOptions {
     pub dry_run: bool,
     pub interactive: bool,
     pub backup: bool,
+    /// Convert native doc comments from this format instead of adding directives
+    pub from: Option<ConversionSource>,
 }
 
 impl Default for MigrateOptions {
@@ -32,6 +44,7 @@ impl Default for MigrateOptions {
             dry_run: false,
             interactive: false,
             backup: true,
+            from: None,
         }
     }
 }
@@ -237,28 +250,11 @@ impl MigrationScanner {
     ) -> Result<Vec<AnnotationMigration>> {
         let mut all_migrations = vec![];
 
-        for path in cache.files.keys() {
-            let file_path = PathBuf::from(path);
-
-            // Apply path filter if specified
-            if !filter_paths.is_empty() {
-                let matches = filter_paths.iter().any(|p| {
-                    file_path.starts_with(p) || path.starts_with(p.to_string_lossy().as_ref())
-                });
-                if !matches {
-                    continue;
-                }
-            }
-
-            // Skip if file doesn't exist (cache might be stale)
-            if !file_path.exists() {
-                continue;
-            }
-
+        for file_path in cached_files(cache, filter_paths) {
             match self.scan_file(&file_path) {
                 Ok(migrations) => all_migrations.extend(migrations),
                 Err(e) => {
-                    eprintln!("Warning: Could not scan {}: {}", path, e);
+                    eprintln!("Warning: Could not scan {}: {}", file_path.display(), e);
                 }
             }
         }
@@ -270,6 +266,25 @@ impl MigrationScanner {
     }
 }
 
+/// Cached files on disk under `filter_paths` (all of them when empty), sorted
+fn cached_files(cache: &Cache, filter_paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = cache
+        .files
+        .keys()
+        .filter(|path| {
+            filter_paths.is_empty()
+                || filter_paths.iter().any(|p| {
+                    Path::new(path).starts_with(p) || path.starts_with(p.to_string_lossy().as_ref())
+                })
+        })
+        .map(PathBuf::from)
+        // Skip if file doesn't exist (cache might be stale)
+        .filter(|path| path.exists())
+        .collect();
+    files.sort();
+    files
+}
+
 impl Default for MigrationScanner {
     fn default() -> Self {
         Self::new()
@@ -392,7 +407,11 @@ pub fn print_migration_preview(migrations: &[AnnotationMigration]) {
 }
 
 /// Execute the migrate command
-pub fn execute_migrate(cache: &Cache, options: MigrateOptions) -> Result<()> {
+pub fn execute_migrate(cache: &Cache, options: MigrateOptions, config: &Config) -> Result<()> {
+    if let Some(from) = options.from {
+        return execute_convert(cache, &options, from, config);
+    }
+
     let scanner = MigrationScanner::new();
     let migrations = scanner.scan_cache(cache, &options.paths)?;
 
@@ -481,6 +500,143 @@ pub fn execute_migrate(cache: &Cache, options: MigrateOptions) -> Result<()> {
     Ok(())
 }
 
+/// @acp:summary "Plans converted annotations for one file"
+///
+/// Returns the rewritten source, or `None` when nothing would change.
+/// Suggestions for targets that already have any `@acp:` annotation are
+/// dropped, as are heuristic ones: migration only carries over what the
+/// native docs say.
+fn convert_source(
+    path: &Path,
+    content: &str,
+    analyzer: &Analyzer,
+    suggester: &Suggester,
+    writer: &Writer,
+) -> Result<Option<(usize, String)>> {
+    let analysis = analyzer.analyze_source(path, content)?;
+    let annotated: HashSet<&str> = analysis
+        .existing_annotations
+        .iter()
+        .map(|a| a.target.as_str())
+        .collect();
+    let mut suggestions: Vec<Suggestion> = vec![];
+    for suggestion in suggester.suggest(&analysis) {
+        // Exported symbols can be reported twice (export and declaration)
+        if suggestion.source == SuggestionSource::Converted
+            && !annotated.contains(suggestion.target.as_str())
+            && !suggestions.contains(&suggestion)
+        {
+            suggestions.push(suggestion);
+        }
+    }
+    if suggestions.is_empty() {
+        return Ok(None);
+    }
+
+    let changes = writer.plan_changes(path, &suggestions, &analysis)?;
+    let converted = writer.render(path, content, &changes)?;
+    Ok((converted != content).then_some((suggestions.len(), converted)))
+}
+
+/// Execute `acp migrate --from <format>`
+fn execute_convert(
+    cache: &Cache,
+    options: &MigrateOptions,
+    from: ConversionSource,
+    config: &Config,
+) -> Result<()> {
+    let analyzer = Analyzer::new(config)?.with_level(AnnotateLevel::Standard);
+    let suggester = Suggester::new(AnnotateLevel::Standard)
+        .with_conversion_source(from)
+        .with_heuristics(false);
+    let writer = Writer::new().with_provenance(ProvenanceConfig::new());
+    let backups = MigrationWriter::new();
+
+    let mut converted_files = 0;
+    let mut converted_count = 0;
+    let mut skipped_count = 0;
+    for file_path in cached_files(cache, &options.paths) {
+        let content = fs::read_to_string(&file_path)?;
+        let converted = match convert_source(&file_path, &content, &analyzer, &suggester, &writer) {
+            Ok(Some(converted)) => converted,
+            Ok(None) => continue,
+            Err(e) => {
+                eprintln!(
+                    "{} Could not convert {}: {}",
+                    style("⚠").yellow(),
+                    file_path.display(),
+                    e
+                );
+                continue;
+            }
+        };
+        let (count, new_content) = converted;
+
+        if options.dry_run || options.interactive {
+            print!(
+                "{}",
+                crate::annotate::writer::generate_unified_diff(
+                    &file_path.to_string_lossy(),
+                    &content,
+                    &new_content
+                )
+            );
+        }
+        if options.dry_run {
+            converted_files += 1;
+            converted_count += count;
+            continue;
+        }
+        if options.interactive {
+            let confirmed = Confirm::new()
+                .with_prompt(format!("Convert {} annotations?", count))
+                .default(true)
+                .interact()
+                .map_err(|e| std::io::Error::other(e.to_string()))?;
+            if !confirmed {
+                skipped_count += count;
+                continue;
+            }
+        }
+
+        if options.backup {
+            backups.backup_file(&file_path)?;
+        }
+        fs::write(&file_path, new_content)?;
+        converted_files += 1;
+        converted_count += count;
+        println!(
+            "{} Converted {} ({} annotations)",
+            style("✓").green(),
+            file_path.display(),
+            count
+        );
+    }
+
+    println!();
+    if options.dry_run {
+        if converted_count == 0 {
+            println!("{}", style("No native docs need conversion.").green());
+        } else {
+            println!(
+                "Would convert {} annotations in {} files.",
+                style(converted_count).bold(),
+                converted_files
+            );
+            println!("{}", style("Run without --dry-run to apply changes.").dim());
+        }
+        return Ok(());
+    }
+    println!(
+        "{} Converted {} annotations in {} files, skipped {}",
+        style("Done.").bold(),
+        style(converted_count).green(),
+        converted_files,
+        style(skipped_count).yellow()
+    );
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -518,4 +674,31 @@ mod tests {
         assert_eq!(cap.get(1).unwrap().as_str(), "lock");
         assert_eq!(cap.get(2).unwrap().as_str(), "frozen");
     }
+
+    #[test]
+    fn test_convert_source_skips_annotated_targets() {
+        let analyzer = Analyzer::new(&Config::default()).unwrap();
+        let suggester = Suggester::new(AnnotateLevel::Standard)
+            .with_conversion_source(ConversionSource::Jsdoc)
+            .with_heuristics(false);
+        let writer = Writer::new().with_provenance(ProvenanceConfig::new());
+        let path = Path::new("src/math.ts");
+        let source = "/**\n * Adds two numbers.\n * @deprecated Use sum instead\n */\n\
+                      export function add(a: number, b: number): number {\n    return a + b;\n}\n";
+
+        let (count, converted) = convert_source(path, source, &analyzer, &suggester, &writer)
+            .unwrap()
+            .expect("JSDoc should convert");
+        assert!(count > 0);
+        assert!(converted.contains("@acp:summary \"Adds two numbers.\""));
+        assert!(converted.contains("@acp:source converted"));
+        assert!(converted.contains("Adds two numbers."));
+
+        // Already-converted output is left alone
+        assert!(
+            convert_source(path, &converted, &analyzer, &suggester, &writer)
+                .unwrap()
+                .is_none()
+        );
+    }
 }
@@ -13,7 +13,10 @@ pub mod chain;
 pub mod check;
 pub mod context;
 pub mod daemon;
+pub mod diff;
 pub mod expand;
+pub mod export;
+pub mod gate;
 pub mod index;
 pub mod init;
 pub mod install;
@@ -22,8 +25,10 @@ pub mod migrate;
 pub mod output;
 pub mod primer;
 pub mod query;
+pub mod query_describe;
 pub mod revert;
 pub mod review;
+pub mod scan;
 pub mod validate;
 pub mod vars;
 pub mod watch;
@@ -32,10 +37,13 @@ pub use annotate::{execute_annotate, AnnotateOptions};
 pub use attempt::{execute_attempt, AttemptSubcommand};
 pub use bridge::{execute_bridge, BridgeOptions, BridgeSubcommand};
 pub use chain::{execute_chain, ChainOptions};
-pub use check::{execute_check, CheckOptions};
+pub use check::{execute_check, CheckFormat, CheckOptions};
 pub use context::{execute_context, ContextOperation, ContextOptions};
 pub use daemon::{execute_daemon, DaemonSubcommand};
+pub use diff::{execute_diff, DiffOptions};
 pub use expand::{execute_expand, ExpandOptions};
+pub use export::{execute_export, ExportOptions, ExportSubcommand};
+pub use gate::{execute_gate, GateOptions};
 pub use index::{execute_index, IndexOptions};
 pub use init::{execute_init, InitOptions};
 pub use install::{
@@ -44,12 +52,18 @@ pub use install::{
 pub use map::{execute_map, MapBuilder, MapFormat, MapOptions};
 pub use migrate::{execute_migrate, DirectiveDefaults, MigrateOptions, MigrationScanner};
 pub use output::{
-    format_constraint_level, format_symbol_ref, format_symbol_ref_range, TreeRenderer,
+    format_constraint_level, format_symbol_ref, format_symbol_ref_range, Page, Paged,
+    TabularFormat, TreeRenderer,
 };
 pub use primer::{execute_primer, PrimerOptions};
-pub use query::{execute_query, ConfidenceFilter, QueryOptions, QuerySubcommand};
+pub use query::{
+    execute_query, CalleeFilter, ConfidenceFilter, QueryOptions, QuerySubcommand, StatsGroup,
+    SymbolMatchMode,
+};
+pub use query_describe::{describe_queries, execute_query_describe};
 pub use revert::{execute_revert, RevertOptions};
 pub use review::{execute_review, ReviewOptions, ReviewSubcommand};
+pub use scan::{execute_scan, ScanOptions};
 pub use validate::{execute_validate, ValidateOptions};
 pub use vars::{execute_vars, VarsOptions};
 pub use watch::{execute_watch, WatchOptions};
@@ -3,11 +3,14 @@
 //! @acp:domain cli
 //! @acp:layer service
 //!
-//! Provides tree rendering, symbol formatting, and colored output.
+//! Provides tree rendering, symbol formatting, colored output,
+//! delimited (CSV/TSV) table export, and `--limit`/`--offset` paging.
 
 use console::{style, StyledObject};
+use serde::Serialize;
 
-use crate::cache::SymbolType;
+use crate::cache::{SymbolEntry, SymbolType};
+use crate::config::{AnnotateProvenanceConfig, ConfidenceBand};
 use crate::constraints::LockLevel;
 
 /// Tree renderer with box-drawing characters
@@ -119,10 +122,291 @@ pub fn constraint_level_str(level: &LockLevel) -> &'static str {
     }
 }
 
+/// Delimited tabular output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabularFormat {
+    /// Comma-separated values, quoted per RFC 4180
+    Csv,
+    /// Tab-separated values, with tabs and newlines escaped
+    Tsv,
+}
+
+/// Stable column set for symbol rows
+pub const SYMBOL_COLUMNS: [&str; 7] = [
+    "name",
+    "type",
+    "file",
+    "start_line",
+    "end_line",
+    "exported",
+    "summary",
+];
+
+impl TabularFormat {
+    fn delimiter(&self) -> char {
+        match self {
+            Self::Csv => ',',
+            Self::Tsv => '\t',
+        }
+    }
+
+    /// Escape a single field for this format
+    ///
+    /// CSV fields containing a delimiter, quote, or line break are wrapped in
+    /// double quotes with embedded quotes doubled (RFC 4180). TSV has no
+    /// quoting, so tabs and line breaks are written as `\t`, `\n`, `\r`.
+    pub fn escape_field(&self, field: &str) -> String {
+        match self {
+            Self::Csv => {
+                if field.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                } else {
+                    field.to_string()
+                }
+            }
+            Self::Tsv => field
+                .replace('\\', "\\\\")
+                .replace('\t', "\\t")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r"),
+        }
+    }
+
+    /// Format one row (without trailing newline)
+    pub fn format_row<S: AsRef<str>>(&self, fields: &[S]) -> String {
+        fields
+            .iter()
+            .map(|f| self.escape_field(f.as_ref()))
+            .collect::<Vec<_>>()
+            .join(&self.delimiter().to_string())
+    }
+
+    /// Format a header row plus data rows, one line each
+    pub fn format_table<S: AsRef<str>>(&self, headers: &[&str], rows: &[Vec<S>]) -> String {
+        let mut out = self.format_row(headers);
+        out.push('\n');
+        for row in rows {
+            out.push_str(&self.format_row(row));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// `--limit`/`--offset` window over a list query's results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Page {
+    /// Most results to show; `None` shows the rest
+    pub limit: Option<usize>,
+    /// Results to skip before the first one shown
+    pub offset: usize,
+}
+
+impl Page {
+    /// Whether the user asked for a window
+    pub fn is_requested(&self) -> bool {
+        self.limit.is_some() || self.offset > 0
+    }
+
+    /// Cut sorted results down to this window
+    ///
+    /// `default_limit` bounds queries that were already truncated before
+    /// paging existed, without counting as a requested window.
+    pub fn apply<T>(&self, items: Vec<T>, default_limit: Option<usize>) -> Paged<T> {
+        let total = items.len();
+        let limit = self.limit.or(default_limit);
+        let items = items
+            .into_iter()
+            .skip(self.offset)
+            .take(limit.unwrap_or(usize::MAX))
+            .collect();
+        Paged {
+            items,
+            total,
+            offset: self.offset,
+            limit,
+            requested: self.is_requested(),
+        }
+    }
+}
+
+/// One window of a list query's results
+#[derive(Debug, Clone, PartialEq)]
+pub struct Paged<T> {
+    pub items: Vec<T>,
+    /// Results before paging
+    pub total: usize,
+    pub offset: usize,
+    pub limit: Option<usize>,
+    requested: bool,
+}
+
+impl<T> Paged<T> {
+    /// Convert the shown results, keeping the window
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Paged<U> {
+        Paged {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            offset: self.offset,
+            limit: self.limit,
+            requested: self.requested,
+        }
+    }
+
+    /// Whether results remain after this window
+    pub fn has_more(&self) -> bool {
+        self.offset + self.items.len() < self.total
+    }
+
+    /// Whether this is less than every result, by request or by a default
+    /// limit; JSON lists are then wrapped in an envelope with the counts
+    pub fn is_windowed(&self) -> bool {
+        self.requested || self.offset > 0 || self.has_more()
+    }
+
+    /// "showing 51-100 of 1203", or `None` when every result is shown
+    pub fn footer(&self) -> Option<String> {
+        if self.offset == 0 && !self.has_more() {
+            return None;
+        }
+        if self.items.is_empty() {
+            return Some(format!("showing none of {}", self.total));
+        }
+        Some(format!(
+            "showing {}-{} of {}",
+            self.offset + 1,
+            self.offset + self.items.len(),
+            self.total
+        ))
+    }
+
+    /// Add `total`, `offset`, `limit` and `hasMore` to a JSON object result
+    /// when it is windowed
+    pub fn annotate_json(&self, value: &mut serde_json::Value) {
+        if self.is_windowed() {
+            value["total"] = self.total.into();
+            value["offset"] = self.offset.into();
+            value["limit"] = self.limit.into();
+            value["hasMore"] = self.has_more().into();
+        }
+    }
+}
+
+impl<T: Serialize> Paged<T> {
+    /// `--json` output: the bare array when every result is shown, otherwise
+    /// `{items, total, offset, limit, hasMore}`
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        if !self.is_windowed() {
+            return serde_json::to_string_pretty(&self.items);
+        }
+        let mut value = serde_json::json!({ "items": self.items });
+        self.annotate_json(&mut value);
+        serde_json::to_string_pretty(&value)
+    }
+}
+
+/// Format a confidence score with the configured precision, colored by band
+///
+/// Red below `minConfidence`, yellow below `reviewThreshold`, green otherwise.
+pub fn format_confidence(
+    confidence: f64,
+    provenance: &AnnotateProvenanceConfig,
+) -> StyledObject<String> {
+    let text = format!("{:.*}", provenance.display_precision, confidence);
+    match provenance.band(confidence) {
+        ConfidenceBand::Low => style(text).red(),
+        ConfidenceBand::Review => style(text).yellow(),
+        ConfidenceBand::High => style(text).green(),
+    }
+}
+
+/// Render values as a one-line bar chart scaled between their min and max
+pub fn sparkline(values: &[f64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    values
+        .iter()
+        .map(|v| {
+            if range > 0.0 {
+                BARS[((v - min) / range * (BARS.len() - 1) as f64).round() as usize]
+            } else {
+                BARS[BARS.len() / 2]
+            }
+        })
+        .collect()
+}
+
+/// Build a row matching [`SYMBOL_COLUMNS`] for a symbol
+pub fn symbol_row(sym: &SymbolEntry) -> Vec<String> {
+    vec![
+        sym.name.clone(),
+        format!("{:?}", sym.symbol_type).to_lowercase(),
+        sym.file.clone(),
+        sym.lines[0].to_string(),
+        sym.lines[1].to_string(),
+        sym.exported.to_string(),
+        sym.summary.clone().unwrap_or_default(),
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_format_confidence() {
+        let provenance = AnnotateProvenanceConfig {
+            display_precision: 1,
+            ..Default::default()
+        };
+        let formatted = |c: f64| format_confidence(c, &provenance).force_styling(false);
+        assert_eq!(formatted(0.456).to_string(), "0.5");
+        assert_eq!(provenance.band(0.45), ConfidenceBand::Low);
+        assert_eq!(provenance.band(0.5), ConfidenceBand::Review);
+        assert_eq!(provenance.band(0.8), ConfidenceBand::High);
+    }
+
+    #[test]
+    fn test_page_window_and_json_envelope() {
+        let items: Vec<usize> = (1..=25).collect();
+
+        let all = Page::default().apply(items.clone(), None);
+        assert_eq!(all.items.len(), 25);
+        assert_eq!(all.footer(), None);
+        assert_eq!(
+            all.to_json().unwrap(),
+            serde_json::to_string_pretty(&items).unwrap()
+        );
+
+        let page = Page {
+            limit: Some(10),
+            offset: 20,
+        };
+        let last = page.apply(items.clone(), None);
+        assert_eq!(last.items, vec![21, 22, 23, 24, 25]);
+        assert!(!last.has_more());
+        assert_eq!(last.footer().as_deref(), Some("showing 21-25 of 25"));
+        let json: serde_json::Value = serde_json::from_str(&last.to_json().unwrap()).unwrap();
+        assert_eq!(json["total"], 25);
+        assert_eq!(json["hasMore"], false);
+        assert_eq!(json["items"][0], 21);
+
+        // Truncating by a default limit is reported the same way
+        let first = Page::default().apply(items.clone(), Some(10));
+        assert!(first.has_more());
+        assert_eq!(first.footer().as_deref(), Some("showing 1-10 of 25"));
+        let json: serde_json::Value = serde_json::from_str(&first.to_json().unwrap()).unwrap();
+        assert_eq!(json["total"], 25);
+        assert_eq!(json["limit"], 10);
+        assert_eq!(json["hasMore"], true);
+
+        // ...but a default limit that cuts nothing keeps the bare array
+        let short = Page::default().apply(items, Some(100));
+        assert!(short.to_json().unwrap().starts_with('['));
+    }
+
     #[test]
     fn test_tree_renderer() {
         let renderer = TreeRenderer::default();
@@ -150,4 +434,35 @@ mod tests {
             "validate (fn:45-89)"
         );
     }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[40.0, 50.0, 75.0, 110.0]), "▁▂▅█");
+        assert_eq!(sparkline(&[60.0, 60.0]), "▅▅");
+        assert_eq!(sparkline(&[]), "");
+    }
+
+    #[test]
+    fn test_csv_escaping() {
+        let csv = TabularFormat::Csv;
+        assert_eq!(csv.escape_field("plain"), "plain");
+        assert_eq!(csv.escape_field("a,b"), "\"a,b\"");
+        assert_eq!(csv.escape_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv.escape_field("line1\nline2"), "\"line1\nline2\"");
+    }
+
+    #[test]
+    fn test_tsv_escaping() {
+        let tsv = TabularFormat::Tsv;
+        assert_eq!(tsv.escape_field("a\tb"), "a\\tb");
+        assert_eq!(tsv.escape_field("a\nb"), "a\\nb");
+        assert_eq!(tsv.format_row(&["x", "y"]), "x\ty");
+    }
+
+    #[test]
+    fn test_format_table() {
+        let rows = vec![vec!["validate", "1"], vec!["a, b", "2"]];
+        let table = TabularFormat::Csv.format_table(&["name", "line"], &rows);
+        assert_eq!(table, "name,line\nvalidate,1\n\"a, b\",2\n");
+    }
 }
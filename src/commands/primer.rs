@@ -7,6 +7,7 @@
 //! RFC-0015: Foundation prompt for standalone/raw API usage
 //! Generates token-efficient bootstrap text for AI agents.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// RFC-0015 Section 4.2: Foundation prompt for raw API usage (~576 tokens)
@@ -62,8 +63,8 @@ use serde::Serialize;
 
 use crate::cache::Cache;
 use crate::primer::{
-    self, load_primer_config, render_primer_with_tier, select_sections, CliOverrides,
-    IdeEnvironment, OutputFormat, PrimerTier, ProjectState,
+    self, load_primer_config, render_primer_with_tier, select_sections, validate_section_weights,
+    CliOverrides, IdeEnvironment, OutputFormat, PrimerTier, ProjectState,
 };
 
 /// Options for the primer command
@@ -83,6 +84,8 @@ pub struct PrimerOptions {
     pub json: bool,
     /// Weight preset (safe, efficient, accurate, balanced)
     pub preset: Option<String>,
+    /// Per-section value multipliers keyed by section or category ID
+    pub weights: HashMap<String, f64>,
     /// Force include section IDs
     pub include: Vec<String>,
     /// Exclude section IDs
@@ -117,6 +120,7 @@ impl Default for PrimerOptions {
             format: OutputFormat::Markdown,
             json: false,
             preset: None,
+            weights: HashMap::new(),
             include: vec![],
             exclude: vec![],
             categories: vec![],
@@ -273,10 +277,14 @@ pub fn generate_primer(options: &PrimerOptions) -> Result<PrimerOutput> {
         preset: options.preset.clone(),
         categories: options.categories.clone(),
         no_dynamic: options.no_dynamic,
+        weights: options.weights.clone(),
     };
 
     // Load primer config with 3-layer merge
-    let config = load_primer_config(options.primer_config.as_deref(), &cli_overrides)?;
+    let mut config = load_primer_config(options.primer_config.as_deref(), &cli_overrides)?;
+    for warning in validate_section_weights(&mut config) {
+        eprintln!("{}: {}", console::style("warning").yellow().bold(), warning);
+    }
 
     // Load project state from cache
     let project_state = if let Some(ref cache_path) = options.cache {
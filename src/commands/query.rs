@@ -3,14 +3,28 @@
 //! @acp:domain cli
 //! @acp:layer handler
 
+use std::io::{self, BufWriter, Write};
 use std::path::PathBuf;
 
 use anyhow::{anyhow, Result};
 use console::style;
+use serde::Serialize;
 
-use crate::cache::Cache;
+use crate::cache::{
+    history_path, read_history, AnnotationProvenance, BehavioralAnnotations, Cache, DomainEntry,
+    DomainSource, FileEntry, SymbolEntry,
+};
+use crate::config::{AnnotateProvenanceConfig, CoverageSettings};
+use crate::constraints::LockLevel;
 use crate::parse::SourceOrigin;
-use crate::query::Query;
+use crate::query::{
+    BehaviorEntry, BehaviorFilter, CalleeScope, CoverageBucket, Query, SymbolMatcher,
+};
+
+use super::output::{
+    format_confidence, format_constraint_level, sparkline, symbol_row, Page, Paged, TabularFormat,
+    SYMBOL_COLUMNS,
+};
 
 /// Options for the query command
 #[derive(Debug, Clone)]
@@ -19,12 +33,56 @@ pub struct QueryOptions {
     pub cache: PathBuf,
     /// Output as JSON
     pub json: bool,
+    /// Output as CSV/TSV (symbol, symbols, file, stats, domains, and deprecated queries)
+    pub format: Option<TabularFormat>,
     /// RFC-0003: Filter by source origin
     pub source: Option<SourceOrigin>,
     /// RFC-0003: Filter by confidence expression (e.g., "<0.7", ">=0.9")
     pub confidence: Option<String>,
     /// RFC-0003: Show only annotations needing review
     pub needs_review: bool,
+    /// RFC-0003: Confidence display precision and color thresholds
+    pub provenance: AnnotateProvenanceConfig,
+    /// Window over list results (`--limit`/`--offset`)
+    pub page: Page,
+    /// List builtin callees under `callees --external` (`call_graph.include_stdlib`)
+    pub include_stdlib: bool,
+}
+
+/// Pattern and fuzzy symbol matches shown without `--limit`
+const DEFAULT_MATCH_LIMIT: usize = 100;
+
+/// Hotpaths shown without `--limit`
+const DEFAULT_HOTPATHS_LIMIT: usize = 10;
+
+/// Which callees `query callees --internal` / `--external` keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalleeFilter {
+    /// Symbols defined in the cache
+    Internal,
+    /// Standard library and dependency calls
+    External,
+}
+
+/// Grouping for `query stats --by-language` / `--by-domain`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsGroup {
+    Language,
+    Domain,
+}
+
+/// How `query symbol` interprets the name argument
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SymbolMatchMode {
+    /// Exact name lookup
+    #[default]
+    Exact,
+    /// Shell-style glob over all symbol names
+    Glob,
+    /// Regular expression over all symbol names
+    Regex,
+    /// Approximate subsequence match, ranked by score
+    Fuzzy,
 }
 
 /// Query subcommand types
@@ -32,47 +90,233 @@ pub struct QueryOptions {
 pub enum QuerySubcommand {
     Symbol {
         name: String,
+        /// Exact, glob, regex, or fuzzy matching
+        mode: SymbolMatchMode,
+        /// With exact matching, also list direct callers and callees
+        full: bool,
+        /// With exact matching, compare names ignoring case and accents
+        ignore_case: bool,
     },
     File {
         path: String,
+        /// List the file's symbols by line instead of its metadata
+        symbols: bool,
+    },
+    /// Every symbol, sorted by qualified name
+    Symbols {
+        /// Stream one JSON object per line
+        ndjson: bool,
+    },
+    /// Every file, sorted by path
+    Files {
+        /// Stream one JSON object per line
+        ndjson: bool,
+        /// Only files with this tag
+        tag: Option<String>,
     },
     Callers {
         symbol: String,
     },
     Callees {
         symbol: String,
+        /// Also list possible callees from `graph.heuristic_edges`
+        include_heuristic: bool,
+        /// Only internal or only external callees, each with its scope
+        filter: Option<CalleeFilter>,
+    },
+    /// Shortest call path(s) between two symbols
+    Path {
+        from: String,
+        to: String,
+        /// Number of distinct paths to list (1 = shortest only)
+        max: usize,
     },
     Domains,
     Domain {
         name: String,
     },
-    Hotpaths,
-    Stats,
-    /// RFC-0003: Show provenance statistics
+    /// Symbols ranked by fan-in × fan-out
+    Hotpaths {
+        /// Also weight by commits to the defining file
+        churn: bool,
+    },
+    /// Global totals, or a breakdown when `by` is set
+    Stats {
+        by: Option<StatsGroup>,
+    },
+    /// Stats snapshots from the history log next to the cache
+    StatsHistory {
+        /// Number of most recent snapshots to show
+        last: usize,
+    },
+    /// Annotation coverage by domain and language
+    Coverage {
+        /// Emit a shields.io endpoint badge instead of the report
+        shields: bool,
+        /// Badge color thresholds
+        thresholds: CoverageSettings,
+    },
+    /// RFC-0003: Show provenance statistics, or list annotations needing
+    /// review when `needs_review` is set
     Provenance,
+    /// Files and symbols marked `@acp:deprecated`, sorted by file
+    Deprecated,
+    /// Locked files and symbols grouped by level
+    Constraints {
+        /// Only this level; otherwise every level except `normal`
+        level: Option<LockLevel>,
+        /// Also list hack markers with their expiry status
+        hacks: bool,
+    },
+    /// Symbols with every behavioral annotation in `filter`
+    Behavior {
+        filter: BehaviorFilter,
+    },
+    /// Symbols declaring they throw `exception`
+    Throws {
+        exception: String,
+        /// Treat `exception` as a glob pattern
+        glob: bool,
+    },
 }
 
 /// Execute the query command
 pub fn execute_query(options: QueryOptions, subcommand: QuerySubcommand) -> Result<()> {
+    // The history log stands on its own; the cache need not exist
+    if let QuerySubcommand::StatsHistory { last } = subcommand {
+        return query_stats_history(last, &options);
+    }
+
     let cache_data = Cache::from_json(&options.cache)?;
+    warn_if_stale(&cache_data);
     let q = Query::new(&cache_data);
 
     match subcommand {
-        QuerySubcommand::Symbol { name } => query_symbol(&q, &name, options.json),
-        QuerySubcommand::File { path } => query_file(&q, &cache_data, &path, options.json),
-        QuerySubcommand::Callers { symbol } => query_callers(&q, &symbol, options.json),
-        QuerySubcommand::Callees { symbol } => query_callees(&q, &symbol, options.json),
-        QuerySubcommand::Domains => query_domains(&q, options.json),
+        QuerySubcommand::Symbol {
+            name,
+            mode,
+            full,
+            ignore_case,
+        } => match mode {
+            SymbolMatchMode::Exact if full => query_symbol_full(&q, &name, ignore_case, &options),
+            SymbolMatchMode::Exact => query_symbol(&q, &name, ignore_case, &options),
+            SymbolMatchMode::Glob => {
+                query_symbol_pattern(&q, &SymbolMatcher::glob(&name)?, &options)
+            }
+            SymbolMatchMode::Regex => {
+                query_symbol_pattern(&q, &SymbolMatcher::regex(&name)?, &options)
+            }
+            SymbolMatchMode::Fuzzy => query_symbol_fuzzy(&q, &name, &options),
+        },
+        QuerySubcommand::File {
+            path,
+            symbols: false,
+        } => query_file(&q, &cache_data, &path, &options),
+        QuerySubcommand::File {
+            path,
+            symbols: true,
+        } => query_outline(&q, &path, &options),
+        QuerySubcommand::Symbols { ndjson } => query_symbols(&cache_data, ndjson, &options),
+        QuerySubcommand::Files { ndjson, tag } => {
+            query_files(&cache_data, ndjson, tag.as_deref(), &options)
+        }
+        QuerySubcommand::Callers { symbol } => query_callers(&q, &symbol, &options),
+        QuerySubcommand::Callees {
+            symbol,
+            filter: Some(filter),
+            ..
+        } => query_callees_scoped(&q, &symbol, filter, &options),
+        QuerySubcommand::Callees {
+            symbol,
+            include_heuristic: false,
+            ..
+        } => query_callees(&q, &symbol, &options),
+        QuerySubcommand::Callees {
+            symbol,
+            include_heuristic: true,
+            ..
+        } => query_callees_with_heuristic(&q, &symbol, &options),
+        QuerySubcommand::Path { from, to, max } => query_path(&q, &from, &to, max, &options),
+        QuerySubcommand::Domains => query_domains(&q, &options),
         QuerySubcommand::Domain { name } => query_domain(&q, &name),
-        QuerySubcommand::Hotpaths => query_hotpaths(&q),
-        QuerySubcommand::Stats => query_stats(&cache_data, options.json),
+        QuerySubcommand::Hotpaths { churn } => query_hotpaths(&q, churn, &options),
+        QuerySubcommand::Stats { by: None } => query_stats(&cache_data, &options),
+        QuerySubcommand::Stats { by: Some(group) } => query_stats_by(&q, group, &options),
+        QuerySubcommand::StatsHistory { .. } => unreachable!("handled before loading the cache"),
+        QuerySubcommand::Coverage {
+            shields,
+            thresholds,
+        } => query_coverage(&q, shields, &thresholds, &options),
         QuerySubcommand::Provenance => query_provenance(&cache_data, &options),
+        QuerySubcommand::Deprecated => query_deprecated(&q, &options),
+        QuerySubcommand::Constraints { level, hacks } => {
+            query_constraints(&q, level, hacks, &options)
+        }
+        QuerySubcommand::Behavior { filter } => query_behavior(&q, &filter, &options),
+        QuerySubcommand::Throws { exception, glob } => query_throws(&q, &exception, glob, &options),
+    }
+}
+
+/// Print a warning when the cache no longer matches the tree it was built from
+///
+/// Only re-stats files already in the cache: walking the tree for files
+/// added since indexing would cost more than most queries do.
+fn warn_if_stale(cache: &Cache) {
+    let report = cache.changed_source_files(&cache.project.root);
+    if report.hash_mismatch {
+        eprintln!(
+            "{} cache content hash doesn't match its source files; run acp index.",
+            style("⚠").yellow()
+        );
+    } else if report.is_stale() {
+        let n = report.out_of_date();
+        eprintln!(
+            "{} cache is {} file{} out of date; run acp index.",
+            style("⚠").yellow(),
+            n,
+            if n == 1 { "" } else { "s" }
+        );
+    }
+}
+
+/// Look up exact matches of `name`, or every case- and accent-insensitive match
+fn named_symbols<'a>(q: &'a Query, name: &str, ignore_case: bool) -> Vec<&'a SymbolEntry> {
+    if ignore_case {
+        q.symbols_named_ci(name)
+    } else {
+        q.symbols_named(name)
     }
 }
 
-fn query_symbol(q: &Query, name: &str, json: bool) -> Result<()> {
-    if let Some(sym) = q.symbol(name) {
-        if json {
+/// Print the "showing a-b of n" footer when results were cut
+///
+/// Goes to stdout under text output and to stderr under CSV/TSV; the JSON
+/// envelope of a cut list already carries the counts.
+fn print_footer<T>(paged: &Paged<T>, options: &QueryOptions) {
+    let Some(footer) = paged.footer() else {
+        return;
+    };
+    if options.format.is_some() {
+        eprintln!("{}", footer);
+    } else if !options.json {
+        println!("{}", style(footer).dim());
+    }
+}
+
+fn query_symbol(q: &Query, name: &str, ignore_case: bool, options: &QueryOptions) -> Result<()> {
+    let matches = named_symbols(q, name, ignore_case);
+    if matches.len() > 1 {
+        return list_ambiguous_symbols(name, &matches, options);
+    }
+
+    // Falls back to the cache key and qualified names
+    if let Some(sym) = matches.first().copied().or_else(|| q.symbol(name)) {
+        if let Some(format) = options.format {
+            print!(
+                "{}",
+                format.format_table(&SYMBOL_COLUMNS, &[symbol_row(sym)])
+            );
+        } else if options.json {
             println!("{}", serde_json::to_string_pretty(sym)?);
         } else {
             println!("{}", style(&sym.name).bold());
@@ -109,6 +353,17 @@ fn query_symbol(q: &Query, name: &str, json: bool) -> Result<()> {
                 println!("  {}", sig);
             }
 
+            if !sym.throws.is_empty() {
+                println!();
+                println!("{}:", style("Throws").bold());
+                for throws in &sym.throws {
+                    match throws.description.as_ref().or(throws.directive.as_ref()) {
+                        Some(text) => println!("  {} - {}", throws.exception, text),
+                        None => println!("  {}", throws.exception),
+                    }
+                }
+            }
+
             let callers = q.callers(name);
             if !callers.is_empty() {
                 println!();
@@ -122,9 +377,344 @@ fn query_symbol(q: &Query, name: &str, json: bool) -> Result<()> {
     Ok(())
 }
 
-fn query_file(q: &Query, cache_data: &Cache, path: &str, json: bool) -> Result<()> {
+/// Print a symbol with its direct callers and callees; every definition when ambiguous
+fn query_symbol_full(
+    q: &Query,
+    name: &str,
+    ignore_case: bool,
+    options: &QueryOptions,
+) -> Result<()> {
+    if options.format.is_some() {
+        return Err(anyhow!("--full supports text and --json output only"));
+    }
+    let mut matches = named_symbols(q, name, ignore_case);
+    if matches.is_empty() {
+        // Falls back to the cache key and qualified names
+        matches.extend(q.symbol(name));
+    }
+    if matches.is_empty() {
+        eprintln!("{} Symbol not found: {}", style("✗").red(), name);
+        return Ok(());
+    }
+    let hoods: Vec<_> = matches.into_iter().map(|sym| q.neighborhood(sym)).collect();
+
+    if options.json {
+        // Always an array, so the shape does not depend on how many match
+        println!("{}", serde_json::to_string_pretty(&hoods)?);
+        return Ok(());
+    }
+
+    if hoods.len() > 1 {
+        println!(
+            "{} {} symbols named {}",
+            style("⚠").yellow(),
+            hoods.len(),
+            style(name).bold()
+        );
+        println!();
+    }
+    for (i, hood) in hoods.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let sym = hood.symbol;
+        println!(
+            "{} ({}, {}:{}-{})",
+            style(&sym.name).bold(),
+            format!("{:?}", sym.symbol_type).to_lowercase(),
+            sym.file,
+            sym.lines[0],
+            sym.lines[1]
+        );
+        if let Some(ref summary) = sym.summary {
+            println!("  {}", summary);
+        }
+        if let Some(ref sig) = sym.signature {
+            println!("  {}", style(sig).dim());
+        }
+        for (label, neighbors) in [("Callers", &hood.callers), ("Callees", &hood.callees)] {
+            println!("{} ({}):", style(label).bold(), neighbors.len());
+            for neighbor in neighbors.iter() {
+                let files = if neighbor.files.is_empty() {
+                    style("(not indexed)".to_string()).dim()
+                } else {
+                    style(neighbor.files.join(", ")).cyan()
+                };
+                println!("  {}  {}", neighbor.name, files);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// List every definition of a name that exists in more than one place
+fn list_ambiguous_symbols(
+    name: &str,
+    matches: &[&SymbolEntry],
+    options: &QueryOptions,
+) -> Result<()> {
+    if let Some(format) = options.format {
+        let rows: Vec<_> = matches.iter().map(|s| symbol_row(s)).collect();
+        print!("{}", format.format_table(&SYMBOL_COLUMNS, &rows));
+        return Ok(());
+    }
+    if options.json {
+        println!("{}", serde_json::to_string_pretty(matches)?);
+        return Ok(());
+    }
+
+    println!(
+        "{} '{}' is defined in {} places:",
+        style("⚠").yellow(),
+        name,
+        matches.len()
+    );
+    for sym in matches {
+        let sym_type = format!("{:?}", sym.symbol_type).to_lowercase();
+        println!(
+            "  {:<9}  {}:{}  ({})",
+            sym_type, sym.file, sym.lines[0], sym.qualified_name
+        );
+    }
+    println!();
+    println!("Query a qualified name above to see one definition.");
+    Ok(())
+}
+
+fn query_symbol_pattern(q: &Query, matcher: &SymbolMatcher, options: &QueryOptions) -> Result<()> {
+    let paged = options
+        .page
+        .apply(q.find_symbols(matcher), Some(DEFAULT_MATCH_LIMIT));
+
+    if let Some(format) = options.format {
+        let rows: Vec<_> = paged.items.iter().map(|s| symbol_row(s)).collect();
+        print!("{}", format.format_table(&SYMBOL_COLUMNS, &rows));
+        print_footer(&paged, options);
+        return Ok(());
+    }
+
+    if options.json {
+        println!("{}", paged.to_json()?);
+        print_footer(&paged, options);
+        return Ok(());
+    }
+
+    if paged.total == 0 {
+        eprintln!("{} No symbols match", style("✗").red());
+        return Ok(());
+    }
+
+    print_symbol_table(&paged.items);
+    println!();
+    if paged.footer().is_some() {
+        print_footer(&paged, options);
+    } else {
+        println!("{} matches", paged.total);
+    }
+    Ok(())
+}
+
+fn query_symbol_fuzzy(q: &Query, query: &str, options: &QueryOptions) -> Result<()> {
+    let paged = options
+        .page
+        .apply(q.fuzzy_symbols(query), Some(DEFAULT_MATCH_LIMIT));
+    let shown = &paged.items;
+
+    if let Some(format) = options.format {
+        let headers: Vec<&str> = std::iter::once("score")
+            .chain(SYMBOL_COLUMNS.iter().copied())
+            .collect();
+        let rows: Vec<_> = shown
+            .iter()
+            .map(|m| {
+                let mut row = vec![m.score.to_string()];
+                row.extend(symbol_row(m.symbol));
+                row
+            })
+            .collect();
+        print!("{}", format.format_table(&headers, &rows));
+    } else if options.json {
+        println!("{}", paged.to_json()?);
+    } else if paged.total == 0 {
+        eprintln!("{} No symbols match", style("✗").red());
+        return Ok(());
+    } else {
+        let name_width = shown.iter().map(|m| m.symbol.name.len()).max().unwrap_or(4);
+        let header = format!("{:>5}  {:<name_width$}  FILE", "SCORE", "NAME");
+        println!("{}", style(header).bold());
+        for m in shown {
+            println!(
+                "{:>5}  {:<name_width$}  {}:{}",
+                m.score, m.symbol.name, m.symbol.file, m.symbol.lines[0]
+            );
+        }
+        println!();
+    }
+
+    print_footer(&paged, options);
+    Ok(())
+}
+
+fn print_symbol_table(symbols: &[&SymbolEntry]) {
+    let name_width = symbols
+        .iter()
+        .map(|s| s.name.len())
+        .max()
+        .unwrap_or(4)
+        .max(4);
+    let header = format!("{:<name_width$}  {:<9}  FILE", "NAME", "TYPE");
+    println!("{}", style(header).bold());
+    for sym in symbols {
+        let sym_type = format!("{:?}", sym.symbol_type).to_lowercase();
+        println!(
+            "{:<name_width$}  {:<9}  {}:{}",
+            sym.name, sym_type, sym.file, sym.lines[0]
+        );
+    }
+}
+
+/// Symbols sorted by qualified name, so output is identical across runs
+/// despite HashMap ordering
+fn sorted_symbols(cache_data: &Cache) -> Vec<&SymbolEntry> {
+    let mut symbols: Vec<&SymbolEntry> = cache_data.symbols.values().collect();
+    symbols.sort_by(|a, b| a.qualified_name.cmp(&b.qualified_name));
+    symbols
+}
+
+fn query_symbols(cache_data: &Cache, ndjson: bool, options: &QueryOptions) -> Result<()> {
+    let paged = options.page.apply(sorted_symbols(cache_data), None);
+
+    if ndjson {
+        return write_ndjson(io::stdout().lock(), paged.items);
+    }
+    if let Some(format) = options.format {
+        let rows: Vec<_> = paged.items.iter().map(|s| symbol_row(s)).collect();
+        print!("{}", format.format_table(&SYMBOL_COLUMNS, &rows));
+        print_footer(&paged, options);
+        return Ok(());
+    }
+    if options.json {
+        println!("{}", paged.to_json()?);
+        return Ok(());
+    }
+
+    print_symbol_table(&paged.items);
+    println!();
+    if paged.footer().is_some() {
+        print_footer(&paged, options);
+    } else {
+        println!("{} symbols", paged.total);
+    }
+    Ok(())
+}
+
+fn query_files(
+    cache_data: &Cache,
+    ndjson: bool,
+    tag: Option<&str>,
+    options: &QueryOptions,
+) -> Result<()> {
+    let mut files: Vec<&FileEntry> = cache_data
+        .files
+        .values()
+        .filter(|f| tag.is_none_or(|tag| f.tags.iter().any(|t| t == tag)))
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    let paged = options.page.apply(files, None);
+
+    if ndjson {
+        return write_ndjson(io::stdout().lock(), paged.items);
+    }
+    if let Some(format) = options.format {
+        let rows: Vec<_> = paged
+            .items
+            .iter()
+            .map(|f| {
+                vec![
+                    f.path.clone(),
+                    format!("{:?}", f.language).to_lowercase(),
+                    f.lines.to_string(),
+                    f.tags.join(" "),
+                ]
+            })
+            .collect();
+        print!(
+            "{}",
+            format.format_table(&["path", "language", "lines", "tags"], &rows)
+        );
+        print_footer(&paged, options);
+        return Ok(());
+    }
+    if options.json {
+        println!("{}", paged.to_json()?);
+        return Ok(());
+    }
+
+    let files = &paged.items;
+    let path_width = files.iter().map(|f| f.path.len()).max().unwrap_or(4).max(4);
+    let header = format!(
+        "{:<path_width$}  {:<10}  {:>5}  TAGS",
+        "PATH", "LANGUAGE", "LINES"
+    );
+    println!("{}", style(header).bold());
+    for file in files {
+        let language = format!("{:?}", file.language).to_lowercase();
+        let row = format!(
+            "{:<path_width$}  {:<10}  {:>5}  {}",
+            file.path,
+            language,
+            file.lines,
+            file.tags.join(", ")
+        );
+        println!("{}", row.trim_end());
+    }
+    println!();
+    if paged.footer().is_some() {
+        print_footer(&paged, options);
+    } else {
+        println!("{} files", paged.total);
+    }
+    Ok(())
+}
+
+/// Write one compact JSON object per line (JSON Lines)
+///
+/// Entries are serialized straight to the output as they are visited. A
+/// reader that stops early (`head`, `jq` exiting) closes the pipe, which ends
+/// the stream quietly instead of failing.
+fn write_ndjson<'a, W, T, I>(out: W, items: I) -> Result<()>
+where
+    W: Write,
+    T: Serialize + 'a,
+    I: IntoIterator<Item = &'a T>,
+{
+    let mut out = BufWriter::new(out);
+    let written = items
+        .into_iter()
+        .try_for_each(|item| {
+            serde_json::to_writer(&mut out, item)?;
+            out.write_all(b"\n")
+        })
+        .and_then(|_| out.flush());
+
+    match written {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        other => Ok(other?),
+    }
+}
+
+fn query_file(q: &Query, cache_data: &Cache, path: &str, options: &QueryOptions) -> Result<()> {
     if let Some(file) = q.file(path) {
-        if json {
+        if let Some(format) = options.format {
+            // One row per exported symbol, same columns as `query symbol`
+            let rows: Vec<_> = file
+                .exports
+                .iter()
+                .filter_map(|name| cache_data.get_symbol_in_file(name, &file.path))
+                .map(symbol_row)
+                .collect();
+            print!("{}", format.format_table(&SYMBOL_COLUMNS, &rows));
+        } else if options.json {
             println!("{}", serde_json::to_string_pretty(file)?);
         } else {
             println!("{}", style(&file.path).bold());
@@ -139,6 +729,9 @@ fn query_file(q: &Query, cache_data: &Cache, path: &str, json: bool) -> Result<(
 
             println!("  Lines:       {}", file.lines);
             println!("  Language:    {:?}", file.language);
+            if !file.tags.is_empty() {
+                println!("  Tags:        {}", file.tags.join(", "));
+            }
 
             if let Some(ref constraints) = cache_data.constraints {
                 if let Some(fc) = constraints.by_file.get(&file.path) {
@@ -152,7 +745,7 @@ fn query_file(q: &Query, cache_data: &Cache, path: &str, json: bool) -> Result<(
                 println!();
                 println!("{}:", style("Symbols").bold());
                 for sym_name in &file.exports {
-                    if let Some(sym) = cache_data.symbols.get(sym_name) {
+                    if let Some(sym) = cache_data.get_symbol_in_file(sym_name, &file.path) {
                         let sym_type = format!("{:?}", sym.symbol_type).to_lowercase();
                         let line_info = if sym.lines.len() >= 2 {
                             format!("{}:{}-{}", sym_type, sym.lines[0], sym.lines[1])
@@ -201,51 +794,314 @@ fn query_file(q: &Query, cache_data: &Cache, path: &str, json: bool) -> Result<(
     Ok(())
 }
 
-fn query_callers(q: &Query, symbol: &str, json: bool) -> Result<()> {
-    let callers = q.callers(symbol);
-    if callers.is_empty() {
+fn query_outline(q: &Query, path: &str, options: &QueryOptions) -> Result<()> {
+    let Some(symbols) = q.outline(path) else {
+        eprintln!("{} File not found: {}", style("✗").red(), path);
+        return Ok(());
+    };
+    let paged = options.page.apply(symbols, None);
+
+    if let Some(format) = options.format {
+        let rows: Vec<_> = paged.items.iter().copied().map(symbol_row).collect();
+        print!("{}", format.format_table(&SYMBOL_COLUMNS, &rows));
+        print_footer(&paged, options);
+        return Ok(());
+    }
+    if options.json {
+        println!("{}", paged.to_json()?);
+        return Ok(());
+    }
+
+    println!("{} ({} symbols)", style(path).bold(), paged.total);
+    // End lines of the symbols enclosing the current one, for indentation
+    let mut enclosing: Vec<usize> = Vec::new();
+    for sym in &paged.items {
+        while enclosing.last().is_some_and(|&end| sym.lines[0] > end) {
+            enclosing.pop();
+        }
+        let summary = sym
+            .summary
+            .as_ref()
+            .map(|s| format!("  {}", s))
+            .unwrap_or_default();
+        println!(
+            "  {:>9}  {}{:<9} {}{}",
+            style(format!("{}-{}", sym.lines[0], sym.lines[1])).cyan(),
+            "  ".repeat(enclosing.len()),
+            format!("{:?}", sym.symbol_type).to_lowercase(),
+            style(&sym.name).bold(),
+            style(summary).dim()
+        );
+        enclosing.push(sym.lines[1]);
+    }
+    print_footer(&paged, options);
+    Ok(())
+}
+
+fn query_callers(q: &Query, symbol: &str, options: &QueryOptions) -> Result<()> {
+    let paged = options.page.apply(q.caller_sites(symbol), None);
+    if let Some(format) = options.format {
+        let rows: Vec<_> = paged
+            .items
+            .iter()
+            .map(|site| {
+                vec![
+                    site.caller.clone(),
+                    site.file.clone().unwrap_or_default(),
+                    site.line.map(|l| l.to_string()).unwrap_or_default(),
+                ]
+            })
+            .collect();
+        print!(
+            "{}",
+            format.format_table(&["caller", "file", "line"], &rows)
+        );
+    } else if paged.total == 0 {
         println!("{} No callers found for {}", style("ℹ").cyan(), symbol);
-    } else if json {
-        println!("{}", serde_json::to_string_pretty(&callers)?);
+    } else if options.json {
+        println!("{}", paged.to_json()?);
     } else {
-        for caller in callers {
-            println!("{}", caller);
+        for site in &paged.items {
+            match (&site.file, site.line) {
+                (Some(file), Some(line)) => println!("{}  {}:{}", site.caller, file, line),
+                _ => println!("{}", site.caller),
+            }
         }
     }
+    print_footer(&paged, options);
     Ok(())
 }
 
-fn query_callees(q: &Query, symbol: &str, json: bool) -> Result<()> {
-    let callees = q.callees(symbol);
-    if callees.is_empty() {
+fn query_callees(q: &Query, symbol: &str, options: &QueryOptions) -> Result<()> {
+    let paged = options.page.apply(q.callees(symbol), None);
+    if let Some(format) = options.format {
+        let rows: Vec<_> = paged.items.iter().map(|c| vec![*c]).collect();
+        print!("{}", format.format_table(&["callee"], &rows));
+    } else if paged.total == 0 {
         println!("{} No callees found for {}", style("ℹ").cyan(), symbol);
-    } else if json {
-        println!("{}", serde_json::to_string_pretty(&callees)?);
+    } else if options.json {
+        println!("{}", paged.to_json()?);
     } else {
-        for callee in callees {
+        for callee in &paged.items {
             println!("{}", callee);
         }
     }
+    print_footer(&paged, options);
     Ok(())
 }
 
-fn query_domains(q: &Query, json: bool) -> Result<()> {
-    let domains: Vec<_> = q.domains().collect();
-    if json {
-        println!("{}", serde_json::to_string_pretty(&domains)?);
+fn query_callees_with_heuristic(q: &Query, symbol: &str, options: &QueryOptions) -> Result<()> {
+    let paged = options.page.apply(q.callees_with_heuristic(symbol), None);
+    if let Some(format) = options.format {
+        let rows: Vec<_> = paged
+            .items
+            .iter()
+            .map(|entry| {
+                vec![
+                    entry.callee.clone(),
+                    entry.heuristic.to_string(),
+                    entry.file.clone().unwrap_or_default(),
+                    entry.line.map(|l| l.to_string()).unwrap_or_default(),
+                ]
+            })
+            .collect();
+        print!(
+            "{}",
+            format.format_table(&["callee", "heuristic", "file", "line"], &rows)
+        );
+    } else if paged.total == 0 {
+        println!("{} No callees found for {}", style("ℹ").cyan(), symbol);
+    } else if options.json {
+        println!("{}", paged.to_json()?);
     } else {
-        for domain in &domains {
+        for entry in &paged.items {
+            match (&entry.file, entry.line) {
+                (Some(file), Some(line)) if entry.heuristic => println!(
+                    "{}  {}",
+                    entry.callee,
+                    style(format!("(heuristic, {}:{})", file, line)).dim()
+                ),
+                _ => println!("{}", entry.callee),
+            }
+        }
+    }
+    print_footer(&paged, options);
+    Ok(())
+}
+
+fn query_callees_scoped(
+    q: &Query,
+    symbol: &str,
+    filter: CalleeFilter,
+    options: &QueryOptions,
+) -> Result<()> {
+    let callees: Vec<_> = q
+        .scoped_callees(symbol, options.include_stdlib)
+        .into_iter()
+        .filter(|c| (c.scope == CalleeScope::Internal) == (filter == CalleeFilter::Internal))
+        .collect();
+    let paged = options.page.apply(callees, None);
+    if let Some(format) = options.format {
+        let rows: Vec<_> = paged
+            .items
+            .iter()
+            .map(|entry| {
+                vec![
+                    entry.callee.to_string(),
+                    format!("{:?}", entry.scope).to_lowercase(),
+                    entry.file.unwrap_or_default().to_string(),
+                    entry.line.map(|l| l.to_string()).unwrap_or_default(),
+                ]
+            })
+            .collect();
+        print!(
+            "{}",
+            format.format_table(&["callee", "scope", "file", "line"], &rows)
+        );
+    } else if paged.total == 0 {
+        let kind = match filter {
+            CalleeFilter::Internal => "internal",
+            CalleeFilter::External => "external",
+        };
+        println!(
+            "{} No {} callees found for {}",
+            style("ℹ").cyan(),
+            kind,
+            symbol
+        );
+    } else if options.json {
+        println!("{}", paged.to_json()?);
+    } else {
+        for entry in &paged.items {
+            let detail = match (entry.scope, entry.file, entry.line) {
+                (CalleeScope::Internal, Some(file), Some(line)) => format!("{}:{}", file, line),
+                (CalleeScope::Stdlib, ..) => "stdlib".to_string(),
+                _ => "external".to_string(),
+            };
+            println!("{}  {}", entry.callee, style(format!("({})", detail)).dim());
+        }
+    }
+    print_footer(&paged, options);
+    Ok(())
+}
+
+fn query_path(q: &Query, from: &str, to: &str, max: usize, options: &QueryOptions) -> Result<()> {
+    // The call graph is keyed by simple name; accept qualified names too
+    let node = |name: &str| q.symbol(name).map_or(name.to_string(), |s| s.name.clone());
+    let (from, to) = (node(from), node(to));
+    let paged = options.page.apply(q.call_paths(&from, &to, max), None);
+
+    if options.json {
+        let paged = paged.map(|path| {
+            path.iter()
+                .map(|hop| {
+                    let sym = q.symbol(hop);
+                    serde_json::json!({
+                        "symbol": hop,
+                        "file": sym.map(|s| &s.file),
+                        "line": sym.map(|s| s.lines[0]),
+                    })
+                })
+                .collect::<Vec<_>>()
+        });
+        println!("{}", paged.to_json()?);
+        return Ok(());
+    }
+
+    if paged.total == 0 {
+        println!("{} No call path from {} to {}", style("ℹ").cyan(), from, to);
+        return Ok(());
+    }
+    for (i, path) in paged.items.iter().enumerate() {
+        if paged.total > 1 {
+            if i > 0 {
+                println!();
+            }
+            println!(
+                "{} ({} hop{}):",
+                style(format!("Path {}", paged.offset + i + 1)).bold(),
+                path.len() - 1,
+                if path.len() == 2 { "" } else { "s" }
+            );
+        }
+        for (depth, hop) in path.iter().enumerate() {
+            let location = q
+                .symbol(hop)
+                .map(|s| format!("{}:{}", s.file, s.lines[0]))
+                .unwrap_or_else(|| "(external)".to_string());
+            let arrow = if depth == 0 { " " } else { "→" };
+            println!("  {} {}  {}", arrow, hop, style(location).dim());
+        }
+    }
+    print_footer(&paged, options);
+    Ok(())
+}
+
+fn query_domains(q: &Query, options: &QueryOptions) -> Result<()> {
+    let mut domains: Vec<_> = q.domains().collect();
+    domains.sort_by(|a, b| a.name.cmp(&b.name));
+    let paged = options.page.apply(domains, None);
+    if let Some(format) = options.format {
+        let rows: Vec<_> = paged
+            .items
+            .iter()
+            .map(|d| {
+                let (annotated, matched) = membership_counts(d);
+                vec![
+                    d.name.clone(),
+                    d.files.len().to_string(),
+                    d.symbols.len().to_string(),
+                    annotated.to_string(),
+                    matched.to_string(),
+                    d.description.clone().unwrap_or_default(),
+                ]
+            })
+            .collect();
+        print!(
+            "{}",
+            format.format_table(
+                &[
+                    "name",
+                    "files",
+                    "symbols",
+                    "annotated",
+                    "pattern",
+                    "description"
+                ],
+                &rows
+            )
+        );
+    } else if options.json {
+        println!("{}", paged.to_json()?);
+    } else {
+        for domain in &paged.items {
+            let (annotated, matched) = membership_counts(domain);
             println!(
-                "{}: {} files, {} symbols",
+                "{}: {} files ({} annotated, {} by pattern), {} symbols",
                 style(&domain.name).cyan(),
                 domain.files.len(),
+                annotated,
+                matched,
                 domain.symbols.len()
             );
         }
     }
+    print_footer(&paged, options);
     Ok(())
 }
 
+/// Files that joined a domain by `@acp:domain` and by config pattern
+///
+/// Caches built before membership was recorded count every file as annotated.
+fn membership_counts(domain: &DomainEntry) -> (usize, usize) {
+    let matched = domain
+        .membership
+        .values()
+        .filter(|s| **s == DomainSource::Pattern)
+        .count();
+    (domain.files.len().saturating_sub(matched), matched)
+}
+
 fn query_domain(q: &Query, name: &str) -> Result<()> {
     if let Some(domain) = q.domain(name) {
         println!("{}", serde_json::to_string_pretty(domain)?);
@@ -255,15 +1111,417 @@ fn query_domain(q: &Query, name: &str) -> Result<()> {
     Ok(())
 }
 
-fn query_hotpaths(q: &Query) -> Result<()> {
-    for hp in q.hotpaths() {
-        println!("{}", hp);
+fn query_hotpaths(q: &Query, churn: bool, options: &QueryOptions) -> Result<()> {
+    let paged = options
+        .page
+        .apply(q.hotpaths(churn), Some(DEFAULT_HOTPATHS_LIMIT));
+    let hotpaths = &paged.items;
+
+    if let Some(format) = options.format {
+        let rows: Vec<_> = hotpaths
+            .iter()
+            .map(|h| {
+                vec![
+                    h.symbol.to_string(),
+                    h.score.to_string(),
+                    h.fan_in.to_string(),
+                    h.fan_out.to_string(),
+                    h.churn.map(|c| c.to_string()).unwrap_or_default(),
+                ]
+            })
+            .collect();
+        print!(
+            "{}",
+            format.format_table(&["symbol", "score", "fan_in", "fan_out", "churn"], &rows)
+        );
+        print_footer(&paged, options);
+        return Ok(());
+    }
+    if options.json {
+        println!("{}", paged.to_json()?);
+        return Ok(());
+    }
+
+    if paged.total == 0 {
+        println!(
+            "{} No symbols both call and are called in the call graph",
+            style("•").dim()
+        );
+        return Ok(());
+    }
+
+    println!("{} ({}):", style("Hotpaths").bold(), hotpaths.len());
+    let width = hotpaths.first().map_or(1, |h| h.score.to_string().len());
+    for h in hotpaths {
+        let mut factors = format!("{} callers × {} callees", h.fan_in, h.fan_out);
+        if let Some(commits) = h.churn {
+            factors.push_str(&format!(" × {} commits", commits));
+        }
+        println!(
+            "  {:>width$}  {} {}",
+            h.score,
+            style(h.symbol).bold(),
+            style(format!("({})", factors)).dim(),
+            width = width
+        );
+    }
+    print_footer(&paged, options);
+    println!(
+        "\n  {}",
+        style(
+            "Heuristic: the call graph resolves calls by name, so same-named symbols share a score"
+        )
+        .dim()
+    );
+    Ok(())
+}
+
+fn query_deprecated(q: &Query, options: &QueryOptions) -> Result<()> {
+    let paged = options.page.apply(q.deprecated(), None);
+    let entries = &paged.items;
+
+    if let Some(format) = options.format {
+        let rows: Vec<_> = entries
+            .iter()
+            .map(|e| {
+                vec![
+                    e.kind.to_string(),
+                    e.name.to_string(),
+                    e.file.to_string(),
+                    e.line.map(|l| l.to_string()).unwrap_or_default(),
+                    e.since.unwrap_or_default().to_string(),
+                    e.age_days.map(|d| d.to_string()).unwrap_or_default(),
+                    e.message.to_string(),
+                ]
+            })
+            .collect();
+        let headers = [
+            "kind", "name", "file", "line", "since", "age_days", "message",
+        ];
+        print!("{}", format.format_table(&headers, &rows));
+        print_footer(&paged, options);
+        return Ok(());
+    }
+    if options.json {
+        println!("{}", paged.to_json()?);
+        return Ok(());
+    }
+
+    if paged.total == 0 {
+        println!("{} Nothing is deprecated", style("✓").green());
+        return Ok(());
+    }
+
+    println!("{} ({}):", style("Deprecated").bold(), paged.total);
+    for entry in entries {
+        let location = match entry.line {
+            Some(line) => format!("{}:{}", entry.file, line),
+            None => entry.file.to_string(),
+        };
+        let label = match entry.kind {
+            "symbol" => entry
+                .name
+                .rsplit_once(':')
+                .map_or(entry.name, |(_, name)| name),
+            _ => "(file)",
+        };
+        let mut details = Vec::new();
+        if let Some(since) = entry.since {
+            details.push(format!("since {}", since));
+        }
+        if let Some(days) = entry.age_days {
+            details.push(format!("unchanged {}d", days));
+        }
+        let details = if details.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", details.join(", "))
+        };
+        println!(
+            "  {} {}{}",
+            style(location).cyan(),
+            style(label).bold(),
+            style(details).dim()
+        );
+        println!("      {}", entry.message);
+    }
+    print_footer(&paged, options);
+    Ok(())
+}
+
+fn query_constraints(
+    q: &Query,
+    level: Option<LockLevel>,
+    hacks: bool,
+    options: &QueryOptions,
+) -> Result<()> {
+    let paged = options.page.apply(q.locked(level), None);
+    let mut groups: Vec<(LockLevel, Vec<_>)> = Vec::new();
+    for entry in paged.items.iter().cloned() {
+        match groups.last_mut() {
+            Some((level, entries)) if *level == entry.level => entries.push(entry),
+            _ => groups.push((entry.level, vec![entry])),
+        }
+    }
+    let hacks = if hacks { Some(q.hacks()) } else { None };
+
+    if options.json {
+        let mut output = serde_json::json!({
+            "locks": groups
+                .iter()
+                .map(|(level, entries)| serde_json::json!({ "level": level, "entries": entries }))
+                .collect::<Vec<_>>(),
+        });
+        if let Some(hacks) = &hacks {
+            output["hacks"] = hacks
+                .iter()
+                .map(|hack| {
+                    let mut value = serde_json::to_value(hack)?;
+                    value["expired"] = hack.is_expired().into();
+                    Ok(value)
+                })
+                .collect::<Result<_>>()?;
+        }
+        paged.annotate_json(&mut output);
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if paged.total == 0 {
+        println!("{} No locked files or symbols", style("✓").green());
+    }
+    for (level, entries) in &groups {
+        println!("{} ({}):", format_constraint_level(level), entries.len());
+        for entry in entries {
+            let location = match entry.line {
+                Some(line) => format!("{}:{}", entry.file, line),
+                None => entry.file.to_string(),
+            };
+            let label = match entry.kind {
+                "symbol" => entry
+                    .name
+                    .rsplit_once(':')
+                    .map_or(entry.name, |(_, name)| name),
+                _ => "(file)",
+            };
+            let from = entry
+                .inherited_from
+                .as_ref()
+                .map(|pattern| format!(" (from {})", pattern))
+                .unwrap_or_default();
+            println!(
+                "  {} {}{}",
+                style(location).cyan(),
+                style(label).bold(),
+                style(from).dim()
+            );
+            if let Some(directive) = entry.directive {
+                println!("      {}", directive);
+            }
+        }
+    }
+    print_footer(&paged, options);
+
+    if let Some(hacks) = hacks {
+        println!("\n{} ({}):", style("Hacks").bold(), hacks.len());
+        for hack in hacks {
+            let location = match hack.line {
+                Some(line) => format!("{}:{}", hack.file, line),
+                None => hack.file.clone(),
+            };
+            let status = match hack.expires {
+                Some(expires) if hack.is_expired() => {
+                    style(format!("expired {}", expires.format("%Y-%m-%d"))).red()
+                }
+                Some(expires) => style(format!("expires {}", expires.format("%Y-%m-%d"))).yellow(),
+                None => style("no expiry".to_string()).dim(),
+            };
+            println!("  {} {} - {}", style(location).cyan(), status, hack.reason);
+        }
+    }
+    Ok(())
+}
+
+fn query_behavior(q: &Query, filter: &BehaviorFilter, options: &QueryOptions) -> Result<()> {
+    let paged = options.page.apply(q.behavior(filter), None);
+    let entries = &paged.items;
+
+    if let Some(format) = options.format {
+        let rows: Vec<_> = entries
+            .iter()
+            .map(|e| {
+                vec![
+                    e.name.to_string(),
+                    e.file.to_string(),
+                    e.line.to_string(),
+                    entry_tags(e).join(" "),
+                ]
+            })
+            .collect();
+        print!(
+            "{}",
+            format.format_table(&["name", "file", "line", "behavior"], &rows)
+        );
+        print_footer(&paged, options);
+        return Ok(());
+    }
+    if options.json {
+        println!("{}", paged.to_json()?);
+        return Ok(());
+    }
+
+    if paged.total == 0 {
+        println!("{} No symbols match", style("✓").green());
+        return Ok(());
+    }
+
+    println!("{} ({}):", style("Behavior").bold(), paged.total);
+    for entry in entries {
+        let name = entry.name.rsplit_once(':').map_or(entry.name, |(_, n)| n);
+        println!(
+            "  {} {} {}",
+            style(format!("{}:{}", entry.file, entry.line)).cyan(),
+            style(name).bold(),
+            style(format!("[{}]", entry_tags(entry).join(", "))).dim()
+        );
+    }
+    print_footer(&paged, options);
+    Ok(())
+}
+
+fn query_throws(q: &Query, exception: &str, glob: bool, options: &QueryOptions) -> Result<()> {
+    let matcher = glob.then(|| SymbolMatcher::glob(exception)).transpose()?;
+    let paged = options
+        .page
+        .apply(q.throwing(exception, matcher.as_ref()), None);
+    let entries = &paged.items;
+
+    if let Some(format) = options.format {
+        let rows: Vec<_> = entries
+            .iter()
+            .map(|e| {
+                vec![
+                    e.name.to_string(),
+                    e.file.to_string(),
+                    e.line.to_string(),
+                    e.throws.exception.clone(),
+                    e.throws.description.clone().unwrap_or_default(),
+                    e.throws.directive.clone().unwrap_or_default(),
+                ]
+            })
+            .collect();
+        print!(
+            "{}",
+            format.format_table(
+                &[
+                    "name",
+                    "file",
+                    "line",
+                    "exception",
+                    "description",
+                    "directive"
+                ],
+                &rows
+            )
+        );
+        print_footer(&paged, options);
+        return Ok(());
+    }
+    if options.json {
+        println!("{}", paged.to_json()?);
+        return Ok(());
+    }
+
+    if paged.total == 0 {
+        println!(
+            "{} No symbols declare they throw {}",
+            style("✓").green(),
+            exception
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} {} ({}):",
+        style("Throws").bold(),
+        style(exception).yellow(),
+        paged.total
+    );
+    for entry in entries {
+        let name = entry.name.rsplit_once(':').map_or(entry.name, |(_, n)| n);
+        let exception = if glob {
+            format!(" {}", style(&entry.throws.exception).yellow())
+        } else {
+            String::new()
+        };
+        println!(
+            "  {} {}{}",
+            style(format!("{}:{}", entry.file, entry.line)).cyan(),
+            style(name).bold(),
+            exception
+        );
+        if let Some(description) = &entry.throws.description {
+            println!("      {}", description);
+        }
+        if let Some(directive) = &entry.throws.directive {
+            println!("      {} {}", style("→").cyan(), style(directive).dim());
+        }
     }
+    print_footer(&paged, options);
     Ok(())
 }
 
-fn query_stats(cache_data: &Cache, json: bool) -> Result<()> {
-    if json {
+/// Behavior labels for a behavior query entry, including detected recursion
+fn entry_tags(entry: &BehaviorEntry) -> Vec<String> {
+    let mut tags = behavior_tags(entry.behavioral);
+    if entry.recursive {
+        tags.push("recursive".to_string());
+    }
+    tags
+}
+
+/// Short labels for the behavioral annotations a symbol carries
+fn behavior_tags(behavioral: &BehavioralAnnotations) -> Vec<String> {
+    let flags = [
+        (behavioral.pure, "pure"),
+        (behavioral.idempotent, "idempotent"),
+        (behavioral.memoized.is_some(), "memoized"),
+        (behavioral.r#async, "async"),
+        (behavioral.generator, "generator"),
+        (behavioral.transactional, "transactional"),
+    ];
+    let mut tags: Vec<String> = flags
+        .iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, tag)| tag.to_string())
+        .collect();
+    if let Some(limit) = &behavioral.throttled {
+        tags.push(format!("throttled={}", limit));
+    }
+    if !behavioral.side_effects.is_empty() {
+        tags.push(format!(
+            "side-effects={}",
+            behavioral.side_effects.join(",")
+        ));
+    }
+    tags
+}
+
+fn query_stats(cache_data: &Cache, options: &QueryOptions) -> Result<()> {
+    if let Some(format) = options.format {
+        let stats = &cache_data.stats;
+        let rows = vec![
+            vec!["files".to_string(), stats.files.to_string()],
+            vec!["symbols".to_string(), stats.symbols.to_string()],
+            vec!["lines".to_string(), stats.lines.to_string()],
+            vec![
+                "annotation_coverage".to_string(),
+                format!("{:.1}", stats.annotation_coverage),
+            ],
+            vec!["domains".to_string(), cache_data.domains.len().to_string()],
+        ];
+        print!("{}", format.format_table(&["metric", "value"], &rows));
+    } else if options.json {
         println!("{}", serde_json::to_string_pretty(&cache_data.stats)?);
     } else {
         println!("Files: {}", cache_data.stats.files);
@@ -275,6 +1533,245 @@ fn query_stats(cache_data: &Cache, json: bool) -> Result<()> {
     Ok(())
 }
 
+fn query_stats_by(q: &Query, group: StatsGroup, options: &QueryOptions) -> Result<()> {
+    let (label, buckets) = match group {
+        StatsGroup::Language => ("language", q.stats_by_language()),
+        StatsGroup::Domain => ("domain", q.stats_by_domain()),
+    };
+
+    if let Some(format) = options.format {
+        let rows: Vec<_> = buckets
+            .iter()
+            .map(|(name, b)| {
+                vec![
+                    name.clone(),
+                    b.files.to_string(),
+                    b.coverage.symbols.to_string(),
+                    b.lines.to_string(),
+                    b.coverage.annotated.to_string(),
+                    format!("{:.1}", b.coverage.coverage),
+                ]
+            })
+            .collect();
+        print!(
+            "{}",
+            format.format_table(
+                &[label, "files", "symbols", "lines", "annotated", "coverage"],
+                &rows
+            )
+        );
+    } else if options.json {
+        println!("{}", serde_json::to_string_pretty(&buckets)?);
+    } else if buckets.is_empty() {
+        println!("No {} information in cache", label);
+    } else {
+        println!(
+            "{}",
+            style(format!(
+                "  {:<20} {:>7} {:>9} {:>9} {:>9}",
+                label, "files", "symbols", "lines", "coverage"
+            ))
+            .bold()
+        );
+        for (name, b) in &buckets {
+            println!(
+                "  {:<20} {:>7} {:>9} {:>9} {:>8.1}%",
+                name, b.files, b.coverage.symbols, b.lines, b.coverage.coverage
+            );
+        }
+    }
+    Ok(())
+}
+
+fn query_stats_history(last: usize, options: &QueryOptions) -> Result<()> {
+    let path = history_path(&options.cache);
+    let history = read_history(&path)?;
+    let shown = &history[history.len().saturating_sub(last)..];
+
+    if let Some(format) = options.format {
+        let rows: Vec<_> = shown
+            .iter()
+            .map(|s| {
+                vec![
+                    s.timestamp.to_rfc3339(),
+                    s.git_commit.clone().unwrap_or_default(),
+                    s.files.to_string(),
+                    s.symbols.to_string(),
+                    s.lines.to_string(),
+                    format!("{:.1}", s.coverage),
+                    s.needs_review.to_string(),
+                ]
+            })
+            .collect();
+        let headers = [
+            "timestamp",
+            "commit",
+            "files",
+            "symbols",
+            "lines",
+            "coverage",
+            "needs_review",
+        ];
+        print!("{}", format.format_table(&headers, &rows));
+        return Ok(());
+    }
+    if options.json {
+        println!("{}", serde_json::to_string_pretty(shown)?);
+        return Ok(());
+    }
+
+    if shown.is_empty() {
+        println!(
+            "{} No stats history at {} (run acp index --history)",
+            style("ℹ").cyan(),
+            path.display()
+        );
+        return Ok(());
+    }
+
+    let coverage: Vec<f64> = shown.iter().map(|s| s.coverage).collect();
+    println!(
+        "Coverage: {} {:.1}% → {:.1}% over {} snapshot(s)",
+        sparkline(&coverage),
+        coverage[0],
+        coverage[coverage.len() - 1],
+        shown.len()
+    );
+    println!();
+    println!(
+        "{}",
+        style(format!(
+            "  {:<17} {:<8} {:>7} {:>9} {:>9} {:>8}",
+            "date", "commit", "files", "symbols", "coverage", "change"
+        ))
+        .bold()
+    );
+    let mut previous: Option<f64> = None;
+    for snapshot in shown {
+        let change = previous
+            .map(|p| format!("{:+.1}", snapshot.coverage - p))
+            .unwrap_or_default();
+        let commit = snapshot.git_commit.as_deref().unwrap_or("-");
+        println!(
+            "  {:<17} {:<8} {:>7} {:>9} {:>8.1}% {:>8}",
+            snapshot.timestamp.format("%Y-%m-%d %H:%M"),
+            commit.chars().take(7).collect::<String>(),
+            snapshot.files,
+            snapshot.symbols,
+            snapshot.coverage,
+            change
+        );
+        previous = Some(snapshot.coverage);
+    }
+    Ok(())
+}
+
+/// shields.io endpoint badge
+/// (<https://shields.io/badges/endpoint-badge>)
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ShieldsBadge {
+    schema_version: u8,
+    label: &'static str,
+    message: String,
+    color: &'static str,
+}
+
+fn query_coverage(
+    q: &Query,
+    shields: bool,
+    thresholds: &CoverageSettings,
+    options: &QueryOptions,
+) -> Result<()> {
+    let report = q.coverage();
+
+    if shields {
+        let badge = ShieldsBadge {
+            schema_version: 1,
+            label: "acp coverage",
+            message: format!("{:.0}%", report.total.coverage),
+            color: thresholds.color(report.total.coverage),
+        };
+        println!("{}", serde_json::to_string_pretty(&badge)?);
+        return Ok(());
+    }
+
+    if let Some(format) = options.format {
+        let mut rows = vec![coverage_row("total", "all", &report.total)];
+        rows.extend(
+            report
+                .by_domain
+                .iter()
+                .map(|(name, bucket)| coverage_row("domain", name, bucket)),
+        );
+        rows.extend(
+            report
+                .by_language
+                .iter()
+                .map(|(name, bucket)| coverage_row("language", name, bucket)),
+        );
+        print!(
+            "{}",
+            format.format_table(
+                &["group", "name", "symbols", "annotated", "coverage"],
+                &rows
+            )
+        );
+        return Ok(());
+    }
+
+    if options.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    let colored = |bucket: &CoverageBucket| {
+        let text = format!("{:.1}%", bucket.coverage);
+        match thresholds.color(bucket.coverage) {
+            "green" => style(text).green(),
+            "yellow" => style(text).yellow(),
+            _ => style(text).red(),
+        }
+    };
+
+    println!(
+        "Coverage: {} ({}/{} symbols)",
+        colored(&report.total),
+        report.total.annotated,
+        report.total.symbols
+    );
+    for (title, groups) in [
+        ("By domain:", &report.by_domain),
+        ("By language:", &report.by_language),
+    ] {
+        if groups.is_empty() {
+            continue;
+        }
+        println!();
+        println!("{}", style(title).bold());
+        for (name, bucket) in groups {
+            println!(
+                "  {:<20} {:>7} ({}/{})",
+                name,
+                colored(bucket),
+                bucket.annotated,
+                bucket.symbols
+            );
+        }
+    }
+    Ok(())
+}
+
+fn coverage_row(group: &str, name: &str, bucket: &CoverageBucket) -> Vec<String> {
+    vec![
+        group.to_string(),
+        name.to_string(),
+        bucket.symbols.to_string(),
+        bucket.annotated.to_string(),
+        format!("{:.1}", bucket.coverage),
+    ]
+}
+
 // =============================================================================
 // RFC-0003: Provenance Query Support
 // =============================================================================
@@ -325,8 +1822,148 @@ impl ConfidenceFilter {
     }
 }
 
+/// One annotation awaiting review (RFC-0003)
+#[derive(Debug, Clone, Serialize)]
+struct NeedsReviewEntry {
+    /// File path, or `file:symbol` for symbol annotations
+    target: String,
+    /// Annotation key without the `@acp:` prefix
+    annotation: String,
+    confidence: Option<f64>,
+    source: SourceOrigin,
+    value: String,
+}
+
+/// Collect annotations flagged `needs_review`, lowest confidence first
+///
+/// Annotations without a confidence score sort last, and are left out
+/// entirely when a confidence filter is given.
+fn collect_needs_review(
+    cache: &Cache,
+    source: Option<SourceOrigin>,
+    confidence: Option<&ConfidenceFilter>,
+) -> Vec<NeedsReviewEntry> {
+    let keep = |prov: &AnnotationProvenance| {
+        prov.needs_review
+            && source.is_none_or(|s| prov.source == s)
+            && match (confidence, prov.confidence) {
+                (Some(filter), Some(c)) => filter.matches(c),
+                (Some(_), None) => false,
+                (None, _) => true,
+            }
+    };
+    let entry = |target: String, key: &str, prov: &AnnotationProvenance| NeedsReviewEntry {
+        target,
+        annotation: key.trim_start_matches("@acp:").to_string(),
+        confidence: prov.confidence,
+        source: prov.source,
+        value: prov.value.clone(),
+    };
+
+    let mut entries: Vec<NeedsReviewEntry> = Vec::new();
+    for (path, file) in &cache.files {
+        entries.extend(
+            file.annotations
+                .iter()
+                .filter(|(_, prov)| keep(prov))
+                .map(|(key, prov)| entry(path.clone(), key, prov)),
+        );
+    }
+    for sym in cache.symbols.values() {
+        entries.extend(
+            sym.annotations
+                .iter()
+                .filter(|(_, prov)| keep(prov))
+                .map(|(key, prov)| entry(format!("{}:{}", sym.file, sym.name), key, prov)),
+        );
+    }
+
+    entries.sort_by(|a, b| {
+        a.confidence
+            .unwrap_or(f64::INFINITY)
+            .total_cmp(&b.confidence.unwrap_or(f64::INFINITY))
+            .then_with(|| a.target.cmp(&b.target))
+            .then_with(|| a.annotation.cmp(&b.annotation))
+    });
+    entries
+}
+
+/// List annotations needing review for triage (RFC-0003)
+fn query_needs_review(cache_data: &Cache, options: &QueryOptions) -> Result<()> {
+    let filter = options
+        .confidence
+        .as_deref()
+        .map(ConfidenceFilter::parse)
+        .transpose()?;
+    let paged = options.page.apply(
+        collect_needs_review(cache_data, options.source, filter.as_ref()),
+        None,
+    );
+
+    if let Some(format) = options.format {
+        let rows: Vec<_> = paged
+            .items
+            .iter()
+            .map(|entry| {
+                vec![
+                    entry.target.clone(),
+                    entry.annotation.clone(),
+                    entry.value.clone(),
+                    entry.source.as_str().to_string(),
+                    entry.confidence.map(|c| c.to_string()).unwrap_or_default(),
+                ]
+            })
+            .collect();
+        let headers = ["target", "annotation", "value", "source", "confidence"];
+        print!("{}", format.format_table(&headers, &rows));
+        print_footer(&paged, options);
+        return Ok(());
+    }
+    if options.json {
+        println!("{}", paged.to_json()?);
+        return Ok(());
+    }
+
+    if paged.total == 0 {
+        println!("{} No annotations need review!", style("✓").green());
+        return Ok(());
+    }
+
+    println!(
+        "{} ({}):",
+        style("Annotations Needing Review").bold(),
+        paged.total
+    );
+    for entry in &paged.items {
+        let confidence = match entry.confidence {
+            Some(c) => format_confidence(c, &options.provenance),
+            None => style("-".to_string()),
+        };
+        println!(
+            "  {:>4}  {} [{}]: \"{}\"",
+            confidence,
+            style(&entry.target).cyan(),
+            entry.annotation,
+            truncate_value(&entry.value, 50)
+        );
+    }
+    print_footer(&paged, options);
+
+    Ok(())
+}
+
 /// Display provenance statistics dashboard (RFC-0003)
 fn query_provenance(cache_data: &Cache, options: &QueryOptions) -> Result<()> {
+    if options.needs_review {
+        return query_needs_review(cache_data, options);
+    }
+
+    if options.format.is_some() {
+        return Err(anyhow!(
+            "query provenance supports text and --json output only; \
+             use --needs-review for CSV/TSV"
+        ));
+    }
     let stats = &cache_data.provenance;
 
     if options.json {
@@ -399,7 +2036,11 @@ fn query_provenance(cache_data: &Cache, options: &QueryOptions) -> Result<()> {
         println!();
         println!("{}:", style("Average Confidence").bold());
         for (source, avg) in &stats.summary.average_confidence {
-            println!("  {}: {:.2}", source, avg);
+            println!(
+                "  {}: {}",
+                source,
+                format_confidence(*avg, &options.provenance)
+            );
         }
     }
 
@@ -413,11 +2054,11 @@ fn query_provenance(cache_data: &Cache, options: &QueryOptions) -> Result<()> {
         );
         for entry in stats.low_confidence.iter().take(10) {
             println!(
-                "  {} [{}]: \"{}\" ({:.2})",
+                "  {} [{}]: \"{}\" ({})",
                 style(&entry.target).cyan(),
                 entry.annotation,
                 truncate_value(&entry.value, 30),
-                entry.confidence
+                format_confidence(entry.confidence, &options.provenance)
             );
         }
         if stats.low_confidence.len() > 10 {
@@ -446,3 +2087,115 @@ fn truncate_value(s: &str, max_len: usize) -> String {
         format!("{}...", &s[..max_len - 3])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::SymbolType;
+
+    fn prov(value: &str, source: SourceOrigin, confidence: Option<f64>) -> AnnotationProvenance {
+        AnnotationProvenance {
+            value: value.to_string(),
+            source,
+            confidence,
+            needs_review: source != SourceOrigin::Explicit,
+            reviewed: false,
+            reviewed_at: None,
+            generated_at: None,
+            generation_id: None,
+        }
+    }
+
+    fn sample_cache() -> Cache {
+        let mut cache = Cache::new("demo", ".");
+        let mut file: FileEntry = serde_json::from_value(serde_json::json!({
+            "path": "src/auth.ts",
+            "lines": 10,
+            "language": "typescript",
+        }))
+        .unwrap();
+        file.annotations.insert(
+            "@acp:summary".to_string(),
+            prov("Auth helpers", SourceOrigin::Heuristic, Some(0.8)),
+        );
+        file.annotations.insert(
+            "@acp:domain".to_string(),
+            prov("auth", SourceOrigin::Explicit, None),
+        );
+        cache.files.insert("src/auth.ts".to_string(), file);
+
+        let mut sym = SymbolEntry {
+            lines: [1, 5],
+            ..SymbolEntry::new("login", "src/auth.ts")
+        };
+        sym.annotations.insert(
+            "@acp:summary".to_string(),
+            prov("Logs in", SourceOrigin::Converted, Some(0.4)),
+        );
+        sym.annotations.insert(
+            "@acp:fn".to_string(),
+            prov("Login", SourceOrigin::Heuristic, None),
+        );
+        cache.symbols.insert("login".to_string(), sym);
+        cache
+    }
+
+    #[test]
+    fn test_needs_review_sorted_by_confidence() {
+        let entries = collect_needs_review(&sample_cache(), None, None);
+        let listed: Vec<_> = entries
+            .iter()
+            .map(|e| (e.target.as_str(), e.annotation.as_str(), e.confidence))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("src/auth.ts:login", "summary", Some(0.4)),
+                ("src/auth.ts", "summary", Some(0.8)),
+                ("src/auth.ts:login", "fn", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_needs_review_filters() {
+        let cache = sample_cache();
+
+        let heuristic = collect_needs_review(&cache, Some(SourceOrigin::Heuristic), None);
+        assert_eq!(heuristic.len(), 2);
+        assert!(heuristic
+            .iter()
+            .all(|e| e.source == SourceOrigin::Heuristic));
+
+        let low = ConfidenceFilter::parse("<0.7").unwrap();
+        let entries = collect_needs_review(&cache, None, Some(&low));
+        let values: Vec<_> = entries.iter().map(|e| e.value.as_str()).collect();
+        assert_eq!(values, vec!["Logs in"]);
+    }
+
+    #[test]
+    fn test_symbols_ndjson_is_sorted_one_per_line() {
+        let mut cache = sample_cache();
+        let sym = SymbolEntry {
+            symbol_type: SymbolType::Class,
+            lines: [1, 40],
+            ..SymbolEntry::new("Account", "src/account.ts")
+        };
+        cache.symbols.insert("Account".to_string(), sym);
+
+        let mut out = Vec::new();
+        write_ndjson(&mut out, sorted_symbols(&cache)).unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        let names: Vec<String> = text
+            .lines()
+            .map(|line| {
+                serde_json::from_str::<SymbolEntry>(line)
+                    .unwrap()
+                    .qualified_name
+            })
+            .collect();
+        assert_eq!(names, ["src/account.ts:Account", "src/auth.ts:login"]);
+        assert!(text.ends_with('\n'));
+    }
+}
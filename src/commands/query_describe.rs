@@ -0,0 +1,671 @@
+//! @acp:module "Query Descriptor"
+//! @acp:summary "Machine-readable description of the acp query surface"
+//! @acp:domain cli
+//! @acp:layer handler
+//!
+//! `acp query --describe` lists every query subcommand with its parameters
+//! and the JSON Schema of what `--json` prints, for generating typed clients.
+//! Parameters are read from the clap definition of `acp query`, so they
+//! cannot drift from the CLI. Result schemas are written by hand next to the
+//! handlers' output types; a subcommand without one is an error rather than
+//! a silently incomplete descriptor.
+
+use anyhow::{anyhow, Result};
+use clap::builder::ValueParser;
+use clap::{value_parser, Arg, Command};
+use console::style;
+use serde_json::{json, Value};
+
+/// Bumped when the descriptor layout (not the query surface) changes
+pub const DESCRIPTOR_VERSION: u32 = 1;
+
+/// @acp:summary "Descriptor for every subcommand of the `acp query` command"
+pub fn describe_queries(query: &Command) -> Result<Value> {
+    let queries = query
+        .get_subcommands()
+        .filter(|sub| sub.get_name() != "help")
+        .map(|sub| {
+            let name = sub.get_name();
+            let result = result_schema(name)
+                .ok_or_else(|| anyhow!("No result schema for query '{}'", name))?;
+            let mut entry = json!({
+                "name": name,
+                "description": about(sub),
+                "parameters": parameters(sub),
+                "json": !result.is_null(),
+            });
+            if !result.is_null() {
+                entry["result"] = result;
+            }
+            Ok(entry)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(json!({
+        "descriptor_version": DESCRIPTOR_VERSION,
+        "acp_version": crate::VERSION,
+        "command": "acp query",
+        "options": parameters(query),
+        "paging": {
+            "description": "With --limit or --offset, array results become this envelope; object results gain its total, offset, limit and hasMore",
+            "$ref": "#/$defs/Page"
+        },
+        "queries": queries,
+        "$defs": definitions(),
+    }))
+}
+
+/// @acp:summary "Print the query descriptor as JSON or a readable listing"
+pub fn execute_query_describe(query: &Command, json: bool) -> Result<()> {
+    let descriptor = describe_queries(query)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&descriptor)?);
+        return Ok(());
+    }
+
+    for entry in descriptor["queries"].as_array().into_iter().flatten() {
+        let output = if entry["json"] == true {
+            ""
+        } else {
+            " (text only)"
+        };
+        println!(
+            "{}  {}{}",
+            style(entry["name"].as_str().unwrap_or_default()).bold(),
+            entry["description"].as_str().unwrap_or_default(),
+            style(output).dim()
+        );
+        for param in entry["parameters"].as_array().into_iter().flatten() {
+            let name = param["name"].as_str().unwrap_or_default();
+            let usage = match param["kind"].as_str() {
+                Some("positional") => format!("<{}>", name),
+                _ => format!("--{}", name),
+            };
+            println!(
+                "    {:<22} {}",
+                style(usage).cyan(),
+                param["description"].as_str().unwrap_or_default()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn about(cmd: &Command) -> String {
+    cmd.get_about().map(|s| s.to_string()).unwrap_or_default()
+}
+
+/// @acp:summary "Parameter descriptors for a command's own arguments"
+fn parameters(cmd: &Command) -> Vec<Value> {
+    cmd.get_arguments()
+        .filter(|arg| !matches!(arg.get_id().as_str(), "help" | "version"))
+        .map(parameter)
+        .collect()
+}
+
+fn parameter(arg: &Arg) -> Value {
+    let takes_value = arg.get_action().takes_values();
+    let kind = if arg.is_positional() {
+        "positional"
+    } else if takes_value {
+        "option"
+    } else {
+        "flag"
+    };
+    let name = arg
+        .get_long()
+        .map_or_else(|| arg.get_id().to_string(), str::to_string);
+
+    let mut param = json!({
+        "name": name,
+        "kind": kind,
+        "type": value_type(arg),
+        "required": arg.is_required_set(),
+        "description": arg.get_help().map(|s| s.to_string()).unwrap_or_default(),
+    });
+    if let Some(short) = arg.get_short() {
+        param["short"] = json!(short.to_string());
+    }
+    let values: Vec<_> = arg
+        .get_possible_values()
+        .iter()
+        .filter(|v| !v.is_hide_set())
+        .map(|v| v.get_name().to_string())
+        .collect();
+    if takes_value && !values.is_empty() {
+        param["enum"] = json!(values);
+    }
+    if let [default] = arg.get_default_values() {
+        if takes_value {
+            param["default"] = json!(default.to_string_lossy());
+        }
+    }
+    param
+}
+
+/// @acp:summary "JSON type of an argument's parsed value"
+fn value_type(arg: &Arg) -> &'static str {
+    if !arg.get_action().takes_values() {
+        return "boolean";
+    }
+    let id = arg.get_value_parser().type_id();
+    let integers = [
+        ValueParser::from(value_parser!(usize)),
+        ValueParser::from(value_parser!(u64)),
+        ValueParser::from(value_parser!(u32)),
+        ValueParser::from(value_parser!(i64)),
+    ];
+    if integers.iter().any(|parser| parser.type_id() == id) {
+        "integer"
+    } else {
+        "string"
+    }
+}
+
+fn array_of(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+fn def(name: &str) -> Value {
+    json!({ "$ref": format!("#/$defs/{}", name) })
+}
+
+/// @acp:summary "JSON Schema of a query's `--json` output"
+///
+/// `Some(Value::Null)` marks a query that only prints text. Queries whose
+/// shape depends on a flag list each shape under `oneOf`.
+fn result_schema(query: &str) -> Option<Value> {
+    let schema = match query {
+        "symbol" => json!({
+            "oneOf": [
+                def("SymbolEntry"),
+                {
+                    "description": "Ambiguous names, or --glob/--regex/--fuzzy matches",
+                    "type": "array",
+                    "items": def("SymbolEntry")
+                },
+                {
+                    "description": "With --full; an array of them for ambiguous names",
+                    "$ref": "#/$defs/Neighborhood"
+                }
+            ]
+        }),
+        "file" => json!({
+            "oneOf": [
+                def("FileEntry"),
+                {
+                    "description": "With --symbols, sorted by start line",
+                    "type": "array",
+                    "items": def("SymbolEntry")
+                }
+            ]
+        }),
+        "symbols" => json!({
+            "description": "Sorted by qualified name; --ndjson prints one item per line",
+            "type": "array",
+            "items": def("SymbolEntry")
+        }),
+        "files" => json!({
+            "description": "Sorted by path; --ndjson prints one item per line",
+            "type": "array",
+            "items": def("FileEntry")
+        }),
+        "callers" => array_of(def("CallerSite")),
+        "callees" => json!({
+            "oneOf": [
+                array_of(json!({ "type": "string" })),
+                {
+                    "description": "With --include-heuristic",
+                    "type": "array",
+                    "items": def("CalleeEntry")
+                },
+                {
+                    "description": "With --internal or --external",
+                    "type": "array",
+                    "items": def("ScopedCallee")
+                }
+            ]
+        }),
+        "path" => json!({
+            "description": "Call paths, shortest first; each path lists its hops in order",
+            "type": "array",
+            "items": array_of(def("PathHop"))
+        }),
+        "domains" => array_of(def("DomainEntry")),
+        "domain" => def("DomainEntry"),
+        "hotpaths" => json!({
+            "description": "Highest score first; score is fanIn × fanOut (× churn with --churn)",
+            "type": "array",
+            "items": def("Hotpath")
+        }),
+        "stats" => json!({
+            "oneOf": [
+                def("Stats"),
+                {
+                    "description": "With --by-language or --by-domain, keyed by group name",
+                    "type": "object",
+                    "additionalProperties": def("StatsBucket")
+                },
+                {
+                    "description": "With --history, oldest first",
+                    "type": "array",
+                    "items": def("StatsSnapshot")
+                }
+            ]
+        }),
+        "coverage" => json!({
+            "oneOf": [
+                def("CoverageReport"),
+                { "description": "With --format shields", "$ref": "#/$defs/ShieldsBadge" }
+            ]
+        }),
+        "provenance" => json!({
+            "oneOf": [
+                def("ProvenanceStats"),
+                {
+                    "description": "With --needs-review, lowest confidence first",
+                    "type": "array",
+                    "items": def("NeedsReviewEntry")
+                }
+            ]
+        }),
+        "deprecated" => array_of(def("DeprecatedEntry")),
+        "constraints" => json!({
+            "type": "object",
+            "required": ["locks"],
+            "properties": {
+                "locks": {
+                    "description": "Most restrictive level first; entries sorted by file then line",
+                    "type": "array",
+                    "items": def("LockGroup")
+                },
+                "hacks": {
+                    "description": "With --hacks, sorted by file then line",
+                    "type": "array",
+                    "items": def("HackEntry")
+                },
+                "total": { "description": "With --limit or --offset; lock entries before paging", "type": "integer" },
+                "hasMore": { "description": "With --limit or --offset", "type": "boolean" }
+            }
+        }),
+        "behavior" => array_of(def("BehaviorEntry")),
+        "throws" => array_of(def("ThrowingEntry")),
+        _ => return None,
+    };
+    Some(schema)
+}
+
+/// @acp:summary "Schemas shared by the query results"
+///
+/// Cache entities list the fields clients rely on; the full shapes are in
+/// the cache schema.
+fn definitions() -> Value {
+    let cache_schema = "https://acp-protocol.dev/schemas/v1/cache.schema.json";
+    let count = json!({ "type": "integer", "minimum": 0 });
+    let lock_level = json!({
+        "enum": ["frozen", "restricted", "approval-required", "tests-required",
+                 "docs-required", "review-required", "normal", "experimental"]
+    });
+    let coverage_bucket = json!({
+        "type": "object",
+        "required": ["symbols", "annotated", "coverage"],
+        "properties": {
+            "symbols": count,
+            "annotated": count,
+            "coverage": { "type": "number", "description": "Percentage of symbols with a summary" }
+        }
+    });
+
+    json!({
+        "SymbolEntry": {
+            "description": format!("A value of the cache's `symbols` map ({})", cache_schema),
+            "type": "object",
+            "required": ["name", "qualified_name", "type", "file", "lines", "exported"],
+            "properties": {
+                "name": { "type": "string" },
+                "qualified_name": { "type": "string" },
+                "type": {
+                    "enum": ["function", "method", "class", "interface", "type", "enum",
+                             "struct", "trait", "const"]
+                },
+                "file": { "type": "string" },
+                "lines": { "type": "array", "items": count, "minItems": 2, "maxItems": 2 },
+                "exported": { "type": "boolean" },
+                "signature": { "type": "string" },
+                "summary": { "type": "string" },
+                "calls": { "type": "array", "items": { "type": "string" } },
+                "called_by": { "type": "array", "items": { "type": "string" } }
+            }
+        },
+        "FileEntry": {
+            "description": format!("A value of the cache's `files` map ({})", cache_schema),
+            "type": "object",
+            "required": ["path", "lines", "language"],
+            "properties": {
+                "path": { "type": "string" },
+                "lines": count,
+                "language": { "type": "string" },
+                "exports": { "type": "array", "items": { "type": "string" } },
+                "imports": { "type": "array", "items": { "type": "string" } },
+                "module": { "type": "string" },
+                "summary": { "type": "string" }
+            }
+        },
+        "DomainEntry": {
+            "description": format!("A value of the cache's `domains` map ({})", cache_schema),
+            "type": "object",
+            "required": ["name", "files", "symbols"],
+            "properties": {
+                "name": { "type": "string" },
+                "files": { "type": "array", "items": { "type": "string" } },
+                "symbols": { "type": "array", "items": { "type": "string" } },
+                "description": { "type": "string" }
+            }
+        },
+        "Stats": {
+            "description": format!("The cache's `stats` object ({})", cache_schema),
+            "type": "object",
+            "required": ["files", "symbols", "lines", "annotation_coverage"],
+            "properties": {
+                "files": count,
+                "symbols": count,
+                "lines": count,
+                "annotation_coverage": { "type": "number" },
+                "primary_language": { "type": "string" }
+            }
+        },
+        "StatsSnapshot": {
+            "description": "A line of stats-history.jsonl",
+            "type": "object",
+            "required": ["timestamp", "files", "symbols", "lines", "coverage",
+                         "provenance", "needs_review"],
+            "properties": {
+                "timestamp": { "type": "string", "format": "date-time" },
+                "git_commit": { "type": "string" },
+                "files": count,
+                "symbols": count,
+                "lines": count,
+                "coverage": { "type": "number" },
+                "provenance": {
+                    "type": "object",
+                    "additionalProperties": count,
+                    "description": "Annotation counts by source origin"
+                },
+                "needs_review": count
+            }
+        },
+        "ProvenanceStats": {
+            "description": format!("The cache's `provenance` object ({})", cache_schema),
+            "type": "object",
+            "required": ["summary"]
+        },
+        "CallerSite": {
+            "type": "object",
+            "required": ["caller"],
+            "properties": {
+                "caller": { "type": "string" },
+                "file": { "type": "string" },
+                "line": count
+            }
+        },
+        "Neighborhood": {
+            "type": "object",
+            "required": ["symbol", "callers", "callees"],
+            "properties": {
+                "symbol": def("SymbolEntry"),
+                "callers": array_of(def("Neighbor")),
+                "callees": array_of(def("Neighbor"))
+            }
+        },
+        "Neighbor": {
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "name": { "type": "string" },
+                "files": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Files defining the symbol; absent outside the index"
+                }
+            }
+        },
+        "ScopedCallee": {
+            "type": "object",
+            "required": ["callee", "scope"],
+            "properties": {
+                "callee": { "type": "string" },
+                "scope": {
+                    "description": "stdlib is guessed from the callee name and the caller's language",
+                    "enum": ["internal", "stdlib", "external"]
+                },
+                "file": { "type": "string", "description": "Internal callees only" },
+                "line": count
+            }
+        },
+        "CalleeEntry": {
+            "type": "object",
+            "required": ["callee", "heuristic"],
+            "properties": {
+                "callee": { "type": "string" },
+                "heuristic": { "type": "boolean" },
+                "file": { "type": "string", "description": "Heuristic callees only" },
+                "line": count
+            }
+        },
+        "PathHop": {
+            "type": "object",
+            "required": ["symbol", "file", "line"],
+            "properties": {
+                "symbol": { "type": "string" },
+                "file": { "type": ["string", "null"] },
+                "line": { "type": ["integer", "null"] }
+            }
+        },
+        "CoverageBucket": coverage_bucket,
+        "StatsBucket": {
+            "type": "object",
+            "required": ["files", "lines", "symbols", "annotated", "coverage"],
+            "properties": {
+                "files": count,
+                "lines": count,
+                "symbols": count,
+                "annotated": count,
+                "coverage": { "type": "number" }
+            }
+        },
+        "CoverageReport": {
+            "type": "object",
+            "required": ["total", "by_domain", "by_language"],
+            "properties": {
+                "total": def("CoverageBucket"),
+                "by_domain": { "type": "object", "additionalProperties": def("CoverageBucket") },
+                "by_language": { "type": "object", "additionalProperties": def("CoverageBucket") }
+            }
+        },
+        "ShieldsBadge": {
+            "description": "shields.io endpoint badge",
+            "type": "object",
+            "required": ["schemaVersion", "label", "message", "color"],
+            "properties": {
+                "schemaVersion": { "const": 1 },
+                "label": { "type": "string" },
+                "message": { "type": "string" },
+                "color": { "type": "string" }
+            }
+        },
+        "NeedsReviewEntry": {
+            "type": "object",
+            "required": ["target", "annotation", "confidence", "source", "value"],
+            "properties": {
+                "target": { "type": "string", "description": "File path, or file:symbol" },
+                "annotation": { "type": "string" },
+                "confidence": { "type": ["number", "null"] },
+                "source": { "enum": ["explicit", "converted", "heuristic", "refined", "inferred"] },
+                "value": { "type": "string" }
+            }
+        },
+        "DeprecatedEntry": {
+            "type": "object",
+            "required": ["kind", "name", "file", "message"],
+            "properties": {
+                "kind": { "enum": ["file", "symbol"] },
+                "name": { "type": "string" },
+                "file": { "type": "string" },
+                "line": count,
+                "message": { "type": "string" },
+                "since": { "type": "string" },
+                "age_days": count
+            }
+        },
+        "Page": {
+            "type": "object",
+            "required": ["items", "total", "offset", "limit", "hasMore"],
+            "properties": {
+                "items": { "description": "The query's usual array result, cut to the window", "type": "array" },
+                "total": count,
+                "offset": count,
+                "limit": { "type": ["integer", "null"], "minimum": 0 },
+                "hasMore": { "type": "boolean" }
+            }
+        },
+        "LockGroup": {
+            "type": "object",
+            "required": ["level", "entries"],
+            "properties": {
+                "level": lock_level,
+                "entries": array_of(def("LockedEntry"))
+            }
+        },
+        "LockedEntry": {
+            "type": "object",
+            "required": ["level", "kind", "name", "file"],
+            "properties": {
+                "level": lock_level,
+                "kind": { "enum": ["file", "symbol"] },
+                "name": { "type": "string" },
+                "file": { "type": "string" },
+                "line": count,
+                "directive": { "type": "string" },
+                "inherited_from": {
+                    "description": "Directory lock pattern from config, for files without @acp:lock",
+                    "type": "string"
+                }
+            }
+        },
+        "HackEntry": {
+            "description": format!("A `constraints.hacks` marker ({}) plus its expiry status", cache_schema),
+            "type": "object",
+            "required": ["id", "file", "reason", "expired"],
+            "properties": {
+                "id": { "type": "string" },
+                "file": { "type": "string" },
+                "line": count,
+                "reason": { "type": "string" },
+                "ticket": { "type": "string" },
+                "expires": { "type": "string", "format": "date-time" },
+                "expired": { "type": "boolean" }
+            }
+        },
+        "BehaviorEntry": {
+            "type": "object",
+            "required": ["name", "file", "line", "behavioral"],
+            "properties": {
+                "name": { "type": "string" },
+                "file": { "type": "string" },
+                "line": count,
+                "behavioral": {
+                    "description": format!("The symbol's `behavioral` object ({}); empty for symbols listed only as recursive", cache_schema),
+                    "type": "object"
+                },
+                "recursive": {
+                    "description": "Present when the symbol calls itself, directly or through a short cycle",
+                    "type": "boolean"
+                }
+            }
+        },
+        "ThrowingEntry": {
+            "type": "object",
+            "required": ["name", "file", "line", "exception"],
+            "properties": {
+                "name": { "type": "string" },
+                "file": { "type": "string" },
+                "line": count,
+                "exception": { "type": "string" },
+                "description": { "description": "When or why the exception is thrown", "type": "string" },
+                "directive": { "type": "string" },
+                "source": { "description": "Omitted for explicit @acp:throws", "type": "string" },
+                "sourceFormat": { "type": "string" }
+            }
+        },
+        "Hotpath": {
+            "type": "object",
+            "required": ["symbol", "fanIn", "fanOut", "score"],
+            "properties": {
+                "symbol": { "type": "string" },
+                "fanIn": count,
+                "fanOut": count,
+                "churn": {
+                    "description": "Commits touching the defining file; with --churn only",
+                    "type": "integer",
+                    "minimum": 1
+                },
+                "score": count
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_reads_parameters_from_clap() {
+        let query = Command::new("query")
+            .arg(
+                Arg::new("json")
+                    .long("json")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .subcommand(
+                Command::new("path")
+                    .about("Show the shortest call path")
+                    .arg(Arg::new("from").required(true).help("Calling symbol"))
+                    .arg(
+                        Arg::new("max")
+                            .long("max")
+                            .value_parser(value_parser!(usize))
+                            .default_value("10"),
+                    ),
+            )
+            .subcommand(Command::new("hotpaths"));
+
+        let descriptor = describe_queries(&query).unwrap();
+        assert_eq!(descriptor["options"][0]["name"], "json");
+        assert_eq!(descriptor["options"][0]["type"], "boolean");
+
+        let path = &descriptor["queries"][0];
+        assert_eq!(path["description"], "Show the shortest call path");
+        assert_eq!(path["parameters"][0]["kind"], "positional");
+        assert_eq!(path["parameters"][0]["required"], true);
+        assert_eq!(path["parameters"][1]["type"], "integer");
+        assert_eq!(path["parameters"][1]["default"], "10");
+        assert_eq!(path["result"]["items"]["items"]["$ref"], "#/$defs/PathHop");
+
+        let hotpaths = &descriptor["queries"][1];
+        assert_eq!(hotpaths["json"], true);
+        assert_eq!(hotpaths["result"]["items"]["$ref"], "#/$defs/Hotpath");
+
+        // Every referenced definition exists
+        let defs = descriptor["$defs"].as_object().unwrap();
+        let text = descriptor.to_string();
+        for name in text.split("#/$defs/").skip(1) {
+            let name = name.split('"').next().unwrap();
+            assert!(defs.contains_key(name), "missing $defs/{}", name);
+        }
+
+        let unknown = Command::new("query").subcommand(Command::new("frobnicate"));
+        let err = describe_queries(&unknown).unwrap_err().to_string();
+        assert!(err.contains("frobnicate"));
+    }
+}
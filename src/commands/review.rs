@@ -7,6 +7,7 @@
 //! - List annotations needing review
 //! - Mark annotations as reviewed
 //! - Interactive review mode
+//! - Generation run history
 
 use std::io::{self, Write};
 use std::path::PathBuf;
@@ -15,8 +16,11 @@ use anyhow::Result;
 use chrono::Utc;
 use console::style;
 
+use crate::annotate::GenerationLog;
 use crate::cache::{AnnotationProvenance, Cache};
+use crate::commands::output::format_confidence;
 use crate::commands::query::ConfidenceFilter;
+use crate::config::AnnotateProvenanceConfig;
 use crate::parse::SourceOrigin;
 
 /// Options for the review command (RFC-0003)
@@ -24,21 +28,27 @@ use crate::parse::SourceOrigin;
 pub struct ReviewOptions {
     /// Cache file path
     pub cache: PathBuf,
+    /// Generation audit log, shared with `annotate`
+    pub generation_log: PathBuf,
     /// Filter by source origin
     pub source: Option<SourceOrigin>,
     /// Filter by confidence expression (e.g., "<0.7", ">=0.9")
     pub confidence: Option<String>,
     /// Output as JSON
     pub json: bool,
+    /// Confidence display precision and color thresholds
+    pub provenance: AnnotateProvenanceConfig,
 }
 
 impl Default for ReviewOptions {
     fn default() -> Self {
         Self {
             cache: PathBuf::from(".acp/acp.cache.json"),
+            generation_log: PathBuf::from(".acp/generations.jsonl"),
             source: None,
             confidence: None,
             json: false,
+            provenance: AnnotateProvenanceConfig::default(),
         }
     }
 }
@@ -53,9 +63,43 @@ pub enum ReviewSubcommand {
         file: Option<PathBuf>,
         symbol: Option<String>,
         all: bool,
+        /// Mark non-explicit annotations with confidence >= this threshold
+        above: Option<f64>,
     },
     /// Interactive review mode
     Interactive,
+    /// Show generation runs from the audit log next to the cache
+    History,
+}
+
+/// Which annotations `review mark` selects
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MarkSelection {
+    /// Annotations matching the `--source`/`--confidence` filters
+    Filtered,
+    /// Every annotation
+    All,
+    /// Auto-generated annotations with confidence at or above the threshold;
+    /// explicit annotations are never touched
+    Above(f64),
+}
+
+impl MarkSelection {
+    fn selects(
+        self,
+        prov: &AnnotationProvenance,
+        options: &ReviewOptions,
+        conf_filter: &Option<ConfidenceFilter>,
+    ) -> bool {
+        match self {
+            Self::Filtered => should_include(prov, options, conf_filter),
+            Self::All => true,
+            Self::Above(threshold) => {
+                prov.source != SourceOrigin::Explicit
+                    && prov.confidence.is_some_and(|c| c >= threshold)
+            }
+        }
+    }
 }
 
 /// Item for review display
@@ -75,9 +119,25 @@ pub fn execute_review(options: ReviewOptions, subcommand: ReviewSubcommand) -> R
             let cache = Cache::from_json(&options.cache)?;
             list_for_review(&cache, &options)
         }
-        ReviewSubcommand::Mark { file, symbol, all } => {
+        ReviewSubcommand::Mark {
+            file,
+            symbol,
+            all,
+            above,
+        } => {
             let mut cache = Cache::from_json(&options.cache)?;
-            mark_reviewed(&mut cache, &options, file.as_ref(), symbol.as_deref(), all)?;
+            let selection = match above {
+                Some(threshold) => MarkSelection::Above(threshold),
+                None if all => MarkSelection::All,
+                None => MarkSelection::Filtered,
+            };
+            mark_reviewed(
+                &mut cache,
+                &options,
+                file.as_ref(),
+                symbol.as_deref(),
+                selection,
+            )?;
             cache.write_json(&options.cache)?;
             Ok(())
         }
@@ -87,7 +147,75 @@ pub fn execute_review(options: ReviewOptions, subcommand: ReviewSubcommand) -> R
             cache.write_json(&options.cache)?;
             Ok(())
         }
+        ReviewSubcommand::History => show_history(&options),
+    }
+}
+
+/// Print recorded generation runs, oldest first
+fn show_history(options: &ReviewOptions) -> Result<()> {
+    let log = GenerationLog::new(&options.generation_log);
+    let (records, skipped) = log.read()?;
+
+    if skipped > 0 {
+        eprintln!(
+            "{} Skipped {} unreadable line(s) in {}",
+            style("⚠").yellow(),
+            skipped,
+            log.path().display()
+        );
+    }
+
+    if options.json {
+        println!("{}", serde_json::to_string_pretty(&records)?);
+        return Ok(());
+    }
+
+    if records.is_empty() {
+        println!(
+            "{} No generation runs recorded in {}",
+            style("ℹ").cyan(),
+            log.path().display()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} {} generation runs recorded:",
+        style("→").cyan(),
+        records.len()
+    );
+    println!();
+
+    for record in &records {
+        let info = &record.info;
+        let conf = &record.confidence;
+        println!("{}  {}", style(&info.id).cyan(), info.timestamp);
+        println!(
+            "   {} annotations in {} files",
+            info.annotations_generated, info.files_affected
+        );
+        let fmt = |c: f64| format_confidence(c, &options.provenance);
+        println!(
+            "   Confidence: avg {} (min {}, max {}; low {}, medium {}, high {})",
+            fmt(conf.average),
+            fmt(conf.min),
+            fmt(conf.max),
+            conf.low,
+            conf.medium,
+            conf.high
+        );
+        if !record.by_source.is_empty() {
+            let sources: Vec<String> = record
+                .by_source
+                .iter()
+                .map(|(source, count)| format!("{} {}", source, count))
+                .collect();
+            println!("   Sources: {}", sources.join(", "));
+        }
+        println!();
     }
+
+    Ok(())
 }
 
 /// List all annotations needing review
@@ -132,7 +260,10 @@ fn list_for_review(cache: &Cache, options: &ReviewOptions) -> Result<()> {
         );
         println!("   Source: {:?}", item.source);
         if let Some(conf) = item.confidence {
-            println!("   Confidence: {:.2}", conf);
+            println!(
+                "   Confidence: {}",
+                format_confidence(conf, &options.provenance)
+            );
         }
         println!();
     }
@@ -224,14 +355,16 @@ fn should_include(
     true
 }
 
-/// Mark annotations as reviewed
+/// Mark annotations as reviewed, returning how many were newly marked
+///
+/// Already-reviewed annotations are skipped, so re-running is a no-op.
 fn mark_reviewed(
     cache: &mut Cache,
     options: &ReviewOptions,
     file: Option<&PathBuf>,
     symbol: Option<&str>,
-    all: bool,
-) -> Result<()> {
+    selection: MarkSelection,
+) -> Result<usize> {
     let now = Utc::now().to_rfc3339();
     let conf_filter = options
         .confidence
@@ -249,7 +382,7 @@ fn mark_reviewed(
         }
 
         for prov in file_entry.annotations.values_mut() {
-            if (all || should_include(prov, options, &conf_filter)) && !prov.reviewed {
+            if !prov.reviewed && selection.selects(prov, options, &conf_filter) {
                 prov.reviewed = true;
                 prov.needs_review = false;
                 prov.reviewed_at = Some(now.clone());
@@ -278,7 +411,7 @@ fn mark_reviewed(
         }
 
         for prov in sym.annotations.values_mut() {
-            if (all || should_include(prov, options, &conf_filter)) && !prov.reviewed {
+            if !prov.reviewed && selection.selects(prov, options, &conf_filter) {
                 prov.reviewed = true;
                 prov.needs_review = false;
                 prov.reviewed_at = Some(now.clone());
@@ -296,7 +429,7 @@ fn mark_reviewed(
         count
     );
 
-    Ok(())
+    Ok(count)
 }
 
 /// Interactive review mode
@@ -328,7 +461,10 @@ fn interactive_review(cache: &mut Cache, options: &ReviewOptions) -> Result<()>
         );
         println!("  Source: {:?}", item.source);
         if let Some(conf) = item.confidence {
-            println!("  Confidence: {:.2}", conf);
+            println!(
+                "  Confidence: {}",
+                format_confidence(conf, &options.provenance)
+            );
         }
         println!();
 
@@ -384,8 +520,12 @@ fn mark_single_reviewed(cache: &mut Cache, item: &ReviewItem, timestamp: &str) -
         // Symbol (format: file:name)
         let parts: Vec<&str> = item.target.rsplitn(2, ':').collect();
         if parts.len() == 2 {
-            let sym_name = parts[0];
-            if let Some(sym) = cache.symbols.get_mut(sym_name) {
+            let (sym_name, sym_file) = (parts[0], parts[1]);
+            if let Some(sym) = cache
+                .symbols
+                .values_mut()
+                .find(|s| s.name == sym_name && s.file == sym_file)
+            {
                 if let Some(prov) = sym.annotations.get_mut(&key) {
                     prov.reviewed = true;
                     prov.needs_review = false;
@@ -477,3 +617,88 @@ fn truncate_value(s: &str, max_len: usize) -> String {
         format!("{}...", &s[..max_len - 3])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{FileEntry, SymbolEntry};
+
+    fn prov(source: SourceOrigin, confidence: Option<f64>) -> AnnotationProvenance {
+        AnnotationProvenance {
+            value: "value".to_string(),
+            source,
+            confidence,
+            needs_review: source != SourceOrigin::Explicit,
+            reviewed: false,
+            reviewed_at: None,
+            generated_at: None,
+            generation_id: None,
+        }
+    }
+
+    fn sample_cache() -> Cache {
+        let mut cache = Cache::new("demo", ".");
+        let mut file: FileEntry = serde_json::from_value(serde_json::json!({
+            "path": "src/app.ts",
+            "lines": 10,
+            "language": "typescript",
+        }))
+        .unwrap();
+        file.annotations.insert(
+            "@acp:summary".to_string(),
+            prov(SourceOrigin::Heuristic, Some(0.95)),
+        );
+        file.annotations.insert(
+            "@acp:domain".to_string(),
+            prov(SourceOrigin::Explicit, Some(1.0)),
+        );
+        cache.files.insert("src/app.ts".to_string(), file);
+
+        let mut sym = SymbolEntry {
+            lines: [1, 5],
+            ..SymbolEntry::new("run", "src/app.ts")
+        };
+        sym.annotations.insert(
+            "@acp:summary".to_string(),
+            prov(SourceOrigin::Converted, Some(0.9)),
+        );
+        sym.annotations.insert(
+            "@acp:fn".to_string(),
+            prov(SourceOrigin::Heuristic, Some(0.5)),
+        );
+        cache.symbols.insert("run".to_string(), sym);
+        cache
+    }
+
+    #[test]
+    fn test_mark_above_threshold_skips_explicit_and_is_idempotent() {
+        let mut cache = sample_cache();
+        let options = ReviewOptions::default();
+        let above = MarkSelection::Above(0.9);
+
+        let marked = mark_reviewed(&mut cache, &options, None, None, above).unwrap();
+        assert_eq!(marked, 2);
+
+        let file = &cache.files["src/app.ts"].annotations;
+        assert!(file["@acp:summary"].reviewed);
+        assert!(!file["@acp:summary"].needs_review);
+        assert!(file["@acp:summary"].reviewed_at.is_some());
+        assert!(!file["@acp:domain"].reviewed);
+
+        let sym = &cache.symbols["run"].annotations;
+        assert!(sym["@acp:summary"].reviewed);
+        assert!(!sym["@acp:fn"].reviewed);
+        assert!(sym["@acp:fn"].needs_review);
+
+        assert_eq!(cache.provenance.summary.reviewed, 2);
+        assert_eq!(cache.provenance.summary.needs_review, 1);
+
+        let first_reviewed_at = sym["@acp:summary"].reviewed_at.clone();
+        let again = mark_reviewed(&mut cache, &options, None, None, above).unwrap();
+        assert_eq!(again, 0);
+        assert_eq!(
+            cache.symbols["run"].annotations["@acp:summary"].reviewed_at,
+            first_reviewed_at
+        );
+    }
+}
@@ -0,0 +1,239 @@
+//! @acp:module "Scan Command"
+//! @acp:summary "Quick project overview: languages, sizes, manifests and AI tools"
+//! @acp:domain cli
+//! @acp:layer handler
+//!
+//! Needs no config or cache, so it can run before `acp init` to show what
+//! indexing would cover.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use console::style;
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::scan::{scan_project, ProjectScan};
+use crate::sync::SyncExecutor;
+
+/// Options for the scan command
+#[derive(Debug, Clone)]
+pub struct ScanOptions {
+    /// Directory to scan
+    pub root: PathBuf,
+    /// Output as JSON
+    pub json: bool,
+}
+
+/// @acp:summary "Scan results plus the AI tools detected in the project"
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanReport {
+    pub root: String,
+    #[serde(flatten)]
+    pub scan: ProjectScan,
+    /// Build manifests found (package.json, Cargo.toml, ...)
+    pub manifests: Vec<&'static str>,
+    pub tools: Vec<DetectedTool>,
+}
+
+/// @acp:summary "An AI tool that appears to be in use"
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectedTool {
+    /// Tool id (`cursor`, `claude-code`, or a custom adapter name)
+    pub id: String,
+    pub name: String,
+    /// Context file the tool reads
+    pub output_path: String,
+    /// Why the tool was detected
+    pub reason: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub custom: bool,
+}
+
+/// Execute the scan command
+pub fn execute_scan(options: ScanOptions, config: &Config) -> Result<()> {
+    let report = build_report(&options, config);
+
+    if options.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!("{} Scanned {}\n", style("→").cyan(), report.root);
+
+    if report.scan.languages.is_empty() {
+        println!("{} No supported languages detected", style("⚠").yellow());
+    } else {
+        println!("{}", style("Languages:").bold());
+        let width = report
+            .scan
+            .languages
+            .iter()
+            .map(|l| l.name.len())
+            .max()
+            .unwrap_or_default();
+        for lang in &report.scan.languages {
+            println!(
+                "  {}  {:>6} files  {:>9}",
+                style(format!("{:<width$}", lang.name, width = width)).cyan(),
+                lang.file_count,
+                format_bytes(lang.bytes),
+            );
+        }
+    }
+    println!(
+        "\n  Total: {} files, {}",
+        report.scan.total_files,
+        format_bytes(report.scan.total_bytes)
+    );
+
+    if !report.manifests.is_empty() {
+        println!(
+            "\n{} {}",
+            style("Manifests:").bold(),
+            report.manifests.join(", ")
+        );
+    }
+
+    if report.tools.is_empty() {
+        println!("\n{} No AI tools detected", style("•").dim());
+    } else {
+        println!("\n{}", style("AI tools:").bold());
+        for tool in &report.tools {
+            let custom = if tool.custom { ", custom" } else { "" };
+            println!(
+                "  {} ({}{}) - {}",
+                style(&tool.name).cyan(),
+                tool.output_path,
+                custom,
+                tool.reason
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// @acp:summary "Run the scan and tool detection for the report"
+fn build_report(options: &ScanOptions, config: &Config) -> ScanReport {
+    let scan = scan_project(&options.root);
+    let manifests = [
+        (scan.has_package_json, "package.json"),
+        (scan.has_cargo_toml, "Cargo.toml"),
+        (scan.has_pyproject_toml, "pyproject.toml"),
+        (scan.has_go_mod, "go.mod"),
+    ]
+    .into_iter()
+    .filter_map(|(found, name)| found.then_some(name))
+    .collect();
+
+    let sync = SyncExecutor::from_config(config).unwrap_or_else(|e| {
+        eprintln!(
+            "{} Ignoring custom sync adapters: {}",
+            style("⚠").yellow(),
+            e
+        );
+        SyncExecutor::new()
+    });
+    // Generic is always "detected" as the fallback, so it says nothing here
+    let mut tools: Vec<DetectedTool> = sync
+        .detect_all(&options.root)
+        .into_iter()
+        .filter(|d| d.detected && d.tool != crate::sync::Tool::Generic)
+        .map(|d| DetectedTool {
+            id: serde_json::to_value(d.tool)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default(),
+            name: d.tool.name().to_string(),
+            output_path: d.tool.output_path().to_string(),
+            reason: d.reason,
+            custom: false,
+        })
+        .collect();
+    tools.sort_by(|a, b| a.id.cmp(&b.id));
+    tools.extend(
+        sync.detect_custom(&options.root)
+            .into_iter()
+            .map(|adapter| DetectedTool {
+                id: adapter.name().to_string(),
+                name: adapter.name().to_string(),
+                output_path: adapter.output_path().to_string(),
+                reason: "Custom adapter detection matched".to_string(),
+                custom: true,
+            }),
+    );
+
+    ScanReport {
+        root: options.root.display().to_string(),
+        scan,
+        manifests,
+        tools,
+    }
+}
+
+/// Format a byte count as B, KB or MB
+fn format_bytes(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    let value = bytes as f64;
+    if value < KB {
+        format!("{} B", bytes)
+    } else if value < KB * KB {
+        format!("{:.1} KB", value / KB)
+    } else {
+        format!("{:.1} MB", value / (KB * KB))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_scan_report_skips_dependency_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        fs::write(root.join("src/a.ts"), "export const a = 1;\n").unwrap();
+        fs::write(root.join("src/b.py"), "b = 2\n").unwrap();
+        fs::write(
+            root.join("node_modules/pkg/index.js"),
+            "module.exports = 1;\n",
+        )
+        .unwrap();
+        fs::write(root.join("package.json"), "{}").unwrap();
+
+        let options = ScanOptions {
+            root: root.to_path_buf(),
+            json: true,
+        };
+        let report = build_report(&options, &Config::default());
+
+        let languages: Vec<_> = report
+            .scan
+            .languages
+            .iter()
+            .map(|l| (l.name, l.file_count, l.bytes))
+            .collect();
+        assert_eq!(languages, vec![("Python", 1, 6), ("TypeScript", 1, 20)]);
+        assert_eq!(report.scan.total_files, 3);
+        assert_eq!(report.scan.total_bytes, 28);
+        assert_eq!(report.manifests, vec!["package.json"]);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["languages"][0]["fileCount"], 1);
+        assert_eq!(json["totalBytes"], 28);
+        assert_eq!(json["hasPackageJson"], true);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MB");
+    }
+}
@@ -3,24 +3,49 @@
 //! @acp:domain cli
 //! @acp:layer handler
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use console::style;
 
+use crate::cache::{parse_version, Cache, RefReport, VersionReport};
+use crate::config::{Config, VersionScheme};
+use crate::error::AcpError;
+use crate::index::find_source_files;
+use crate::parse::{AnnotationProblem, Parser};
 use crate::schema;
 
 /// Options for the validate command
 #[derive(Debug, Clone)]
 pub struct ValidateOptions {
     /// File to validate
-    pub file: PathBuf,
+    pub file: Option<PathBuf>,
+    /// Check @acp:see and @acp:link references in a cache file
+    pub refs: bool,
+    /// Check @acp:version and @acp:since values in a cache file as semver
+    pub semver: bool,
+    /// Scan source files for unrecognized @acp: annotation names
+    pub annotations: bool,
 }
 
 /// Execute the validate command
-pub fn execute_validate(options: ValidateOptions) -> Result<()> {
-    let content = std::fs::read_to_string(&options.file)?;
-    let filename = options.file.to_string_lossy();
+pub fn execute_validate(options: ValidateOptions, config: &Config) -> Result<()> {
+    if let Some(file) = &options.file {
+        validate_file(file, options.refs)?;
+        if options.semver {
+            check_versions(file, config)?;
+        }
+    }
+    if options.annotations {
+        check_annotations(config)?;
+    }
+    Ok(())
+}
+
+/// Validate a JSON file against its schema
+fn validate_file(file: &Path, refs: bool) -> Result<()> {
+    let content = std::fs::read_to_string(file)?;
+    let filename = file.to_string_lossy();
 
     // Use detect_schema_type() for all 6 schema types
     if let Some(schema_type) = schema::detect_schema_type(&filename) {
@@ -89,5 +114,183 @@ pub fn execute_validate(options: ValidateOptions) -> Result<()> {
         }
     }
 
+    if refs {
+        check_refs(file)?;
+    }
+
     Ok(())
 }
+
+/// Report @acp:see targets that don't resolve and malformed @acp:link URLs
+fn check_refs(path: &Path) -> Result<()> {
+    let cache = Cache::from_json(path)?;
+    let report = RefReport::check(&cache);
+
+    if report.is_clean() {
+        println!(
+            "{} All {} references resolve",
+            style("✓").green(),
+            report.checked
+        );
+        return Ok(());
+    }
+
+    eprintln!(
+        "{} {} of {} references are broken:",
+        style("✗").red(),
+        report.broken.len(),
+        report.checked
+    );
+    for broken in &report.broken {
+        eprintln!(
+            "  {}:{} {} {} \"{}\": {}",
+            broken.file,
+            broken.line,
+            style(&broken.symbol).bold(),
+            broken.kind.as_str(),
+            broken.target,
+            style(&broken.reason).dim()
+        );
+    }
+    std::process::exit(1);
+}
+
+/// Report @acp:version/@acp:since values that aren't semver or are out of order
+fn check_versions(path: &Path, config: &Config) -> Result<()> {
+    if config.documentation.version_scheme != VersionScheme::Semver {
+        println!(
+            "{} documentation.versionScheme is not semver; skipping version checks",
+            style("•").dim()
+        );
+        return Ok(());
+    }
+    let project = config
+        .documentation
+        .project_version
+        .as_deref()
+        .map(parse_version)
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("documentation.projectVersion is not semver: {}", e))?;
+    let cache = Cache::from_json(path)?;
+    let report = VersionReport::check(&cache, project.as_ref());
+
+    if report.is_clean() {
+        println!(
+            "{} All {} versions are valid semver",
+            style("✓").green(),
+            report.checked
+        );
+        return Ok(());
+    }
+
+    eprintln!(
+        "{} {} of {} versions have problems:",
+        style("✗").red(),
+        report.problems.len(),
+        report.checked
+    );
+    for problem in &report.problems {
+        let location = match problem.line {
+            Some(line) => format!("{}:{}", problem.file, line),
+            None => problem.file.clone(),
+        };
+        let symbol = problem
+            .symbol
+            .as_ref()
+            .map(|s| format!(" {}", style(s).bold()))
+            .unwrap_or_default();
+        eprintln!(
+            "  {}{} {} \"{}\": {}",
+            location,
+            symbol,
+            problem.annotation,
+            problem.value,
+            style(&problem.reason).dim()
+        );
+    }
+    anyhow::bail!("{} version problem(s) found", report.problems.len())
+}
+
+/// Report @acp: annotation names no parser recognizes, with a likely fix,
+/// and annotations whose values are malformed
+fn check_annotations(config: &Config) -> Result<()> {
+    let parser = Parser::from_config(config);
+    let files = find_source_files(
+        &config.root,
+        &config.include,
+        &config.exclude,
+        config.respect_gitignore,
+    );
+
+    let mut unknown = Vec::new();
+    let mut malformed = Vec::new();
+    for file in &files {
+        let Ok(result) = parser.parse(file) else {
+            continue;
+        };
+        let path = Path::new(file)
+            .strip_prefix(&config.root)
+            .unwrap_or(Path::new(file))
+            .display()
+            .to_string();
+        malformed.extend(
+            result
+                .annotation_errors
+                .into_iter()
+                .map(|problem| AnnotationProblem {
+                    file: path.clone(),
+                    ..problem
+                }),
+        );
+        unknown.extend(
+            result
+                .unknown_annotations
+                .into_iter()
+                .map(|ann| (path.clone(), ann)),
+        );
+    }
+
+    if unknown.is_empty() && malformed.is_empty() {
+        println!(
+            "{} All annotations in {} files are recognized",
+            style("✓").green(),
+            files.len()
+        );
+        return Ok(());
+    }
+
+    malformed.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    if !malformed.is_empty() {
+        eprintln!(
+            "{} {} malformed annotation(s):",
+            style("✗").red(),
+            malformed.len()
+        );
+    }
+    for problem in malformed {
+        eprintln!("  {}", AcpError::from(problem));
+    }
+
+    unknown.sort_by(|a, b| (&a.0, a.1.line).cmp(&(&b.0, b.1.line)));
+    if !unknown.is_empty() {
+        eprintln!(
+            "{} {} unknown annotation(s):",
+            style("✗").red(),
+            unknown.len()
+        );
+    }
+    for (path, ann) in &unknown {
+        let hint = ann
+            .suggestion
+            .map(|s| format!(" (did you mean @acp:{}?)", s))
+            .unwrap_or_default();
+        eprintln!(
+            "  {}:{} {}{}",
+            path,
+            ann.line,
+            style(format!("@acp:{}", ann.name)).bold(),
+            style(hint).dim()
+        );
+    }
+    std::process::exit(1);
+}
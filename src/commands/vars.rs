@@ -19,6 +19,8 @@ pub struct VarsOptions {
     pub cache: PathBuf,
     /// Output vars file path
     pub output: PathBuf,
+    /// Drop hand-added vars from an existing output file instead of keeping them
+    pub fresh: bool,
 }
 
 /// Execute the vars command
@@ -28,7 +30,12 @@ pub fn execute_vars(options: VarsOptions) -> Result<()> {
     let cache_data = Cache::from_json(&options.cache)?;
     let config = Config::default();
     let indexer = Indexer::new(config)?;
-    let vars_file = indexer.generate_vars(&cache_data);
+    let mut vars_file = indexer.generate_vars(&cache_data);
+    let kept = if options.fresh {
+        0
+    } else {
+        vars_file.keep_manual_from(&options.output)?
+    };
 
     vars_file.write_json(&options.output)?;
     println!(
@@ -37,6 +44,9 @@ pub fn execute_vars(options: VarsOptions) -> Result<()> {
         options.output.display()
     );
     println!("  Variables: {}", vars_file.variables.len());
+    if kept > 0 {
+        println!("  Kept {} hand-added vars", kept);
+    }
 
     Ok(())
 }
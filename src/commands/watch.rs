@@ -2,24 +2,274 @@
 //! @acp:summary "Watch for changes and update cache"
 //! @acp:domain cli
 //! @acp:layer handler
+//!
+//! Re-indexes whenever source files change. With `--exec`, a shell command
+//! runs after each successful re-index with the changed files in
+//! `ACP_CHANGED_FILES` (one path per line). Changes are debounced before
+//! re-indexing, and the command never overlaps itself: changes indexed while
+//! it runs are batched into a single follow-up run.
 
-use std::path::PathBuf;
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+use std::time::Duration;
 
 use anyhow::Result;
+use console::style;
+use tokio::task::JoinHandle;
 
+use super::daemon::shutdown_signal;
+use super::index::report_index_problems;
 use crate::config::Config;
-use crate::watch::FileWatcher;
+use crate::index::Indexer;
+use crate::watch::ChangeStream;
 
 /// Options for the watch command
 #[derive(Debug, Clone)]
 pub struct WatchOptions {
     /// Root directory to watch
     pub root: PathBuf,
+    /// Shell command to run after each successful re-index
+    pub exec: Option<String>,
+    /// Quiet period after the last change before re-indexing
+    pub debounce: Duration,
 }
 
 /// Execute the watch command
-pub fn execute_watch(options: WatchOptions, config: Config) -> Result<()> {
-    let watcher = FileWatcher::new(config);
-    watcher.watch(&options.root)?;
+pub async fn execute_watch(options: WatchOptions, config: Config) -> Result<()> {
+    let root = options.root.clone();
+    let cache_path = config.cache_path();
+    let indexer = Indexer::new(config.clone())?;
+    rebuild(&indexer, &root, &cache_path).await?;
+
+    let mut changes = ChangeStream::spawn(config, root.clone(), options.debounce);
+
+    println!(
+        "{} Watching {} for changes{}",
+        style("✓").green(),
+        root.display(),
+        options
+            .exec
+            .as_ref()
+            .map(|cmd| format!(" (exec: {})", cmd))
+            .unwrap_or_default()
+    );
+
+    watch_loop(
+        &options,
+        &indexer,
+        &cache_path,
+        &mut changes,
+        shutdown_signal(),
+    )
+    .await;
+    changes.stop()?;
+    Ok(())
+}
+
+/// Re-index on each batch of changes and run the --exec command until `shutdown`
+async fn watch_loop(
+    options: &WatchOptions,
+    indexer: &Indexer,
+    cache_path: &Path,
+    changes: &mut ChangeStream,
+    shutdown: impl Future<Output = ()>,
+) {
+    let root = &options.root;
+    // Changes not yet indexed (after a failed re-index), and indexed
+    // changes the command hasn't seen yet
+    let mut pending = BTreeSet::new();
+    let mut unrun = BTreeSet::new();
+    let mut running: Option<JoinHandle<std::io::Result<ExitStatus>>> = None;
+
+    tokio::pin!(shutdown);
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            changed = changes.next_batch() => {
+                let Some(changed) = changed else {
+                    eprintln!("{} File watcher stopped", style("✗").red());
+                    break;
+                };
+                pending.extend(changed);
+                match rebuild(indexer, root, cache_path).await {
+                    Ok(()) => unrun.append(&mut pending),
+                    // Keep the batch; it is retried with the next change
+                    Err(e) => eprintln!("{} Re-index failed: {}", style("✗").red(), e),
+                }
+            }
+            status = async { running.as_mut().unwrap().await }, if running.is_some() => {
+                running = None;
+                report_exit(status);
+            }
+        }
+
+        if let Some(cmd) = &options.exec {
+            if running.is_none() && !unrun.is_empty() {
+                let changed: Vec<String> = std::mem::take(&mut unrun).into_iter().collect();
+                running = Some(spawn_exec(cmd, root, &changed));
+            }
+        }
+    }
+
+    println!("{} Shutting down...", style("→").cyan());
+    if let Some(handle) = running {
+        report_exit(handle.await);
+    }
+}
+
+/// Re-index and write the cache file
+async fn rebuild(indexer: &Indexer, root: &Path, cache_path: &Path) -> Result<()> {
+    let report = indexer.index_with_report(root).await?;
+    report_index_problems(&report, false);
+    let cache = report.cache;
+    if let Some(parent) = cache_path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    cache.write_json(cache_path)?;
+    println!(
+        "{} Cache rebuilt ({} files, {} symbols)",
+        style("→").cyan(),
+        cache.stats.files,
+        cache.stats.symbols
+    );
     Ok(())
 }
+
+/// @acp:summary "Run the --exec command through the shell in the background"
+fn spawn_exec(
+    cmd: &str,
+    root: &Path,
+    changed: &[String],
+) -> JoinHandle<std::io::Result<ExitStatus>> {
+    println!(
+        "{} Running: {} ({} changed file(s))",
+        style("→").cyan(),
+        cmd,
+        changed.len()
+    );
+    let mut command = if cfg!(windows) {
+        let mut command = tokio::process::Command::new("cmd");
+        command.arg("/C");
+        command
+    } else {
+        let mut command = tokio::process::Command::new("sh");
+        command.arg("-c");
+        command
+    };
+    command
+        .arg(cmd)
+        .current_dir(root)
+        .env("ACP_CHANGED_FILES", changed.join("\n"));
+    tokio::spawn(async move { command.status().await })
+}
+
+/// Log how the --exec command ended; failures never stop the watch
+fn report_exit(result: std::result::Result<std::io::Result<ExitStatus>, tokio::task::JoinError>) {
+    match result {
+        Ok(Ok(status)) if status.success() => {}
+        Ok(Ok(status)) => eprintln!("{} Command failed ({})", style("✗").red(), status),
+        Ok(Err(e)) => eprintln!("{} Failed to run command: {}", style("✗").red(), e),
+        Err(e) => eprintln!("{} Command task failed: {}", style("✗").red(), e),
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tokio::sync::oneshot;
+    use tokio::time::sleep;
+
+    /// Runs the loop over a one-file project, feeding it `events` as
+    /// (delay in ms before sending, changed files), then stops after `settle_ms`
+    async fn run_watch(
+        exec: &str,
+        debounce_ms: u64,
+        events: &[(u64, &[&str])],
+        settle_ms: u64,
+    ) -> String {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/app.ts"), "export function run() {}\n").unwrap();
+        let config = Config {
+            include: vec!["src/**/*.ts".to_string()],
+            ..Default::default()
+        };
+        let indexer = Indexer::new(config).unwrap();
+        let options = WatchOptions {
+            root: root.to_path_buf(),
+            exec: Some(exec.to_string()),
+            debounce: Duration::from_millis(debounce_ms),
+        };
+
+        let (change_tx, change_rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut changes = ChangeStream::from_channel(change_rx, options.debounce);
+        let (stop_tx, stop_rx) = oneshot::channel::<()>();
+        let driver = async move {
+            for (delay, files) in events {
+                sleep(Duration::from_millis(*delay)).await;
+                change_tx
+                    .send(files.iter().map(|f| f.to_string()).collect())
+                    .unwrap();
+            }
+            sleep(Duration::from_millis(settle_ms)).await;
+            stop_tx.send(()).unwrap();
+        };
+        let shutdown = async {
+            let _ = stop_rx.await;
+        };
+        let cache_path = root.join(".acp/acp.cache.json");
+        tokio::join!(
+            watch_loop(&options, &indexer, &cache_path, &mut changes, shutdown),
+            driver
+        );
+
+        std::fs::read_to_string(root.join("exec.log")).unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn test_burst_of_changes_runs_exec_once() {
+        let log = run_watch(
+            r#"echo "run $(echo $ACP_CHANGED_FILES)" >> exec.log"#,
+            200,
+            &[
+                (0, &["src/a.ts"]),
+                (20, &["src/b.ts"]),
+                (20, &["src/c.ts", "src/a.ts"]),
+                (20, &["src/d.ts"]),
+            ],
+            800,
+        )
+        .await;
+
+        assert_eq!(
+            log.lines().collect::<Vec<_>>(),
+            vec!["run src/a.ts src/b.ts src/c.ts src/d.ts"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exec_never_overlaps_itself() {
+        let log = run_watch(
+            r#"echo "start $(echo $ACP_CHANGED_FILES)" >> exec.log; sleep 0.5; echo end >> exec.log"#,
+            50,
+            &[
+                (0, &["src/a.ts"]),
+                // Both arrive while the first run is still sleeping
+                (300, &["src/b.ts"]),
+                (100, &["src/c.ts"]),
+            ],
+            1500,
+        )
+        .await;
+
+        assert_eq!(
+            log.lines().collect::<Vec<_>>(),
+            vec!["start src/a.ts", "end", "start src/b.ts src/c.ts", "end"]
+        );
+    }
+}
@@ -0,0 +1,199 @@
+//! @acp:module "Environment Overrides"
+//! @acp:summary "ACP_* environment variables layered between CLI flags and the config file"
+//! @acp:domain cli
+//! @acp:layer config
+//!
+//! Containerized CI can set these instead of passing flags to every command.
+//! Each setting resolves with the same precedence, highest first:
+//!
+//! 1. Explicit CLI flag
+//! 2. `ACP_*` environment variable
+//! 3. Value from `.acp.config.json`
+//! 4. Built-in default
+//!
+//! `main` applies layers 2 and 3 through [`Config::load_layered`], which
+//! loads the config file and then calls [`Config::apply_env`]; command flags
+//! are `Option`s that fall back to the resulting config.
+
+use std::path::PathBuf;
+
+use super::Config;
+use crate::error::{AcpError, Result};
+
+/// Config file path (replaces `.acp.config.json`)
+pub const ENV_CONFIG: &str = "ACP_CONFIG";
+/// Cache file path (replaces `.acp/acp.cache.json`)
+pub const ENV_CACHE_PATH: &str = "ACP_CACHE_PATH";
+/// Number of parallel workers
+pub const ENV_WORKERS: &str = "ACP_WORKERS";
+/// Project root directory
+pub const ENV_ROOT: &str = "ACP_ROOT";
+
+/// @acp:summary "Settings read from ACP_* environment variables"
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EnvOverrides {
+    pub config: Option<PathBuf>,
+    pub cache_path: Option<PathBuf>,
+    pub workers: Option<usize>,
+    pub root: Option<PathBuf>,
+}
+
+impl EnvOverrides {
+    /// @acp:summary "Read overrides from the process environment"
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// @acp:summary "Read overrides through a lookup function"
+    ///
+    /// Empty values count as unset. A non-numeric `ACP_WORKERS` is an error
+    /// rather than silently falling back.
+    pub fn from_lookup<F>(lookup: F) -> Result<Self>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let get = |name: &str| lookup(name).filter(|v| !v.trim().is_empty());
+
+        let workers = match get(ENV_WORKERS) {
+            Some(value) => Some(value.trim().parse::<usize>().map_err(|_| {
+                AcpError::Config(format!(
+                    "{} must be a whole number, got '{}'",
+                    ENV_WORKERS, value
+                ))
+            })?),
+            None => None,
+        };
+
+        Ok(Self {
+            config: get(ENV_CONFIG).map(PathBuf::from),
+            cache_path: get(ENV_CACHE_PATH).map(PathBuf::from),
+            workers,
+            root: get(ENV_ROOT).map(PathBuf::from),
+        })
+    }
+
+    /// @acp:summary "Config file path: CLI flag, then ACP_CONFIG, then the default"
+    pub fn config_path(&self, cli: Option<PathBuf>) -> PathBuf {
+        cli.or_else(|| self.config.clone())
+            .unwrap_or_else(|| PathBuf::from(".acp.config.json"))
+    }
+}
+
+impl Config {
+    /// @acp:summary "Load the config file chosen by CLI flag or ACP_CONFIG, then apply env"
+    ///
+    /// Returns the path that was looked up alongside the config; a missing
+    /// file gives the built-in defaults with environment overrides applied.
+    pub fn load_layered(
+        cli_config: Option<PathBuf>,
+        env: &EnvOverrides,
+    ) -> Result<(PathBuf, Self)> {
+        let path = env.config_path(cli_config);
+        let mut config = if path.exists() {
+            Config::load(&path)?
+        } else {
+            Config::default()
+        };
+        config.apply_env(env);
+        Ok((path, config))
+    }
+
+    /// @acp:summary "Layer environment overrides over values loaded from the file"
+    pub fn apply_env(&mut self, env: &EnvOverrides) {
+        if let Some(root) = &env.root {
+            self.root = root.clone();
+        }
+        if let Some(cache) = &env.cache_path {
+            self.output.get_or_insert_with(Default::default).cache = cache.clone();
+        }
+        if let Some(workers) = env.workers {
+            self.workers = Some(workers);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> Result<EnvOverrides> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        EnvOverrides::from_lookup(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_precedence_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, value: serde_json::Value| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, value.to_string()).unwrap();
+            path
+        };
+        let file = write(
+            "file.json",
+            serde_json::json!({
+                "root": "from-file",
+                "output": { "cache": "file/cache.json" },
+                "workers": 2
+            }),
+        );
+        let cli_file = write("cli.json", serde_json::json!({ "workers": 4 }));
+        let config_var = file.to_string_lossy().to_string();
+
+        // Built-in default when no file exists
+        let none = env(&[]).unwrap();
+        let (_, config) =
+            Config::load_layered(Some(dir.path().join("missing.json")), &none).unwrap();
+        assert_eq!(config.cache_path(), PathBuf::from(".acp/acp.cache.json"));
+        assert_eq!(config.workers, None);
+
+        // Config file beats the default
+        let (_, config) = Config::load_layered(Some(file.clone()), &none).unwrap();
+        assert_eq!(config.cache_path(), PathBuf::from("file/cache.json"));
+        assert_eq!(config.workers, Some(2));
+
+        // Environment beats the config file; unset variables leave it alone
+        let overrides = env(&[
+            (ENV_CONFIG, &config_var),
+            (ENV_CACHE_PATH, "env/cache.json"),
+            (ENV_WORKERS, "8"),
+        ])
+        .unwrap();
+        let (path, config) = Config::load_layered(None, &overrides).unwrap();
+        assert_eq!(path, file);
+        assert_eq!(config.cache_path(), PathBuf::from("env/cache.json"));
+        assert_eq!(config.workers, Some(8));
+        assert_eq!(config.root, PathBuf::from("from-file"));
+
+        // CLI flag beats the environment: --config wins over ACP_CONFIG
+        let overrides = env(&[(ENV_CONFIG, &config_var)]).unwrap();
+        let (path, config) = Config::load_layered(Some(cli_file.clone()), &overrides).unwrap();
+        assert_eq!(path, cli_file);
+        assert_eq!(config.workers, Some(4));
+        assert_eq!(config.cache_path(), PathBuf::from(".acp/acp.cache.json"));
+    }
+
+    #[test]
+    fn test_config_path_precedence() {
+        let none = env(&[]).unwrap();
+        assert_eq!(none.config_path(None), PathBuf::from(".acp.config.json"));
+
+        let set = env(&[(ENV_CONFIG, "ci/acp.json"), (ENV_ROOT, "")]).unwrap();
+        assert_eq!(set.root, None);
+        assert_eq!(set.config_path(None), PathBuf::from("ci/acp.json"));
+        assert_eq!(
+            set.config_path(Some(PathBuf::from("cli.json"))),
+            PathBuf::from("cli.json")
+        );
+    }
+
+    #[test]
+    fn test_invalid_workers_is_an_error() {
+        let err = env(&[(ENV_WORKERS, "lots")]).unwrap_err();
+        assert!(err.to_string().contains(ENV_WORKERS));
+    }
+}
@@ -4,10 +4,17 @@
 //! @acp:layer config
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 use crate::bridge::config as bridge_config;
+use crate::cache::Language;
+
+mod env;
+mod profiles;
+
+pub use env::{EnvOverrides, ENV_CACHE_PATH, ENV_CONFIG, ENV_ROOT, ENV_WORKERS};
+pub use profiles::PROFILE_NAMES;
 
 fn default_config_schema() -> String {
     "https://acp-protocol.dev/schemas/v1/config.schema.json".to_string()
@@ -33,7 +40,9 @@ pub struct Config {
     #[serde(default = "default_include")]
     pub include: Vec<String>,
 
-    /// File patterns to exclude (glob syntax)
+    /// File patterns to exclude (glob syntax); `acp init` adds the
+    /// [`language_excludes`] of the languages it finds. `.acpignore` files
+    /// are applied on top
     #[serde(default = "default_exclude")]
     pub exclude: Vec<String>,
 
@@ -47,7 +56,11 @@ pub struct Config {
 
     /// Domain patterns for automatic classification
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub domains: Option<HashMap<String, DomainPatternConfig>>,
+    pub domains: Option<DomainsConfig>,
+
+    /// File tags: label to the globs that apply it and what it implies
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tags: HashMap<String, TagConfig>,
 
     /// Call graph generation configuration
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -66,6 +79,15 @@ pub struct Config {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub output: Option<OutputConfig>,
 
+    /// Parallel workers for annotate (internal; default: one per CPU)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workers: Option<usize>,
+
+    /// Skip files matched by `.gitignore` during discovery; `exclude` still
+    /// applies on top (internal; default: true)
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub respect_gitignore: bool,
+
     /// RFC-0006: Documentation bridging configuration
     #[serde(default)]
     pub bridge: bridge_config::BridgeConfig,
@@ -77,12 +99,32 @@ pub struct Config {
     /// RFC-0002: Documentation references and style guides
     #[serde(default)]
     pub documentation: DocumentationConfig,
+
+    /// RFC-0004: Primer section selection tuning
+    #[serde(default, skip_serializing_if = "PrimerSettings::is_empty")]
+    pub primer: PrimerSettings,
+
+    /// Coverage badge color thresholds (`acp query coverage`)
+    #[serde(default, skip_serializing_if = "CoverageSettings::is_default")]
+    pub coverage: CoverageSettings,
+
+    /// AI tool sync settings, including user-defined tool adapters
+    #[serde(default, skip_serializing_if = "SyncSettings::is_empty")]
+    pub sync: SyncSettings,
+
+    /// Annotation parsing settings
+    #[serde(default, skip_serializing_if = "ParseSettings::is_empty")]
+    pub parse: ParseSettings,
 }
 
 fn is_default_root(p: &std::path::Path) -> bool {
     p == std::path::Path::new(".")
 }
 
+fn is_true(b: &bool) -> bool {
+    *b
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -93,13 +135,20 @@ impl Default for Config {
             error_handling: None,
             constraints: None,
             domains: None,
+            tags: HashMap::new(),
             call_graph: None,
             limits: None,
             root: default_root(),
             output: None,
+            workers: None,
+            respect_gitignore: true,
             bridge: bridge_config::BridgeConfig::default(),
             annotate: AnnotateConfig::default(),
             documentation: DocumentationConfig::default(),
+            primer: PrimerSettings::default(),
+            coverage: CoverageSettings::default(),
+            sync: SyncSettings::default(),
+            parse: ParseSettings::default(),
         }
     }
 }
@@ -123,6 +172,19 @@ impl Config {
         Self::load(".acp.config.json").unwrap_or_default()
     }
 
+    /// @acp:summary "Exclude the dependency and build directories of these languages"
+    ///
+    /// Patterns already listed are skipped, so the defaults keep their order.
+    pub fn add_language_excludes(&mut self, languages: impl IntoIterator<Item = Language>) {
+        for language in languages {
+            for pattern in language_excludes(language) {
+                if !self.exclude.iter().any(|p| p == pattern) {
+                    self.exclude.push(pattern.to_string());
+                }
+            }
+        }
+    }
+
     /// Get cache output path
     pub fn cache_path(&self) -> PathBuf {
         self.output
@@ -154,9 +216,46 @@ fn default_include() -> Vec<String> {
         "**/*.py".to_string(),
         "**/*.go".to_string(),
         "**/*.java".to_string(),
+        "**/*.rb".to_string(),
+        "**/*.swift".to_string(),
+        "**/*.kt".to_string(),
     ]
 }
 
+/// @acp:summary "Dependency, build and cache directories a language's tooling creates"
+pub fn language_excludes(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::Typescript | Language::Javascript => &[
+            "**/node_modules/**",
+            "**/dist/**",
+            "**/build/**",
+            "**/out/**",
+            "**/.next/**",       // Next.js
+            "**/.nuxt/**",       // Nuxt.js
+            "**/.output/**",     // Nitro/Nuxt 3
+            "**/.svelte-kit/**", // SvelteKit
+            "**/.vite/**",       // Vite
+            "**/.turbo/**",      // Turborepo
+        ],
+        Language::Rust => &["**/target/**"],
+        Language::Python => &[
+            "**/__pycache__/**",
+            "**/.pytest_cache/**",
+            "**/.mypy_cache/**",
+            "**/.venv/**",
+            "**/venv/**",
+            "**/.tox/**",
+            "**/*.egg-info/**",
+        ],
+        Language::Go | Language::Php => &["**/vendor/**"],
+        Language::Java | Language::Kotlin => &["**/target/**", "**/build/**", "**/.gradle/**"],
+        Language::Ruby => &["**/vendor/**", "**/.bundle/**"],
+        Language::Swift => &["**/.build/**", "**/DerivedData/**"],
+        Language::CSharp => &["**/obj/**"],
+        Language::Cpp | Language::C => &["**/build/**"],
+    }
+}
+
 fn default_exclude() -> Vec<String> {
     vec![
         // Package managers
@@ -232,6 +331,44 @@ pub struct ConstraintConfig {
     /// Violation log file path
     #[serde(default = "default_audit_file")]
     pub audit_file: String,
+
+    /// Directory-level lock rules: glob pattern to lock level
+    ///
+    /// Files inherit the most specific matching rule unless they carry
+    /// their own `@acp:lock` annotation.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub locks: HashMap<String, crate::constraints::LockLevel>,
+
+    /// Project-wide quality gate thresholds for `acp gate`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gates: Option<GatesConfig>,
+
+    /// Authors (name or email) allowed to modify frozen/restricted files,
+    /// checked by `acp check --policy`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub approvers: Vec<String>,
+}
+
+/// @acp:summary "Quality gate thresholds evaluated by `acp gate`"
+///
+/// Each gate is enabled by setting its threshold; omitted gates are skipped.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GatesConfig {
+    /// Minimum symbol annotation coverage percentage (0-100)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_coverage: Option<f64>,
+
+    /// Maximum number of hacks past their expiry date
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_expired_hacks: Option<usize>,
+
+    /// Maximum number of low-confidence generated annotations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_low_confidence: Option<usize>,
+
+    /// Fail if any frozen file changed on disk since the last index
+    #[serde(default)]
+    pub no_frozen_changes: bool,
 }
 
 fn default_audit_file() -> String {
@@ -281,11 +418,117 @@ pub enum Behavior {
     Aggressive,
 }
 
+/// @acp:summary "Domain classification settings"
+///
+/// Every key except `normalize` names a domain.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DomainsConfig {
+    /// How domain names are canonicalized during indexing
+    #[serde(default, skip_serializing_if = "DomainNormalize::is_none")]
+    pub normalize: DomainNormalize,
+    /// Domain name to the patterns that classify files into it
+    #[serde(flatten)]
+    pub patterns: HashMap<String, DomainPatternConfig>,
+}
+
+impl DomainsConfig {
+    /// @acp:summary "Config entry for a canonical domain name"
+    pub fn get(&self, canonical: &str) -> Option<&DomainPatternConfig> {
+        self.patterns
+            .iter()
+            .find(|(name, _)| self.normalize.apply(name) == canonical)
+            .map(|(_, domain)| domain)
+    }
+}
+
+impl From<HashMap<String, DomainPatternConfig>> for DomainsConfig {
+    fn from(patterns: HashMap<String, DomainPatternConfig>) -> Self {
+        Self {
+            patterns,
+            ..Default::default()
+        }
+    }
+}
+
+/// @acp:summary "Domain name canonicalization (`domains.normalize`)"
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DomainNormalize {
+    /// Keep names as written; `acp index` warns about names that would merge
+    #[default]
+    None,
+    /// Lowercase: `API` and `Api` become `api`
+    Lower,
+    /// Lowercase kebab-case: `UserAuth` and `user_auth` become `user-auth`
+    Kebab,
+}
+
+impl DomainNormalize {
+    fn is_none(&self) -> bool {
+        *self == Self::None
+    }
+
+    /// @acp:summary "Canonical form of a domain name"
+    pub fn apply(&self, name: &str) -> String {
+        match self {
+            Self::None => name.to_string(),
+            Self::Lower => name.trim().to_lowercase(),
+            Self::Kebab => {
+                let mut kebab = String::with_capacity(name.len());
+                let mut prev: Option<char> = None;
+                for c in name.trim().chars() {
+                    if c.is_alphanumeric() {
+                        // Split camelCase words
+                        if c.is_uppercase()
+                            && prev.is_some_and(|p| p.is_lowercase() || p.is_numeric())
+                        {
+                            kebab.push('-');
+                        }
+                        kebab.extend(c.to_lowercase());
+                    } else if !kebab.is_empty() && !kebab.ends_with('-') {
+                        kebab.push('-');
+                    }
+                    prev = Some(c);
+                }
+                kebab.trim_end_matches('-').to_string()
+            }
+        }
+    }
+}
+
 /// @acp:summary "Domain pattern configuration (schema-compliant)"
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DomainPatternConfig {
     /// Glob patterns for this domain
+    #[serde(default)]
     pub patterns: Vec<String>,
+    /// Human description, copied to the cache's domain entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// @acp:summary "Glob patterns that label files with a tag"
+///
+/// Tagged files record the label in `FileEntry.tags`. A tag can also pull
+/// otherwise-excluded files into the index, mark them `@acp:internal`, or
+/// lock them the way a `constraints.locks` rule would.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagConfig {
+    /// Glob patterns matched against project-relative paths
+    pub patterns: Vec<String>,
+
+    /// Index matching files even when `exclude` matches them
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub include: bool,
+
+    /// Treat matching files as `@acp:internal`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub internal: bool,
+
+    /// Lock level for matching files without their own `@acp:lock`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock: Option<crate::constraints::LockLevel>,
 }
 
 /// @acp:summary "Call graph generation configuration (schema-compliant)"
@@ -302,6 +545,11 @@ pub struct CallGraphConfig {
     /// Patterns to exclude from graph
     #[serde(default)]
     pub exclude_patterns: Vec<String>,
+
+    /// Record possible callees from string literals naming known functions
+    /// in `graph.heuristic_edges` (off by default)
+    #[serde(default)]
+    pub heuristic_edges: bool,
 }
 
 /// @acp:summary "Implementation limits (schema-compliant)"
@@ -324,6 +572,17 @@ pub struct LimitsConfig {
     pub max_cache_size_mb: usize,
 }
 
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_file_size_mb: default_max_file_size(),
+            max_files: default_max_files(),
+            max_annotations_per_file: default_max_annotations(),
+            max_cache_size_mb: default_max_cache_size(),
+        }
+    }
+}
+
 fn default_max_file_size() -> usize {
     10
 }
@@ -354,6 +613,10 @@ pub struct OutputConfig {
     /// Whether to also output SQLite database
     #[serde(default)]
     pub sqlite: bool,
+
+    /// Append a stats snapshot to `stats-history.jsonl` on every index
+    #[serde(default)]
+    pub history: bool,
 }
 
 impl Default for OutputConfig {
@@ -362,6 +625,7 @@ impl Default for OutputConfig {
             cache: default_cache_path(),
             vars: default_vars_path(),
             sqlite: false,
+            history: false,
         }
     }
 }
@@ -390,6 +654,127 @@ fn default_min_confidence() -> f64 {
     0.5
 }
 
+fn default_display_precision() -> usize {
+    2
+}
+
+/// @acp:summary "Primer selection settings (RFC-0004)"
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PrimerSettings {
+    /// Value multipliers keyed by section or category ID (e.g. `constraints`)
+    #[serde(default)]
+    pub weights: HashMap<String, f64>,
+}
+
+impl PrimerSettings {
+    /// True when nothing is configured
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+}
+
+/// @acp:summary "Annotation parsing settings"
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ParseSettings {
+    /// Alternative annotation names mapped to canonical ones
+    /// (`{"desc": "summary"}` reads `@acp:desc` as `@acp:summary`)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub aliases: HashMap<String, String>,
+}
+
+impl ParseSettings {
+    /// True when nothing is configured
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty()
+    }
+}
+
+/// @acp:summary "AI tool sync settings"
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncSettings {
+    /// Adapters for tools without built-in support
+    #[serde(default)]
+    pub custom: Vec<CustomAdapterConfig>,
+    /// Keep hand edits inside generated sections instead of overwriting them
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub preserve_edits: bool,
+}
+
+impl SyncSettings {
+    /// True when nothing is configured
+    pub fn is_empty(&self) -> bool {
+        self.custom.is_empty() && !self.preserve_edits
+    }
+}
+
+/// @acp:summary "A user-defined sync target rendered from a template"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomAdapterConfig {
+    /// Tool name shown in output and available as `{{name}}`
+    pub name: String,
+    /// File to write, relative to the project root
+    pub output_path: String,
+    /// Globs (relative to the project root) whose matches mean the tool is in use
+    #[serde(default)]
+    pub detect_globs: Vec<String>,
+    /// Start and end markers delimiting the generated section
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section_markers: Option<(String, String)>,
+    /// Content with `{{placeholder}}` substitution
+    pub template: String,
+}
+
+/// @acp:summary "Annotation coverage reporting settings"
+///
+/// Coverage below `yellow` is red, below `green` is yellow, and green
+/// otherwise. Both thresholds are percentages.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoverageSettings {
+    /// Lowest coverage shown as yellow
+    #[serde(default = "default_coverage_yellow")]
+    pub yellow: f64,
+    /// Lowest coverage shown as green
+    #[serde(default = "default_coverage_green")]
+    pub green: f64,
+}
+
+fn default_coverage_yellow() -> f64 {
+    50.0
+}
+
+fn default_coverage_green() -> f64 {
+    80.0
+}
+
+impl Default for CoverageSettings {
+    fn default() -> Self {
+        Self {
+            yellow: default_coverage_yellow(),
+            green: default_coverage_green(),
+        }
+    }
+}
+
+impl CoverageSettings {
+    /// True when both thresholds are the built-in defaults
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// @acp:summary "Badge color name for a coverage percentage"
+    pub fn color(&self, percent: f64) -> &'static str {
+        if percent >= self.green {
+            "green"
+        } else if percent >= self.yellow {
+            "yellow"
+        } else {
+            "red"
+        }
+    }
+}
+
 /// @acp:summary "Annotation generation configuration (RFC-0003)"
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AnnotateConfig {
@@ -400,6 +785,11 @@ pub struct AnnotateConfig {
     /// Default settings for annotation generation
     #[serde(default)]
     pub defaults: AnnotateDefaults,
+
+    /// `--check` coverage thresholds (percent) by file glob, e.g.
+    /// `{"**/*.rs": 90}`; files matching none use `--min-coverage`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub coverage: BTreeMap<String, f32>,
 }
 
 /// @acp:summary "Provenance tracking configuration"
@@ -417,9 +807,14 @@ pub struct AnnotateProvenanceConfig {
     #[serde(default = "default_review_threshold", rename = "reviewThreshold")]
     pub review_threshold: f64,
 
-    /// Minimum confidence required to emit an annotation
+    /// Minimum confidence required to emit an annotation; lower scores also
+    /// count as low confidence in provenance stats
     #[serde(default = "default_min_confidence", rename = "minConfidence")]
     pub min_confidence: f64,
+
+    /// Decimal places when printing confidence scores
+    #[serde(default = "default_display_precision", rename = "displayPrecision")]
+    pub display_precision: usize,
 }
 
 impl Default for AnnotateProvenanceConfig {
@@ -429,6 +824,31 @@ impl Default for AnnotateProvenanceConfig {
             include_confidence: true,
             review_threshold: 0.8,
             min_confidence: 0.5,
+            display_precision: 2,
+        }
+    }
+}
+
+/// @acp:summary "How a confidence score compares to the configured thresholds"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfidenceBand {
+    /// Below `minConfidence`
+    Low,
+    /// At least `minConfidence` but below `reviewThreshold`
+    Review,
+    /// At or above `reviewThreshold`
+    High,
+}
+
+impl AnnotateProvenanceConfig {
+    /// Classify a confidence score against `minConfidence` and `reviewThreshold`
+    pub fn band(&self, confidence: f64) -> ConfidenceBand {
+        if confidence < self.min_confidence {
+            ConfidenceBand::Low
+        } else if confidence < self.review_threshold {
+            ConfidenceBand::Review
+        } else {
+            ConfidenceBand::High
         }
     }
 }
@@ -467,6 +887,29 @@ pub struct DocumentationConfig {
     /// Reference validation settings
     #[serde(default)]
     pub validation: DocumentationValidation,
+
+    /// How @acp:version and @acp:since values are written
+    #[serde(default, rename = "versionScheme")]
+    pub version_scheme: VersionScheme,
+
+    /// Current project version; no @acp:since may be newer than it
+    #[serde(
+        default,
+        rename = "projectVersion",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub project_version: Option<String>,
+}
+
+/// @acp:summary "Versioning scheme checked by `acp validate --semver`"
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionScheme {
+    /// Semantic versions (`1.4.0`), checked and compared
+    #[default]
+    Semver,
+    /// Calendar versions (`2025.03`); not checked
+    Calver,
 }
 
 /// @acp:summary "Approved documentation source (RFC-0002)"
@@ -563,3 +1006,32 @@ impl Default for DocumentationValidation {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_language_excludes() {
+        let mut config = Config::default();
+        let defaults = config.exclude.clone();
+        assert!(!defaults.iter().any(|p| p == "**/.venv/**"));
+
+        config.add_language_excludes([Language::Python, Language::Rust]);
+        // Python's extra patterns follow the defaults; target/ is already there
+        assert_eq!(&config.exclude[..defaults.len()], &defaults[..]);
+        assert_eq!(
+            &config.exclude[defaults.len()..],
+            &[
+                "**/.mypy_cache/**",
+                "**/.venv/**",
+                "**/venv/**",
+                "**/.tox/**",
+                "**/*.egg-info/**"
+            ]
+        );
+        assert!(!config.exclude.iter().any(|p| p == "**/obj/**"));
+
+        assert_eq!(language_excludes(Language::Rust), &["**/target/**"]);
+    }
+}
@@ -0,0 +1,159 @@
+//! @acp:module "Config Profiles"
+//! @acp:summary "Named, stack-specific config presets for acp init --profile"
+//! @acp:domain cli
+//! @acp:layer config
+//!
+//! A profile is a JSON fragment merged over [`Config::default`]. Objects merge
+//! key by key; arrays and scalars in the fragment replace the default, so a
+//! profile's `include` is the complete include list for that stack.
+
+use serde_json::{json, Value};
+
+use super::Config;
+use crate::error::{AcpError, Result};
+
+/// Profile names accepted by `acp init --profile`
+pub const PROFILE_NAMES: &[&str] = &["rust", "node", "python", "go", "monorepo"];
+
+/// @acp:summary "The config fragment for a named profile"
+fn fragment(name: &str) -> Option<Value> {
+    let fragment = match name {
+        "rust" => json!({
+            "include": ["**/*.rs"],
+            "exclude": ["**/target/**", "**/.git/**", "**/vendor/**"],
+            "domains": {
+                "cli": { "patterns": ["src/main.rs", "src/bin/**", "src/commands/**"] },
+                "tests": { "patterns": ["tests/**", "benches/**", "examples/**"] }
+            },
+            "bridge": {
+                "enabled": true,
+                "jsdoc": { "enabled": false },
+                "python": { "enabled": false }
+            }
+        }),
+        "node" => json!({
+            "include": ["**/*.ts", "**/*.tsx", "**/*.js", "**/*.jsx", "**/*.mjs", "**/*.cjs"],
+            "exclude": [
+                "**/node_modules/**", "**/dist/**", "**/build/**", "**/coverage/**",
+                "**/.next/**", "**/.nuxt/**", "**/.svelte-kit/**", "**/.turbo/**",
+                "**/*.d.ts", "**/*.min.js", "**/.git/**"
+            ],
+            "domains": {
+                "api": { "patterns": ["src/api/**", "src/routes/**", "src/server/**"] },
+                "ui": { "patterns": ["src/components/**", "src/pages/**", "src/app/**"] },
+                "tests": { "patterns": ["**/*.test.*", "**/*.spec.*", "**/__tests__/**"] }
+            },
+            "bridge": {
+                "enabled": true,
+                "python": { "enabled": false },
+                "rust": { "enabled": false }
+            }
+        }),
+        "python" => json!({
+            "include": ["**/*.py"],
+            "exclude": [
+                "**/__pycache__/**", "**/.venv/**", "**/venv/**", "**/.tox/**",
+                "**/.pytest_cache/**", "**/.mypy_cache/**", "**/build/**",
+                "**/dist/**", "**/*.egg-info/**", "**/.git/**"
+            ],
+            "domains": {
+                "tests": { "patterns": ["tests/**", "**/test_*.py", "**/*_test.py"] }
+            },
+            "bridge": {
+                "enabled": true,
+                "jsdoc": { "enabled": false },
+                "rust": { "enabled": false }
+            }
+        }),
+        "go" => json!({
+            "include": ["**/*.go"],
+            "exclude": ["**/vendor/**", "**/testdata/**", "**/*.pb.go", "**/.git/**"],
+            "domains": {
+                "cli": { "patterns": ["cmd/**"] },
+                "internal": { "patterns": ["internal/**"] },
+                "tests": { "patterns": ["**/*_test.go"] }
+            },
+            "bridge": {
+                "enabled": true,
+                "markdown": { "enabled": true, "patterns": ["doc.md", "README.md"] }
+            }
+        }),
+        // Mixed stacks: keep the default multi-language include list
+        "monorepo" => json!({
+            "exclude": [
+                "**/node_modules/**", "**/dist/**", "**/build/**", "**/target/**",
+                "**/out/**", "**/coverage/**", "**/.turbo/**", "**/.nx/**",
+                "**/.next/**", "**/*.d.ts", "**/.git/**"
+            ],
+            "domains": {
+                "apps": { "patterns": ["apps/**"] },
+                "packages": { "patterns": ["packages/**", "libs/**", "crates/**"] },
+                "services": { "patterns": ["services/**"] }
+            },
+            "bridge": {
+                "enabled": true,
+                "markdown": { "enabled": true }
+            }
+        }),
+        _ => return None,
+    };
+    Some(fragment)
+}
+
+/// @acp:summary "Recursively merges `overlay` into `base`"
+fn merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                merge(base.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+impl Config {
+    /// @acp:summary "The default config with a named profile merged over it"
+    pub fn from_profile(name: &str) -> Result<Self> {
+        let fragment = fragment(name).ok_or_else(|| {
+            AcpError::Config(format!(
+                "Unknown profile '{}' (valid profiles: {})",
+                name,
+                PROFILE_NAMES.join(", ")
+            ))
+        })?;
+
+        let mut config = serde_json::to_value(Config::default())?;
+        merge(&mut config, fragment);
+        Ok(serde_json::from_value(config)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles_merge_over_default() {
+        for name in PROFILE_NAMES {
+            let config = Config::from_profile(name).unwrap();
+            assert!(config.bridge.enabled, "{}", name);
+            assert!(config.domains.is_some(), "{}", name);
+        }
+
+        let rust = Config::from_profile("rust").unwrap();
+        assert_eq!(rust.include, vec!["**/*.rs"]);
+        assert!(!rust.bridge.jsdoc.enabled);
+        assert!(rust.bridge.rust.enabled);
+        // Untouched defaults survive the merge
+        assert_eq!(
+            rust.bridge.rust.convert_sections,
+            Config::default().bridge.rust.convert_sections
+        );
+        assert!(rust.respect_gitignore);
+
+        let err = Config::from_profile("cobol").unwrap_err().to_string();
+        assert!(err.contains("cobol"));
+        assert!(err.contains("rust, node, python, go, monorepo"));
+    }
+}
@@ -6,6 +6,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::guardrails::FileGuardrails;
+use crate::cache::SymbolEntry;
 
 /// @acp:summary "Result of checking guardrails against proposed changes"
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +23,12 @@ pub struct Violation {
     pub rule: String,
     pub message: String,
     pub severity: Severity,
+    /// Symbol the rule applies to, when narrower than the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    /// The symbol's [start_line, end_line]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lines: Option<[usize; 2]>,
 }
 
 /// @acp:summary "A guardrail warning"
@@ -29,6 +36,12 @@ pub struct Violation {
 pub struct Warning {
     pub rule: String,
     pub message: String,
+    /// Symbol the rule applies to, when narrower than the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    /// The symbol's [start_line, end_line]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lines: Option<[usize; 2]>,
 }
 
 /// @acp:summary "A required action before changes can proceed"
@@ -75,6 +88,8 @@ impl GuardrailEnforcer {
                         .unwrap_or_default()
                 ),
                 severity: Severity::Error,
+                symbol: None,
+                lines: None,
             });
         }
 
@@ -83,6 +98,8 @@ impl GuardrailEnforcer {
             check.warnings.push(Warning {
                 rule: "ai-careful".to_string(),
                 message: format!("Extra caution required: {}", careful),
+                symbol: None,
+                lines: None,
             });
         }
 
@@ -105,6 +122,58 @@ impl GuardrailEnforcer {
         check
     }
 
+    /// @acp:summary "Report symbol-scoped @acp:lock constraints"
+    ///
+    /// Frozen and restricted symbols are violations (only frozen fails the
+    /// check); other non-normal levels are warnings. Results are in line order.
+    pub fn check_symbols<'a>(symbols: impl IntoIterator<Item = &'a SymbolEntry>) -> GuardrailCheck {
+        let mut check = GuardrailCheck {
+            passed: true,
+            violations: vec![],
+            warnings: vec![],
+            required_actions: vec![],
+        };
+
+        let mut locked: Vec<_> = symbols
+            .into_iter()
+            .filter_map(|s| Some((s, s.constraints.as_ref()?)))
+            .collect();
+        locked.sort_by_key(|(s, _)| (s.lines[0], s.name.clone()));
+
+        for (symbol, constraint) in locked {
+            let message = if constraint.directive.is_empty() {
+                constraint.level.clone()
+            } else {
+                format!("{}: {}", constraint.level, constraint.directive)
+            };
+            let severity = match constraint.level.as_str() {
+                "frozen" => Severity::Error,
+                "restricted" => Severity::Warning,
+                "normal" => continue,
+                _ => {
+                    check.warnings.push(Warning {
+                        rule: "symbol-lock".to_string(),
+                        message,
+                        symbol: Some(symbol.name.clone()),
+                        lines: Some(symbol.lines),
+                    });
+                    continue;
+                }
+            };
+
+            check.passed &= severity != Severity::Error;
+            check.violations.push(Violation {
+                rule: "symbol-lock".to_string(),
+                message,
+                severity,
+                symbol: Some(symbol.name.clone()),
+                lines: Some(symbol.lines),
+            });
+        }
+
+        check
+    }
+
     /// Check if proposed changes violate constraints
     pub fn check_changes(guardrails: &FileGuardrails, proposed_content: &str) -> GuardrailCheck {
         let mut check = Self::can_modify(guardrails);
@@ -117,6 +186,8 @@ impl GuardrailEnforcer {
                     rule: "forbids".to_string(),
                     message: format!("Contains forbidden pattern: {}", forbidden),
                     severity: Severity::Error,
+                    symbol: None,
+                    lines: None,
                 });
             }
         }
@@ -139,6 +210,7 @@ impl GuardrailEnforcer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cache::SymbolConstraint;
     use crate::constraints::guardrails::AIBehavior;
 
     #[test]
@@ -156,4 +228,41 @@ mod tests {
         assert!(!check.passed);
         assert_eq!(check.violations.len(), 1);
     }
+
+    #[test]
+    fn test_check_symbols() {
+        let symbol = |name: &str, line: usize, level: Option<&str>| SymbolEntry {
+            lines: [line, line + 5],
+            constraints: level.map(|level| SymbolConstraint {
+                level: level.to_string(),
+                directive: format!("{} is sensitive", name),
+                auto_generated: false,
+            }),
+            ..SymbolEntry::new(name, "src/pay.ts")
+        };
+        let symbols = [
+            symbol("refund", 30, Some("restricted")),
+            symbol("charge", 10, Some("frozen")),
+            symbol("format", 20, None),
+            symbol("audit", 40, Some("tests-required")),
+        ];
+
+        let check = GuardrailEnforcer::check_symbols(&symbols);
+        assert!(!check.passed);
+        let locked: Vec<_> = check
+            .violations
+            .iter()
+            .map(|v| (v.symbol.as_deref().unwrap(), v.lines.unwrap(), v.severity))
+            .collect();
+        assert_eq!(
+            locked,
+            vec![
+                ("charge", [10, 15], Severity::Error),
+                ("refund", [30, 35], Severity::Warning),
+            ]
+        );
+        assert_eq!(check.violations[0].message, "frozen: charge is sensitive");
+        assert_eq!(check.warnings.len(), 1);
+        assert_eq!(check.warnings[0].symbol.as_deref(), Some("audit"));
+    }
 }
@@ -0,0 +1,248 @@
+//! @acp:module "Quality Gates"
+//! @acp:summary "Project-wide quality gate evaluation for CI"
+//! @acp:domain cli
+//! @acp:layer service
+//!
+//! Evaluates the thresholds in `constraints.gates` against a cache and the
+//! working tree. Each configured gate produces one [`GateOutcome`].
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::cache::{normalize_path, Cache};
+use crate::config::GatesConfig;
+
+/// @acp:summary "Result of evaluating a single gate"
+#[derive(Debug, Clone, Serialize)]
+pub struct GateOutcome {
+    /// Gate identifier (matches the config key)
+    pub gate: String,
+    pub passed: bool,
+    /// Measured value
+    pub actual: f64,
+    /// Configured threshold
+    pub threshold: f64,
+    /// Human-readable result, including the margin on failure
+    pub message: String,
+    /// Files or targets responsible for a failure
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub offenders: Vec<String>,
+}
+
+/// @acp:summary "Result of evaluating all configured gates"
+#[derive(Debug, Clone, Serialize)]
+pub struct GateReport {
+    pub passed: bool,
+    pub gates: Vec<GateOutcome>,
+}
+
+/// @acp:summary "Evaluate every enabled gate against the cache"
+///
+/// `root` is the project root used to stat frozen files on disk.
+pub fn evaluate_gates(cache: &Cache, config: &GatesConfig, root: &Path) -> GateReport {
+    let mut gates = Vec::new();
+
+    if let Some(min) = config.min_coverage {
+        gates.push(check_min_coverage(cache, min));
+    }
+    if let Some(max) = config.max_expired_hacks {
+        gates.push(check_expired_hacks(cache, max));
+    }
+    if let Some(max) = config.max_low_confidence {
+        gates.push(check_low_confidence(cache, max));
+    }
+    if config.no_frozen_changes {
+        gates.push(check_frozen_changes(cache, root));
+    }
+
+    GateReport {
+        passed: gates.iter().all(|g| g.passed),
+        gates,
+    }
+}
+
+fn check_min_coverage(cache: &Cache, min: f64) -> GateOutcome {
+    let actual = cache.stats.annotation_coverage;
+    let passed = actual >= min;
+    let message = if passed {
+        format!("Coverage {:.1}% meets minimum {:.1}%", actual, min)
+    } else {
+        format!(
+            "Coverage {:.1}% is {:.1} points below minimum {:.1}%",
+            actual,
+            min - actual,
+            min
+        )
+    };
+    GateOutcome {
+        gate: "min_coverage".to_string(),
+        passed,
+        actual,
+        threshold: min,
+        message,
+        offenders: vec![],
+    }
+}
+
+fn check_expired_hacks(cache: &Cache, max: usize) -> GateOutcome {
+    let expired: Vec<String> = cache
+        .constraints
+        .as_ref()
+        .map(|c| {
+            c.get_expired_hacks()
+                .into_iter()
+                .map(|h| match h.line {
+                    Some(line) => format!("{}:{}", h.file, line),
+                    None => h.file.clone(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    count_gate("max_expired_hacks", "expired hacks", expired, max)
+}
+
+fn check_low_confidence(cache: &Cache, max: usize) -> GateOutcome {
+    let low: Vec<String> = cache
+        .provenance
+        .low_confidence
+        .iter()
+        .map(|e| format!("{} [{}]", e.target, e.annotation))
+        .collect();
+    count_gate("max_low_confidence", "low-confidence annotations", low, max)
+}
+
+fn check_frozen_changes(cache: &Cache, root: &Path) -> GateOutcome {
+    let mut modified = Vec::new();
+
+    if let Some(ref constraints) = cache.constraints {
+        for path in constraints.get_frozen_files() {
+            let normalized = normalize_path(path);
+            let indexed_at = cache
+                .source_files
+                .iter()
+                .find(|(k, _)| normalize_path(k) == normalized)
+                .map(|(_, v)| *v);
+
+            let on_disk: Option<DateTime<Utc>> = std::fs::metadata(root.join(&normalized))
+                .and_then(|m| m.modified())
+                .ok()
+                .map(Into::into);
+
+            match (indexed_at, on_disk) {
+                (Some(indexed), Some(current)) if current > indexed => modified.push(normalized),
+                // Deleted since indexing also counts as a change
+                (Some(_), None) => modified.push(normalized),
+                _ => {}
+            }
+        }
+    }
+
+    modified.sort();
+    count_gate("no_frozen_changes", "frozen files modified", modified, 0)
+}
+
+/// Build an outcome for "at most `max` items" style gates
+fn count_gate(gate: &str, noun: &str, mut offenders: Vec<String>, max: usize) -> GateOutcome {
+    let actual = offenders.len();
+    let passed = actual <= max;
+    let message = if passed {
+        format!("{} {} (max {})", actual, noun, max)
+    } else {
+        format!("{} {}, {} over max {}", actual, noun, actual - max, max)
+    };
+    if passed {
+        offenders.clear();
+    }
+    GateOutcome {
+        gate: gate.to_string(),
+        passed,
+        actual: actual as f64,
+        threshold: max as f64,
+        message,
+        offenders,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{LowConfidenceEntry, Stats};
+    use crate::constraints::{ConstraintIndex, HackMarker, HackType};
+
+    fn hack(file: &str, expired: bool) -> HackMarker {
+        let offset = chrono::Duration::days(if expired { -1 } else { 1 });
+        HackMarker {
+            id: file.to_string(),
+            hack_type: HackType::Hack,
+            file: file.to_string(),
+            line: Some(10),
+            created_at: Utc::now(),
+            author: None,
+            reason: "test".to_string(),
+            ticket: None,
+            expires: Some(Utc::now() + offset),
+            original_code: None,
+            revert_instructions: None,
+        }
+    }
+
+    #[test]
+    fn test_disabled_gates_are_skipped() {
+        let cache = Cache::new("test", ".");
+        let report = evaluate_gates(&cache, &GatesConfig::default(), Path::new("."));
+        assert!(report.passed);
+        assert!(report.gates.is_empty());
+    }
+
+    #[test]
+    fn test_coverage_gate_reports_margin() {
+        let mut cache = Cache::new("test", ".");
+        cache.stats = Stats {
+            annotation_coverage: 62.5,
+            ..Default::default()
+        };
+        let config = GatesConfig {
+            min_coverage: Some(80.0),
+            ..Default::default()
+        };
+
+        let report = evaluate_gates(&cache, &config, Path::new("."));
+        assert!(!report.passed);
+        assert_eq!(report.gates[0].gate, "min_coverage");
+        assert!(report.gates[0].message.contains("17.5 points below"));
+    }
+
+    #[test]
+    fn test_count_gates() {
+        let mut cache = Cache::new("test", ".");
+        cache.constraints = Some(ConstraintIndex {
+            hacks: vec![hack("a.rs", true), hack("b.rs", true), hack("c.rs", false)],
+            ..Default::default()
+        });
+        cache.provenance.low_confidence.push(LowConfidenceEntry {
+            target: "a.rs:foo".to_string(),
+            annotation: "@acp:summary".to_string(),
+            confidence: 0.3,
+            value: "Foo".to_string(),
+        });
+        let config = GatesConfig {
+            max_expired_hacks: Some(1),
+            max_low_confidence: Some(1),
+            ..Default::default()
+        };
+
+        let report = evaluate_gates(&cache, &config, Path::new("."));
+        assert!(!report.passed);
+
+        let hacks = &report.gates[0];
+        assert!(!hacks.passed);
+        assert_eq!(hacks.actual, 2.0);
+        assert_eq!(hacks.offenders, vec!["a.rs:10", "b.rs:10"]);
+
+        let low = &report.gates[1];
+        assert!(low.passed);
+        assert!(low.offenders.is_empty());
+    }
+}
@@ -12,14 +12,17 @@
 //! - Quality gates
 
 mod enforcer;
+mod gates;
 mod guardrails;
+mod policy;
+mod sarif;
 mod types;
 
 pub use types::{
     Approach, BehaviorModifier, ConstraintIndex, Constraints, DebugAttempt, DebugResult,
-    DebugSession, DebugStatus, DeprecationAction, DeprecationInfo, HackMarker, HackType, LockLevel,
-    ModifyPermission, MutationConstraint, PerformanceBudget, Priority, QualityGate, Reference,
-    StyleConstraint,
+    DebugSession, DebugStatus, DeprecationAction, DeprecationInfo, DirectoryLock, EffectiveLock,
+    HackMarker, HackType, LockLevel, LockSource, ModifyPermission, MutationConstraint,
+    PerformanceBudget, Priority, QualityGate, Reference, StyleConstraint,
 };
 
 pub use guardrails::{
@@ -31,3 +34,12 @@ pub use guardrails::{
 pub use enforcer::{
     GuardrailCheck, GuardrailEnforcer, RequiredAction, Severity, Violation, Warning,
 };
+
+pub use gates::{evaluate_gates, GateOutcome, GateReport};
+
+pub use policy::{check_lock_policy, LockViolation, PolicyReport};
+
+pub use sarif::{
+    build_findings_sarif_report, build_sarif_report, SarifFinding, SarifLevel, SarifLog,
+    SarifResult,
+};
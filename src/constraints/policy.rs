@@ -0,0 +1,300 @@
+//! @acp:module "Lock Policy"
+//! @acp:summary "Detects edits to frozen/restricted files made after the lock was committed"
+//! @acp:domain cli
+//! @acp:layer service
+//!
+//! A lock only protects a file if later edits are reviewed. The commit that
+//! introduced a file's `@acp:lock` is found by walking the file's history
+//! back to the oldest commit of the run in which the lock has been present;
+//! every later commit touching the file must come from an approved author.
+//! Blame is not used: it names whoever last touched the lock line, so
+//! re-editing that line would let anyone approve themselves. The lock's
+//! own author and the file's `@acp:owner` are always approved, alongside
+//! `constraints.approvers` from the config.
+
+use std::path::Path;
+use std::sync::LazyLock;
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+
+use super::types::LockLevel;
+use crate::cache::Cache;
+use crate::error::Result;
+use crate::git::{FileHistory, GitRepository, HistoryEntry};
+
+static LOCK_LINE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"@acp:lock\s+(frozen|restricted)\b").expect("Invalid lock regex"));
+
+/// @acp:summary "A commit that modified a locked file without approval"
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockViolation {
+    pub file: String,
+    pub level: LockLevel,
+    /// 1-indexed line of the `@acp:lock` annotation
+    pub lock_line: usize,
+    /// Commit that introduced the lock
+    pub lock_commit: String,
+    pub lock_author: String,
+    /// Offending commit
+    pub commit: String,
+    pub author: String,
+    pub author_email: String,
+    pub timestamp: DateTime<Utc>,
+    pub message: String,
+}
+
+/// @acp:summary "Result of checking lock policy across a cache"
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PolicyReport {
+    /// Number of frozen/restricted files examined
+    pub checked: usize,
+    /// Violations, sorted by file then newest commit first
+    pub violations: Vec<LockViolation>,
+    /// Locked files that couldn't be checked, with the reason
+    pub skipped: Vec<(String, String)>,
+}
+
+impl PolicyReport {
+    /// @acp:summary "True when no locked file has unapproved edits"
+    pub fn is_clean(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// @acp:summary "Check frozen/restricted files for unapproved post-lock commits"
+///
+/// `root` is the project root the cache's paths are relative to. Only
+/// locked files listed in `files` (cache paths) are checked.
+/// `approvers` match an author's name or email, case-insensitively.
+pub fn check_lock_policy(
+    repo: &GitRepository,
+    root: &Path,
+    cache: &Cache,
+    files: &[String],
+    approvers: &[String],
+) -> Result<PolicyReport> {
+    let mut report = PolicyReport::default();
+    let Some(constraints) = &cache.constraints else {
+        return Ok(report);
+    };
+
+    let mut locked: Vec<(&String, LockLevel)> = constraints
+        .by_file
+        .iter()
+        .filter(|(path, _)| files.contains(path))
+        .filter_map(|(path, c)| {
+            let level = c.mutation.as_ref()?.level;
+            matches!(level, LockLevel::Frozen | LockLevel::Restricted).then_some((path, level))
+        })
+        .collect();
+    locked.sort_by(|a, b| a.0.cmp(b.0));
+
+    for (file, level) in locked {
+        report.checked += 1;
+        let path = root.join(file);
+
+        let content = match repo.file_at_head(&path)? {
+            Some(content) => content,
+            None => {
+                report.skipped.push((file.clone(), "not committed".into()));
+                continue;
+            }
+        };
+        let Some(lock_line) = content
+            .lines()
+            .position(|line| LOCK_LINE.is_match(line))
+            .map(|i| i + 1)
+        else {
+            report
+                .skipped
+                .push((file.clone(), "lock not committed".into()));
+            continue;
+        };
+
+        let Some(lock) = lock_introduced(repo, &path)? else {
+            report
+                .skipped
+                .push((file.clone(), "lock has no history".into()));
+            continue;
+        };
+
+        let owner = cache.get_file(file).and_then(|f| f.owner.as_deref());
+        let approved = |entry: &HistoryEntry| {
+            let is = |who: &str| {
+                who.eq_ignore_ascii_case(&entry.author)
+                    || who.eq_ignore_ascii_case(&entry.author_email)
+            };
+            is(&lock.author)
+                || is(&lock.author_email)
+                || owner.is_some_and(is)
+                || approvers.iter().any(|a| is(a))
+        };
+
+        let history = FileHistory::since(repo, &path, &lock.commit)?;
+        for entry in history.entries().iter().filter(|e| !approved(e)) {
+            report.violations.push(LockViolation {
+                file: file.clone(),
+                level,
+                lock_line,
+                lock_commit: lock.commit.clone(),
+                lock_author: lock.author.clone(),
+                commit: entry.commit.clone(),
+                author: entry.author.clone(),
+                author_email: entry.author_email.clone(),
+                timestamp: entry.timestamp,
+                message: entry.message.clone(),
+            });
+        }
+    }
+
+    Ok(report)
+}
+
+/// @acp:summary "Find the commit that introduced a file's current lock"
+///
+/// Walks the file's history newest first while each version still carries
+/// an `@acp:lock frozen|restricted` line; the oldest such version is where
+/// the lock came in. Later edits to the lock line itself don't move it.
+fn lock_introduced(repo: &GitRepository, path: &Path) -> Result<Option<HistoryEntry>> {
+    let history = FileHistory::for_file(repo, path, 0)?;
+    let mut introduced = None;
+    for entry in history.entries() {
+        let locked = repo
+            .file_at_commit(&entry.commit, path)?
+            .is_some_and(|content| content.lines().any(|line| LOCK_LINE.is_match(line)));
+        if !locked {
+            break;
+        }
+        introduced = Some(entry.clone());
+    }
+    Ok(introduced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheBuilder;
+    use crate::constraints::{ConstraintIndex, Constraints, MutationConstraint};
+    use git2::{Repository, Signature};
+
+    fn commit(repo: &Repository, who: &str, file: &str, content: &str, message: &str) {
+        let root = repo.workdir().unwrap();
+        std::fs::write(root.join(file), content).unwrap();
+
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(file)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+
+        let sig = Signature::now(who, &format!("{}@example.com", who)).unwrap();
+        let parent = repo.head().ok().map(|h| h.peel_to_commit().unwrap());
+        let parents: Vec<_> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap();
+    }
+
+    fn frozen_cache(file: &str) -> Cache {
+        let mut index = ConstraintIndex::default();
+        index.by_file.insert(
+            file.to_string(),
+            Constraints {
+                mutation: Some(MutationConstraint::from_level(LockLevel::Frozen)),
+                ..Default::default()
+            },
+        );
+        let mut cache = CacheBuilder::new("test", ".").build();
+        cache.constraints = Some(index);
+        cache
+    }
+
+    #[test]
+    fn test_unapproved_edit_after_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+
+        commit(&repo, "bob", "core.ts", "export const x = 1;\n", "initial");
+        commit(
+            &repo,
+            "alice",
+            "core.ts",
+            "// @acp:lock frozen\nexport const x = 1;\n",
+            "lock core",
+        );
+        commit(
+            &repo,
+            "alice",
+            "core.ts",
+            "// @acp:lock frozen\nexport const x = 2;\n",
+            "lock author edit",
+        );
+        commit(
+            &repo,
+            "mallory",
+            "core.ts",
+            "// @acp:lock frozen\nexport const x = 3;\n",
+            "sneaky edit",
+        );
+
+        let git = GitRepository::open(dir.path()).unwrap();
+        let root = git.root().unwrap().to_path_buf();
+        let cache = frozen_cache("core.ts");
+
+        let files = vec!["core.ts".to_string()];
+        let report = check_lock_policy(&git, &root, &cache, &files, &[]).unwrap();
+        assert_eq!(report.checked, 1);
+        assert_eq!(report.violations.len(), 1);
+        let violation = &report.violations[0];
+        assert_eq!(violation.author, "mallory");
+        assert_eq!(violation.lock_author, "alice");
+        assert_eq!(violation.lock_line, 1);
+        assert_eq!(violation.message, "sneaky edit");
+
+        // Approvers match by email, case-insensitively
+        let approvers = vec!["MALLORY@example.com".to_string()];
+        let report = check_lock_policy(&git, &root, &cache, &files, &approvers).unwrap();
+        assert!(report.is_clean());
+
+        // Files outside the requested set aren't checked
+        let report = check_lock_policy(&git, &root, &cache, &[], &[]).unwrap();
+        assert_eq!(report.checked, 0);
+    }
+
+    #[test]
+    fn test_editing_lock_line_does_not_self_approve() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+
+        commit(
+            &repo,
+            "alice",
+            "core.ts",
+            "// @acp:lock frozen\nexport const x = 1;\n",
+            "lock core",
+        );
+        commit(
+            &repo,
+            "mallory",
+            "core.ts",
+            "// @acp:lock frozen - do not touch\nexport const x = 2;\n",
+            "reword lock",
+        );
+        commit(
+            &repo,
+            "mallory",
+            "core.ts",
+            "// @acp:lock frozen - do not touch\nexport const x = 3;\n",
+            "follow-up edit",
+        );
+
+        let git = GitRepository::open(dir.path()).unwrap();
+        let root = git.root().unwrap().to_path_buf();
+        let cache = frozen_cache("core.ts");
+        let files = vec!["core.ts".to_string()];
+
+        let report = check_lock_policy(&git, &root, &cache, &files, &[]).unwrap();
+        assert_eq!(report.violations.len(), 2);
+        assert!(report.violations.iter().all(|v| v.lock_author == "alice"));
+        assert!(report.violations.iter().all(|v| v.author == "mallory"));
+    }
+}
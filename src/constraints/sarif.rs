@@ -0,0 +1,579 @@
+//! @acp:module "SARIF Report"
+//! @acp:summary "Serializes guardrail findings as SARIF 2.1.0 for code scanning"
+//! @acp:domain cli
+//! @acp:layer service
+//!
+//! GitHub code scanning ingests SARIF directly, so `acp check --format sarif`
+//! turns locked files, expired hacks and missing tests into PR annotations,
+//! and `acp check --types --format sarif` does the same for @acp:param drift.
+//! Rule ids are fixed strings and fingerprints avoid line numbers where
+//! possible, so the same finding dedupes across runs even as code moves.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::enforcer::{GuardrailEnforcer, Severity};
+use super::guardrails::GuardrailParser;
+use super::types::{LockLevel, LockSource};
+use crate::cache::{normalize_path, Cache};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const FINGERPRINT_KEY: &str = "acpFinding/v1";
+
+/// @acp:summary "Top-level SARIF log"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub version: String,
+    pub runs: Vec<SarifRun>,
+}
+
+/// @acp:summary "A single analysis run"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifTool {
+    pub driver: SarifDriver,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifDriver {
+    pub name: String,
+    pub version: String,
+    pub information_uri: String,
+    pub rules: Vec<SarifRule>,
+}
+
+/// @acp:summary "Rule metadata referenced by results"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRule {
+    pub id: String,
+    pub name: String,
+    pub short_description: SarifMessage,
+    pub default_configuration: SarifRuleConfiguration,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifRuleConfiguration {
+    pub level: SarifLevel,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifMessage {
+    pub text: String,
+}
+
+/// @acp:summary "A finding at a location"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule_index: Option<usize>,
+    pub level: SarifLevel,
+    pub message: SarifMessage,
+    pub locations: Vec<SarifLocation>,
+    pub partial_fingerprints: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    pub physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    pub artifact_location: SarifArtifactLocation,
+    pub region: SarifRegion,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifArtifactLocation {
+    pub uri: String,
+    pub uri_base_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+    pub start_line: usize,
+}
+
+/// @acp:summary "SARIF result level"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SarifLevel {
+    Error,
+    Warning,
+    Note,
+}
+
+impl From<Severity> for SarifLevel {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Error => SarifLevel::Error,
+            Severity::Warning => SarifLevel::Warning,
+            Severity::Info => SarifLevel::Note,
+        }
+    }
+}
+
+/// Rule catalogue: (id, name, description, default level)
+const RULES: &[(&str, &str, &str, SarifLevel)] = &[
+    (
+        "acp/lock/frozen",
+        "FrozenFile",
+        "File is frozen and must not be modified",
+        SarifLevel::Error,
+    ),
+    (
+        "acp/lock/restricted",
+        "RestrictedFile",
+        "File is restricted; changes need explicit permission",
+        SarifLevel::Warning,
+    ),
+    (
+        "acp/lock/approval-required",
+        "ApprovalRequiredFile",
+        "Changes to this file require approval",
+        SarifLevel::Note,
+    ),
+    (
+        "acp/hack/expired",
+        "ExpiredHack",
+        "Temporary hack is past its expiry date",
+        SarifLevel::Warning,
+    ),
+    (
+        "acp/tests/missing",
+        "MissingTests",
+        "File is tests-required but no test file was found",
+        SarifLevel::Warning,
+    ),
+    (
+        "acp/guardrail/ai-readonly",
+        "AiReadonly",
+        "File is marked AI-readonly",
+        SarifLevel::Error,
+    ),
+    (
+        "acp/param/missing",
+        "UndocumentedParam",
+        "Parameter has no @acp:param annotation",
+        SarifLevel::Error,
+    ),
+    (
+        "acp/param/extra",
+        "UnknownParam",
+        "@acp:param names a parameter the signature does not have",
+        SarifLevel::Error,
+    ),
+    (
+        "acp/param/reordered",
+        "ReorderedParams",
+        "@acp:param annotations are out of signature order",
+        SarifLevel::Error,
+    ),
+    (
+        "acp/param/type",
+        "ParamTypeMismatch",
+        "Annotated parameter type differs from the declared type",
+        SarifLevel::Note,
+    ),
+];
+
+/// @acp:summary "Build a SARIF report for the given files"
+///
+/// `files` are cache paths; pass every cached file to report on the whole
+/// project. `root` is used to read file contents for inline guardrails.
+pub fn build_sarif_report(cache: &Cache, files: &[String], root: &Path) -> SarifLog {
+    let mut results = Vec::new();
+    let parser = GuardrailParser::new();
+
+    let mut files: Vec<&String> = files.iter().collect();
+    files.sort();
+    files.dedup();
+
+    for path in files {
+        let uri = normalize_path(path);
+
+        if let Some(constraints) = &cache.constraints {
+            if let Some(lock) = constraints.effective_lock(path) {
+                let rule = match lock.level {
+                    LockLevel::Frozen => Some("acp/lock/frozen"),
+                    LockLevel::Restricted => Some("acp/lock/restricted"),
+                    LockLevel::ApprovalRequired => Some("acp/lock/approval-required"),
+                    _ => None,
+                };
+                if let Some(rule) = rule {
+                    let origin = match &lock.source {
+                        LockSource::Explicit => String::new(),
+                        LockSource::Inherited { pattern } => {
+                            format!(" (inherited from {})", pattern)
+                        }
+                    };
+                    results.push(make_result(
+                        rule,
+                        None,
+                        format!("{} is locked as {:?}{}", uri, lock.level, origin),
+                        &uri,
+                        1,
+                        "",
+                    ));
+                }
+
+                if lock.level == LockLevel::TestsRequired && !has_test_file(cache, &uri) {
+                    results.push(make_result(
+                        "acp/tests/missing",
+                        None,
+                        format!("{} requires tests but no matching test file exists", uri),
+                        &uri,
+                        1,
+                        "",
+                    ));
+                }
+            }
+
+            for hack in constraints
+                .hacks
+                .iter()
+                .filter(|h| h.is_expired() && normalize_path(&h.file) == uri)
+            {
+                let expires = hack
+                    .expires
+                    .map(|e| e.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default();
+                results.push(make_result(
+                    "acp/hack/expired",
+                    None,
+                    format!("Hack expired on {}: {}", expires, hack.reason),
+                    &uri,
+                    hack.line.unwrap_or(1),
+                    // Reasons repeat ("temporary fix"); the id is unique per hack
+                    &hack.id,
+                ));
+            }
+        }
+
+        if let Ok(content) = std::fs::read_to_string(root.join(&uri)) {
+            let check = GuardrailEnforcer::can_modify(&parser.parse(&content));
+            for violation in check.violations {
+                let rule = format!("acp/guardrail/{}", violation.rule);
+                results.push(make_result(
+                    &rule,
+                    Some(violation.severity.into()),
+                    violation.message,
+                    &uri,
+                    1,
+                    "",
+                ));
+            }
+        }
+    }
+
+    sarif_log(results)
+}
+
+/// @acp:summary "A finding from another checker, as plain data for SARIF output"
+#[derive(Debug, Clone)]
+pub struct SarifFinding {
+    /// One of the rule ids in the report's rule list, e.g. `acp/param/extra`
+    pub rule_id: &'static str,
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+    /// Stable text identifying the finding, for the fingerprint
+    pub key: String,
+}
+
+/// @acp:summary "Build a SARIF report from findings such as `acp check --types`"
+pub fn build_findings_sarif_report(findings: &[SarifFinding]) -> SarifLog {
+    let results = findings
+        .iter()
+        .map(|finding| {
+            make_result(
+                finding.rule_id,
+                None,
+                finding.message.clone(),
+                &normalize_path(&finding.file),
+                finding.line,
+                &finding.key,
+            )
+        })
+        .collect();
+    sarif_log(results)
+}
+
+/// Wrap results in a single-run log listing every rule
+fn sarif_log(results: Vec<SarifResult>) -> SarifLog {
+    SarifLog {
+        schema: SARIF_SCHEMA.to_string(),
+        version: SARIF_VERSION.to_string(),
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: "acp".to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    information_uri: "https://acp-protocol.dev".to_string(),
+                    rules: RULES
+                        .iter()
+                        .map(|(id, name, description, level)| SarifRule {
+                            id: id.to_string(),
+                            name: name.to_string(),
+                            short_description: SarifMessage {
+                                text: description.to_string(),
+                            },
+                            default_configuration: SarifRuleConfiguration { level: *level },
+                        })
+                        .collect(),
+                },
+            },
+            results,
+        }],
+    }
+}
+
+/// Build one result; `key` distinguishes multiple findings of a rule in a file
+fn make_result(
+    rule_id: &str,
+    level: Option<SarifLevel>,
+    message: String,
+    uri: &str,
+    line: usize,
+    key: &str,
+) -> SarifResult {
+    let rule_index = RULES.iter().position(|(id, ..)| *id == rule_id);
+    let default_level = rule_index
+        .map(|i| RULES[i].3)
+        .unwrap_or(SarifLevel::Warning);
+
+    let mut partial_fingerprints = BTreeMap::new();
+    partial_fingerprints.insert(
+        FINGERPRINT_KEY.to_string(),
+        fingerprint(&[rule_id, uri, key]),
+    );
+
+    SarifResult {
+        rule_id: rule_id.to_string(),
+        rule_index,
+        level: level.unwrap_or(default_level),
+        message: SarifMessage { text: message },
+        locations: vec![SarifLocation {
+            physical_location: SarifPhysicalLocation {
+                artifact_location: SarifArtifactLocation {
+                    uri: uri.to_string(),
+                    uri_base_id: "%SRCROOT%".to_string(),
+                },
+                region: SarifRegion {
+                    start_line: line.max(1),
+                },
+            },
+        }],
+        partial_fingerprints,
+    }
+}
+
+/// Stable fingerprint independent of line numbers
+fn fingerprint(parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part.as_bytes());
+        hasher.update([0u8]);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Check whether the cache contains a test file for `path`
+///
+/// Matches common conventions: `foo.test.ts`, `foo.spec.ts`, `foo_test.go`,
+/// `test_foo.py` and `FooTest.java`.
+fn has_test_file(cache: &Cache, path: &str) -> bool {
+    let stem = match Path::new(path).file_stem().and_then(|s| s.to_str()) {
+        Some(s) => s,
+        None => return false,
+    };
+    let candidates = [
+        format!("{}.test", stem),
+        format!("{}.spec", stem),
+        format!("{}_test", stem),
+        format!("test_{}", stem),
+        format!("{}Test", stem),
+    ];
+
+    cache.files.keys().any(|file| {
+        Path::new(file)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .map(|s| candidates.iter().any(|c| c == s))
+            .unwrap_or(false)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::{ConstraintIndex, DirectoryLock, HackMarker, HackType};
+    use chrono::Utc;
+
+    fn cache_with(index: ConstraintIndex, files: &[&str]) -> Cache {
+        let mut cache = Cache::new("test", ".");
+        for file in files {
+            cache.files.insert(
+                file.to_string(),
+                serde_json::from_value(serde_json::json!({
+                    "path": file,
+                    "lines": 10,
+                    "language": "typescript",
+                }))
+                .unwrap(),
+            );
+        }
+        cache.constraints = Some(index);
+        cache
+    }
+
+    fn expired_hack(file: &str, line: usize) -> HackMarker {
+        HackMarker {
+            id: format!("{}:{}", file, line),
+            hack_type: HackType::Workaround,
+            file: file.to_string(),
+            line: Some(line),
+            created_at: Utc::now(),
+            author: None,
+            reason: "temporary fix".to_string(),
+            ticket: None,
+            expires: Some(Utc::now() - chrono::Duration::days(1)),
+            original_code: None,
+            revert_instructions: None,
+        }
+    }
+
+    #[test]
+    fn test_sarif_report_maps_rules() {
+        let index = ConstraintIndex {
+            directory_locks: vec![
+                DirectoryLock {
+                    pattern: "src/generated/**".to_string(),
+                    level: LockLevel::Frozen,
+                },
+                DirectoryLock {
+                    pattern: "src/core/**".to_string(),
+                    level: LockLevel::TestsRequired,
+                },
+            ],
+            hacks: vec![expired_hack("src/app.ts", 7)],
+            ..Default::default()
+        };
+        let files = ["src/generated/api.ts", "src/core/math.ts", "src/app.ts"];
+        let cache = cache_with(index, &files);
+        let paths: Vec<String> = files.iter().map(|f| f.to_string()).collect();
+
+        let log = build_sarif_report(&cache, &paths, Path::new("/nonexistent"));
+        let results = &log.runs[0].results;
+        let rules: Vec<&str> = results.iter().map(|r| r.rule_id.as_str()).collect();
+        assert_eq!(
+            rules,
+            vec!["acp/hack/expired", "acp/tests/missing", "acp/lock/frozen"]
+        );
+
+        let hack = &results[0];
+        assert_eq!(hack.locations[0].physical_location.region.start_line, 7);
+        assert_eq!(hack.level, SarifLevel::Warning);
+        assert_eq!(
+            log.runs[0].tool.driver.rules[hack.rule_index.unwrap()].id,
+            "acp/hack/expired"
+        );
+        assert_eq!(results[2].level, SarifLevel::Error);
+    }
+
+    #[test]
+    fn test_sarif_tests_present_suppresses_finding() {
+        let index = ConstraintIndex {
+            directory_locks: vec![DirectoryLock {
+                pattern: "src/**".to_string(),
+                level: LockLevel::TestsRequired,
+            }],
+            ..Default::default()
+        };
+        let cache = cache_with(index, &["src/math.ts", "src/math.test.ts"]);
+        let log = build_sarif_report(&cache, &["src/math.ts".to_string()], Path::new("."));
+        assert!(log.runs[0].results.is_empty());
+    }
+
+    #[test]
+    fn test_sarif_hacks_with_same_reason_stay_distinct() {
+        let index = ConstraintIndex {
+            hacks: vec![
+                expired_hack("src/app.ts", 7),
+                expired_hack("src/app.ts", 30),
+            ],
+            ..Default::default()
+        };
+        let cache = cache_with(index, &["src/app.ts"]);
+        let log = build_sarif_report(&cache, &["src/app.ts".to_string()], Path::new("."));
+        let results = &log.runs[0].results;
+        assert_eq!(results.len(), 2);
+        assert_ne!(
+            results[0].partial_fingerprints,
+            results[1].partial_fingerprints
+        );
+    }
+
+    #[test]
+    fn test_sarif_fingerprint_ignores_line() {
+        let a = make_result("acp/hack/expired", None, "a".into(), "src/a.ts", 3, "why");
+        let b = make_result("acp/hack/expired", None, "b".into(), "src/a.ts", 40, "why");
+        let c = make_result("acp/hack/expired", None, "a".into(), "src/b.ts", 3, "why");
+        assert_eq!(a.partial_fingerprints, b.partial_fingerprints);
+        assert_ne!(a.partial_fingerprints, c.partial_fingerprints);
+    }
+
+    #[test]
+    fn test_param_sarif_levels() {
+        let finding = |rule_id, message: &str| SarifFinding {
+            rule_id,
+            file: "./src/auth.ts".to_string(),
+            line: 4,
+            message: message.to_string(),
+            key: format!("login:{}", message),
+        };
+        let log = build_findings_sarif_report(&[
+            finding("acp/param/extra", "@acp:param user is not a parameter"),
+            finding("acp/param/type", "name: annotated number, declared string"),
+        ]);
+        let results = &log.runs[0].results;
+        assert_eq!(results[0].rule_id, "acp/param/extra");
+        assert_eq!(results[0].level, SarifLevel::Error);
+        assert_eq!(results[1].level, SarifLevel::Note);
+        let location = &results[0].locations[0].physical_location;
+        assert_eq!(location.artifact_location.uri, "src/auth.ts");
+        assert_eq!(location.region.start_line, 4);
+    }
+
+    #[test]
+    fn test_sarif_serializes_schema_fields() {
+        let cache = cache_with(ConstraintIndex::default(), &[]);
+        let value = serde_json::to_value(build_sarif_report(&cache, &[], Path::new("."))).unwrap();
+        assert_eq!(value["version"], "2.1.0");
+        assert_eq!(value["$schema"], SARIF_SCHEMA);
+        assert_eq!(value["runs"][0]["tool"]["driver"]["name"], "acp");
+        assert!(
+            value["runs"][0]["tool"]["driver"]["rules"][0]["defaultConfiguration"]["level"]
+                .is_string()
+        );
+    }
+}
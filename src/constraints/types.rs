@@ -7,6 +7,9 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::cache::serialize_sorted;
+use crate::globs::MATCH_OPTIONS;
+
 /// @acp:summary "Complete constraint set for a scope (RFC-001 compliant)"
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Constraints {
@@ -200,8 +203,28 @@ pub struct MutationConstraint {
     pub forbidden_operations: Option<Vec<String>>,
 }
 
+impl MutationConstraint {
+    /// Build a constraint carrying only a lock level and its implied requirements
+    pub fn from_level(level: LockLevel) -> Self {
+        Self {
+            level,
+            reason: None,
+            contact: None,
+            requires_approval: matches!(level, LockLevel::ApprovalRequired),
+            requires_tests: matches!(level, LockLevel::TestsRequired),
+            requires_docs: matches!(level, LockLevel::DocsRequired),
+            max_lines_changed: None,
+            allowed_operations: None,
+            forbidden_operations: None,
+        }
+    }
+}
+
 /// @acp:summary "Lock level for code modification"
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+/// Ordered from most to least restrictive, with `Experimental` last.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
 #[serde(rename_all = "kebab-case")]
 pub enum LockLevel {
     /// Cannot be modified under any circumstances
@@ -566,7 +589,7 @@ pub enum DebugResult {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ConstraintIndex {
     /// Constraints by file path
-    #[serde(default)]
+    #[serde(default, serialize_with = "serialize_sorted")]
     pub by_file: HashMap<String, Constraints>,
 
     /// Active hack markers
@@ -578,17 +601,132 @@ pub struct ConstraintIndex {
     pub debug_sessions: Vec<DebugSession>,
 
     /// Files by lock level
-    #[serde(default)]
+    #[serde(default, serialize_with = "serialize_sorted")]
     pub by_lock_level: HashMap<String, Vec<String>>,
+
+    /// Directory-level lock rules from config (`constraints.locks`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub directory_locks: Vec<DirectoryLock>,
+}
+
+/// @acp:summary "Lock level applied to every file matching a glob"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectoryLock {
+    /// Glob pattern relative to the project root (e.g. `src/generated/**`)
+    pub pattern: String,
+    pub level: LockLevel,
+}
+
+impl DirectoryLock {
+    /// Check whether a file path matches this rule
+    pub fn matches(&self, path: &str) -> bool {
+        glob::Pattern::new(&self.pattern)
+            .map(|p| p.matches_with(&crate::cache::normalize_path(path), MATCH_OPTIONS))
+            .unwrap_or(false)
+    }
+
+    /// Specificity used to pick between overlapping rules
+    ///
+    /// Counts literal (non-wildcard) characters, so `src/generated/api/**`
+    /// beats `src/generated/**`, which beats `**/*.ts`.
+    fn specificity(&self) -> usize {
+        let mut count = 0;
+        let mut in_class = false;
+        for c in self.pattern.chars() {
+            match c {
+                '[' => in_class = true,
+                ']' => in_class = false,
+                '*' | '?' => {}
+                _ if !in_class => count += 1,
+                _ => {}
+            }
+        }
+        count
+    }
+}
+
+/// @acp:summary "Where a file's effective lock level came from"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum LockSource {
+    /// File-level `@acp:lock` annotation
+    Explicit,
+    /// Inherited from a directory rule in config
+    Inherited { pattern: String },
+}
+
+/// @acp:summary "Resolved lock level for a file"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EffectiveLock {
+    pub level: LockLevel,
+    pub source: LockSource,
 }
 
 impl ConstraintIndex {
     /// Get effective constraints for a file (with inheritance)
+    ///
+    /// Files without their own mutation constraint pick up the lock level of
+    /// the most specific matching directory rule.
     pub fn get_effective(&self, file: &str, project_defaults: &Constraints) -> Constraints {
-        let file_constraints = self.by_file.get(file).cloned().unwrap_or_default();
+        let mut file_constraints = self.by_file.get(file).cloned().unwrap_or_default();
+        if file_constraints.mutation.is_none() {
+            if let Some(rule) = self.directory_lock_for(file) {
+                file_constraints.mutation = Some(MutationConstraint::from_level(rule.level));
+            }
+        }
         project_defaults.merge(&file_constraints)
     }
 
+    /// Resolve the effective lock level for a file
+    ///
+    /// A file-level `@acp:lock` always wins; otherwise the most specific
+    /// matching directory rule applies. Returns `None` if neither exists.
+    pub fn effective_lock(&self, file: &str) -> Option<EffectiveLock> {
+        let explicit = self
+            .by_file
+            .get(file)
+            .or_else(|| {
+                let normalized = crate::cache::normalize_path(file);
+                self.by_file
+                    .iter()
+                    .find(|(k, _)| crate::cache::normalize_path(k) == normalized)
+                    .map(|(_, v)| v)
+            })
+            .and_then(|c| c.mutation.as_ref());
+
+        if let Some(mutation) = explicit {
+            return Some(EffectiveLock {
+                level: mutation.level,
+                source: LockSource::Explicit,
+            });
+        }
+
+        self.directory_lock_for(file).map(|rule| EffectiveLock {
+            level: rule.level,
+            source: LockSource::Inherited {
+                pattern: rule.pattern.clone(),
+            },
+        })
+    }
+
+    /// Find the most specific directory rule matching a file
+    ///
+    /// Ties on specificity go to the longer pattern, then to the rule listed first.
+    pub fn directory_lock_for(&self, file: &str) -> Option<&DirectoryLock> {
+        self.directory_locks
+            .iter()
+            .filter(|rule| rule.matches(file))
+            .fold(None, |best: Option<&DirectoryLock>, rule| match best {
+                Some(b)
+                    if (b.specificity(), b.pattern.len())
+                        >= (rule.specificity(), rule.pattern.len()) =>
+                {
+                    Some(b)
+                }
+                _ => Some(rule),
+            })
+    }
+
     /// Get all expired hacks
     pub fn get_expired_hacks(&self) -> Vec<&HackMarker> {
         self.hacks.iter().filter(|h| h.is_expired()).collect()
@@ -700,4 +838,81 @@ mod tests {
         session.resolve("Fixed by changing Y");
         assert_eq!(session.status, DebugStatus::Resolved);
     }
+
+    fn index_with_locks(locks: &[(&str, LockLevel)]) -> ConstraintIndex {
+        ConstraintIndex {
+            directory_locks: locks
+                .iter()
+                .map(|(pattern, level)| DirectoryLock {
+                    pattern: pattern.to_string(),
+                    level: *level,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_directory_lock_most_specific_wins() {
+        let index = index_with_locks(&[
+            ("src/**", LockLevel::ReviewRequired),
+            ("src/generated/**", LockLevel::Frozen),
+            ("src/generated/api/**", LockLevel::Restricted),
+        ]);
+
+        let lock = index.effective_lock("src/generated/models.ts").unwrap();
+        assert_eq!(lock.level, LockLevel::Frozen);
+        assert_eq!(
+            lock.source,
+            LockSource::Inherited {
+                pattern: "src/generated/**".to_string()
+            }
+        );
+
+        let lock = index
+            .effective_lock("./src/generated/api/client.ts")
+            .unwrap();
+        assert_eq!(lock.level, LockLevel::Restricted);
+
+        let lock = index.effective_lock("src/app.ts").unwrap();
+        assert_eq!(lock.level, LockLevel::ReviewRequired);
+
+        assert!(index.effective_lock("lib/app.ts").is_none());
+    }
+
+    #[test]
+    fn test_directory_lock_overlap_independent_of_order() {
+        let index = index_with_locks(&[
+            ("src/generated/**", LockLevel::Frozen),
+            ("**/*.ts", LockLevel::TestsRequired),
+        ]);
+        let lock = index.effective_lock("src/generated/models.ts").unwrap();
+        assert_eq!(lock.level, LockLevel::Frozen);
+
+        let reversed = index_with_locks(&[
+            ("**/*.ts", LockLevel::TestsRequired),
+            ("src/generated/**", LockLevel::Frozen),
+        ]);
+        let lock = reversed.effective_lock("src/generated/models.ts").unwrap();
+        assert_eq!(lock.level, LockLevel::Frozen);
+    }
+
+    #[test]
+    fn test_explicit_lock_overrides_directory_rule() {
+        let mut index = index_with_locks(&[("src/generated/**", LockLevel::Frozen)]);
+        index.by_file.insert(
+            "src/generated/editable.ts".to_string(),
+            Constraints {
+                mutation: Some(MutationConstraint::from_level(LockLevel::Normal)),
+                ..Default::default()
+            },
+        );
+
+        let lock = index.effective_lock("src/generated/editable.ts").unwrap();
+        assert_eq!(lock.level, LockLevel::Normal);
+        assert_eq!(lock.source, LockSource::Explicit);
+
+        let effective = index.get_effective("src/generated/other.ts", &Constraints::default());
+        assert_eq!(effective.mutation.unwrap().level, LockLevel::Frozen);
+    }
 }
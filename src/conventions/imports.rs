@@ -0,0 +1,377 @@
+//! @acp:module "Import Conventions"
+//! @acp:summary "RFC-0015: Module system and import path style detection"
+//! @acp:domain cli
+//! @acp:layer analysis
+//!
+//! # Import Convention Detection Algorithm
+//!
+//! Works on imports already extracted by the AST parser, so detection stays
+//! O(imports) and within the <10ms/1000-files budget:
+//!
+//! 1. **Tally** `import` vs `require` in JS/TS files
+//! 2. **Classify** specifiers as relative, alias or absolute (packages are ignored)
+//! 3. **Count** index files whose statements are mostly re-exports (barrels)
+//! 4. **Tally** Python `from x import y` vs `import x`
+//! 5. **Choose** the majority per dimension (>70% wins, otherwise `mixed`)
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::ast::{Import, ImportKind};
+use crate::cache::{ImportConventions, ModuleSystem, PathStyle, PythonImportStyle};
+
+use super::CONFIDENCE_THRESHOLD;
+
+/// Index file names treated as potential barrels
+const INDEX_FILES: &[&str] = &[
+    "index.ts",
+    "index.tsx",
+    "index.js",
+    "index.jsx",
+    "index.mjs",
+];
+
+/// Directory names known to the project, used to tell project imports from packages
+#[derive(Debug, Default, Clone)]
+pub struct ProjectDirs {
+    /// First path segment of every file (e.g. `src`, `lib`)
+    top_level: HashSet<String>,
+    /// Every directory name at any depth (e.g. `components`, `utils`)
+    all: HashSet<String>,
+}
+
+impl ProjectDirs {
+    /// Collect directory names from project file paths
+    pub fn from_files(files: &[String]) -> Self {
+        let mut dirs = Self::default();
+        for file in files {
+            let file = file.strip_prefix("./").unwrap_or(file);
+            let mut segments: Vec<&str> = file.split('/').collect();
+            segments.pop();
+            if let Some(first) = segments.first() {
+                dirs.top_level.insert(first.to_string());
+            }
+            dirs.all.extend(segments.iter().map(|s| s.to_string()));
+        }
+        dirs
+    }
+}
+
+/// @acp:summary "Raw import counts accumulated across files"
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ImportTally {
+    /// ESM `import`/`export ... from` statements
+    pub esm: usize,
+    /// CommonJS `require()` calls
+    pub commonjs: usize,
+    /// `./x` and `../x` specifiers
+    pub relative: usize,
+    /// Path aliases such as `@/x`, `~/x` or `@components/x`
+    pub alias: usize,
+    /// Rooted specifiers such as `/x` or `src/x`
+    pub absolute: usize,
+    /// JS/TS index files seen
+    pub index_files: usize,
+    /// Index files dominated by re-exports
+    pub barrel_files: usize,
+    /// Python `from x import y`
+    pub python_from: usize,
+    /// Python `import x`
+    pub python_import: usize,
+}
+
+impl ImportTally {
+    /// Add one file's imports to the tally
+    pub fn add_file(&mut self, path: &str, language: &str, imports: &[Import], dirs: &ProjectDirs) {
+        match language {
+            "typescript" | "javascript" => self.add_js_file(path, imports, dirs),
+            "python" => self.add_python_file(imports, dirs),
+            _ => {}
+        }
+    }
+
+    fn add_js_file(&mut self, path: &str, imports: &[Import], dirs: &ProjectDirs) {
+        for import in imports {
+            match import.kind {
+                ImportKind::Require => self.commonjs += 1,
+                _ => self.esm += 1,
+            }
+            self.add_path_style(classify_specifier(&import.source, dirs));
+        }
+
+        let is_index = Path::new(path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| INDEX_FILES.contains(&n))
+            .unwrap_or(false);
+        if is_index {
+            self.index_files += 1;
+            let reexports = imports
+                .iter()
+                .filter(|i| i.kind == ImportKind::ReExport)
+                .count();
+            if reexports > 0 && reexports * 2 >= imports.len() {
+                self.barrel_files += 1;
+            }
+        }
+    }
+
+    fn add_python_file(&mut self, imports: &[Import], dirs: &ProjectDirs) {
+        for import in imports {
+            match import.kind {
+                ImportKind::From => self.python_from += 1,
+                _ => self.python_import += 1,
+            }
+            self.add_path_style(classify_specifier(&import.source, dirs));
+        }
+    }
+
+    fn add_path_style(&mut self, style: Option<PathStyle>) {
+        match style {
+            Some(PathStyle::Relative) => self.relative += 1,
+            Some(PathStyle::Alias) => self.alias += 1,
+            Some(PathStyle::Absolute) => self.absolute += 1,
+            _ => {}
+        }
+    }
+
+    /// Turn the tally into conventions, or `None` if nothing was counted
+    pub fn to_conventions(&self) -> Option<ImportConventions> {
+        let module_system = majority(
+            &[
+                (ModuleSystem::Esm, self.esm),
+                (ModuleSystem::Commonjs, self.commonjs),
+            ],
+            ModuleSystem::Mixed,
+        );
+        let path_style = majority(
+            &[
+                (PathStyle::Relative, self.relative),
+                (PathStyle::Alias, self.alias),
+                (PathStyle::Absolute, self.absolute),
+            ],
+            PathStyle::Mixed,
+        );
+        let python_style = majority(
+            &[
+                (PythonImportStyle::From, self.python_from),
+                (PythonImportStyle::Import, self.python_import),
+            ],
+            PythonImportStyle::Mixed,
+        );
+
+        if module_system.is_none() && path_style.is_none() && python_style.is_none() {
+            return None;
+        }
+
+        Some(ImportConventions {
+            module_system: module_system.map(|(m, _)| m),
+            path_style: path_style.map(|(p, _)| p),
+            index_exports: self.index_files > 0 && self.barrel_files * 2 > self.index_files,
+            module_system_confidence: module_system.map(|(_, c)| c),
+            path_style_confidence: path_style.map(|(_, c)| c),
+            python_style: python_style.map(|(p, _)| p),
+            python_style_confidence: python_style.map(|(_, c)| c),
+        })
+    }
+}
+
+/// @acp:summary "Tally imports for every file and derive conventions"
+///
+/// `file_imports` maps file paths to imports extracted by the AST parser.
+pub fn detect_import_conventions(
+    files: &[String],
+    file_languages: &HashMap<String, String>,
+    file_imports: &HashMap<String, Vec<Import>>,
+) -> Option<ImportConventions> {
+    let dirs = ProjectDirs::from_files(files);
+    let mut tally = ImportTally::default();
+
+    for file in files {
+        let (Some(language), Some(imports)) = (file_languages.get(file), file_imports.get(file))
+        else {
+            continue;
+        };
+        tally.add_file(file, language, imports, &dirs);
+    }
+
+    tally.to_conventions()
+}
+
+/// Classify an import specifier; returns `None` for third-party packages
+pub fn classify_specifier(spec: &str, dirs: &ProjectDirs) -> Option<PathStyle> {
+    // `./x`, `../x` and Python's `.x`/`..x`
+    if spec.starts_with('.') {
+        return Some(PathStyle::Relative);
+    }
+    if spec.starts_with("@/") || spec.starts_with("~/") || spec.starts_with('#') {
+        return Some(PathStyle::Alias);
+    }
+    if spec.starts_with('/') {
+        return Some(PathStyle::Absolute);
+    }
+
+    // Dotted Python modules and slash paths share the same first-segment check
+    let first = spec.split(['/', '.']).next().unwrap_or(spec);
+    if let Some(name) = first.strip_prefix('@') {
+        // `@components/x` is an alias when it names a project directory;
+        // otherwise it's a scoped package like `@scope/pkg`
+        return dirs.all.contains(name).then_some(PathStyle::Alias);
+    }
+    dirs.top_level
+        .contains(first)
+        .then_some(PathStyle::Absolute)
+}
+
+/// Pick the dominant option and its share of the total
+///
+/// Returns `mixed` with the leader's share when no option reaches
+/// [`CONFIDENCE_THRESHOLD`], and `None` when every count is zero.
+pub fn majority<T: Copy>(counts: &[(T, usize)], mixed: T) -> Option<(T, f64)> {
+    let total: usize = counts.iter().map(|(_, n)| n).sum();
+    if total == 0 {
+        return None;
+    }
+    let (leader, count) = counts.iter().max_by_key(|(_, n)| *n)?;
+    let share = *count as f64 / total as f64;
+    if share >= CONFIDENCE_THRESHOLD {
+        Some((*leader, share))
+    } else {
+        Some((mixed, share))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import(source: &str, kind: ImportKind) -> Import {
+        Import {
+            source: source.to_string(),
+            names: Vec::new(),
+            is_default: false,
+            is_namespace: false,
+            line: 1,
+            kind,
+        }
+    }
+
+    fn dirs() -> ProjectDirs {
+        ProjectDirs::from_files(&[
+            "src/components/Button.tsx".to_string(),
+            "src/utils/format.ts".to_string(),
+            "app/models.py".to_string(),
+        ])
+    }
+
+    #[test]
+    fn test_classify_specifier() {
+        let dirs = dirs();
+        assert_eq!(
+            classify_specifier("./format", &dirs),
+            Some(PathStyle::Relative)
+        );
+        assert_eq!(
+            classify_specifier("../utils", &dirs),
+            Some(PathStyle::Relative)
+        );
+        assert_eq!(classify_specifier("@/utils", &dirs), Some(PathStyle::Alias));
+        assert_eq!(
+            classify_specifier("@components/Button", &dirs),
+            Some(PathStyle::Alias)
+        );
+        assert_eq!(
+            classify_specifier("src/utils/format", &dirs),
+            Some(PathStyle::Absolute)
+        );
+        assert_eq!(
+            classify_specifier("app.models", &dirs),
+            Some(PathStyle::Absolute)
+        );
+        assert_eq!(classify_specifier("react", &dirs), None);
+        assert_eq!(classify_specifier("@tanstack/query", &dirs), None);
+    }
+
+    #[test]
+    fn test_majority_threshold() {
+        assert_eq!(majority(&[("a", 8), ("b", 2)], "mixed"), Some(("a", 0.8)));
+        assert_eq!(
+            majority(&[("a", 6), ("b", 4)], "mixed"),
+            Some(("mixed", 0.6))
+        );
+        assert_eq!(majority(&[("a", 0), ("b", 0)], "mixed"), None);
+    }
+
+    #[test]
+    fn test_tally_js_module_system_and_barrels() {
+        let dirs = dirs();
+        let mut tally = ImportTally::default();
+        tally.add_file(
+            "src/utils/index.ts",
+            "typescript",
+            &[
+                import("./format", ImportKind::ReExport),
+                import("./parse", ImportKind::ReExport),
+            ],
+            &dirs,
+        );
+        tally.add_file(
+            "src/app.ts",
+            "typescript",
+            &[
+                import("react", ImportKind::Import),
+                import("./utils", ImportKind::Import),
+                import("fs", ImportKind::Require),
+            ],
+            &dirs,
+        );
+
+        assert_eq!(tally.esm, 4);
+        assert_eq!(tally.commonjs, 1);
+        assert_eq!(tally.relative, 3);
+        assert_eq!((tally.index_files, tally.barrel_files), (1, 1));
+
+        let conventions = tally.to_conventions().unwrap();
+        assert_eq!(conventions.module_system, Some(ModuleSystem::Esm));
+        assert_eq!(conventions.module_system_confidence, Some(0.8));
+        assert_eq!(conventions.path_style, Some(PathStyle::Relative));
+        assert!(conventions.index_exports);
+        assert!(conventions.python_style.is_none());
+    }
+
+    #[test]
+    fn test_tally_python_styles() {
+        let dirs = dirs();
+        let mut tally = ImportTally::default();
+        tally.add_file(
+            "app/views.py",
+            "python",
+            &[
+                import("app.models", ImportKind::From),
+                import(".forms", ImportKind::From),
+                import("os", ImportKind::Import),
+            ],
+            &dirs,
+        );
+
+        let conventions = tally.to_conventions().unwrap();
+        assert_eq!(conventions.python_style, Some(PythonImportStyle::Mixed));
+        assert!(conventions.module_system.is_none());
+        assert_eq!(conventions.path_style, Some(PathStyle::Mixed));
+    }
+
+    #[test]
+    fn test_detect_import_conventions_skips_unknown_files() {
+        let files = vec!["src/app.ts".to_string(), "src/lib.rs".to_string()];
+        let languages = HashMap::from([
+            ("src/app.ts".to_string(), "typescript".to_string()),
+            ("src/lib.rs".to_string(), "rust".to_string()),
+        ]);
+        let imports = HashMap::from([(
+            "src/lib.rs".to_string(),
+            vec![import("crate::cache", ImportKind::Import)],
+        )]);
+
+        assert!(detect_import_conventions(&files, &languages, &imports).is_none());
+    }
+}
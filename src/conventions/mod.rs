@@ -10,6 +10,7 @@
 //! - Anti-patterns (similar but unused patterns to avoid)
 //! - Import/module system preferences
 //! - Path style preferences (relative, absolute, alias)
+//! - Test file naming and placement
 //!
 //! ## Algorithm
 //!
@@ -23,11 +24,14 @@
 //!
 //! Performance: O(n) in file count, <10ms for 1000 files.
 
+pub mod imports;
 pub mod naming;
 
+pub use imports::{detect_import_conventions, ImportTally, ProjectDirs};
 pub use naming::{detect_naming_conventions, NamingDetector};
 
-use crate::cache::{Conventions, ImportConventions};
+use crate::ast::Import;
+use crate::cache::Conventions;
 use crate::error::Result;
 use std::collections::HashMap;
 
@@ -64,60 +68,46 @@ impl ConventionsAnalyzer {
     /// Analyze files and detect all conventions
     pub fn analyze(&self, files: &[String]) -> Conventions {
         let file_naming = self.naming_detector.detect_patterns(files);
+        let tests = self.naming_detector.detect_test_conventions(files);
 
-        // Import conventions detection will be added in T2.4
+        // Import conventions need parsed imports; see analyze_with_imports
         let imports = None;
 
         Conventions {
             file_naming,
             imports,
+            tests,
         }
     }
 
     /// Analyze with additional file metadata (language info)
+    ///
+    /// Without parsed imports no import conventions can be detected; use
+    /// [`Self::analyze_with_imports`] when AST imports are available.
     pub fn analyze_with_languages(
         &self,
         files: &[String],
         file_languages: &HashMap<String, String>,
     ) -> Conventions {
-        let file_naming = self.naming_detector.detect_patterns(files);
-
-        // Detect import conventions based on languages
-        let imports = self.detect_import_conventions(files, file_languages);
-
-        Conventions {
-            file_naming,
-            imports,
-        }
+        self.analyze_with_imports(files, file_languages, &HashMap::new())
     }
 
-    /// Detect import conventions based on file languages
-    fn detect_import_conventions(
+    /// Analyze with language info and imports extracted by the AST parser
+    pub fn analyze_with_imports(
         &self,
         files: &[String],
         file_languages: &HashMap<String, String>,
-    ) -> Option<ImportConventions> {
-        use crate::cache::{ModuleSystem, PathStyle};
-
-        // Count JS/TS files to determine module system
-        let js_ts_files: Vec<_> = files
-            .iter()
-            .filter(|f| {
-                let lang = file_languages.get(*f).map(|s| s.as_str()).unwrap_or("");
-                matches!(lang, "typescript" | "javascript")
-            })
-            .collect();
-
-        if js_ts_files.is_empty() {
-            return None;
-        }
+        file_imports: &HashMap<String, Vec<Import>>,
+    ) -> Conventions {
+        let file_naming = self.naming_detector.detect_patterns(files);
+        let imports = detect_import_conventions(files, file_languages, file_imports);
+        let tests = self.naming_detector.detect_test_conventions(files);
 
-        // Default to ESM for modern projects (can be enhanced with actual detection)
-        Some(ImportConventions {
-            module_system: Some(ModuleSystem::Esm),
-            path_style: Some(PathStyle::Relative),
-            index_exports: false,
-        })
+        Conventions {
+            file_naming,
+            imports,
+            tests,
+        }
     }
 }
 
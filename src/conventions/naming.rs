@@ -13,13 +13,20 @@
 //! 4. **Calculate** confidence (files matching / total files)
 //! 5. **Resolve** conflicts when multiple patterns exist (>70% dominance wins)
 //! 6. **Detect** anti-patterns (similar but unused patterns)
+//!
+//! Test files are reported separately by [`NamingDetector::detect_test_conventions`],
+//! covering naming (`*.test.ts`, `test_*.py`, ...) and placement (co-located
+//! vs `__tests__`/`tests` folders).
 
-use crate::cache::FileNamingConvention;
-use std::collections::HashMap;
+use crate::cache::{FileNamingConvention, TestConventions, TestFilePattern, TestLocation};
+use std::collections::{BTreeSet, HashMap};
 use std::path::Path;
 
 use super::{CONFIDENCE_THRESHOLD, MAX_EXAMPLES, MIN_FILES_FOR_PATTERN};
 
+/// Directory names that hold tests rather than sources
+const TEST_DIRECTORIES: &[&str] = &["__tests__", "tests", "test", "spec"];
+
 /// Naming pattern detector
 #[derive(Debug, Default)]
 pub struct NamingDetector {
@@ -266,6 +273,105 @@ impl NamingDetector {
     }
 }
 
+impl NamingDetector {
+    /// Detect test file naming and placement conventions
+    ///
+    /// A file counts as a test if its name matches a test pattern or it lives
+    /// under a dedicated test directory. Returns `None` when no tests exist.
+    pub fn detect_test_conventions(&self, files: &[String]) -> Option<TestConventions> {
+        let mut pattern_counts: HashMap<String, usize> = HashMap::new();
+        let mut test_directories = BTreeSet::new();
+        let mut test_files = 0;
+        let mut dedicated = 0;
+
+        for file in files {
+            let path = Path::new(file.strip_prefix("./").unwrap_or(file));
+            let Some(filename) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let pattern = test_file_pattern(filename);
+            let test_dir = path.parent().and_then(dedicated_test_dir);
+
+            if pattern.is_none() && test_dir.is_none() {
+                continue;
+            }
+
+            test_files += 1;
+            if let Some(pattern) = pattern {
+                *pattern_counts.entry(pattern).or_default() += 1;
+            }
+            if let Some(dir) = test_dir {
+                dedicated += 1;
+                test_directories.insert(dir);
+            }
+        }
+
+        if test_files == 0 {
+            return None;
+        }
+
+        let named: usize = pattern_counts.values().sum();
+        let mut patterns: Vec<TestFilePattern> = pattern_counts
+            .into_iter()
+            .map(|(pattern, count)| TestFilePattern {
+                pattern,
+                files: count,
+                percentage: (count as f64 / named as f64 * 1000.0).round() / 10.0,
+            })
+            .collect();
+        patterns.sort_by(|a, b| b.files.cmp(&a.files).then(a.pattern.cmp(&b.pattern)));
+
+        let dedicated_share = dedicated as f64 / test_files as f64;
+        let location = if dedicated_share >= CONFIDENCE_THRESHOLD {
+            TestLocation::Dedicated
+        } else if 1.0 - dedicated_share >= CONFIDENCE_THRESHOLD {
+            TestLocation::Colocated
+        } else {
+            TestLocation::Mixed
+        };
+
+        Some(TestConventions {
+            test_files,
+            patterns,
+            location,
+            test_directories: test_directories.into_iter().collect(),
+        })
+    }
+}
+
+/// Test naming pattern for a filename, e.g. `*.spec.ts` or `test_*.py`
+fn test_file_pattern(filename: &str) -> Option<String> {
+    let (stem, ext) = filename.rsplit_once('.')?;
+
+    if let Some((_, marker)) = stem.rsplit_once('.') {
+        if marker == "test" || marker == "spec" {
+            return Some(format!("*.{}.{}", marker, ext));
+        }
+    }
+    if stem.len() > 5 && stem.starts_with("test_") {
+        return Some(format!("test_*.{}", ext));
+    }
+    for suffix in ["_test", "_spec", "Tests", "Test"] {
+        if stem.len() > suffix.len() && stem.ends_with(suffix) {
+            return Some(format!("*{}.{}", suffix, ext));
+        }
+    }
+    None
+}
+
+/// Path of the nearest dedicated test directory containing `dir`, if any
+fn dedicated_test_dir(dir: &Path) -> Option<String> {
+    let mut prefix = Vec::new();
+    for component in dir.iter() {
+        let name = component.to_str()?;
+        prefix.push(name);
+        if TEST_DIRECTORIES.contains(&name) {
+            return Some(prefix.join("/"));
+        }
+    }
+    None
+}
+
 /// Convenience function for detecting naming conventions
 pub fn detect_naming_conventions(files: &[String]) -> Vec<FileNamingConvention> {
     NamingDetector::new().detect_patterns(files)
@@ -423,4 +529,88 @@ mod tests {
         assert!(!conventions[0].examples.is_empty());
         assert!(conventions[0].examples.len() <= MAX_EXAMPLES);
     }
+
+    fn paths(files: &[&str]) -> Vec<String> {
+        files.iter().map(|f| f.to_string()).collect()
+    }
+
+    #[test]
+    fn test_test_file_patterns() {
+        assert_eq!(
+            test_file_pattern("auth.test.ts").as_deref(),
+            Some("*.test.ts")
+        );
+        assert_eq!(
+            test_file_pattern("auth.spec.tsx").as_deref(),
+            Some("*.spec.tsx")
+        );
+        assert_eq!(
+            test_file_pattern("auth_test.go").as_deref(),
+            Some("*_test.go")
+        );
+        assert_eq!(
+            test_file_pattern("test_auth.py").as_deref(),
+            Some("test_*.py")
+        );
+        assert_eq!(
+            test_file_pattern("AuthTest.java").as_deref(),
+            Some("*Test.java")
+        );
+        assert_eq!(
+            test_file_pattern("AuthTests.cs").as_deref(),
+            Some("*Tests.cs")
+        );
+        assert_eq!(test_file_pattern("auth.ts"), None);
+        assert_eq!(test_file_pattern("latest.ts"), None);
+        assert_eq!(test_file_pattern("test.py"), None);
+    }
+
+    #[test]
+    fn test_mixed_test_and_spec_reported_with_percentages() {
+        let detector = NamingDetector::new();
+        let files = paths(&[
+            "src/auth.ts",
+            "src/auth.test.ts",
+            "src/users.test.ts",
+            "src/login.test.ts",
+            "src/signup.spec.ts",
+        ]);
+
+        let tests = detector.detect_test_conventions(&files).unwrap();
+        assert_eq!(tests.test_files, 4);
+        assert_eq!(tests.patterns.len(), 2);
+        assert_eq!(tests.patterns[0].pattern, "*.test.ts");
+        assert_eq!(tests.patterns[0].percentage, 75.0);
+        assert_eq!(tests.patterns[1].pattern, "*.spec.ts");
+        assert_eq!(tests.patterns[1].percentage, 25.0);
+        assert_eq!(tests.location, TestLocation::Colocated);
+        assert!(tests.test_directories.is_empty());
+    }
+
+    #[test]
+    fn test_dedicated_test_directories() {
+        let detector = NamingDetector::new();
+        let files = paths(&[
+            "./src/lib.rs",
+            "./tests/cli.rs",
+            "./tests/common/mod.rs",
+            "./src/parser/__tests__/parse.test.ts",
+        ]);
+
+        let tests = detector.detect_test_conventions(&files).unwrap();
+        assert_eq!(tests.test_files, 3);
+        assert_eq!(tests.location, TestLocation::Dedicated);
+        assert_eq!(
+            tests.test_directories,
+            vec!["src/parser/__tests__", "tests"]
+        );
+        assert_eq!(tests.patterns.len(), 1);
+    }
+
+    #[test]
+    fn test_no_tests_detected() {
+        let detector = NamingDetector::new();
+        let files = paths(&["src/a.ts", "src/b.ts"]);
+        assert!(detector.detect_test_conventions(&files).is_none());
+    }
 }
@@ -20,6 +20,10 @@ pub enum AcpError {
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    /// Binary (MessagePack) cache encoding or decoding failed
+    #[error("Binary cache error: {0}")]
+    BinaryCache(String),
+
     /// Source code parsing failed
     #[error("Parse error: {message}")]
     Parse {
@@ -28,6 +32,14 @@ pub enum AcpError {
         line: Option<usize>,
     },
 
+    /// An @acp: annotation is malformed; parsing continues without it
+    #[error("{file}:{line}: {message}")]
+    AnnotationParse {
+        file: String,
+        line: usize,
+        message: String,
+    },
+
     /// File not found
     #[error("File not found: {0}")]
     FileNotFound(String),
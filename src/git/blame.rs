@@ -41,11 +41,26 @@ pub struct BlameInfo {
 impl BlameInfo {
     /// Get blame information for a file
     pub fn for_file(repo: &GitRepository, path: &Path) -> Result<Self> {
+        Self::for_file_with_summaries(repo, path, None, &mut HashMap::new())
+    }
+
+    /// Get blame information, reusing (and filling) a commit summary cache
+    ///
+    /// Blames as of `newest` when given, otherwise as of the current HEAD.
+    pub(crate) fn for_file_with_summaries(
+        repo: &GitRepository,
+        path: &Path,
+        newest: Option<Oid>,
+        summaries: &mut HashMap<Oid, String>,
+    ) -> Result<Self> {
         let relative_path = Self::make_relative_path(repo, path)?;
 
         let mut opts = BlameOptions::new();
         opts.track_copies_same_commit_moves(true)
             .track_copies_same_commit_copies(true);
+        if let Some(newest) = newest {
+            opts.newest_commit(newest);
+        }
 
         let blame = repo
             .inner()
@@ -60,8 +75,15 @@ impl BlameInfo {
             let sig = hunk.final_signature();
             let commit_id = hunk.final_commit_id();
 
-            // Get commit message summary
-            let summary = Self::get_commit_summary(repo, commit_id)?;
+            // Get commit message summary (hunks often share a commit)
+            let summary = match summaries.get(&commit_id) {
+                Some(summary) => summary.clone(),
+                None => {
+                    let summary = Self::get_commit_summary(repo, commit_id)?;
+                    summaries.insert(commit_id, summary.clone());
+                    summary
+                }
+            };
 
             // Convert time to DateTime<Utc>
             let timestamp = Self::git_time_to_datetime(sig.when());
@@ -72,7 +94,7 @@ impl BlameInfo {
                 author: sig.name().unwrap_or("Unknown").to_string(),
                 author_email: sig.email().unwrap_or("").to_string(),
                 timestamp,
-                summary: summary.clone(),
+                summary,
             };
 
             // Add entry for each line in the hunk
@@ -143,7 +165,7 @@ impl BlameInfo {
     }
 
     /// Helper: make path relative to repo root
-    fn make_relative_path(repo: &GitRepository, path: &Path) -> Result<String> {
+    pub(crate) fn make_relative_path(repo: &GitRepository, path: &Path) -> Result<String> {
         let root = repo.root()?;
 
         let relative = if path.is_absolute() {
@@ -0,0 +1,243 @@
+//! @acp:module "Git Blame Cache"
+//! @acp:summary "Memoizes blame and file history lookups across an indexing run"
+//! @acp:domain cli
+//! @acp:layer integration
+//!
+//! The cache keeps the git pass cheap: histories for all files come from a
+//! single revision walk, each file is blamed at most once, and commit
+//! summaries are looked up once per commit instead of once per blame hunk.
+//! `git2::Repository` is not `Sync`, so [`BlameCache::prefetch_blames`] gives
+//! every rayon worker its own handle on the repository to blame in parallel.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use git2::Oid;
+use rayon::prelude::*;
+
+use super::blame::BlameInfo;
+use super::history::FileHistory;
+use super::repository::GitRepository;
+use crate::error::Result;
+
+/// @acp:summary "Per-run cache of blame results and file histories"
+pub struct BlameCache<'a> {
+    repo: &'a GitRepository,
+    /// Maximum commits per file history (0 = unlimited)
+    history_limit: usize,
+    /// Keyed by path relative to the repository root
+    histories: HashMap<String, FileHistory>,
+    /// `None` records a file that could not be blamed
+    blames: HashMap<String, Option<BlameInfo>>,
+    summaries: HashMap<Oid, String>,
+    /// HEAD when the cache was created; every blame is taken as of this commit
+    head: Option<Oid>,
+}
+
+impl<'a> BlameCache<'a> {
+    pub fn new(repo: &'a GitRepository, history_limit: usize) -> Self {
+        Self {
+            repo,
+            history_limit,
+            histories: HashMap::new(),
+            blames: HashMap::new(),
+            summaries: HashMap::new(),
+            head: repo.inner().head().ok().and_then(|head| head.target()),
+        }
+    }
+
+    /// @acp:summary "Load the histories of many files with one revision walk"
+    ///
+    /// Paths that are already cached or outside the repository are skipped.
+    pub fn prefetch_histories<P: AsRef<Path>>(&mut self, paths: &[P]) -> Result<()> {
+        let mut wanted: Vec<String> = paths
+            .iter()
+            .filter_map(|p| FileHistory::make_relative_path(self.repo, p.as_ref()).ok())
+            .filter(|p| !self.histories.contains_key(p))
+            .collect();
+        wanted.sort();
+        wanted.dedup();
+
+        let histories = FileHistory::for_files(self.repo, &wanted, self.history_limit)?;
+        self.histories.extend(histories);
+        Ok(())
+    }
+
+    /// @acp:summary "Blame many files in parallel, one repository handle per worker"
+    ///
+    /// Each rayon worker opens its own handle on the same repository, and all
+    /// of them blame as of the same HEAD commit, so results are stored by path
+    /// and do not depend on scheduling. Files already blamed are skipped;
+    /// failures are remembered as with [`BlameCache::blame`].
+    pub fn prefetch_blames<P: AsRef<Path>>(&mut self, paths: &[P]) -> Result<()> {
+        let root = self.repo.root()?.to_path_buf();
+        let mut wanted: Vec<String> = paths
+            .iter()
+            .filter_map(|p| BlameInfo::make_relative_path(self.repo, p.as_ref()).ok())
+            .filter(|p| !self.blames.contains_key(p))
+            .collect();
+        wanted.sort();
+        wanted.dedup();
+
+        let head = self.head;
+        let blamed: Vec<(String, Option<BlameInfo>)> = wanted
+            .into_par_iter()
+            .map_init(
+                || (GitRepository::open(&root).ok(), HashMap::new()),
+                |(repo, summaries), relative| {
+                    let blame = repo.as_ref().and_then(|repo| {
+                        let path = root.join(&relative);
+                        BlameInfo::for_file_with_summaries(repo, &path, head, summaries).ok()
+                    });
+                    (relative, blame)
+                },
+            )
+            .collect();
+        self.blames.extend(blamed);
+        Ok(())
+    }
+
+    /// @acp:summary "A file's history, walking it now if it was not prefetched"
+    pub fn history(&mut self, path: &Path) -> Result<&FileHistory> {
+        let relative = FileHistory::make_relative_path(self.repo, path)?;
+        if !self.histories.contains_key(&relative) {
+            let history = FileHistory::for_file(self.repo, path, self.history_limit)?;
+            self.histories.insert(relative.clone(), history);
+        }
+        Ok(&self.histories[&relative])
+    }
+
+    /// @acp:summary "A file's blame, computed on first use"
+    ///
+    /// Returns `None` for files git cannot blame (untracked, binary, outside
+    /// the repository); the failure is remembered too.
+    pub fn blame(&mut self, path: &Path) -> Option<&BlameInfo> {
+        let relative = BlameInfo::make_relative_path(self.repo, path).ok()?;
+        if !self.blames.contains_key(&relative) {
+            let blame =
+                BlameInfo::for_file_with_summaries(self.repo, path, self.head, &mut self.summaries);
+            self.blames.insert(relative.clone(), blame.ok());
+        }
+        self.blames[&relative].as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{Repository, Signature};
+
+    fn commit(repo: &Repository, who: &str, files: &[(&str, &str)], message: &str) {
+        let root = repo.workdir().unwrap();
+        let mut index = repo.index().unwrap();
+        for (file, content) in files {
+            std::fs::write(root.join(file), content).unwrap();
+            index.add_path(Path::new(file)).unwrap();
+        }
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+
+        let sig = Signature::now(who, &format!("{}@example.com", who)).unwrap();
+        let parent = repo.head().ok().map(|h| h.peel_to_commit().unwrap());
+        let parents: Vec<_> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_batched_history_matches_per_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        commit(&repo, "alice", &[("a.ts", "1\n"), ("b.ts", "1\n")], "init");
+        commit(&repo, "bob", &[("a.ts", "1\n2\n3\n")], "grow a");
+        commit(
+            &repo,
+            "carol",
+            &[("a.ts", "4\n"), ("b.ts", "2\n")],
+            "edit both",
+        );
+        commit(&repo, "dave", &[("c.ts", "1\n")], "add c");
+
+        let git = GitRepository::open(dir.path()).unwrap();
+        let root = git.root().unwrap().to_path_buf();
+        let paths: Vec<_> = ["a.ts", "b.ts", "c.ts"].map(|f| root.join(f)).to_vec();
+
+        for limit in [0, 1] {
+            let mut cache = BlameCache::new(&git, limit);
+            cache.prefetch_histories(&paths).unwrap();
+            for path in &paths {
+                let expected = FileHistory::for_file(&git, path, limit).unwrap();
+                let history = cache.history(path).unwrap();
+                let summary = |h: &FileHistory| -> Vec<_> {
+                    h.entries()
+                        .iter()
+                        .map(|e| (e.commit.clone(), e.lines_added, e.lines_removed))
+                        .collect()
+                };
+                assert_eq!(summary(history), summary(&expected), "{}", path.display());
+            }
+        }
+
+        let mut cache = BlameCache::new(&git, 0);
+        cache.prefetch_histories(&paths).unwrap();
+        assert_eq!(cache.history(&paths[0]).unwrap().commit_count(), 3);
+        assert_eq!(
+            cache.history(&paths[1]).unwrap().contributors(),
+            vec!["alice".to_string(), "carol".to_string()]
+        );
+
+        let blame = cache.blame(&paths[0]).unwrap();
+        assert_eq!(blame.get_line(1).unwrap().author, "carol");
+        assert_eq!(blame.get_line(1).unwrap().summary, "edit both");
+        assert!(cache.blame(&root.join("missing.ts")).is_none());
+    }
+
+    #[test]
+    fn test_parallel_blame_matches_sequential() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let files: Vec<String> = (0..12).map(|i| format!("f{}.ts", i)).collect();
+        let initial: Vec<(&str, &str)> = files.iter().map(|f| (f.as_str(), "a\nb\n")).collect();
+        commit(&repo, "alice", &initial, "init");
+        commit(
+            &repo,
+            "bob",
+            &[("f3.ts", "a\nc\n"), ("f7.ts", "z\n")],
+            "edit",
+        );
+
+        let git = GitRepository::open(dir.path()).unwrap();
+        let root = git.root().unwrap().to_path_buf();
+        let mut paths: Vec<_> = files.iter().map(|f| root.join(f)).collect();
+        paths.push(root.join("missing.ts"));
+
+        let mut parallel = BlameCache::new(&git, 0);
+        parallel.prefetch_blames(&paths).unwrap();
+        // Commits made after the cache was created are not seen
+        commit(&repo, "carol", &[("f0.ts", "new\n")], "late");
+
+        let authors = |blame: &BlameInfo| -> Vec<String> {
+            (1..=blame.line_count())
+                .map(|line| blame.get_line(line).unwrap().author.clone())
+                .collect()
+        };
+        let mut sequential = BlameCache::new(&git, 0);
+        for path in &paths[1..] {
+            let expected = sequential.blame(path).map(authors);
+            assert_eq!(
+                parallel.blame(path).map(authors),
+                expected,
+                "{}",
+                path.display()
+            );
+        }
+        assert_eq!(
+            authors(parallel.blame(&paths[0]).unwrap()),
+            vec!["alice"; 2]
+        );
+        assert_eq!(
+            authors(parallel.blame(&paths[3]).unwrap()),
+            vec!["alice", "bob"]
+        );
+    }
+}
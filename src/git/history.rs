@@ -6,6 +6,7 @@
 use chrono::{DateTime, TimeZone, Utc};
 use git2::{DiffOptions, Oid};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 
 use super::repository::GitRepository;
@@ -49,6 +50,138 @@ impl FileHistory {
     /// * `path` - Path to the file
     /// * `limit` - Maximum number of commits to retrieve (0 = unlimited)
     pub fn for_file(repo: &GitRepository, path: &Path, limit: usize) -> Result<Self> {
+        Self::walk(repo, path, limit, None)
+    }
+
+    /// Get the commits that touched a file after `commit` (exclusive)
+    ///
+    /// Only commits on HEAD that `commit` can't reach are considered, so
+    /// history that was merged in from before `commit` is not reported.
+    pub fn since(repo: &GitRepository, path: &Path, commit: &str) -> Result<Self> {
+        let oid = Oid::from_str(commit)
+            .map_err(|e| AcpError::Other(format!("Invalid commit {}: {}", commit, e)))?;
+        Self::walk(repo, path, 0, Some(oid))
+    }
+
+    /// Get the commit histories of many files in a single walk
+    ///
+    /// Equivalent to calling `for_file` for each path, but every commit is
+    /// diffed once instead of once per file. `paths` are relative to the
+    /// repository root; files with no commits get an empty history.
+    pub fn for_files(
+        repo: &GitRepository,
+        paths: &[String],
+        limit: usize,
+    ) -> Result<HashMap<String, Self>> {
+        let mut histories: HashMap<String, Self> = paths
+            .iter()
+            .map(|path| {
+                let history = Self {
+                    commits: Vec::new(),
+                    path: path.clone(),
+                };
+                (path.clone(), history)
+            })
+            .collect();
+        if histories.is_empty() {
+            return Ok(histories);
+        }
+
+        let mut revwalk = repo
+            .inner()
+            .revwalk()
+            .map_err(|e| AcpError::Other(format!("Failed to create revwalk: {}", e)))?;
+        revwalk
+            .push_head()
+            .map_err(|e| AcpError::Other(format!("Failed to push HEAD: {}", e)))?;
+        revwalk
+            .set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)
+            .map_err(|e| AcpError::Other(format!("Failed to set sorting: {}", e)))?;
+
+        let full = |h: &Self| limit > 0 && h.commits.len() >= limit;
+        let mut remaining = histories.values().filter(|h| !full(h)).count();
+
+        for oid_result in revwalk {
+            if remaining == 0 {
+                break;
+            }
+            let oid = oid_result
+                .map_err(|e| AcpError::Other(format!("Failed to get commit oid: {}", e)))?;
+
+            let commit = repo
+                .inner()
+                .find_commit(oid)
+                .map_err(|e| AcpError::Other(format!("Failed to find commit: {}", e)))?;
+
+            for (path, stats) in Self::commit_changes(repo, &commit, &histories)? {
+                let history = histories.get_mut(&path).expect("path was requested");
+                if full(history) {
+                    continue;
+                }
+                history.commits.push(Self::entry_with_stats(&commit, stats));
+                if full(history) {
+                    remaining -= 1;
+                }
+            }
+        }
+
+        Ok(histories)
+    }
+
+    /// Requested files changed by a commit, with their (added, removed) lines
+    fn commit_changes(
+        repo: &GitRepository,
+        commit: &git2::Commit,
+        wanted: &HashMap<String, Self>,
+    ) -> Result<Vec<(String, (usize, usize))>> {
+        let tree = commit
+            .tree()
+            .map_err(|e| AcpError::Other(format!("Failed to get commit tree: {}", e)))?;
+
+        // Initial commit - every file in the tree was added here
+        if commit.parent_count() == 0 {
+            let mut changes = Vec::new();
+            for path in wanted.keys() {
+                if tree.get_path(Path::new(path)).is_ok() {
+                    changes.push((path.clone(), Self::count_file_lines(repo, commit, path)?));
+                }
+            }
+            return Ok(changes);
+        }
+
+        let parent_tree = commit
+            .parent(0)
+            .and_then(|parent| parent.tree())
+            .map_err(|e| AcpError::Other(format!("Failed to get parent tree: {}", e)))?;
+        let diff = repo
+            .inner()
+            .diff_tree_to_tree(Some(&parent_tree), Some(&tree), None)
+            .map_err(|e| AcpError::Other(format!("Failed to diff trees: {}", e)))?;
+
+        let mut changes = Vec::new();
+        for (idx, delta) in diff.deltas().enumerate() {
+            let path = [delta.new_file().path(), delta.old_file().path()]
+                .into_iter()
+                .flatten()
+                .filter_map(|p| p.to_str())
+                .find(|p| wanted.contains_key(*p));
+            let Some(path) = path else {
+                continue;
+            };
+
+            let stats = match git2::Patch::from_diff(&diff, idx) {
+                Ok(Some(patch)) => patch
+                    .line_stats()
+                    .map(|(_, added, removed)| (added, removed))
+                    .unwrap_or_default(),
+                _ => (0, 0),
+            };
+            changes.push((path.to_string(), stats));
+        }
+        Ok(changes)
+    }
+
+    fn walk(repo: &GitRepository, path: &Path, limit: usize, hide: Option<Oid>) -> Result<Self> {
         let relative_path = Self::make_relative_path(repo, path)?;
 
         let mut revwalk = repo
@@ -61,9 +194,16 @@ impl FileHistory {
             .push_head()
             .map_err(|e| AcpError::Other(format!("Failed to push HEAD: {}", e)))?;
 
-        // Sort by time (newest first)
+        if let Some(oid) = hide {
+            revwalk
+                .hide(oid)
+                .map_err(|e| AcpError::Other(format!("Failed to hide {}: {}", oid, e)))?;
+        }
+
+        // Newest first; topological so commits in the same second keep
+        // their parent/child order
         revwalk
-            .set_sorting(git2::Sort::TIME)
+            .set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)
             .map_err(|e| AcpError::Other(format!("Failed to set sorting: {}", e)))?;
 
         let mut commits = Vec::new();
@@ -197,18 +337,26 @@ impl FileHistory {
         path: &str,
         is_initial: bool,
     ) -> Result<HistoryEntry> {
-        let sig = commit.author();
-        let timestamp = Self::git_time_to_datetime(sig.when());
-
         // Calculate lines added/removed
-        let (lines_added, lines_removed) = if is_initial {
+        let stats = if is_initial {
             // For initial commit, count all lines as added
             Self::count_file_lines(repo, commit, path)?
         } else {
             Self::calculate_diff_stats(repo, commit, path)?
         };
 
-        Ok(HistoryEntry {
+        Ok(Self::entry_with_stats(commit, stats))
+    }
+
+    /// Create a history entry from a commit and its (added, removed) line counts
+    fn entry_with_stats(
+        commit: &git2::Commit,
+        (lines_added, lines_removed): (usize, usize),
+    ) -> HistoryEntry {
+        let sig = commit.author();
+        let timestamp = Self::git_time_to_datetime(sig.when());
+
+        HistoryEntry {
             commit: commit.id().to_string(),
             commit_short: commit.id().to_string().chars().take(7).collect(),
             author: sig.name().unwrap_or("Unknown").to_string(),
@@ -217,7 +365,7 @@ impl FileHistory {
             message: commit.summary().unwrap_or("").to_string(),
             lines_added,
             lines_removed,
-        })
+        }
     }
 
     /// Count lines in a file for initial commit
@@ -278,7 +426,7 @@ impl FileHistory {
     }
 
     /// Helper: make path relative to repo root
-    fn make_relative_path(repo: &GitRepository, path: &Path) -> Result<String> {
+    pub(crate) fn make_relative_path(repo: &GitRepository, path: &Path) -> Result<String> {
         let root = repo.root()?;
 
         let relative = if path.is_absolute() {
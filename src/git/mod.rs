@@ -9,12 +9,15 @@
 //! - Repository operations (HEAD, branch, remotes)
 //! - Blame tracking (line-level authorship)
 //! - File history (commits, contributors)
+//! - A per-run cache of blame and history lookups
 
 pub mod blame;
+pub mod cache;
 pub mod history;
 pub mod repository;
 
 pub use blame::{BlameInfo, LineBlame};
+pub use cache::BlameCache;
 pub use history::{FileHistory, HistoryEntry};
 pub use repository::{FileStatus, GitRepository};
 
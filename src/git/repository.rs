@@ -103,6 +103,38 @@ impl GitRepository {
         }
     }
 
+    /// Get a file's content as committed at HEAD (None if not in HEAD)
+    pub fn file_at_head(&self, path: &Path) -> Result<Option<String>> {
+        let tree = self
+            .repo
+            .head()
+            .and_then(|head| head.peel_to_tree())
+            .map_err(|e| AcpError::Other(format!("Failed to get HEAD tree: {}", e)))?;
+        self.file_in_tree(&tree, path)
+    }
+
+    /// Get a file's content as of `commit` (None if not in that commit)
+    pub fn file_at_commit(&self, commit: &str, path: &Path) -> Result<Option<String>> {
+        let tree = git2::Oid::from_str(commit)
+            .and_then(|oid| self.repo.find_commit(oid))
+            .and_then(|commit| commit.tree())
+            .map_err(|e| AcpError::Other(format!("Failed to get tree of {}: {}", commit, e)))?;
+        self.file_in_tree(&tree, path)
+    }
+
+    fn file_in_tree(&self, tree: &git2::Tree, path: &Path) -> Result<Option<String>> {
+        let relative_path = self.make_relative(path);
+        let Ok(entry) = tree.get_path(&relative_path) else {
+            return Ok(None);
+        };
+        let blob = self
+            .repo
+            .find_blob(entry.id())
+            .map_err(|e| AcpError::Other(format!("Failed to get blob: {}", e)))?;
+
+        Ok(Some(String::from_utf8_lossy(blob.content()).into_owned()))
+    }
+
     /// Check if a file path is tracked by git
     pub fn is_tracked(&self, path: &Path) -> bool {
         // Make path relative to repo root
@@ -151,6 +183,36 @@ impl GitRepository {
         Ok(files)
     }
 
+    /// Get the files that differ between `commit` and HEAD
+    ///
+    /// Both sides of a rename are listed. Fails if `commit` is not in the
+    /// repository (for example after a rebase).
+    pub fn changed_since(&self, commit: &str) -> Result<Vec<String>> {
+        let old_tree = git2::Oid::from_str(commit)
+            .and_then(|oid| self.repo.find_commit(oid))
+            .and_then(|c| c.tree())
+            .map_err(|e| AcpError::Other(format!("Failed to find commit {}: {}", commit, e)))?;
+        let head_tree = self
+            .repo
+            .head()
+            .and_then(|head| head.peel_to_tree())
+            .map_err(|e| AcpError::Other(format!("Failed to get HEAD tree: {}", e)))?;
+        let diff = self
+            .repo
+            .diff_tree_to_tree(Some(&old_tree), Some(&head_tree), None)
+            .map_err(|e| AcpError::Other(format!("Failed to diff trees: {}", e)))?;
+
+        let mut files: Vec<String> = diff
+            .deltas()
+            .flat_map(|delta| [delta.old_file().path(), delta.new_file().path()])
+            .flatten()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+        files.sort();
+        files.dedup();
+        Ok(files)
+    }
+
     /// Check if the repository has uncommitted changes
     pub fn is_dirty(&self) -> Result<bool> {
         let mut opts = StatusOptions::new();
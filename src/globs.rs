@@ -0,0 +1,82 @@
+//! @acp:module "Glob Matching"
+//! @acp:summary "Shared glob options and named pattern groups"
+//! @acp:domain cli
+//! @acp:layer utility
+//!
+//! Config globs (`include`, `exclude`, `domains`, `tags`, lock rules, bridge
+//! overrides, coverage thresholds) and glob arguments on the command line
+//! all match with [`MATCH_OPTIONS`], so a pattern means the same thing
+//! wherever it is written.
+
+use glob::{MatchOptions, Pattern};
+
+use crate::cache::normalize_path;
+
+/// Options for every glob the CLI matches: case-sensitive, and `*` may
+/// cross directory separators as in the original include/exclude matching
+pub const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: false,
+    require_literal_leading_dot: false,
+};
+
+/// @acp:summary "Named groups of glob patterns, such as domains or tags"
+///
+/// A path belongs to every group with a matching pattern. Paths are matched
+/// relative to the project root; invalid patterns are ignored.
+#[derive(Debug, Default)]
+pub struct NamedGlobs {
+    rules: Vec<(String, Vec<Pattern>)>,
+}
+
+impl NamedGlobs {
+    /// Compile `(name, patterns)` pairs
+    pub fn new<'a>(groups: impl IntoIterator<Item = (&'a String, &'a Vec<String>)>) -> Self {
+        let mut rules: Vec<_> = groups
+            .into_iter()
+            .map(|(name, patterns)| {
+                let patterns = patterns
+                    .iter()
+                    .filter_map(|p| Pattern::new(p).ok())
+                    .collect();
+                (name.clone(), patterns)
+            })
+            .collect();
+        rules.sort_by(|a, b| a.0.cmp(&b.0));
+        Self { rules }
+    }
+
+    /// Names of the groups whose patterns match `path`, sorted
+    pub fn matching(&self, path: &str) -> Vec<String> {
+        let path = normalize_path(path);
+        self.rules
+            .iter()
+            .filter(|(_, patterns)| {
+                patterns
+                    .iter()
+                    .any(|p| p.matches_with(&path, MATCH_OPTIONS))
+            })
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_named_globs_match_relative_paths() {
+        let groups = [
+            ("web".to_string(), vec!["src/web/*".to_string()]),
+            ("ts".to_string(), vec!["**/*.ts".to_string()]),
+            ("broken".to_string(), vec!["[invalid".to_string()]),
+        ];
+        let globs = NamedGlobs::new(groups.iter().map(|(n, p)| (n, p)));
+
+        assert_eq!(globs.matching("./src/web/app.ts"), vec!["ts", "web"]);
+        // `*` crosses separators, as for include/exclude
+        assert_eq!(globs.matching("src/web/pages/home.ts"), vec!["ts", "web"]);
+        assert!(globs.matching("README.md").is_empty());
+    }
+}
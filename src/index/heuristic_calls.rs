@@ -0,0 +1,127 @@
+//! @acp:module "Heuristic Call Edges"
+//! @acp:summary "Possible callees from string literals naming known functions"
+//! @acp:domain cli
+//! @acp:layer service
+//!
+//! Static call extraction cannot see dynamic dispatch: `obj[method]()`,
+//! reflection, DI containers, route tables. This pass records a *possible*
+//! call wherever a string literal spells the name of a known function or
+//! method, attributed to the innermost symbol enclosing the literal.
+//! Literals outside any symbol (module-level registrations) have no caller
+//! to attach to and are ignored. The edges land in `graph.heuristic_edges`,
+//! never in the precise forward/reverse graph.
+
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::cache::{CallEdge, SymbolEntry, SymbolType};
+
+/// Quoted identifiers (`'handleLogin'`, `"UserService.find"`, `` `run` ``)
+static STRING_IDENT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"["'`]([A-Za-z_$][\w$]*(?:[.:]{1,2}[A-Za-z_$][\w$]*)*)["'`]"#).unwrap()
+});
+
+/// Literals shorter than this are too ambiguous to count as a reference
+const MIN_NAME_LEN: usize = 3;
+
+/// @acp:summary "Identifier-like string literals in source, with their lines"
+///
+/// A dotted literal (`"UserService.find"`) yields its last segment, the
+/// name the call graph is keyed by.
+pub fn string_refs(source: &str) -> Vec<(String, usize)> {
+    let mut refs = Vec::new();
+    for (index, line) in source.lines().enumerate() {
+        for capture in STRING_IDENT.captures_iter(line) {
+            let literal = &capture[1];
+            let name = literal.rsplit(['.', ':']).next().unwrap_or(literal);
+            if name.len() >= MIN_NAME_LEN {
+                refs.push((name.to_string(), index + 1));
+            }
+        }
+    }
+    refs
+}
+
+/// @acp:summary "Heuristic edges for one file's string references"
+///
+/// `callables` holds every known function and method name in the project;
+/// `symbols` are the symbols defined in `file`.
+pub fn heuristic_edges(
+    file: &str,
+    refs: &[(String, usize)],
+    symbols: &[SymbolEntry],
+    callables: &HashSet<&str>,
+) -> Vec<CallEdge> {
+    let mut edges: Vec<CallEdge> = Vec::new();
+    for (name, line) in refs {
+        if !callables.contains(name.as_str()) {
+            continue;
+        }
+        let Some(caller) = symbols
+            .iter()
+            .filter(|s| s.lines[0] <= *line && *line <= s.lines[1])
+            .min_by_key(|s| s.lines[1] - s.lines[0])
+        else {
+            continue;
+        };
+        if caller.name == *name {
+            continue;
+        }
+        let edge = CallEdge {
+            from: caller.name.clone(),
+            to: name.clone(),
+            file: file.to_string(),
+            line: *line,
+        };
+        if !edges.contains(&edge) {
+            edges.push(edge);
+        }
+    }
+    edges
+}
+
+/// @acp:summary "Whether a symbol can be the target of a call"
+pub fn is_callable(symbol: &SymbolEntry) -> bool {
+    matches!(
+        symbol.symbol_type,
+        SymbolType::Function | SymbolType::Method
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(name: &str, lines: [usize; 2]) -> SymbolEntry {
+        SymbolEntry {
+            lines,
+            ..SymbolEntry::new(name, "app.ts")
+        }
+    }
+
+    #[test]
+    fn test_heuristic_edges_from_string_literals() {
+        let source = r#"const routes = { home: "renderHome" };
+function dispatch(action) {
+    return handlers["handleLogin"](action) || container.get('UserService.find');
+}
+function handleLogin() { return dispatch("handleLogin"); }
+"#;
+        let refs = string_refs(source);
+        assert!(refs.contains(&("renderHome".to_string(), 1)));
+        assert!(refs.contains(&("handleLogin".to_string(), 3)));
+        assert!(refs.contains(&("find".to_string(), 3)));
+
+        let symbols = vec![symbol("dispatch", [2, 4]), symbol("handleLogin", [5, 5])];
+        let callables = HashSet::from(["dispatch", "handleLogin", "renderHome"]);
+        let edges = heuristic_edges("app.ts", &refs, &symbols, &callables);
+
+        // Module-level literals and self-references are skipped
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].from, "dispatch");
+        assert_eq!(edges[0].to, "handleLogin");
+        assert_eq!(edges[0].line, 3);
+    }
+}
@@ -8,37 +8,70 @@
 //! Supports RFC-0003 annotation provenance tracking.
 //! Supports RFC-0006 documentation system bridging.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
 use glob::Pattern;
+use ignore::WalkBuilder;
 use rayon::prelude::*;
-use walkdir::WalkDir;
 
 use crate::annotate::converters::{
     DocStandardParser, DocstringParser, GodocParser, JavadocParser, JsDocParser,
     ParsedDocumentation, RustdocParser,
 };
-use crate::ast::{AstParser, ExtractedSymbol, SymbolKind, Visibility as AstVisibility};
+use crate::ast::{AstParser, ExtractedSymbol, Import, SymbolKind, Visibility as AstVisibility};
 use crate::bridge::merger::AcpAnnotations;
-use crate::bridge::{BridgeConfig, BridgeMerger, FormatDetector};
+use crate::bridge::{BridgeConfig, BridgeMerger, FormatDetector, MarkdownBridge};
 use crate::cache::{
-    AnnotationProvenance, BridgeMetadata, BridgeSource, BridgeStats, BridgeSummary, Cache,
-    CacheBuilder, DomainEntry, Language, LowConfidenceEntry, ProvenanceStats, SourceFormat,
-    SymbolEntry, SymbolType, Visibility,
+    normalize_path, AnnotationProvenance, BridgeMetadata, BridgeSource, BridgeStats, BridgeSummary,
+    Cache, CacheBuilder, CallEdge, CallGraph, DomainEntry, DomainSource, Language,
+    LowConfidenceEntry, ProvenanceStats, SkippedFile, SourceFormat, SymbolEntry, SymbolType,
+    Visibility,
 };
-use crate::config::Config;
+use crate::config::{Config, LimitsConfig};
 use crate::constraints::{
-    ConstraintIndex, Constraints, HackMarker, HackType, LockLevel, MutationConstraint,
+    ConstraintIndex, Constraints, DirectoryLock, HackMarker, HackType, LockLevel,
+    MutationConstraint,
+};
+use crate::conventions::ConventionsAnalyzer;
+use crate::error::{AcpError, Result};
+use crate::git::{BlameCache, FileHistory, GitFileInfo, GitRepository, GitSymbolInfo};
+use crate::globs::{NamedGlobs, MATCH_OPTIONS};
+use crate::parse::{
+    normalize_imports, read_source, AnnotationKey, AnnotationWithProvenance, Parser, SourceOrigin,
 };
-use crate::error::Result;
-use crate::git::{BlameInfo, FileHistory, GitFileInfo, GitRepository, GitSymbolInfo};
-use crate::parse::{AnnotationWithProvenance, Parser, SourceOrigin};
 use crate::vars::{VarEntry, VarsFile};
 
+use super::heuristic_calls;
+use super::param_check::{align_params, fill_type_hints};
+use super::plan::{IndexPlan, PlannedSkip, SkipReason};
+
+/// Gitignore-syntax exclusion file, read in the project root and every
+/// directory below it whether or not `.gitignore` is respected
+pub const ACPIGNORE_FILE: &str = ".acpignore";
+
+/// @acp:summary "A freshly built cache and the problems met building it"
+#[derive(Debug)]
+pub struct IndexReport {
+    /// The cache, without the files that failed to parse
+    pub cache: Cache,
+    /// Files that failed to parse, in file order
+    pub failures: Vec<(PathBuf, AcpError)>,
+    /// Non-fatal problems, in file order
+    pub warnings: Vec<IndexWarning>,
+}
+
+/// @acp:summary "A non-fatal problem found in one file while indexing"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexWarning {
+    /// Path relative to the project root
+    pub file: String,
+    pub message: String,
+}
+
 /// @acp:summary "Codebase indexer with parallel file processing"
 /// Uses tree-sitter AST parsing for accurate symbol extraction and git2 for metadata.
 /// Supports RFC-0006 documentation bridging.
@@ -50,6 +83,8 @@ pub struct Indexer {
     format_detector: Arc<FormatDetector>,
     /// RFC-0006: Merger for native docs with ACP annotations
     bridge_merger: Arc<BridgeMerger>,
+    /// Earlier cache whose git metadata is reused for unchanged files
+    previous: Option<Cache>,
 }
 
 impl Indexer {
@@ -57,19 +92,40 @@ impl Indexer {
         // RFC-0006: Initialize bridge components
         let format_detector = FormatDetector::new(&config.bridge);
         let bridge_merger = BridgeMerger::new(&config.bridge);
+        let parser = Parser::from_config(&config);
 
         Ok(Self {
             config,
-            parser: Arc::new(Parser::new()),
+            parser: Arc::new(parser),
             ast_parser: Arc::new(AstParser::new()?),
             format_detector: Arc::new(format_detector),
             bridge_merger: Arc::new(bridge_merger),
+            previous: None,
         })
     }
 
+    /// @acp:summary "Reuse git metadata from an earlier cache for unchanged files"
+    /// Files that no commit has touched since the earlier cache's `git_commit`,
+    /// and that have no uncommitted changes, keep their git metadata instead
+    /// of being blamed again.
+    pub fn with_previous_cache(mut self, cache: Cache) -> Self {
+        self.previous = Some(cache);
+        self
+    }
+
     /// @acp:summary "Index the codebase and generate cache"
-    /// @acp:ai-careful "This processes many files in parallel"
+    /// Files that fail to parse are left out of the cache, and warnings are
+    /// dropped; use [`Indexer::index_with_report`] to report them.
     pub async fn index<P: AsRef<Path>>(&self, root: P) -> Result<Cache> {
+        Ok(self.index_with_report(root).await?.cache)
+    }
+
+    /// @acp:summary "Index the codebase, also returning parse failures and warnings"
+    /// @acp:ai-careful "This processes many files in parallel"
+    ///
+    /// Nothing is printed here; failures and warnings come back in file
+    /// order so callers can report them consistently.
+    pub async fn index_with_report<P: AsRef<Path>>(&self, root: P) -> Result<IndexReport> {
         let root = root.as_ref();
         let project_name = root
             .file_name()
@@ -89,21 +145,33 @@ impl Indexer {
         }
 
         // Find all matching files
-        let files = self.find_files(root)?;
+        let mut files = self.find_files(root)?;
+        let max_file_bytes = self.max_file_bytes();
 
-        // Add source_files with modification times
+        // Add source_files with modification times; oversized files are
+        // tracked (so they don't look new to staleness checks) but not parsed
+        let mut oversized = HashSet::new();
         for file_path in &files {
             if let Ok(metadata) = fs::metadata(file_path) {
+                let relative_path = Path::new(file_path)
+                    .strip_prefix(root)
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or_else(|_| file_path.clone());
                 if let Ok(modified) = metadata.modified() {
                     let modified_dt: DateTime<Utc> = modified.into();
-                    let relative_path = Path::new(file_path)
-                        .strip_prefix(root)
-                        .map(|p| p.to_string_lossy().to_string())
-                        .unwrap_or_else(|_| file_path.clone());
-                    builder = builder.add_source_file(relative_path, modified_dt);
+                    builder = builder.add_source_file(relative_path.clone(), modified_dt);
+                }
+                if metadata.len() > max_file_bytes {
+                    builder = builder.add_skipped_file(SkippedFile {
+                        path: relative_path,
+                        size_bytes: metadata.len(),
+                        lines: count_lines(Path::new(file_path)).unwrap_or(0),
+                    });
+                    oversized.insert(file_path.clone());
                 }
             }
         }
+        files.retain(|f| !oversized.contains(f));
 
         // Parse files in parallel using rayon
         // Uses annotation parser as primary for metadata, AST parser for accurate symbols
@@ -118,24 +186,62 @@ impl Indexer {
         let format_detector = Arc::clone(&self.format_detector);
         let bridge_merger = Arc::clone(&self.bridge_merger);
         let bridge_enabled = self.config.bridge.enabled;
+        let markdown_bridge = (bridge_enabled && self.config.bridge.markdown.enabled)
+            .then(|| MarkdownBridge::new(&self.config.bridge.markdown));
+        let heuristic_edges = self
+            .config
+            .call_graph
+            .as_ref()
+            .is_some_and(|c| c.heuristic_edges);
 
-        let mut results: Vec<_> = files
+        // Each file yields its parse output or the error that kept it out of
+        // the cache; they are split apart on this thread afterwards
+        let parsed: Vec<_> = files
             .par_iter()
-            .filter_map(|path| {
+            .map(|path| {
                 // Parse with annotation parser (metadata, domains, etc.)
-                let mut parse_result = annotation_parser.parse(path).ok()?;
+                let mut parse_result = annotation_parser
+                    .parse(path)
+                    .map_err(|e| (PathBuf::from(path), e))?;
+                // RFC-0015: AST imports for import convention detection
+                let mut file_imports = Vec::new();
+                let mut string_refs = Vec::new();
+                let mut warnings = Vec::new();
 
                 // Try AST parsing for accurate symbol extraction
-                if let Ok(source) = std::fs::read_to_string(path) {
+                if let Ok(source) = read_source(Path::new(path)) {
                     // RFC-0003: Parse annotations with provenance support
-                    let annotations_with_prov =
-                        annotation_parser.parse_annotations_with_provenance(&source);
+                    let annotations_with_prov = annotation_parser
+                        .parse_annotations_with_provenance(&source, parse_result.file.language);
                     let file_provenance =
                         extract_provenance(&annotations_with_prov, review_threshold);
 
                     // Add provenance to file entry
                     parse_result.file.annotations = file_provenance;
 
+                    // RFC-0006: Borrow a missing file summary from a paired Markdown doc
+                    if parse_result.file.summary.is_none() {
+                        if let Some(summary) = markdown_bridge
+                            .as_ref()
+                            .and_then(|bridge| bridge.summary_for(Path::new(path)))
+                        {
+                            parse_result.file.annotations.insert(
+                                "@acp:summary".to_string(),
+                                AnnotationProvenance {
+                                    value: summary.clone(),
+                                    source: SourceOrigin::Converted,
+                                    confidence: None,
+                                    needs_review: false,
+                                    reviewed: false,
+                                    reviewed_at: None,
+                                    generated_at: None,
+                                    generation_id: None,
+                                },
+                            );
+                            parse_result.file.summary = Some(summary);
+                        }
+                    }
+
                     // RFC-0006: Detect documentation format and populate bridge metadata
                     if bridge_enabled {
                         let language = language_name_from_enum(parse_result.file.language);
@@ -180,24 +286,58 @@ impl Indexer {
 
                         // Merge: prefer AST symbols but keep annotation metadata
                         if !converted.is_empty() {
-                            // Keep summaries from annotation parser
-                            let annotation_summaries: HashMap<_, _> = parse_result
-                                .symbols
-                                .iter()
-                                .filter_map(|s| {
-                                    s.summary.as_ref().map(|sum| (s.name.clone(), sum.clone()))
-                                })
-                                .collect();
-
-                            parse_result.symbols = converted;
-
-                            // Restore summaries from annotations
+                            // Keep annotation metadata on the AST symbols it describes
+                            let annotated = std::mem::replace(&mut parse_result.symbols, converted);
+                            merge_annotated_symbols(&mut parse_result.symbols, annotated);
+
+                            // Fall back to inline type hints, then put @acp:param
+                            // entries in real signature order
+                            let type_hints = matches!(
+                                parse_result.file.language,
+                                Language::Python | Language::Typescript
+                            );
+                            let mut warned = HashSet::new();
                             for symbol in &mut parse_result.symbols {
-                                if symbol.summary.is_none() {
-                                    if let Some(sum) = annotation_summaries.get(&symbol.name) {
-                                        symbol.summary = Some(sum.clone());
+                                let Some(ast_symbol) = ast_symbols.iter().find(|s| {
+                                    s.name == symbol.name
+                                        && s.start_line == symbol.lines[0]
+                                        && matches!(s.kind, SymbolKind::Function | SymbolKind::Method)
+                                }) else {
+                                    continue;
+                                };
+                                if type_hints {
+                                    let type_info = symbol.type_info.get_or_insert_with(Default::default);
+                                    fill_type_hints(type_info, ast_symbol);
+                                    if type_info.is_empty() {
+                                        symbol.type_info = None;
                                     }
                                 }
+                                let Some(type_info) = symbol.type_info.as_mut() else {
+                                    continue;
+                                };
+                                let alignment = align_params(&mut type_info.params, ast_symbol);
+                                // Exports can be reported twice; warn once per declaration
+                                if !warned.insert((symbol.name.clone(), symbol.lines)) {
+                                    continue;
+                                }
+                                if alignment.reordered {
+                                    warnings.push(IndexWarning {
+                                        file: relative_path.clone(),
+                                        message: format!(
+                                            "@acp:param annotations on {} were out of order; reordered to match the signature",
+                                            symbol.name
+                                        ),
+                                    });
+                                }
+                                for name in alignment.unknown {
+                                    warnings.push(IndexWarning {
+                                        file: relative_path.clone(),
+                                        message: format!(
+                                            "@acp:param {} on {} does not name a parameter",
+                                            name, symbol.name
+                                        ),
+                                    });
+                                }
                             }
 
                             // RFC-0006: Apply bridge merging for symbols with doc comments
@@ -215,14 +355,18 @@ impl Indexer {
                                         })
                                         .collect();
 
+                                    let precedence = bridge_merger.precedence_for(&relative_path);
                                     let mut merged_count = 0u64;
                                     for symbol in &mut parse_result.symbols {
                                         if let Some(doc_comment) =
                                             ast_doc_comments.get(&symbol.name)
                                         {
                                             // Parse native documentation
-                                            let native_docs =
-                                                parse_native_docs(doc_comment, detected_format);
+                                            let native_docs = parse_native_docs(
+                                                doc_comment,
+                                                detected_format,
+                                                &symbol.name,
+                                            );
 
                                             // Extract ACP annotations from doc comment
                                             let acp_annotations = extract_acp_annotations(
@@ -231,10 +375,11 @@ impl Indexer {
                                             );
 
                                             // Merge using bridge merger
-                                            let bridge_result = bridge_merger.merge(
+                                            let bridge_result = bridge_merger.merge_with(
                                                 native_docs.as_ref(),
                                                 *detected_format,
                                                 &acp_annotations,
+                                                precedence,
                                             );
 
                                             // Update symbol with merged data
@@ -244,6 +389,16 @@ impl Indexer {
                                             if bridge_result.directive.is_some() {
                                                 symbol.purpose = bridge_result.directive;
                                             }
+                                            // Native @throws add to, never replace, @acp:throws
+                                            for throws in bridge_result.throws {
+                                                if !symbol
+                                                    .throws
+                                                    .iter()
+                                                    .any(|t| t.exception == throws.exception)
+                                                {
+                                                    symbol.throws.push(throws);
+                                                }
+                                            }
 
                                             // Track merged count
                                             if matches!(bridge_result.source, BridgeSource::Merged)
@@ -257,33 +412,111 @@ impl Indexer {
                             }
                         }
 
-                        // Extract calls from AST
+                        // Extract calls (with their call sites) from AST
                         if let Ok(calls) = ast_parser.parse_calls(Path::new(path), &source) {
                             for call in calls {
                                 if !call.caller.is_empty() {
-                                    parse_result
-                                        .calls
-                                        .push((call.caller.clone(), vec![call.callee.clone()]));
+                                    parse_result.call_sites.push(CallEdge {
+                                        from: call.caller,
+                                        to: call.callee,
+                                        file: parse_result.file.path.clone(),
+                                        line: call.line,
+                                    });
                                 }
                             }
                         }
                     }
+
+                    let language = language_name_from_enum(parse_result.file.language);
+                    if matches!(language, "typescript" | "javascript" | "python") {
+                        file_imports = ast_parser
+                            .extract_imports(&source, language)
+                            .unwrap_or_default();
+                        // Merge real imports with the annotated ones
+                        let imports = &mut parse_result.file.imports;
+                        imports.extend(file_imports.iter().map(|i| i.source.clone()));
+                        normalize_imports(imports);
+                    }
+
+                    if heuristic_edges {
+                        string_refs = heuristic_calls::string_refs(&source);
+                    }
                 }
 
-                Some(parse_result)
+                Ok((parse_result, file_imports, string_refs, warnings))
             })
             .collect();
 
-        // Add git metadata sequentially (git2::Repository is not Sync)
+        let mut results = Vec::with_capacity(parsed.len());
+        let mut failures = Vec::new();
+        let mut warnings = Vec::new();
+        let mut imports_by_file: HashMap<String, Vec<Import>> = HashMap::new();
+        let mut string_refs_by_file = Vec::new();
+        for outcome in parsed {
+            let (parse_result, file_imports, string_refs, file_warnings) = match outcome {
+                Ok(parsed) => parsed,
+                Err(failure) => {
+                    failures.push(failure);
+                    continue;
+                }
+            };
+            if !file_imports.is_empty() {
+                imports_by_file.insert(normalize_path(&parse_result.file.path), file_imports);
+            }
+            string_refs_by_file.push(string_refs);
+            warnings.extend(file_warnings);
+            results.push(parse_result);
+        }
+
+        // Add git metadata; blames run in parallel with a repository handle per
+        // worker (git2::Repository is not Sync), everything else on this thread
         if let Some(ref repo) = git_repo {
+            let reusable = self.reusable_git_metadata(repo);
+            let reused = |file_path: &str| {
+                let (prev, changed) = reusable.as_ref()?;
+                let relative = FileHistory::make_relative_path(repo, git_path(file_path)).ok()?;
+                if changed.contains(&relative) {
+                    return None;
+                }
+                prev.get_file(file_path).filter(|f| f.git.is_some())
+            };
+            let prev_symbol_git: HashMap<&str, &GitSymbolInfo> = reusable
+                .iter()
+                .flat_map(|(prev, _)| prev.symbols.values())
+                .filter_map(|s| Some((s.qualified_name.as_str(), s.git.as_ref()?)))
+                .collect();
+
+            let mut blame_cache = BlameCache::new(repo, 100);
+            let pending: Vec<&Path> = results
+                .iter()
+                .filter(|r| reused(&r.file.path).is_none())
+                .map(|r| git_path(&r.file.path))
+                .collect();
+            // On failure, histories are walked and files blamed one by one below
+            let _ = blame_cache.prefetch_histories(&pending);
+            let _ = blame_cache.prefetch_blames(&pending);
+
             for parse_result in &mut results {
-                let file_path = &parse_result.file.path;
-                // Strip "./" prefix if present - git expects paths like "src/lib.rs" not "./src/lib.rs"
-                let clean_path = file_path.strip_prefix("./").unwrap_or(file_path);
-                let relative_path = Path::new(clean_path);
+                if let Some(prev_file) = reused(&parse_result.file.path) {
+                    let (prev, _) = reusable.as_ref().expect("reuse implies a previous cache");
+                    let elapsed = (Utc::now() - prev.generated_at).num_days().max(0) as u32;
+                    parse_result.file.git = prev_file.git.clone();
+                    for symbol in &mut parse_result.symbols {
+                        symbol.git =
+                            prev_symbol_git
+                                .get(symbol.qualified_name.as_str())
+                                .map(|git| GitSymbolInfo {
+                                    code_age_days: git.code_age_days + elapsed,
+                                    ..(*git).clone()
+                                });
+                    }
+                    continue;
+                }
+
+                let relative_path = git_path(&parse_result.file.path);
 
                 // Add git metadata for the file (only if we have valid git history)
-                if let Ok(history) = FileHistory::for_file(repo, relative_path, 100) {
+                if let Ok(history) = blame_cache.history(relative_path) {
                     if let Some(latest) = history.latest() {
                         // Only set git info if we have actual commit data
                         parse_result.file.git = Some(GitFileInfo {
@@ -297,7 +530,7 @@ impl Indexer {
                 }
 
                 // Add git metadata for symbols using blame
-                if let Ok(blame) = BlameInfo::for_file(repo, relative_path) {
+                if let Some(blame) = blame_cache.blame(relative_path) {
                     for symbol in &mut parse_result.symbols {
                         if let Some(line_blame) =
                             blame.last_modified(symbol.lines[0], symbol.lines[1])
@@ -316,13 +549,89 @@ impl Indexer {
         }
 
         // Build cache from results
-        let mut domains: std::collections::HashMap<String, Vec<String>> =
-            std::collections::HashMap::new();
+        let mut domains: HashMap<String, BTreeMap<String, DomainSource>> = HashMap::new();
+        // Longest `@acp:domain "x" - description` seen for each domain
+        let mut domain_descriptions: HashMap<String, String> = HashMap::new();
+        let classifier = DomainClassifier::from_config(&self.config);
+        // Other spellings seen for each canonical domain name
+        let mut domain_aliases: HashMap<String, BTreeSet<String>> = HashMap::new();
+        let normalize = self
+            .config
+            .domains
+            .as_ref()
+            .map(|d| d.normalize)
+            .unwrap_or_default();
+        let mut canonical = |name: &String| {
+            let canonical = normalize.apply(name);
+            if canonical != *name {
+                domain_aliases
+                    .entry(canonical.clone())
+                    .or_default()
+                    .insert(name.clone());
+            }
+            canonical
+        };
+        let tagger = FileTagger::from_config(&self.config);
         let mut constraint_index = ConstraintIndex::default();
+        let config_locks = self
+            .config
+            .constraints
+            .iter()
+            .flat_map(|c| &c.locks)
+            .map(|(pattern, level)| (pattern.clone(), *level));
+        let mut rules: Vec<_> = config_locks
+            .map(|(pattern, level)| DirectoryLock { pattern, level })
+            .collect();
+        // Tag locks act like directory rules; an explicit rule for the same
+        // pattern wins
+        for (pattern, level) in tagger.locks() {
+            if !rules.iter().any(|r| &r.pattern == pattern) {
+                rules.push(DirectoryLock {
+                    pattern: pattern.clone(),
+                    level: *level,
+                });
+            }
+        }
+        rules.sort_by(|a, b| a.pattern.cmp(&b.pattern));
+        constraint_index.directory_locks = rules;
 
         for result in &results {
+            // Config patterns add domains on top of @acp:domain annotations
+            let mut annotated: Vec<String> = Vec::new();
+            for name in result.file.domains.iter().map(&mut canonical) {
+                if !annotated.contains(&name) {
+                    annotated.push(name);
+                }
+            }
+            let pattern_domains: Vec<String> = classifier
+                .classify(&result.file.path)
+                .iter()
+                .map(&mut canonical)
+                .collect();
+            for (name, description) in &result.domain_descriptions {
+                let name = canonical(name);
+                let current = domain_descriptions.entry(name).or_default();
+                // Ties go to the alphabetically first so reruns agree
+                if (description.len(), std::cmp::Reverse(description))
+                    > (current.len(), std::cmp::Reverse(&*current))
+                {
+                    *current = description.clone();
+                }
+            }
+            let mut file = result.file.clone();
+            file.domains = annotated.clone();
+            for domain in &pattern_domains {
+                if !file.domains.contains(domain) {
+                    file.domains.push(domain.clone());
+                }
+            }
+            file.tags = tagger.tag(&file.path);
+            if tagger.is_internal(&file.tags) {
+                file.lifecycle.get_or_insert_with(Default::default).internal = true;
+            }
+
             // Add file
-            builder = builder.add_file(result.file.clone());
+            builder = builder.add_file(file);
 
             // Add symbols
             for symbol in &result.symbols {
@@ -333,17 +642,36 @@ impl Indexer {
             for (from, to) in &result.calls {
                 builder = builder.add_call_edge(from, to.clone());
             }
+            for edge in &result.call_sites {
+                builder = builder.add_call_site(edge.clone());
+            }
 
-            // Track domains
-            for domain in &result.file.domains {
+            // Track domains; an explicit annotation wins over a pattern match
+            for domain in annotated {
                 domains
-                    .entry(domain.clone())
+                    .entry(domain)
                     .or_default()
-                    .push(result.file.path.clone());
+                    .insert(result.file.path.clone(), DomainSource::Annotation);
             }
+            for domain in pattern_domains {
+                domains
+                    .entry(domain)
+                    .or_default()
+                    .entry(result.file.path.clone())
+                    .or_insert(DomainSource::Pattern);
+            }
+
+            // Directory-level lock applies only when the file has no @acp:lock
+            let inherited = if result.lock_level.is_none() {
+                constraint_index
+                    .directory_lock_for(&result.file.path)
+                    .map(|rule| rule.level)
+            } else {
+                None
+            };
 
             // Build constraints from parse result (RFC-001 compliant)
-            if result.lock_level.is_some() || !result.ai_hints.is_empty() {
+            if result.lock_level.is_some() || (!result.ai_hints.is_empty() && inherited.is_none()) {
                 let lock_level = result
                     .lock_level
                     .as_ref()
@@ -386,6 +714,13 @@ impl Indexer {
                     .entry(level_str)
                     .or_default()
                     .push(result.file.path.clone());
+            } else if let Some(level) = inherited {
+                let level_str = format!("{:?}", level).to_lowercase();
+                constraint_index
+                    .by_lock_level
+                    .entry(level_str)
+                    .or_default()
+                    .push(result.file.path.clone());
             }
 
             // Build hack markers
@@ -415,18 +750,69 @@ impl Indexer {
             }
         }
 
-        // Add domains to cache
-        for (name, files) in domains {
+        // Possible callees from string literals, after every precise edge
+        // is in so duplicates of real calls can be dropped
+        if heuristic_edges {
+            let callables: HashSet<&str> = results
+                .iter()
+                .flat_map(|r| &r.symbols)
+                .filter(|s| heuristic_calls::is_callable(s))
+                .map(|s| s.name.as_str())
+                .collect();
+            for (result, refs) in results.iter().zip(&string_refs_by_file) {
+                let edges = heuristic_calls::heuristic_edges(
+                    &result.file.path,
+                    refs,
+                    &result.symbols,
+                    &callables,
+                );
+                for edge in edges {
+                    builder = builder.add_heuristic_edge(edge);
+                }
+            }
+        }
+
+        // Add domains to cache; a file in several domains lends its symbols
+        // to each of them
+        let file_symbols: HashMap<&str, &[SymbolEntry]> = results
+            .iter()
+            .map(|r| (r.file.path.as_str(), r.symbols.as_slice()))
+            .collect();
+        for (name, membership) in domains {
+            let mut symbols: Vec<String> = membership
+                .keys()
+                .filter_map(|path| file_symbols.get(path.as_str()))
+                .flat_map(|syms| syms.iter().map(|s| s.qualified_name.clone()))
+                .collect();
+            symbols.sort();
+            symbols.dedup();
+            // A description in config wins over one in source
+            let description = self
+                .config
+                .domains
+                .as_ref()
+                .and_then(|d| d.get(&name))
+                .and_then(|d| d.description.clone())
+                .or_else(|| domain_descriptions.remove(&name));
+            let aliases = domain_aliases
+                .remove(&name)
+                .map(|a| a.into_iter().collect())
+                .unwrap_or_default();
             builder = builder.add_domain(DomainEntry {
-                name: name.clone(),
-                files: files.clone(),
-                symbols: vec![],
-                description: None,
+                name,
+                files: membership.keys().cloned().collect(),
+                symbols,
+                description,
+                membership,
+                aliases,
             });
         }
 
         // Add constraints if any were found
-        if !constraint_index.by_file.is_empty() || !constraint_index.hacks.is_empty() {
+        if !constraint_index.by_file.is_empty()
+            || !constraint_index.hacks.is_empty()
+            || !constraint_index.directory_locks.is_empty()
+        {
             builder = builder.set_constraints(constraint_index);
         }
 
@@ -435,74 +821,191 @@ impl Indexer {
 
         // RFC-0015: Compute reverse import graph (imported_by)
         compute_import_graph(&mut cache);
+        mark_recursive_symbols(&mut cache);
+
+        // RFC-0015: Detect naming, import and test conventions
+        let mut convention_files = Vec::with_capacity(cache.files.len());
+        let mut file_languages = HashMap::new();
+        for (path, file) in &cache.files {
+            let path = normalize_path(path);
+            file_languages.insert(
+                path.clone(),
+                language_name_from_enum(file.language).to_string(),
+            );
+            convention_files.push(path);
+        }
+        convention_files.sort();
+        cache.conventions = ConventionsAnalyzer::new().analyze_with_imports(
+            &convention_files,
+            &file_languages,
+            &imports_by_file,
+        );
 
         // RFC-0003: Compute provenance statistics
-        let low_conf_threshold = 0.5; // TODO: Read from config when available
+        let low_conf_threshold = self.config.annotate.provenance.min_confidence;
         cache.provenance = compute_provenance_stats(&cache, low_conf_threshold);
 
         // RFC-0006: Compute bridge statistics
         cache.bridge = compute_bridge_stats(&cache, &self.config.bridge);
 
-        Ok(cache)
+        Ok(IndexReport {
+            cache,
+            failures,
+            warnings,
+        })
+    }
+
+    /// The earlier cache and the files (relative to the repository root)
+    /// changed since it was built, if its git metadata can be reused
+    fn reusable_git_metadata(&self, repo: &GitRepository) -> Option<(&Cache, HashSet<String>)> {
+        let prev = self.previous.as_ref()?;
+        let commit = prev.git_commit.as_deref()?;
+        let mut changed: HashSet<String> = repo.changed_since(commit).ok()?.into_iter().collect();
+        changed.extend(repo.modified_files().ok()?);
+        Some((prev, changed))
     }
 
     /// @acp:summary "Find all files matching include/exclude patterns"
+    ///
+    /// Files matched by a tag with `include` set are added even if excluded.
+    ///
+    /// Stops walking once `limits.max_files` is exceeded and returns an error,
+    /// so a mis-scoped include pattern fails fast instead of exhausting memory.
     fn find_files<P: AsRef<Path>>(&self, root: P) -> Result<Vec<String>> {
-        let root = root.as_ref();
-        let include_patterns: Vec<_> = self
-            .config
-            .include
-            .iter()
-            .filter_map(|p| Pattern::new(p).ok())
-            .collect();
+        let max_files = self.max_files();
+        let files = self.matched_files(root.as_ref(), max_files.saturating_add(1));
+        if files.len() > max_files {
+            return Err(AcpError::Index(format!(
+                "More than {} files match the include patterns (limits.max_files); \
+                 narrow include/exclude or raise the limit",
+                max_files
+            )));
+        }
+        Ok(files)
+    }
 
-        let exclude_patterns: Vec<_> = self
+    /// Files matching include/exclude plus tag-forced ones; the walk stops
+    /// after `limit` files
+    fn matched_files(&self, root: &Path, limit: usize) -> Vec<String> {
+        let mut files: Vec<String> = walk_source_files(
+            root,
+            &self.config.include,
+            &self.config.exclude,
+            self.config.respect_gitignore,
+        )
+        .take(limit)
+        .collect();
+        if files.len() >= limit {
+            return files;
+        }
+
+        let forced: Vec<String> = self
             .config
-            .exclude
-            .iter()
-            .filter_map(|p| Pattern::new(p).ok())
+            .tags
+            .values()
+            .filter(|tag| tag.include)
+            .flat_map(|tag| tag.patterns.iter().cloned())
             .collect();
+        if !forced.is_empty() {
+            let mut seen: HashSet<String> = files.iter().cloned().collect();
+            for path in find_source_files(root, &forced, &[], self.config.respect_gitignore) {
+                // Only files a parser understands are worth indexing
+                if detect_language(&path).is_some() && seen.insert(path.clone()) {
+                    files.push(path);
+                }
+            }
+        }
+        files
+    }
 
-        let files: Vec<String> = WalkDir::new(root)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| e.file_type().is_file())
-            .filter_map(|e| {
-                // Get path relative to root for pattern matching
-                let full_path = e.path().to_string_lossy().to_string();
-                let relative_path = e
-                    .path()
-                    .strip_prefix(root)
-                    .map(|p| p.to_string_lossy().to_string())
-                    .unwrap_or_else(|_| full_path.clone());
+    fn max_files(&self) -> usize {
+        self.config
+            .limits
+            .as_ref()
+            .map_or_else(|| LimitsConfig::default().max_files, |l| l.max_files)
+    }
 
-                // Must match at least one include pattern
-                let match_opts = glob::MatchOptions {
-                    case_sensitive: true,
-                    require_literal_separator: false,
-                    require_literal_leading_dot: false,
-                };
-                let included = include_patterns.is_empty()
-                    || include_patterns
-                        .iter()
-                        .any(|p| p.matches_with(&relative_path, match_opts));
-                // Must not match any exclude pattern
-                let excluded = exclude_patterns
-                    .iter()
-                    .any(|p| p.matches_with(&relative_path, match_opts));
+    fn max_file_bytes(&self) -> u64 {
+        self.config.limits.as_ref().map_or_else(
+            || LimitsConfig::default().max_file_size_mb,
+            |l| l.max_file_size_mb,
+        ) as u64
+            * 1024
+            * 1024
+    }
 
-                if included && !excluded {
-                    Some(full_path)
-                } else {
-                    None
+    /// @acp:summary "Report what `index` would cover without parsing anything"
+    ///
+    /// Walks the tree like `index` and reads only file sizes. Unlike `index`
+    /// it does not stop at `limits.max_files`; the plan records whether the
+    /// limit would be hit. Gitignored and `.acpignore`d files are not reported.
+    pub fn plan<P: AsRef<Path>>(&self, root: P) -> IndexPlan {
+        let root = root.as_ref();
+        let max_file_bytes = self.max_file_bytes();
+        let files = self.matched_files(root, usize::MAX);
+        let relative = |path: &str| {
+            Path::new(path)
+                .strip_prefix(root)
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|_| path.to_string())
+        };
+
+        let mut plan = IndexPlan {
+            max_files: self.max_files(),
+            exceeds_max_files: files.len() > self.max_files(),
+            ..Default::default()
+        };
+        for path in &files {
+            let size_bytes = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+            let reason = match detect_language(path) {
+                None => SkipReason::Unsupported,
+                Some(_) if size_bytes > max_file_bytes => SkipReason::Oversized,
+                Some(language) => {
+                    let name = serde_json::to_value(language)
+                        .ok()
+                        .and_then(|v| v.as_str().map(str::to_string))
+                        .unwrap_or_default();
+                    plan.add_file(name, size_bytes);
+                    continue;
                 }
-            })
-            .collect();
+            };
+            plan.skipped.push(PlannedSkip {
+                path: relative(path),
+                reason,
+                size_bytes,
+            });
+        }
+        plan.skipped.sort_by(|a, b| a.path.cmp(&b.path));
 
-        Ok(files)
+        // Walk again without excludes to see what each pattern removed
+        let kept: HashSet<&String> = files.iter().collect();
+        let exclude: Vec<(&String, Pattern)> = self
+            .config
+            .exclude
+            .iter()
+            .filter_map(|p| Some((p, Pattern::new(p).ok()?)))
+            .collect();
+        let walk = walk_source_files(
+            root,
+            &self.config.include,
+            &[],
+            self.config.respect_gitignore,
+        );
+        for path in walk.filter(|p| !kept.contains(p)) {
+            let path = relative(&path);
+            if let Some((pattern, _)) = exclude
+                .iter()
+                .find(|(_, p)| p.matches_with(&path, MATCH_OPTIONS))
+            {
+                *plan.excluded.entry(pattern.to_string()).or_default() += 1;
+            }
+        }
+        plan
     }
 
     /// @acp:summary "Generate vars file from cache (schema-compliant)"
+    ///
+    /// Every entry is marked `generated`; see [`VarsFile::keep_manual`].
     pub fn generate_vars(&self, cache: &Cache) -> VarsFile {
         let mut vars_file = VarsFile::new();
 
@@ -511,7 +1014,7 @@ impl Indexer {
             std::collections::HashMap::new();
         for (name, symbol) in &cache.symbols {
             if symbol.exported {
-                let var_name = format!("SYM_{}", name.to_uppercase().replace('.', "_"));
+                let var_name = symbol_var_name(name);
                 symbol_to_var.insert(name.clone(), var_name);
             }
         }
@@ -519,7 +1022,7 @@ impl Indexer {
         // Generate symbol vars with refs from call graph
         for (name, symbol) in &cache.symbols {
             if symbol.exported {
-                let var_name = format!("SYM_{}", name.to_uppercase().replace('.', "_"));
+                let var_name = symbol_var_name(name);
 
                 // Build refs from symbols this one calls
                 let refs: Vec<String> = symbol
@@ -535,6 +1038,7 @@ impl Indexer {
                     refs,
                     source: Some(symbol.file.clone()),
                     lines: Some(symbol.lines),
+                    generated: false,
                 };
 
                 vars_file.add_variable(var_name, entry);
@@ -591,10 +1095,191 @@ impl Indexer {
             );
         }
 
+        for entry in vars_file.variables.values_mut() {
+            entry.generated = true;
+        }
         vars_file
     }
 }
 
+/// Path git should use for a cache path
+///
+/// Strips the "./" prefix - git expects paths like "src/lib.rs" not "./src/lib.rs"
+fn git_path(path: &str) -> &Path {
+    Path::new(path.strip_prefix("./").unwrap_or(path))
+}
+
+/// @acp:summary "Walk a tree for files matching include/exclude globs"
+///
+/// Patterns match paths relative to `root`; returned paths are joined to `root`.
+/// With `respect_gitignore`, files ignored by `.gitignore` (nested files and
+/// `!` negations included), `.git/info/exclude` or a `.gitignore` above `root`
+/// are skipped before the globs are applied. Files matched by an
+/// [`ACPIGNORE_FILE`] are always skipped.
+pub fn find_source_files<P: AsRef<Path>>(
+    root: P,
+    include: &[String],
+    exclude: &[String],
+    respect_gitignore: bool,
+) -> Vec<String> {
+    walk_source_files(root, include, exclude, respect_gitignore).collect()
+}
+
+/// Lazy form of [`find_source_files`], for callers that stop early
+fn walk_source_files<P: AsRef<Path>>(
+    root: P,
+    include: &[String],
+    exclude: &[String],
+    respect_gitignore: bool,
+) -> impl Iterator<Item = String> {
+    let root = root.as_ref().to_path_buf();
+    let include_patterns: Vec<_> = include
+        .iter()
+        .filter_map(|p| Pattern::new(p).ok())
+        .collect();
+
+    let exclude_patterns: Vec<_> = exclude
+        .iter()
+        .filter_map(|p| Pattern::new(p).ok())
+        .collect();
+
+    // Hidden files and .ignore files are not filtered, matching a plain walk;
+    // the global gitignore is skipped so results don't depend on the machine
+    WalkBuilder::new(&root)
+        .standard_filters(false)
+        .git_ignore(respect_gitignore)
+        .git_exclude(respect_gitignore)
+        .parents(respect_gitignore)
+        .require_git(false)
+        .add_custom_ignore_filename(ACPIGNORE_FILE)
+        .build()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_some_and(|t| t.is_file()))
+        .filter_map(move |e| {
+            // Get path relative to root for pattern matching
+            let full_path = e.path().to_string_lossy().to_string();
+            let relative_path = e
+                .path()
+                .strip_prefix(&root)
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|_| full_path.clone());
+
+            // Must match at least one include pattern
+            let included = include_patterns.is_empty()
+                || include_patterns
+                    .iter()
+                    .any(|p| p.matches_with(&relative_path, MATCH_OPTIONS));
+            // Must not match any exclude pattern
+            let excluded = exclude_patterns
+                .iter()
+                .any(|p| p.matches_with(&relative_path, MATCH_OPTIONS));
+
+            if included && !excluded {
+                Some(full_path)
+            } else {
+                None
+            }
+        })
+}
+
+/// Count lines by streaming the file in chunks, so huge files are never
+/// held in memory at once
+fn count_lines(path: &Path) -> std::io::Result<usize> {
+    use std::io::Read;
+
+    let mut file = fs::File::open(path)?;
+    let mut buf = [0u8; 64 * 1024];
+    let mut lines = 0;
+    let mut last = b'\n';
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        lines += buf[..n].iter().filter(|&&b| b == b'\n').count();
+        last = buf[n - 1];
+    }
+    // A final line without a trailing newline still counts, as with str::lines
+    Ok(lines + usize::from(last != b'\n'))
+}
+
+/// @acp:summary "Assigns files to domains using the config's glob patterns"
+///
+/// A file joins every domain with a matching pattern. Patterns are matched
+/// against the file's path relative to the project root.
+pub struct DomainClassifier {
+    rules: NamedGlobs,
+}
+
+impl DomainClassifier {
+    /// Compile `config.domains`, ignoring invalid patterns
+    pub fn from_config(config: &Config) -> Self {
+        let rules = config
+            .domains
+            .iter()
+            .flat_map(|d| &d.patterns)
+            .map(|(name, domain)| (name, &domain.patterns));
+        Self {
+            rules: NamedGlobs::new(rules),
+        }
+    }
+
+    /// Names of the domains whose patterns match `path`, sorted
+    pub fn classify(&self, path: &str) -> Vec<String> {
+        self.rules.matching(path)
+    }
+}
+
+/// @acp:summary "Labels files using the config's `tags` glob patterns"
+///
+/// Matching follows [`DomainClassifier`].
+pub struct FileTagger {
+    rules: NamedGlobs,
+    internal: HashSet<String>,
+    locks: Vec<(String, LockLevel)>,
+}
+
+impl FileTagger {
+    /// Compile `config.tags`
+    pub fn from_config(config: &Config) -> Self {
+        let rules = NamedGlobs::new(config.tags.iter().map(|(name, tag)| (name, &tag.patterns)));
+        let internal = config
+            .tags
+            .iter()
+            .filter(|(_, tag)| tag.internal)
+            .map(|(name, _)| name.clone())
+            .collect();
+        let mut locks: Vec<_> = config
+            .tags
+            .values()
+            .filter_map(|tag| Some((tag.patterns.iter(), tag.lock?)))
+            .flat_map(|(patterns, level)| patterns.map(move |p| (p.clone(), level)))
+            .collect();
+        locks.sort_by(|a, b| a.0.cmp(&b.0));
+
+        Self {
+            rules,
+            internal,
+            locks,
+        }
+    }
+
+    /// Names of the tags whose patterns match `path`, sorted
+    pub fn tag(&self, path: &str) -> Vec<String> {
+        self.rules.matching(path)
+    }
+
+    /// Whether any of the tags marks files as internal
+    pub fn is_internal(&self, tags: &[String]) -> bool {
+        tags.iter().any(|t| self.internal.contains(t))
+    }
+
+    /// `(pattern, level)` lock rules implied by tags, sorted by pattern
+    pub fn locks(&self) -> &[(String, LockLevel)] {
+        &self.locks
+    }
+}
+
 /// Detect language from file extension
 pub fn detect_language(path: &str) -> Option<Language> {
     let path = Path::new(path);
@@ -610,7 +1295,7 @@ pub fn detect_language(path: &str) -> Option<Language> {
         "cs" => Some(Language::CSharp),
         "cpp" | "cxx" | "cc" | "hpp" | "hxx" => Some(Language::Cpp),
         "c" | "h" => Some(Language::C),
-        "rb" => Some(Language::Ruby),
+        "rb" | "rake" => Some(Language::Ruby),
         "php" => Some(Language::Php),
         "swift" => Some(Language::Swift),
         "kt" | "kts" => Some(Language::Kotlin),
@@ -618,8 +1303,56 @@ pub fn detect_language(path: &str) -> Option<Language> {
     }
 }
 
+/// Var name for a symbol cache key; qualified keys (`src/a.ts:run`) flatten to `SYM_SRC_A_TS_RUN`
+fn symbol_var_name(key: &str) -> String {
+    let flat: String = key
+        .to_uppercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("SYM_{}", flat)
+}
+
+/// Infer a symbol's visibility from its language's naming conventions
+///
+/// Python marks private names with a leading underscore (dunders such as
+/// `__init__` stay public) and Go exports only capitalized names. Other
+/// languages rely on the visibility the AST extractor found.
+pub fn infer_visibility(
+    name: &str,
+    language: Option<Language>,
+    ast_visibility: AstVisibility,
+) -> Visibility {
+    let from_ast = match ast_visibility {
+        AstVisibility::Public => Visibility::Public,
+        AstVisibility::Private => Visibility::Private,
+        AstVisibility::Protected => Visibility::Protected,
+        AstVisibility::Internal | AstVisibility::Crate => Visibility::Private,
+    };
+
+    match language {
+        Some(Language::Python) => {
+            let dunder = name.len() > 4 && name.starts_with("__") && name.ends_with("__");
+            if from_ast == Visibility::Public && name.starts_with('_') && !dunder {
+                Visibility::Private
+            } else {
+                from_ast
+            }
+        }
+        Some(Language::Go) => {
+            if name.chars().next().is_some_and(char::is_uppercase) {
+                Visibility::Public
+            } else {
+                Visibility::Private
+            }
+        }
+        _ => from_ast,
+    }
+}
+
 /// Convert AST-extracted symbols to cache SymbolEntry format
 fn convert_ast_symbols(ast_symbols: &[ExtractedSymbol], file_path: &str) -> Vec<SymbolEntry> {
+    let language = detect_language(file_path);
     ast_symbols
         .iter()
         .map(|sym| {
@@ -642,12 +1375,7 @@ fn convert_ast_symbols(ast_symbols: &[ExtractedSymbol], file_path: &str) -> Vec<
                 SymbolKind::Impl => SymbolType::Class,      // Map impl to class
             };
 
-            let visibility = match sym.visibility {
-                AstVisibility::Public => Visibility::Public,
-                AstVisibility::Private => Visibility::Private,
-                AstVisibility::Protected => Visibility::Protected,
-                AstVisibility::Internal | AstVisibility::Crate => Visibility::Private,
-            };
+            let visibility = infer_visibility(&sym.name, language, sym.visibility);
 
             let qualified_name = sym
                 .qualified_name
@@ -660,7 +1388,7 @@ fn convert_ast_symbols(ast_symbols: &[ExtractedSymbol], file_path: &str) -> Vec<
                 symbol_type,
                 file: file_path.to_string(),
                 lines: [sym.start_line, sym.end_line],
-                exported: matches!(sym.visibility, AstVisibility::Public),
+                exported: visibility == Visibility::Public,
                 signature: sym.signature.clone(),
                 summary: sym.doc_comment.clone(),
                 purpose: None, // RFC-001: Populated from @acp:fn/@acp:class annotations
@@ -678,11 +1406,75 @@ fn convert_ast_symbols(ast_symbols: &[ExtractedSymbol], file_path: &str) -> Vec<
                 performance: None,
                 // RFC-0008: Type annotation info
                 type_info: None,
+                throws: vec![],   // Populated from @acp:throws and bridged docs
+                recursive: false, // Set after the call graph is built
+                extra: serde_json::Map::new(),
             }
         })
         .collect()
 }
 
+/// @acp:summary "Copy annotation-derived fields onto the matching AST symbols"
+///
+/// Each annotation symbol (`@acp:fn`, `@acp:symbol`, ...) is matched to one AST
+/// symbol of the same name: the one whose body contains the annotation line
+/// (Python docstrings), else the nearest one starting below it, else the
+/// nearest one above. Matched symbols are claimed, so same-named methods in
+/// one file keep their own summary, lock, throws, lifecycle and behavior.
+/// Fields the AST already filled in are kept.
+fn merge_annotated_symbols(symbols: &mut [SymbolEntry], annotated: Vec<SymbolEntry>) {
+    let mut claimed = vec![false; symbols.len()];
+    for from in annotated {
+        let line = from.lines[0];
+        let best = symbols
+            .iter()
+            .enumerate()
+            .filter(|(i, s)| !claimed[*i] && s.name == from.name)
+            .min_by_key(|(_, s)| {
+                let [start, end] = s.lines;
+                if (start..=end).contains(&line) {
+                    (0, end - start)
+                } else if start > line {
+                    (1, start - line)
+                } else {
+                    (2, line - end)
+                }
+            })
+            .map(|(i, _)| i);
+        let Some(best) = best else {
+            continue;
+        };
+        // A declaration the AST reported twice (e.g. via its export) is one match
+        let lines = symbols[best].lines;
+        for (i, symbol) in symbols.iter_mut().enumerate() {
+            if claimed[i] || symbol.name != from.name || symbol.lines != lines {
+                continue;
+            }
+            claimed[i] = true;
+            merge_annotated_symbol(symbol, from.clone());
+        }
+    }
+}
+
+/// Fill the fields an AST symbol lacks from its annotation symbol
+fn merge_annotated_symbol(symbol: &mut SymbolEntry, from: SymbolEntry) {
+    symbol.summary = symbol.summary.take().or(from.summary);
+    symbol.purpose = symbol.purpose.take().or(from.purpose);
+    symbol.constraints = symbol.constraints.take().or(from.constraints);
+    symbol.behavioral = symbol.behavioral.take().or(from.behavioral);
+    symbol.lifecycle = symbol.lifecycle.take().or(from.lifecycle);
+    symbol.documentation = symbol.documentation.take().or(from.documentation);
+    symbol.performance = symbol.performance.take().or(from.performance);
+    symbol.type_info = symbol.type_info.take().or(from.type_info);
+    if symbol.throws.is_empty() {
+        symbol.throws = from.throws;
+    }
+    if symbol.calls.is_empty() {
+        symbol.calls = from.calls;
+    }
+    symbol.async_fn |= from.async_fn;
+}
+
 // ============================================================================
 // RFC-0003: Annotation Provenance Functions
 // ============================================================================
@@ -922,7 +1714,11 @@ fn format_to_string(format: &SourceFormat) -> String {
 }
 
 /// Parse native documentation from a doc comment based on detected format
-fn parse_native_docs(doc_comment: &str, format: &SourceFormat) -> Option<ParsedDocumentation> {
+fn parse_native_docs(
+    doc_comment: &str,
+    format: &SourceFormat,
+    symbol_name: &str,
+) -> Option<ParsedDocumentation> {
     let parsed = match format {
         SourceFormat::Jsdoc => JsDocParser::new().parse(doc_comment),
         SourceFormat::DocstringGoogle
@@ -930,7 +1726,9 @@ fn parse_native_docs(doc_comment: &str, format: &SourceFormat) -> Option<ParsedD
         | SourceFormat::DocstringSphinx => DocstringParser::new().parse(doc_comment),
         SourceFormat::Rustdoc => RustdocParser::new().parse(doc_comment),
         SourceFormat::Javadoc => JavadocParser::new().parse(doc_comment),
-        SourceFormat::Godoc => GodocParser::new().parse(doc_comment),
+        SourceFormat::Godoc => GodocParser::new()
+            .with_element_name(symbol_name)
+            .parse(doc_comment),
         SourceFormat::Acp | SourceFormat::TypeHint => return None,
     };
 
@@ -948,13 +1746,13 @@ fn extract_acp_annotations(doc_comment: &str, parser: &Parser) -> AcpAnnotations
     let mut result = AcpAnnotations::default();
 
     for ann in annotations {
-        match ann.name.as_str() {
-            "summary" => {
+        match AnnotationKey::from_name(&ann.name) {
+            Some(AnnotationKey::Summary) => {
                 if let Some(ref value) = ann.value {
                     result.summary = Some(value.clone());
                 }
             }
-            "fn" | "method" => {
+            Some(AnnotationKey::Fn | AnnotationKey::Method) => {
                 // @acp:fn "summary" - directive
                 // The parser already extracts value and directive separately
                 if let Some(ref value) = ann.value {
@@ -970,7 +1768,7 @@ fn extract_acp_annotations(doc_comment: &str, parser: &Parser) -> AcpAnnotations
                     result.directive = Some(directive.clone());
                 }
             }
-            "param" => {
+            Some(AnnotationKey::Param) => {
                 // @acp:param {type} name - directive
                 // Extract name from value, directive is already parsed
                 if let Some(ref value) = ann.value {
@@ -980,7 +1778,7 @@ fn extract_acp_annotations(doc_comment: &str, parser: &Parser) -> AcpAnnotations
                     }
                 }
             }
-            "returns" => {
+            Some(AnnotationKey::Returns) => {
                 // @acp:returns {type} - directive
                 // Directive is already parsed by the Parser
                 if let Some(ref directive) = ann.directive {
@@ -992,7 +1790,7 @@ fn extract_acp_annotations(doc_comment: &str, parser: &Parser) -> AcpAnnotations
                     }
                 }
             }
-            "throws" => {
+            Some(AnnotationKey::Throws | AnnotationKey::Raises) => {
                 // @acp:throws {exception} - directive
                 if let Some(ref value) = ann.value {
                     // Extract exception type from value
@@ -1105,6 +1903,49 @@ fn parse_returns_annotation(value: &str) -> Option<String> {
     }
 }
 
+// ============================================================================
+// Call Graph Post-processing
+// ============================================================================
+
+/// Longest call cycle (in calls) that marks its members recursive
+const MAX_RECURSION_CYCLE: usize = 3;
+
+/// Set `recursive` on symbols that call themselves through the call graph
+///
+/// The graph is keyed by simple name, so a name defined more than once
+/// can't be told apart from a call to another definition (`new` calling
+/// `Inner::new`); those names are dropped from the graph before searching.
+fn mark_recursive_symbols(cache: &mut Cache) {
+    let Some(graph) = &cache.graph else {
+        return;
+    };
+    let ambiguous: HashSet<String> = cache
+        .ambiguous_symbol_names()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    let unambiguous = CallGraph {
+        forward: graph
+            .forward
+            .iter()
+            .filter(|(caller, _)| !ambiguous.contains(*caller))
+            .map(|(caller, callees)| {
+                let callees = callees
+                    .iter()
+                    .filter(|callee| !ambiguous.contains(*callee))
+                    .cloned()
+                    .collect();
+                (caller.clone(), callees)
+            })
+            .collect(),
+        ..Default::default()
+    };
+    let recursive = unambiguous.recursive_nodes(MAX_RECURSION_CYCLE);
+    for symbol in cache.symbols.values_mut() {
+        symbol.recursive = recursive.contains(&symbol.name);
+    }
+}
+
 // ============================================================================
 // RFC-0015: Import Graph Computation
 // ============================================================================
@@ -1155,6 +1996,7 @@ fn compute_import_graph(cache: &mut Cache) {
 ///
 /// Handles:
 /// - Relative imports: `./utils`, `../lib/helper`
+/// - Importers under an absolute root (indexing `/abs/project`)
 /// - Index file resolution: `./utils` -> `./utils/index.ts`
 /// - Extension resolution: `./utils` -> `./utils.ts`
 fn resolve_import_path(
@@ -1175,7 +2017,13 @@ fn resolve_import_path(
         } else {
             format!("{}/{}", importer_dir, import_source)
         };
-        crate::cache::normalize_path(&combined)
+        let normalized = crate::cache::normalize_path(&combined);
+        // Keep the root of an absolute importer (indexing an absolute path)
+        if importer_dir.starts_with('/') {
+            format!("/{}", normalized)
+        } else {
+            normalized
+        }
     } else {
         // Absolute import (starts with /)
         crate::cache::normalize_path(import_source)
@@ -1221,3 +2069,824 @@ fn resolve_import_path(
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{ModuleSystem, PathStyle, TypeSource};
+    use crate::config::{DomainNormalize, DomainPatternConfig, DomainsConfig, TagConfig};
+    use crate::index::SkipReason;
+
+    #[test]
+    fn test_domain_classifier_matches_all_patterns() {
+        let mut config = Config::default();
+        let domain = |patterns: &[&str]| DomainPatternConfig {
+            patterns: patterns.iter().map(|p| p.to_string()).collect(),
+            ..Default::default()
+        };
+        config.domains = Some(
+            HashMap::from([
+                ("auth".to_string(), domain(&["src/auth/**"])),
+                (
+                    "api".to_string(),
+                    domain(&["src/**/routes.ts", "src/api/**"]),
+                ),
+                ("broken".to_string(), domain(&["[invalid"])),
+            ])
+            .into(),
+        );
+        let classifier = DomainClassifier::from_config(&config);
+
+        assert_eq!(
+            classifier.classify("./src/auth/routes.ts"),
+            vec!["api".to_string(), "auth".to_string()]
+        );
+        assert_eq!(classifier.classify("src/api/users.ts"), vec!["api"]);
+        assert!(classifier.classify("src/util.ts").is_empty());
+        assert!(DomainClassifier::from_config(&Config::default())
+            .classify("src/auth/login.ts")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_index_enforces_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("small.ts"), "export function a() {}\n").unwrap();
+        let bundle = "var x = 1;\n".repeat(120_000);
+        fs::write(root.join("bundle.js"), format!("{}tail", bundle)).unwrap();
+
+        let limits = |max_file_size_mb, max_files| LimitsConfig {
+            max_file_size_mb,
+            max_files,
+            ..Default::default()
+        };
+        let config = Config {
+            include: vec!["*.ts".to_string(), "*.js".to_string()],
+            limits: Some(limits(1, 10)),
+            ..Default::default()
+        };
+        let cache = Indexer::new(config.clone())
+            .unwrap()
+            .index(root)
+            .await
+            .unwrap();
+        assert_eq!(cache.stats.files, 1);
+        assert_eq!(cache.stats.skipped.len(), 1);
+        assert_eq!(cache.stats.skipped[0].path, "bundle.js");
+        assert_eq!(cache.stats.skipped[0].lines, 120_001);
+        assert_eq!(cache.source_files.len(), 2);
+
+        let config = Config {
+            limits: Some(limits(1, 1)),
+            ..config
+        };
+        let err = Indexer::new(config).unwrap().index(root).await.unwrap_err();
+        assert!(err.to_string().contains("limits.max_files"));
+    }
+
+    #[test]
+    fn test_plan_reports_scope_without_parsing() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        fs::write(root.join("src/a.ts"), "export function a() {}\n").unwrap();
+        fs::write(root.join("src/b.py"), "def b():\n    pass\n").unwrap();
+        fs::write(root.join("src/notes.txt"), "todo\n").unwrap();
+        fs::write(root.join("src/bundle.js"), "var x = 1;\n".repeat(120_000)).unwrap();
+        fs::write(root.join("src/a.test.ts"), "test('a', () => {});\n").unwrap();
+        fs::write(root.join("node_modules/pkg/index.ts"), "export {};\n").unwrap();
+
+        let config = Config {
+            include: vec!["**/*".to_string()],
+            exclude: vec!["**/node_modules/**".to_string(), "**/*.test.ts".to_string()],
+            limits: Some(LimitsConfig {
+                max_file_size_mb: 1,
+                max_files: 3,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let plan = Indexer::new(config).unwrap().plan(root);
+
+        assert_eq!(plan.total_files, 2);
+        assert_eq!(plan.total_bytes, 23 + 18);
+        assert_eq!(plan.estimated_lines, 2);
+        let languages: Vec<_> = plan
+            .languages
+            .iter()
+            .map(|(name, l)| (name.as_str(), l.files))
+            .collect();
+        assert_eq!(languages, [("python", 1), ("typescript", 1)]);
+
+        let skipped: Vec<_> = plan
+            .skipped
+            .iter()
+            .map(|s| (s.path.as_str(), s.reason))
+            .collect();
+        assert_eq!(
+            skipped,
+            [
+                ("src/bundle.js", SkipReason::Oversized),
+                ("src/notes.txt", SkipReason::Unsupported),
+            ]
+        );
+        // Four files match, one more than limits.max_files
+        assert!(plan.exceeds_max_files);
+        assert_eq!(plan.excluded["**/node_modules/**"], 1);
+        assert_eq!(plan.excluded["**/*.test.ts"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_index_output_is_deterministic() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/api")).unwrap();
+        for i in 0..12 {
+            let source = format!(
+                "// @acp:domain api\nexport function handler{i}() {{ return helper{i}(); }}\n\
+                 export function helper{i}() {{ return log(); }}\nfunction log() {{}}\n"
+            );
+            fs::write(root.join(format!("src/api/h{}.ts", i)), source).unwrap();
+        }
+        let config = Config {
+            include: vec!["src/**/*.ts".to_string()],
+            domains: Some(
+                HashMap::from([(
+                    "handlers".to_string(),
+                    DomainPatternConfig {
+                        patterns: vec!["src/api/**".to_string()],
+                        ..Default::default()
+                    },
+                )])
+                .into(),
+            ),
+            ..Default::default()
+        };
+
+        let mut outputs = Vec::new();
+        for run in 0..2 {
+            let mut cache = Indexer::new(config.clone())
+                .unwrap()
+                .index(root)
+                .await
+                .unwrap();
+            assert_eq!(cache.stats.files, 12);
+            cache.generated_at = DateTime::UNIX_EPOCH;
+            let out = root.join(format!("cache{}.json", run));
+            cache.write_json(&out).unwrap();
+            outputs.push(fs::read(out).unwrap());
+        }
+        assert!(
+            outputs[0] == outputs[1],
+            "cache output differs between runs"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_index_reports_parse_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("good.ts"), "export function a() {}\n").unwrap();
+        fs::write(root.join("bad.ts"), b"export const s = '\xff\xfe';\n").unwrap();
+        let config = Config {
+            include: vec!["*.ts".to_string()],
+            ..Default::default()
+        };
+        let indexer = Indexer::new(config).unwrap();
+
+        let report = indexer.index_with_report(root).await.unwrap();
+        assert_eq!(report.cache.stats.files, 1);
+        assert_eq!(report.failures.len(), 1);
+        assert!(report.failures[0].0.ends_with("bad.ts"));
+        assert!(matches!(report.failures[0].1, AcpError::Io(_)));
+
+        // The plain entry point still indexes what it can
+        assert_eq!(indexer.index(root).await.unwrap().stats.files, 1);
+    }
+
+    #[tokio::test]
+    async fn test_index_populates_domain_symbols() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/auth")).unwrap();
+        fs::write(
+            root.join("src/auth/login.ts"),
+            "// @acp:domain api\nexport function login() {}\nexport function logout() {}\n",
+        )
+        .unwrap();
+        fs::write(root.join("src/util.ts"), "export function helper() {}\n").unwrap();
+        let config = Config {
+            include: vec!["src/**/*.ts".to_string()],
+            domains: Some(
+                HashMap::from([(
+                    "auth".to_string(),
+                    DomainPatternConfig {
+                        patterns: vec!["**/auth/**".to_string()],
+                        description: Some("Sign-in and sessions".to_string()),
+                    },
+                )])
+                .into(),
+            ),
+            ..Default::default()
+        };
+        let cache = Indexer::new(config).unwrap().index(root).await.unwrap();
+
+        // login.ts is in both domains, so both list its symbols
+        let auth = &cache.domains["auth"];
+        assert_eq!(auth.description.as_deref(), Some("Sign-in and sessions"));
+        assert_eq!(auth.symbols.len(), 2);
+        assert!(auth.symbols.iter().any(|s| s.ends_with("login")));
+        let api = &cache.domains["api"];
+        assert_eq!(api.symbols, auth.symbols);
+        assert!(api.description.is_none());
+        assert!(!auth.symbols.iter().any(|s| s.ends_with("helper")));
+    }
+
+    #[tokio::test]
+    async fn test_index_takes_domain_description_from_directive() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src/invoice.ts"),
+            "// @acp:domain \"payments\" - Handles billing and invoicing\nexport function bill() {}\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/refund.ts"),
+            "// @acp:domain payments - Billing\n// @acp:domain audit\nexport function refund() {}\n",
+        )
+        .unwrap();
+        let config = Config {
+            include: vec!["src/**/*.ts".to_string()],
+            ..Default::default()
+        };
+        let cache = Indexer::new(config).unwrap().index(root).await.unwrap();
+
+        // The longest description wins, whichever file is indexed first
+        let payments = &cache.domains["payments"];
+        assert_eq!(
+            payments.description.as_deref(),
+            Some("Handles billing and invoicing")
+        );
+        assert_eq!(payments.files.len(), 2);
+        assert!(cache.domains["audit"].description.is_none());
+        let refund = cache.files.values().find(|f| f.path.ends_with("refund.ts"));
+        assert_eq!(refund.unwrap().domains, ["payments", "audit"]);
+    }
+
+    #[tokio::test]
+    async fn test_index_orders_annotated_params() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src/net.ts"),
+            "// @acp:fn \"connect\" - Open a connection\n\
+             // @acp:param {number} port - Port\n\
+             // @acp:param {boolean} secure - Removed parameter\n\
+             // @acp:param {string} host - Host name\n\
+             export function connect(host: string, port: number) {}\n",
+        )
+        .unwrap();
+        let config = Config {
+            include: vec!["src/**/*.ts".to_string()],
+            ..Default::default()
+        };
+        let report = Indexer::new(config)
+            .unwrap()
+            .index_with_report(root)
+            .await
+            .unwrap();
+        let cache = report.cache;
+
+        let symbol = cache
+            .symbols
+            .values()
+            .find(|s| s.name == "connect")
+            .unwrap();
+        let params: Vec<_> = symbol
+            .type_info
+            .as_ref()
+            .unwrap()
+            .params
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(params, vec!["host", "port", "secure"]);
+
+        // Reported once each, even though the export yields two symbols
+        let messages: Vec<_> = report.warnings.iter().map(|w| w.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "@acp:param annotations on connect were out of order; reordered to match the signature",
+                "@acp:param secure on connect does not name a parameter",
+            ]
+        );
+        assert!(report.warnings.iter().all(|w| w.file == "src/net.ts"));
+    }
+
+    #[tokio::test]
+    async fn test_index_uses_python_type_hints() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src/fmt.py"),
+            "def pad(text: str, width: int = 8) -> str:\n    return text.ljust(width)\n",
+        )
+        .unwrap();
+        let config = Config {
+            include: vec!["src/**/*.py".to_string()],
+            ..Default::default()
+        };
+        let cache = Indexer::new(config).unwrap().index(root).await.unwrap();
+
+        let symbol = cache.symbols.values().find(|s| s.name == "pad").unwrap();
+        let type_info = symbol.type_info.as_ref().unwrap();
+        let params: Vec<_> = type_info
+            .params
+            .iter()
+            .map(|p| (p.name.as_str(), p.r#type.as_deref(), p.type_source))
+            .collect();
+        assert_eq!(
+            params,
+            vec![
+                ("text", Some("str"), Some(TypeSource::TypeHint)),
+                ("width", Some("int"), Some(TypeSource::TypeHint)),
+            ]
+        );
+        assert_eq!(type_info.params[1].default.as_deref(), Some("8"));
+        let returns = type_info.returns.as_ref().unwrap();
+        assert_eq!(returns.r#type.as_deref(), Some("str"));
+        assert_eq!(returns.type_source, Some(TypeSource::TypeHint));
+    }
+
+    #[tokio::test]
+    async fn test_annotated_param_type_overrides_hint() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src/users.ts"),
+            "// @acp:fn \"find\" - Look up a user\n\
+             // @acp:param {UserId} id - User id\n\
+             // @acp:param limit - Max results\n\
+             export function find(id: string, limit: number): User[] {}\n",
+        )
+        .unwrap();
+        let config = Config {
+            include: vec!["src/**/*.ts".to_string()],
+            ..Default::default()
+        };
+        let cache = Indexer::new(config).unwrap().index(root).await.unwrap();
+
+        let symbol = cache.symbols.values().find(|s| s.name == "find").unwrap();
+        let type_info = symbol.type_info.as_ref().unwrap();
+        let params: Vec<_> = type_info
+            .params
+            .iter()
+            .map(|p| (p.name.as_str(), p.r#type.as_deref(), p.type_source))
+            .collect();
+        assert_eq!(
+            params,
+            vec![
+                ("id", Some("UserId"), Some(TypeSource::Acp)),
+                ("limit", Some("number"), Some(TypeSource::TypeHint)),
+            ]
+        );
+        assert!(type_info.params[1].directive.is_some());
+        assert_eq!(
+            type_info.returns.as_ref().unwrap().r#type.as_deref(),
+            Some("User[]")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_low_confidence_uses_min_confidence() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src/app.ts"),
+            "// @acp:summary \"App entry\"\n\
+             // @acp:source heuristic\n\
+             // @acp:source-confidence 0.6\n\
+             export const app = 1;\n",
+        )
+        .unwrap();
+        let low = |provenance: serde_json::Value| async move {
+            let config: Config = serde_json::from_value(serde_json::json!({
+                "include": ["src/**/*.ts"],
+                "annotate": { "provenance": provenance },
+            }))
+            .unwrap();
+            let cache = Indexer::new(config).unwrap().index(root).await.unwrap();
+            cache
+                .provenance
+                .low_confidence
+                .iter()
+                .map(|e| (e.annotation.clone(), e.confidence))
+                .collect::<Vec<_>>()
+        };
+
+        // The default cutoff is 0.5
+        assert!(low(serde_json::json!({})).await.is_empty());
+        assert_eq!(
+            low(serde_json::json!({ "minConfidence": 0.7 })).await,
+            vec![("@acp:summary".to_string(), 0.6)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_index_keeps_behavioral_annotations() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src/orders.ts"),
+            "// @acp:fn \"place\" - Place an order\n\
+             // @acp:transactional\n\
+             // @acp:side-effects db, email\n\
+             export function place() {}\n",
+        )
+        .unwrap();
+        let config = Config {
+            include: vec!["src/**/*.ts".to_string()],
+            ..Default::default()
+        };
+        let cache = Indexer::new(config).unwrap().index(root).await.unwrap();
+
+        let symbol = cache.symbols.values().find(|s| s.name == "place").unwrap();
+        let behavioral = symbol.behavioral.as_ref().unwrap();
+        assert!(behavioral.transactional);
+        assert_eq!(behavioral.side_effects, vec!["db", "email"]);
+    }
+
+    #[test]
+    fn test_merge_keeps_annotations_on_same_named_methods() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("jobs.ts");
+        let source = "class Import {\n\
+                      \x20 // @acp:fn \"run\" - Import rows\n\
+                      \x20 // @acp:lock frozen\n\
+                      \x20 // @acp:pure\n\
+                      \x20 run() {}\n\
+                      }\n\
+                      class Export {\n\
+                      \x20 // @acp:fn \"run\" - Export rows\n\
+                      \x20 // @acp:deprecated \"Use stream\"\n\
+                      \x20 run() {}\n\
+                      }\n";
+        fs::write(&path, source).unwrap();
+
+        let annotated = Parser::new().parse(&path).unwrap().symbols;
+        let ast_symbols = AstParser::new().unwrap().parse_file(&path, source).unwrap();
+        let mut symbols = convert_ast_symbols(&ast_symbols, "jobs.ts");
+        merge_annotated_symbols(&mut symbols, annotated);
+
+        let mut runs: Vec<_> = symbols.iter().filter(|s| s.name == "run").collect();
+        runs.sort_by_key(|s| s.lines[0]);
+        assert_eq!(runs.len(), 2);
+
+        assert_eq!(runs[0].purpose.as_deref(), Some("Import rows"));
+        assert_eq!(runs[0].constraints.as_ref().unwrap().level, "frozen");
+        assert!(runs[0].behavioral.as_ref().unwrap().pure);
+        assert!(runs[0].lifecycle.is_none());
+
+        assert_eq!(runs[1].purpose.as_deref(), Some("Export rows"));
+        assert!(runs[1].constraints.is_none());
+        assert!(runs[1].behavioral.is_none());
+        assert!(runs[1].lifecycle.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_index_marks_recursive_symbols() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src/tree.ts"),
+            "export function depth(node) { return node ? 1 + depth(node.child) : 0; }
+             export function isEven(n) { return n === 0 || isOdd(n - 1); }
+             export function isOdd(n) { return n !== 0 && isEven(n - 1); }
+             export function size(node) { return depth(node); }
+",
+        )
+        .unwrap();
+        let config = Config {
+            include: vec!["src/**/*.ts".to_string()],
+            ..Default::default()
+        };
+        let cache = Indexer::new(config).unwrap().index(root).await.unwrap();
+
+        let mut recursive: Vec<_> = cache
+            .symbols
+            .values()
+            .filter(|s| s.recursive)
+            .map(|s| s.name.as_str())
+            .collect();
+        recursive.sort();
+        assert_eq!(recursive, vec!["depth", "isEven", "isOdd"]);
+    }
+
+    #[tokio::test]
+    async fn test_index_normalizes_domain_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        for (file, domain) in [("a.ts", "API"), ("b.ts", "api"), ("c.ts", "userAuth")] {
+            fs::write(
+                root.join("src").join(file),
+                format!("// @acp:domain {}\nexport const x = 1;\n", domain),
+            )
+            .unwrap();
+        }
+        let mut config = Config {
+            include: vec!["src/**/*.ts".to_string()],
+            ..Default::default()
+        };
+
+        // Preserved by default, and reported as a collision
+        let cache = Indexer::new(config.clone())
+            .unwrap()
+            .index(root)
+            .await
+            .unwrap();
+        let mut names: Vec<_> = cache.domains.keys().cloned().collect();
+        names.sort();
+        assert_eq!(names, vec!["API", "api", "userAuth"]);
+        assert_eq!(cache.domain_collisions(), vec![vec!["API", "api"]]);
+
+        let domains: DomainsConfig = serde_json::from_value(serde_json::json!({
+            "normalize": "kebab",
+            "Sessions": { "patterns": ["**/c.ts"] },
+        }))
+        .unwrap();
+        assert_eq!(domains.normalize, DomainNormalize::Kebab);
+        config.domains = Some(domains);
+        let cache = Indexer::new(config).unwrap().index(root).await.unwrap();
+        let mut names: Vec<_> = cache.domains.keys().cloned().collect();
+        names.sort();
+        assert_eq!(names, vec!["api", "sessions", "user-auth"]);
+        assert_eq!(cache.domains["api"].files.len(), 2);
+        assert_eq!(cache.domains["api"].aliases, vec!["API"]);
+        assert_eq!(cache.domains["user-auth"].aliases, vec!["userAuth"]);
+        assert_eq!(cache.domains["sessions"].aliases, vec!["Sessions"]);
+        assert!(cache.domain_collisions().is_empty());
+        assert!(cache.files.values().all(|f| f.domains != vec!["API"]));
+    }
+
+    #[tokio::test]
+    async fn test_index_merges_ast_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src/app.ts"),
+            "// @acp:imports ./util, lodash
+import { helper } from './util';
+export function run() { helper(); }
+",
+        )
+        .unwrap();
+        fs::write(root.join("src/util.ts"), "export function helper() {}\n").unwrap();
+        let config = Config {
+            include: vec!["src/**/*.ts".to_string()],
+            ..Default::default()
+        };
+        let cache = Indexer::new(config).unwrap().index(root).await.unwrap();
+
+        let file = |name: &str| {
+            cache
+                .files
+                .values()
+                .find(|f| f.path.ends_with(name))
+                .unwrap()
+        };
+        assert_eq!(file("app.ts").imports, vec!["./util", "lodash"]);
+        assert_eq!(file("util.ts").imported_by.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_index_detects_import_conventions() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src/app.ts"),
+            "import { helper } from './util';\nimport { fmt } from './format';\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/cli.ts"),
+            "import { run } from './app';\nexport function main() { run(); }\n",
+        )
+        .unwrap();
+        fs::write(root.join("src/util.ts"), "export function helper() {}\n").unwrap();
+        fs::write(root.join("src/format.ts"), "export function fmt() {}\n").unwrap();
+        let config = Config {
+            include: vec!["src/**/*.ts".to_string()],
+            ..Default::default()
+        };
+        let cache = Indexer::new(config).unwrap().index(root).await.unwrap();
+
+        let imports = cache.conventions.imports.expect("import conventions");
+        assert_eq!(imports.module_system, Some(ModuleSystem::Esm));
+        assert_eq!(imports.path_style, Some(PathStyle::Relative));
+    }
+
+    #[test]
+    fn test_resolve_import_path_under_absolute_root() {
+        let files: std::collections::HashSet<String> = [
+            "/work/app/src/util.ts".to_string(),
+            "./src/lib.ts".to_string(),
+        ]
+        .into();
+
+        assert_eq!(
+            resolve_import_path("./util", "/work/app/src", &files).as_deref(),
+            Some("/work/app/src/util.ts")
+        );
+        assert_eq!(
+            resolve_import_path("../lib", "./src/api", &files).as_deref(),
+            Some("./src/lib.ts")
+        );
+        assert_eq!(resolve_import_path("lodash", "/work/app/src", &files), None);
+    }
+
+    #[test]
+    fn test_file_tagger() {
+        let config = Config {
+            tags: HashMap::from([
+                (
+                    "generated".to_string(),
+                    TagConfig {
+                        patterns: vec!["src/gen/**".to_string(), "**/*.pb.ts".to_string()],
+                        internal: true,
+                        lock: Some(LockLevel::Frozen),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "test".to_string(),
+                    TagConfig {
+                        patterns: vec!["**/*.test.ts".to_string()],
+                        ..Default::default()
+                    },
+                ),
+            ]),
+            ..Default::default()
+        };
+        let tagger = FileTagger::from_config(&config);
+
+        assert_eq!(tagger.tag("./src/gen/api.ts"), vec!["generated"]);
+        assert_eq!(
+            tagger.tag("src/gen/api.pb.test.ts"),
+            vec!["generated".to_string(), "test".to_string()]
+        );
+        assert!(tagger.tag("src/app.ts").is_empty());
+
+        assert!(tagger.is_internal(&tagger.tag("src/gen/api.ts")));
+        assert!(!tagger.is_internal(&tagger.tag("src/app.test.ts")));
+        assert_eq!(
+            tagger.locks(),
+            [
+                ("**/*.pb.ts".to_string(), LockLevel::Frozen),
+                ("src/gen/**".to_string(), LockLevel::Frozen),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_infer_visibility_by_language() {
+        let py = Some(Language::Python);
+        assert_eq!(
+            infer_visibility("_helper", py, AstVisibility::Public),
+            Visibility::Private
+        );
+        assert_eq!(
+            infer_visibility("__init__", py, AstVisibility::Public),
+            Visibility::Public
+        );
+        assert_eq!(
+            infer_visibility("_cached", py, AstVisibility::Protected),
+            Visibility::Protected
+        );
+        assert_eq!(
+            infer_visibility("load", py, AstVisibility::Public),
+            Visibility::Public
+        );
+
+        let go = Some(Language::Go);
+        assert_eq!(
+            infer_visibility("parseArgs", go, AstVisibility::Public),
+            Visibility::Private
+        );
+        assert_eq!(
+            infer_visibility("ParseArgs", go, AstVisibility::Private),
+            Visibility::Public
+        );
+
+        let ts = Some(Language::Typescript);
+        assert_eq!(
+            infer_visibility("_internal", ts, AstVisibility::Public),
+            Visibility::Public
+        );
+        assert_eq!(
+            infer_visibility("helper", ts, AstVisibility::Crate),
+            Visibility::Private
+        );
+    }
+
+    #[test]
+    fn test_find_source_files_respects_gitignore() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for path in [
+            "src/app.ts",
+            "src/gen/schema.ts",
+            "src/gen/keep.ts",
+            "out/bundle.ts",
+            "lib/vendor.ts",
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "export {};").unwrap();
+        }
+        fs::write(root.join(".gitignore"), "out/\n").unwrap();
+        // Nested file with a negation
+        fs::write(root.join("src/gen/.gitignore"), "*.ts\n!keep.ts\n").unwrap();
+
+        let find = |respect_gitignore| {
+            let mut files: Vec<String> = find_source_files(
+                root,
+                &["**/*.ts".to_string()],
+                &["lib/**".to_string()],
+                respect_gitignore,
+            )
+            .into_iter()
+            .map(|f| {
+                Path::new(&f)
+                    .strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+            files.sort();
+            files
+        };
+
+        assert_eq!(find(true), ["src/app.ts", "src/gen/keep.ts"]);
+        // Explicit excludes still apply when .gitignore is off
+        assert_eq!(
+            find(false),
+            [
+                "out/bundle.ts",
+                "src/app.ts",
+                "src/gen/keep.ts",
+                "src/gen/schema.ts"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_source_files_reads_acpignore() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for path in [
+            "src/app.ts",
+            "src/api.gen.ts",
+            "src/gen/types.gen.ts",
+            "src/gen/keep.gen.ts",
+            "fixtures/sample.ts",
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "export {};").unwrap();
+        }
+        fs::write(root.join(ACPIGNORE_FILE), "fixtures/\n*.gen.ts\n").unwrap();
+        // Nested file re-includes one generated file
+        fs::write(root.join("src/gen").join(ACPIGNORE_FILE), "!keep.gen.ts\n").unwrap();
+
+        for respect_gitignore in [true, false] {
+            let mut files: Vec<String> =
+                find_source_files(root, &["**/*.ts".to_string()], &[], respect_gitignore)
+                    .into_iter()
+                    .map(|f| {
+                        Path::new(&f)
+                            .strip_prefix(root)
+                            .unwrap()
+                            .to_string_lossy()
+                            .replace('\\', "/")
+                    })
+                    .collect();
+            files.sort();
+            assert_eq!(files, ["src/app.ts", "src/gen/keep.gen.ts"]);
+        }
+    }
+}
@@ -3,6 +3,11 @@
 //! @acp:domain cli
 //! @acp:layer service
 
+mod heuristic_calls;
 mod indexer;
+mod param_check;
+mod plan;
 
 pub use indexer::*;
+pub use param_check::{check_file_params, ParamIssue, ParamIssueKind};
+pub use plan::{IndexPlan, PlannedLanguage, PlannedSkip, SkipReason, ESTIMATED_BYTES_PER_LINE};
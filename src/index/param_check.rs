@@ -0,0 +1,465 @@
+//! @acp:module "Parameter Drift Check"
+//! @acp:summary "Compare @acp:param annotations against AST parameter lists (RFC-0008)"
+//! @acp:domain cli
+//! @acp:layer service
+//!
+//! Catches documentation that went stale after a refactor: annotated
+//! parameters that no longer exist, real parameters nobody documented, and
+//! annotations listed in a different order than the signature. Names and
+//! arity are authoritative; type differences are reported as advisory only,
+//! since type syntax rarely lines up across annotation and source language.
+//!
+//! The indexer also uses [`align_params`] to store @acp:param type info in
+//! signature order, and [`fill_type_hints`] to fall back to inline type
+//! hints for parameters and returns that have no `{Type}` annotation.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::ast::{AstParser, ExtractedSymbol, Parameter};
+use crate::cache::{TypeInfo, TypeParamInfo, TypeReturnInfo, TypeSource};
+use crate::error::Result;
+use crate::parse::Parser;
+
+/// @acp:summary "Kind of mismatch between annotations and the real signature"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamIssueKind {
+    /// Parameter exists in the signature but has no @acp:param
+    Missing,
+    /// @acp:param names a parameter the signature does not have
+    Extra,
+    /// Same parameters, different order
+    Reordered,
+    /// Annotated type differs from the declared type (advisory)
+    Type,
+}
+
+/// @acp:summary "One parameter mismatch found in a symbol"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParamIssue {
+    pub file: String,
+    pub symbol: String,
+    pub line: usize,
+    pub kind: ParamIssueKind,
+    pub message: String,
+}
+
+impl ParamIssue {
+    /// Type mismatches are informational and never fail a check
+    pub fn is_advisory(&self) -> bool {
+        self.kind == ParamIssueKind::Type
+    }
+}
+
+/// An @acp:param as written: name and optional `{Type}`
+#[derive(Debug, Clone, PartialEq)]
+struct AnnotatedParam {
+    name: String,
+    type_expr: Option<String>,
+}
+
+/// Check every symbol in a source file that carries @acp:param annotations
+///
+/// Symbols without any @acp:param are skipped; an undocumented function is
+/// not drift.
+pub fn check_file_params(
+    path: &str,
+    source: &str,
+    ast_parser: &AstParser,
+    annotation_parser: &Parser,
+) -> Result<Vec<ParamIssue>> {
+    let symbols = ast_parser.parse_file(Path::new(path), source)?;
+    let mut issues = Vec::new();
+    let mut seen = HashSet::new();
+
+    for symbol in &symbols {
+        // Exported declarations can be extracted twice; check each once
+        if !seen.insert((symbol.name.as_str(), symbol.start_line)) {
+            continue;
+        }
+        let Some(doc) = symbol.doc_comment.as_deref() else {
+            continue;
+        };
+        let annotated: Vec<AnnotatedParam> = annotation_parser
+            .parse_annotations(doc)
+            .into_iter()
+            .filter(|a| a.name == "param")
+            .filter_map(|a| a.value.as_deref().and_then(parse_param_value))
+            // `options.name` documents a property of `options`, not a parameter
+            .filter(|p| !p.name.contains('.'))
+            .collect();
+        if annotated.is_empty() {
+            continue;
+        }
+
+        issues.extend(
+            compare_params(&annotated, &actual_params(symbol))
+                .into_iter()
+                .map(|(kind, message)| ParamIssue {
+                    file: path.to_string(),
+                    symbol: symbol.name.clone(),
+                    line: symbol.start_line,
+                    kind,
+                    message,
+                }),
+        );
+    }
+
+    Ok(issues)
+}
+
+/// Parameters that callers pass, without receivers like `self`
+fn actual_params(symbol: &ExtractedSymbol) -> Vec<&Parameter> {
+    symbol
+        .parameters
+        .iter()
+        .filter(|p| !matches!(normalize_name(&p.name).as_str(), "self" | "cls" | ""))
+        .collect()
+}
+
+fn compare_params(
+    annotated: &[AnnotatedParam],
+    actual: &[&Parameter],
+) -> Vec<(ParamIssueKind, String)> {
+    let mut issues = Vec::new();
+    let actual_names: Vec<String> = actual.iter().map(|p| normalize_name(&p.name)).collect();
+    let annotated_names: Vec<String> = annotated.iter().map(|p| normalize_name(&p.name)).collect();
+
+    for name in &actual_names {
+        if !annotated_names.contains(name) {
+            issues.push((
+                ParamIssueKind::Missing,
+                format!("parameter '{}' has no @acp:param", name),
+            ));
+        }
+    }
+    for name in &annotated_names {
+        if !actual_names.contains(name) {
+            issues.push((
+                ParamIssueKind::Extra,
+                format!("@acp:param '{}' is not in the signature", name),
+            ));
+        }
+    }
+
+    // Order only matters between names both sides agree on
+    let shared_actual: Vec<&String> = actual_names
+        .iter()
+        .filter(|n| annotated_names.contains(n))
+        .collect();
+    let shared_annotated: Vec<&String> = annotated_names
+        .iter()
+        .filter(|n| actual_names.contains(n))
+        .collect();
+    if shared_actual != shared_annotated {
+        issues.push((
+            ParamIssueKind::Reordered,
+            format!(
+                "@acp:param order ({}) differs from signature ({})",
+                join(&shared_annotated),
+                join(&shared_actual)
+            ),
+        ));
+    }
+
+    for (param, name) in annotated.iter().zip(&annotated_names) {
+        let declared = actual
+            .iter()
+            .zip(&actual_names)
+            .find(|(_, n)| *n == name)
+            .and_then(|(p, _)| p.type_info.as_deref());
+        if let (Some(annotated_type), Some(declared)) = (param.type_expr.as_deref(), declared) {
+            if squash(annotated_type) != squash(declared) {
+                issues.push((
+                    ParamIssueKind::Type,
+                    format!(
+                        "'{}' annotated as {{{}}} but declared as {}",
+                        name, annotated_type, declared
+                    ),
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+/// How a symbol's `@acp:param` entries compared with its real signature
+#[derive(Debug, Default, PartialEq)]
+pub struct ParamAlignment {
+    /// Documented parameters were not in signature order
+    pub reordered: bool,
+    /// Documented names that match no parameter
+    pub unknown: Vec<String>,
+}
+
+/// @acp:summary "Reorder @acp:param type info to match the real signature"
+///
+/// The sort is stable: entries naming no real parameter keep their relative
+/// order at the end. Dotted names (`options.timeout`) follow their root
+/// parameter. Unknown names are only reported when every parameter is a
+/// plain identifier; destructuring patterns can't be matched by name.
+pub fn align_params(params: &mut [TypeParamInfo], symbol: &ExtractedSymbol) -> ParamAlignment {
+    let actual: Vec<String> = actual_params(symbol)
+        .iter()
+        .map(|p| normalize_name(&p.name))
+        .collect();
+    let position = |param: &TypeParamInfo| {
+        let name = normalize_name(&param.name);
+        let root = name.split('.').next().unwrap_or_default();
+        actual.iter().position(|p| p == root)
+    };
+
+    let known: Vec<usize> = params.iter().filter_map(position).collect();
+    let reordered = known.windows(2).any(|w| w[0] > w[1]);
+    let plain = symbol.parameters.iter().all(|p| {
+        let name = normalize_name(&p.name);
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+    });
+    let unknown = if plain {
+        params
+            .iter()
+            .filter(|p| position(p).is_none())
+            .map(|p| p.name.clone())
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    params.sort_by_key(|p| position(p).unwrap_or(usize::MAX));
+    ParamAlignment { reordered, unknown }
+}
+
+/// @acp:summary "Fill missing parameter and return types from inline type hints"
+///
+/// An explicit `@acp:param {Type}` or `@acp:returns {Type}` always wins; hints
+/// only fill entries with no type and add parameters that were never
+/// annotated. Destructured parameters have no name to key on and are skipped.
+pub fn fill_type_hints(type_info: &mut TypeInfo, symbol: &ExtractedSymbol) {
+    for param in actual_params(symbol) {
+        let Some(hint) = param.type_info.as_ref() else {
+            continue;
+        };
+        let name = normalize_name(&param.name);
+        match type_info
+            .params
+            .iter_mut()
+            .find(|p| normalize_name(&p.name) == name)
+        {
+            Some(existing) if existing.r#type.is_none() => {
+                existing.r#type = Some(hint.clone());
+                existing.type_source = Some(TypeSource::TypeHint);
+            }
+            Some(_) => {}
+            None if name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '$') =>
+            {
+                type_info.params.push(TypeParamInfo {
+                    name,
+                    r#type: Some(hint.clone()),
+                    type_source: Some(TypeSource::TypeHint),
+                    optional: param.is_optional,
+                    default: param.default_value.clone(),
+                    directive: None,
+                });
+            }
+            None => {}
+        }
+    }
+
+    let Some(hint) = symbol.return_type.as_ref() else {
+        return;
+    };
+    match type_info.returns.as_mut() {
+        Some(returns) if returns.r#type.is_none() => {
+            returns.r#type = Some(hint.clone());
+            returns.type_source = Some(TypeSource::TypeHint);
+        }
+        Some(_) => {}
+        None => {
+            type_info.returns = Some(TypeReturnInfo {
+                r#type: Some(hint.clone()),
+                type_source: Some(TypeSource::TypeHint),
+                directive: None,
+            });
+        }
+    }
+}
+
+/// Parse `{Type} name`, `[name=default]` or `name` from an @acp:param value
+fn parse_param_value(value: &str) -> Option<AnnotatedParam> {
+    let value = value.trim();
+    let (type_expr, rest) = match value.strip_prefix('{').and_then(|v| v.split_once('}')) {
+        Some((type_expr, rest)) => (Some(type_expr.trim().to_string()), rest.trim()),
+        None => (None, value),
+    };
+
+    let name = match rest.strip_prefix('[') {
+        Some(inner) => inner.split(['=', ']']).next().unwrap_or(""),
+        None => rest.split_whitespace().next().unwrap_or(""),
+    }
+    .trim()
+    .trim_matches('"');
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(AnnotatedParam {
+            name: name.to_string(),
+            type_expr,
+        })
+    }
+}
+
+/// Strip rest/splat/reference markers so `...args`, `*args` and `args` match
+fn normalize_name(name: &str) -> String {
+    let name = name.trim().trim_start_matches(['.', '*', '&']);
+    name.strip_prefix("mut ").unwrap_or(name).trim().to_string()
+}
+
+/// Compare type text without regard to whitespace
+fn squash(type_expr: &str) -> String {
+    type_expr.split_whitespace().collect()
+}
+
+fn join(names: &[&String]) -> String {
+    names
+        .iter()
+        .map(|n| n.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(source: &str, path: &str) -> Vec<ParamIssue> {
+        check_file_params(path, source, &AstParser::new().unwrap(), &Parser::new()).unwrap()
+    }
+
+    fn kinds(issues: &[ParamIssue]) -> Vec<ParamIssueKind> {
+        issues.iter().map(|i| i.kind).collect()
+    }
+
+    #[test]
+    fn test_matching_params_are_clean() {
+        let source = r#"
+/**
+ * @acp:param {string} name - User name
+ * @acp:param {number} [age=0] - Age
+ * @acp:param {...string} tags - Tags
+ */
+export function greet(name: string, age: number = 0, ...tags: string[]) {}
+"#;
+        let issues = check(source, "greet.ts");
+        assert!(
+            issues.iter().all(|i| i.is_advisory()),
+            "unexpected: {:?}",
+            issues
+        );
+    }
+
+    #[test]
+    fn test_missing_extra_and_reordered() {
+        let source = r#"
+/**
+ * @acp:param {string} b - Second
+ * @acp:param {string} a - First
+ * @acp:param {string} removed - Gone after refactor
+ */
+export function run(a: string, b: string, added: number) {}
+"#;
+        let issues = check(source, "run.ts");
+        assert_eq!(
+            kinds(&issues),
+            vec![
+                ParamIssueKind::Missing,
+                ParamIssueKind::Extra,
+                ParamIssueKind::Reordered
+            ]
+        );
+        assert!(issues[0].message.contains("'added'"));
+        assert!(issues[1].message.contains("'removed'"));
+        assert_eq!(issues[0].symbol, "run");
+    }
+
+    #[test]
+    fn test_type_mismatch_is_advisory() {
+        let source = r#"
+/**
+ * @acp:param {number} id - Identifier
+ */
+export function load(id: string) {}
+"#;
+        let issues = check(source, "load.ts");
+        assert_eq!(kinds(&issues), vec![ParamIssueKind::Type]);
+        assert!(issues[0].is_advisory());
+    }
+
+    #[test]
+    fn test_undocumented_symbols_and_receivers_skipped() {
+        let source = r#"
+class Repo:
+    def save(self, item):
+        """
+        @acp:param item - Item to persist
+        """
+        pass
+
+def helper(x, y):
+    pass
+"#;
+        assert!(check(source, "repo.py").is_empty());
+    }
+
+    #[test]
+    fn test_parse_param_value() {
+        let parsed = parse_param_value("{Map<string, number>} [limit=10]").unwrap();
+        assert_eq!(parsed.name, "limit");
+        assert_eq!(parsed.type_expr.as_deref(), Some("Map<string, number>"));
+        assert_eq!(parse_param_value("name").unwrap().type_expr, None);
+        assert!(parse_param_value("{string}").is_none());
+    }
+
+    #[test]
+    fn test_align_params_to_signature() {
+        let source = "function fetch(url, options, ...rest) {}\nfunction pick(a, { c, d }) {}\n";
+        let symbols = AstParser::new()
+            .unwrap()
+            .parse_file(Path::new("api.ts"), source)
+            .unwrap();
+        let symbol = |name: &str| symbols.iter().find(|s| s.name == name).unwrap();
+        let params = |names: &[&str]| -> Vec<TypeParamInfo> {
+            names
+                .iter()
+                .map(|name| serde_json::from_value(serde_json::json!({ "name": name })).unwrap())
+                .collect()
+        };
+        let names = |params: &[TypeParamInfo]| -> Vec<String> {
+            params.iter().map(|p| p.name.clone()).collect()
+        };
+
+        let mut documented = params(&["gone", "options.timeout", "url", "options", "...rest"]);
+        let alignment = align_params(&mut documented, symbol("fetch"));
+        assert!(alignment.reordered);
+        assert_eq!(alignment.unknown, vec!["gone"]);
+        assert_eq!(
+            names(&documented),
+            vec!["url", "options.timeout", "options", "...rest", "gone"]
+        );
+
+        // In order already; a destructured parameter suppresses unknown names
+        let mut documented = params(&["a", "b"]);
+        let alignment = align_params(&mut documented, symbol("pick"));
+        assert_eq!(alignment, ParamAlignment::default());
+        assert_eq!(names(&documented), vec!["a", "b"]);
+    }
+}
@@ -0,0 +1,74 @@
+//! @acp:module "Index Plan"
+//! @acp:summary "What an index run would cover, without parsing anything"
+//! @acp:domain cli
+//! @acp:layer model
+//!
+//! Built by [`Indexer::plan`](super::Indexer::plan) from the file walk and
+//! file sizes alone, so it stays fast on trees that take minutes to index.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+/// Average source line length used to estimate line counts from file sizes
+pub const ESTIMATED_BYTES_PER_LINE: u64 = 32;
+
+/// @acp:summary "Files an index run would parse, and those it would skip"
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IndexPlan {
+    /// Files that would be parsed
+    pub total_files: usize,
+    pub total_bytes: u64,
+    /// Estimated from sizes; see [`ESTIMATED_BYTES_PER_LINE`]
+    pub estimated_lines: u64,
+    /// Parsed files by language (schema name, e.g. `typescript`)
+    pub languages: BTreeMap<String, PlannedLanguage>,
+    /// `limits.max_files` in effect
+    pub max_files: usize,
+    /// More files match than `limits.max_files` allows, so indexing would fail
+    pub exceeds_max_files: bool,
+    /// Matched files that would not be parsed, sorted by path
+    pub skipped: Vec<PlannedSkip>,
+    /// Files left out by each exclude pattern (credited to the first match)
+    pub excluded: BTreeMap<String, usize>,
+}
+
+/// @acp:summary "Per-language totals in an index plan"
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PlannedLanguage {
+    pub files: usize,
+    pub bytes: u64,
+    pub estimated_lines: u64,
+}
+
+/// @acp:summary "A matched file the indexer would skip"
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedSkip {
+    /// Path relative to the project root
+    pub path: String,
+    pub reason: SkipReason,
+    pub size_bytes: u64,
+}
+
+/// @acp:summary "Why a matched file would not be parsed"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// Larger than `limits.max_file_size_mb`
+    Oversized,
+    /// No parser for the file's extension
+    Unsupported,
+}
+
+impl IndexPlan {
+    /// @acp:summary "Count a file that would be parsed"
+    pub(super) fn add_file(&mut self, language: String, bytes: u64) {
+        let entry = self.languages.entry(language).or_default();
+        entry.files += 1;
+        entry.bytes += bytes;
+        entry.estimated_lines = entry.bytes.div_ceil(ESTIMATED_BYTES_PER_LINE);
+        self.total_files += 1;
+        self.total_bytes += bytes;
+        self.estimated_lines = self.total_bytes.div_ceil(ESTIMATED_BYTES_PER_LINE);
+    }
+}
@@ -49,12 +49,14 @@ pub mod conventions;
 pub mod error;
 pub mod expand;
 pub mod git;
+pub mod globs;
 pub mod index;
 pub mod parse;
 pub mod primer;
 pub mod query;
 pub mod scan;
 pub mod schema;
+pub mod server;
 pub mod sync;
 pub mod vars;
 pub mod watch;
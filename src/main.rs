@@ -3,21 +3,27 @@
 
 use std::path::PathBuf;
 
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 use console::style;
 
-use acp::annotate::{AnnotateLevel, ConversionSource, OutputFormat};
+use acp::annotate::{AnnotateLevel, ConversionSource, GenerationLog, OutputFormat};
 use acp::commands::{
     execute_annotate, execute_attempt, execute_bridge, execute_chain, execute_check,
-    execute_context, execute_daemon, execute_expand, execute_index, execute_init, execute_install,
-    execute_list_installed, execute_map, execute_migrate, execute_primer, execute_query,
-    execute_revert, execute_review, execute_uninstall, execute_validate, execute_vars,
-    execute_watch, AnnotateOptions, AttemptSubcommand, BridgeOptions, BridgeSubcommand,
-    ChainOptions, CheckOptions, ContextOperation, ContextOptions, DaemonSubcommand, ExpandOptions,
-    IndexOptions, InitOptions, InstallOptions, InstallTarget, MapFormat, MapOptions,
-    MigrateOptions, PrimerOptions, QueryOptions, QuerySubcommand, RevertOptions, ReviewOptions,
-    ReviewSubcommand, ValidateOptions, VarsOptions, WatchOptions,
+    execute_context, execute_daemon, execute_diff, execute_expand, execute_export, execute_gate,
+    execute_index, execute_init, execute_install, execute_list_installed, execute_map,
+    execute_migrate, execute_primer, execute_query, execute_query_describe, execute_revert,
+    execute_review, execute_scan, execute_uninstall, execute_validate, execute_vars, execute_watch,
+    AnnotateOptions, AttemptSubcommand, BridgeOptions, BridgeSubcommand, CalleeFilter,
+    ChainOptions, CheckFormat, CheckOptions, ContextOperation, ContextOptions, DaemonSubcommand,
+    DiffOptions, ExpandOptions, ExportOptions, ExportSubcommand, GateOptions, IndexOptions,
+    InitOptions, InstallOptions, InstallTarget, MapFormat, MapOptions, MigrateOptions, Page,
+    PrimerOptions, QueryOptions, QuerySubcommand, RevertOptions, ReviewOptions, ReviewSubcommand,
+    ScanOptions, StatsGroup, SymbolMatchMode, TabularFormat, ValidateOptions, VarsOptions,
+    WatchOptions,
 };
+use acp::config::EnvOverrides;
+use acp::constraints::LockLevel;
+use acp::query::BehaviorFilter;
 use acp::{Cache, Config};
 
 #[derive(Parser)]
@@ -28,9 +34,9 @@ struct Cli {
     #[command(subcommand)]
     command: Commands,
 
-    /// Config file path
-    #[arg(short, long, global = true, default_value = ".acp.config.json")]
-    config: PathBuf,
+    /// Config file path (default: $ACP_CONFIG or .acp.config.json)
+    #[arg(short, long, global = true)]
+    config: Option<PathBuf>,
 
     /// Verbose output
     #[arg(short, long, global = true)]
@@ -76,6 +82,10 @@ enum Commands {
         /// Skip AI tool bootstrap (don't create CLAUDE.md, .cursorrules, etc.)
         #[arg(long)]
         no_bootstrap: bool,
+
+        /// Start from a stack preset: rust, node, python, go, monorepo
+        #[arg(long)]
+        profile: Option<String>,
     },
 
     /// Install ACP plugins (daemon, mcp)
@@ -103,13 +113,13 @@ enum Commands {
 
     /// Index the codebase and generate cache
     Index {
-        /// Root directory to index
-        #[arg(default_value = ".")]
-        root: PathBuf,
+        /// Root directory to index (default: $ACP_ROOT, then config root)
+        root: Option<PathBuf>,
 
-        /// Output cache file path
-        #[arg(short, long, default_value = ".acp/acp.cache.json")]
-        output: PathBuf,
+        /// Output cache file path (default: $ACP_CACHE_PATH, then config output.cache).
+        /// A path ending in .bin writes the binary cache format
+        #[arg(short, long)]
+        output: Option<PathBuf>,
 
         /// Also generate vars file
         #[arg(long)]
@@ -122,6 +132,26 @@ enum Commands {
         /// Disable documentation bridging (overrides config)
         #[arg(long)]
         no_bridge: bool,
+
+        /// Only recompute git metadata for files changed since the existing cache
+        #[arg(long)]
+        git_changed_only: bool,
+
+        /// Fail without writing the cache if any file fails to parse
+        #[arg(long)]
+        strict: bool,
+
+        /// Append a stats snapshot to stats-history.jsonl next to the cache
+        #[arg(long)]
+        history: bool,
+
+        /// Show which files would be indexed or skipped, without parsing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Output the --dry-run report as JSON
+        #[arg(long, requires = "dry_run")]
+        json: bool,
     },
 
     /// Manage documentation bridging (RFC-0006)
@@ -130,43 +160,78 @@ enum Commands {
         #[command(subcommand)]
         subcommand: BridgeCommands,
 
-        /// Cache file to read
-        #[arg(long, default_value = ".acp/acp.cache.json", global = true)]
-        cache: PathBuf,
+        /// Cache file to read (default: $ACP_CACHE_PATH, then config output.cache)
+        #[arg(long, global = true)]
+        cache: Option<PathBuf>,
     },
 
     /// Generate vars file from cache
     Vars {
-        /// Cache file to read
-        #[arg(short, long, default_value = ".acp/acp.cache.json")]
-        cache: PathBuf,
+        /// Cache file to read (default: $ACP_CACHE_PATH, then config output.cache)
+        #[arg(short, long)]
+        cache: Option<PathBuf>,
 
         /// Output vars file path
         #[arg(short, long, default_value = ".acp/acp.vars.json")]
         output: PathBuf,
+
+        /// Regenerate from scratch, dropping hand-added vars in the output file
+        #[arg(long)]
+        fresh: bool,
+    },
+
+    /// Export the cache for other tools
+    Export {
+        /// Export format
+        #[command(subcommand)]
+        format: ExportCommands,
+
+        /// Cache file to read (default: $ACP_CACHE_PATH, then config output.cache)
+        #[arg(long, global = true)]
+        cache: Option<PathBuf>,
     },
 
     /// Query the cache
     Query {
         /// Query type
         #[command(subcommand)]
-        query: QueryCommands,
+        query: Option<QueryCommands>,
 
-        /// Cache file to query
-        #[arg(short, long, default_value = ".acp/acp.cache.json")]
-        cache: PathBuf,
+        /// Describe every query, its parameters and JSON result schema
+        #[arg(long, conflicts_with = "format")]
+        describe: bool,
+
+        /// Cache file to query (default: $ACP_CACHE_PATH, then config output.cache)
+        #[arg(short, long)]
+        cache: Option<PathBuf>,
 
         /// Output as JSON (default: human-readable)
         #[arg(long, global = true)]
         json: bool,
+
+        /// Output format (csv/tsv for symbol, symbols, file, files, callers, callees, stats, domains, provenance --needs-review, deprecated, behavior, and throws; shields for coverage)
+        #[arg(long, value_enum, global = true, conflicts_with = "json")]
+        format: Option<QueryFormatArg>,
+
+        /// Show at most N results of a list query (symbol patterns default to 100, hotpaths to 10)
+        #[arg(long, global = true)]
+        limit: Option<usize>,
+
+        /// Skip the first N results of a list query; with --limit, JSON lists report total and hasMore
+        #[arg(long, global = true, default_value = "0")]
+        offset: usize,
     },
 
     /// Expand variable references in text
     Expand {
-        /// Text to expand (reads from stdin if not provided)
+        /// Text to expand (reads from --file or stdin if not provided)
         text: Option<String>,
 
-        /// Expansion mode
+        /// Read the text from a file
+        #[arg(short, long, conflicts_with = "text")]
+        file: Option<PathBuf>,
+
+        /// Expansion mode (none, summary, inline, annotated, block, interactive, template)
         #[arg(short, long, default_value = "annotated")]
         mode: String,
 
@@ -177,6 +242,10 @@ enum Commands {
         /// Show inheritance chains
         #[arg(long)]
         chains: bool,
+
+        /// Fail if any variable reference is unresolved
+        #[arg(long)]
+        strict: bool,
     },
 
     /// Show variable inheritance chain
@@ -191,6 +260,14 @@ enum Commands {
         /// Show as tree
         #[arg(long)]
         tree: bool,
+
+        /// List each variable once with its depth
+        #[arg(long, conflicts_with = "tree")]
+        flat: bool,
+
+        /// Stop following refs below this depth
+        #[arg(long)]
+        max_depth: Option<usize>,
     },
 
     /// Manage troubleshooting attempts
@@ -201,13 +278,68 @@ enum Commands {
 
     /// Check guardrails for a file
     Check {
-        /// File to check (default: show all files with constraints)
+        /// Files or glob patterns to check (default: show all files with constraints)
         #[arg(default_value = ".")]
-        file: PathBuf,
+        files: Vec<PathBuf>,
+
+        /// Cache file (default: $ACP_CACHE_PATH, then config output.cache)
+        #[arg(short, long)]
+        cache: Option<PathBuf>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: CheckFormatArg,
+
+        /// Compare @acp:param names and order against actual signatures
+        #[arg(long)]
+        types: bool,
+
+        /// Flag commits to frozen/restricted files made after the lock by unapproved authors
+        #[arg(long, conflicts_with = "types")]
+        policy: bool,
+
+        /// Fix missing directives, malformed hack dates and orphaned lock reasons in source
+        #[arg(long, conflicts_with_all = ["types", "policy"])]
+        fix: bool,
 
-        /// Cache file
-        #[arg(short, long, default_value = ".acp/acp.cache.json")]
-        cache: PathBuf,
+        /// With --fix, show the changes without writing them
+        #[arg(long, requires = "fix")]
+        dry_run: bool,
+    },
+
+    /// Show languages, file counts, sizes and AI tools detected in a project
+    Scan {
+        /// Directory to scan
+        #[arg(default_value = ".")]
+        root: PathBuf,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Evaluate quality gates from constraints.gates (exits non-zero on failure)
+    Gate {
+        /// Cache file (default: $ACP_CACHE_PATH, then config output.cache)
+        #[arg(long)]
+        cache: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Compare two cache files (e.g. base branch vs PR branch)
+    Diff {
+        /// Cache before the change
+        old: PathBuf,
+
+        /// Cache after the change
+        new: PathBuf,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Get operation-specific context for AI agents (RFC-0015)
@@ -222,9 +354,9 @@ enum Commands {
         #[command(subcommand)]
         operation: ContextCommands,
 
-        /// Cache file path
-        #[arg(long, default_value = ".acp/acp.cache.json", global = true)]
-        cache: PathBuf,
+        /// Cache file path (default: $ACP_CACHE_PATH, then config output.cache)
+        #[arg(long, global = true)]
+        cache: Option<PathBuf>,
 
         /// Output as JSON
         #[arg(long, global = true)]
@@ -251,12 +383,35 @@ enum Commands {
         /// Root directory to watch
         #[arg(default_value = ".")]
         root: PathBuf,
+
+        /// Shell command to run after each re-index (changed files in $ACP_CHANGED_FILES)
+        #[arg(long, value_name = "CMD")]
+        exec: Option<String>,
+
+        /// Milliseconds without changes before re-indexing
+        #[arg(long, value_name = "MS", default_value = "500")]
+        debounce: u64,
     },
 
     /// Validate cache/vars files
     Validate {
         /// File to validate
-        file: PathBuf,
+        #[arg(required_unless_present = "annotations")]
+        file: Option<PathBuf>,
+
+        /// Also check that @acp:see targets resolve and @acp:link URLs are well-formed (cache files)
+        #[arg(long, requires = "file")]
+        refs: bool,
+
+        /// Also check that @acp:version/@acp:since values are semver and no since is newer
+        /// than its file's version or documentation.projectVersion (cache files; see
+        /// documentation.versionScheme)
+        #[arg(long, requires = "file")]
+        semver: bool,
+
+        /// Scan source files for unknown @acp: annotation names (likely typos)
+        #[arg(long)]
+        annotations: bool,
     },
 
     /// Manage the ACP daemon
@@ -279,6 +434,18 @@ enum Commands {
         #[arg(long)]
         dry_run: bool,
 
+        /// Keep a <file>.acp.bak copy of each file modified by --apply (default)
+        #[arg(long, overrides_with = "no_backup")]
+        backup: bool,
+
+        /// Don't write .acp.bak copies when applying
+        #[arg(long, overrides_with = "backup")]
+        no_backup: bool,
+
+        /// Remove the annotations inserted by a generation (default: the most recent)
+        #[arg(long, value_name = "GENERATION_ID")]
+        undo: Option<Option<String>>,
+
         /// Convert-only mode: only use doc comment conversion, disable heuristics
         #[arg(long)]
         convert: bool,
@@ -307,15 +474,17 @@ enum Commands {
         #[arg(long)]
         symbols_only: bool,
 
-        /// Exit with error if coverage below threshold (CI mode)
+        /// Exit with error if coverage below threshold (CI mode).
+        /// With --format json, prints a per-file coverage report instead
         #[arg(long)]
         check: bool,
 
-        /// Minimum coverage threshold for --check (default: 80%)
+        /// Minimum coverage threshold for --check (default: 80%); with annotate.coverage
+        /// in config, applies only to files no pattern matches
         #[arg(long)]
         min_coverage: Option<f32>,
 
-        /// Number of parallel workers (default: number of CPUs)
+        /// Number of parallel workers (default: $ACP_WORKERS, config workers, or CPU count)
         #[arg(long, short = 'j')]
         workers: Option<usize>,
 
@@ -326,6 +495,14 @@ enum Commands {
         /// RFC-0003: Mark all generated annotations as needing review
         #[arg(long)]
         mark_needs_review: bool,
+
+        /// Annotate source read from stdin and print it to stdout (PATH names the buffer)
+        #[arg(long, conflicts_with_all = ["apply", "undo", "check", "filter"])]
+        stdin: bool,
+
+        /// Language of the stdin buffer (default: inferred from PATH's extension)
+        #[arg(long, requires = "stdin")]
+        lang: Option<String>,
     },
 
     /// RFC-0003: Review auto-generated annotations
@@ -342,9 +519,9 @@ enum Commands {
         #[arg(long)]
         confidence: Option<String>,
 
-        /// Cache file path
-        #[arg(long, default_value = ".acp/acp.cache.json")]
-        cache: PathBuf,
+        /// Cache file path (default: $ACP_CACHE_PATH, then config output.cache)
+        #[arg(long)]
+        cache: Option<PathBuf>,
 
         /// Output as JSON
         #[arg(long)]
@@ -365,21 +542,25 @@ enum Commands {
         #[arg(long)]
         inline: bool,
 
-        /// Output format (tree, flat, json)
+        /// Output format (tree, flat, json, mermaid)
         #[arg(long, value_enum, default_value = "tree")]
         format: MapFormatArg,
 
-        /// Cache file
-        #[arg(long, default_value = ".acp/acp.cache.json")]
-        cache: PathBuf,
+        /// Cache file (default: $ACP_CACHE_PATH, then config output.cache)
+        #[arg(long)]
+        cache: Option<PathBuf>,
     },
 
-    /// Migrate annotations to RFC-001 format
+    /// Migrate annotations to RFC-001 format, or convert native docs to ACP
     Migrate {
         /// Add directive suffixes to annotations
         #[arg(long)]
         add_directives: bool,
 
+        /// Convert native doc comments in this format into @acp: annotations
+        #[arg(long, value_enum, conflicts_with = "add_directives")]
+        from: Option<AnnotateFrom>,
+
         /// Paths to migrate (default: all indexed files)
         paths: Vec<PathBuf>,
 
@@ -395,9 +576,9 @@ enum Commands {
         #[arg(long, default_value = "true")]
         backup: bool,
 
-        /// Cache file
-        #[arg(long, default_value = ".acp/acp.cache.json")]
-        cache: PathBuf,
+        /// Cache file (default: $ACP_CACHE_PATH, then config output.cache)
+        #[arg(long)]
+        cache: Option<PathBuf>,
     },
 
     /// Generate AI bootstrap primer (RFC-0015: Tiered Interface Primers)
@@ -416,7 +597,7 @@ enum Commands {
         #[arg(long, value_delimiter = ',')]
         capabilities: Vec<String>,
 
-        /// Output format (markdown, compact, json, text)
+        /// Output format (markdown, compact, json, text/plaintext)
         #[arg(long, short = 'f', default_value = "markdown")]
         format: PrimerFormatArg,
 
@@ -428,6 +609,10 @@ enum Commands {
         #[arg(long, short = 'p')]
         preset: Option<String>,
 
+        /// Scale a section or category's value (repeatable, e.g. constraints=2.0)
+        #[arg(long = "weight", value_parser = parse_weight)]
+        weights: Vec<(String, f64)>,
+
         /// Force include section IDs (comma-separated)
         #[arg(long, value_delimiter = ',')]
         include: Vec<String>,
@@ -479,9 +664,9 @@ enum Commands {
         #[arg(long)]
         primer_config: Option<PathBuf>,
 
-        /// Cache file (for project state)
-        #[arg(long, default_value = ".acp/acp.cache.json")]
-        cache: PathBuf,
+        /// Cache file for project state (default: $ACP_CACHE_PATH, then config output.cache)
+        #[arg(long)]
+        cache: Option<PathBuf>,
     },
 }
 
@@ -492,6 +677,26 @@ enum MapFormatArg {
     Tree,
     Flat,
     Json,
+    Mermaid,
+}
+
+/// Output format for check command
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default)]
+enum CheckFormatArg {
+    #[default]
+    Text,
+    Sarif,
+}
+
+/// Output format for query command
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum QueryFormatArg {
+    Csv,
+    Tsv,
+    /// Same as --json
+    Json,
+    /// shields.io endpoint badge (coverage only)
+    Shields,
 }
 
 /// Output format for primer command
@@ -501,6 +706,7 @@ enum PrimerFormatArg {
     Markdown,
     Compact,
     Json,
+    #[value(alias = "plaintext")]
     Text,
 }
 
@@ -517,6 +723,48 @@ enum AnnotateFrom {
     Javadoc,
 }
 
+impl From<AnnotateFrom> for ConversionSource {
+    fn from(from: AnnotateFrom) -> Self {
+        match from {
+            AnnotateFrom::Auto => ConversionSource::Auto,
+            AnnotateFrom::Jsdoc => ConversionSource::Jsdoc,
+            AnnotateFrom::Tsdoc => ConversionSource::Tsdoc,
+            AnnotateFrom::Docstring => ConversionSource::Docstring,
+            AnnotateFrom::Rustdoc => ConversionSource::Rustdoc,
+            AnnotateFrom::Godoc => ConversionSource::Godoc,
+            AnnotateFrom::Javadoc => ConversionSource::Javadoc,
+        }
+    }
+}
+
+/// Lock level for `query constraints --level`
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum LockLevelArg {
+    Frozen,
+    Restricted,
+    ApprovalRequired,
+    TestsRequired,
+    DocsRequired,
+    ReviewRequired,
+    Normal,
+    Experimental,
+}
+
+impl From<LockLevelArg> for LockLevel {
+    fn from(level: LockLevelArg) -> Self {
+        match level {
+            LockLevelArg::Frozen => LockLevel::Frozen,
+            LockLevelArg::Restricted => LockLevel::Restricted,
+            LockLevelArg::ApprovalRequired => LockLevel::ApprovalRequired,
+            LockLevelArg::TestsRequired => LockLevel::TestsRequired,
+            LockLevelArg::DocsRequired => LockLevel::DocsRequired,
+            LockLevelArg::ReviewRequired => LockLevel::ReviewRequired,
+            LockLevelArg::Normal => LockLevel::Normal,
+            LockLevelArg::Experimental => LockLevel::Experimental,
+        }
+    }
+}
+
 /// Annotation generation level
 #[derive(clap::ValueEnum, Clone, Copy, Debug, Default)]
 enum AnnotateLevelArg {
@@ -626,6 +874,10 @@ enum DaemonCommands {
         /// HTTP server port
         #[arg(long, default_value = "9222")]
         port: u16,
+
+        /// Re-index on file changes and serve /cache and /events (Server-Sent Events)
+        #[arg(long)]
+        watch: bool,
     },
 
     /// Stop the ACP daemon
@@ -660,14 +912,61 @@ enum BridgeCommands {
 enum QueryCommands {
     /// Query a symbol
     Symbol {
-        /// Symbol name
+        /// Symbol name (or pattern with --glob/--regex/--fuzzy)
         name: String,
+
+        /// Treat name as a glob pattern and list all matches
+        #[arg(long, conflicts_with_all = ["regex", "fuzzy"])]
+        glob: bool,
+
+        /// Treat name as a regular expression and list all matches
+        #[arg(long, conflicts_with = "fuzzy")]
+        regex: bool,
+
+        /// Rank symbols by how closely their names match (subsequence scoring)
+        #[arg(long)]
+        fuzzy: bool,
+
+        /// Also show direct callers and callees, with the files defining them
+        /// (--json prints an array with one entry per definition)
+        #[arg(
+            long,
+            visible_alias = "callers-callees",
+            conflicts_with_all = ["glob", "regex", "fuzzy"]
+        )]
+        full: bool,
+
+        /// Match the name ignoring case and accents (cafe finds Café) and show every match
+        #[arg(short = 'i', long, conflicts_with_all = ["glob", "regex", "fuzzy"])]
+        ignore_case: bool,
     },
 
     /// Query a file
     File {
         /// File path
         path: String,
+
+        /// List the file's symbols sorted by line (an outline) instead of its metadata
+        #[arg(long)]
+        symbols: bool,
+    },
+
+    /// List every symbol, sorted by qualified name
+    Symbols {
+        /// Stream one JSON object per line (for jq)
+        #[arg(long)]
+        ndjson: bool,
+    },
+
+    /// List every file, sorted by path
+    Files {
+        /// Stream one JSON object per line (for jq)
+        #[arg(long)]
+        ndjson: bool,
+
+        /// Only files carrying this tag (from the config's `tags`)
+        #[arg(long)]
+        tag: Option<String>,
     },
 
     /// Get callers of a symbol
@@ -680,6 +979,35 @@ enum QueryCommands {
     Callees {
         /// Symbol name
         symbol: String,
+
+        /// Also list possible callees guessed from string literals (call_graph.heuristic_edges)
+        #[arg(long)]
+        include_heuristic: bool,
+
+        /// Only callees not defined in the cache; language builtins are listed only with call_graph.include_stdlib
+        #[arg(long, conflicts_with_all = ["internal", "include_heuristic"])]
+        external: bool,
+
+        /// Only callees defined in the cache, with their location
+        #[arg(long, conflicts_with = "include_heuristic")]
+        internal: bool,
+    },
+
+    /// Show the shortest call path from one symbol to another
+    Path {
+        /// Calling symbol
+        from: String,
+
+        /// Symbol to reach
+        to: String,
+
+        /// List several distinct paths, shortest first
+        #[arg(long)]
+        all: bool,
+
+        /// Maximum number of paths for --all
+        #[arg(long, default_value = "10", requires = "all")]
+        max: usize,
     },
 
     /// List domains
@@ -691,17 +1019,125 @@ enum QueryCommands {
         name: String,
     },
 
-    /// List hotpaths
-    Hotpaths,
+    /// Rank symbols by fan-in × fan-out (a heuristic on the name-resolved call graph)
+    Hotpaths {
+        /// Also weight by git churn (commits to the defining file)
+        #[arg(long)]
+        churn: bool,
+    },
 
     /// Show stats
-    Stats,
+    Stats {
+        /// Break down files, symbols, lines and coverage by language
+        #[arg(long, conflicts_with = "by_domain")]
+        by_language: bool,
+
+        /// Break down files, symbols, lines and coverage by domain
+        #[arg(long, conflicts_with = "history")]
+        by_domain: bool,
+
+        /// Show coverage over past `acp index --history` snapshots
+        #[arg(long, conflicts_with = "by_language")]
+        history: bool,
+
+        /// Number of most recent snapshots to show with --history
+        #[arg(long, default_value = "20", requires = "history")]
+        last: usize,
+    },
+
+    /// Show annotation coverage by domain and language
+    Coverage,
 
     /// RFC-0003: Show provenance statistics
-    Provenance,
+    Provenance {
+        /// List every annotation flagged for review instead of statistics
+        #[arg(long)]
+        needs_review: bool,
+
+        /// Filter listed annotations by source origin (explicit, converted, heuristic, refined, inferred)
+        #[arg(long, requires = "needs_review")]
+        source: Option<String>,
+
+        /// Filter listed annotations by confidence expression (e.g., "<0.7", ">=0.9")
+        #[arg(long, requires = "needs_review")]
+        confidence: Option<String>,
+    },
+
+    /// List deprecated files and symbols with their message and since version
+    Deprecated,
+
+    /// List locked files and symbols grouped by lock level, with their directives
+    Constraints {
+        /// Only this lock level (default: every level except normal)
+        #[arg(long, value_enum)]
+        level: Option<LockLevelArg>,
+
+        /// Also list hack markers and whether they have expired
+        #[arg(long)]
+        hacks: bool,
+    },
+
+    /// List symbols by behavioral annotation; combined flags must all match
+    Behavior {
+        /// Symbols marked @acp:pure
+        #[arg(long)]
+        pure: bool,
+
+        /// Symbols marked @acp:idempotent
+        #[arg(long)]
+        idempotent: bool,
+
+        /// Symbols marked @acp:memoized
+        #[arg(long)]
+        memoized: bool,
+
+        /// Symbols marked @acp:async
+        #[arg(long = "async")]
+        is_async: bool,
+
+        /// Symbols marked @acp:generator
+        #[arg(long)]
+        generator: bool,
+
+        /// Symbols marked @acp:throttled
+        #[arg(long)]
+        throttled: bool,
+
+        /// Symbols marked @acp:transactional
+        #[arg(long)]
+        transactional: bool,
+
+        /// Symbols with @acp:side-effects
+        #[arg(long)]
+        side_effects: bool,
+
+        /// Symbols that call themselves, directly or through a short cycle (from the call graph)
+        #[arg(long)]
+        recursive: bool,
+    },
+
+    /// List symbols that declare they throw an exception (@acp:throws and doc comments)
+    Throws {
+        /// Exception type (or pattern with --glob)
+        exception: String,
+
+        /// Treat the exception as a glob pattern
+        #[arg(long)]
+        glob: bool,
+    },
 }
 
 /// RFC-0003: Review subcommands
+#[derive(Subcommand)]
+enum ExportCommands {
+    /// Write an Exuberant-ctags tags file for editor navigation
+    Ctags {
+        /// Output tags file
+        #[arg(short, long, default_value = "tags")]
+        output: PathBuf,
+    },
+}
+
 #[derive(Subcommand)]
 enum ReviewCommands {
     /// List annotations needing review
@@ -720,10 +1156,17 @@ enum ReviewCommands {
         /// Mark all matching annotations as reviewed
         #[arg(long)]
         all: bool,
+
+        /// Mark auto-generated annotations with confidence at or above this threshold
+        #[arg(long, conflicts_with = "all")]
+        above: Option<f64>,
     },
 
     /// Interactive review mode
     Interactive,
+
+    /// Show past annotation generation runs from the audit log
+    History,
 }
 
 /// RFC-0015: Context subcommands for operation-specific context
@@ -760,12 +1203,10 @@ enum ContextCommands {
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
-    // Load config
-    let config = if cli.config.exists() {
-        Config::load(&cli.config)?
-    } else {
-        Config::default()
-    };
+    // Settings resolve as: CLI flag > ACP_* env var > config file > built-in default
+    let env = EnvOverrides::from_env()?;
+    let (config_path, config) = Config::load_layered(cli.config.clone(), &env)?;
+    let default_cache = config.cache_path();
 
     // Check for config requirement (most commands require .acp.config.json)
     let requires_config = !matches!(
@@ -776,18 +1217,17 @@ async fn main() -> anyhow::Result<()> {
             | Commands::Daemon { .. }
             | Commands::Primer { .. }
             | Commands::Context { .. }
+            | Commands::Scan { .. }
     );
-    if requires_config {
-        let config_path = PathBuf::from(".acp.config.json");
-        if !config_path.exists() {
-            eprintln!(
-                "{} No .acp.config.json found in project root",
-                style("✗").red()
-            );
-            eprintln!("  Run 'acp init' to initialize the project");
-            eprintln!("  Use 'acp init --help' for configuration options");
-            std::process::exit(1);
-        }
+    if requires_config && !config_path.exists() {
+        eprintln!(
+            "{} No {} found in project root",
+            style("✗").red(),
+            config_path.display()
+        );
+        eprintln!("  Run 'acp init' to initialize the project");
+        eprintln!("  Use 'acp init --help' for configuration options");
+        std::process::exit(1);
     }
 
     match cli.command {
@@ -801,6 +1241,7 @@ async fn main() -> anyhow::Result<()> {
             workers,
             yes,
             no_bootstrap,
+            profile,
         } => {
             let options = InitOptions {
                 force,
@@ -812,6 +1253,7 @@ async fn main() -> anyhow::Result<()> {
                 workers,
                 yes,
                 no_bootstrap,
+                profile,
             };
             execute_init(options)?;
         }
@@ -853,13 +1295,23 @@ async fn main() -> anyhow::Result<()> {
             vars,
             bridge,
             no_bridge,
+            git_changed_only,
+            strict,
+            history,
+            dry_run,
+            json,
         } => {
             let options = IndexOptions {
-                root,
-                output,
+                root: root.unwrap_or_else(|| config.root.clone()),
+                output: output.unwrap_or(default_cache),
                 vars,
                 bridge,
                 no_bridge,
+                git_changed_only,
+                strict,
+                history,
+                dry_run,
+                json,
             };
             execute_index(options, config).await?;
         }
@@ -868,60 +1320,249 @@ async fn main() -> anyhow::Result<()> {
             let subcommand = match subcommand {
                 BridgeCommands::Status { json } => BridgeSubcommand::Status { json },
             };
-            let options = BridgeOptions { cache, subcommand };
+            let options = BridgeOptions {
+                cache: cache.unwrap_or(default_cache),
+                subcommand,
+            };
             execute_bridge(options, config)?;
         }
 
-        Commands::Vars { cache, output } => {
-            let options = VarsOptions { cache, output };
+        Commands::Vars {
+            cache,
+            output,
+            fresh,
+        } => {
+            let options = VarsOptions {
+                cache: cache.unwrap_or(default_cache),
+                output,
+                fresh,
+            };
             execute_vars(options)?;
         }
 
-        Commands::Query { query, cache, json } => {
-            let options = QueryOptions {
-                cache,
-                json,
+        Commands::Export { format, cache } => {
+            let subcommand = match format {
+                ExportCommands::Ctags { output } => ExportSubcommand::Ctags { output },
+            };
+            let options = ExportOptions {
+                cache: cache.unwrap_or(default_cache),
+                subcommand,
+            };
+            execute_export(options, &config)?;
+        }
+
+        Commands::Query {
+            query,
+            describe,
+            cache,
+            json,
+            format,
+            limit,
+            offset,
+        } => {
+            let query = match (query, describe) {
+                (None, true) => {
+                    let cli = Cli::command();
+                    let query = cli.find_subcommand("query").expect("query command");
+                    return execute_query_describe(query, json);
+                }
+                (Some(_), true) => {
+                    anyhow::bail!("--describe cannot be combined with a query subcommand")
+                }
+                (None, false) => {
+                    anyhow::bail!("A query subcommand is required (see acp query --help)")
+                }
+                (Some(query), false) => query,
+            };
+            let shields = matches!(format, Some(QueryFormatArg::Shields));
+            if shields && !matches!(query, QueryCommands::Coverage) {
+                anyhow::bail!("--format shields is only supported by query coverage");
+            }
+            let mut options = QueryOptions {
+                cache: cache.unwrap_or(default_cache),
+                json: json || matches!(format, Some(QueryFormatArg::Json)),
+                format: format.and_then(|f| match f {
+                    QueryFormatArg::Csv => Some(TabularFormat::Csv),
+                    QueryFormatArg::Tsv => Some(TabularFormat::Tsv),
+                    QueryFormatArg::Json | QueryFormatArg::Shields => None,
+                }),
                 source: None,
                 confidence: None,
                 needs_review: false,
+                provenance: config.annotate.provenance.clone(),
+                page: Page { limit, offset },
+                include_stdlib: config.call_graph.as_ref().is_some_and(|c| c.include_stdlib),
             };
             let subcommand = match query {
-                QueryCommands::Symbol { name } => QuerySubcommand::Symbol { name },
-                QueryCommands::File { path } => QuerySubcommand::File { path },
+                QueryCommands::Symbol {
+                    name,
+                    glob,
+                    regex,
+                    fuzzy,
+                    full,
+                    ignore_case,
+                } => {
+                    let mode = if glob {
+                        SymbolMatchMode::Glob
+                    } else if regex {
+                        SymbolMatchMode::Regex
+                    } else if fuzzy {
+                        SymbolMatchMode::Fuzzy
+                    } else {
+                        SymbolMatchMode::Exact
+                    };
+                    QuerySubcommand::Symbol {
+                        name,
+                        mode,
+                        full,
+                        ignore_case,
+                    }
+                }
+                QueryCommands::File { path, symbols } => QuerySubcommand::File { path, symbols },
+                QueryCommands::Symbols { ndjson } => QuerySubcommand::Symbols { ndjson },
+                QueryCommands::Files { ndjson, tag } => QuerySubcommand::Files { ndjson, tag },
                 QueryCommands::Callers { symbol } => QuerySubcommand::Callers { symbol },
-                QueryCommands::Callees { symbol } => QuerySubcommand::Callees { symbol },
+                QueryCommands::Callees {
+                    symbol,
+                    include_heuristic,
+                    external,
+                    internal,
+                } => QuerySubcommand::Callees {
+                    symbol,
+                    include_heuristic,
+                    filter: if external {
+                        Some(CalleeFilter::External)
+                    } else if internal {
+                        Some(CalleeFilter::Internal)
+                    } else {
+                        None
+                    },
+                },
+                QueryCommands::Path { from, to, all, max } => QuerySubcommand::Path {
+                    from,
+                    to,
+                    max: if all { max } else { 1 },
+                },
                 QueryCommands::Domains => QuerySubcommand::Domains,
                 QueryCommands::Domain { name } => QuerySubcommand::Domain { name },
-                QueryCommands::Hotpaths => QuerySubcommand::Hotpaths,
-                QueryCommands::Stats => QuerySubcommand::Stats,
-                QueryCommands::Provenance => QuerySubcommand::Provenance,
+                QueryCommands::Hotpaths { churn } => QuerySubcommand::Hotpaths { churn },
+                QueryCommands::Stats {
+                    history: true,
+                    last,
+                    ..
+                } => QuerySubcommand::StatsHistory { last },
+                QueryCommands::Stats {
+                    by_language,
+                    by_domain,
+                    ..
+                } => QuerySubcommand::Stats {
+                    by: if by_language {
+                        Some(StatsGroup::Language)
+                    } else if by_domain {
+                        Some(StatsGroup::Domain)
+                    } else {
+                        None
+                    },
+                },
+                QueryCommands::Coverage => QuerySubcommand::Coverage {
+                    shields,
+                    thresholds: config.coverage.clone(),
+                },
+                QueryCommands::Provenance {
+                    needs_review,
+                    source,
+                    confidence,
+                } => {
+                    options.needs_review = needs_review;
+                    options.source = source
+                        .map(|s| s.parse())
+                        .transpose()
+                        .map_err(|e: String| anyhow::anyhow!(e))?;
+                    options.confidence = confidence;
+                    QuerySubcommand::Provenance
+                }
+                QueryCommands::Deprecated => QuerySubcommand::Deprecated,
+                QueryCommands::Constraints { level, hacks } => QuerySubcommand::Constraints {
+                    level: level.map(Into::into),
+                    hacks,
+                },
+                QueryCommands::Behavior {
+                    pure,
+                    idempotent,
+                    memoized,
+                    is_async,
+                    generator,
+                    throttled,
+                    transactional,
+                    side_effects,
+                    recursive,
+                } => QuerySubcommand::Behavior {
+                    filter: BehaviorFilter {
+                        pure,
+                        idempotent,
+                        memoized,
+                        r#async: is_async,
+                        generator,
+                        throttled,
+                        transactional,
+                        side_effects,
+                        recursive,
+                    },
+                },
+                QueryCommands::Throws { exception, glob } => {
+                    QuerySubcommand::Throws { exception, glob }
+                }
             };
             execute_query(options, subcommand)?;
         }
 
         Commands::Expand {
             text,
+            file,
             mode,
             vars,
             chains,
+            strict,
         } => {
             let options = ExpandOptions {
                 text,
+                file,
                 mode,
                 vars,
                 chains,
+                strict,
             };
             execute_expand(options)?;
         }
 
-        Commands::Chain { name, vars, tree } => {
-            let options = ChainOptions { name, vars, tree };
+        Commands::Chain {
+            name,
+            vars,
+            tree,
+            flat,
+            max_depth,
+        } => {
+            let options = ChainOptions {
+                name,
+                vars,
+                tree,
+                flat,
+                max_depth,
+            };
             execute_chain(options)?;
         }
 
-        Commands::Watch { root } => {
-            let options = WatchOptions { root };
-            execute_watch(options, config)?;
+        Commands::Watch {
+            root,
+            exec,
+            debounce,
+        } => {
+            let options = WatchOptions {
+                root,
+                exec,
+                debounce: std::time::Duration::from_millis(debounce),
+            };
+            execute_watch(options, config).await?;
         }
 
         Commands::Attempt { cmd } => {
@@ -963,9 +1604,45 @@ async fn main() -> anyhow::Result<()> {
             execute_attempt(subcommand)?;
         }
 
-        Commands::Check { file, cache } => {
-            let options = CheckOptions { file, cache };
-            execute_check(options)?;
+        Commands::Check {
+            files,
+            cache,
+            format,
+            types,
+            policy,
+            fix,
+            dry_run,
+        } => {
+            let format = match format {
+                CheckFormatArg::Text => CheckFormat::Text,
+                CheckFormatArg::Sarif => CheckFormat::Sarif,
+            };
+            let options = CheckOptions {
+                files,
+                cache: cache.unwrap_or(default_cache),
+                format,
+                types,
+                policy,
+                fix,
+                dry_run,
+            };
+            execute_check(options, &config)?;
+        }
+
+        Commands::Scan { root, json } => {
+            execute_scan(ScanOptions { root, json }, &config)?;
+        }
+
+        Commands::Gate { cache, json } => {
+            let options = GateOptions {
+                cache: cache.unwrap_or(default_cache),
+                json,
+            };
+            execute_gate(options, &config)?;
+        }
+
+        Commands::Diff { old, new, json } => {
+            execute_diff(DiffOptions { old, new, json })?;
         }
 
         Commands::Context {
@@ -975,7 +1652,7 @@ async fn main() -> anyhow::Result<()> {
             verbose,
         } => {
             let options = ContextOptions {
-                cache,
+                cache: cache.unwrap_or(default_cache),
                 json,
                 verbose,
             };
@@ -999,27 +1676,47 @@ async fn main() -> anyhow::Result<()> {
             execute_revert(options)?;
         }
 
-        Commands::Validate { file } => {
-            let options = ValidateOptions { file };
-            execute_validate(options)?;
+        Commands::Validate {
+            file,
+            refs,
+            semver,
+            annotations,
+        } => {
+            let options = ValidateOptions {
+                file,
+                refs,
+                semver,
+                annotations,
+            };
+            execute_validate(options, &config)?;
         }
 
         Commands::Daemon { cmd } => {
             let subcommand = match cmd {
-                DaemonCommands::Start { foreground, port } => {
-                    DaemonSubcommand::Start { foreground, port }
-                }
+                DaemonCommands::Start {
+                    foreground,
+                    port,
+                    watch,
+                } => DaemonSubcommand::Start {
+                    foreground,
+                    port,
+                    watch,
+                    config_path: config_path.clone(),
+                },
                 DaemonCommands::Stop => DaemonSubcommand::Stop,
                 DaemonCommands::Status => DaemonSubcommand::Status,
                 DaemonCommands::Logs { lines, follow } => DaemonSubcommand::Logs { lines, follow },
             };
-            execute_daemon(subcommand)?;
+            execute_daemon(subcommand, &config).await?;
         }
 
         Commands::Annotate {
             path,
             apply,
             dry_run,
+            backup: _,
+            no_backup,
+            undo,
             convert,
             from,
             level,
@@ -1032,6 +1729,8 @@ async fn main() -> anyhow::Result<()> {
             workers,
             no_provenance,
             mark_needs_review,
+            stdin,
+            lang,
         } => {
             // --dry-run overrides --apply (for explicit user intent)
             let apply = apply && !dry_run;
@@ -1042,15 +1741,7 @@ async fn main() -> anyhow::Result<()> {
                 AnnotateLevelArg::Full => AnnotateLevel::Full,
             };
 
-            let conversion_source = match from {
-                AnnotateFrom::Auto => ConversionSource::Auto,
-                AnnotateFrom::Jsdoc => ConversionSource::Jsdoc,
-                AnnotateFrom::Tsdoc => ConversionSource::Tsdoc,
-                AnnotateFrom::Docstring => ConversionSource::Docstring,
-                AnnotateFrom::Rustdoc => ConversionSource::Rustdoc,
-                AnnotateFrom::Godoc => ConversionSource::Godoc,
-                AnnotateFrom::Javadoc => ConversionSource::Javadoc,
-            };
+            let conversion_source = ConversionSource::from(from);
 
             let output_format = match format {
                 AnnotateFormat::Diff => OutputFormat::Diff,
@@ -1061,6 +1752,8 @@ async fn main() -> anyhow::Result<()> {
             let options = AnnotateOptions {
                 path,
                 apply,
+                backup: !no_backup,
+                undo,
                 convert,
                 from: conversion_source,
                 level: annotate_level,
@@ -1070,10 +1763,12 @@ async fn main() -> anyhow::Result<()> {
                 symbols_only,
                 check,
                 min_coverage,
-                workers,
+                workers: workers.or(config.workers),
                 verbose: cli.verbose,
                 no_provenance,
                 mark_needs_review,
+                stdin,
+                lang,
             };
 
             execute_annotate(options, config)?;
@@ -1087,17 +1782,28 @@ async fn main() -> anyhow::Result<()> {
             json,
         } => {
             let options = ReviewOptions {
-                cache,
+                cache: cache.unwrap_or(default_cache),
+                generation_log: GenerationLog::for_config(&config).path().to_path_buf(),
                 source: source.and_then(|s| s.parse().ok()),
                 confidence,
                 json,
+                provenance: config.annotate.provenance.clone(),
             };
             let subcommand = match cmd {
                 ReviewCommands::List => ReviewSubcommand::List,
-                ReviewCommands::Mark { file, symbol, all } => {
-                    ReviewSubcommand::Mark { file, symbol, all }
-                }
+                ReviewCommands::Mark {
+                    file,
+                    symbol,
+                    all,
+                    above,
+                } => ReviewSubcommand::Mark {
+                    file,
+                    symbol,
+                    all,
+                    above,
+                },
                 ReviewCommands::Interactive => ReviewSubcommand::Interactive,
+                ReviewCommands::History => ReviewSubcommand::History,
             };
             execute_review(options, subcommand)?;
         }
@@ -1109,12 +1815,13 @@ async fn main() -> anyhow::Result<()> {
             format,
             cache,
         } => {
-            let cache_data = Cache::from_json(&cache)?;
+            let cache_data = Cache::from_json(cache.unwrap_or(default_cache))?;
 
             let map_format = match format {
                 MapFormatArg::Tree => MapFormat::Tree,
                 MapFormatArg::Flat => MapFormat::Flat,
                 MapFormatArg::Json => MapFormat::Json,
+                MapFormatArg::Mermaid => MapFormat::Mermaid,
             };
 
             let options = MapOptions {
@@ -1128,31 +1835,34 @@ async fn main() -> anyhow::Result<()> {
 
         Commands::Migrate {
             add_directives,
+            from,
             paths,
             dry_run,
             interactive,
             backup,
             cache,
         } => {
-            if !add_directives {
+            if !add_directives && from.is_none() {
                 eprintln!(
-                    "{} Currently only --add-directives is supported",
+                    "{} Choose a migration: --add-directives or --from <format>",
                     style("!").yellow()
                 );
                 eprintln!("  Run: acp migrate --add-directives");
+                eprintln!("   or: acp migrate --from jsdoc");
                 std::process::exit(1);
             }
 
-            let cache_data = Cache::from_json(&cache)?;
+            let cache_data = Cache::from_json(cache.unwrap_or(default_cache))?;
 
             let options = MigrateOptions {
                 paths,
                 dry_run,
                 interactive,
                 backup,
+                from: from.map(ConversionSource::from),
             };
 
-            execute_migrate(&cache_data, options)?;
+            execute_migrate(&cache_data, options, &config)?;
         }
 
         Commands::Primer {
@@ -1161,6 +1871,7 @@ async fn main() -> anyhow::Result<()> {
             format,
             json,
             preset,
+            weights,
             include,
             exclude,
             categories,
@@ -1191,11 +1902,19 @@ async fn main() -> anyhow::Result<()> {
             let options = PrimerOptions {
                 budget,
                 capabilities,
-                cache: if cache.exists() { Some(cache) } else { None },
+                cache: Some(cache.unwrap_or(default_cache)).filter(|c| c.exists()),
                 primer_config,
                 format: output_format,
                 json,
                 preset,
+                // Config weights first so CLI values override them
+                weights: config
+                    .primer
+                    .weights
+                    .clone()
+                    .into_iter()
+                    .chain(weights)
+                    .collect(),
                 include,
                 exclude,
                 categories,
@@ -1214,3 +1933,29 @@ async fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Parse a `SECTION=WEIGHT` pair for `acp primer --weight`
+fn parse_weight(s: &str) -> Result<(String, f64), String> {
+    let (name, weight) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected SECTION=WEIGHT, got '{}'", s))?;
+    let weight = weight
+        .trim()
+        .parse::<f64>()
+        .map_err(|e| format!("invalid weight '{}': {}", weight, e))?;
+    Ok((name.trim().to_string(), weight))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_describe_covers_every_query() {
+        let cli = Cli::command();
+        let query = cli.find_subcommand("query").unwrap();
+        let descriptor = acp::commands::describe_queries(query).unwrap();
+        let queries = descriptor["queries"].as_array().unwrap();
+        assert_eq!(queries.len(), query.get_subcommands().count());
+    }
+}
@@ -0,0 +1,536 @@
+//! @acp:module "Comment Regions"
+//! @acp:summary "Minimal per-language lexing that isolates comment and docstring text"
+//! @acp:domain cli
+//! @acp:layer service
+//!
+//! Annotations only count inside comments. Instead of matching `@acp:`
+//! anywhere on a line, the source is lexed just far enough to tell comments
+//! and docstrings apart from code and string literals. The result has one
+//! line per source line:
+//!
+//! - line comments are kept verbatim (`// ...`, `# ...`)
+//! - block comments and docstrings lose their framing (`/*`, `*/`, a leading
+//!   `*` gutter, triple quotes, `=begin`/`=end`) and are prefixed with `//`;
+//!   indentation beyond the block's own is kept, so continuation lines still
+//!   read as continuations
+//! - lines with no comment text are empty
+//!
+//! The lexer is deliberately small: it knows string, char and raw-string
+//! literals well enough not to mistake their contents for comments, but it
+//! does not parse regex literals or string interpolation.
+
+use crate::cache::Language;
+
+/// Lexical rules for one language family
+struct Syntax {
+    /// Line comment markers
+    line_comments: &'static [&'static str],
+    /// `/* ... */` block comments
+    block_comments: bool,
+    /// Block comments nest (Rust, Swift, Kotlin)
+    nested_blocks: bool,
+    /// Single-character string delimiters
+    quotes: &'static [u8],
+    /// `"` and `'` strings may span lines
+    multiline_strings: bool,
+    /// `'` starts a char literal, and only if it closes (so Rust lifetimes pass)
+    char_literals: bool,
+    /// `"""` strings (Python, Java/Kotlin/Swift text blocks)
+    triple_quotes: bool,
+    /// Triple-quoted strings that open a statement are docstrings (Python)
+    docstrings: bool,
+    /// Rust `r"..."` / `r#"..."#`
+    raw_strings: bool,
+    /// Backtick strings have no escapes (Go)
+    raw_backticks: bool,
+    /// Ruby `=begin` / `=end` blocks
+    begin_end: bool,
+}
+
+impl Syntax {
+    fn for_language(language: Language) -> Self {
+        const C_LIKE: Syntax = Syntax {
+            line_comments: &["//"],
+            block_comments: true,
+            nested_blocks: false,
+            quotes: b"\"",
+            multiline_strings: false,
+            char_literals: true,
+            triple_quotes: false,
+            docstrings: false,
+            raw_strings: false,
+            raw_backticks: false,
+            begin_end: false,
+        };
+
+        match language {
+            Language::Typescript | Language::Javascript => Syntax {
+                quotes: b"\"'`",
+                char_literals: false,
+                ..C_LIKE
+            },
+            Language::Rust => Syntax {
+                nested_blocks: true,
+                multiline_strings: true,
+                raw_strings: true,
+                ..C_LIKE
+            },
+            Language::Go => Syntax {
+                quotes: b"\"`",
+                raw_backticks: true,
+                ..C_LIKE
+            },
+            Language::Java => Syntax {
+                triple_quotes: true,
+                ..C_LIKE
+            },
+            Language::Kotlin | Language::Swift => Syntax {
+                nested_blocks: true,
+                triple_quotes: true,
+                ..C_LIKE
+            },
+            Language::CSharp | Language::Cpp | Language::C => C_LIKE,
+            Language::Php => Syntax {
+                line_comments: &["//", "#"],
+                quotes: b"\"'",
+                multiline_strings: true,
+                char_literals: false,
+                ..C_LIKE
+            },
+            Language::Python => Syntax {
+                line_comments: &["#"],
+                block_comments: false,
+                quotes: b"\"'",
+                char_literals: false,
+                triple_quotes: true,
+                docstrings: true,
+                ..C_LIKE
+            },
+            Language::Ruby => Syntax {
+                line_comments: &["#"],
+                block_comments: false,
+                quotes: b"\"'",
+                multiline_strings: true,
+                char_literals: false,
+                begin_end: true,
+                ..C_LIKE
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpanKind {
+    Line,
+    Block,
+    Docstring,
+}
+
+/// A comment or docstring as a byte range of the source
+#[derive(Debug)]
+struct Span {
+    start: usize,
+    end: usize,
+    kind: SpanKind,
+}
+
+/// @acp:summary "One normalized line per source line, holding only comment text"
+pub fn comment_lines(content: &str, language: Language) -> Vec<String> {
+    let syntax = Syntax::for_language(language);
+    let mut lines = vec![String::new(); content.lines().count()];
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+
+    for span in comment_spans(content, &syntax) {
+        let first = line_starts.partition_point(|&s| s <= span.start) - 1;
+        let text = &content[span.start..span.end];
+        let rendered = match span.kind {
+            SpanKind::Line => vec![text.trim_end().to_string()],
+            SpanKind::Block => strip_block(text),
+            SpanKind::Docstring => reframe(&text[3..text.len().saturating_sub(3).max(3)], false),
+        };
+
+        for (offset, line) in rendered.into_iter().enumerate() {
+            if let Some(slot) = lines.get_mut(first + offset) {
+                if !slot.is_empty() {
+                    slot.push(' ');
+                }
+                slot.push_str(&line);
+            }
+        }
+    }
+
+    lines
+}
+
+/// Find every comment and docstring in `src`
+fn comment_spans(src: &str, syntax: &Syntax) -> Vec<Span> {
+    let b = src.as_bytes();
+    let mut spans = Vec::new();
+    // First non-whitespace byte on the current line
+    let mut line_first: Option<usize> = None;
+    let mut i = 0;
+
+    while i < b.len() {
+        let c = b[i];
+        let rest = &src[i..];
+
+        if c == b'\n' {
+            line_first = None;
+            i += 1;
+            continue;
+        }
+        if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+        let first = *line_first.get_or_insert(i);
+
+        if syntax.begin_end && (i == 0 || b[i - 1] == b'\n') && rest.starts_with("=begin") {
+            let end = begin_end_block(src, i);
+            spans.push(Span {
+                start: i,
+                end,
+                kind: SpanKind::Block,
+            });
+            i = end;
+            continue;
+        }
+
+        if syntax.line_comments.iter().any(|m| rest.starts_with(m)) {
+            let end = rest.find('\n').map_or(b.len(), |n| i + n);
+            spans.push(Span {
+                start: i,
+                end,
+                kind: SpanKind::Line,
+            });
+            i = end;
+            continue;
+        }
+
+        if syntax.block_comments && rest.starts_with("/*") {
+            let end = block_end(b, i, syntax.nested_blocks);
+            spans.push(Span {
+                start: i,
+                end,
+                kind: SpanKind::Block,
+            });
+            i = end;
+            continue;
+        }
+
+        let triple = if rest.starts_with("\"\"\"") {
+            Some("\"\"\"")
+        } else if syntax.docstrings && rest.starts_with("'''") {
+            Some("'''")
+        } else {
+            None
+        };
+        if let Some(quote) = triple.filter(|_| syntax.triple_quotes) {
+            let end = string_end(src, i + 3, quote, true, true);
+            // Only string prefixes (r, u, b, f) may precede a docstring
+            let prefix = &src[first..i];
+            let is_docstring = syntax.docstrings
+                && prefix.len() <= 2
+                && prefix.bytes().all(|p| b"rRuUbBfF".contains(&p));
+            if is_docstring {
+                spans.push(Span {
+                    start: i,
+                    end,
+                    kind: SpanKind::Docstring,
+                });
+            }
+            i = end;
+            continue;
+        }
+
+        if syntax.raw_strings && c == b'r' && !(i > 0 && is_ident(b[i - 1])) {
+            let hashes = rest[1..].bytes().take_while(|&h| h == b'#').count();
+            if b.get(i + 1 + hashes) == Some(&b'"') {
+                let close = format!("\"{}", "#".repeat(hashes));
+                i = string_end(src, i + 2 + hashes, &close, true, false);
+                continue;
+            }
+        }
+
+        if syntax.quotes.contains(&c) {
+            i = if c == b'`' {
+                string_end(src, i + 1, "`", true, !syntax.raw_backticks)
+            } else {
+                let quote = if c == b'"' { "\"" } else { "'" };
+                string_end(src, i + 1, quote, syntax.multiline_strings, true)
+            };
+            continue;
+        }
+
+        if c == b'\'' && syntax.char_literals {
+            i = char_literal_end(src, i);
+            continue;
+        }
+
+        // Skip a whole code character (keeps slicing on char boundaries)
+        i += rest.chars().next().map_or(1, char::len_utf8);
+    }
+
+    spans
+}
+
+fn is_ident(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_'
+}
+
+/// End of a `/* ... */` comment starting at `start` (or end of input)
+fn block_end(b: &[u8], start: usize, nested: bool) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+    while i + 1 < b.len() {
+        if b[i] == b'/' && b[i + 1] == b'*' && (nested || depth == 0) {
+            depth += 1;
+            i += 2;
+        } else if b[i] == b'*' && b[i + 1] == b'/' {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    b.len()
+}
+
+/// End of a Ruby `=begin` block: the end of its `=end` line (or end of input)
+fn begin_end_block(src: &str, start: usize) -> usize {
+    let mut pos = src[start..].find('\n').map_or(src.len(), |n| start + n + 1);
+    while pos < src.len() {
+        let line_end = src[pos..].find('\n').map_or(src.len(), |n| pos + n);
+        if src[pos..line_end].starts_with("=end") {
+            return line_end;
+        }
+        pos = line_end + 1;
+    }
+    src.len()
+}
+
+/// Byte just past the closing `quote`, scanning from `from`
+///
+/// Unterminated single-line strings end at the newline; others run to the
+/// end of input.
+fn string_end(src: &str, from: usize, quote: &str, multiline: bool, escapes: bool) -> usize {
+    let b = src.as_bytes();
+    let mut i = from;
+    while i < b.len() {
+        if escapes && b[i] == b'\\' {
+            i += 2;
+        } else if b[i..].starts_with(quote.as_bytes()) {
+            // Compare bytes: `i` may sit inside a multi-byte character
+            return i + quote.len();
+        } else if b[i] == b'\n' && !multiline {
+            return i;
+        } else {
+            i += 1;
+        }
+    }
+    b.len()
+}
+
+/// Byte past a char literal such as `'x'` or `'\n'`; a lone `'` is skipped
+fn char_literal_end(src: &str, start: usize) -> usize {
+    let rest = &src[start + 1..];
+    let body = if rest.starts_with('\\') {
+        // Escapes are short: '\n', '\x7f', '\u{1F600}'
+        rest.char_indices()
+            .skip(1)
+            .take(10)
+            .find(|&(_, ch)| ch == '\'' || ch == '\n')
+            .filter(|&(_, ch)| ch == '\'')
+            .map(|(n, _)| n)
+    } else {
+        rest.chars()
+            .next()
+            .filter(|&ch| ch != '\n')
+            .map(char::len_utf8)
+            .filter(|&n| rest[n..].starts_with('\''))
+    };
+    body.map_or(start + 1, |n| start + 1 + n + 1)
+}
+
+/// Lines of a block comment without its delimiters
+fn strip_block(text: &str) -> Vec<String> {
+    if let Some(body) = text.strip_prefix("=begin") {
+        // Drop the `=end` line itself
+        let body =
+            body.rsplit_once('\n').map_or(
+                body,
+                |(body, last)| {
+                    if last.starts_with("=end") {
+                        body
+                    } else {
+                        text
+                    }
+                },
+            );
+        let mut lines = reframe(body, false);
+        if lines.len() < text.lines().count() {
+            lines.push("//".to_string());
+        }
+        return lines;
+    }
+
+    let body = text[2..].trim_start_matches('*');
+    let body = body.strip_suffix("*/").unwrap_or(body);
+    reframe(body, true)
+}
+
+/// Prefix comment text with `//`, stripping a `*` gutter and common indentation
+///
+/// The first line is the text right after the opening delimiter. Later lines
+/// either carry a `*` gutter (when `starred`), whose text is kept as is, or
+/// are dedented together like `inspect.cleandoc`.
+fn reframe(body: &str, starred: bool) -> Vec<String> {
+    let lines: Vec<&str> = body.split('\n').map(str::trim_end).collect();
+    let gutter = |line: &str| {
+        starred
+            .then(|| line.trim_start().strip_prefix('*'))
+            .flatten()
+            .map(str::to_string)
+    };
+    let indent = lines
+        .iter()
+        .skip(1)
+        .filter(|l| gutter(l).is_none() && !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let text = if i == 0 {
+                line.to_string()
+            } else if let Some(rest) = gutter(line) {
+                rest
+            } else if line.trim().is_empty() {
+                String::new()
+            } else {
+                format!(" {}", line.get(indent..).unwrap_or(line.trim_start()))
+            };
+            format!("//{}", text)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn annotated(lines: &[String]) -> Vec<(usize, &str)> {
+        lines
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| l.contains("@acp:"))
+            .map(|(i, l)| (i + 1, l.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_c_style_blocks_and_strings() {
+        let src = r#"/** @acp:module "Auth" */
+const url = "// @acp:fake not a comment";
+const tpl = `/* @acp:fake */`;
+/*
+ * @acp:summary "Login"
+ *   continued on the next line
+ */
+function login() { return 1; } /* @acp:perf hot */
+/*
+  @acp:lock frozen
+*/
+"#;
+        let lines = comment_lines(src, Language::Typescript);
+        assert_eq!(lines.len(), src.lines().count());
+        assert_eq!(
+            annotated(&lines),
+            vec![
+                (1, r#"// @acp:module "Auth""#),
+                (5, r#"// @acp:summary "Login""#),
+                (8, "// @acp:perf hot"),
+                (10, "// @acp:lock frozen"),
+            ]
+        );
+        assert_eq!(lines[5], "//   continued on the next line");
+        assert!(lines[1].is_empty() && lines[2].is_empty());
+    }
+
+    #[test]
+    fn test_rust_lifetimes_raw_strings_and_nesting() {
+        let src = r###"fn f<'a>(x: &'a str) -> char { '"' }
+const S: &str = r#"// @acp:fake "quoted""#;
+const T: &str = "a → b"; // @acp:perf after unicode
+/* outer /* inner */ @acp:todo still a comment */
+"###;
+        let lines = comment_lines(src, Language::Rust);
+        assert_eq!(
+            annotated(&lines),
+            vec![
+                (3, "// @acp:perf after unicode"),
+                (4, "// outer /* inner */ @acp:todo still a comment")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_python_docstrings() {
+        let src = r#"def load(path):
+    """Load the config.
+
+    @acp:summary "Load config"
+        from the given path
+    """
+    text = """@acp:fake inside a string value"""
+    # @acp:todo handle errors
+    return r'''@acp:fake'''
+"#;
+        let lines = comment_lines(src, Language::Python);
+        assert_eq!(
+            annotated(&lines),
+            vec![
+                (4, r#"// @acp:summary "Load config""#),
+                (8, "# @acp:todo handle errors"),
+            ]
+        );
+        assert_eq!(lines[4], "//     from the given path");
+    }
+
+    #[test]
+    fn test_non_ascii_string_literals() {
+        // Multi-byte characters inside strings must not split the scan
+        let ts =
+            "const s = \"café → ☕\"; // @acp:perf hot\nconst t = `naïve`; /* @acp:todo ü */\n";
+        let lines = comment_lines(ts, Language::Typescript);
+        assert_eq!(
+            annotated(&lines),
+            vec![(1, "// @acp:perf hot"), (2, "// @acp:todo ü")]
+        );
+
+        let py = "name = \"Zoë\"  # @acp:owner ünïcode\ndoc = '''日本語''' # @acp:todo\n";
+        let lines = comment_lines(py, Language::Python);
+        assert_eq!(
+            annotated(&lines),
+            vec![(1, "# @acp:owner ünïcode"), (2, "# @acp:todo")]
+        );
+    }
+
+    #[test]
+    fn test_ruby_begin_end() {
+        let src = "x = \"# @acp:fake\"\n=begin\n@acp:summary \"Ruby\"\n=end\n# @acp:owner team\n";
+        let lines = comment_lines(src, Language::Ruby);
+        assert_eq!(
+            annotated(&lines),
+            vec![(3, r#"// @acp:summary "Ruby""#), (5, "# @acp:owner team")]
+        );
+    }
+}
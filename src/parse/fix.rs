@@ -0,0 +1,257 @@
+//! @acp:module "Annotation Fixes"
+//! @acp:summary "Plan and apply mechanical fixes to @acp: annotations"
+//! @acp:domain cli
+//! @acp:layer service
+//!
+//! Only rewrites that need no judgement are planned: spelling out the
+//! directive the parser would otherwise generate, rewriting hack expiry dates
+//! into `YYYY-MM-DD`, and adding `@acp:lock normal` where an
+//! `@acp:lock-reason` explains a lock that was never declared. Anything else
+//! is reported as skipped and left for a human.
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use super::{comment_lines, Parser};
+use crate::cache::Language;
+
+/// Annotations whose directive tells an agent how to treat the code
+const DIRECTIVE_ANNOTATIONS: &[&str] = &[
+    "lock",
+    "ref",
+    "hack",
+    "deprecated",
+    "todo",
+    "fixme",
+    "critical",
+    "perf",
+];
+
+/// Date layouts accepted for `expires=`, rewritten to `%Y-%m-%d`
+const DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%Y/%m/%d", "%Y.%m.%d", "%Y%m%d"];
+
+/// @acp:summary "Kind of mechanical annotation fix"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FixKind {
+    /// Spell out the default directive
+    Directive,
+    /// Rewrite a hack expiry date as YYYY-MM-DD
+    HackDate,
+    /// Insert `@acp:lock normal` for an orphaned `@acp:lock-reason`
+    MissingLock,
+}
+
+/// @acp:summary "One planned rewrite of a source line"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AnnotationFix {
+    pub kind: FixKind,
+    /// Source line (1-indexed)
+    pub line: usize,
+    /// Line text before the fix; empty for inserted lines
+    pub before: String,
+    /// Replacement text, or the line inserted above `line`
+    pub after: String,
+}
+
+/// @acp:summary "Fixes for one file, plus findings that need a human"
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FixPlan {
+    pub fixes: Vec<AnnotationFix>,
+    /// Line and reason for each problem left untouched
+    pub skipped: Vec<(usize, String)>,
+}
+
+/// @acp:summary "Plan mechanical annotation fixes for a source file"
+///
+/// Fixes to the same line are chained: each one's `before` is the previous
+/// one's `after`, so applying them in order gives the combined result.
+pub fn plan_fixes(source: &str, language: Language, parser: &Parser) -> FixPlan {
+    let mut lines: Vec<String> = source.lines().map(str::to_string).collect();
+    let comments = comment_lines(source, language);
+    let comments: Vec<&str> = comments.iter().map(String::as_str).collect();
+    let annotations = parser.annotations_from_lines(&comments);
+    let mut plan = FixPlan::default();
+
+    for ann in &annotations {
+        let Some(text) = lines.get(ann.line - 1).cloned() else {
+            continue;
+        };
+        let mut fixed = text.clone();
+
+        if ann.name == "hack" {
+            if let Some(raw) = ann.value.as_deref().and_then(|v| {
+                v.split_whitespace()
+                    .find_map(|p| p.strip_prefix("expires="))
+            }) {
+                match normalize_date(raw) {
+                    Some(date) if date != raw => {
+                        fixed = fixed.replacen(
+                            &format!("expires={}", raw),
+                            &format!("expires={}", date),
+                            1,
+                        );
+                    }
+                    Some(_) => {}
+                    None => plan
+                        .skipped
+                        .push((ann.line, format!("Unrecognized hack expiry date '{}'", raw))),
+                }
+            }
+        }
+        if fixed != text {
+            plan.fixes.push(AnnotationFix {
+                kind: FixKind::HackDate,
+                line: ann.line,
+                before: text,
+                after: fixed.clone(),
+            });
+        }
+
+        if ann.auto_generated && DIRECTIVE_ANNOTATIONS.contains(&ann.name.as_str()) {
+            if let Some(directive) = &ann.directive {
+                if fixed.matches("@acp:").count() == 1 {
+                    let with_directive = append_directive(&fixed, directive);
+                    plan.fixes.push(AnnotationFix {
+                        kind: FixKind::Directive,
+                        line: ann.line,
+                        before: std::mem::replace(&mut fixed, with_directive.clone()),
+                        after: with_directive,
+                    });
+                } else {
+                    plan.skipped.push((
+                        ann.line,
+                        format!(
+                            "@acp:{} has no directive, but shares its line with another annotation",
+                            ann.name
+                        ),
+                    ));
+                }
+            }
+        }
+        lines[ann.line - 1] = fixed;
+    }
+
+    if !annotations.iter().any(|a| a.name == "lock") {
+        if let Some(reason) = annotations.iter().find(|a| a.name == "lock-reason") {
+            let text = &lines[reason.line - 1];
+            let prefix = &text[..text.find("@acp:").unwrap_or_default()];
+            // A copied block-comment opener would leave the new line unclosed
+            if prefix.contains("/*") || prefix.contains("\"\"\"") || prefix.contains("'''") {
+                plan.skipped.push((
+                    reason.line,
+                    "@acp:lock-reason without @acp:lock; add the lock by hand".to_string(),
+                ));
+            } else {
+                let directive = parser
+                    .default_directive("lock", Some("normal"))
+                    .unwrap_or_default();
+                plan.fixes.push(AnnotationFix {
+                    kind: FixKind::MissingLock,
+                    line: reason.line,
+                    before: String::new(),
+                    after: format!("{}@acp:lock normal - {}", prefix, directive),
+                });
+            }
+        }
+    }
+
+    plan.fixes.sort_by_key(|f| f.line);
+    plan.skipped.sort();
+    plan
+}
+
+/// @acp:summary "Apply planned fixes to source text"
+///
+/// Line endings are kept as they were; inserted lines reuse the ending of
+/// the line they are inserted above.
+pub fn apply_fixes(source: &str, fixes: &[AnnotationFix]) -> String {
+    let mut out = String::with_capacity(source.len());
+    for (i, raw) in source.split_inclusive('\n').enumerate() {
+        let line = i + 1;
+        let body = raw.trim_end_matches(['\n', '\r']);
+        let ending = &raw[body.len()..];
+        let mut text = body;
+        for fix in fixes.iter().filter(|f| f.line == line) {
+            if fix.kind == FixKind::MissingLock {
+                out.push_str(&fix.after);
+                out.push_str(if ending.is_empty() { "\n" } else { ending });
+            } else {
+                text = &fix.after;
+            }
+        }
+        out.push_str(text);
+        out.push_str(ending);
+    }
+    out
+}
+
+/// Parse an expiry date in any accepted layout and format it as YYYY-MM-DD
+fn normalize_date(raw: &str) -> Option<String> {
+    DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(raw, format).ok())
+        .map(|date| date.format("%Y-%m-%d").to_string())
+}
+
+/// Append ` - directive`, keeping a closing `*/` or triple quote at the end
+fn append_directive(line: &str, directive: &str) -> String {
+    let line = line.trim_end();
+    for closer in ["*/", "\"\"\"", "'''"] {
+        if let Some(body) = line.strip_suffix(closer) {
+            return format!("{} - {} {}", body.trim_end(), directive, closer);
+        }
+    }
+    format!("{} - {}", line, directive)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_and_apply_fixes() {
+        let source = "// @acp:lock-reason \"Billing totals\"\r\n\
+                      // @acp:hack expires=2025/3/1 \"Vendor bug\"\r\n\
+                      // @acp:todo - Already has a directive\r\n\
+                      /** @acp:critical */\r\n\
+                      // @acp:hack expires=next-week\r\n\
+                      export function total() {}\r\n";
+        let plan = plan_fixes(source, Language::Typescript, &Parser::new());
+
+        let kinds: Vec<_> = plan.fixes.iter().map(|f| (f.line, f.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (1, FixKind::MissingLock),
+                (2, FixKind::HackDate),
+                (2, FixKind::Directive),
+                (4, FixKind::Directive),
+                (5, FixKind::Directive),
+            ]
+        );
+        assert_eq!(plan.fixes[2].before, plan.fixes[1].after);
+        assert_eq!(plan.skipped.len(), 1);
+        assert_eq!(plan.skipped[0].0, 5);
+
+        let fixed = apply_fixes(source, &plan.fixes);
+        let lines: Vec<&str> = fixed.split("\r\n").collect();
+        assert_eq!(
+            lines[0],
+            "// @acp:lock normal - Safe to modify following project conventions"
+        );
+        assert_eq!(
+            lines[2],
+            "// @acp:hack expires=2025-03-01 \"Vendor bug\" - Temporary workaround - check expiry before modifying"
+        );
+        assert_eq!(lines[3], "// @acp:todo - Already has a directive");
+        assert_eq!(
+            lines[4],
+            "/** @acp:critical - Critical section - changes require extra review */"
+        );
+
+        // Fixed source has nothing left to fix
+        let again = plan_fixes(&fixed, Language::Typescript, &Parser::new());
+        assert!(again.fixes.is_empty());
+    }
+}
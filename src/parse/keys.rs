@@ -0,0 +1,201 @@
+//! @acp:module "Annotation Keys"
+//! @acp:summary "The recognized @acp: annotation names and typo suggestions"
+//! @acp:domain cli
+//! @acp:layer parsing
+//!
+//! Several components read `@acp:` annotations: the parser, the provenance
+//! and guardrail regexes, and the annotation generator. [`KNOWN_ANNOTATIONS`]
+//! is the union of everything they understand, so a name outside it is almost
+//! certainly a typo that would otherwise be dropped without a trace.
+
+use serde::Serialize;
+
+/// Defines [`AnnotationKey`] and [`KNOWN_ANNOTATIONS`] from one list, so a
+/// name the parser matches on cannot be missing from the known set
+macro_rules! annotation_keys {
+    ($($variant:ident => $name:literal,)*) => {
+        /// @acp:summary "A recognized @acp: annotation name"
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum AnnotationKey {
+            $($variant,)*
+        }
+
+        impl AnnotationKey {
+            /// @acp:summary "The key for an annotation name, if it is recognized"
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    $($name => Some(Self::$variant),)*
+                    _ => None,
+                }
+            }
+
+            /// @acp:summary "The annotation name without the `@acp:` prefix"
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)*
+                }
+            }
+        }
+
+        /// Every `@acp:` annotation name the CLI understands, sorted
+        pub const KNOWN_ANNOTATIONS: &[&str] = &[$($name,)*];
+    };
+}
+
+annotation_keys! {
+    AiAsk => "ai-ask",
+    AiAvoid => "ai-avoid",
+    AiCareful => "ai-careful",
+    AiContext => "ai-context",
+    AiHint => "ai-hint",
+    AiNoModify => "ai-no-modify",
+    AiReadonly => "ai-readonly",
+    AiReference => "ai-reference",
+    Async => "async",
+    AttemptStart => "attempt-start",
+    Author => "author",
+    Beta => "beta",
+    Cached => "cached",
+    Calls => "calls",
+    Checkpoint => "checkpoint",
+    Class => "class",
+    Critical => "critical",
+    Depends => "depends",
+    Deprecated => "deprecated",
+    Domain => "domain",
+    Example => "example",
+    Experimental => "experimental",
+    Fixme => "fixme",
+    Fn => "fn",
+    Forbids => "forbids",
+    Framework => "framework",
+    Function => "function",
+    Generator => "generator",
+    Hack => "hack",
+    Idempotent => "idempotent",
+    Imports => "imports",
+    Internal => "internal",
+    Layer => "layer",
+    License => "license",
+    Link => "link",
+    Lock => "lock",
+    LockReason => "lock-reason",
+    Memoized => "memoized",
+    Memory => "memory",
+    Method => "method",
+    Module => "module",
+    Note => "note",
+    Owner => "owner",
+    Param => "param",
+    Perf => "perf",
+    PublicApi => "public-api",
+    Pure => "pure",
+    Purpose => "purpose",
+    Raises => "raises",
+    Ref => "ref",
+    RefFetch => "ref-fetch",
+    RefSection => "ref-section",
+    RefVersion => "ref-version",
+    Requires => "requires",
+    Return => "return",
+    Returns => "returns",
+    ReviewRequired => "review-required",
+    See => "see",
+    SideEffects => "side-effects",
+    Since => "since",
+    Source => "source",
+    SourceConfidence => "source-confidence",
+    SourceId => "source-id",
+    SourceReviewed => "source-reviewed",
+    Stability => "stability",
+    Style => "style",
+    StyleExtends => "style-extends",
+    StyleRules => "style-rules",
+    Summary => "summary",
+    Symbol => "symbol",
+    TechDebt => "tech-debt",
+    Template => "template",
+    TestRequired => "test-required",
+    Throttled => "throttled",
+    Throws => "throws",
+    Todo => "todo",
+    Transactional => "transactional",
+    Version => "version",
+    Warning => "warning",
+}
+
+/// Suggestions further than this many edits away are not offered
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// @acp:summary "Whether an annotation name is recognized"
+pub fn is_known_annotation(name: &str) -> bool {
+    AnnotationKey::from_name(name).is_some()
+}
+
+/// @acp:summary "The closest known annotation name, if one is close enough"
+pub fn suggest_annotation(name: &str) -> Option<&'static str> {
+    let name = name.to_lowercase();
+    KNOWN_ANNOTATIONS
+        .iter()
+        .map(|known| (strsim::damerau_levenshtein(&name, known), *known))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min()
+        .map(|(_, known)| known)
+}
+
+/// @acp:summary "An unrecognized @acp: annotation found while parsing"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UnknownAnnotation {
+    /// Annotation name as written, without the `@acp:` prefix
+    pub name: String,
+    /// Source line number (1-indexed)
+    pub line: usize,
+    /// Closest known name, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<&'static str>,
+}
+
+impl UnknownAnnotation {
+    /// @acp:summary "Records `name` if it is not a known annotation"
+    pub fn check(name: &str, line: usize) -> Option<Self> {
+        (!is_known_annotation(name)).then(|| Self {
+            name: name.to_string(),
+            line,
+            suggestion: suggest_annotation(name),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_annotations_sorted() {
+        assert!(KNOWN_ANNOTATIONS.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_annotation_keys_round_trip() {
+        for name in KNOWN_ANNOTATIONS {
+            assert_eq!(AnnotationKey::from_name(name).unwrap().as_str(), *name);
+        }
+        assert_eq!(
+            AnnotationKey::from_name("ai-no-modify"),
+            Some(AnnotationKey::AiNoModify)
+        );
+        assert_eq!(AnnotationKey::from_name("sumary"), None);
+    }
+
+    #[test]
+    fn test_unknown_annotation_suggestions() {
+        assert!(UnknownAnnotation::check("summary", 1).is_none());
+
+        let typo = UnknownAnnotation::check("sumary", 3).unwrap();
+        assert_eq!(typo.line, 3);
+        assert_eq!(typo.suggestion, Some("summary"));
+        assert_eq!(suggest_annotation("lokc"), Some("lock"));
+        assert_eq!(suggest_annotation("Domian"), Some("domain"));
+        assert_eq!(suggest_annotation("frobnicate"), None);
+    }
+}
@@ -6,8 +6,11 @@
 //! Parses source files to extract symbols, calls, and documentation.
 //! Supports RFC-001 self-documenting annotations with directive extraction.
 //! Supports RFC-003 annotation provenance tracking.
+//! Annotation names listed in the config's `parse.aliases` are read as their
+//! canonical names before anything else looks at them.
 //! Currently uses regex-based parsing with tree-sitter support planned.
 
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::LazyLock;
 
@@ -15,18 +18,31 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 use crate::cache::{
-    BehavioralAnnotations, DocumentationAnnotations, FileEntry, InlineAnnotation,
-    LifecycleAnnotations, MemoizedValue, PerformanceAnnotations, SymbolEntry, SymbolType, TypeInfo,
-    TypeParamInfo, TypeReturnInfo, TypeSource, TypeTypeParam, Visibility,
+    BehavioralAnnotations, BridgeSource, CallEdge, DocumentationAnnotations, FileEntry,
+    InlineAnnotation, Language, LifecycleAnnotations, MemoizedValue, PerformanceAnnotations,
+    SourceFormat, SymbolConstraint, SymbolEntry, SymbolType, ThrowsEntry, TypeInfo, TypeParamInfo,
+    TypeReturnInfo, TypeSource, TypeTypeParam, Visibility,
 };
 use crate::error::{AcpError, Result};
 use crate::index::detect_language;
 
+mod comments;
+mod fix;
+pub mod keys;
+
+pub use comments::comment_lines;
+pub use fix::{apply_fixes, plan_fixes, AnnotationFix, FixKind, FixPlan};
+pub use keys::{
+    is_known_annotation, suggest_annotation, AnnotationKey, UnknownAnnotation, KNOWN_ANNOTATIONS,
+};
+
 /// Regex pattern for parsing @acp: annotations with directive support (RFC-001)
 /// Matches: @acp:name [value] [- directive]
 /// Groups: 1=name, 2=value (before dash), 3=directive (after dash)
+/// Values may contain hyphens (`expires=2025-03-01`, `ticket=JIRA-123`) but
+/// not start with one; the directive starts at the first ` - `.
 static ANNOTATION_PATTERN: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"@acp:([\w-]+)(?:\s+([^-\n]+?))?(?:\s+-\s+(.+))?$").unwrap());
+    LazyLock::new(|| Regex::new(r"@acp:([\w-]+)(?:\s+([^-\s][^\n]*?))?(?:\s+-\s+(.+))?$").unwrap());
 
 /// Regex for detecting comment continuation lines (for multiline directives)
 static CONTINUATION_PATTERN: LazyLock<Regex> =
@@ -137,6 +153,7 @@ pub struct ParseResult {
     pub file: FileEntry,
     pub symbols: Vec<SymbolEntry>,
     pub calls: Vec<(String, Vec<String>)>, // (caller, callees)
+    pub call_sites: Vec<CallEdge>,         // from AST call extraction
     pub lock_level: Option<String>,        // from @acp:lock
     pub lock_directive: Option<String>,    // RFC-001: directive text for lock
     pub ai_hints: Vec<String>,             // from @acp:ai-careful, @acp:ai-readonly, etc.
@@ -144,6 +161,30 @@ pub struct ParseResult {
     pub inline_annotations: Vec<InlineAnnotation>, // RFC-001: inline annotations (todo, fixme, critical, perf)
     pub purpose: Option<String>,                   // RFC-001: file purpose from @acp:purpose
     pub owner: Option<String>,                     // RFC-001: file owner from @acp:owner
+    pub unknown_annotations: Vec<UnknownAnnotation>, // unrecognized @acp: names (likely typos)
+    pub domain_descriptions: Vec<(String, String)>, // (domain, description) from @acp:domain directives
+    pub annotation_errors: Vec<AnnotationProblem>,  // malformed annotations, parsing continued
+}
+
+/// @acp:summary "A malformed annotation that was skipped or corrected"
+///
+/// Kept as plain data so [`ParseResult`] stays `Clone`; convert into
+/// [`AcpError::AnnotationParse`] where it is reported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnotationProblem {
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+}
+
+impl From<AnnotationProblem> for AcpError {
+    fn from(problem: AnnotationProblem) -> Self {
+        AcpError::AnnotationParse {
+            file: problem.file,
+            line: problem.line,
+            message: problem.message,
+        }
+    }
 }
 
 /// @acp:summary "Parsed hack annotation"
@@ -157,19 +198,34 @@ pub struct HackAnnotation {
 
 /// @acp:summary "Parser for source files"
 pub struct Parser {
-    // tree-sitter parsers would be initialized here
-    // For now, this is a stub implementation
+    /// Alternative annotation names mapped to canonical ones (`parse.aliases`)
+    aliases: HashMap<String, String>,
 }
 
 impl Parser {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            aliases: HashMap::new(),
+        }
+    }
+
+    /// Read aliased annotation names as their canonical names
+    ///
+    /// Keys and values are given without the `@acp:` prefix.
+    pub fn with_aliases(mut self, aliases: HashMap<String, String>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    /// Parser configured from the config's `parse` settings
+    pub fn from_config(config: &crate::config::Config) -> Self {
+        Self::new().with_aliases(config.parse.aliases.clone())
     }
 
     /// @acp:summary "Parse a source file and extract metadata"
     pub fn parse<P: AsRef<Path>>(&self, path: P) -> Result<ParseResult> {
         let path = path.as_ref();
-        let content = std::fs::read_to_string(path)?;
+        let content = read_source(path)?;
         let file_path = path.to_string_lossy().to_string();
 
         let language = detect_language(&file_path).ok_or_else(|| {
@@ -186,13 +242,25 @@ impl Parser {
             .map(|s| s.to_string_lossy().to_string())
             .unwrap_or_default();
 
-        // Parse @acp: annotations from source
-        let annotations = self.parse_annotations(&content);
+        // Parse @acp: annotations from source comments
+        let comments = comment_lines(&content, language);
+        let comment_refs: Vec<&str> = comments.iter().map(String::as_str).collect();
+        let annotations = self.annotations_from_lines(&comment_refs);
+
+        // True when 1-indexed lines `from..=to` form one unbroken comment block
+        let same_block = |from: usize, to: usize| {
+            (from..=to).all(|line| {
+                comments
+                    .get(line.wrapping_sub(1))
+                    .is_some_and(|text| !text.trim().is_empty())
+            })
+        };
 
         // Extract file-level metadata from annotations
         let mut module_name = None;
         let mut file_summary = None;
         let mut domains = vec![];
+        let mut domain_descriptions = vec![];
         let mut layer = None;
         let mut symbols = vec![];
         let mut exports = vec![];
@@ -205,6 +273,8 @@ impl Parser {
         let mut inline_annotations = vec![];
         let mut purpose = None;
         let mut owner = None;
+        let mut unknown_annotations = vec![];
+        let mut annotation_errors = vec![];
 
         // RFC-0009: File-level extended annotation accumulators
         let mut file_version: Option<String> = None;
@@ -217,13 +287,21 @@ impl Parser {
         let mut current_symbol: Option<SymbolBuilder> = None;
 
         for ann in &annotations {
-            match ann.name.as_str() {
-                "module" => {
+            if let Some(message) = annotation_problem(ann) {
+                annotation_errors.push(AnnotationProblem {
+                    file: file_path.clone(),
+                    line: ann.line,
+                    message,
+                });
+            }
+            let key = AnnotationKey::from_name(&ann.name);
+            match key {
+                Some(AnnotationKey::Module) => {
                     if let Some(val) = &ann.value {
                         module_name = Some(val.trim_matches('"').to_string());
                     }
                 }
-                "summary" => {
+                Some(AnnotationKey::Summary) => {
                     if let Some(ref mut builder) = current_symbol {
                         if let Some(val) = &ann.value {
                             builder.summary = Some(val.trim_matches('"').to_string());
@@ -233,25 +311,46 @@ impl Parser {
                         file_summary = Some(val.trim_matches('"').to_string());
                     }
                 }
-                "domain" => {
+                Some(AnnotationKey::Domain) => {
                     if let Some(val) = &ann.value {
-                        domains.push(val.trim_matches('"').to_string());
+                        let name = val.trim_matches('"').to_string();
+                        if let Some(description) =
+                            ann.directive.as_ref().filter(|_| !ann.auto_generated)
+                        {
+                            domain_descriptions.push((name.clone(), description.clone()));
+                        }
+                        domains.push(name);
                     }
                 }
-                "layer" => {
+                Some(AnnotationKey::Layer) => {
                     if let Some(val) = &ann.value {
                         layer = Some(val.trim_matches('"').to_string());
                     }
                 }
-                "lock" => {
-                    if let Some(val) = &ann.value {
-                        lock_level = Some(val.trim_matches('"').to_string());
+                // In the same comment block as an @acp:fn/@acp:symbol the lock
+                // covers only that symbol; anywhere else it locks the file
+                Some(AnnotationKey::Lock) => {
+                    if let Some(builder) = current_symbol
+                        .as_mut()
+                        .filter(|b| same_block(b.line, ann.line))
+                    {
+                        if let Some(val) = &ann.value {
+                            builder.constraints = Some(SymbolConstraint {
+                                level: val.trim_matches('"').to_lowercase(),
+                                directive: ann.directive.clone().unwrap_or_default(),
+                                auto_generated: ann.directive.is_none(),
+                            });
+                        }
+                    } else {
+                        if let Some(val) = &ann.value {
+                            lock_level = Some(val.trim_matches('"').to_string());
+                        }
+                        // RFC-001: Capture directive for lock annotation
+                        lock_directive = ann.directive.clone();
                     }
-                    // RFC-001: Capture directive for lock annotation
-                    lock_directive = ann.directive.clone();
                 }
                 // RFC-001: File purpose annotation
-                "purpose" => {
+                Some(AnnotationKey::Purpose) => {
                     if let Some(val) = &ann.value {
                         purpose = Some(val.trim_matches('"').to_string());
                     } else if let Some(dir) = &ann.directive {
@@ -259,12 +358,17 @@ impl Parser {
                     }
                 }
                 // RFC-001: File owner annotation
-                "owner" => {
+                Some(AnnotationKey::Owner) => {
                     if let Some(val) = &ann.value {
                         owner = Some(val.trim_matches('"').to_string());
                     }
                 }
-                "ai-careful" | "ai-readonly" | "ai-avoid" | "ai-no-modify" => {
+                Some(
+                    AnnotationKey::AiCareful
+                    | AnnotationKey::AiReadonly
+                    | AnnotationKey::AiAvoid
+                    | AnnotationKey::AiNoModify,
+                ) => {
                     let hint = if let Some(val) = &ann.value {
                         format!("{}: {}", ann.name, val.trim_matches('"'))
                     } else {
@@ -272,7 +376,7 @@ impl Parser {
                     };
                     ai_hints.push(hint);
                 }
-                "hack" => {
+                Some(AnnotationKey::Hack) => {
                     // Parse hack annotation: @acp:hack expires=2025-03-01 ticket=JIRA-123 "reason"
                     let mut expires = None;
                     let mut ticket = None;
@@ -316,27 +420,25 @@ impl Parser {
                     });
                 }
                 // RFC-001: Inline annotation types
-                "todo" | "fixme" | "critical" => {
+                Some(AnnotationKey::Todo | AnnotationKey::Fixme | AnnotationKey::Critical) => {
                     inline_annotations.push(InlineAnnotation {
                         line: ann.line,
                         annotation_type: ann.name.clone(),
                         value: ann.value.clone(),
-                        directive: ann.directive.clone().unwrap_or_else(|| {
-                            match ann.name.as_str() {
-                                "todo" => "Pending work item".to_string(),
-                                "fixme" => "Known issue requiring fix".to_string(),
-                                "critical" => {
-                                    "Critical section - extra review required".to_string()
-                                }
-                                _ => "".to_string(),
+                        directive: ann.directive.clone().unwrap_or_else(|| match key {
+                            Some(AnnotationKey::Todo) => "Pending work item".to_string(),
+                            Some(AnnotationKey::Fixme) => "Known issue requiring fix".to_string(),
+                            Some(AnnotationKey::Critical) => {
+                                "Critical section - extra review required".to_string()
                             }
+                            _ => "".to_string(),
                         }),
                         expires: None,
                         ticket: None,
                         auto_generated: ann.auto_generated,
                     });
                     // RFC-0009: Also add to symbol documentation.todos
-                    if ann.name == "todo" {
+                    if key == Some(AnnotationKey::Todo) {
                         if let Some(ref mut builder) = current_symbol {
                             let todo_text = ann
                                 .directive
@@ -350,7 +452,7 @@ impl Parser {
                     }
                 }
                 // RFC-0009: Performance annotation (extends RFC-001 perf)
-                "perf" => {
+                Some(AnnotationKey::Perf) => {
                     inline_annotations.push(InlineAnnotation {
                         line: ann.line,
                         annotation_type: ann.name.clone(),
@@ -371,7 +473,7 @@ impl Parser {
                         }
                     }
                 }
-                "symbol" => {
+                Some(AnnotationKey::Symbol) => {
                     // Save previous symbol if exists
                     if let Some(builder) = current_symbol.take() {
                         let sym = builder.build(&file_path);
@@ -388,7 +490,12 @@ impl Parser {
                     }
                 }
                 // RFC-001: Symbol-level annotations
-                "fn" | "function" | "class" | "method" => {
+                Some(
+                    AnnotationKey::Fn
+                    | AnnotationKey::Function
+                    | AnnotationKey::Class
+                    | AnnotationKey::Method,
+                ) => {
                     // Save previous symbol if exists
                     if let Some(builder) = current_symbol.take() {
                         let sym = builder.build(&file_path);
@@ -402,17 +509,19 @@ impl Parser {
                             ann.line,
                             &file_path,
                         );
-                        builder.symbol_type = match ann.name.as_str() {
-                            "fn" | "function" => SymbolType::Function,
-                            "class" => SymbolType::Class,
-                            "method" => SymbolType::Method,
+                        builder.symbol_type = match key {
+                            Some(AnnotationKey::Fn | AnnotationKey::Function) => {
+                                SymbolType::Function
+                            }
+                            Some(AnnotationKey::Class) => SymbolType::Class,
+                            Some(AnnotationKey::Method) => SymbolType::Method,
                             _ => SymbolType::Function,
                         };
                         builder.purpose = ann.directive.clone();
                         current_symbol = Some(builder);
                     }
                 }
-                "calls" => {
+                Some(AnnotationKey::Calls) => {
                     if let Some(ref mut builder) = current_symbol {
                         if let Some(val) = &ann.value {
                             let callees: Vec<String> = val
@@ -423,11 +532,12 @@ impl Parser {
                         }
                     }
                 }
-                "imports" | "depends" => {
+                Some(AnnotationKey::Imports | AnnotationKey::Depends) => {
                     if let Some(val) = &ann.value {
                         let import_list: Vec<String> = val
                             .split(',')
-                            .map(|s| s.trim().trim_matches('"').to_string())
+                            .map(|s| s.trim().trim_matches('"').trim().to_string())
+                            .filter(|s| !s.is_empty())
                             .collect();
                         imports.extend(import_list);
                     }
@@ -436,7 +546,7 @@ impl Parser {
                 // ================================================================
                 // RFC-0008: Type Annotations
                 // ================================================================
-                "param" => {
+                Some(AnnotationKey::Param) => {
                     if let Some(ref mut builder) = current_symbol {
                         // Parse type, name, optional marker, and default from value
                         // Value format: "{Type} [name]=default" or just "name"
@@ -494,7 +604,7 @@ impl Parser {
                         }
                     }
                 }
-                "returns" | "return" => {
+                Some(AnnotationKey::Returns | AnnotationKey::Return) => {
                     if let Some(ref mut builder) = current_symbol {
                         // Parse type from value: "{Type}" or empty
                         let type_expr = ann.value.as_ref().and_then(|val| {
@@ -514,7 +624,18 @@ impl Parser {
                         });
                     }
                 }
-                "template" => {
+                Some(AnnotationKey::Throws | AnnotationKey::Raises) => {
+                    if let Some(ref mut builder) = current_symbol {
+                        // Value format: "{Exception} [description]" or "Exception [description]"
+                        if let Some(throws) = ann.value.as_deref().and_then(parse_throws_value) {
+                            builder.throws.push(ThrowsEntry {
+                                directive: ann.directive.clone(),
+                                ..throws
+                            });
+                        }
+                    }
+                }
+                Some(AnnotationKey::Template) => {
                     if let Some(ref mut builder) = current_symbol {
                         // Parse @acp:template T [extends Constraint] from value
                         if let Some(val) = &ann.value {
@@ -546,17 +667,17 @@ impl Parser {
                 // ================================================================
                 // RFC-0009: Behavioral Annotations
                 // ================================================================
-                "pure" => {
+                Some(AnnotationKey::Pure) => {
                     if let Some(ref mut builder) = current_symbol {
                         builder.behavioral.pure = true;
                     }
                 }
-                "idempotent" => {
+                Some(AnnotationKey::Idempotent) => {
                     if let Some(ref mut builder) = current_symbol {
                         builder.behavioral.idempotent = true;
                     }
                 }
-                "memoized" => {
+                Some(AnnotationKey::Memoized) => {
                     if let Some(ref mut builder) = current_symbol {
                         if let Some(val) = &ann.value {
                             builder.behavioral.memoized =
@@ -566,29 +687,29 @@ impl Parser {
                         }
                     }
                 }
-                "async" => {
+                Some(AnnotationKey::Async) => {
                     if let Some(ref mut builder) = current_symbol {
                         builder.behavioral.r#async = true;
                     }
                 }
-                "generator" => {
+                Some(AnnotationKey::Generator) => {
                     if let Some(ref mut builder) = current_symbol {
                         builder.behavioral.generator = true;
                     }
                 }
-                "throttled" => {
+                Some(AnnotationKey::Throttled) => {
                     if let Some(ref mut builder) = current_symbol {
                         if let Some(val) = &ann.value {
                             builder.behavioral.throttled = Some(val.trim_matches('"').to_string());
                         }
                     }
                 }
-                "transactional" => {
+                Some(AnnotationKey::Transactional) => {
                     if let Some(ref mut builder) = current_symbol {
                         builder.behavioral.transactional = true;
                     }
                 }
-                "side-effects" => {
+                Some(AnnotationKey::SideEffects) => {
                     if let Some(ref mut builder) = current_symbol {
                         if let Some(val) = &ann.value {
                             let effects: Vec<String> = val
@@ -603,47 +724,50 @@ impl Parser {
                 // ================================================================
                 // RFC-0009: Lifecycle Annotations (file and symbol level)
                 // ================================================================
-                "deprecated" => {
-                    let message = ann
-                        .directive
-                        .clone()
-                        .or_else(|| ann.value.clone().map(|v| v.trim_matches('"').to_string()))
-                        .unwrap_or_else(|| "Deprecated".to_string());
+                Some(AnnotationKey::Deprecated) => {
+                    let value = ann.value.clone().map(|v| v.trim_matches('"').to_string());
+                    // An explicit value beats the generic auto-generated directive
+                    let message = if ann.auto_generated {
+                        value.or_else(|| ann.directive.clone())
+                    } else {
+                        ann.directive.clone().or(value)
+                    }
+                    .unwrap_or_else(|| "Deprecated".to_string());
                     if let Some(ref mut builder) = current_symbol {
                         builder.lifecycle.deprecated = Some(message);
                     } else {
                         file_lifecycle.deprecated = Some(message);
                     }
                 }
-                "experimental" => {
+                Some(AnnotationKey::Experimental) => {
                     if let Some(ref mut builder) = current_symbol {
                         builder.lifecycle.experimental = true;
                     } else {
                         file_lifecycle.experimental = true;
                     }
                 }
-                "beta" => {
+                Some(AnnotationKey::Beta) => {
                     if let Some(ref mut builder) = current_symbol {
                         builder.lifecycle.beta = true;
                     } else {
                         file_lifecycle.beta = true;
                     }
                 }
-                "internal" => {
+                Some(AnnotationKey::Internal) => {
                     if let Some(ref mut builder) = current_symbol {
                         builder.lifecycle.internal = true;
                     } else {
                         file_lifecycle.internal = true;
                     }
                 }
-                "public-api" => {
+                Some(AnnotationKey::PublicApi) => {
                     if let Some(ref mut builder) = current_symbol {
                         builder.lifecycle.public_api = true;
                     } else {
                         file_lifecycle.public_api = true;
                     }
                 }
-                "since" => {
+                Some(AnnotationKey::Since) => {
                     if let Some(val) = &ann.value {
                         let version = val.trim_matches('"').to_string();
                         if let Some(ref mut builder) = current_symbol {
@@ -657,7 +781,7 @@ impl Parser {
                 // ================================================================
                 // RFC-0009: Documentation Annotations
                 // ================================================================
-                "example" => {
+                Some(AnnotationKey::Example) => {
                     if let Some(ref mut builder) = current_symbol {
                         let example_text = ann
                             .directive
@@ -669,7 +793,7 @@ impl Parser {
                         }
                     }
                 }
-                "see" => {
+                Some(AnnotationKey::See) => {
                     if let Some(ref mut builder) = current_symbol {
                         if let Some(val) = &ann.value {
                             builder
@@ -679,7 +803,7 @@ impl Parser {
                         }
                     }
                 }
-                "link" => {
+                Some(AnnotationKey::Link) => {
                     if let Some(ref mut builder) = current_symbol {
                         if let Some(val) = &ann.value {
                             builder
@@ -689,7 +813,7 @@ impl Parser {
                         }
                     }
                 }
-                "note" => {
+                Some(AnnotationKey::Note) => {
                     if let Some(ref mut builder) = current_symbol {
                         let note_text = ann
                             .directive
@@ -701,7 +825,7 @@ impl Parser {
                         }
                     }
                 }
-                "warning" => {
+                Some(AnnotationKey::Warning) => {
                     if let Some(ref mut builder) = current_symbol {
                         let warning_text = ann
                             .directive
@@ -717,14 +841,14 @@ impl Parser {
                 // ================================================================
                 // RFC-0009: Performance Annotations (memory, cached)
                 // ================================================================
-                "memory" => {
+                Some(AnnotationKey::Memory) => {
                     if let Some(ref mut builder) = current_symbol {
                         if let Some(val) = &ann.value {
                             builder.performance.memory = Some(val.trim_matches('"').to_string());
                         }
                     }
                 }
-                "cached" => {
+                Some(AnnotationKey::Cached) => {
                     if let Some(ref mut builder) = current_symbol {
                         if let Some(val) = &ann.value {
                             builder.performance.cached = Some(val.trim_matches('"').to_string());
@@ -737,23 +861,25 @@ impl Parser {
                 // ================================================================
                 // RFC-0009: File-Level Annotations
                 // ================================================================
-                "version" => {
+                Some(AnnotationKey::Version) => {
                     if let Some(val) = &ann.value {
                         file_version = Some(val.trim_matches('"').to_string());
                     }
                 }
-                "license" => {
+                Some(AnnotationKey::License) => {
                     if let Some(val) = &ann.value {
                         file_license = Some(val.trim_matches('"').to_string());
                     }
                 }
-                "author" => {
+                Some(AnnotationKey::Author) => {
                     if let Some(val) = &ann.value {
                         file_author = Some(val.trim_matches('"').to_string());
                     }
                 }
 
-                _ => {}
+                // Handled elsewhere (provenance, guardrails)
+                Some(_) => {}
+                None => unknown_annotations.extend(UnknownAnnotation::check(&ann.name, ann.line)),
             }
         }
 
@@ -774,6 +900,8 @@ impl Parser {
             }
         }
 
+        normalize_imports(&mut imports);
+
         let file = FileEntry {
             path: file_path,
             lines,
@@ -787,6 +915,7 @@ impl Parser {
             owner: owner.clone(),
             inline: inline_annotations.clone(),
             domains,
+            tags: Vec::new(), // Populated during indexing
             layer,
             stability: None,
             ai_hints: ai_hints.clone(),
@@ -806,12 +935,14 @@ impl Parser {
             // RFC-0002: Populated during indexing with validation
             refs: Vec::new(),
             style: None,
+            extra: serde_json::Map::new(),
         };
 
         Ok(ParseResult {
             file,
             symbols,
             calls,
+            call_sites: vec![],
             lock_level,
             lock_directive,
             ai_hints,
@@ -819,14 +950,32 @@ impl Parser {
             inline_annotations,
             purpose,
             owner,
+            unknown_annotations,
+            domain_descriptions,
+            annotation_errors,
         })
     }
 
-    /// @acp:summary "Parse @acp: annotations from source comments (RFC-001)"
+    /// @acp:summary "Parse @acp: annotations from comment text (RFC-001)"
     /// Extracts annotations with directive suffix support and multiline continuation.
+    /// Every line is scanned, so `content` should already be comment text (such
+    /// as an extracted doc comment); use `parse_source_annotations` for whole files.
     pub fn parse_annotations(&self, content: &str) -> Vec<Annotation> {
-        let mut annotations = Vec::new();
         let lines: Vec<&str> = content.lines().collect();
+        self.annotations_from_lines(&lines)
+    }
+
+    /// @acp:summary "Parse @acp: annotations from the comments of a source file"
+    /// Only comments and docstrings are scanned; block comment framing is
+    /// stripped first so annotations inside `/** */` or `"""` blocks are found.
+    pub fn parse_source_annotations(&self, content: &str, language: Language) -> Vec<Annotation> {
+        let lines = comment_lines(content, language);
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        self.annotations_from_lines(&lines)
+    }
+
+    fn annotations_from_lines(&self, lines: &[&str]) -> Vec<Annotation> {
+        let mut annotations = Vec::new();
         let mut i = 0;
 
         while i < lines.len() {
@@ -834,13 +983,18 @@ impl Parser {
             let line_1indexed = i + 1;
 
             for cap in ANNOTATION_PATTERN.captures_iter(line) {
-                let name = cap.get(1).unwrap().as_str().to_string();
+                let name = cap.get(1).unwrap().as_str();
+                let name = self
+                    .aliases
+                    .get(name)
+                    .map_or(name, String::as_str)
+                    .to_string();
                 let value = cap.get(2).map(|m| m.as_str().trim().to_string());
                 let mut directive = cap.get(3).map(|m| m.as_str().trim().to_string());
 
                 // Check for multiline directive continuation
                 let mut j = i + 1;
-                while j < lines.len() {
+                while j < lines.len() && !lines[j].contains("@acp:") {
                     if let Some(cont_cap) = CONTINUATION_PATTERN.captures(lines[j]) {
                         let continuation = cont_cap.get(1).unwrap().as_str().trim();
                         if let Some(ref mut dir) = directive {
@@ -990,12 +1144,15 @@ impl Parser {
     ///
     /// Returns annotations paired with their provenance metadata if present.
     /// Provenance is detected from @acp:source* annotations following the main annotation.
+    /// Like `parse_source_annotations`, only comments and docstrings are scanned.
     pub fn parse_annotations_with_provenance(
         &self,
         content: &str,
+        language: Language,
     ) -> Vec<AnnotationWithProvenance> {
-        let annotations = self.parse_annotations(content);
-        let lines: Vec<&str> = content.lines().collect();
+        let lines = comment_lines(content, language);
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let annotations = self.annotations_from_lines(&lines);
 
         annotations
             .into_iter()
@@ -1040,10 +1197,114 @@ pub struct Annotation {
     pub line: usize,
 }
 
+/// @acp:summary "Sort and deduplicate import specifiers"
+///
+/// Entries are trimmed and compared case-sensitively; empty ones are dropped.
+pub fn normalize_imports(imports: &mut Vec<String>) {
+    for import in imports.iter_mut() {
+        *import = import.trim().to_string();
+    }
+    imports.retain(|import| !import.is_empty());
+    imports.sort();
+    imports.dedup();
+}
+
+/// @acp:summary "Read a source file with its BOM stripped and line endings normalized"
+///
+/// See [`normalize_source`].
+pub fn read_source(path: &Path) -> Result<String> {
+    Ok(normalize_source(&std::fs::read_to_string(path)?))
+}
+
+/// @acp:summary "Strip a leading UTF-8 BOM and convert line endings to `\n`"
+///
+/// `\r\n` and lone `\r` each become a single `\n`, so line `N` of the result is
+/// line `N` of the file as an editor shows it and recorded line numbers stay
+/// correct even for mixed line endings. Patterns anchored with `$` also no
+/// longer see a trailing `\r`.
+pub fn normalize_source(content: &str) -> String {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    if !content.contains('\r') {
+        return content.to_string();
+    }
+    content.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// Why an annotation's value can't be used as written, if it can't
+///
+/// Parsing never fails on these: the annotation is kept (or the value
+/// clamped) as before, and the problem is reported alongside.
+fn annotation_problem(ann: &Annotation) -> Option<String> {
+    let value = ann.value.as_deref()?;
+    // A ` - ` inside quotes splits the text between value and directive
+    let quotes = value.matches('"').count()
+        + ann
+            .directive
+            .as_deref()
+            .map_or(0, |d| d.matches('"').count());
+    if quotes % 2 == 1 {
+        return Some(format!("Unterminated quote in @acp:{} value", ann.name));
+    }
+    match AnnotationKey::from_name(&ann.name)? {
+        AnnotationKey::Hack => value
+            .split_whitespace()
+            .find_map(|part| part.strip_prefix("expires="))
+            .filter(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err())
+            .map(|date| {
+                format!(
+                    "Invalid @acp:hack expiry '{}' (expected YYYY-MM-DD); the hack never expires",
+                    date
+                )
+            }),
+        AnnotationKey::SourceConfidence => match value.parse::<f64>() {
+            Err(_) => Some(format!(
+                "Invalid @acp:source-confidence '{}' (expected a number from 0.0 to 1.0)",
+                value
+            )),
+            Ok(conf) if !(0.0..=1.0).contains(&conf) => Some(format!(
+                "@acp:source-confidence {} is outside 0.0-1.0 and was clamped",
+                value
+            )),
+            Ok(_) => None,
+        },
+        _ => None,
+    }
+}
+
 fn is_false(b: &bool) -> bool {
     !*b
 }
 
+/// Parse an @acp:throws/@acp:raises value into the exception and description
+///
+/// Accepts `{ValidationError} when input is invalid` as well as the bare
+/// `ValidationError when input is invalid`.
+fn parse_throws_value(value: &str) -> Option<ThrowsEntry> {
+    let value = value.trim();
+    let (exception, rest) = match value.strip_prefix('{') {
+        Some(inner) => {
+            let close = inner.find('}')?;
+            (inner[..close].trim(), &inner[close + 1..])
+        }
+        None => {
+            let exception = value.split_whitespace().next()?;
+            (exception, &value[exception.len()..])
+        }
+    };
+    if exception.is_empty() {
+        return None;
+    }
+
+    let description = rest.trim().trim_matches('"').trim();
+    Some(ThrowsEntry {
+        exception: exception.to_string(),
+        description: (!description.is_empty()).then(|| description.to_string()),
+        directive: None,
+        source: BridgeSource::Explicit,
+        source_format: Some(SourceFormat::Acp),
+    })
+}
+
 /// Helper to build SymbolEntry from annotations
 struct SymbolBuilder {
     name: String,
@@ -1053,6 +1314,7 @@ struct SymbolBuilder {
     purpose: Option<String>,
     calls: Vec<String>,
     symbol_type: SymbolType,
+    constraints: Option<SymbolConstraint>,
     // RFC-0009: Extended annotation accumulators
     behavioral: BehavioralAnnotations,
     lifecycle: LifecycleAnnotations,
@@ -1060,6 +1322,7 @@ struct SymbolBuilder {
     performance: PerformanceAnnotations,
     // RFC-0008: Type annotation accumulator
     type_info: TypeInfo,
+    throws: Vec<ThrowsEntry>,
 }
 
 impl SymbolBuilder {
@@ -1073,6 +1336,7 @@ impl SymbolBuilder {
             purpose: None,
             calls: vec![],
             symbol_type: SymbolType::Function,
+            constraints: None,
             // RFC-0009: Initialize with defaults
             behavioral: BehavioralAnnotations::default(),
             lifecycle: LifecycleAnnotations::default(),
@@ -1080,6 +1344,7 @@ impl SymbolBuilder {
             performance: PerformanceAnnotations::default(),
             // RFC-0008: Initialize with defaults
             type_info: TypeInfo::default(),
+            throws: vec![],
         }
     }
 
@@ -1099,7 +1364,7 @@ impl SymbolBuilder {
             calls: self.calls,
             called_by: vec![], // Populated later by indexer
             git: None,
-            constraints: None,
+            constraints: self.constraints,
             annotations: std::collections::HashMap::new(), // RFC-0003
             // RFC-0009: Extended annotation types (sparse serialization)
             behavioral: if self.behavioral.is_empty() {
@@ -1128,6 +1393,9 @@ impl SymbolBuilder {
             } else {
                 Some(self.type_info)
             },
+            throws: self.throws,
+            recursive: false,
+            extra: serde_json::Map::new(),
         }
     }
 }
@@ -1149,6 +1417,128 @@ mod type_annotation_tests {
         parser.parse(file.path()).unwrap()
     }
 
+    #[test]
+    fn test_aliased_key_populates_canonical_field() {
+        let mut file = NamedTempFile::with_suffix(".ts").unwrap();
+        write!(
+            file,
+            "// @acp:desc \"Session handling\"\nexport const x = 1;\n"
+        )
+        .unwrap();
+        let parser = Parser::new()
+            .with_aliases(HashMap::from([("desc".to_string(), "summary".to_string())]));
+        let result = parser.parse(file.path()).unwrap();
+
+        assert_eq!(result.file.summary.as_deref(), Some("Session handling"));
+        assert!(result.unknown_annotations.is_empty());
+
+        let annotations = parser.parse_annotations_with_provenance(
+            "// @acp:desc \"Session handling\"\n",
+            Language::Typescript,
+        );
+        assert_eq!(annotations[0].annotation.name, "summary");
+
+        // Without the alias the key is still unknown
+        let result = Parser::new().parse(file.path()).unwrap();
+        assert!(result.file.summary.is_none());
+        assert!(result.unknown_annotations.iter().any(|u| u.name == "desc"));
+    }
+
+    #[test]
+    fn test_lock_scope_follows_comment_block() {
+        let content = "/**\n\
+                       * @acp:fn \"charge\"\n\
+                       * @acp:lock frozen - Audited billing path\n\
+                       */\n\
+                       export function charge() {}\n\
+                       \n\
+                       // @acp:lock restricted - Owned by payments\n\
+                       export const x = 1;\n";
+        let result = parse_test_file(content);
+
+        let charge = result.symbols.iter().find(|s| s.name == "charge").unwrap();
+        assert_eq!(charge.constraints.as_ref().unwrap().level, "frozen");
+        // The later file-level lock isn't swallowed by the open symbol
+        assert_eq!(result.lock_level.as_deref(), Some("restricted"));
+        assert_eq!(result.lock_directive.as_deref(), Some("Owned by payments"));
+    }
+
+    #[test]
+    fn test_malformed_annotations_are_reported_with_location() {
+        let content = "// @acp:summary \"Splits a - b\"\n\
+                       // @acp:hack expires=bad-date \"Vendor bug\"\n\
+                       // @acp:hack expires=2025-03-01\n\
+                       // @acp:purpose \"Never closed\n\
+                       // @acp:source-confidence 1.5\n\
+                       export function f() {}\n";
+        let result = parse_test_file(content);
+
+        let errors: Vec<(usize, &str)> = result
+            .annotation_errors
+            .iter()
+            .map(|e| (e.line, e.message.as_str()))
+            .collect();
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0].0, 2);
+        assert!(errors[0].1.contains("'bad-date'"));
+        assert_eq!(errors[1].0, 4);
+        assert!(errors[1].1.contains("Unterminated quote"));
+        assert_eq!(errors[2].0, 5);
+        assert!(errors[2].1.contains("clamped"));
+        let error = AcpError::from(result.annotation_errors[0].clone());
+        assert!(matches!(error, AcpError::AnnotationParse { line: 2, .. }));
+        assert!(error.to_string().contains(".ts:2: "));
+
+        // The rest of the file still parses
+        assert_eq!(result.hacks.len(), 2);
+        assert_eq!(result.purpose.as_deref(), Some("Never closed"));
+    }
+
+    #[test]
+    fn test_bom_and_crlf_keep_line_numbers() {
+        // BOM, CRLF, and one lone CR line ending before the @acp:critical line
+        let content = "\u{feff}// @acp:module \"Auth\"\r\n\
+                       \r\n\
+                       // @acp:todo - Retry twice\r\n\
+                       export function login() {}\r\
+                       // @acp:critical - Token refresh\r\n\
+                       export function refresh() {}\r\n";
+        let result = parse_test_file(content);
+
+        assert_eq!(result.file.module.as_deref(), Some("Auth"));
+        assert_eq!(result.file.lines, 6);
+        let lines: Vec<(&str, usize)> = result
+            .file
+            .inline
+            .iter()
+            .map(|a| (a.annotation_type.as_str(), a.line))
+            .collect();
+        assert_eq!(lines, vec![("todo", 3), ("critical", 5)]);
+        assert_eq!(result.file.inline[1].directive, "Token refresh");
+    }
+
+    #[test]
+    fn test_hack_values_with_hyphens() {
+        let result = parse_test_file(
+            "// @acp:hack expires=2025-03-01 ticket=JIRA-123 \"Vendor bug\" - Remove after upgrade\n\
+             // @acp:lock - Safe to edit\n",
+        );
+
+        let hack = &result.hacks[0];
+        assert_eq!(hack.expires.as_deref(), Some("2025-03-01"));
+        assert_eq!(hack.ticket.as_deref(), Some("JIRA-123"));
+        assert_eq!(hack.reason.as_deref(), Some("Vendor bug"));
+        assert_eq!(result.file.inline[0].directive, "Remove after upgrade");
+        assert!(result.lock_level.is_none());
+        assert_eq!(result.lock_directive.as_deref(), Some("Safe to edit"));
+    }
+
+    #[test]
+    fn test_normalize_source() {
+        assert_eq!(normalize_source("\u{feff}a\r\nb\rc\n"), "a\nb\nc\n");
+        assert_eq!(normalize_source("a\nb"), "a\nb");
+    }
+
     #[test]
     fn test_param_with_type() {
         let content = r#"
@@ -1334,4 +1724,39 @@ mod type_annotation_tests {
             Some(TypeSource::Acp)
         );
     }
+
+    #[test]
+    fn test_throws_and_raises() {
+        let content = r#"
+// @acp:fn "test" - Test function
+// @acp:throws {ValidationError} when input is invalid - Catch and report
+// @acp:raises IOError
+"#;
+        let result = parse_test_file(content);
+        let throws = &result.symbols[0].throws;
+        assert_eq!(throws.len(), 2);
+
+        assert_eq!(throws[0].exception, "ValidationError");
+        assert_eq!(
+            throws[0].description.as_deref(),
+            Some("when input is invalid")
+        );
+        assert_eq!(throws[0].directive.as_deref(), Some("Catch and report"));
+        assert_eq!(throws[0].source_format, Some(SourceFormat::Acp));
+
+        assert_eq!(throws[1].exception, "IOError");
+        assert!(throws[1].description.is_none());
+        assert!(throws[1].directive.is_none());
+    }
+
+    #[test]
+    fn test_imports_deduplicated_and_sorted() {
+        let content = r#"
+// @acp:module "Auth"
+// @acp:imports ./session, "./db" ,  ./session
+// @acp:depends ./db,, lodash
+"#;
+        let result = parse_test_file(content);
+        assert_eq!(result.file.imports, vec!["./db", "./session", "lodash"]);
+    }
 }
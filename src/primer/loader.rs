@@ -4,6 +4,7 @@
 //! @acp:layer io
 
 use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 use super::types::*;
@@ -16,6 +17,8 @@ pub struct CliOverrides {
     pub preset: Option<String>,
     pub categories: Vec<String>,
     pub no_dynamic: bool,
+    /// Per-section value multipliers keyed by section or category ID
+    pub weights: HashMap<String, f64>,
 }
 
 /// Load primer configuration with 3-layer merging:
@@ -102,7 +105,22 @@ fn merge_configs(base: PrimerConfig, project: PrimerConfig) -> Result<PrimerConf
             || project.selection_strategy.weights.accuracy != 1.0
             || project.selection_strategy.weights.base != 1.0
         {
+            let sections = std::mem::take(&mut result.selection_strategy.weights.sections);
             result.selection_strategy.weights = project.selection_strategy.weights;
+            for (id, weight) in sections {
+                result
+                    .selection_strategy
+                    .weights
+                    .sections
+                    .entry(id)
+                    .or_insert(weight);
+            }
+        } else {
+            result
+                .selection_strategy
+                .weights
+                .sections
+                .extend(project.selection_strategy.weights.sections);
         }
     }
 
@@ -142,6 +160,13 @@ fn apply_cli_overrides(mut config: PrimerConfig, cli: &CliOverrides) -> Result<P
         }
     }
 
+    // Per-section weights: CLI wins over project config
+    config
+        .selection_strategy
+        .weights
+        .sections
+        .extend(cli.weights.clone());
+
     // Filter by categories if specified
     if !cli.categories.is_empty() {
         config
@@ -149,14 +174,16 @@ fn apply_cli_overrides(mut config: PrimerConfig, cli: &CliOverrides) -> Result<P
             .retain(|s| cli.categories.contains(&s.category));
     }
 
-    // Apply preset weights if specified
+    // Apply preset weights if specified, keeping per-section weights
     if let Some(ref preset_name) = cli.preset {
+        let sections = std::mem::take(&mut config.selection_strategy.weights.sections);
         if let Some(weights) = config.selection_strategy.presets.get(preset_name) {
             config.selection_strategy.weights = weights.clone();
         } else {
             // Use built-in presets
             config.selection_strategy.weights = super::scoring::get_preset_weights(preset_name);
         }
+        config.selection_strategy.weights.sections.extend(sections);
     }
 
     // Disable dynamic modifiers if requested
@@ -167,6 +194,34 @@ fn apply_cli_overrides(mut config: PrimerConfig, cli: &CliOverrides) -> Result<P
     Ok(config)
 }
 
+/// Drop weights for unknown section/category IDs and clamp negatives to 0
+///
+/// Returns a warning for every weight that was dropped or clamped.
+pub fn validate_section_weights(config: &mut PrimerConfig) -> Vec<String> {
+    let known: HashSet<&str> = config
+        .sections
+        .iter()
+        .flat_map(|s| [s.id.as_str(), s.category.as_str()])
+        .collect();
+
+    let mut warnings = Vec::new();
+    let mut weights = std::mem::take(&mut config.selection_strategy.weights.sections);
+    weights.retain(|id, weight| {
+        if !known.contains(id.as_str()) {
+            warnings.push(format!("Ignoring weight for unknown section: {}", id));
+            return false;
+        }
+        if weight.is_nan() || *weight < 0.0 {
+            warnings.push(format!("Weight for {} is negative; using 0", id));
+            *weight = 0.0;
+        }
+        true
+    });
+    warnings.sort();
+    config.selection_strategy.weights.sections = weights;
+    warnings
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -190,4 +245,27 @@ mod tests {
             .disabled_sections
             .contains(&"cli-overview".to_string()));
     }
+
+    #[test]
+    fn test_cli_weights_survive_preset_and_validate() {
+        let config = load_builtin_defaults().unwrap();
+        let cli = CliOverrides {
+            preset: Some("safe".to_string()),
+            weights: HashMap::from([
+                ("constraints".to_string(), 2.0),
+                ("cli-index".to_string(), -1.0),
+                ("no-such-section".to_string(), 3.0),
+            ]),
+            ..Default::default()
+        };
+        let mut result = apply_cli_overrides(config, &cli).unwrap();
+        assert_eq!(result.selection_strategy.weights.safety, 2.5);
+
+        let warnings = validate_section_weights(&mut result);
+        assert_eq!(warnings.len(), 2);
+        let sections = &result.selection_strategy.weights.sections;
+        assert_eq!(sections.get("constraints"), Some(&2.0));
+        assert_eq!(sections.get("cli-index"), Some(&0.0));
+        assert!(!sections.contains_key("no-such-section"));
+    }
 }
@@ -14,7 +14,7 @@ pub mod types;
 
 pub use condition::{evaluate_condition, ProjectState};
 pub use ide::IdeEnvironment;
-pub use loader::{load_primer_config, CliOverrides};
+pub use loader::{load_primer_config, validate_section_weights, CliOverrides};
 pub use renderer::{render_primer, render_primer_with_tier, OutputFormat};
 pub use scoring::{calculate_section_value, get_preset_weights};
 pub use selector::select_sections;
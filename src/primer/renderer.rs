@@ -16,6 +16,8 @@ pub enum OutputFormat {
     Markdown,
     Compact,
     Json,
+    /// Plain text; markdown templates are degraded (headings in ALL CAPS,
+    /// no emphasis or code marks) when a section has no text template
     Text,
 }
 
@@ -27,7 +29,7 @@ impl std::str::FromStr for OutputFormat {
             "markdown" | "md" => Ok(OutputFormat::Markdown),
             "compact" => Ok(OutputFormat::Compact),
             "json" => Ok(OutputFormat::Json),
-            "text" | "txt" => Ok(OutputFormat::Text),
+            "text" | "txt" | "plaintext" | "plain" => Ok(OutputFormat::Text),
             _ => Err(anyhow!("Unknown output format: {}", s)),
         }
     }
@@ -79,6 +81,20 @@ pub fn render_primer_with_tier(
 
 /// Render a single section
 fn render_section(section: &Section, format: OutputFormat, state: &ProjectState) -> Result<String> {
+    let rendered = render_section_template(section, format, state)?;
+    // Text falls back to the markdown template; strip its markup
+    if format == OutputFormat::Text && section.formats.text.is_none() {
+        return Ok(markdown_to_plaintext(&rendered));
+    }
+    Ok(rendered)
+}
+
+/// Render a section with the template chosen for `format`
+fn render_section_template(
+    section: &Section,
+    format: OutputFormat,
+    state: &ProjectState,
+) -> Result<String> {
     // Get template with fallback chain
     let template = match section.formats.get(format) {
         Some(t) => t,
@@ -104,6 +120,25 @@ fn render_section(section: &Section, format: OutputFormat, state: &ProjectState)
     }
 }
 
+/// Degrade markdown to plain text
+///
+/// Headings become ALL-CAPS lines; bold and inline-code marks are dropped.
+fn markdown_to_plaintext(markdown: &str) -> String {
+    markdown
+        .lines()
+        .map(|line| {
+            let line = line.replace("**", "").replace('`', "");
+            let trimmed = line.trim_start();
+            let level = trimmed.chars().take_while(|c| *c == '#').count();
+            match trimmed[level..].strip_prefix(' ') {
+                Some(heading) if level > 0 => heading.trim().to_uppercase(),
+                _ => line,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 fn render_static_template(template: &FormatTemplate) -> Result<String> {
     Ok(template.template.clone().unwrap_or_default())
 }
@@ -287,6 +322,19 @@ mod tests {
             OutputFormat::Compact
         );
         assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!(
+            "plaintext".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Text
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_plaintext() {
+        let markdown = "### Protected Files\n- `src/auth.ts`: **frozen**\nSee # not a heading";
+        assert_eq!(
+            markdown_to_plaintext(markdown),
+            "PROTECTED FILES\n- src/auth.ts: frozen\nSee # not a heading"
+        );
     }
 
     #[test]
@@ -11,6 +11,7 @@ use super::types::*;
 /// Note: Scores are unbounded weighted sums, not normalized to 0-100.
 /// A section with all dimensions at 100 and all weights at 1.0 yields score 400.
 /// This is intentional - it allows weight adjustments to have meaningful impact.
+/// The sum is then scaled by the section's entry in `weights.sections`, if any.
 pub fn calculate_section_value(
     section: &Section,
     weights: &DimensionWeights,
@@ -29,10 +30,12 @@ pub fn calculate_section_value(
     }
 
     // Calculate weighted sum (unbounded)
-    (value.safety as f64 * weights.safety)
+    let sum = (value.safety as f64 * weights.safety)
         + (value.efficiency as f64 * weights.efficiency)
         + (value.accuracy as f64 * weights.accuracy)
-        + (value.base as f64 * weights.base)
+        + (value.base as f64 * weights.base);
+
+    sum * weights.section_weight(section)
 }
 
 /// Calculate value per token for ranking
@@ -92,18 +95,21 @@ pub fn get_preset_weights(preset: &str) -> DimensionWeights {
             efficiency: 0.8,
             accuracy: 1.0,
             base: 0.8,
+            ..Default::default()
         },
         "efficient" => DimensionWeights {
             safety: 1.2,
             efficiency: 2.0,
             accuracy: 0.9,
             base: 0.8,
+            ..Default::default()
         },
         "accurate" => DimensionWeights {
             safety: 1.2,
             efficiency: 0.9,
             accuracy: 2.0,
             base: 0.8,
+            ..Default::default()
         },
         _ => DimensionWeights::default(),
     }
@@ -163,6 +169,35 @@ mod tests {
         assert_eq!(value, 340.0);
     }
 
+    #[test]
+    fn test_section_weights_scale_value() {
+        let section = Section {
+            id: "lock-frozen".to_string(),
+            category: "constraints".to_string(),
+            value: SectionValue {
+                base: 100,
+                ..SectionValue::default()
+            },
+            ..default_section()
+        };
+        let state = ProjectState::default();
+        let mut weights = DimensionWeights::default();
+        let base = calculate_section_value(&section, &weights, &state, true);
+
+        weights.sections.insert("constraints".to_string(), 2.0);
+        assert_eq!(
+            calculate_section_value(&section, &weights, &state, true),
+            base * 2.0
+        );
+
+        // Section ID takes precedence over category
+        weights.sections.insert("lock-frozen".to_string(), 0.5);
+        assert_eq!(
+            calculate_section_value(&section, &weights, &state, true),
+            base * 0.5
+        );
+    }
+
     #[test]
     fn test_preset_weights() {
         let safe = get_preset_weights("safe");
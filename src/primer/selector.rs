@@ -102,11 +102,11 @@ pub fn select_sections(
         .cloned()
         .collect();
 
-    // Sort by value descending
+    // Sort by value descending, ties by id so selection is stable
     safety_sections.sort_by(|a, b| {
         let va = calculate_section_value(a, weights, project_state, dynamic_enabled);
         let vb = calculate_section_value(b, weights, project_state, dynamic_enabled);
-        vb.partial_cmp(&va).unwrap_or(std::cmp::Ordering::Equal)
+        vb.total_cmp(&va).then_with(|| a.id.cmp(&b.id))
     });
 
     for section in safety_sections {
@@ -136,11 +136,11 @@ pub fn select_sections(
         .cloned()
         .collect();
 
-    // Sort by value-per-token descending
+    // Sort by value-per-token descending, ties by id
     remaining_sections.sort_by(|a, b| {
         let va = value_per_token(a, weights, project_state, dynamic_enabled);
         let vb = value_per_token(b, weights, project_state, dynamic_enabled);
-        vb.partial_cmp(&va).unwrap_or(std::cmp::Ordering::Equal)
+        vb.total_cmp(&va).then_with(|| a.id.cmp(&b.id))
     });
 
     for section in remaining_sections {
@@ -160,8 +160,8 @@ pub fn select_sections(
         }
     }
 
-    // Sort final output by priority
-    selected.sort_by_key(|s| s.priority);
+    // Sort final output by priority, ties by id
+    selected.sort_by(|a, b| a.priority.cmp(&b.priority).then_with(|| a.id.cmp(&b.id)));
 
     selected
 }
@@ -314,6 +314,28 @@ mod tests {
         assert!(matches_capabilities(&section, &[]));
     }
 
+    #[test]
+    fn test_selection_order_independent_of_config_order() {
+        let config =
+            crate::primer::load_primer_config(None, &crate::primer::CliOverrides::default())
+                .unwrap();
+        let mut reversed = config.clone();
+        reversed.sections.reverse();
+        let state = ProjectState::default();
+        let shell = ["shell".to_string()];
+
+        for budget in [200, 500, 1000] {
+            let ids = |c: &PrimerConfig| -> Vec<String> {
+                select_sections(c, budget, &shell, &state)
+                    .into_iter()
+                    .map(|s| s.id)
+                    .collect()
+            };
+            assert_eq!(ids(&config), ids(&config));
+            assert_eq!(ids(&config), ids(&reversed), "budget {}", budget);
+        }
+    }
+
     fn default_section() -> Section {
         Section {
             id: String::new(),
@@ -375,6 +375,10 @@ pub struct DimensionWeights {
 
     #[serde(default = "default_weight")]
     pub base: f64,
+
+    /// Per-section value multipliers keyed by section or category ID
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub sections: HashMap<String, f64>,
 }
 
 impl Default for DimensionWeights {
@@ -384,10 +388,23 @@ impl Default for DimensionWeights {
             efficiency: default_weight(),
             accuracy: default_weight(),
             base: default_weight(),
+            sections: HashMap::new(),
         }
     }
 }
 
+impl DimensionWeights {
+    /// Multiplier for a section: its own ID wins over its category
+    pub fn section_weight(&self, section: &Section) -> f64 {
+        self.sections
+            .get(&section.id)
+            .or_else(|| self.sections.get(&section.category))
+            .copied()
+            .unwrap_or(1.0)
+            .max(0.0)
+    }
+}
+
 fn default_safety_weight() -> f64 {
     1.5
 }
@@ -5,7 +5,303 @@
 //!
 //! Provides type-safe queries similar to jq but in Rust.
 
-use crate::cache::{Cache, DomainEntry, FileEntry, SymbolEntry};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+
+use chrono::Utc;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::cache::{
+    normalize_path, BehavioralAnnotations, Cache, DomainEntry, FileEntry, Language, SymbolEntry,
+    ThrowsEntry,
+};
+use crate::constraints::{HackMarker, LockLevel, LockSource};
+use crate::error::{AcpError, Result};
+
+/// @acp:summary "Annotated symbol count for one slice of the codebase"
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct CoverageBucket {
+    pub symbols: usize,
+    pub annotated: usize,
+    /// Percentage of symbols with a summary (0 when there are none)
+    pub coverage: f64,
+}
+
+impl CoverageBucket {
+    fn add(&mut self, annotated: bool) {
+        self.symbols += 1;
+        if annotated {
+            self.annotated += 1;
+        }
+        self.coverage = self.annotated as f64 / self.symbols as f64 * 100.0;
+    }
+}
+
+/// @acp:summary "Annotation coverage overall and broken down by domain and language"
+///
+/// A symbol counts as annotated when it has a summary, matching
+/// `Stats.annotation_coverage`. Symbols in files with several domains count
+/// toward each of them; files without a domain are left out of `by_domain`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CoverageReport {
+    pub total: CoverageBucket,
+    pub by_domain: BTreeMap<String, CoverageBucket>,
+    pub by_language: BTreeMap<String, CoverageBucket>,
+}
+
+/// @acp:summary "Size and annotation coverage for one language or domain"
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct StatsBucket {
+    pub files: usize,
+    pub lines: usize,
+    #[serde(flatten)]
+    pub coverage: CoverageBucket,
+}
+
+/// @acp:summary "A caller of a symbol and, when recorded, where the call is"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CallerSite {
+    pub caller: String,
+    /// Absent for callers known only from the call graph (older caches,
+    /// annotation-derived calls)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+/// @acp:summary "A direct caller or callee of a symbol"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Neighbor<'a> {
+    pub name: &'a str,
+    /// Files defining a symbol of that name; empty for code outside the index
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<&'a str>,
+}
+
+/// @acp:summary "A symbol with its direct callers and callees"
+#[derive(Debug, Clone, Serialize)]
+pub struct Neighborhood<'a> {
+    pub symbol: &'a SymbolEntry,
+    pub callers: Vec<Neighbor<'a>>,
+    pub callees: Vec<Neighbor<'a>>,
+}
+
+/// @acp:summary "A callee of a symbol, precise or guessed from a string literal"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CalleeEntry {
+    pub callee: String,
+    /// From `graph.heuristic_edges` rather than static call extraction
+    pub heuristic: bool,
+    /// Where the naming literal appears (heuristic callees only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+/// @acp:summary "Where a callee is defined"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CalleeScope {
+    /// A symbol in the cache
+    Internal,
+    /// Not in the cache or the caller's imports, and a builtin of the
+    /// caller's language (a heuristic: calls are recorded by name only)
+    Stdlib,
+    /// Not in the cache: named by one of the caller file's imports, or a
+    /// library call not recognized
+    External,
+}
+
+/// @acp:summary "A callee classified by where it is defined"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScopedCallee<'a> {
+    pub callee: &'a str,
+    pub scope: CalleeScope,
+    /// Defining file and line (internal callees only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+/// @acp:summary "Compiled symbol name pattern for multi-match lookups"
+///
+/// Compile once with [`SymbolMatcher::glob`] or [`SymbolMatcher::regex`],
+/// then pass to [`Query::find_symbols`].
+#[derive(Debug, Clone)]
+pub enum SymbolMatcher {
+    /// Shell-style glob (`handle*`, `get?ser`)
+    Glob(glob::Pattern),
+    /// Regular expression, unanchored unless the pattern says otherwise
+    Regex(Regex),
+}
+
+impl SymbolMatcher {
+    /// Compile a glob pattern
+    pub fn glob(pattern: &str) -> Result<Self> {
+        glob::Pattern::new(pattern)
+            .map(Self::Glob)
+            .map_err(|e| AcpError::Other(format!("Invalid glob pattern '{}': {}", pattern, e)))
+    }
+
+    /// Compile a regular expression
+    pub fn regex(pattern: &str) -> Result<Self> {
+        Regex::new(pattern)
+            .map(Self::Regex)
+            .map_err(|e| AcpError::Other(format!("Invalid regex '{}': {}", pattern, e)))
+    }
+
+    /// Check whether a symbol name matches
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            Self::Glob(p) => p.matches(name),
+            Self::Regex(r) => r.is_match(name),
+        }
+    }
+}
+
+/// @acp:summary "A symbol matched by fuzzy search, with its score"
+#[derive(Debug, Clone, Serialize)]
+pub struct FuzzyMatch<'a> {
+    /// Higher is a closer match
+    pub score: i64,
+    #[serde(flatten)]
+    pub symbol: &'a SymbolEntry,
+}
+
+/// @acp:summary "A deprecated file or symbol"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeprecatedEntry<'a> {
+    /// "file" or "symbol"
+    pub kind: &'static str,
+    /// File path, or the symbol's qualified name
+    pub name: &'a str,
+    pub file: &'a str,
+    /// First line of a symbol (absent for files)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Deprecation message from @acp:deprecated
+    pub message: &'a str,
+    /// Version the file or symbol was introduced (@acp:since)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<&'a str>,
+    /// Days since the code last changed, from git metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age_days: Option<u32>,
+}
+
+/// @acp:summary "A file or symbol under a lock"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LockedEntry<'a> {
+    pub level: LockLevel,
+    /// "file" or "symbol"
+    pub kind: &'static str,
+    /// File path, or the symbol's qualified name
+    pub name: &'a str,
+    pub file: &'a str,
+    /// First line of a symbol (absent for files)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Directive from the @acp:lock annotation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub directive: Option<&'a str>,
+    /// Directory rule the lock comes from, for files without @acp:lock
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inherited_from: Option<String>,
+}
+
+/// @acp:summary "Behavioral annotations a symbol must all carry"
+///
+/// An empty filter matches every symbol with any behavioral annotation or
+/// detected recursion.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BehaviorFilter {
+    pub pure: bool,
+    pub idempotent: bool,
+    pub memoized: bool,
+    pub r#async: bool,
+    pub generator: bool,
+    pub throttled: bool,
+    pub transactional: bool,
+    pub side_effects: bool,
+    /// Symbols marked recursive from the call graph
+    pub recursive: bool,
+}
+
+/// Stands in for symbols listed only because they are recursive
+static NO_BEHAVIOR: BehavioralAnnotations = BehavioralAnnotations {
+    pure: false,
+    idempotent: false,
+    memoized: None,
+    r#async: false,
+    generator: false,
+    throttled: None,
+    transactional: false,
+    side_effects: Vec::new(),
+};
+
+impl BehaviorFilter {
+    /// Check whether `behavioral` has every flag this filter asks for
+    pub fn matches(&self, behavioral: &BehavioralAnnotations) -> bool {
+        (!self.pure || behavioral.pure)
+            && (!self.idempotent || behavioral.idempotent)
+            && (!self.memoized || behavioral.memoized.is_some())
+            && (!self.r#async || behavioral.r#async)
+            && (!self.generator || behavioral.generator)
+            && (!self.throttled || behavioral.throttled.is_some())
+            && (!self.transactional || behavioral.transactional)
+            && (!self.side_effects || !behavioral.side_effects.is_empty())
+    }
+}
+
+/// @acp:summary "A symbol matched by a behavior query"
+#[derive(Debug, Clone, Serialize)]
+pub struct BehaviorEntry<'a> {
+    /// Qualified name of the symbol
+    pub name: &'a str,
+    pub file: &'a str,
+    pub line: usize,
+    pub behavioral: &'a BehavioralAnnotations,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub recursive: bool,
+}
+
+/// @acp:summary "A symbol that declares it throws a matching exception"
+#[derive(Debug, Clone, Serialize)]
+pub struct ThrowingEntry<'a> {
+    /// Qualified name of the symbol
+    pub name: &'a str,
+    pub file: &'a str,
+    pub line: usize,
+    /// The matching `throws` entry, with its description and directive
+    #[serde(flatten)]
+    pub throws: &'a ThrowsEntry,
+}
+
+/// @acp:summary "A symbol ranked by [`Query::hotpaths`]"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Hotpath<'a> {
+    /// Call graph node name
+    pub symbol: &'a str,
+    /// Distinct callers
+    pub fan_in: usize,
+    /// Distinct callees
+    pub fan_out: usize,
+    /// Commits touching the defining file, when churn weighting is on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub churn: Option<usize>,
+    /// fan_in × fan_out, times churn when present
+    pub score: usize,
+}
+
+/// Upper bound on partial paths explored by [`Query::call_paths`], which
+/// keeps enumeration on dense graphs from running away
+const MAX_PATH_EXPANSIONS: usize = 100_000;
 
 /// Query builder for cache
 pub struct Query<'a> {
@@ -17,9 +313,24 @@ impl<'a> Query<'a> {
         Self { cache }
     }
 
-    /// Get symbol by name
+    /// Get symbol by name, falling back to a qualified name match
     pub fn symbol(&self, name: &str) -> Option<&SymbolEntry> {
-        self.cache.get_symbol(name)
+        self.cache.get_symbol(name).or_else(|| {
+            self.cache
+                .symbols
+                .values()
+                .find(|s| s.qualified_name == name)
+        })
+    }
+
+    /// Get every symbol sharing a simple name, ordered by file
+    pub fn symbols_named(&self, name: &str) -> Vec<&SymbolEntry> {
+        self.cache.symbols_named(name)
+    }
+
+    /// Get every symbol whose simple or qualified name matches ignoring case and accents
+    pub fn symbols_named_ci(&self, name: &str) -> Vec<&SymbolEntry> {
+        self.cache.get_symbol_ci(name)
     }
 
     /// Get file by path with cross-platform path normalization
@@ -32,6 +343,22 @@ impl<'a> Query<'a> {
         self.cache.get_file(path)
     }
 
+    /// @acp:summary "A file's symbols sorted by start line, or None for an unknown file"
+    ///
+    /// The path is resolved like [`Query::file`], so `./src/x.ts` and
+    /// `src/x.ts` give the same outline.
+    pub fn outline(&self, path: &str) -> Option<Vec<&SymbolEntry>> {
+        let file = normalize_path(&self.cache.get_file(path)?.path);
+        let mut symbols: Vec<_> = self
+            .cache
+            .symbols
+            .values()
+            .filter(|symbol| normalize_path(&symbol.file) == file)
+            .collect();
+        symbols.sort_by(|a, b| (a.lines, &a.name).cmp(&(b.lines, &b.name)));
+        Some(symbols)
+    }
+
     /// Get callers of a symbol
     pub fn callers(&self, symbol: &str) -> Vec<&str> {
         self.cache
@@ -40,6 +367,62 @@ impl<'a> Query<'a> {
             .unwrap_or_default()
     }
 
+    /// @acp:summary "Callers of a symbol with their call sites"
+    ///
+    /// One entry per recorded call site, ordered by file and line, followed
+    /// by any callers with no recorded site.
+    pub fn caller_sites(&self, symbol: &str) -> Vec<CallerSite> {
+        let mut sites: Vec<CallerSite> = self
+            .cache
+            .get_call_sites(symbol)
+            .into_iter()
+            .map(|edge| CallerSite {
+                caller: edge.from.clone(),
+                file: Some(edge.file.clone()),
+                line: Some(edge.line),
+            })
+            .collect();
+        sites.sort_by(|a, b| (&a.file, a.line, &a.caller).cmp(&(&b.file, b.line, &b.caller)));
+
+        for caller in self.callers(symbol) {
+            if !sites.iter().any(|s| s.caller == caller) {
+                sites.push(CallerSite {
+                    caller: caller.to_string(),
+                    file: None,
+                    line: None,
+                });
+            }
+        }
+        sites
+    }
+
+    /// @acp:summary "A symbol with its direct callers and callees, in call graph order"
+    pub fn neighborhood<'s>(&'s self, symbol: &'s SymbolEntry) -> Neighborhood<'s> {
+        let neighbor = |name: &'s str| {
+            let mut files: Vec<&str> = self
+                .symbols_named(name)
+                .iter()
+                .map(|s| s.file.as_str())
+                .collect();
+            files.sort();
+            files.dedup();
+            Neighbor { name, files }
+        };
+        Neighborhood {
+            symbol,
+            callers: self
+                .callers(&symbol.name)
+                .into_iter()
+                .map(neighbor)
+                .collect(),
+            callees: self
+                .callees(&symbol.name)
+                .into_iter()
+                .map(neighbor)
+                .collect(),
+        }
+    }
+
     /// Get callees of a symbol
     pub fn callees(&self, symbol: &str) -> Vec<&str> {
         self.cache
@@ -48,9 +431,152 @@ impl<'a> Query<'a> {
             .unwrap_or_default()
     }
 
+    /// @acp:summary "Callees classified as internal, stdlib or external"
+    ///
+    /// A callee is internal when a cached symbol has its name, and external
+    /// when it names one of the caller file's imports. Otherwise it is
+    /// [`CalleeScope::Stdlib`] if it is a builtin of the caller's language;
+    /// those are left out unless `include_stdlib` is set
+    /// (`call_graph.include_stdlib`).
+    pub fn scoped_callees(&self, symbol: &str, include_stdlib: bool) -> Vec<ScopedCallee<'_>> {
+        let file = self
+            .symbol(symbol)
+            .and_then(|s| self.cache.get_file(&s.file));
+        let builtins = file.map_or(&[][..], |f| builtin_names(f.language));
+        let imported = file.map(imported_names).unwrap_or_default();
+        self.callees(symbol)
+            .into_iter()
+            .filter_map(|callee| {
+                let scoped = match self.cache.get_symbol(callee) {
+                    Some(def) => ScopedCallee {
+                        callee,
+                        scope: CalleeScope::Internal,
+                        file: Some(&def.file),
+                        line: Some(def.lines[0]),
+                    },
+                    None => ScopedCallee {
+                        callee,
+                        scope: if !imported.contains(&callee) && builtins.contains(&callee) {
+                            CalleeScope::Stdlib
+                        } else {
+                            CalleeScope::External
+                        },
+                        file: None,
+                        line: None,
+                    },
+                };
+                (include_stdlib || scoped.scope != CalleeScope::Stdlib).then_some(scoped)
+            })
+            .collect()
+    }
+
+    /// @acp:summary "Precise callees followed by heuristic ones"
+    ///
+    /// Heuristic callees are ordered by file and line; a callee guessed at
+    /// several places is listed once, at its first site.
+    pub fn callees_with_heuristic(&self, symbol: &str) -> Vec<CalleeEntry> {
+        let mut entries: Vec<CalleeEntry> = self
+            .callees(symbol)
+            .into_iter()
+            .map(|callee| CalleeEntry {
+                callee: callee.to_string(),
+                heuristic: false,
+                file: None,
+                line: None,
+            })
+            .collect();
+
+        let mut guessed = self.cache.get_heuristic_callees(symbol);
+        guessed.sort_by(|a, b| (&a.file, a.line, &a.to).cmp(&(&b.file, b.line, &b.to)));
+        for edge in guessed {
+            if !entries.iter().any(|e| e.callee == edge.to) {
+                entries.push(CalleeEntry {
+                    callee: edge.to.clone(),
+                    heuristic: true,
+                    file: Some(edge.file.clone()),
+                    line: Some(edge.line),
+                });
+            }
+        }
+        entries
+    }
+
+    /// @acp:summary "Up to `max` distinct call paths from one symbol to another"
+    ///
+    /// Breadth-first over the forward call graph, so paths come back
+    /// shortest first. Callees are visited in name order, which makes the
+    /// choice among equal-length paths deterministic. Paths never revisit a
+    /// symbol; `from == to` yields the single one-hop path `[from]`.
+    pub fn call_paths(&self, from: &str, to: &str, max: usize) -> Vec<Vec<String>> {
+        let Some(graph) = self.cache.graph.as_ref() else {
+            return Vec::new();
+        };
+        if max == 0 {
+            return Vec::new();
+        }
+        if from == to {
+            return vec![vec![from.to_string()]];
+        }
+
+        // Only symbols that can still reach `to` are worth extending
+        let mut reaches = HashSet::from([to]);
+        let mut pending = VecDeque::from([to]);
+        while let Some(node) = pending.pop_front() {
+            for caller in graph.reverse.get(node).into_iter().flatten() {
+                if reaches.insert(caller.as_str()) {
+                    pending.push_back(caller.as_str());
+                }
+            }
+        }
+        if !reaches.contains(from) {
+            return Vec::new();
+        }
+
+        let mut paths = Vec::new();
+        let mut queue = VecDeque::from([vec![from]]);
+        let mut expansions = 0;
+        while let Some(path) = queue.pop_front() {
+            expansions += 1;
+            if expansions > MAX_PATH_EXPANSIONS {
+                break;
+            }
+            let last = path[path.len() - 1];
+            let mut callees: Vec<&str> = graph
+                .forward
+                .get(last)
+                .into_iter()
+                .flatten()
+                .map(String::as_str)
+                .filter(|c| reaches.contains(c) && !path.contains(c))
+                .collect();
+            callees.sort_unstable();
+            callees.dedup();
+
+            for callee in callees {
+                let mut next = path.clone();
+                next.push(callee);
+                if callee == to {
+                    paths.push(next.into_iter().map(str::to_string).collect());
+                    if paths.len() == max {
+                        return paths;
+                    }
+                } else {
+                    queue.push_back(next);
+                }
+            }
+        }
+        paths
+    }
+
     /// Get domain by name
     pub fn domain(&self, name: &str) -> Option<&DomainEntry> {
-        self.cache.domains.get(name)
+        self.cache.domains.get(name).or_else(|| {
+            // A spelling merged away by domains.normalize
+            self.cache
+                .domains
+                .values()
+                .find(|d| d.aliases.iter().any(|a| a == name))
+        })
     }
 
     /// Get all domains
@@ -86,9 +612,296 @@ impl<'a> Query<'a> {
             .collect()
     }
 
-    /// Get hotpath symbols (symbols with many callers)
-    pub fn hotpaths(&self) -> impl Iterator<Item = &str> {
-        // Compute hotpaths from call graph
+    /// Find all symbols whose name matches a compiled pattern
+    ///
+    /// Results are sorted by name, then file, so output is stable across runs.
+    pub fn find_symbols(&self, matcher: &SymbolMatcher) -> Vec<&SymbolEntry> {
+        let mut matches: Vec<&SymbolEntry> = self
+            .cache
+            .symbols
+            .values()
+            .filter(|s| matcher.matches(&s.name))
+            .collect();
+        matches.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.file.cmp(&b.file)));
+        matches
+    }
+
+    /// @acp:summary "Symbols whose name fuzzily matches a query, best first"
+    ///
+    /// Every query character must appear in order in the name (case-insensitive
+    /// unless the query has uppercase); contiguous runs and word-boundary hits
+    /// score higher. Equal scores are ordered by name, then file.
+    pub fn fuzzy_symbols(&self, query: &str) -> Vec<FuzzyMatch<'_>> {
+        let matcher = SkimMatcherV2::default().smart_case();
+        let mut matches: Vec<FuzzyMatch> = self
+            .cache
+            .symbols
+            .values()
+            .filter_map(|symbol| {
+                let score = matcher.fuzzy_match(&symbol.name, query)?;
+                Some(FuzzyMatch { score, symbol })
+            })
+            .collect();
+        matches.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| a.symbol.name.cmp(&b.symbol.name))
+                .then_with(|| a.symbol.file.cmp(&b.symbol.file))
+        });
+        matches
+    }
+
+    /// @acp:summary "Deprecated files and symbols, sorted by file then line"
+    ///
+    /// A file entry sorts before the symbols inside it.
+    pub fn deprecated(&self) -> Vec<DeprecatedEntry<'_>> {
+        let now = Utc::now();
+        let files = self.cache.files.values().filter_map(|file| {
+            let lifecycle = file.lifecycle.as_ref()?;
+            Some(DeprecatedEntry {
+                kind: "file",
+                name: &file.path,
+                file: &file.path,
+                line: None,
+                message: lifecycle.deprecated.as_deref()?,
+                since: lifecycle.since.as_deref().or(file.since.as_deref()),
+                age_days: file
+                    .git
+                    .as_ref()
+                    .map(|git| (now - git.last_modified).num_days().max(0) as u32),
+            })
+        });
+        let symbols = self.cache.symbols.values().filter_map(|symbol| {
+            let lifecycle = symbol.lifecycle.as_ref()?;
+            Some(DeprecatedEntry {
+                kind: "symbol",
+                name: &symbol.qualified_name,
+                file: &symbol.file,
+                line: Some(symbol.lines[0]),
+                message: lifecycle.deprecated.as_deref()?,
+                since: lifecycle.since.as_deref(),
+                age_days: symbol.git.as_ref().map(|git| git.code_age_days),
+            })
+        });
+
+        let mut entries: Vec<_> = files.chain(symbols).collect();
+        // File keys may carry a "./" prefix that symbol paths lack
+        let path = |e: &DeprecatedEntry| e.file.trim_start_matches("./").to_string();
+        entries.sort_by(|a, b| {
+            path(a)
+                .cmp(&path(b))
+                .then_with(|| a.line.cmp(&b.line))
+                .then_with(|| a.name.cmp(b.name))
+        });
+        entries
+    }
+
+    /// @acp:summary "Locked files and symbols, sorted by level, then file and line"
+    ///
+    /// With no `level`, everything stricter or looser than `normal` is listed.
+    /// File locks are resolved like
+    /// [`ConstraintIndex::effective_lock`](crate::constraints::ConstraintIndex::effective_lock), so
+    /// directory rules from config are included.
+    pub fn locked(&self, level: Option<LockLevel>) -> Vec<LockedEntry<'_>> {
+        let wanted = |l: LockLevel| level.map_or(l != LockLevel::Normal, |want| l == want);
+        let Some(constraints) = &self.cache.constraints else {
+            return Vec::new();
+        };
+
+        let files = self.cache.files.keys().filter_map(|path| {
+            let lock = constraints.effective_lock(path)?;
+            wanted(lock.level).then(|| LockedEntry {
+                level: lock.level,
+                kind: "file",
+                name: path,
+                file: path,
+                line: None,
+                directive: constraints
+                    .by_file
+                    .get(path)
+                    .and_then(|c| c.directive.as_deref()),
+                inherited_from: match lock.source {
+                    LockSource::Explicit => None,
+                    LockSource::Inherited { pattern } => Some(pattern),
+                },
+            })
+        });
+        let symbols = self.cache.symbols.values().filter_map(|symbol| {
+            let constraint = symbol.constraints.as_ref()?;
+            let lock: LockLevel =
+                serde_json::from_value(serde_json::Value::from(constraint.level.as_str())).ok()?;
+            wanted(lock).then_some(LockedEntry {
+                level: lock,
+                kind: "symbol",
+                name: &symbol.qualified_name,
+                file: &symbol.file,
+                line: Some(symbol.lines[0]),
+                directive: Some(constraint.directive.as_str()),
+                inherited_from: None,
+            })
+        });
+
+        let mut entries: Vec<_> = files.chain(symbols).collect();
+        // File keys may carry a "./" prefix that symbol paths lack
+        let path = |e: &LockedEntry| e.file.trim_start_matches("./").to_string();
+        entries.sort_by(|a, b| {
+            a.level
+                .cmp(&b.level)
+                .then_with(|| path(a).cmp(&path(b)))
+                .then_with(|| a.line.cmp(&b.line))
+                .then_with(|| a.name.cmp(b.name))
+        });
+        entries
+    }
+
+    /// @acp:summary "Hack markers, sorted by file then line"
+    pub fn hacks(&self) -> Vec<&HackMarker> {
+        let mut hacks: Vec<_> = self
+            .cache
+            .constraints
+            .iter()
+            .flat_map(|c| &c.hacks)
+            .collect();
+        hacks.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        hacks
+    }
+
+    /// @acp:summary "Symbols whose behavioral annotations match, sorted by file then line"
+    pub fn behavior(&self, filter: &BehaviorFilter) -> Vec<BehaviorEntry<'_>> {
+        let mut entries: Vec<_> = self
+            .cache
+            .symbols
+            .values()
+            .filter_map(|symbol| {
+                let behavioral = symbol.behavioral.as_ref().unwrap_or(&NO_BEHAVIOR);
+                let listed = !behavioral.is_empty() || symbol.recursive;
+                (listed && filter.matches(behavioral) && (!filter.recursive || symbol.recursive))
+                    .then_some(BehaviorEntry {
+                        name: &symbol.qualified_name,
+                        file: &symbol.file,
+                        line: symbol.lines[0],
+                        behavioral,
+                        recursive: symbol.recursive,
+                    })
+            })
+            .collect();
+        entries.sort_by(|a, b| (a.file, a.line, a.name).cmp(&(b.file, b.line, b.name)));
+        entries
+    }
+
+    /// @acp:summary "Symbols declaring an exception, sorted by file then line"
+    ///
+    /// Without a matcher the exception type must equal `exception` exactly.
+    /// A symbol appears once per matching `throws` entry.
+    pub fn throwing(
+        &self,
+        exception: &str,
+        matcher: Option<&SymbolMatcher>,
+    ) -> Vec<ThrowingEntry<'_>> {
+        let matches = |ty: &str| matcher.map_or(ty == exception, |m| m.matches(ty));
+        let mut entries: Vec<_> = self
+            .cache
+            .symbols
+            .values()
+            .flat_map(|symbol| {
+                symbol
+                    .throws
+                    .iter()
+                    .filter(|t| matches(&t.exception))
+                    .map(move |throws| ThrowingEntry {
+                        name: &symbol.qualified_name,
+                        file: &symbol.file,
+                        line: symbol.lines[0],
+                        throws,
+                    })
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            (a.file, a.line, a.name, &a.throws.exception).cmp(&(
+                b.file,
+                b.line,
+                b.name,
+                &b.throws.exception,
+            ))
+        });
+        entries
+    }
+
+    /// Compute annotation coverage per domain and per language
+    pub fn coverage(&self) -> CoverageReport {
+        let mut report = CoverageReport::default();
+        for symbol in self.cache.symbols.values() {
+            let annotated = symbol.summary.is_some();
+            report.total.add(annotated);
+
+            let Some(file) = self.cache.get_file(&symbol.file) else {
+                continue;
+            };
+            report
+                .by_language
+                .entry(language_name(file))
+                .or_default()
+                .add(annotated);
+            for domain in &file.domains {
+                report
+                    .by_domain
+                    .entry(domain.clone())
+                    .or_default()
+                    .add(annotated);
+            }
+        }
+        report
+    }
+
+    /// @acp:summary "Files, symbols, lines and coverage per language"
+    ///
+    /// Coverage is [`Query::coverage`]'s `by_language`, so symbols whose
+    /// file isn't in the cache are left out here too.
+    pub fn stats_by_language(&self) -> BTreeMap<String, StatsBucket> {
+        self.stats_by(self.coverage().by_language, |file| {
+            vec![language_name(file)]
+        })
+    }
+
+    /// @acp:summary "Files, symbols, lines and coverage per domain"
+    ///
+    /// Files in several domains, and their symbols, count toward each.
+    /// Files without a domain are left out.
+    pub fn stats_by_domain(&self) -> BTreeMap<String, StatsBucket> {
+        self.stats_by(self.coverage().by_domain, |file| file.domains.clone())
+    }
+
+    /// Add file and line counts per group to a coverage breakdown
+    fn stats_by(
+        &self,
+        coverage: BTreeMap<String, CoverageBucket>,
+        groups: impl Fn(&FileEntry) -> Vec<String>,
+    ) -> BTreeMap<String, StatsBucket> {
+        let mut buckets: BTreeMap<String, StatsBucket> = coverage
+            .into_iter()
+            .map(|(group, coverage)| {
+                let bucket = StatsBucket {
+                    coverage,
+                    ..Default::default()
+                };
+                (group, bucket)
+            })
+            .collect();
+        for file in self.cache.files.values() {
+            for group in groups(file) {
+                let bucket = buckets.entry(group).or_default();
+                bucket.files += 1;
+                bucket.lines += file.lines;
+            }
+        }
+        buckets
+    }
+
+    /// Get the ten most-called symbols (by caller count)
+    ///
+    /// This is the ranking `debug-context` reports; `query hotpaths` uses
+    /// [`Query::hotpaths`] instead.
+    pub fn most_called(&self) -> impl Iterator<Item = &str> {
         self.cache
             .graph
             .as_ref()
@@ -105,4 +918,859 @@ impl<'a> Query<'a> {
             .unwrap_or_default()
             .into_iter()
     }
+
+    /// @acp:summary "Rank call graph nodes by fan-in × fan-out, highest first"
+    ///
+    /// A heuristic: the graph is keyed by name, so same-named symbols share
+    /// one node and their callers and callees are pooled. Nodes that are only
+    /// called or only call out score zero and are left out. With `churn`,
+    /// the score is also multiplied by the commit count of the defining file
+    /// (the busiest one when several define the name); symbols have no commit
+    /// count of their own.
+    pub fn hotpaths(&self, churn: bool) -> Vec<Hotpath<'_>> {
+        let Some(graph) = self.cache.graph.as_ref() else {
+            return Vec::new();
+        };
+        let distinct = |edges: Option<&Vec<String>>| {
+            edges.map_or(0, |e| e.iter().collect::<HashSet<_>>().len())
+        };
+
+        let mut hotpaths: Vec<_> = graph
+            .forward
+            .keys()
+            .filter_map(|name| {
+                let fan_in = distinct(graph.reverse.get(name));
+                let fan_out = distinct(graph.forward.get(name));
+                let churn = churn.then(|| {
+                    self.cache
+                        .symbols_named(name)
+                        .iter()
+                        .filter_map(|s| self.cache.get_file(&s.file)?.git.as_ref())
+                        .map(|git| git.commit_count)
+                        .max()
+                        .unwrap_or(1)
+                        .max(1)
+                });
+                let score = fan_in * fan_out * churn.unwrap_or(1);
+                (score > 0).then_some(Hotpath {
+                    symbol: name,
+                    fan_in,
+                    fan_out,
+                    churn,
+                    score,
+                })
+            })
+            .collect();
+        hotpaths.sort_by(|a, b| b.score.cmp(&a.score).then(a.symbol.cmp(b.symbol)));
+        hotpaths
+    }
+}
+
+/// Lowercase language name used as a grouping key
+fn language_name(file: &FileEntry) -> String {
+    format!("{:?}", file.language).to_lowercase()
+}
+
+/// Builtin functions and macros of each language
+///
+/// Only names that are callable without an import or a receiver; calls are
+/// recorded by simple name, so methods such as `map` or `get` could belong
+/// to anything and are not listed.
+fn builtin_names(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::Rust => &[
+            "assert",
+            "assert_eq",
+            "assert_ne",
+            "dbg",
+            "eprintln",
+            "format",
+            "panic",
+            "print",
+            "println",
+            "todo",
+            "unreachable",
+            "vec",
+            "write",
+            "writeln",
+        ],
+        Language::Typescript | Language::Javascript => &[
+            "clearInterval",
+            "clearTimeout",
+            "decodeURIComponent",
+            "encodeURIComponent",
+            "fetch",
+            "isNaN",
+            "parseFloat",
+            "parseInt",
+            "queueMicrotask",
+            "require",
+            "setInterval",
+            "setTimeout",
+            "structuredClone",
+        ],
+        Language::Python => &[
+            "abs",
+            "all",
+            "any",
+            "bool",
+            "dict",
+            "enumerate",
+            "filter",
+            "float",
+            "getattr",
+            "hasattr",
+            "int",
+            "isinstance",
+            "issubclass",
+            "iter",
+            "len",
+            "list",
+            "map",
+            "max",
+            "min",
+            "next",
+            "open",
+            "print",
+            "range",
+            "repr",
+            "reversed",
+            "set",
+            "setattr",
+            "sorted",
+            "str",
+            "sum",
+            "super",
+            "tuple",
+            "type",
+            "zip",
+        ],
+        Language::Go => &[
+            "append", "cap", "clear", "close", "copy", "delete", "len", "make", "max", "min",
+            "new", "panic", "print", "println", "recover",
+        ],
+        Language::Kotlin => &[
+            "check",
+            "error",
+            "lazy",
+            "listOf",
+            "mapOf",
+            "mutableListOf",
+            "mutableMapOf",
+            "print",
+            "println",
+            "repeat",
+            "require",
+            "setOf",
+        ],
+        Language::Ruby => &[
+            "format",
+            "lambda",
+            "loop",
+            "p",
+            "print",
+            "proc",
+            "puts",
+            "raise",
+            "require",
+            "require_relative",
+            "sprintf",
+        ],
+        Language::Swift => &[
+            "abs",
+            "assert",
+            "fatalError",
+            "max",
+            "min",
+            "precondition",
+            "print",
+            "stride",
+            "zip",
+        ],
+        Language::Java | Language::CSharp | Language::Cpp | Language::C | Language::Php => &[],
+    }
+}
+
+/// Names a file's imports bring into scope, from their module specifiers
+///
+/// Only the module itself is recorded (`express`, `os.path` -> `path`), so
+/// this finds calls through a module or default import. Relative imports
+/// name project files, which are already in the cache.
+fn imported_names(file: &FileEntry) -> Vec<&str> {
+    file.imports
+        .iter()
+        .filter(|import| !import.starts_with('.') && !import.starts_with('/'))
+        .filter_map(|import| {
+            let last = import.rsplit(['/', ':']).next()?;
+            let last = last.rsplit('.').next()?;
+            (!last.is_empty()).then_some(last)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{CacheBuilder, CallEdge, LifecycleAnnotations};
+
+    fn symbol(name: &str, file: &str) -> SymbolEntry {
+        SymbolEntry {
+            lines: [1, 5],
+            ..SymbolEntry::new(name, file)
+        }
+    }
+
+    fn sample_cache() -> Cache {
+        CacheBuilder::new("test", ".")
+            .add_symbol(symbol("handleRequest", "src/server.ts"))
+            .add_symbol(symbol("handleError", "src/errors.ts"))
+            .add_symbol(symbol("parseRequest", "src/parser.ts"))
+            .build()
+    }
+
+    #[test]
+    fn test_outline_sorted_by_line() {
+        let at = |name: &str, file: &str, start: usize| {
+            let mut sym = symbol(name, file);
+            sym.lines = [start, start + 3];
+            sym
+        };
+        let file: FileEntry = serde_json::from_value(serde_json::json!({
+            "path": "./src/server.ts",
+            "lines": 40,
+            "language": "typescript",
+        }))
+        .unwrap();
+        let cache = CacheBuilder::new("test", ".")
+            .add_file(file)
+            .add_symbol(at("stop", "src/server.ts", 30))
+            .add_symbol(at("start", "src/server.ts", 5))
+            .add_symbol(at("parse", "src/parser.ts", 1))
+            .build();
+        let q = Query::new(&cache);
+
+        for path in ["src/server.ts", "./src/server.ts"] {
+            let names: Vec<_> = q
+                .outline(path)
+                .unwrap()
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect();
+            assert_eq!(names, vec!["start", "stop"]);
+        }
+        assert!(q.outline("src/missing.ts").is_none());
+    }
+
+    #[test]
+    fn test_find_symbols_glob() {
+        let cache = sample_cache();
+        let q = Query::new(&cache);
+        let matcher = SymbolMatcher::glob("handle*").unwrap();
+
+        let names: Vec<_> = q.find_symbols(&matcher).iter().map(|s| &s.name).collect();
+        assert_eq!(names, vec!["handleError", "handleRequest"]);
+    }
+
+    #[test]
+    fn test_find_symbols_regex() {
+        let cache = sample_cache();
+        let q = Query::new(&cache);
+        let matcher = SymbolMatcher::regex("Request$").unwrap();
+
+        let names: Vec<_> = q.find_symbols(&matcher).iter().map(|s| &s.name).collect();
+        assert_eq!(names, vec!["handleRequest", "parseRequest"]);
+    }
+
+    #[test]
+    fn test_fuzzy_symbols() {
+        let cache = CacheBuilder::new("test", ".")
+            .add_symbol(symbol("handleRequest", "src/server.ts"))
+            .add_symbol(symbol("handleError", "src/errors.ts"))
+            .add_symbol(symbol("parseRequest", "src/parser.ts"))
+            .add_symbol(symbol("handleRequest", "src/legacy.ts"))
+            .build();
+        let q = Query::new(&cache);
+
+        let found: Vec<_> = q
+            .fuzzy_symbols("hndlreq")
+            .iter()
+            .map(|m| m.symbol.file.as_str())
+            .collect();
+        // Same name and score: ties break on file
+        assert_eq!(found, vec!["src/legacy.ts", "src/server.ts"]);
+
+        let ranked = q.fuzzy_symbols("req");
+        assert_eq!(ranked.len(), 3);
+        assert!(ranked.windows(2).all(|w| w[0].score >= w[1].score));
+        assert!(q.fuzzy_symbols("xyz").is_empty());
+    }
+
+    #[test]
+    fn test_deprecated() {
+        let deprecated = |message: &str, since: Option<&str>| LifecycleAnnotations {
+            deprecated: Some(message.to_string()),
+            since: since.map(str::to_string),
+            ..Default::default()
+        };
+        let mut old = symbol("oldLogin", "src/auth.ts");
+        old.lines = [40, 50];
+        old.lifecycle = Some(deprecated("Use login()", Some("1.2")));
+        let mut older = symbol("legacyHash", "src/auth.ts");
+        older.lines = [10, 20];
+        older.lifecycle = Some(deprecated("Use argon2", None));
+        let mut beta = symbol("preview", "src/auth.ts");
+        beta.lifecycle = Some(LifecycleAnnotations {
+            beta: true,
+            ..Default::default()
+        });
+
+        let cache = CacheBuilder::new("test", ".")
+            .add_symbol(old)
+            .add_symbol(older)
+            .add_symbol(beta)
+            .add_symbol(symbol("handleRequest", "src/server.ts"))
+            .build();
+        let q = Query::new(&cache);
+
+        let found: Vec<_> = q
+            .deprecated()
+            .iter()
+            .map(|e| (e.line, e.message, e.since))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (Some(10), "Use argon2", None),
+                (Some(40), "Use login()", Some("1.2")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_locked_groups_by_level() {
+        let file = |path: &str| -> FileEntry {
+            serde_json::from_value(serde_json::json!({
+                "path": path,
+                "lines": 10,
+                "language": "typescript",
+                "exports": [],
+                "imports": []
+            }))
+            .unwrap()
+        };
+        let mut guarded = symbol("charge", "src/billing.ts");
+        guarded.lines = [12, 30];
+        guarded.constraints = Some(crate::cache::SymbolConstraint {
+            level: "restricted".to_string(),
+            directive: "Ask before changing".to_string(),
+            auto_generated: false,
+        });
+        let mut cache = CacheBuilder::new("test", ".")
+            .add_file(file("src/billing.ts"))
+            .add_file(file("src/generated/api.ts"))
+            .add_file(file("src/utils.ts"))
+            .add_symbol(guarded)
+            .build();
+        cache.constraints = Some(
+            serde_json::from_value(serde_json::json!({
+                "by_file": {
+                    "src/billing.ts": {
+                        "mutation": { "level": "frozen" },
+                        "directive": "Do not modify"
+                    },
+                    "src/utils.ts": { "mutation": { "level": "normal" } }
+                },
+                "directory_locks": [{ "pattern": "src/generated/**", "level": "frozen" }]
+            }))
+            .unwrap(),
+        );
+        let q = Query::new(&cache);
+
+        let locked: Vec<_> = q
+            .locked(None)
+            .iter()
+            .map(|e| (e.level, e.kind, e.file, e.directive))
+            .collect();
+        assert_eq!(
+            locked,
+            vec![
+                (
+                    LockLevel::Frozen,
+                    "file",
+                    "src/billing.ts",
+                    Some("Do not modify")
+                ),
+                (LockLevel::Frozen, "file", "src/generated/api.ts", None),
+                (
+                    LockLevel::Restricted,
+                    "symbol",
+                    "src/billing.ts",
+                    Some("Ask before changing")
+                ),
+            ]
+        );
+        assert_eq!(
+            q.locked(None)[1].inherited_from.as_deref(),
+            Some("src/generated/**")
+        );
+        assert_eq!(q.locked(Some(LockLevel::Normal))[0].file, "src/utils.ts");
+    }
+
+    #[test]
+    fn test_behavior_filters_combine() {
+        let with = |name: &str, line: usize, behavioral: BehavioralAnnotations| {
+            let mut sym = symbol(name, "src/orders.ts");
+            sym.lines = [line, line + 5];
+            sym.behavioral = Some(behavioral);
+            sym
+        };
+        let cache = CacheBuilder::new("test", ".")
+            .add_symbol(with(
+                "total",
+                30,
+                BehavioralAnnotations {
+                    pure: true,
+                    ..Default::default()
+                },
+            ))
+            .add_symbol(with(
+                "place",
+                10,
+                BehavioralAnnotations {
+                    transactional: true,
+                    side_effects: vec!["db".to_string()],
+                    ..Default::default()
+                },
+            ))
+            .add_symbol(with(
+                "refund",
+                20,
+                BehavioralAnnotations {
+                    transactional: true,
+                    ..Default::default()
+                },
+            ))
+            .add_symbol(symbol("handleRequest", "src/server.ts"))
+            .add_symbol(SymbolEntry {
+                recursive: true,
+                ..symbol("walk", "src/tree.ts")
+            })
+            .build();
+        let q = Query::new(&cache);
+        let names = |filter: BehaviorFilter| -> Vec<String> {
+            q.behavior(&filter)
+                .iter()
+                .map(|e| e.name.rsplit(':').next().unwrap().to_string())
+                .collect()
+        };
+
+        assert_eq!(
+            names(BehaviorFilter::default()),
+            vec!["place", "refund", "total", "walk"]
+        );
+        assert_eq!(
+            names(BehaviorFilter {
+                recursive: true,
+                ..Default::default()
+            }),
+            vec!["walk"]
+        );
+        let transactional = BehaviorFilter {
+            transactional: true,
+            ..Default::default()
+        };
+        assert_eq!(names(transactional), vec!["place", "refund"]);
+        assert_eq!(
+            names(BehaviorFilter {
+                side_effects: true,
+                ..transactional
+            }),
+            vec!["place"]
+        );
+        assert!(names(BehaviorFilter {
+            pure: true,
+            ..transactional
+        })
+        .is_empty());
+    }
+
+    #[test]
+    fn test_throwing_exact_and_glob() {
+        let throws = |exception: &str, description: Option<&str>| -> ThrowsEntry {
+            serde_json::from_value(serde_json::json!({
+                "exception": exception,
+                "description": description,
+            }))
+            .unwrap()
+        };
+        let cache = CacheBuilder::new("test", ".")
+            .add_symbol(SymbolEntry {
+                throws: vec![
+                    throws("TimeoutError", Some("When the upstream is slow")),
+                    throws("ValidationError", None),
+                ],
+                ..symbol("fetchUser", "src/api.ts")
+            })
+            .add_symbol(SymbolEntry {
+                throws: vec![throws("TimeoutErrorLike", None)],
+                ..symbol("retry", "src/retry.ts")
+            })
+            .add_symbol(symbol("render", "src/view.ts"))
+            .build();
+        let q = Query::new(&cache);
+
+        let exact = q.throwing("TimeoutError", None);
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact[0].name, "src/api.ts:fetchUser");
+        assert_eq!(
+            exact[0].throws.description.as_deref(),
+            Some("When the upstream is slow")
+        );
+        let json = serde_json::to_value(&exact[0]).unwrap();
+        assert_eq!(json["exception"], "TimeoutError");
+
+        let glob = SymbolMatcher::glob("*Error*").unwrap();
+        let found: Vec<_> = q
+            .throwing("*Error*", Some(&glob))
+            .iter()
+            .map(|e| e.throws.exception.as_str())
+            .collect();
+        assert_eq!(
+            found,
+            vec!["TimeoutError", "ValidationError", "TimeoutErrorLike"]
+        );
+        assert!(q.throwing("timeouterror", None).is_empty());
+    }
+
+    #[test]
+    fn test_coverage_breakdown() {
+        let file = |path: &str, language: &str, domains: &[&str]| -> FileEntry {
+            serde_json::from_value(serde_json::json!({
+                "path": path,
+                "lines": 10,
+                "language": language,
+                "exports": [],
+                "imports": [],
+                "domains": domains
+            }))
+            .unwrap()
+        };
+        let mut documented = symbol("handleRequest", "src/server.ts");
+        documented.summary = Some("Handle a request".to_string());
+        let cache = CacheBuilder::new("test", ".")
+            .add_file(file("./src/server.ts", "typescript", &["api"]))
+            .add_file(file("src/parser.py", "python", &["api", "parsing"]))
+            .add_symbol(documented)
+            .add_symbol(symbol("handleError", "src/server.ts"))
+            .add_symbol(symbol("parseRequest", "src/parser.py"))
+            .build();
+
+        let report = Query::new(&cache).coverage();
+        assert_eq!(report.total.symbols, 3);
+        assert_eq!(report.total.annotated, 1);
+        assert_eq!(report.by_domain["api"].symbols, 3);
+        assert_eq!(report.by_domain["parsing"].annotated, 0);
+        assert_eq!(report.by_language["typescript"].coverage, 50.0);
+        assert_eq!(report.by_language["python"].coverage, 0.0);
+    }
+
+    #[test]
+    fn test_stats_breakdown() {
+        let file = |path: &str, lines: usize, language: &str, domains: &[&str]| -> FileEntry {
+            serde_json::from_value(serde_json::json!({
+                "path": path,
+                "lines": lines,
+                "language": language,
+                "exports": [],
+                "imports": [],
+                "domains": domains
+            }))
+            .unwrap()
+        };
+        let mut documented = symbol("parseRequest", "src/parser.py");
+        documented.summary = Some("Parse a request".to_string());
+        let cache = CacheBuilder::new("test", ".")
+            .add_file(file("src/server.ts", 40, "typescript", &["api"]))
+            .add_file(file("src/parser.py", 25, "python", &["api", "parsing"]))
+            .add_file(file("scripts/seed.py", 5, "python", &[]))
+            .add_symbol(documented)
+            .add_symbol(symbol("handleRequest", "src/server.ts"))
+            .add_symbol(symbol("vanished", "src/deleted.go"))
+            .build();
+        let q = Query::new(&cache);
+
+        let by_language = q.stats_by_language();
+        let python = &by_language["python"];
+        assert_eq!((python.files, python.lines), (2, 30));
+        assert_eq!(python.coverage.symbols, 1);
+        assert_eq!(python.coverage.coverage, 100.0);
+        assert_eq!(by_language["typescript"].coverage.annotated, 0);
+        // Agrees with the coverage report: uncached files are left out
+        assert!(!by_language.contains_key("unknown"));
+        let coverage = q.coverage();
+        for (language, bucket) in &by_language {
+            assert_eq!(bucket.coverage, coverage.by_language[language]);
+        }
+
+        // Multi-domain files count toward each domain; undomained files don't
+        let by_domain = q.stats_by_domain();
+        assert_eq!(by_domain.len(), 2);
+        assert_eq!((by_domain["api"].files, by_domain["api"].lines), (2, 65));
+        assert_eq!(by_domain["api"].coverage.symbols, 2);
+        assert_eq!(by_domain["parsing"].coverage.annotated, 1);
+    }
+
+    #[test]
+    fn test_caller_sites() {
+        let site = |from: &str, file: &str, line: usize| CallEdge {
+            from: from.to_string(),
+            to: "save".to_string(),
+            file: file.to_string(),
+            line,
+        };
+        let cache = CacheBuilder::new("test", ".")
+            .add_call_site(site("handle", "src/routes.ts", 30))
+            .add_call_site(site("boot", "src/main.ts", 4))
+            .add_call_site(site("handle", "src/routes.ts", 12))
+            .add_call_site(site("handle", "src/routes.ts", 12))
+            .add_call_edge("legacy", vec!["save".into()])
+            .build();
+        let q = Query::new(&cache);
+        assert_eq!(q.callers("save"), vec!["handle", "boot", "legacy"]);
+
+        let sites: Vec<_> = q
+            .caller_sites("save")
+            .into_iter()
+            .map(|s| (s.caller, s.file, s.line))
+            .collect();
+        let at = |caller: &str, file: &str, line| {
+            (caller.to_string(), Some(file.to_string()), Some(line))
+        };
+        assert_eq!(
+            sites,
+            vec![
+                at("boot", "src/main.ts", 4),
+                at("handle", "src/routes.ts", 12),
+                at("handle", "src/routes.ts", 30),
+                ("legacy".to_string(), None, None),
+            ]
+        );
+
+        let json = serde_json::to_value(&q.caller_sites("save")[3]).unwrap();
+        assert_eq!(json, serde_json::json!({ "caller": "legacy" }));
+    }
+
+    #[test]
+    fn test_scoped_callees() {
+        let file: FileEntry = serde_json::from_value(serde_json::json!({
+            "path": "src/server.ts",
+            "lines": 10,
+            "language": "typescript",
+            "exports": [],
+            "imports": ["express", "./parser", "node:path"]
+        }))
+        .unwrap();
+        let mut parser = symbol("parseRequest", "src/parser.ts");
+        parser.lines = [12, 30];
+        let cache = CacheBuilder::new("test", ".")
+            .add_file(file)
+            .add_symbol(symbol("handleRequest", "src/server.ts"))
+            .add_symbol(parser)
+            .add_call_edge(
+                "handleRequest",
+                vec![
+                    "parseRequest".into(),
+                    "setTimeout".into(),
+                    "express".into(),
+                    "path".into(),
+                    "map".into(),
+                ],
+            )
+            .build();
+        let q = Query::new(&cache);
+
+        let scoped: Vec<_> = q
+            .scoped_callees("handleRequest", true)
+            .into_iter()
+            .map(|c| (c.callee, c.scope, c.file, c.line))
+            .collect();
+        assert_eq!(
+            scoped,
+            vec![
+                (
+                    "parseRequest",
+                    CalleeScope::Internal,
+                    Some("src/parser.ts"),
+                    Some(12)
+                ),
+                ("setTimeout", CalleeScope::Stdlib, None, None),
+                ("express", CalleeScope::External, None, None),
+                ("path", CalleeScope::External, None, None),
+                // Method names are not guessed to be stdlib
+                ("map", CalleeScope::External, None, None),
+            ]
+        );
+
+        let json = serde_json::to_value(&q.scoped_callees("handleRequest", true)[1]).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "callee": "setTimeout", "scope": "stdlib" })
+        );
+
+        // call_graph.include_stdlib off leaves builtins out
+        let names: Vec<_> = q
+            .scoped_callees("handleRequest", false)
+            .into_iter()
+            .map(|c| c.callee)
+            .collect();
+        assert_eq!(names, vec!["parseRequest", "express", "path", "map"]);
+    }
+
+    #[test]
+    fn test_neighborhood() {
+        let cache = CacheBuilder::new("test", ".")
+            .add_symbol(symbol("handleRequest", "src/server.ts"))
+            .add_symbol(symbol("parseRequest", "src/parser.ts"))
+            .add_call_edge("main", vec!["handleRequest".into()])
+            .add_call_edge("handleRequest", vec!["parseRequest".into(), "log".into()])
+            .build();
+        let q = Query::new(&cache);
+        let hood = q.neighborhood(q.symbol("handleRequest").unwrap());
+
+        let names = |n: &[Neighbor]| -> Vec<(String, Vec<String>)> {
+            n.iter()
+                .map(|n| {
+                    let files = n.files.iter().map(|f| f.to_string()).collect();
+                    (n.name.to_string(), files)
+                })
+                .collect()
+        };
+        assert_eq!(names(&hood.callers), vec![("main".to_string(), vec![])]);
+        assert_eq!(
+            names(&hood.callees),
+            vec![
+                (
+                    "parseRequest".to_string(),
+                    vec!["src/parser.ts".to_string()]
+                ),
+                ("log".to_string(), vec![]),
+            ]
+        );
+
+        let json = serde_json::to_value(&hood).unwrap();
+        assert_eq!(json["symbol"]["name"], "handleRequest");
+        assert_eq!(json["callees"][1], serde_json::json!({ "name": "log" }));
+    }
+
+    #[test]
+    fn test_hotpaths_rank_by_fan_in_times_fan_out() {
+        let file = serde_json::from_value(serde_json::json!({
+            "path": "src/server.ts",
+            "lines": 10,
+            "language": "typescript",
+            "git": {
+                "last_commit": "abc",
+                "last_author": "dev",
+                "last_modified": "2025-01-01T00:00:00Z",
+                "commit_count": 7,
+                "contributors": ["dev"]
+            }
+        }))
+        .unwrap();
+        let cache = CacheBuilder::new("test", ".")
+            .add_file(file)
+            .add_symbol(symbol("handle", "src/server.ts"))
+            .add_call_edge("main", vec!["handle".into(), "parse".into()])
+            .add_call_edge("cli", vec!["handle".into(), "parse".into()])
+            .add_call_edge("handle", vec!["parse".into(), "log".into()])
+            .add_call_edge("parse", vec!["log".into()])
+            .build();
+        let q = Query::new(&cache);
+
+        let ranked: Vec<_> = q
+            .hotpaths(false)
+            .iter()
+            .map(|h| (h.symbol, h.fan_in, h.fan_out, h.score))
+            .collect();
+        // main/cli call but are never called; log is never a caller
+        assert_eq!(ranked, vec![("handle", 2, 2, 4), ("parse", 3, 1, 3)]);
+
+        let churned = q.hotpaths(true);
+        assert_eq!(churned[0].churn, Some(7));
+        assert_eq!(churned[0].score, 28);
+        // No git data for the file: churn counts as one commit
+        assert_eq!((churned[1].symbol, churned[1].churn), ("parse", Some(1)));
+
+        // debug-context keeps the plain caller-count ranking
+        assert_eq!(q.most_called().next(), Some("parse"));
+    }
+
+    #[test]
+    fn test_callees_with_heuristic() {
+        let guess = |to: &str, line: usize| CallEdge {
+            from: "dispatch".to_string(),
+            to: to.to_string(),
+            file: "src/app.ts".to_string(),
+            line,
+        };
+        let cache = CacheBuilder::new("test", ".")
+            .add_call_edge("dispatch", vec!["log".into()])
+            .add_heuristic_edge(guess("handleLogin", 9))
+            .add_heuristic_edge(guess("handleLogin", 3))
+            // Already a precise callee: dropped
+            .add_heuristic_edge(guess("log", 5))
+            .build();
+        let q = Query::new(&cache);
+        assert_eq!(q.callees("dispatch"), vec!["log"]);
+
+        let entries: Vec<_> = q
+            .callees_with_heuristic("dispatch")
+            .into_iter()
+            .map(|e| (e.callee, e.heuristic, e.line))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("log".to_string(), false, None),
+                ("handleLogin".to_string(), true, Some(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_call_paths() {
+        let cache = CacheBuilder::new("test", ".")
+            // One edge per call site, as the indexer reports them
+            .add_call_edge("main", vec!["route".into()])
+            .add_call_edge("main", vec!["boot".into(), "route".into()])
+            .add_call_edge("boot", vec!["save".into()])
+            .add_call_edge("route", vec!["handle".into(), "main".into()])
+            .add_call_edge("handle", vec!["save".into()])
+            .add_call_edge("orphan", vec!["log".into()])
+            .build();
+        let q = Query::new(&cache);
+        assert_eq!(q.callees("main"), vec!["route", "boot"]);
+        assert_eq!(q.callers("route"), vec!["main"]);
+
+        assert_eq!(
+            q.call_paths("main", "save", 1),
+            vec![vec!["main", "boot", "save"]]
+        );
+        assert_eq!(
+            q.call_paths("main", "save", 5),
+            vec![
+                vec!["main", "boot", "save"],
+                vec!["main", "route", "handle", "save"],
+            ]
+        );
+        assert_eq!(q.call_paths("main", "main", 1), vec![vec!["main"]]);
+        assert!(q.call_paths("main", "log", 1).is_empty());
+        assert!(q.call_paths("save", "main", 1).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_patterns() {
+        assert!(SymbolMatcher::glob("[").is_err());
+        assert!(SymbolMatcher::regex("(").is_err());
+    }
 }
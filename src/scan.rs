@@ -5,12 +5,21 @@
 
 use std::collections::HashMap;
 use std::path::Path;
+
+use serde::Serialize;
 use walkdir::WalkDir;
 
+use crate::cache::Language;
+
 /// Detected project information
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ProjectScan {
     pub languages: Vec<DetectedLanguage>,
+    /// Files scanned, in any language
+    pub total_files: usize,
+    /// Size of all scanned files in bytes
+    pub total_bytes: u64,
     pub has_package_json: bool,
     pub has_cargo_toml: bool,
     pub has_pyproject_toml: bool,
@@ -18,36 +27,50 @@ pub struct ProjectScan {
     // pub mcp_available: bool,  // TODO: Detect MCP server availability
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DetectedLanguage {
     pub name: &'static str,
+    #[serde(skip)]
+    pub language: Language,
     pub patterns: Vec<&'static str>,
     pub file_count: usize,
+    /// Size of this language's files in bytes
+    pub bytes: u64,
 }
 
 /// Scan project directory to detect languages and configuration
 pub fn scan_project<P: AsRef<Path>>(root: P) -> ProjectScan {
     let root = root.as_ref();
-    let mut ext_counts: HashMap<String, usize> = HashMap::new();
+    let mut ext_counts: HashMap<String, (usize, u64)> = HashMap::new();
     let mut scan = ProjectScan::default();
 
+    // Skip common non-source directories and everything under them
+    let skipped = |entry: &walkdir::DirEntry| {
+        entry.depth() > 0
+            && entry.file_type().is_dir()
+            && matches!(
+                entry.file_name().to_str(),
+                Some(
+                    "node_modules"
+                        | "target"
+                        | "dist"
+                        | "build"
+                        | ".git"
+                        | "vendor"
+                        | "__pycache__"
+                )
+            )
+    };
+
     for entry in WalkDir::new(root)
         .max_depth(10)
         .into_iter()
+        .filter_entry(|e| !skipped(e))
         .filter_map(|e| e.ok())
     {
         let path = entry.path();
 
-        // Skip common non-source directories
-        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-            if matches!(
-                name,
-                "node_modules" | "target" | "dist" | "build" | ".git" | "vendor" | "__pycache__"
-            ) {
-                continue;
-            }
-        }
-
         // Check for project files
         if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
             match name {
@@ -59,44 +82,75 @@ pub fn scan_project<P: AsRef<Path>>(root: P) -> ProjectScan {
             }
         }
 
-        // Count file extensions
-        if path.is_file() {
+        // Count files and sizes per extension
+        if entry.file_type().is_file() {
+            let bytes = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            scan.total_files += 1;
+            scan.total_bytes += bytes;
             if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-                let ext = ext.to_lowercase();
-                *ext_counts.entry(ext).or_insert(0) += 1;
+                let counts = ext_counts.entry(ext.to_lowercase()).or_default();
+                counts.0 += 1;
+                counts.1 += bytes;
             }
         }
     }
 
     // Map extensions to languages
-    let lang_mappings: [(&str, &[&str], &[&str]); 6] = [
-        ("TypeScript", &["ts", "tsx"], &["**/*.ts", "**/*.tsx"]),
+    let lang_mappings: [(&str, Language, &[&str], &[&str]); 9] = [
+        (
+            "TypeScript",
+            Language::Typescript,
+            &["ts", "tsx"],
+            &["**/*.ts", "**/*.tsx"],
+        ),
         (
             "JavaScript",
+            Language::Javascript,
             &["js", "jsx", "mjs"],
             &["**/*.js", "**/*.jsx", "**/*.mjs"],
         ),
-        ("Rust", &["rs"], &["**/*.rs"]),
-        ("Python", &["py"], &["**/*.py"]),
-        ("Go", &["go"], &["**/*.go"]),
-        ("Java", &["java"], &["**/*.java"]),
+        ("Rust", Language::Rust, &["rs"], &["**/*.rs"]),
+        ("Python", Language::Python, &["py"], &["**/*.py"]),
+        ("Go", Language::Go, &["go"], &["**/*.go"]),
+        ("Java", Language::Java, &["java"], &["**/*.java"]),
+        (
+            "Ruby",
+            Language::Ruby,
+            &["rb", "rake"],
+            &["**/*.rb", "**/*.rake"],
+        ),
+        ("Swift", Language::Swift, &["swift"], &["**/*.swift"]),
+        (
+            "Kotlin",
+            Language::Kotlin,
+            &["kt", "kts"],
+            &["**/*.kt", "**/*.kts"],
+        ),
     ];
 
-    for (name, exts, patterns) in lang_mappings {
-        let count: usize = exts.iter().filter_map(|e| ext_counts.get(*e)).sum();
+    for (name, language, exts, patterns) in lang_mappings {
+        let (count, bytes) = exts
+            .iter()
+            .filter_map(|e| ext_counts.get(*e))
+            .fold((0, 0), |(c, b), (count, bytes)| (c + count, b + bytes));
 
         if count > 0 {
             scan.languages.push(DetectedLanguage {
                 name,
+                language,
                 patterns: patterns.to_vec(),
                 file_count: count,
+                bytes,
             });
         }
     }
 
-    // Sort by file count descending
-    scan.languages
-        .sort_by(|a, b| b.file_count.cmp(&a.file_count));
+    // Sort by file count descending, ties by name for stable output
+    scan.languages.sort_by(|a, b| {
+        b.file_count
+            .cmp(&a.file_count)
+            .then_with(|| a.name.cmp(b.name))
+    });
 
     // TODO: MCP detection (commented out for future implementation)
     // scan.mcp_available = detect_mcp_server();
@@ -141,7 +141,11 @@ pub fn validate_vars(json: &str) -> Result<()> {
     }
 
     // Also validate with serde for type checking
-    let _: crate::vars::VarsFile = serde_json::from_value(value)?;
+    let vars_file: crate::vars::VarsFile = serde_json::from_value(value)?;
+
+    // Semantic validation: refs must not form inheritance cycles
+    crate::vars::VarResolver::new(vars_file).validate()?;
+
     Ok(())
 }
 
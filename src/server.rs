@@ -0,0 +1,617 @@
+//! @acp:module "Cache Server"
+//! @acp:summary "HTTP endpoints and Server-Sent Events for live cache updates"
+//! @acp:domain cli
+//! @acp:layer service
+//!
+//! Backs `acp daemon start --watch`. Four endpoints:
+//!
+//! - `GET /health` - liveness and the latest event id
+//! - `GET /status` - which cache is served and whether the tree has moved on
+//! - `GET /cache` - the current cache as JSON
+//! - `GET /events` - a Server-Sent Events stream with one `cache` event per
+//!   rebuild
+//!
+//! Event ids only ever increase, and the server keeps a short history, so a
+//! client that reconnects with `Last-Event-ID` receives whatever it missed.
+//!
+//! The server is unauthenticated, so it sends no CORS headers: browsers keep
+//! other sites' pages from reading the cache.
+
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, watch};
+
+use crate::cache::{stale_source_files, Cache};
+use crate::error::Result;
+
+/// Events kept for clients that reconnect with `Last-Event-ID`
+const HISTORY: usize = 64;
+
+/// Comment sent on idle streams so proxies and clients notice dead peers
+const KEEPALIVE: Duration = Duration::from_secs(15);
+
+/// Reconnect delay suggested to clients, in milliseconds
+const RETRY_MS: u64 = 3000;
+
+/// Largest request head (request line and headers) read from a client
+const MAX_HEAD_BYTES: u64 = 16 * 1024;
+
+/// Time a client gets to send its request head
+const HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// @acp:summary "Notification that the cache was rebuilt"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CacheEvent {
+    /// Monotonic event id
+    pub id: u64,
+    pub generated_at: DateTime<Utc>,
+    pub files: usize,
+    pub symbols: usize,
+    /// Source files whose changes triggered the rebuild (empty for the
+    /// initial index)
+    pub changed: Vec<String>,
+}
+
+impl CacheEvent {
+    /// @acp:summary "Encode as one Server-Sent Events message"
+    pub fn to_sse(&self) -> String {
+        format!(
+            "id: {}\nevent: cache\ndata: {}\n\n",
+            self.id,
+            serde_json::to_string(self).unwrap_or_default()
+        )
+    }
+}
+
+/// @acp:summary "The cache a daemon serves and how fresh it is"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheStatus {
+    /// Cache file the daemon writes, when it told the hub
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_path: Option<String>,
+    /// When the served cache was indexed
+    pub generated_at: DateTime<Utc>,
+    pub files: usize,
+    pub symbols: usize,
+    /// Source files added, removed or modified since indexing
+    pub out_of_date: usize,
+    pub stale: bool,
+    #[serde(default)]
+    pub last_event_id: Option<u64>,
+}
+
+/// What `/status` needs from the latest cache, without keeping all of it
+struct Snapshot {
+    root: String,
+    generated_at: DateTime<Utc>,
+    files: usize,
+    symbols: usize,
+    source_files: HashMap<String, DateTime<Utc>>,
+}
+
+struct HubState {
+    next_id: u64,
+    cache_json: Option<Arc<String>>,
+    snapshot: Option<Arc<Snapshot>>,
+    history: VecDeque<CacheEvent>,
+}
+
+/// @acp:summary "Latest cache plus a broadcast of rebuild events"
+pub struct CacheHub {
+    state: Mutex<HubState>,
+    tx: broadcast::Sender<CacheEvent>,
+    cache_path: Option<PathBuf>,
+}
+
+impl CacheHub {
+    /// Create a hub whose first event gets `first_id`
+    ///
+    /// The daemon seeds this from the start time in milliseconds, so ids
+    /// keep increasing across restarts too.
+    pub fn new(first_id: u64) -> Self {
+        let (tx, _) = broadcast::channel(HISTORY);
+        Self {
+            state: Mutex::new(HubState {
+                next_id: first_id,
+                cache_json: None,
+                snapshot: None,
+                history: VecDeque::with_capacity(HISTORY),
+            }),
+            tx,
+            cache_path: None,
+        }
+    }
+
+    /// Record the cache file being served, for `/status`
+    pub fn with_cache_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cache_path = Some(path.into());
+        self
+    }
+
+    /// @acp:summary "Store a rebuilt cache and notify subscribers"
+    pub fn publish(&self, cache: &Cache, changed: Vec<String>) -> Result<CacheEvent> {
+        let json = Arc::new(serde_json::to_string_pretty(cache)?);
+        let snapshot = Arc::new(Snapshot {
+            root: cache.project.root.clone(),
+            generated_at: cache.generated_at,
+            files: cache.stats.files,
+            symbols: cache.stats.symbols,
+            source_files: cache.source_files.clone(),
+        });
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        let event = CacheEvent {
+            id: state.next_id,
+            generated_at: Utc::now(),
+            files: cache.stats.files,
+            symbols: cache.stats.symbols,
+            changed,
+        };
+        state.next_id += 1;
+        state.cache_json = Some(json);
+        state.snapshot = Some(snapshot);
+        if state.history.len() == HISTORY {
+            state.history.pop_front();
+        }
+        state.history.push_back(event.clone());
+
+        // Sent under the lock so a concurrent subscribe sees each event
+        // exactly once, either in its replay or on its receiver
+        let _ = self.tx.send(event.clone());
+        Ok(event)
+    }
+
+    /// Current cache JSON, once the first index has finished
+    pub fn cache_json(&self) -> Option<Arc<String>> {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .cache_json
+            .clone()
+    }
+
+    /// @acp:summary "Describe the served cache, re-statting the tree for staleness"
+    ///
+    /// Stat-only, like [`Cache::is_stale`]; `None` until the first index
+    /// has finished.
+    pub fn status(&self) -> Option<CacheStatus> {
+        let (snapshot, last_event_id) = {
+            let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            (state.snapshot.clone()?, state.history.back().map(|e| e.id))
+        };
+        let report = stale_source_files(&snapshot.root, &snapshot.source_files);
+        Some(CacheStatus {
+            cache_path: self
+                .cache_path
+                .as_ref()
+                .map(|p| p.to_string_lossy().to_string()),
+            generated_at: snapshot.generated_at,
+            files: snapshot.files,
+            symbols: snapshot.symbols,
+            out_of_date: report.out_of_date(),
+            stale: report.is_stale(),
+            last_event_id,
+        })
+    }
+
+    /// Id of the most recent event
+    pub fn last_id(&self) -> Option<u64> {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .history
+            .back()
+            .map(|e| e.id)
+    }
+
+    /// @acp:summary "Subscribe, returning the events a client has not seen yet"
+    ///
+    /// New clients get the latest event so they learn the current state;
+    /// reconnecting clients get every retained event after `last_event_id`.
+    pub fn subscribe(
+        &self,
+        last_event_id: Option<u64>,
+    ) -> (Vec<CacheEvent>, broadcast::Receiver<CacheEvent>) {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let replay = match last_event_id {
+            Some(last) => state
+                .history
+                .iter()
+                .filter(|e| e.id > last)
+                .cloned()
+                .collect(),
+            None => state.history.back().cloned().into_iter().collect(),
+        };
+        (replay, self.tx.subscribe())
+    }
+}
+
+/// The parts of an HTTP request the server looks at
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    last_event_id: Option<u64>,
+}
+
+/// Parse a request line and headers (without the blank terminator line)
+fn parse_request(head: &[String]) -> Option<Request> {
+    let mut parts = head.first()?.split_whitespace();
+    let method = parts.next()?.to_string();
+    let target = parts.next()?;
+    let path = target.split('?').next().unwrap_or(target).to_string();
+
+    let last_event_id = head[1..].iter().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("last-event-id")
+            .then(|| value.trim().parse().ok())
+            .flatten()
+    });
+
+    Some(Request {
+        method,
+        path,
+        last_event_id,
+    })
+}
+
+/// @acp:summary "Accept connections until shutdown is signalled"
+///
+/// Open event streams are closed when `shutdown` changes, so awaiting this
+/// function's task is enough for a clean exit.
+pub async fn serve(listener: TcpListener, hub: Arc<CacheHub>, mut shutdown: watch::Receiver<bool>) {
+    loop {
+        tokio::select! {
+            _ = shutdown.changed() => break,
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    let hub = hub.clone();
+                    let shutdown = shutdown.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = handle_connection(stream, hub, shutdown).await {
+                            tracing::debug!("connection closed: {}", e);
+                        }
+                    });
+                }
+                Err(e) => tracing::warn!("accept failed: {}", e),
+            },
+        }
+    }
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    hub: Arc<CacheHub>,
+    shutdown: watch::Receiver<bool>,
+) -> std::io::Result<()> {
+    let (read, mut write) = stream.into_split();
+    let head = match tokio::time::timeout(HEAD_TIMEOUT, read_head(read)).await {
+        Ok(head) => head?,
+        Err(_) => return Ok(()),
+    };
+    let Some(head) = head else {
+        return respond(
+            &mut write,
+            "431 Request Header Fields Too Large",
+            "text/plain",
+            "request head too large",
+        )
+        .await;
+    };
+
+    let Some(request) = parse_request(&head) else {
+        return respond(&mut write, "400 Bad Request", "text/plain", "bad request").await;
+    };
+
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/health") => {
+            let body = serde_json::json!({
+                "status": "ok",
+                "watch": true,
+                "last_event_id": hub.last_id(),
+            });
+            respond(&mut write, "200 OK", "application/json", &body.to_string()).await
+        }
+        ("GET", "/status") => {
+            // Re-statting the tree blocks, so keep it off the async workers
+            let status = tokio::task::spawn_blocking({
+                let hub = hub.clone();
+                move || hub.status()
+            })
+            .await
+            .ok()
+            .flatten();
+            match status {
+                Some(status) => {
+                    let body = serde_json::to_string(&status).unwrap_or_default();
+                    respond(&mut write, "200 OK", "application/json", &body).await
+                }
+                None => {
+                    respond(
+                        &mut write,
+                        "503 Service Unavailable",
+                        "text/plain",
+                        "initial index in progress",
+                    )
+                    .await
+                }
+            }
+        }
+        ("GET", "/cache") => match hub.cache_json() {
+            Some(json) => respond(&mut write, "200 OK", "application/json", &json).await,
+            None => {
+                respond(
+                    &mut write,
+                    "503 Service Unavailable",
+                    "text/plain",
+                    "initial index in progress",
+                )
+                .await
+            }
+        },
+        ("GET", "/events") => {
+            stream_events(&mut write, &hub, request.last_event_id, shutdown).await
+        }
+        (_, "/health" | "/status" | "/cache" | "/events") => {
+            respond(
+                &mut write,
+                "405 Method Not Allowed",
+                "text/plain",
+                "method not allowed",
+            )
+            .await
+        }
+        _ => respond(&mut write, "404 Not Found", "text/plain", "not found").await,
+    }
+}
+
+/// @acp:summary "Read request lines up to the blank line ending the head"
+///
+/// Returns `None` when the head does not end within [`MAX_HEAD_BYTES`].
+async fn read_head<R: AsyncRead + Unpin>(read: R) -> std::io::Result<Option<Vec<String>>> {
+    let mut reader = BufReader::new(read.take(MAX_HEAD_BYTES));
+    let mut head = Vec::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 {
+            // A closed connection ends the head; running out of budget does not
+            let exhausted = reader.get_ref().limit() == 0;
+            return Ok((!exhausted).then_some(head));
+        }
+        if !line.ends_with('\n') && reader.get_ref().limit() == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            return Ok(Some(head));
+        }
+        head.push(line.to_string());
+    }
+}
+
+async fn respond<W: AsyncWrite + Unpin>(
+    write: &mut W,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    write.write_all(response.as_bytes()).await?;
+    write.shutdown().await
+}
+
+async fn stream_events<W: AsyncWrite + Unpin>(
+    write: &mut W,
+    hub: &CacheHub,
+    last_event_id: Option<u64>,
+    mut shutdown: watch::Receiver<bool>,
+) -> std::io::Result<()> {
+    write
+        .write_all(
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n",
+        )
+        .await?;
+    write
+        .write_all(format!("retry: {}\n\n", RETRY_MS).as_bytes())
+        .await?;
+
+    let (replay, mut rx) = hub.subscribe(last_event_id);
+    for event in replay {
+        write.write_all(event.to_sse().as_bytes()).await?;
+    }
+    write.flush().await?;
+
+    let mut keepalive = tokio::time::interval(KEEPALIVE);
+    keepalive.tick().await;
+    loop {
+        tokio::select! {
+            _ = shutdown.changed() => break,
+            _ = keepalive.tick() => write.write_all(b": keepalive\n\n").await?,
+            received = rx.recv() => match received {
+                Ok(event) => write.write_all(event.to_sse().as_bytes()).await?,
+                // A slow client skipped events; the next one still carries
+                // the current state, and /cache has the full cache
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        }
+        write.flush().await?;
+    }
+
+    write.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheBuilder;
+    use tokio::io::AsyncReadExt;
+
+    fn cache() -> Cache {
+        CacheBuilder::new("test", ".").build()
+    }
+
+    #[test]
+    fn test_event_ids_and_replay() {
+        let hub = CacheHub::new(100);
+        assert!(hub.cache_json().is_none());
+
+        let first = hub.publish(&cache(), vec![]).unwrap();
+        let second = hub.publish(&cache(), vec!["src/a.ts".to_string()]).unwrap();
+        assert_eq!((first.id, second.id), (100, 101));
+        assert_eq!(hub.last_id(), Some(101));
+
+        // New subscribers learn the current state
+        let (replay, _) = hub.subscribe(None);
+        assert_eq!(replay, vec![second.clone()]);
+        // Reconnecting subscribers get what they missed
+        let (replay, _) = hub.subscribe(Some(99));
+        assert_eq!(replay, vec![first, second]);
+        let (replay, _) = hub.subscribe(Some(101));
+        assert!(replay.is_empty());
+    }
+
+    #[test]
+    fn test_status_reports_staleness() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("a.ts"), "export {};").unwrap();
+        let modified = std::fs::metadata(root.join("a.ts"))
+            .unwrap()
+            .modified()
+            .unwrap();
+        let mut cache = CacheBuilder::new("test", &root.to_string_lossy()).build();
+        cache
+            .source_files
+            .insert("a.ts".to_string(), DateTime::<Utc>::from(modified));
+        cache.stats.files = 1;
+
+        let hub = CacheHub::new(1).with_cache_path(".acp/acp.cache.json");
+        assert!(hub.status().is_none());
+        hub.publish(&cache, vec![]).unwrap();
+
+        let status = hub.status().unwrap();
+        assert_eq!(status.cache_path.as_deref(), Some(".acp/acp.cache.json"));
+        assert_eq!((status.files, status.out_of_date), (1, 0));
+        assert!(!status.stale);
+        assert_eq!(status.last_event_id, Some(1));
+
+        std::fs::write(root.join("b.ts"), "export {};").unwrap();
+        let status = hub.status().unwrap();
+        assert!(status.stale);
+        assert_eq!(status.out_of_date, 1);
+    }
+
+    #[test]
+    fn test_sse_format() {
+        let event = CacheEvent {
+            id: 7,
+            generated_at: Utc::now(),
+            files: 1,
+            symbols: 2,
+            changed: vec![],
+        };
+        let sse = event.to_sse();
+        assert!(sse.starts_with("id: 7\nevent: cache\ndata: {"));
+        assert!(sse.ends_with("}\n\n"));
+    }
+
+    #[test]
+    fn test_parse_request() {
+        let head = vec![
+            "GET /events?x=1 HTTP/1.1".to_string(),
+            "Host: localhost".to_string(),
+            "Last-Event-ID: 42".to_string(),
+        ];
+        assert_eq!(
+            parse_request(&head),
+            Some(Request {
+                method: "GET".to_string(),
+                path: "/events".to_string(),
+                last_event_id: Some(42),
+            })
+        );
+        assert!(parse_request(&[]).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_head_is_bounded() {
+        let head = read_head(&b"GET /health HTTP/1.1\r\nHost: x\r\n\r\nignored"[..])
+            .await
+            .unwrap();
+        assert_eq!(
+            head,
+            Some(vec![
+                "GET /health HTTP/1.1".to_string(),
+                "Host: x".to_string()
+            ])
+        );
+
+        let mut flood = b"GET / HTTP/1.1\r\n".to_vec();
+        flood.extend(b"X-Filler: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\r\n".repeat(1000));
+        assert_eq!(read_head(&flood[..]).await.unwrap(), None);
+        let long_line = vec![b'a'; MAX_HEAD_BYTES as usize * 2];
+        assert_eq!(read_head(&long_line[..]).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_events_stream_and_shutdown() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let hub = Arc::new(CacheHub::new(1));
+        hub.publish(&cache(), vec![]).unwrap();
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let server = tokio::spawn(serve(listener, hub.clone(), shutdown_rx));
+
+        let mut cache_conn = TcpStream::connect(addr).await.unwrap();
+        cache_conn
+            .write_all(b"GET /cache HTTP/1.1\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        cache_conn.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("\"project\""));
+        // Other sites' pages must not be able to read the cache
+        assert!(!response.contains("Access-Control-Allow-Origin"));
+
+        let mut events = TcpStream::connect(addr).await.unwrap();
+        events
+            .write_all(b"GET /events HTTP/1.1\r\nLast-Event-ID: 1\r\n\r\n")
+            .await
+            .unwrap();
+        // Wait for the headers so the subscription exists before publishing
+        let mut buf = [0u8; 512];
+        let n = events.read(&mut buf).await.unwrap();
+        assert!(String::from_utf8_lossy(&buf[..n]).contains("text/event-stream"));
+
+        hub.publish(&cache(), vec!["src/a.ts".to_string()]).unwrap();
+        let mut received = String::new();
+        while !received.contains("id: 2\n") {
+            let n = events.read(&mut buf).await.unwrap();
+            assert!(n > 0, "stream ended early: {}", received);
+            received.push_str(&String::from_utf8_lossy(&buf[..n]));
+        }
+        assert!(received.contains("src/a.ts"));
+
+        // Shutdown ends the stream, so reading to EOF terminates
+        shutdown_tx.send(true).unwrap();
+        let mut rest = String::new();
+        events.read_to_string(&mut rest).await.unwrap();
+        server.await.unwrap();
+    }
+}
@@ -0,0 +1,182 @@
+//! @acp:module "Custom Tool Adapter"
+//! @acp:summary "Config-defined sync targets rendered from a template"
+//! @acp:domain cli
+//! @acp:layer service
+//!
+//! Teams using tools without a built-in adapter declare them under
+//! `sync.custom` in `.acp.config.json`. The template supports these
+//! placeholders:
+//!
+//! - `{{name}}` - the adapter's name
+//! - `{{output_path}}` - the file being written
+//! - `{{context}}` - the standard ACP context (Markdown, CLI flavour)
+
+use std::path::{Component, Path};
+
+use super::content::generate_bootstrap_markdown;
+use super::tool::Tool;
+use crate::config::CustomAdapterConfig;
+use crate::error::{AcpError, Result};
+
+const DEFAULT_START_MARKER: &str = "<!-- BEGIN ACP GENERATED CONTENT - DO NOT EDIT -->";
+const DEFAULT_END_MARKER: &str = "<!-- END ACP GENERATED CONTENT -->";
+
+/// @acp:summary "A validated user-defined tool adapter"
+#[derive(Debug, Clone)]
+pub struct CustomAdapter {
+    config: CustomAdapterConfig,
+}
+
+impl CustomAdapter {
+    /// @acp:summary "Validate a `sync.custom` entry"
+    ///
+    /// Rejects an empty name or output path, output paths outside the
+    /// project, markers that are empty, identical or contain one another,
+    /// and templates with unknown or unterminated placeholders.
+    pub fn new(config: CustomAdapterConfig) -> Result<Self> {
+        let invalid = |msg: String| AcpError::Config(format!("sync.custom: {}", msg));
+
+        if config.name.trim().is_empty() {
+            return Err(invalid("adapter name is empty".into()));
+        }
+        let name = config.name.clone();
+        if config.output_path.trim().is_empty() {
+            return Err(invalid(format!("{}: outputPath is empty", name)));
+        }
+        let escapes = Path::new(&config.output_path)
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir));
+        if escapes {
+            return Err(invalid(format!(
+                "{}: outputPath must be relative to the project root",
+                name
+            )));
+        }
+
+        if let Some((start, end)) = &config.section_markers {
+            if start.trim().is_empty() || end.trim().is_empty() {
+                return Err(invalid(format!("{}: section markers are empty", name)));
+            }
+            if start.contains(end.as_str()) || end.contains(start.as_str()) {
+                return Err(invalid(format!(
+                    "{}: section markers conflict ({:?} and {:?})",
+                    name, start, end
+                )));
+            }
+        }
+
+        let adapter = Self { config };
+        adapter
+            .render()
+            .map_err(|e| invalid(format!("{}: {}", name, e)))?;
+        Ok(adapter)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    pub fn output_path(&self) -> &str {
+        &self.config.output_path
+    }
+
+    /// Start and end markers for the generated section
+    pub fn section_markers(&self) -> (&str, &str) {
+        self.config.section_markers.as_ref().map_or(
+            (DEFAULT_START_MARKER, DEFAULT_END_MARKER),
+            |(start, end)| (start.as_str(), end.as_str()),
+        )
+    }
+
+    /// @acp:summary "True when the output file or any detect glob exists"
+    pub fn detect(&self, project_root: &Path) -> bool {
+        if project_root.join(self.output_path()).exists() {
+            return true;
+        }
+        self.config.detect_globs.iter().any(|pattern| {
+            let pattern = project_root.join(pattern);
+            glob::glob(&pattern.to_string_lossy())
+                .map(|mut paths| paths.any(|p| p.is_ok()))
+                .unwrap_or(false)
+        })
+    }
+
+    /// @acp:summary "Render the template"
+    pub fn generate(&self) -> Result<String> {
+        self.render()
+            .map_err(|e| AcpError::Config(format!("sync.custom: {}: {}", self.name(), e)))
+    }
+
+    fn render(&self) -> std::result::Result<String, String> {
+        let template = &self.config.template;
+        let mut out = String::with_capacity(template.len());
+        let mut rest = template.as_str();
+
+        while let Some(open) = rest.find("{{") {
+            out.push_str(&rest[..open]);
+            let after = &rest[open + 2..];
+            let close = after
+                .find("}}")
+                .ok_or_else(|| "unterminated {{ in template".to_string())?;
+            match after[..close].trim() {
+                "name" => out.push_str(self.name()),
+                "output_path" => out.push_str(self.output_path()),
+                "context" => out.push_str(&generate_bootstrap_markdown(Tool::Generic)),
+                other => return Err(format!("unknown placeholder {{{{{}}}}}", other)),
+            }
+            rest = &after[close + 2..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(output_path: &str, template: &str) -> CustomAdapterConfig {
+        CustomAdapterConfig {
+            name: "Zed".to_string(),
+            output_path: output_path.to_string(),
+            detect_globs: vec![".zed/*.json".to_string()],
+            section_markers: None,
+            template: template.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_and_detect() {
+        let adapter = CustomAdapter::new(config(
+            ".zed/rules.md",
+            "# {{ name }} ({{output_path}})\n{{context}}",
+        ))
+        .unwrap();
+        let content = adapter.generate().unwrap();
+        assert!(content.starts_with("# Zed (.zed/rules.md)\n"));
+        assert!(content.contains("# ACP Context"));
+        assert_eq!(adapter.section_markers().0, DEFAULT_START_MARKER);
+
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!adapter.detect(dir.path()));
+        std::fs::create_dir_all(dir.path().join(".zed")).unwrap();
+        std::fs::write(dir.path().join(".zed/settings.json"), "{}").unwrap();
+        assert!(adapter.detect(dir.path()));
+    }
+
+    #[test]
+    fn test_rejects_invalid_config() {
+        assert!(CustomAdapter::new(config("", "{{context}}")).is_err());
+        assert!(CustomAdapter::new(config("../outside.md", "{{context}}")).is_err());
+        assert!(CustomAdapter::new(config("rules.md", "{{unknown}}")).is_err());
+        assert!(CustomAdapter::new(config("rules.md", "{{context")).is_err());
+
+        let mut conflicting = config("rules.md", "{{context}}");
+        conflicting.section_markers = Some(("# ACP".to_string(), "# ACP END".to_string()));
+        assert!(CustomAdapter::new(conflicting).is_err());
+
+        let mut same = config("rules.md", "{{context}}");
+        same.section_markers = Some(("# ACP".to_string(), "# ACP".to_string()));
+        assert!(CustomAdapter::new(same).is_err());
+    }
+}
@@ -3,8 +3,22 @@
 //! @acp:domain cli
 //! @acp:layer service
 
+use sha2::{Digest, Sha256};
+
 use super::tool::MergeStrategy;
 
+/// Key of the hidden line recording the fingerprint of generated content
+const FINGERPRINT_KEY: &str = "acp-fingerprint:";
+
+/// @acp:summary "Outcome of a merge that refuses to overwrite hand edits"
+#[derive(Debug, PartialEq, Eq)]
+pub enum PreservedMerge {
+    /// Section replaced (or added); the new file content
+    Merged(String),
+    /// Section content no longer matches its recorded fingerprint
+    Edited,
+}
+
 /// Merge generated content with existing file content
 pub fn merge_content(
     strategy: MergeStrategy,
@@ -27,7 +41,82 @@ pub fn merge_content(
             // Fall back to section merge for safety
             merge_with_markers(existing, generated, start_marker, end_marker)
         }
+
+        MergeStrategy::PreserveEdits => {
+            match merge_preserving_edits(existing, generated, start_marker, end_marker) {
+                PreservedMerge::Merged(merged) => merged,
+                PreservedMerge::Edited => existing.to_string(),
+            }
+        }
+    }
+}
+
+/// @acp:summary "Section merge that leaves hand-edited sections alone"
+///
+/// The generated section starts with a hidden fingerprint line. On the next
+/// merge the section body is hashed again; if it no longer matches, someone
+/// edited it and [`PreservedMerge::Edited`] is returned instead. Sections
+/// without a fingerprint (written before this strategy) are replaced.
+pub fn merge_preserving_edits(
+    existing: &str,
+    generated: &str,
+    start_marker: &str,
+    end_marker: &str,
+) -> PreservedMerge {
+    if let Some(body) = section_body(existing, start_marker, end_marker) {
+        let recorded = body.lines().find_map(parse_fingerprint);
+        if let Some(recorded) = recorded {
+            let content: Vec<&str> = body
+                .lines()
+                .filter(|line| parse_fingerprint(line).is_none())
+                .collect();
+            if recorded != fingerprint(&content.join("\n")) {
+                return PreservedMerge::Edited;
+            }
+        }
     }
+
+    let section = format!(
+        "{}\n{}",
+        fingerprint_line(start_marker, &fingerprint(generated)),
+        generated.trim_matches('\n')
+    );
+    PreservedMerge::Merged(merge_with_markers(
+        existing,
+        &section,
+        start_marker,
+        end_marker,
+    ))
+}
+
+/// Content between the markers, without the newlines framing it
+fn section_body<'a>(existing: &'a str, start_marker: &str, end_marker: &str) -> Option<&'a str> {
+    let start = existing.find(start_marker)? + start_marker.len();
+    let end = existing.find(end_marker)?;
+    (start <= end).then(|| existing[start..end].trim_matches(['\r', '\n']))
+}
+
+/// Short hash of section content, ignoring line endings and outer whitespace
+fn fingerprint(content: &str) -> String {
+    let normalized = content.replace("\r\n", "\n");
+    let hash = format!("{:x}", Sha256::digest(normalized.trim().as_bytes()));
+    hash[..16].to_string()
+}
+
+/// Fingerprint line in the comment syntax of the start marker
+fn fingerprint_line(start_marker: &str, hash: &str) -> String {
+    if start_marker.starts_with("<!--") {
+        format!("<!-- {} {} -->", FINGERPRINT_KEY, hash)
+    } else {
+        let prefix = start_marker.split_whitespace().next().unwrap_or("#");
+        format!("{} {} {}", prefix, FINGERPRINT_KEY, hash)
+    }
+}
+
+/// The hash recorded on a fingerprint line, if it is one
+fn parse_fingerprint(line: &str) -> Option<&str> {
+    let (_, rest) = line.split_once(FINGERPRINT_KEY)?;
+    rest.split_whitespace().next()
 }
 
 /// Merge content using section markers
@@ -151,6 +240,58 @@ mod tests {
         assert!(!result.contains("Old ACP content"));
     }
 
+    #[test]
+    fn test_preserve_edits_skips_edited_section() {
+        let generated = "# Rules\n\nUse the cache.";
+        let merged = match merge_preserving_edits("# Notes\n", generated, START, END) {
+            PreservedMerge::Merged(merged) => merged,
+            PreservedMerge::Edited => panic!("new section reported as edited"),
+        };
+        assert!(merged.contains("<!-- acp-fingerprint: "));
+
+        // Untouched (even with CRLF line endings): regenerated in place
+        let crlf = merged.replace('\n', "\r\n");
+        let PreservedMerge::Merged(updated) =
+            merge_preserving_edits(&crlf, "# Rules\n\nUse the index.", START, END)
+        else {
+            panic!("untouched section reported as edited");
+        };
+        assert!(updated.contains("Use the index."));
+        assert!(updated.starts_with("# Notes"));
+
+        // Edited: left alone
+        let edited = updated.replace("Use the index.", "Use the index. Ask first!");
+        assert_eq!(
+            merge_preserving_edits(&edited, generated, START, END),
+            PreservedMerge::Edited
+        );
+        assert_eq!(
+            merge_content(MergeStrategy::PreserveEdits, &edited, generated, START, END),
+            edited
+        );
+
+        // Sections from before fingerprints were recorded are replaced
+        let legacy = format!("{}\nOld content\n{}", START, END);
+        assert!(matches!(
+            merge_preserving_edits(&legacy, generated, START, END),
+            PreservedMerge::Merged(m) if m.contains("Use the cache.")
+        ));
+    }
+
+    #[test]
+    fn test_fingerprint_line_follows_marker_syntax() {
+        assert_eq!(
+            fingerprint_line(START, "abc"),
+            "<!-- acp-fingerprint: abc -->"
+        );
+        assert_eq!(
+            fingerprint_line("# BEGIN ACP", "abc"),
+            "# acp-fingerprint: abc"
+        );
+        assert_eq!(parse_fingerprint("# acp-fingerprint: abc"), Some("abc"));
+        assert_eq!(parse_fingerprint("# Rules"), None);
+    }
+
     #[test]
     fn test_merge_json() {
         let existing = r#"{"name": "test", "version": "1.0"}"#;
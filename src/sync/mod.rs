@@ -21,10 +21,16 @@
 //! - Cline (.clinerules)
 //! - Aider (.aider.conf.yml)
 //! - Generic fallback (AGENTS.md)
+//! - Custom tools declared under `sync.custom` in the config
+//!
+//! With `sync.preserveEdits` set, marked sections that were edited by hand
+//! since they were generated are left alone; the new content goes to a
+//! `<file>.acp-conflict` sidecar instead.
 
 pub mod adapter;
 pub mod adapters;
 pub mod content;
+pub mod custom;
 pub mod merge;
 pub mod tool;
 
@@ -32,14 +38,20 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 pub use adapter::{BootstrapContext, DetectionResult, ToolAdapter};
+pub use custom::CustomAdapter;
 pub use tool::{MergeStrategy, OutputFormat, Tool};
 
-use crate::error::Result;
+use crate::cache::normalize_path;
+use crate::config::Config;
+use crate::error::{AcpError, Result};
 use adapters::*;
 
 /// Main sync executor - coordinates tool detection and bootstrap generation
 pub struct SyncExecutor {
     adapters: HashMap<Tool, Box<dyn ToolAdapter>>,
+    custom: Vec<CustomAdapter>,
+    /// Use [`MergeStrategy::PreserveEdits`] for marked sections
+    preserve_edits: bool,
 }
 
 impl SyncExecutor {
@@ -56,7 +68,69 @@ impl SyncExecutor {
         adapters.insert(Tool::Aider, Box::new(AiderAdapter));
         adapters.insert(Tool::Generic, Box::new(GenericAdapter));
 
-        Self { adapters }
+        Self {
+            adapters,
+            custom: Vec::new(),
+            preserve_edits: false,
+        }
+    }
+
+    /// Create a sync executor with the built-in adapters plus the config's
+    /// `sync.custom` adapters
+    ///
+    /// Fails if a custom adapter is invalid, reuses a name, or writes to a
+    /// file another adapter already owns.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let mut executor = Self::new();
+        executor.preserve_edits = config.sync.preserve_edits;
+
+        for entry in &config.sync.custom {
+            let adapter = CustomAdapter::new(entry.clone())?;
+            let conflict = |what: &str| {
+                AcpError::Config(format!(
+                    "sync.custom: {}: {} conflicts with another adapter",
+                    adapter.name(),
+                    what
+                ))
+            };
+
+            if Tool::from_name(adapter.name()).is_some()
+                || executor
+                    .custom
+                    .iter()
+                    .any(|c| c.name().eq_ignore_ascii_case(adapter.name()))
+            {
+                return Err(conflict("name"));
+            }
+            let output = normalize_path(adapter.output_path());
+            if Tool::all()
+                .iter()
+                .any(|t| normalize_path(t.output_path()) == output)
+                || executor
+                    .custom
+                    .iter()
+                    .any(|c| normalize_path(c.output_path()) == output)
+            {
+                return Err(conflict("outputPath"));
+            }
+
+            executor.custom.push(adapter);
+        }
+
+        Ok(executor)
+    }
+
+    /// Custom adapters loaded from the config, in config order
+    pub fn custom_adapters(&self) -> &[CustomAdapter] {
+        &self.custom
+    }
+
+    /// Custom adapters whose tool appears to be in use
+    pub fn detect_custom(&self, project_root: &Path) -> Vec<&CustomAdapter> {
+        self.custom
+            .iter()
+            .filter(|adapter| adapter.detect(project_root))
+            .collect()
     }
 
     /// Detect which tools are in use in the project
@@ -108,41 +182,27 @@ impl SyncExecutor {
         }
 
         // Handle existing file
-        let action = if output_path.exists() {
+        let (start_marker, end_marker) = adapter.section_markers();
+        let action = if output_path.exists() && start_marker.is_empty() {
             let existing = std::fs::read_to_string(&output_path)?;
-            let (start_marker, end_marker) = adapter.section_markers();
 
-            let merged = if start_marker.is_empty() {
-                // Special handling for JSON (Continue.dev)
-                if tool == Tool::Continue {
-                    merge::merge_json(&existing, &content)
-                        .map_err(|e| crate::error::AcpError::Other(e.to_string()))?
-                } else {
-                    content.clone()
-                }
+            // Special handling for JSON (Continue.dev)
+            let merged = if tool == Tool::Continue {
+                merge::merge_json(&existing, &content)
+                    .map_err(|e| crate::error::AcpError::Other(e.to_string()))?
             } else {
-                merge::merge_content(
-                    adapter.merge_strategy(),
-                    &existing,
-                    &content,
-                    start_marker,
-                    end_marker,
-                )
+                content.clone()
             };
 
             std::fs::write(&output_path, merged)?;
             BootstrapAction::Merged
         } else {
-            // New file - wrap with markers if applicable
-            let (start_marker, end_marker) = adapter.section_markers();
-            let final_content = if !start_marker.is_empty() {
-                format!("{}\n{}\n{}", start_marker, content, end_marker)
-            } else {
-                content
-            };
-
-            std::fs::write(&output_path, final_content)?;
-            BootstrapAction::Created
+            write_section(
+                &output_path,
+                &content,
+                (start_marker, end_marker),
+                self.section_strategy(adapter.merge_strategy()),
+            )?
         };
 
         Ok(BootstrapResult {
@@ -152,6 +212,43 @@ impl SyncExecutor {
         })
     }
 
+    /// Bootstrap a custom adapter with ACP context
+    pub fn bootstrap_custom(
+        &self,
+        adapter: &CustomAdapter,
+        project_root: &Path,
+    ) -> Result<CustomBootstrapResult> {
+        let content = adapter.generate()?;
+        let output_path = project_root.join(adapter.output_path());
+
+        if let Some(parent) = output_path.parent() {
+            if !parent.exists() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+
+        let action = write_section(
+            &output_path,
+            &content,
+            adapter.section_markers(),
+            self.section_strategy(MergeStrategy::Section),
+        )?;
+
+        Ok(CustomBootstrapResult {
+            name: adapter.name().to_string(),
+            output_path,
+            action,
+        })
+    }
+
+    /// Section merges keep hand edits when `sync.preserveEdits` is set
+    fn section_strategy(&self, strategy: MergeStrategy) -> MergeStrategy {
+        match strategy {
+            MergeStrategy::Section if self.preserve_edits => MergeStrategy::PreserveEdits,
+            other => other,
+        }
+    }
+
     /// Bootstrap all detected tools plus the generic fallback
     pub fn bootstrap_all(&self, project_root: &Path) -> Vec<Result<BootstrapResult>> {
         let mut tools = self.detect_tools(project_root);
@@ -174,6 +271,68 @@ impl Default for SyncExecutor {
     }
 }
 
+/// Write generated content into its marked section, creating the file if needed
+fn write_section(
+    output_path: &Path,
+    content: &str,
+    (start_marker, end_marker): (&str, &str),
+    strategy: MergeStrategy,
+) -> Result<BootstrapAction> {
+    if strategy == MergeStrategy::PreserveEdits {
+        let existed = output_path.exists();
+        let existing = if existed {
+            std::fs::read_to_string(output_path)?
+        } else {
+            String::new()
+        };
+        return match merge::merge_preserving_edits(&existing, content, start_marker, end_marker) {
+            merge::PreservedMerge::Merged(merged) => {
+                std::fs::write(output_path, merged)?;
+                Ok(if existed {
+                    BootstrapAction::Merged
+                } else {
+                    BootstrapAction::Created
+                })
+            }
+            merge::PreservedMerge::Edited => {
+                std::fs::write(conflict_path(output_path), content)?;
+                Ok(BootstrapAction::Conflict)
+            }
+        };
+    }
+
+    if output_path.exists() {
+        let existing = std::fs::read_to_string(output_path)?;
+        let merged = merge::merge_content(strategy, &existing, content, start_marker, end_marker);
+        std::fs::write(output_path, merged)?;
+        return Ok(BootstrapAction::Merged);
+    }
+
+    // New file - wrap with markers if applicable
+    let final_content = if !start_marker.is_empty() {
+        format!("{}\n{}\n{}", start_marker, content, end_marker)
+    } else {
+        content.to_string()
+    };
+    std::fs::write(output_path, final_content)?;
+    Ok(BootstrapAction::Created)
+}
+
+/// Sidecar that receives generated content when a section was hand-edited
+pub fn conflict_path(output_path: &Path) -> PathBuf {
+    let mut name = output_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".acp-conflict");
+    output_path.with_file_name(name)
+}
+
+/// Result of bootstrapping a custom adapter
+#[derive(Debug)]
+pub struct CustomBootstrapResult {
+    pub name: String,
+    pub output_path: PathBuf,
+    pub action: BootstrapAction,
+}
+
 /// Result of bootstrapping a tool
 #[derive(Debug)]
 pub struct BootstrapResult {
@@ -191,6 +350,8 @@ pub enum BootstrapAction {
     Merged,
     /// File was skipped
     Skipped,
+    /// Section was edited by hand and kept; new content is in the sidecar
+    Conflict,
 }
 
 #[cfg(test)]
@@ -204,6 +365,70 @@ mod tests {
         assert_eq!(executor.adapters.len(), 8);
     }
 
+    #[test]
+    fn test_custom_adapters_from_config() {
+        let temp = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.sync.custom = vec![crate::config::CustomAdapterConfig {
+            name: "Zed".to_string(),
+            output_path: ".zed/rules.md".to_string(),
+            detect_globs: vec![".zed".to_string()],
+            section_markers: None,
+            template: "# Rules for {{name}}\n\n{{context}}".to_string(),
+        }];
+
+        let executor = SyncExecutor::from_config(&config).unwrap();
+        assert_eq!(executor.adapters.len(), 8);
+        assert!(executor.detect_custom(temp.path()).is_empty());
+
+        std::fs::create_dir(temp.path().join(".zed")).unwrap();
+        let detected = executor.detect_custom(temp.path());
+        assert_eq!(detected.len(), 1);
+
+        let result = executor.bootstrap_custom(detected[0], temp.path()).unwrap();
+        assert_eq!(result.action, BootstrapAction::Created);
+        let content = std::fs::read_to_string(&result.output_path).unwrap();
+        assert!(content.contains("# Rules for Zed"));
+        assert!(content.contains("BEGIN ACP GENERATED"));
+
+        let result = executor.bootstrap_custom(detected[0], temp.path()).unwrap();
+        assert_eq!(result.action, BootstrapAction::Merged);
+
+        // Names and output paths must not collide with other adapters
+        let mut clash = config.clone();
+        clash.sync.custom[0].output_path = "./CLAUDE.md".to_string();
+        assert!(SyncExecutor::from_config(&clash).is_err());
+        let mut clash = config.clone();
+        clash.sync.custom[0].name = "cursor".to_string();
+        assert!(SyncExecutor::from_config(&clash).is_err());
+    }
+
+    #[test]
+    fn test_preserve_edits_writes_conflict_sidecar() {
+        let temp = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.sync.preserve_edits = true;
+        let executor = SyncExecutor::from_config(&config).unwrap();
+
+        let result = executor.bootstrap_tool(Tool::Cursor, temp.path()).unwrap();
+        assert_eq!(result.action, BootstrapAction::Created);
+        let result = executor.bootstrap_tool(Tool::Cursor, temp.path()).unwrap();
+        assert_eq!(result.action, BootstrapAction::Merged);
+
+        let generated = std::fs::read_to_string(&result.output_path).unwrap();
+        let (start, _) = CursorAdapter.section_markers();
+        let edited = generated.replacen(start, &format!("{}\nAlways ask first.", start), 1);
+        std::fs::write(&result.output_path, &edited).unwrap();
+
+        let result = executor.bootstrap_tool(Tool::Cursor, temp.path()).unwrap();
+        assert_eq!(result.action, BootstrapAction::Conflict);
+        assert_eq!(
+            std::fs::read_to_string(&result.output_path).unwrap(),
+            edited
+        );
+        assert!(conflict_path(&result.output_path).exists());
+    }
+
     #[test]
     fn test_detect_tools_empty_project() {
         let temp = TempDir::new().unwrap();
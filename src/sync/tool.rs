@@ -118,6 +118,8 @@ pub enum MergeStrategy {
     Append,
     /// Deep merge (for JSON/YAML)
     Merge,
+    /// Replace only marked section, unless it was edited since last generated
+    PreserveEdits,
 }
 
 #[cfg(test)]
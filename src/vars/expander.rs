@@ -100,6 +100,69 @@ impl VarExpander {
         }
     }
 
+    /// @acp:summary "Substitute `${VAR}` placeholders in arbitrary text"
+    ///
+    /// Each placeholder ends at the first `}` and is replaced by the variable's
+    /// value with nested references expanded; `${VAR.description}` and the
+    /// other modifiers work as in `$VAR.modifier`. Unknown placeholders are
+    /// left intact and listed in `vars_unresolved`. `\${` produces a literal
+    /// `${`.
+    pub fn expand_template(&mut self, text: &str) -> ExpansionResult {
+        let mut expanded = String::with_capacity(text.len());
+        let mut vars_expanded = Vec::new();
+        let mut vars_unresolved = Vec::new();
+        let mut chains = Vec::new();
+        let mut rest = text;
+
+        while let Some(open) = rest.find("${") {
+            let (before, after) = rest.split_at(open);
+            if let Some(literal) = before.strip_suffix('\\') {
+                expanded.push_str(literal);
+                expanded.push_str("${");
+                rest = &after[2..];
+                continue;
+            }
+            expanded.push_str(before);
+
+            let Some(close) = after.find('}') else {
+                rest = after;
+                break;
+            };
+            let inner = after[2..close].trim();
+            let (name, modifier) = match inner.split_once('.') {
+                Some((name, modifier)) if self.resolver.get(inner).is_none() => {
+                    (name, Some(modifier))
+                }
+                _ => (inner, None),
+            };
+
+            match self.resolver.get(name).cloned() {
+                Some(var) => {
+                    vars_expanded.push(name.to_string());
+                    chains.push(self.get_inheritance_chain(name));
+                    let value = self.format_var(name, &var, modifier, ExpansionMode::Inline);
+                    expanded.push_str(&value);
+                }
+                None => {
+                    vars_unresolved.push(inner.to_string());
+                    expanded.push_str(&after[..=close]);
+                }
+            }
+            rest = &after[close + 1..];
+        }
+        expanded.push_str(rest);
+
+        ExpansionResult {
+            original: text.to_string(),
+            tokens_before: estimate_tokens(text),
+            tokens_after: estimate_tokens(&expanded),
+            expanded,
+            vars_expanded,
+            vars_unresolved,
+            inheritance_chains: chains,
+        }
+    }
+
     /// Get inheritance chain for a variable by traversing refs
     pub fn get_inheritance_chain(&self, name: &str) -> InheritanceChain {
         let mut chain = vec![name.to_string()];
@@ -117,6 +180,60 @@ impl VarExpander {
         }
     }
 
+    /// @acp:summary "Inheritance tree for a variable, guarded against cycles"
+    ///
+    /// A ref back to a variable already on the path from the root is kept
+    /// as a leaf marked `cycle`. A variable whose refs were already expanded
+    /// elsewhere in the tree is a leaf marked `repeated`, so shared refs
+    /// don't multiply the tree. With `max_depth`, nodes at that depth keep
+    /// no children and are marked `truncated` if they had refs to follow.
+    pub fn get_inheritance_tree(&self, name: &str, max_depth: Option<usize>) -> InheritanceNode {
+        let mut path = Vec::new();
+        self.build_tree(name, max_depth, &mut path, &mut HashSet::new())
+    }
+
+    fn build_tree(
+        &self,
+        name: &str,
+        max_depth: Option<usize>,
+        path: &mut Vec<String>,
+        expanded: &mut HashSet<String>,
+    ) -> InheritanceNode {
+        let mut node = InheritanceNode {
+            name: name.to_string(),
+            children: vec![],
+            cycle: path.iter().any(|p| p == name),
+            repeated: false,
+            truncated: false,
+            missing: false,
+        };
+        let Some(var) = self.resolver.get(name) else {
+            node.missing = true;
+            return node;
+        };
+        if node.cycle || var.refs.is_empty() {
+            return node;
+        }
+        if expanded.contains(name) {
+            node.repeated = true;
+            return node;
+        }
+        if max_depth.is_some_and(|max| path.len() >= max) {
+            node.truncated = true;
+            return node;
+        }
+
+        expanded.insert(name.to_string());
+        path.push(name.to_string());
+        node.children = var
+            .refs
+            .iter()
+            .map(|r| self.build_tree(r.trim_start_matches('$'), max_depth, path, expanded))
+            .collect();
+        path.pop();
+        node
+    }
+
     /// Build inheritance chain by traversing refs recursively
     /// Returns true if a cycle was detected
     fn build_chain(
@@ -250,3 +367,91 @@ pub struct InheritanceChain {
     pub depth: usize,
     pub has_cycle: bool,
 }
+
+/// A variable in an inheritance tree, with the refs it inherits from
+#[derive(Debug, Clone)]
+pub struct InheritanceNode {
+    pub name: String,
+    pub children: Vec<InheritanceNode>,
+    /// Already on the path from the root; its refs are not followed again
+    pub cycle: bool,
+    /// Refs were already expanded elsewhere in the tree and are not repeated
+    pub repeated: bool,
+    /// Has refs that were not followed because of the depth limit
+    pub truncated: bool,
+    /// Referenced but not defined in the vars file
+    pub missing: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vars::VarsFile;
+
+    fn expander() -> VarExpander {
+        let mut vars_file = VarsFile::new();
+        vars_file.add_variable(
+            "SYM_AUTH".to_string(),
+            VarEntry::symbol("src/auth.ts:login", Some("Logs a user in".to_string())),
+        );
+        vars_file.add_variable(
+            "FLOW".to_string(),
+            VarEntry::pattern("calls $SYM_AUTH", None),
+        );
+        VarExpander::new(VarResolver::new(vars_file))
+    }
+
+    #[test]
+    fn test_inheritance_tree_expands_shared_refs_once() {
+        // Each level refs both variables of the next: 2^40 paths
+        let mut vars_file = VarsFile::new();
+        for level in 0..40 {
+            let refs = vec![format!("SYM_A{}", level + 1), format!("SYM_B{}", level + 1)];
+            for name in [format!("SYM_A{}", level), format!("SYM_B{}", level)] {
+                vars_file.add_variable(
+                    name,
+                    VarEntry::symbol_with_refs("x.rs:x", None, refs.clone()),
+                );
+            }
+        }
+        let expander = VarExpander::new(VarResolver::new(vars_file));
+
+        fn count(node: &InheritanceNode) -> usize {
+            1 + node.children.iter().map(count).sum::<usize>()
+        }
+        let tree = expander.get_inheritance_tree("SYM_A0", None);
+        assert!(count(&tree) <= 4 * 41, "{} nodes", count(&tree));
+
+        let a1 = &tree.children[0];
+        let b1 = &tree.children[1];
+        assert!(!a1.repeated && a1.children.len() == 2);
+        assert!(b1
+            .children
+            .iter()
+            .all(|c| c.repeated && c.children.is_empty()));
+    }
+
+    #[test]
+    fn test_expand_template() {
+        let mut expander = expander();
+        let result = expander.expand_template(
+            "Edit ${SYM_AUTH} (${ SYM_AUTH.description }), then ${FLOW}. Cost: $5, ${MISSING}",
+        );
+        assert_eq!(
+            result.expanded,
+            "Edit src/auth.ts:login (Logs a user in), then calls src/auth.ts:login. \
+             Cost: $5, ${MISSING}"
+        );
+        assert_eq!(result.vars_expanded, vec!["SYM_AUTH", "SYM_AUTH", "FLOW"]);
+        assert_eq!(result.vars_unresolved, vec!["MISSING"]);
+    }
+
+    #[test]
+    fn test_expand_template_escapes_and_unterminated() {
+        let mut expander = expander();
+        let result = expander.expand_template(r"keep \${SYM_AUTH} and ${SYM_AUTH");
+        assert_eq!(result.expanded, "keep ${SYM_AUTH} and ${SYM_AUTH");
+        assert!(result.vars_expanded.is_empty());
+        assert!(result.vars_unresolved.is_empty());
+    }
+}
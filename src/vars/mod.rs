@@ -9,11 +9,13 @@ mod resolver;
 
 pub mod presets;
 
-pub use expander::{ExpansionMode, ExpansionResult, InheritanceChain, VarExpander};
+pub use expander::{
+    ExpansionMode, ExpansionResult, InheritanceChain, InheritanceNode, VarExpander,
+};
 pub use resolver::{VarReference, VarResolver};
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
 use std::path::Path;
@@ -32,8 +34,8 @@ pub struct VarsFile {
     pub schema: String,
     /// ACP specification version (required)
     pub version: String,
-    /// Map of variable names to variable entries (required)
-    pub variables: HashMap<String, VarEntry>,
+    /// Map of variable names to variable entries (required), sorted by name
+    pub variables: BTreeMap<String, VarEntry>,
 }
 
 impl VarsFile {
@@ -42,7 +44,7 @@ impl VarsFile {
         Self {
             schema: default_vars_schema(),
             version: crate::VERSION.to_string(),
-            variables: HashMap::new(),
+            variables: BTreeMap::new(),
         }
     }
 
@@ -65,6 +67,37 @@ impl VarsFile {
     pub fn add_variable(&mut self, name: String, entry: VarEntry) {
         self.variables.insert(name, entry);
     }
+
+    /// @acp:summary "Carry hand-added variables over from an earlier vars file"
+    ///
+    /// Entries of `previous` not marked `generated` are kept, and win over a
+    /// generated variable of the same name. A file written before the marker
+    /// existed has no generated entries at all; it is treated as entirely
+    /// generated so stale variables don't linger. Returns how many were kept.
+    pub fn keep_manual(&mut self, previous: VarsFile) -> usize {
+        if !previous.variables.values().any(|entry| entry.generated) {
+            return 0;
+        }
+        let mut kept = 0;
+        for (name, entry) in previous.variables {
+            if !entry.generated {
+                self.variables.insert(name, entry);
+                kept += 1;
+            }
+        }
+        kept
+    }
+
+    /// @acp:summary "Keep the hand-added variables of the vars file at `path`, if any"
+    ///
+    /// See [`VarsFile::keep_manual`]. A missing file keeps nothing; an
+    /// unreadable one is an error rather than being silently replaced.
+    pub fn keep_manual_from<P: AsRef<Path>>(&mut self, path: P) -> Result<usize> {
+        if !path.as_ref().exists() {
+            return Ok(0);
+        }
+        Ok(self.keep_manual(Self::from_json(path)?))
+    }
 }
 
 impl Default for VarsFile {
@@ -93,6 +126,14 @@ pub struct VarEntry {
     /// Line range [start, end] in source file (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lines: Option<[usize; 2]>,
+    /// Derived from the cache and replaced on regeneration; hand-added
+    /// entries leave it unset
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub generated: bool,
+}
+
+fn is_false(b: &bool) -> bool {
+    !*b
 }
 
 impl VarEntry {
@@ -105,6 +146,7 @@ impl VarEntry {
             refs: vec![],
             source: None,
             lines: None,
+            generated: false,
         }
     }
 
@@ -122,6 +164,7 @@ impl VarEntry {
             refs: vec![],
             source: Some(source),
             lines: Some(lines),
+            generated: false,
         }
     }
 
@@ -138,6 +181,7 @@ impl VarEntry {
             refs,
             source: None,
             lines: None,
+            generated: false,
         }
     }
 
@@ -150,6 +194,7 @@ impl VarEntry {
             refs: vec![],
             source: None,
             lines: None,
+            generated: false,
         }
     }
 
@@ -162,6 +207,7 @@ impl VarEntry {
             refs: vec![],
             source: None,
             lines: None,
+            generated: false,
         }
     }
 
@@ -174,6 +220,7 @@ impl VarEntry {
             refs: vec![],
             source: None,
             lines: None,
+            generated: false,
         }
     }
 
@@ -186,6 +233,7 @@ impl VarEntry {
             refs: vec![],
             source: None,
             lines: None,
+            generated: false,
         }
     }
 
@@ -198,6 +246,7 @@ impl VarEntry {
             refs: vec![],
             source: None,
             lines: None,
+            generated: false,
         }
     }
 }
@@ -251,7 +300,7 @@ mod tests {
         let vars_file = VarsFile {
             schema: default_vars_schema(),
             version: "1.0.0".to_string(),
-            variables: HashMap::new(),
+            variables: BTreeMap::new(),
         };
         let resolver = VarResolver::new(vars_file);
 
@@ -262,6 +311,92 @@ mod tests {
         assert_eq!(refs[1].modifier, Some("value".to_string()));
     }
 
+    #[test]
+    fn test_validate_detects_three_node_cycle() {
+        let mut vars_file = VarsFile::new();
+        vars_file.add_variable(
+            "SYM_A".to_string(),
+            VarEntry::symbol_with_refs("a.rs:a", None, vec!["SYM_B".to_string()]),
+        );
+        vars_file.add_variable(
+            "SYM_B".to_string(),
+            VarEntry::symbol_with_refs("b.rs:b", None, vec!["SYM_C".to_string()]),
+        );
+        vars_file.add_variable(
+            "SYM_C".to_string(),
+            VarEntry::symbol_with_refs("c.rs:c", None, vec!["SYM_A".to_string()]),
+        );
+        let resolver = VarResolver::new(vars_file);
+
+        let cycle = resolver.find_cycle().unwrap();
+        assert_eq!(cycle, vec!["SYM_A", "SYM_B", "SYM_C", "SYM_A"]);
+
+        let err = resolver.validate().unwrap_err();
+        assert!(matches!(err, crate::error::AcpError::CycleDetected(_)));
+        assert!(err.to_string().contains("SYM_A -> SYM_B -> SYM_C -> SYM_A"));
+    }
+
+    #[test]
+    fn test_validate_accepts_acyclic_refs() {
+        let mut vars_file = VarsFile::new();
+        vars_file.add_variable(
+            "SYM_A".to_string(),
+            VarEntry::symbol_with_refs(
+                "a.rs:a",
+                None,
+                vec!["SYM_B".to_string(), "SYM_C".to_string()],
+            ),
+        );
+        vars_file.add_variable(
+            "SYM_B".to_string(),
+            VarEntry::symbol_with_refs("b.rs:b", None, vec!["SYM_C".to_string()]),
+        );
+        vars_file.add_variable(
+            "SYM_C".to_string(),
+            VarEntry::symbol_with_refs("c.rs:c", None, vec!["SYM_MISSING".to_string()]),
+        );
+        let resolver = VarResolver::new(vars_file);
+
+        assert!(resolver.find_cycle().is_none());
+        assert!(resolver.validate().is_ok());
+    }
+
+    #[test]
+    fn test_keep_manual_vars() {
+        let generated = |value: &str| VarEntry {
+            generated: true,
+            ..VarEntry::symbol(value, None)
+        };
+        let mut previous = VarsFile::new();
+        previous.add_variable("SYM_OLD".to_string(), generated("old.rs:gone"));
+        previous.add_variable(
+            "CTX_RELEASE".to_string(),
+            VarEntry::context("release checklist", None),
+        );
+        previous.add_variable(
+            "SYM_MAIN".to_string(),
+            VarEntry::symbol("main.rs:main", Some("Hand-tuned".to_string())),
+        );
+
+        let mut vars_file = VarsFile::new();
+        vars_file.add_variable("SYM_MAIN".to_string(), generated("main.rs:main"));
+        vars_file.add_variable("SYM_NEW".to_string(), generated("new.rs:new"));
+        assert_eq!(vars_file.keep_manual(previous.clone()), 2);
+
+        let names: Vec<_> = vars_file.variables.keys().map(String::as_str).collect();
+        assert_eq!(names, vec!["CTX_RELEASE", "SYM_MAIN", "SYM_NEW"]);
+        assert_eq!(
+            vars_file.variables["SYM_MAIN"].description.as_deref(),
+            Some("Hand-tuned")
+        );
+
+        // Files without generated markers predate them and are fully replaced
+        previous.variables.remove("SYM_OLD");
+        let mut vars_file = VarsFile::new();
+        assert_eq!(vars_file.keep_manual(previous), 0);
+        assert!(vars_file.variables.is_empty());
+    }
+
     #[test]
     fn test_vars_roundtrip() {
         let mut vars_file = VarsFile::new();
@@ -4,9 +4,10 @@
 //! @acp:layer service
 
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use super::{VarEntry, VarType, VarsFile};
+use crate::error::{AcpError, Result};
 
 /// @acp:summary "Resolves variable references from a vars file"
 pub struct VarResolver {
@@ -18,7 +19,7 @@ impl VarResolver {
     /// Create a new resolver from a vars file
     pub fn new(vars_file: VarsFile) -> Self {
         Self {
-            vars: vars_file.variables,
+            vars: vars_file.variables.into_iter().collect(),
             var_pattern: Regex::new(r"\$([A-Z][A-Z0-9_]+)(?:\.(\w+))?").unwrap(),
         }
     }
@@ -64,6 +65,67 @@ impl VarResolver {
             })
             .collect()
     }
+
+    /// @acp:summary "Check the refs graph for inheritance cycles"
+    ///
+    /// Returns `AcpError::CycleDetected` naming the cycle members in order,
+    /// e.g. `A -> B -> C -> A`. Refs to undefined variables are ignored.
+    pub fn validate(&self) -> Result<()> {
+        match self.find_cycle() {
+            Some(cycle) => Err(AcpError::CycleDetected(cycle.join(" -> "))),
+            None => Ok(()),
+        }
+    }
+
+    /// Find the first cycle in the refs graph using DFS
+    ///
+    /// Nodes are visited in sorted order so the reported cycle is deterministic.
+    /// The returned path starts and ends with the same variable name.
+    pub fn find_cycle(&self) -> Option<Vec<String>> {
+        fn dfs<'a>(
+            node: &'a str,
+            vars: &'a HashMap<String, VarEntry>,
+            visited: &mut HashSet<&'a str>,
+            path: &mut Vec<&'a str>,
+        ) -> Option<Vec<String>> {
+            visited.insert(node);
+            path.push(node);
+
+            if let Some(entry) = vars.get(node) {
+                for next in &entry.refs {
+                    let next = next.trim_start_matches('$');
+                    if let Some(start) = path.iter().position(|&n| n == next) {
+                        let mut cycle: Vec<String> =
+                            path[start..].iter().map(|n| n.to_string()).collect();
+                        cycle.push(next.to_string());
+                        return Some(cycle);
+                    }
+                    if !visited.contains(next) && vars.contains_key(next) {
+                        if let Some(cycle) = dfs(next, vars, visited, path) {
+                            return Some(cycle);
+                        }
+                    }
+                }
+            }
+
+            path.pop();
+            None
+        }
+
+        let mut names: Vec<&str> = self.vars.keys().map(|k| k.as_str()).collect();
+        names.sort_unstable();
+
+        let mut visited = HashSet::new();
+        for name in names {
+            if !visited.contains(name) {
+                let mut path = Vec::new();
+                if let Some(cycle) = dfs(name, &self.vars, &mut visited, &mut path) {
+                    return Some(cycle);
+                }
+            }
+        }
+        None
+    }
 }
 
 /// @acp:summary "A parsed variable reference from text"
@@ -4,54 +4,105 @@
 //! @acp:layer service
 //!
 //! Watches for file changes and updates cache/vars incrementally.
+//!
+//! [`FileWatcher`] reports raw changes from a blocking thread; [`ChangeStream`]
+//! runs it and debounces the changes into batches for `acp watch` and
+//! `acp daemon start --watch`.
 
-use std::path::Path;
-use std::sync::mpsc;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
 
 use console::style;
-use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
+use glob::Pattern;
+use notify::{Config, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc as async_mpsc;
+use tokio::time::Instant;
 
 use crate::config::Config as AcpConfig;
 use crate::error::Result;
+use crate::globs::MATCH_OPTIONS;
+
+/// Quiet period that ends a burst of changes (editors often write in steps)
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// How often to check the shutdown flag while idle
+const POLL: Duration = Duration::from_millis(200);
 
 /// File watcher for incremental updates
 pub struct FileWatcher {
-    _config: AcpConfig,
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
 }
 
 impl FileWatcher {
     pub fn new(config: AcpConfig) -> Self {
-        Self { _config: config }
+        let compile = |patterns: &[String]| -> Vec<Pattern> {
+            patterns
+                .iter()
+                .filter_map(|p| Pattern::new(p).ok())
+                .collect()
+        };
+        Self {
+            include: compile(&config.include),
+            exclude: compile(&config.exclude),
+        }
     }
 
-    /// Start watching for changes
-    pub fn watch<P: AsRef<Path>>(&self, root: P) -> Result<()> {
+    /// @acp:summary "Report changed source files as the events arrive"
+    ///
+    /// Blocks until `shutdown` is set, calling `on_change` with the
+    /// root-relative paths of each event; [`ChangeStream`] debounces them.
+    /// Paths outside the config's include patterns, or inside hidden
+    /// directories such as `.acp` and `.git`, are ignored so writing the
+    /// cache doesn't retrigger.
+    pub fn watch_changes<P, F>(
+        &self,
+        root: P,
+        shutdown: &AtomicBool,
+        mut on_change: F,
+    ) -> Result<()>
+    where
+        P: AsRef<Path>,
+        F: FnMut(Vec<String>),
+    {
+        let root = root.as_ref();
         let (tx, rx) = mpsc::channel();
 
         let mut watcher = RecommendedWatcher::new(tx, Config::default())
             .map_err(|e| crate::error::AcpError::Other(e.to_string()))?;
 
         watcher
-            .watch(root.as_ref(), RecursiveMode::Recursive)
+            .watch(root, RecursiveMode::Recursive)
             .map_err(|e| crate::error::AcpError::Other(e.to_string()))?;
 
-        println!("Watching for changes...");
+        // Watch events carry absolute paths
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
 
-        loop {
-            match rx.recv() {
-                Ok(event) => {
-                    match event {
-                        Ok(event) => {
-                            println!("Change detected: {:?}", event);
-                            // TODO: Incremental update based on event.kind
-                        }
-                        Err(e) => {
-                            eprintln!("{} Watch error: {}", style("✗").red(), e);
-                        }
+        while !shutdown.load(Ordering::Relaxed) {
+            match rx.recv_timeout(POLL) {
+                // Reads (including the indexer's own) must not trigger a rebuild
+                Ok(Ok(event)) if !matches!(event.kind, EventKind::Access(_)) => {
+                    let changed: Vec<String> = event
+                        .paths
+                        .iter()
+                        .filter_map(|p| self.relevant(&root, p))
+                        .collect();
+                    if !changed.is_empty() {
+                        on_change(changed);
                     }
                 }
-                Err(e) => {
-                    eprintln!("{} Channel error: {}", style("✗").red(), e);
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => {
+                    eprintln!("{} Watch error: {}", style("✗").red(), e);
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    eprintln!("{} Watch channel closed", style("✗").red());
                     break;
                 }
             }
@@ -59,4 +110,145 @@ impl FileWatcher {
 
         Ok(())
     }
+
+    /// Root-relative path when the change should trigger a rebuild
+    fn relevant(&self, root: &Path, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(root).ok()?;
+        if relative
+            .components()
+            .any(|c| c.as_os_str().to_string_lossy().starts_with('.'))
+        {
+            return None;
+        }
+
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        let included = self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|p| p.matches_with(&relative, MATCH_OPTIONS));
+        let excluded = self
+            .exclude
+            .iter()
+            .any(|p| p.matches_with(&relative, MATCH_OPTIONS));
+
+        (included && !excluded).then_some(relative)
+    }
+}
+
+/// @acp:summary "Debounced batches of changed files from a watcher thread"
+///
+/// A batch is released once `debounce` passes without another change, with
+/// its paths sorted and deduplicated. [`ChangeStream::next_batch`] keeps its
+/// progress in the stream, so it can be raced against other futures.
+pub struct ChangeStream {
+    changes: async_mpsc::UnboundedReceiver<Vec<String>>,
+    debounce: Duration,
+    pending: BTreeSet<String>,
+    deadline: Option<Instant>,
+    watcher: Option<(Arc<AtomicBool>, JoinHandle<Result<()>>)>,
+}
+
+impl ChangeStream {
+    /// Watch `root` on a background thread
+    pub fn spawn(config: AcpConfig, root: PathBuf, debounce: Duration) -> Self {
+        let (tx, rx) = async_mpsc::unbounded_channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = std::thread::spawn({
+            let stop = stop.clone();
+            move || {
+                FileWatcher::new(config).watch_changes(&root, &stop, |changed| {
+                    let _ = tx.send(changed);
+                })
+            }
+        });
+        let mut stream = Self::from_channel(rx, debounce);
+        stream.watcher = Some((stop, thread));
+        stream
+    }
+
+    /// Debounce changes sent by something other than a watcher thread
+    pub fn from_channel(
+        changes: async_mpsc::UnboundedReceiver<Vec<String>>,
+        debounce: Duration,
+    ) -> Self {
+        Self {
+            changes,
+            debounce,
+            pending: BTreeSet::new(),
+            deadline: None,
+            watcher: None,
+        }
+    }
+
+    /// @acp:summary "The next batch of changes, or `None` once the watcher stops"
+    pub async fn next_batch(&mut self) -> Option<Vec<String>> {
+        loop {
+            tokio::select! {
+                changed = self.changes.recv() => match changed {
+                    Some(changed) => {
+                        self.pending.extend(changed);
+                        self.deadline = Some(Instant::now() + self.debounce);
+                    }
+                    // Hand over what is left before reporting the end
+                    None if !self.pending.is_empty() => {
+                        self.deadline = None;
+                        return Some(std::mem::take(&mut self.pending).into_iter().collect());
+                    }
+                    None => return None,
+                },
+                _ = tokio::time::sleep_until(self.deadline.unwrap_or_else(Instant::now)),
+                    if self.deadline.is_some() =>
+                {
+                    self.deadline = None;
+                    return Some(std::mem::take(&mut self.pending).into_iter().collect());
+                }
+            }
+        }
+    }
+
+    /// Stop the watcher thread and wait for it
+    pub fn stop(self) -> Result<()> {
+        let Some((stop, thread)) = self.watcher else {
+            return Ok(());
+        };
+        stop.store(true, Ordering::Relaxed);
+        match thread.join() {
+            Ok(result) => result,
+            Err(_) => {
+                eprintln!("{} File watcher thread panicked", style("✗").red());
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_change_stream_debounces_bursts() {
+        let (tx, rx) = async_mpsc::unbounded_channel();
+        let mut stream = ChangeStream::from_channel(rx, Duration::from_millis(200));
+
+        let sender = tokio::spawn(async move {
+            for file in ["b.ts", "a.ts", "b.ts"] {
+                tx.send(vec![file.to_string()]).unwrap();
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            tokio::time::sleep(Duration::from_millis(400)).await;
+            tx.send(vec!["c.ts".to_string()]).unwrap();
+        });
+
+        let start = Instant::now();
+        assert_eq!(stream.next_batch().await.unwrap(), vec!["a.ts", "b.ts"]);
+        // Released no sooner than one quiet period after the burst's last change
+        assert!(start.elapsed() >= Duration::from_millis(240));
+        assert_eq!(stream.next_batch().await.unwrap(), vec!["c.ts"]);
+
+        sender.await.unwrap();
+        assert_eq!(stream.next_batch().await, None);
+        stream.stop().unwrap();
+    }
 }
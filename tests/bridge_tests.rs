@@ -116,6 +116,24 @@ def process_user(user_id, options):
         );
     }
 
+    #[test]
+    fn test_detect_sphinx_returns_only() {
+        let detector = FormatDetector::new(&BridgeConfig::enabled());
+
+        let sphinx = "Load the config.\n\n:returns: The parsed config.\n";
+        assert_eq!(
+            detector.detect(sphinx, "python"),
+            Some(SourceFormat::DocstringSphinx)
+        );
+
+        // Inline roles in Google-style text are not Sphinx fields
+        let google = "Load the config.\n\nReturns:\n    A :class:`Config` instance.\n";
+        assert_eq!(
+            detector.detect(google, "python"),
+            Some(SourceFormat::DocstringGoogle)
+        );
+    }
+
     #[test]
     fn test_detect_rustdoc() {
         let config = BridgeConfig::enabled();
@@ -473,3 +491,66 @@ mod source_format_tests {
         }
     }
 }
+
+// =============================================================================
+// Sphinx field list parsing
+// =============================================================================
+
+mod sphinx_parsing_tests {
+    use super::*;
+    use acp::annotate::converters::DocstringParser;
+    use acp::annotate::DocStandardParser;
+
+    const SPHINX_PARAMS: &str = include_str!("fixtures/bridge/sphinx_params.py");
+
+    #[test]
+    fn test_sphinx_three_params_two_types() {
+        let detector = FormatDetector::new(&BridgeConfig::enabled());
+        assert_eq!(
+            detector.detect(SPHINX_PARAMS, "python"),
+            Some(SourceFormat::DocstringSphinx)
+        );
+
+        let docstring = SPHINX_PARAMS.split(r#"""""#).nth(1).unwrap();
+        let doc = DocstringParser::new().parse(docstring);
+
+        assert_eq!(
+            doc.summary,
+            Some("Transfer funds between two accounts.".to_string())
+        );
+        assert_eq!(
+            doc.params,
+            vec![
+                (
+                    "source".to_string(),
+                    Some("Account".to_string()),
+                    Some("Account to debit. Must belong to the authenticated user.".to_string()),
+                ),
+                (
+                    "target".to_string(),
+                    None,
+                    Some("Account to credit.".to_string()),
+                ),
+                (
+                    "amount".to_string(),
+                    Some("int".to_string()),
+                    Some("Amount in minor units (cents).".to_string()),
+                ),
+            ]
+        );
+        // :rtype: before :returns: still pairs up
+        assert_eq!(
+            doc.returns,
+            Some((
+                Some("Receipt".to_string()),
+                Some("Receipt for the completed transfer, including the ledger id.".to_string()),
+            ))
+        );
+        let throws: Vec<_> = doc.throws.iter().map(|(exc, _)| exc.as_str()).collect();
+        assert_eq!(throws, vec!["InsufficientFunds", "ValueError", "TypeError"]);
+        assert_eq!(
+            doc.throws[0].1.as_deref(),
+            Some("If the source balance is lower than ``amount``.")
+        );
+    }
+}
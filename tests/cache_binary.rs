@@ -0,0 +1,50 @@
+//! Binary cache round-trip tests
+//!
+//! A synthetic 10k-symbol cache saved in the binary (MessagePack) format
+//! must load back identical to the JSON copy and be smaller on disk. Load
+//! times are compared in `benches/cache_load.rs`.
+
+use acp::cache::{Cache, SymbolEntry};
+
+const SYMBOLS: usize = 10_000;
+
+fn symbol(i: usize) -> (String, SymbolEntry) {
+    let name = format!("symbol_{}", i);
+    let file = format!("src/module_{}.ts", i / 20);
+    let entry = SymbolEntry {
+        lines: [1, 40],
+        signature: Some(format!("function {}(input: string): Result", name)),
+        summary: Some(format!("Handles step {} of the request pipeline", i)),
+        calls: (0..8)
+            .map(|c| format!("symbol_{}", (i + c) % SYMBOLS))
+            .collect(),
+        ..SymbolEntry::new(name.clone(), file)
+    };
+    (name, entry)
+}
+
+#[test]
+fn binary_cache_round_trips() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut cache = Cache::new("bench", ".");
+    cache.symbols = (0..SYMBOLS).map(symbol).collect();
+    cache.update_stats();
+
+    let json_path = dir.path().join(".acp.cache.json");
+    let bin_path = dir.path().join(".acp.cache.bin");
+    cache.write_json(&json_path).unwrap();
+    cache.write_binary(&bin_path).unwrap();
+
+    let json_size = std::fs::metadata(&json_path).unwrap().len();
+    let bin_size = std::fs::metadata(&bin_path).unwrap().len();
+    assert!(bin_size < json_size);
+
+    let from_json = Cache::from_json(&json_path).unwrap();
+    let from_bin = Cache::from_binary(&bin_path).unwrap();
+    assert_eq!(from_bin.symbols.len(), SYMBOLS);
+    assert_eq!(
+        serde_json::to_value(&from_bin.symbols).unwrap(),
+        serde_json::to_value(&from_json.symbols).unwrap()
+    );
+    assert_eq!(from_bin.stats.symbols, from_json.stats.symbols);
+}
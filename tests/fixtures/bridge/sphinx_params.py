@@ -0,0 +1,20 @@
+def transfer(source, target, amount, memo=None):
+    """Transfer funds between two accounts.
+
+    Debits the source account and credits the target in a single
+    transaction.
+
+    :param source: Account to debit. Must belong to the
+        authenticated user.
+    :type source: Account
+    :param target: Account to credit.
+    :param amount: Amount in minor units (cents).
+    :type amount: int
+    :rtype: Receipt
+    :returns: Receipt for the completed transfer,
+        including the ledger id.
+    :raises InsufficientFunds: If the source balance is
+        lower than ``amount``.
+    :raises ValueError, TypeError: If ``amount`` is not a
+        positive integer.
+    """
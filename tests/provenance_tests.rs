@@ -91,6 +91,32 @@ mod parser_tests {
             assert_eq!(parsed, origin);
         }
     }
+
+    #[test]
+    fn test_provenance_inside_block_comment() {
+        let source = r#"const note = "@acp:summary not a comment";
+/**
+   @acp:summary "Validates tokens"
+   @acp:source heuristic
+   @acp:source-confidence 0.6
+ */
+export function validate() {}
+"#;
+        let parser = acp::parse::Parser::new();
+        let parsed =
+            parser.parse_annotations_with_provenance(source, acp::cache::Language::Typescript);
+        assert_eq!(parsed.len(), 3);
+
+        let summary = &parsed[0];
+        assert_eq!(summary.annotation.line, 3);
+        assert_eq!(
+            summary.annotation.value.as_deref(),
+            Some("\"Validates tokens\"")
+        );
+        let provenance = summary.provenance.as_ref().unwrap();
+        assert_eq!(provenance.source, SourceOrigin::Heuristic);
+        assert_eq!(provenance.confidence, Some(0.6));
+    }
 }
 
 // =============================================================================